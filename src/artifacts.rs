@@ -0,0 +1,415 @@
+//! Checks specific to software, datasets and web pages, whose correctness hinges on
+//! versions and URLs rather than on matching a publication record
+
+use crate::entry::{Discrepancy, DiscrepancyField, Entry, Severity};
+use crate::lint::normalize_doi;
+use crate::validators::datacite::{CONCEPT_DOI_FIELD, VERSION_COUNT_FIELD, VERSION_FIELD};
+use crate::validators::github::{github_repository, GITHUB_ARCHIVED_FIELD, GITHUB_TAGS_FIELD};
+use crate::validators::swh::SWHID_FIELD;
+use crate::validators::zenodo::ZENODO_VERSIONS_FIELD;
+use crate::validators::web::UrlCheck;
+
+/// Compare the version a software or dataset entry cites with the release its DOI
+/// was registered for, and check that concept and version DOIs are used consistently
+pub fn check_release(local: &Entry, remote: &Entry) -> Vec<Discrepancy> {
+    let mut findings = Vec::new();
+    let local_version = local.field("version").map(str::trim);
+    let remote_version = remote.field(VERSION_FIELD).map(str::trim);
+
+    if let (Some(local_version), Some(remote_version)) = (local_version, remote_version) {
+        if normalize_version(local_version) != normalize_version(remote_version) {
+            findings.push(Discrepancy {
+                field: DiscrepancyField::Version,
+                severity: Severity::Warning,
+                local_value: local_version.to_string(),
+                remote_value: remote_version.to_string(),
+                message: "Version differs from the release the DOI was registered for".to_string(),
+            });
+        }
+    }
+
+    // Only compare DOIs the remote record was actually looked up by
+    let same_doi = match (&local.doi, &remote.doi) {
+        (Some(local_doi), Some(remote_doi)) => {
+            normalize_doi(local_doi) == normalize_doi(remote_doi)
+        }
+        _ => false,
+    };
+    if !same_doi {
+        return findings;
+    }
+
+    if let (Some(local_version), Some(_)) = (local_version, remote.field(VERSION_COUNT_FIELD)) {
+        findings.push(Discrepancy {
+            field: DiscrepancyField::Doi,
+            severity: Severity::Warning,
+            local_value: local.doi.clone().unwrap_or_default(),
+            remote_value: String::new(),
+            message: format!(
+                "DOI is a concept DOI covering every version; cite the DOI of version {} instead",
+                local_version
+            ),
+        });
+    }
+
+    if let (None, Some(remote_version)) = (local_version, remote_version) {
+        let concept = remote
+            .field(CONCEPT_DOI_FIELD)
+            .map(|doi| {
+                format!(
+                    ", or cite the concept DOI {} to refer to every version",
+                    doi
+                )
+            })
+            .unwrap_or_default();
+        findings.push(Discrepancy {
+            field: DiscrepancyField::Version,
+            severity: Severity::Info,
+            local_value: String::new(),
+            remote_value: remote_version.to_string(),
+            message: format!(
+                "DOI identifies version {}; add a version field{}",
+                remote_version, concept
+            ),
+        });
+    }
+
+    findings
+}
+
+/// A warning when the version an entry cites isn't among those archived under its
+/// concept DOI, given the concept's record of versions
+pub fn check_archived_version(local: &Entry, versions: &Entry) -> Option<Discrepancy> {
+    let version = local.field("version").map(str::trim)?;
+    let archived: Vec<&str> = versions.field(ZENODO_VERSIONS_FIELD)?.lines().collect();
+    if archived
+        .iter()
+        .any(|v| normalize_version(v.trim()) == normalize_version(version))
+    {
+        return None;
+    }
+    Some(Discrepancy {
+        field: DiscrepancyField::Version,
+        severity: Severity::Warning,
+        local_value: version.to_string(),
+        remote_value: archived.first().copied().unwrap_or_default().to_string(),
+        message: format!(
+            "Version {} no longer exists upstream; the archived versions are {}",
+            version,
+            archived.iter().take(5).copied().collect::<Vec<_>>().join(", ")
+        ),
+    })
+}
+
+/// Findings for the GitHub repository a software entry cites at `url`, given the
+/// repository's record (`None` when GitHub has no such repository)
+pub fn check_repository(local: &Entry, url: &str, repository: Option<&Entry>) -> Vec<Discrepancy> {
+    let mut findings = Vec::new();
+    let Some(repository) = repository else {
+        findings.push(Discrepancy {
+            field: DiscrepancyField::Url,
+            severity: Severity::Error,
+            local_value: url.to_string(),
+            remote_value: String::new(),
+            message: "Repository not found on GitHub; it may have been deleted or made private"
+                .to_string(),
+        });
+        return findings;
+    };
+
+    // GitHub redirects renamed and transferred repositories to their new name
+    let cited = github_repository(url).map(|(owner, name)| format!("{}/{}", owner, name));
+    if cited.is_some_and(|cited| !cited.eq_ignore_ascii_case(&repository.key)) {
+        findings.push(Discrepancy {
+            field: DiscrepancyField::Url,
+            severity: Severity::Info,
+            local_value: url.to_string(),
+            remote_value: repository.url.clone().unwrap_or_default(),
+            message: "Repository has moved; consider citing its new address".to_string(),
+        });
+    }
+
+    if repository.field(GITHUB_ARCHIVED_FIELD).is_some() {
+        findings.push(Discrepancy {
+            field: DiscrepancyField::Url,
+            severity: Severity::Info,
+            local_value: url.to_string(),
+            remote_value: String::new(),
+            message: "Repository is archived and no longer maintained".to_string(),
+        });
+    }
+
+    let tags = repository.field(GITHUB_TAGS_FIELD);
+    if let (Some(version), Some(tags)) = (local.field("version"), tags) {
+        if release_tag(local, repository).is_none() {
+            let newest = tags.lines().next().unwrap_or_default();
+            findings.push(Discrepancy {
+                field: DiscrepancyField::Version,
+                severity: Severity::Warning,
+                local_value: version.trim().to_string(),
+                remote_value: newest.to_string(),
+                message: format!(
+                    "Version {} has no matching tag in {}; the newest tag is {}",
+                    version.trim(),
+                    repository.key,
+                    newest
+                ),
+            });
+        }
+    }
+
+    findings
+}
+
+/// The repository tag of the version a software entry cites
+pub fn release_tag<'a>(local: &Entry, repository: &'a Entry) -> Option<&'a str> {
+    let version = normalize_version(local.field("version")?.trim());
+    repository
+        .field(GITHUB_TAGS_FIELD)?
+        .lines()
+        .find(|tag| normalize_version(tag) == version)
+}
+
+/// Suggest the persistent identifiers an archived release can be cited by, given the
+/// repository's Software Heritage record (`None` when the archive couldn't be asked)
+/// and the release's Zenodo record
+pub fn suggest_archives(
+    local: &Entry,
+    archived: Option<Option<&Entry>>,
+    zenodo: Option<&Entry>,
+) -> Vec<Discrepancy> {
+    let mut findings = Vec::new();
+    let has = |name: &str| local.field(name).is_some_and(|v| !v.trim().is_empty());
+
+    let zenodo_doi = zenodo.and_then(|record| record.doi.as_deref());
+    if let (None, Some(doi)) = (&local.doi, zenodo_doi) {
+        findings.push(Discrepancy {
+            field: DiscrepancyField::Doi,
+            severity: Severity::Info,
+            local_value: String::new(),
+            remote_value: doi.to_string(),
+            message: format!("Release is archived on Zenodo; cite its DOI {}", doi),
+        });
+    }
+
+    let swhid = archived.flatten().and_then(|record| record.field(SWHID_FIELD));
+    match (has("swhid"), swhid) {
+        (false, Some(swhid)) => findings.push(Discrepancy {
+            field: DiscrepancyField::Swhid,
+            severity: Severity::Info,
+            local_value: String::new(),
+            remote_value: swhid.to_string(),
+            message: format!("Source code is archived by Software Heritage as {}", swhid),
+        }),
+        (false, None) if archived.is_some() && local.doi.is_none() && zenodo_doi.is_none() => {
+            findings.push(Discrepancy {
+                field: DiscrepancyField::Swhid,
+                severity: Severity::Info,
+                local_value: String::new(),
+                remote_value: String::new(),
+                message: "Repository was never archived; request it at \
+                    https://archive.softwareheritage.org/save/"
+                    .to_string(),
+            })
+        }
+        _ => {}
+    }
+
+    findings
+}
+
+/// Versions compare equal regardless of a leading "v" ("v1.2" and "1.2")
+fn normalize_version(version: &str) -> &str {
+    version
+        .strip_prefix(['v', 'V'])
+        .filter(|rest| rest.starts_with(|c: char| c.is_ascii_digit()))
+        .unwrap_or(version)
+}
+
+/// Findings for a cited web page, given how fetching it went and its closest archived
+/// snapshot (`None` when the archive couldn't be asked)
+pub fn check_web_page(
+    url: &str,
+    check: &UrlCheck,
+    snapshot: Option<Option<&str>>,
+) -> Vec<Discrepancy> {
+    let mut findings = Vec::new();
+    let archived = snapshot.flatten();
+
+    if check.is_dead() {
+        let message = match snapshot {
+            Some(Some(_)) => format!(
+                "URL is dead ({}); an archived copy is available",
+                check.status
+            ),
+            Some(None) => format!("URL is dead ({}) and was never archived", check.status),
+            None => format!("URL is dead ({})", check.status),
+        };
+        findings.push(Discrepancy {
+            field: DiscrepancyField::Url,
+            severity: Severity::Error,
+            local_value: url.to_string(),
+            remote_value: archived.unwrap_or_default().to_string(),
+            message,
+        });
+        return findings;
+    }
+
+    if !check.status.is_success() {
+        findings.push(Discrepancy {
+            field: DiscrepancyField::Url,
+            severity: Severity::Warning,
+            local_value: url.to_string(),
+            remote_value: String::new(),
+            message: format!("URL returned {}", check.status),
+        });
+        return findings;
+    }
+
+    if check.final_url.trim_end_matches('/') != url.trim_end_matches('/') {
+        findings.push(Discrepancy {
+            field: DiscrepancyField::Url,
+            severity: Severity::Info,
+            local_value: url.to_string(),
+            remote_value: check.final_url.clone(),
+            message: "URL redirects elsewhere; consider citing the final address".to_string(),
+        });
+    }
+
+    if snapshot == Some(None) {
+        findings.push(Discrepancy {
+            field: DiscrepancyField::Url,
+            severity: Severity::Info,
+            local_value: url.to_string(),
+            remote_value: String::new(),
+            message: "Page has no Wayback Machine snapshot; consider archiving it".to_string(),
+        });
+    }
+
+    findings
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use reqwest::StatusCode;
+
+    #[test]
+    fn repositories_are_checked_against_the_cited_version() {
+        let url = "https://github.com/NumPy/numpy";
+        let mut local = Entry::new("numpy".to_string(), "software".to_string());
+        local
+            .fields
+            .insert("version".to_string(), "1.26.4".to_string());
+        let mut repository = Entry::new("numpy/numpy".to_string(), "software".to_string());
+        repository.fields.insert(
+            GITHUB_TAGS_FIELD.to_string(),
+            "v2.0.0\nv1.26.4".to_string(),
+        );
+
+        assert!(check_repository(&local, url, Some(&repository)).is_empty());
+        assert_eq!(release_tag(&local, &repository), Some("v1.26.4"));
+        assert_eq!(
+            check_repository(&local, url, None)[0].severity,
+            Severity::Error
+        );
+
+        local
+            .fields
+            .insert("version".to_string(), "1.27".to_string());
+        let findings = check_repository(&local, url, Some(&repository));
+        assert_eq!(findings[0].remote_value, "v2.0.0");
+
+        let mut archived = Entry::new("swh".to_string(), "software".to_string());
+        archived
+            .fields
+            .insert(SWHID_FIELD.to_string(), "swh:1:rel:abc".to_string());
+        let findings = suggest_archives(&local, Some(Some(&archived)), None);
+        assert_eq!(findings[0].remote_value, "swh:1:rel:abc");
+        assert_eq!(suggest_archives(&local, Some(None), None).len(), 1);
+        assert!(suggest_archives(&local, None, None).is_empty());
+    }
+
+    fn release(doi: &str, version: Option<&str>) -> Entry {
+        let mut entry = Entry::new("numpy".to_string(), "software".to_string());
+        entry.doi = Some(doi.to_string());
+        if let Some(version) = version {
+            entry
+                .fields
+                .insert("version".to_string(), version.to_string());
+        }
+        entry
+    }
+
+    #[test]
+    fn versions_are_compared_and_concept_dois_flagged() {
+        let mut remote = release("10.5281/zenodo.1", Some("v1.2"));
+        assert!(check_release(&release("10.5281/zenodo.1", Some("1.2")), &remote).is_empty());
+
+        let findings = check_release(&release("10.5281/zenodo.1", Some("1.3")), &remote);
+        assert_eq!(findings.len(), 1);
+        assert_eq!(findings[0].remote_value, "v1.2");
+
+        remote.fields.insert(
+            CONCEPT_DOI_FIELD.to_string(),
+            "10.5281/zenodo.0".to_string(),
+        );
+        let findings = check_release(&release("10.5281/zenodo.1", None), &remote);
+        assert_eq!(findings[0].severity, Severity::Info);
+        assert!(findings[0].message.contains("10.5281/zenodo.0"));
+
+        let mut concept = release("10.5281/zenodo.0", None);
+        concept
+            .fields
+            .insert(VERSION_COUNT_FIELD.to_string(), "4".to_string());
+        let findings = check_release(&release("10.5281/zenodo.0", Some("1.2")), &concept);
+        assert_eq!(findings[0].field, DiscrepancyField::Doi);
+        assert_eq!(findings[0].severity, Severity::Warning);
+    }
+
+    #[test]
+    fn withdrawn_versions_are_flagged() {
+        let mut versions = release("10.5281/zenodo.0", None);
+        versions
+            .fields
+            .insert(ZENODO_VERSIONS_FIELD.to_string(), "v2.0\nv1.3".to_string());
+        let cited = |version| release("10.5281/zenodo.1", Some(version));
+
+        assert!(check_archived_version(&cited("1.3"), &versions).is_none());
+        let finding = check_archived_version(&cited("1.2"), &versions).unwrap();
+        assert_eq!(finding.severity, Severity::Warning);
+        assert_eq!(finding.remote_value, "v2.0");
+        assert!(finding.message.ends_with("are v2.0, v1.3"));
+        assert!(check_archived_version(&release("10.5281/zenodo.1", None), &versions).is_none());
+    }
+
+    #[test]
+    fn dead_pages_point_to_their_snapshot() {
+        let url = "https://example.com/post";
+        let check = |status, final_url: &str| UrlCheck {
+            status,
+            final_url: final_url.to_string(),
+        };
+        let snapshot = "https://web.archive.org/web/2020/https://example.com/post";
+
+        let findings = check_web_page(
+            url,
+            &check(StatusCode::NOT_FOUND, url),
+            Some(Some(snapshot)),
+        );
+        assert_eq!(findings.len(), 1);
+        assert_eq!(findings[0].severity, Severity::Error);
+        assert_eq!(findings[0].remote_value, snapshot);
+
+        let findings = check_web_page(
+            url,
+            &check(StatusCode::OK, "https://example.com/post/"),
+            Some(None),
+        );
+        assert_eq!(findings.len(), 1);
+        assert!(findings[0].message.contains("Wayback"));
+
+        let findings = check_web_page(url, &check(StatusCode::OK, "https://example.org/"), None);
+        assert_eq!(findings[0].remote_value, "https://example.org/");
+    }
+}
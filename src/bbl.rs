@@ -0,0 +1,512 @@
+//! Best-effort extraction of entries from generated `.bbl` files and hand-written
+//! `thebibliography` environments.
+//!
+//! biblatex `.bbl` files keep the fields of each entry, so they are read exactly.
+//! Formatted `\bibitem`s only have typeset text, so authors, title, venue and year
+//! are recovered heuristically from `\newblock` boundaries, quotes and emphasis.
+
+use crate::entry::Entry;
+use crate::parser::{extract_year_from_string, is_arxiv_id, ParseError};
+use regex_lite::Regex;
+use std::sync::{Arc, OnceLock};
+
+/// Parse the bibliography of a `.bbl` file, or the `thebibliography` environment of a
+/// `.tex` file
+pub fn parse_bbl(content: &str) -> Result<Vec<Entry>, ParseError> {
+    let entries = if content.contains("\\entry{") {
+        parse_biblatex(content)
+    } else {
+        parse_bibitems(content)
+    };
+
+    if entries.is_empty() {
+        return Err(ParseError::ParseError(
+            "no \\bibitem or biblatex entries found".to_string(),
+        ));
+    }
+    Ok(entries)
+}
+
+/// Read `\entry{key}{type}{} ... \endentry` blocks written by biber
+fn parse_biblatex(content: &str) -> Vec<Entry> {
+    let mut entries = Vec::new();
+    let mut rest = content;
+
+    while let Some(start) = rest.find("\\entry{") {
+        rest = &rest[start + "\\entry".len()..];
+        let Some((key, after_key)) = braced(rest) else {
+            break;
+        };
+        let Some((entry_type, body)) = braced(after_key) else {
+            break;
+        };
+        let end = body.find("\\endentry").unwrap_or(body.len());
+        let block = &body[..end];
+        rest = &body[end..];
+
+        let mut entry = Entry::new(key.to_string(), entry_type.to_string());
+        let mut fields = Vec::new();
+
+        for (name, value) in biblatex_fields(block) {
+            let value = clean_latex(value);
+            match name {
+                "title" => entry.title = Some(value.as_str().into()),
+                "journaltitle" | "journal" | "booktitle" => {
+                    entry.venue.get_or_insert_with(|| value.as_str().into());
+                }
+                "year" => entry.year = value.parse().ok(),
+                "date" if entry.year.is_none() => entry.year = extract_year_from_string(&value),
+                "doi" => entry.doi = Some(value.clone()),
+                "url" => entry.url = Some(value.clone()),
+                "eprint" if is_arxiv_id(&value) => entry.arxiv_id = Some(value.clone()),
+                _ => {}
+            }
+            let name = if name == "journaltitle" {
+                "journal"
+            } else {
+                name
+            };
+            fields.push((name.to_string(), value));
+        }
+
+        entry.authors = biblatex_authors(block);
+        if !entry.authors.is_empty() {
+            let names: Vec<&str> = entry.authors.iter().map(|a| a.as_ref()).collect();
+            fields.push(("author".to_string(), names.join(" and ")));
+        }
+
+        entry.fields = fields.into_iter().collect();
+        entries.push(entry);
+    }
+
+    entries
+}
+
+/// `\field{name}{value}` and `\verb{name} \verb value \endverb` pairs of an entry
+fn biblatex_fields(block: &str) -> Vec<(&str, &str)> {
+    let mut fields = Vec::new();
+
+    let mut rest = block;
+    while let Some(start) = rest.find("\\field{") {
+        rest = &rest[start + "\\field".len()..];
+        let Some((name, after_name)) = braced(rest) else {
+            break;
+        };
+        let Some((value, after_value)) = braced(after_name) else {
+            break;
+        };
+        fields.push((name, value));
+        rest = after_value;
+    }
+
+    let mut rest = block;
+    while let Some(start) = rest.find("\\verb{") {
+        rest = &rest[start + "\\verb".len()..];
+        let Some((name, after_name)) = braced(rest) else {
+            break;
+        };
+        let end = after_name.find("\\endverb").unwrap_or(after_name.len());
+        let value = after_name[..end].trim().trim_start_matches("\\verb").trim();
+        fields.push((name, value));
+        rest = &after_name[end..];
+    }
+
+    fields
+}
+
+/// Authors from the `\name{author}{n}{}{...}` list of an entry, as "Given Family"
+fn biblatex_authors(block: &str) -> Vec<Arc<str>> {
+    let Some(start) = block.find("\\name{author}") else {
+        return Vec::new();
+    };
+    let rest = &block[start + "\\name".len()..];
+    let names = braced(rest)
+        .and_then(|(_, rest)| braced(rest))
+        .and_then(|(_, rest)| braced(rest))
+        .and_then(|(_, rest)| braced(rest))
+        .map(|(names, _)| names)
+        .unwrap_or("");
+
+    names
+        .split("family=")
+        .skip(1)
+        .filter_map(|person| {
+            let (family, rest) = braced(person)?;
+            let given = rest
+                .find("given=")
+                .and_then(|i| braced(&rest[i + "given=".len()..]))
+                .map(|(given, _)| clean_latex(given));
+            let family = clean_latex(family);
+            Some(match given {
+                Some(given) => Arc::from(format!("{} {}", given, family)),
+                None => Arc::from(family),
+            })
+        })
+        .collect()
+}
+
+/// Read formatted `\bibitem[label]{key} text` items
+fn parse_bibitems(content: &str) -> Vec<Entry> {
+    let content = match content.find("\\begin{thebibliography}") {
+        Some(start) => &content[start..],
+        None => content,
+    };
+    let content = match content.find("\\end{thebibliography}") {
+        Some(end) => &content[..end],
+        None => content,
+    };
+
+    content
+        .split("\\bibitem")
+        .skip(1)
+        .filter_map(parse_bibitem)
+        .collect()
+}
+
+fn parse_bibitem(item: &str) -> Option<Entry> {
+    let mut rest = item.trim_start();
+    // Skip the optional [label], which may itself contain braces
+    if rest.starts_with('[') {
+        let mut depth = 0;
+        let end = rest.char_indices().find_map(|(i, c)| match c {
+            '{' => {
+                depth += 1;
+                None
+            }
+            '}' => {
+                depth -= 1;
+                None
+            }
+            ']' if depth == 0 => Some(i),
+            _ => None,
+        })?;
+        rest = &rest[end + 1..];
+    }
+    let (key, text) = braced(rest.trim_start())?;
+    let text = strip_comments(text);
+
+    let (authors, title, tail) = split_bibitem(&text);
+    let mut entry = Entry::new(key.trim().to_string(), "misc".to_string());
+    let mut fields = Vec::new();
+
+    let authors = split_authors(&clean_latex(authors));
+    if !authors.is_empty() {
+        fields.push(("author", authors.join(" and ")));
+        entry.authors = authors.into_iter().map(Arc::from).collect();
+    }
+
+    let title = clean_latex(title)
+        .trim_end_matches(['.', ','])
+        .trim()
+        .to_string();
+    if !title.is_empty() {
+        entry.title = Some(title.as_str().into());
+        fields.push(("title", title));
+    }
+
+    if let Some(venue) = venue(tail) {
+        let (entry_type, field) = if clean_latex(tail).starts_with("In ") {
+            ("inproceedings", "booktitle")
+        } else {
+            ("article", "journal")
+        };
+        entry.entry_type = entry_type.to_string();
+        entry.venue = Some(venue.as_str().into());
+        fields.push((field, venue));
+    }
+
+    entry.year = year_regex()
+        .find_iter(tail)
+        .last()
+        .and_then(|m| m.as_str().parse().ok());
+    if let Some(year) = entry.year {
+        fields.push(("year", year.to_string()));
+    }
+
+    entry.doi = doi_regex()
+        .find(&text)
+        .map(|m| m.as_str().trim_end_matches(['.', ',']).to_string());
+    entry.arxiv_id = arxiv_regex()
+        .captures(&text)
+        .map(|c| c[1].trim_end_matches('.').to_string());
+    for (name, value) in [("doi", &entry.doi), ("eprint", &entry.arxiv_id)] {
+        if let Some(value) = value {
+            fields.push((name, value.clone()));
+        }
+    }
+
+    entry.fields = fields
+        .into_iter()
+        .map(|(name, value)| (name.to_string(), value))
+        .collect();
+    Some(entry)
+}
+
+/// Split a formatted reference into its authors, title, and the rest. Styles built on
+/// `\newblock` put each part in its own block; others quote the title (IEEE, ACM) or
+/// end the author list with a period.
+fn split_bibitem(text: &str) -> (&str, &str, &str) {
+    let blocks: Vec<&str> = text.split("\\newblock").collect();
+    if blocks.len() >= 3 {
+        let tail_start = blocks[0].len() + blocks[1].len() + 2 * "\\newblock".len();
+        return (blocks[0], blocks[1], &text[tail_start..]);
+    }
+
+    for (open, close) in [("``", "''"), ("\"", "\""), ("\u{201c}", "\u{201d}")] {
+        if let Some(start) = text.find(open) {
+            let title_start = start + open.len();
+            if let Some(len) = text[title_start..].find(close) {
+                let tail_start = title_start + len + close.len();
+                return (
+                    &text[..start],
+                    &text[title_start..title_start + len],
+                    &text[tail_start..],
+                );
+            }
+        }
+    }
+
+    // "A. Author and B. Author. Title of the paper. Venue, 2020."
+    let sentence_ends: Vec<usize> = text
+        .match_indices(". ")
+        .map(|(i, _)| i)
+        .filter(|&i| {
+            // A period after an initial doesn't end the author list
+            let word = text[..i].rsplit(|c: char| c.is_whitespace() || c == '~');
+            word.take(1)
+                .all(|w| w.chars().filter(|c| c.is_alphabetic()).count() > 1)
+        })
+        .collect();
+    match sentence_ends.as_slice() {
+        [authors_end, title_end, ..] => (
+            &text[..*authors_end],
+            &text[authors_end + 2..*title_end],
+            &text[title_end + 2..],
+        ),
+        [authors_end] => (&text[..*authors_end], &text[authors_end + 2..], ""),
+        [] => ("", text, ""),
+    }
+}
+
+/// The venue of a reference's remainder: its emphasized part, or the text up to the
+/// first comma
+fn venue(tail: &str) -> Option<String> {
+    let emphasized = ["\\emph", "\\textit", "\\textsl"]
+        .iter()
+        .find_map(|command| {
+            let start = tail.find(command)?;
+            braced(&tail[start + command.len()..]).map(|(venue, _)| venue)
+        })
+        .or_else(|| {
+            let start = tail.find("{\\em ").or_else(|| tail.find("{\\it "))?;
+            braced(&tail[start..]).map(|(venue, _)| &venue[4..])
+        });
+
+    let venue = match emphasized {
+        Some(venue) => clean_latex(venue),
+        None => {
+            let text = clean_latex(tail);
+            let text = text.strip_prefix("In ").unwrap_or(&text);
+            text.split(',').next().unwrap_or("").to_string()
+        }
+    };
+    let venue = venue
+        .trim_start_matches("In ")
+        .trim_end_matches(['.', ','])
+        .trim();
+    let is_year = venue.len() == 4 && venue.parse::<i32>().is_ok();
+    (venue.len() > 1 && !is_year).then(|| venue.to_string())
+}
+
+/// Split an author list on commas and "and", rejoining "Last, F." pairs
+fn split_authors(authors: &str) -> Vec<String> {
+    let authors = authors
+        .trim()
+        .trim_end_matches('.')
+        .replace(" et al", "")
+        .replace(", and ", ", ")
+        .replace(" and ", ", ")
+        .replace(';', ",");
+
+    let mut names: Vec<String> = Vec::new();
+    for part in authors.split(',').map(str::trim).filter(|p| !p.is_empty()) {
+        let is_initials = part
+            .split(|c: char| c.is_whitespace() || c == '-')
+            .filter(|w| !w.is_empty())
+            .all(|w| w.trim_end_matches('.').chars().count() <= 1);
+        match names.last_mut() {
+            Some(last) if is_initials && !last.contains(' ') => {
+                *last = format!("{} {}", part, last);
+            }
+            _ => names.push(part.to_string()),
+        }
+    }
+    names
+}
+
+/// Drop `%` comments, keeping escaped `\%`
+fn strip_comments(text: &str) -> String {
+    text.lines()
+        .map(|line| match line.find('%') {
+            Some(i) if !line[..i].ends_with('\\') => &line[..i],
+            _ => line,
+        })
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+/// Reduce typeset LaTeX to plain text
+fn clean_latex(text: &str) -> String {
+    let text = text
+        .replace("\\&", "&")
+        .replace("\\%", "%")
+        .replace("``", "")
+        .replace("''", "")
+        .replace("---", "-")
+        .replace("--", "-")
+        .replace(['~', '\n'], " ");
+    let text = command_regex().replace_all(&text, "");
+    let text: String = text.chars().filter(|c| !matches!(c, '{' | '}')).collect();
+    text.split_whitespace().collect::<Vec<_>>().join(" ")
+}
+
+/// The content of a leading `{...}` group and the text after it
+fn braced(text: &str) -> Option<(&str, &str)> {
+    let text = text.trim_start();
+    if !text.starts_with('{') {
+        return None;
+    }
+    let mut depth = 0;
+    for (i, c) in text.char_indices() {
+        match c {
+            '{' => depth += 1,
+            '}' => {
+                depth -= 1;
+                if depth == 0 {
+                    return Some((&text[1..i], &text[i + 1..]));
+                }
+            }
+            _ => {}
+        }
+    }
+    None
+}
+
+/// LaTeX commands and accent macros, whose arguments are kept
+fn command_regex() -> &'static Regex {
+    static REGEX: OnceLock<Regex> = OnceLock::new();
+    REGEX.get_or_init(|| Regex::new(r"\\(?:[a-zA-Z]+\*?|.)\s?").expect("valid command regex"))
+}
+
+fn year_regex() -> &'static Regex {
+    static REGEX: OnceLock<Regex> = OnceLock::new();
+    REGEX.get_or_init(|| Regex::new(r"\b(?:1[89]|20)\d{2}\b").expect("valid year regex"))
+}
+
+fn doi_regex() -> &'static Regex {
+    static REGEX: OnceLock<Regex> = OnceLock::new();
+    REGEX.get_or_init(|| Regex::new(r"10\.\d{4,9}/[^\s{}]+").expect("valid DOI regex"))
+}
+
+fn arxiv_regex() -> &'static Regex {
+    static REGEX: OnceLock<Regex> = OnceLock::new();
+    REGEX.get_or_init(|| {
+        Regex::new(r"(?i)arxiv[:\s]*(\d{4}\.\d{4,5}(?:v\d+)?)").expect("valid arXiv regex")
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_newblock_and_quoted_bibitems() {
+        let content = r"
+\begin{thebibliography}{10}
+\bibitem[He et~al.(2016)He, Zhang, Ren, and Sun]{he2016deep}
+Kaiming He, Xiangyu Zhang, Shaoqing Ren, and Jian Sun.
+\newblock Deep residual learning for image recognition.
+\newblock In \emph{Proceedings of the IEEE Conference on Computer Vision and
+  Pattern Recognition}, pages 770--778, 2016.
+
+\bibitem{vaswani} A.~Vaswani, N.~Shazeer, and N.~Parmar, ``Attention is all you
+  need,'' \textit{Advances in Neural Information Processing Systems}, vol.~30, 2017,
+  arXiv:1706.03762.
+\end{thebibliography}
+";
+        let entries = parse_bbl(content).unwrap();
+        assert_eq!(entries.len(), 2);
+
+        let he = &entries[0];
+        assert_eq!(he.key, "he2016deep");
+        assert_eq!(he.entry_type, "inproceedings");
+        assert_eq!(
+            he.title.as_deref(),
+            Some("Deep residual learning for image recognition")
+        );
+        assert_eq!(he.authors.len(), 4);
+        assert_eq!(he.authors[3].as_ref(), "Jian Sun");
+        assert_eq!(he.year, Some(2016));
+        assert_eq!(
+            he.venue.as_deref(),
+            Some("Proceedings of the IEEE Conference on Computer Vision and Pattern Recognition")
+        );
+
+        let vaswani = &entries[1];
+        assert_eq!(vaswani.title.as_deref(), Some("Attention is all you need"));
+        assert_eq!(vaswani.authors[0].as_ref(), "A. Vaswani");
+        assert_eq!(vaswani.year, Some(2017));
+        assert_eq!(vaswani.arxiv_id.as_deref(), Some("1706.03762"));
+        assert_eq!(
+            vaswani.field("journal"),
+            Some("Advances in Neural Information Processing Systems")
+        );
+    }
+
+    #[test]
+    fn parses_period_separated_bibitems() {
+        let entries = parse_bbl(
+            r"\bibitem{knuth} D.~E. Knuth and J.~Smith. The Art of Computer Programming. Addison-Wesley, 1968.",
+        )
+        .unwrap();
+        assert_eq!(entries[0].authors[0].as_ref(), "D. E. Knuth");
+        assert_eq!(entries[0].authors[1].as_ref(), "J. Smith");
+        assert_eq!(
+            entries[0].title.as_deref(),
+            Some("The Art of Computer Programming")
+        );
+        assert_eq!(entries[0].year, Some(1968));
+    }
+
+    #[test]
+    fn parses_biblatex_entries() {
+        let content = r"
+\entry{he2016}{inproceedings}{}
+  \name{author}{2}{}{%
+    {{hash=abc}{%
+       family={He},
+       familyi={H\bibinitperiod},
+       given={Kaiming},
+       giveni={K\bibinitperiod}}}%
+    {{hash=def}{%
+       family={Zhang},
+       given={Xiangyu}}}%
+  }
+  \field{booktitle}{CVPR}
+  \field{title}{Deep Residual Learning for Image Recognition}
+  \field{year}{2016}
+  \verb{doi}
+  \verb 10.1109/CVPR.2016.90
+  \endverb
+\endentry
+";
+        let entries = parse_bbl(content).unwrap();
+        let entry = &entries[0];
+        assert_eq!(entry.entry_type, "inproceedings");
+        assert_eq!(entry.authors[0].as_ref(), "Kaiming He");
+        assert_eq!(entry.authors[1].as_ref(), "Xiangyu Zhang");
+        assert_eq!(entry.year, Some(2016));
+        assert_eq!(entry.doi.as_deref(), Some("10.1109/CVPR.2016.90"));
+        assert_eq!(entry.venue.as_deref(), Some("CVPR"));
+    }
+}
@@ -1,11 +1,17 @@
 use serde::{de::DeserializeOwned, Serialize};
+use std::collections::HashMap;
 use std::fs;
 use std::path::PathBuf;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Mutex;
 use std::time::{Duration, SystemTime};
 use thiserror::Error;
 
 const CACHE_TTL_SECS: u64 = 86400 * 7; // 7 days
 
+/// Buffered writes that trigger a flush to disk
+const WRITE_BATCH_SIZE: usize = 32;
+
 #[derive(Error, Debug)]
 pub enum CacheError {
     #[error("Failed to create cache directory: {0}")]
@@ -16,9 +22,34 @@ pub enum CacheError {
     Serialization(#[from] serde_json::Error),
 }
 
+/// On-disk cache of API responses.
+///
+/// Reads and writes go through `tokio::fs` so slow disks don't block the runtime, and
+/// writes are buffered and flushed in batches; call [`Cache::flush`] when a run ends.
 pub struct Cache {
     cache_dir: PathBuf,
     enabled: bool,
+    /// Serialized values not yet written, by cache file
+    pending: Mutex<HashMap<PathBuf, String>>,
+    hits: AtomicU64,
+    misses: AtomicU64,
+}
+
+/// How many lookups the cache answered over a run
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct CacheStats {
+    pub hits: u64,
+    pub misses: u64,
+}
+
+impl CacheStats {
+    /// Share of lookups answered from the cache, or `None` when there were none
+    pub fn hit_rate(&self) -> Option<f64> {
+        match self.hits + self.misses {
+            0 => None,
+            total => Some(self.hits as f64 / total as f64),
+        }
+    }
 }
 
 impl Cache {
@@ -30,7 +61,13 @@ impl Cache {
             fs::create_dir_all(&cache_dir).map_err(CacheError::CreateDir)?;
         }
 
-        Ok(Self { cache_dir, enabled })
+        Ok(Self {
+            cache_dir,
+            enabled,
+            pending: Mutex::new(HashMap::new()),
+            hits: AtomicU64::new(0),
+            misses: AtomicU64::new(0),
+        })
     }
 
     /// Generate a cache key from the API name and query
@@ -41,46 +78,90 @@ impl Cache {
     }
 
     /// Get a cached response if it exists and is not expired
-    pub fn get<T: DeserializeOwned>(&self, api: &str, query: &str) -> Option<T> {
+    pub async fn get<T: DeserializeOwned>(&self, api: &str, query: &str) -> Option<T> {
         if !self.enabled {
             return None;
         }
 
+        let value = self.lookup(api, query).await;
+        let counter = if value.is_some() {
+            &self.hits
+        } else {
+            &self.misses
+        };
+        counter.fetch_add(1, Ordering::Relaxed);
+        value
+    }
+
+    /// Hits and misses of [`Cache::get`] so far
+    pub fn stats(&self) -> CacheStats {
+        CacheStats {
+            hits: self.hits.load(Ordering::Relaxed),
+            misses: self.misses.load(Ordering::Relaxed),
+        }
+    }
+
+    async fn lookup<T: DeserializeOwned>(&self, api: &str, query: &str) -> Option<T> {
         let path = self.cache_key(api, query);
 
+        // A value written this run may still be waiting in the buffer
+        if let Some(content) = self.pending.lock().unwrap().get(&path) {
+            return serde_json::from_str(content).ok();
+        }
+
         // Check if file exists and is not expired
-        let metadata = fs::metadata(&path).ok()?;
+        let metadata = tokio::fs::metadata(&path).await.ok()?;
         let modified = metadata.modified().ok()?;
         let age = SystemTime::now().duration_since(modified).ok()?;
 
         if age > Duration::from_secs(CACHE_TTL_SECS) {
             // Cache expired, remove the file
-            let _ = fs::remove_file(&path);
+            let _ = tokio::fs::remove_file(&path).await;
             return None;
         }
 
-        let content = fs::read_to_string(&path).ok()?;
+        let content = tokio::fs::read_to_string(&path).await.ok()?;
         serde_json::from_str(&content).ok()
     }
 
-    /// Store a response in the cache
-    pub fn set<T: Serialize>(&self, api: &str, query: &str, value: &T) -> Result<(), CacheError> {
+    /// Store a response in the cache, writing to disk once enough values are buffered
+    pub async fn set<T: Serialize>(
+        &self,
+        api: &str,
+        query: &str,
+        value: &T,
+    ) -> Result<(), CacheError> {
         if !self.enabled {
             return Ok(());
         }
 
         let path = self.cache_key(api, query);
         let content = serde_json::to_string(value)?;
-        fs::write(path, content)?;
-        Ok(())
+
+        let batch = {
+            let mut pending = self.pending.lock().unwrap();
+            pending.insert(path, content);
+            if pending.len() < WRITE_BATCH_SIZE {
+                return Ok(());
+            }
+            std::mem::take(&mut *pending)
+        };
+        write_batch(batch).await
+    }
+
+    /// Write all buffered values to disk
+    pub async fn flush(&self) -> Result<(), CacheError> {
+        let batch = std::mem::take(&mut *self.pending.lock().unwrap());
+        write_batch(batch).await
     }
 
     /// Clear all cached data
     pub fn clear(&self) -> Result<(), CacheError> {
+        self.pending.lock().unwrap().clear();
         if self.cache_dir.exists() {
             for entry in fs::read_dir(&self.cache_dir)? {
                 let entry = entry?;
-                if entry.path().extension().map_or(false, |e| e == "json") {
+                if entry.path().extension().is_some_and(|e| e == "json") {
                     fs::remove_file(entry.path())?;
                 }
             }
@@ -89,6 +170,26 @@ impl Cache {
     }
 }
 
+impl Drop for Cache {
+    /// Last-resort write of anything a caller forgot to flush
+    fn drop(&mut self) {
+        for (path, content) in self.pending.get_mut().unwrap().drain() {
+            let _ = fs::write(path, content);
+        }
+    }
+}
+
+/// Write cache files concurrently, reporting the first failure
+async fn write_batch(batch: HashMap<PathBuf, String>) -> Result<(), CacheError> {
+    let writes = batch
+        .into_iter()
+        .map(|(path, content)| tokio::fs::write(path, content));
+    for result in futures::future::join_all(writes).await {
+        result?;
+    }
+    Ok(())
+}
+
 /// Simple hash function for cache keys (stable across runs)
 fn blake3_hash(s: &str) -> String {
     blake3::hash(s.as_bytes()).to_hex().to_string()
@@ -105,8 +206,8 @@ mod tests {
         value: String,
     }
 
-    #[test]
-    fn test_cache_round_trip() {
+    #[tokio::test]
+    async fn test_cache_round_trip() {
         let dir = tempdir().unwrap();
         let mut cache = Cache::new(true).unwrap();
         cache.cache_dir = dir.path().to_path_buf();
@@ -115,18 +216,42 @@ mod tests {
             value: "test".to_string(),
         };
 
-        cache.set("test_api", "query", &data).unwrap();
-        let retrieved: Option<TestData> = cache.get("test_api", "query");
+        cache.set("test_api", "query", &data).await.unwrap();
+        let retrieved: Option<TestData> = cache.get("test_api", "query").await;
 
         assert_eq!(retrieved, Some(data));
     }
 
+    #[tokio::test]
+    async fn writes_are_buffered_until_flushed() {
+        let dir = tempdir().unwrap();
+        let mut cache = Cache::new(true).unwrap();
+        cache.cache_dir = dir.path().to_path_buf();
+
+        let data = TestData {
+            value: "test".to_string(),
+        };
+        cache.set("test_api", "query", &data).await.unwrap();
+        assert!(!cache.cache_key("test_api", "query").exists());
+
+        cache.flush().await.unwrap();
+        assert!(cache.cache_key("test_api", "query").exists());
+        assert_eq!(cache.get("test_api", "query").await, Some(data));
+
+        assert_eq!(cache.get::<TestData>("test_api", "other").await, None);
+        assert_eq!(cache.stats(), CacheStats { hits: 1, misses: 1 });
+        assert_eq!(cache.stats().hit_rate(), Some(0.5));
+    }
+
     #[test]
     fn cache_keys_are_stable() {
         let dir = tempdir().unwrap();
         let cache = Cache {
             cache_dir: dir.path().to_path_buf(),
             enabled: true,
+            pending: Mutex::new(HashMap::new()),
+            hits: AtomicU64::new(0),
+            misses: AtomicU64::new(0),
         };
 
         let first = cache.cache_key("api", "query");
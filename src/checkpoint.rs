@@ -0,0 +1,229 @@
+use crate::entry::Entry;
+use crate::report::{EntryReport, EntryStatus};
+use std::collections::HashMap;
+use std::fs::{self, File, OpenOptions};
+use std::io::{BufRead, BufReader, BufWriter, Write};
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+use thiserror::Error;
+
+/// Completed reports buffered before the run-state file is flushed
+const FLUSH_INTERVAL: usize = 20;
+
+#[derive(Error, Debug)]
+pub enum CheckpointError {
+    #[error("IO error: {0}")]
+    Io(#[from] std::io::Error),
+    #[error("Serialization error: {0}")]
+    Serialization(#[from] serde_json::Error),
+}
+
+/// Run-state file that records each completed entry report as one line of JSON, so an
+/// interrupted run can be resumed without validating those entries again
+pub struct Checkpoint {
+    path: PathBuf,
+    writer: Mutex<CheckpointWriter>,
+}
+
+struct CheckpointWriter {
+    file: BufWriter<File>,
+    unflushed: usize,
+}
+
+impl Checkpoint {
+    /// Default run-state location for a set of input files, under the cache directory
+    pub fn default_path(files: &[PathBuf]) -> PathBuf {
+        dirs::cache_dir()
+            .unwrap_or_else(|| PathBuf::from(".cache"))
+            .join("bibval")
+            .join("runs")
+            .join(format!("{}.ndjson", input_set_id(files)))
+    }
+
+    /// Start recording to `path`, keeping earlier records when resuming
+    pub fn create(path: &Path, resume: bool) -> Result<Self, CheckpointError> {
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        let file = OpenOptions::new()
+            .create(true)
+            .write(true)
+            .append(resume)
+            .truncate(!resume)
+            .open(path)?;
+
+        Ok(Self {
+            path: path.to_path_buf(),
+            writer: Mutex::new(CheckpointWriter {
+                file: BufWriter::new(file),
+                unflushed: 0,
+            }),
+        })
+    }
+
+    /// Append a completed report, flushing every few reports
+    pub fn record(&self, report: &EntryReport) -> Result<(), CheckpointError> {
+        let mut writer = self.writer.lock().unwrap();
+        serde_json::to_writer(&mut writer.file, report)?;
+        writer.file.write_all(b"\n")?;
+
+        writer.unflushed += 1;
+        if writer.unflushed >= FLUSH_INTERVAL {
+            writer.file.flush()?;
+            writer.unflushed = 0;
+        }
+        Ok(())
+    }
+
+    /// The run finished, so there is nothing left to resume
+    pub fn finish(self) -> Result<(), CheckpointError> {
+        drop(self.writer);
+        fs::remove_file(&self.path)?;
+        Ok(())
+    }
+}
+
+impl Drop for CheckpointWriter {
+    fn drop(&mut self) {
+        let _ = self.file.flush();
+    }
+}
+
+/// Short stable identifier for a set of input files, for naming per-project state
+pub fn input_set_id(files: &[PathBuf]) -> String {
+    let inputs: Vec<String> = files
+        .iter()
+        .map(|file| {
+            fs::canonicalize(file)
+                .unwrap_or_else(|_| file.clone())
+                .display()
+                .to_string()
+        })
+        .collect();
+    let hash = blake3::hash(inputs.join("\n").as_bytes()).to_hex();
+    hash[..16].to_string()
+}
+
+/// Reports recorded by an earlier run that can be reused, keyed by citation key
+#[derive(Default)]
+pub struct CompletedReports {
+    reports: HashMap<String, EntryReport>,
+}
+
+impl CompletedReports {
+    /// Read a run-state file. Entries whose lookups failed are left out so they get
+    /// another try, and a line cut short by a crash is skipped.
+    pub fn load(path: &Path) -> Result<Self, CheckpointError> {
+        let mut reports = HashMap::new();
+
+        let file = match File::open(path) {
+            Ok(file) => file,
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(Self::default()),
+            Err(e) => return Err(e.into()),
+        };
+
+        for line in BufReader::new(file).lines() {
+            let Ok(report) = serde_json::from_str::<EntryReport>(&line?) else {
+                tracing::debug!("Skipping unreadable run-state record");
+                continue;
+            };
+            if !matches!(report.status, EntryStatus::Failed(_)) {
+                reports.insert(report.entry.key.clone(), report);
+            }
+        }
+
+        Ok(Self { reports })
+    }
+
+    /// Take the recorded report for `entry`, unless the entry was edited since
+    pub fn take(&mut self, entry: &Entry) -> Option<EntryReport> {
+        let recorded = &self.reports.get(&entry.key)?.entry;
+        if recorded.entry_type != entry.entry_type || recorded.fields != entry.fields {
+            return None;
+        }
+        self.reports.remove(&entry.key)
+    }
+
+    pub fn len(&self) -> usize {
+        self.reports.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.reports.is_empty()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::entry::ApiSource;
+
+    fn report(key: &str, status: EntryStatus) -> EntryReport {
+        let mut entry = Entry::new(key.to_string(), "article".to_string());
+        entry
+            .fields
+            .insert("title".to_string(), "A Title".to_string());
+        EntryReport {
+            entry,
+            status,
+            validation_results: Vec::new(),
+            lints: Vec::new(),
+            replacement: None,
+            citations: None,
+            open_access: None,
+            code: None,
+            trace: Vec::new(),
+        }
+    }
+
+    #[test]
+    fn resumes_only_finished_and_unchanged_entries() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("run.ndjson");
+
+        let checkpoint = Checkpoint::create(&path, false).unwrap();
+        checkpoint
+            .record(&report("done", EntryStatus::Ok(ApiSource::CrossRef)))
+            .unwrap();
+        checkpoint
+            .record(&report(
+                "failed",
+                EntryStatus::Failed("timeout".to_string()),
+            ))
+            .unwrap();
+        checkpoint
+            .record(&report("edited", EntryStatus::Warning))
+            .unwrap();
+        drop(checkpoint);
+
+        // Simulate a crash halfway through writing a record
+        let mut file = OpenOptions::new().append(true).open(&path).unwrap();
+        file.write_all(b"{\"entry\":{\"key\":").unwrap();
+
+        let mut edited = report("edited", EntryStatus::Warning).entry;
+        edited.fields.insert("year".to_string(), "2020".to_string());
+        let entries = [
+            report("done", EntryStatus::Warning).entry,
+            report("failed", EntryStatus::Warning).entry,
+            edited,
+        ];
+
+        let mut completed = CompletedReports::load(&path).unwrap();
+        assert_eq!(completed.len(), 2);
+        let reused: Vec<_> = entries.iter().filter_map(|e| completed.take(e)).collect();
+        assert_eq!(reused.len(), 1);
+        assert_eq!(reused[0].entry.key, "done");
+    }
+
+    #[test]
+    fn finishing_removes_the_run_state() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("runs").join("run.ndjson");
+
+        let checkpoint = Checkpoint::create(&path, false).unwrap();
+        assert!(path.exists());
+        checkpoint.finish().unwrap();
+        assert!(!path.exists());
+        assert!(CompletedReports::load(&path).unwrap().is_empty());
+    }
+}
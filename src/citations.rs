@@ -0,0 +1,201 @@
+//! Scanning LaTeX and Pandoc Markdown sources for the citation keys they use
+
+use regex_lite::Regex;
+use std::collections::{BTreeSet, HashMap};
+use std::sync::OnceLock;
+
+/// Pandoc cross-reference labels (pandoc-crossref) that share the `@` syntax
+const CROSSREF_PREFIXES: &[&str] = &["fig:", "tbl:", "eq:", "sec:", "lst:"];
+
+/// Citation keys used by a set of LaTeX or Markdown sources
+#[derive(Debug, Default, Clone)]
+pub struct Citations {
+    pub keys: BTreeSet<String>,
+    /// `\nocite{*}` or `@*` includes every bibliography entry
+    pub all: bool,
+}
+
+impl Citations {
+    /// Add the citations of one `.tex` file
+    pub fn scan_latex(&mut self, source: &str) {
+        let source = strip_comments(source);
+        for captures in cite_regex().captures_iter(&source) {
+            for key in captures[1].split(',').map(str::trim) {
+                match key {
+                    "" => {}
+                    "*" => self.all = true,
+                    key => {
+                        self.keys.insert(key.to_string());
+                    }
+                }
+            }
+        }
+    }
+
+    /// Add the Pandoc citations (`[@key]`, `@key`, `[-@key, p. 3]`, `@{key}`) of Markdown
+    /// text. Code is skipped, as are e-mail addresses and pandoc-crossref labels.
+    pub fn scan_markdown(&mut self, source: &str) {
+        let source = strip_code(source);
+        for captures in pandoc_cite_regex().captures_iter(&source) {
+            let key = &captures[1];
+            let key = match key.strip_prefix('{') {
+                Some(braced) => braced.trim_end_matches('}'),
+                // Punctuation is only part of a key when followed by more of the key
+                None => key.trim_end_matches(|c: char| !c.is_alphanumeric() && c != '_'),
+            };
+            if key == "*" {
+                self.all = true;
+            } else if !key.is_empty() && !CROSSREF_PREFIXES.iter().any(|p| key.starts_with(p)) {
+                self.keys.insert(key.to_string());
+            }
+        }
+    }
+
+    pub fn merge(&mut self, other: Citations) {
+        self.keys.extend(other.keys);
+        self.all |= other.all;
+    }
+
+    pub fn includes(&self, key: &str) -> bool {
+        self.all || self.keys.contains(key)
+    }
+}
+
+/// Replace renamed keys in the citation commands of LaTeX source, keeping everything
+/// else, spacing inside key lists included, as it was
+pub fn rename_latex(source: &str, renames: &HashMap<String, String>) -> String {
+    let mut renamed = String::with_capacity(source.len());
+    let mut last = 0;
+    for captures in cite_regex().captures_iter(source) {
+        let Some(keys) = captures.get(1) else {
+            continue;
+        };
+        renamed.push_str(&source[last..keys.start()]);
+        let list: Vec<String> = keys
+            .as_str()
+            .split(',')
+            .map(|part| match renames.get(part.trim()) {
+                Some(new) => part.replacen(part.trim(), new, 1),
+                None => part.to_string(),
+            })
+            .collect();
+        renamed.push_str(&list.join(","));
+        last = keys.end();
+    }
+    renamed.push_str(&source[last..]);
+    renamed
+}
+
+/// Matches `\cite`, `\citep*`, `\parencite[p. 3]{a,b}`, `\nocite{...}`, and the other
+/// natbib and biblatex citation commands, capturing the key list
+fn cite_regex() -> &'static Regex {
+    static REGEX: OnceLock<Regex> = OnceLock::new();
+    REGEX.get_or_init(|| {
+        Regex::new(
+            r"\\(?:[a-zA-Z]*cite[a-zA-Z]*|citeauthor|citeyear)\*?\s*(?:\[[^\]]*\]\s*){0,2}\{([^}]*)\}",
+        )
+        .expect("valid citation regex")
+    })
+}
+
+/// Matches a Pandoc citation key after `@`, which must not follow a word character
+fn pandoc_cite_regex() -> &'static Regex {
+    static REGEX: OnceLock<Regex> = OnceLock::new();
+    REGEX.get_or_init(|| {
+        Regex::new(r"(?:^|[^\w@\\])-?@(\{[^}]*\}|\*|\w[\w:.#$%&+?<>~/-]*)")
+            .expect("valid citation regex")
+    })
+}
+
+/// Blank out fenced code blocks and inline code spans
+fn strip_code(source: &str) -> String {
+    let mut in_fence = false;
+    source
+        .lines()
+        .map(|line| {
+            let trimmed = line.trim_start();
+            if trimmed.starts_with("```") || trimmed.starts_with("~~~") {
+                in_fence = !in_fence;
+                return String::new();
+            }
+            if in_fence {
+                return String::new();
+            }
+            line.split('`').step_by(2).collect::<Vec<_>>().join(" ")
+        })
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+/// Drop `%` comments, keeping escaped `\%`
+fn strip_comments(source: &str) -> String {
+    source
+        .lines()
+        .map(|line| {
+            let mut escaped = false;
+            for (i, c) in line.char_indices() {
+                match c {
+                    '\\' => escaped = !escaped,
+                    '%' if !escaped => return &line[..i],
+                    _ => escaped = false,
+                }
+            }
+            line
+        })
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn collects_keys_from_citation_commands() {
+        let mut citations = Citations::default();
+        citations.scan_latex(
+            r"As shown by \citet{he2016}, residual nets \citep[see][p.~3]{he2016, vaswani2017}.
+% \cite{commented_out}
+Rates of 5\% \parencite{smith20} and \textcite*{jones21}.",
+        );
+
+        let keys: Vec<_> = citations.keys.iter().map(String::as_str).collect();
+        assert_eq!(keys, ["he2016", "jones21", "smith20", "vaswani2017"]);
+        assert!(!citations.all);
+        assert!(!citations.includes("commented_out"));
+
+        citations.scan_latex(r"\nocite{*}");
+        assert!(citations.includes("anything"));
+    }
+
+    #[test]
+    fn renames_keys_inside_citation_commands() {
+        let renames = HashMap::from([("He2016".to_string(), "he2016deep".to_string())]);
+        assert_eq!(
+            rename_latex(
+                r"\citep[p.~3]{He2016, vaswani2017} and \cite{he2016} on He2016",
+                &renames
+            ),
+            r"\citep[p.~3]{he2016deep, vaswani2017} and \cite{he2016} on He2016"
+        );
+    }
+
+    #[test]
+    fn collects_keys_from_pandoc_citations() {
+        let mut citations = Citations::default();
+        citations.scan_markdown(
+            "Blah [see @doe99, pp. 33-35; also @smith04, ch. 1]. @knuth:1984 says so.
+Results [-@{key.with.braces}] appear in @fig:results; mail me@example.com.
+`@inline_code` and
+
+```
+@fenced
+```
+",
+        );
+
+        let keys: Vec<_> = citations.keys.iter().map(String::as_str).collect();
+        assert_eq!(keys, ["doe99", "key.with.braces", "knuth:1984", "smith04"]);
+        assert!(!citations.all);
+    }
+}
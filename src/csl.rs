@@ -0,0 +1,298 @@
+//! CSL-JSON and CSL YAML bibliographies, as used by Pandoc
+
+use crate::entry::Entry;
+use crate::lint::{normalize_isbn, normalize_issn};
+use crate::parser::{
+    extract_arxiv_from_url, extract_doi_from_url, extract_pmcid_from_url, extract_pmid_from_url,
+    extract_year_from_string, normalize_pmcid, ParseError,
+};
+use serde::Deserialize;
+use serde_json::Value;
+use std::sync::Arc;
+
+/// One item of a CSL bibliography
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub struct CslItem {
+    /// Citation key; some exporters write numeric IDs
+    pub id: Value,
+    #[serde(rename = "type")]
+    pub item_type: Option<String>,
+    pub title: Option<String>,
+    #[serde(default)]
+    pub author: Vec<CslName>,
+    pub issued: Option<CslDate>,
+    pub container_title: Option<String>,
+    #[serde(rename = "DOI")]
+    pub doi: Option<String>,
+    #[serde(rename = "URL")]
+    pub url: Option<String>,
+    pub volume: Option<Value>,
+    pub issue: Option<Value>,
+    pub page: Option<Value>,
+    pub publisher: Option<String>,
+    #[serde(rename = "ISBN")]
+    pub isbn: Option<String>,
+    #[serde(rename = "ISSN")]
+    pub issn: Option<String>,
+    #[serde(rename = "PMID")]
+    pub pmid: Option<Value>,
+    #[serde(rename = "PMCID")]
+    pub pmcid: Option<String>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub struct CslName {
+    pub family: Option<String>,
+    pub given: Option<String>,
+    pub non_dropping_particle: Option<String>,
+    pub literal: Option<String>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub struct CslDate {
+    /// `[[year, month, day]]`; parts are sometimes written as strings
+    #[serde(default)]
+    pub date_parts: Vec<Vec<Value>>,
+    pub raw: Option<String>,
+    pub literal: Option<String>,
+}
+
+/// Parse a CSL-JSON bibliography (an array of items)
+pub fn parse_csl_json(content: &str) -> Result<Vec<Entry>, ParseError> {
+    let items: Vec<CslItem> =
+        serde_json::from_str(content).map_err(|e| ParseError::ParseError(e.to_string()))?;
+    Ok(items.iter().map(item_to_entry).collect())
+}
+
+/// Parse a CSL YAML bibliography, either a list of items or a `references:` mapping
+pub fn parse_csl_yaml(content: &str) -> Result<Vec<Entry>, ParseError> {
+    #[derive(Deserialize)]
+    #[serde(untagged)]
+    enum CslYaml {
+        Items(Vec<CslItem>),
+        References { references: Vec<CslItem> },
+    }
+
+    let items =
+        match serde_yaml::from_str(content).map_err(|e| ParseError::ParseError(e.to_string()))? {
+            CslYaml::Items(items) | CslYaml::References { references: items } => items,
+        };
+    Ok(items.iter().map(item_to_entry).collect())
+}
+
+/// Map a CSL item to an entry with BibTeX-style fields
+pub fn item_to_entry(item: &CslItem) -> Entry {
+    let key = match &item.id {
+        Value::String(id) => id.clone(),
+        id => id.to_string(),
+    };
+    let entry_type = entry_type(item.item_type.as_deref().unwrap_or(""));
+    let mut entry = Entry::new(key, entry_type.to_string());
+    let mut fields = Vec::new();
+
+    if let Some(title) = non_empty(item.title.as_deref()) {
+        entry.title = Some(title.into());
+        fields.push(("title", title.to_string()));
+    }
+
+    let names: Vec<(Option<&str>, String)> = item
+        .author
+        .iter()
+        .filter_map(|name| {
+            if let Some(literal) = non_empty(name.literal.as_deref()) {
+                return Some((None, literal.to_string()));
+            }
+            let family = non_empty(name.family.as_deref())?;
+            let family = match non_empty(name.non_dropping_particle.as_deref()) {
+                Some(particle) => format!("{} {}", particle, family),
+                None => family.to_string(),
+            };
+            Some((non_empty(name.given.as_deref()), family))
+        })
+        .collect();
+    if !names.is_empty() {
+        let bibtex_names: Vec<String> = names
+            .iter()
+            .map(|(given, family)| match given {
+                Some(given) => format!("{}, {}", family, given),
+                None => format!("{{{}}}", family),
+            })
+            .collect();
+        fields.push(("author", bibtex_names.join(" and ")));
+        entry.authors = names
+            .iter()
+            .map(|(given, family)| match given {
+                Some(given) => Arc::from(format!("{} {}", given, family)),
+                None => Arc::from(family.as_str()),
+            })
+            .collect();
+    }
+
+    entry.year = item.issued.as_ref().and_then(CslDate::year);
+    if let Some(year) = entry.year {
+        fields.push(("year", year.to_string()));
+    }
+
+    if let Some(container) = non_empty(item.container_title.as_deref()) {
+        entry.venue = Some(container.into());
+        let field = if entry_type == "article" {
+            "journal"
+        } else {
+            "booktitle"
+        };
+        fields.push((field, container.to_string()));
+    }
+
+    entry.url = non_empty(item.url.as_deref()).map(str::to_string);
+    entry.doi = non_empty(item.doi.as_deref())
+        .map(str::to_string)
+        .or_else(|| entry.url.as_deref().and_then(extract_doi_from_url));
+    entry.arxiv_id = entry.url.as_deref().and_then(extract_arxiv_from_url);
+    entry.pmid = scalar(item.pmid.as_ref())
+        .or_else(|| entry.url.as_deref().and_then(extract_pmid_from_url));
+    entry.pmcid = item
+        .pmcid
+        .as_deref()
+        .and_then(normalize_pmcid)
+        .or_else(|| entry.url.as_deref().and_then(extract_pmcid_from_url));
+
+    for (name, value) in [
+        ("doi", entry.doi.clone()),
+        ("url", entry.url.clone()),
+        ("eprint", entry.arxiv_id.clone()),
+        ("pmid", entry.pmid.clone()),
+        ("pmcid", entry.pmcid.clone()),
+        ("volume", scalar(item.volume.as_ref())),
+        ("number", scalar(item.issue.as_ref())),
+        ("pages", scalar(item.page.as_ref())),
+        (
+            "publisher",
+            non_empty(item.publisher.as_deref()).map(str::to_string),
+        ),
+        ("isbn", non_empty(item.isbn.as_deref()).map(str::to_string)),
+        ("issn", non_empty(item.issn.as_deref()).map(str::to_string)),
+    ] {
+        if let Some(value) = value {
+            fields.push((name, value));
+        }
+    }
+
+    entry.fields = fields
+        .into_iter()
+        .map(|(name, value)| (name.to_string(), value))
+        .collect();
+    entry.isbn = entry.field("isbn").and_then(normalize_isbn);
+    entry.issn = entry.field("issn").and_then(normalize_issn);
+    entry
+}
+
+impl CslDate {
+    fn year(&self) -> Option<i32> {
+        let from_parts = self
+            .date_parts
+            .first()
+            .and_then(|parts| parts.first())
+            .and_then(|year| match year {
+                Value::Number(n) => n.as_i64().and_then(|y| i32::try_from(y).ok()),
+                Value::String(s) => s.trim().parse().ok(),
+                _ => None,
+            });
+        from_parts.or_else(|| {
+            self.raw
+                .as_deref()
+                .or(self.literal.as_deref())
+                .and_then(extract_year_from_string)
+        })
+    }
+}
+
+fn non_empty(value: Option<&str>) -> Option<&str> {
+    value.map(str::trim).filter(|v| !v.is_empty())
+}
+
+/// Volumes, issues and pages may be written as numbers or strings
+fn scalar(value: Option<&Value>) -> Option<String> {
+    match value? {
+        Value::String(s) => non_empty(Some(s)).map(str::to_string),
+        Value::Number(n) => Some(n.to_string()),
+        _ => None,
+    }
+}
+
+/// Closest BibTeX entry type for a CSL item type
+fn entry_type(item_type: &str) -> &'static str {
+    match item_type {
+        "article-journal" | "article-magazine" | "article-newspaper" => "article",
+        "paper-conference" => "inproceedings",
+        "book" => "book",
+        "chapter" | "entry-encyclopedia" | "entry-dictionary" => "incollection",
+        "thesis" => "phdthesis",
+        "report" => "techreport",
+        "software" => "software",
+        "dataset" => "dataset",
+        "webpage" | "post-weblog" | "post" => "online",
+        "patent" => "patent",
+        _ => "misc",
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_csl_json_items() {
+        let entries = parse_csl_json(
+            r#"[{
+                "id": "vaswani2017",
+                "type": "paper-conference",
+                "title": "Attention Is All You Need",
+                "author": [{"family": "Vaswani", "given": "Ashish"}, {"literal": "Google Brain"}],
+                "issued": {"date-parts": [["2017", 12]]},
+                "container-title": "NeurIPS",
+                "URL": "https://arxiv.org/abs/1706.03762",
+                "page": "5998-6008"
+            }]"#,
+        )
+        .unwrap();
+
+        let entry = &entries[0];
+        assert_eq!(entry.key, "vaswani2017");
+        assert_eq!(entry.entry_type, "inproceedings");
+        assert_eq!(entry.year, Some(2017));
+        assert_eq!(
+            entry.field("author"),
+            Some("Vaswani, Ashish and {Google Brain}")
+        );
+        assert_eq!(entry.field("booktitle"), Some("NeurIPS"));
+        assert_eq!(entry.arxiv_id.as_deref(), Some("1706.03762"));
+        assert_eq!(entry.field("pages"), Some("5998-6008"));
+    }
+
+    #[test]
+    fn parses_csl_yaml_references() {
+        let yaml = "
+references:
+- id: smith20
+  type: article-journal
+  title: Deep Things
+  author:
+  - family: Smith
+    given: Jane
+  issued:
+    date-parts:
+    - [2020]
+  container-title: Nature
+  DOI: 10.1038/x
+  volume: 12
+";
+        let entries = parse_csl_yaml(yaml).unwrap();
+        assert_eq!(entries.len(), 1);
+        assert_eq!(entries[0].field("journal"), Some("Nature"));
+        assert_eq!(entries[0].doi.as_deref(), Some("10.1038/x"));
+        assert_eq!(entries[0].field("volume"), Some("12"));
+    }
+}
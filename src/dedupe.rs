@@ -0,0 +1,181 @@
+//! Merging entries that cite the same work into one
+
+use crate::entry::Entry;
+use crate::fix::entry_span;
+use crate::lint::{near_duplicate_similarity, normalize_doi, same_identifier};
+use crate::parser::ParseError;
+use crate::writer::format_entry;
+use biblatex::RawBibliography;
+use std::collections::HashMap;
+use std::ops::Range;
+
+/// Duplicates folded into one entry
+#[derive(Debug, Clone)]
+pub struct Merge {
+    /// The merged entry, under the key of the duplicate it is based on
+    pub entry: Entry,
+    /// Keys of the other duplicates, which the merge replaces
+    pub removed: Vec<String>,
+}
+
+/// One merge for each group of entries citing the same work: sharing a DOI or arXiv ID,
+/// or with near-identical titles and overlapping authors. Entries with different DOIs
+/// are never merged. The duplicate with the most fields is kept, with the fields only the
+/// others have added to it.
+pub fn merges(entries: &[Entry]) -> Vec<Merge> {
+    let mut groups: Vec<Vec<usize>> = Vec::new();
+    let mut group_of: Vec<Option<usize>> = vec![None; entries.len()];
+    for i in 0..entries.len() {
+        for j in (i + 1)..entries.len() {
+            let (a, b) = (&entries[i], &entries[j]);
+            if !same_identifier(a, b) && near_duplicate_similarity(a, b).is_none() {
+                continue;
+            }
+            match (group_of[i], group_of[j]) {
+                (Some(g), Some(h)) if g == h => {}
+                (Some(g), Some(h)) => {
+                    if !conflicting_dois(entries, &groups[g], &groups[h]) {
+                        let moved = std::mem::take(&mut groups[h]);
+                        for &idx in &moved {
+                            group_of[idx] = Some(g);
+                        }
+                        groups[g].extend(moved);
+                    }
+                }
+                (Some(g), None) | (None, Some(g)) => {
+                    let new = if group_of[i].is_some() { j } else { i };
+                    if !conflicting_dois(entries, &groups[g], &[new]) {
+                        groups[g].push(new);
+                        group_of[new] = Some(g);
+                    }
+                }
+                (None, None) => {
+                    group_of[i] = Some(groups.len());
+                    group_of[j] = Some(groups.len());
+                    groups.push(vec![i, j]);
+                }
+            }
+        }
+    }
+
+    groups
+        .into_iter()
+        .filter(|group| group.len() > 1)
+        .map(|mut group| {
+            group.sort_unstable();
+            merge(group.iter().map(|&idx| &entries[idx]).collect())
+        })
+        .collect()
+}
+
+/// Whether two groups of entries have different DOIs between them
+fn conflicting_dois(entries: &[Entry], a: &[usize], b: &[usize]) -> bool {
+    let dois = |group: &[usize]| -> Vec<String> {
+        group
+            .iter()
+            .filter_map(|&idx| entries[idx].doi.as_deref().map(normalize_doi))
+            .collect()
+    };
+    let (a, b) = (dois(a), dois(b));
+    a.iter().any(|x| b.iter().any(|y| x != y))
+}
+
+fn merge(duplicates: Vec<&Entry>) -> Merge {
+    let richest = |entry: &&Entry| {
+        entry
+            .fields
+            .values()
+            .filter(|v| !v.trim().is_empty())
+            .count()
+    };
+    // The first of the richest, so ties keep the entry that comes first
+    let kept = duplicates
+        .iter()
+        .copied()
+        .rev()
+        .max_by_key(richest)
+        .expect("groups have entries");
+
+    let mut entry = kept.clone();
+    for other in &duplicates {
+        for (name, value) in &other.fields {
+            let missing = entry.fields.get(name).is_none_or(|v| v.trim().is_empty());
+            if missing && !value.trim().is_empty() {
+                entry.fields.insert(name.clone(), value.clone());
+            }
+        }
+    }
+    let removed = duplicates
+        .iter()
+        .filter(|d| d.key != kept.key)
+        .map(|d| d.key.clone())
+        .collect();
+    Merge { entry, removed }
+}
+
+/// Replace each kept entry in BibTeX source with its merged version and delete the
+/// duplicates it replaces. Everything else is kept as it was.
+pub fn apply(content: &str, merges: &[Merge]) -> Result<String, ParseError> {
+    let raw = RawBibliography::parse(content).map_err(|e| ParseError::ParseError(e.to_string()))?;
+    let merged: HashMap<&str, &Merge> = merges.iter().map(|m| (m.entry.key.as_str(), m)).collect();
+    let removed: Vec<&str> = merges
+        .iter()
+        .flat_map(|m| m.removed.iter().map(String::as_str))
+        .collect();
+
+    let mut edits: Vec<(Range<usize>, String)> = Vec::new();
+    for raw_entry in &raw.entries {
+        let key = raw_entry.v.key.v;
+        let span = entry_span(content, &raw_entry.span);
+        if let Some(merge) = merged.get(key) {
+            edits.push((span, format_entry(&merge.entry).trim_end().to_string()));
+        } else if removed.contains(&key) {
+            // Along with the blank lines that separated it from the next entry
+            let end = content.len() - content[span.end..].trim_start().len();
+            edits.push((span.start..end, String::new()));
+        }
+    }
+
+    edits.sort_by_key(|(range, _)| std::cmp::Reverse(range.start));
+    let mut deduped = content.to_string();
+    for (range, text) in edits {
+        deduped.replace_range(range, &text);
+    }
+    Ok(deduped)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parser::parse_bib_string;
+
+    #[test]
+    fn duplicates_merge_into_the_richest_entry() {
+        let content = "@article{vaswani2017,\n  title = {Attention Is All You Need},\n  \
+            author = {Vaswani, Ashish and Shazeer, Noam},\n  year = {2017},\n  \
+            doi = {10.5555/3295222.3295349}\n}\n\n\
+            @misc{attention,\n  title = {Attention is all you need},\n  \
+            author = {Ashish Vaswani and Noam Shazeer},\n  year = {2017},\n  \
+            eprint = {1706.03762},\n  note = {Preprint}\n}\n\n\
+            @article{other, title = {Deep Learning}, author = {LeCun, Yann}, year = {2015}}\n";
+        let entries = parse_bib_string(content).unwrap();
+
+        let merges = merges(&entries);
+        assert_eq!(merges.len(), 1);
+        assert_eq!(merges[0].entry.key, "attention");
+        assert_eq!(merges[0].removed, vec!["vaswani2017"]);
+        assert_eq!(
+            merges[0].entry.field("doi"),
+            Some("10.5555/3295222.3295349")
+        );
+
+        let deduped = apply(content, &merges).unwrap();
+        assert!(deduped.starts_with("@misc{attention,\n"));
+        assert!(deduped.contains("  doi    = {10.5555/3295222.3295349},\n"));
+        assert!(!deduped.contains("vaswani2017"));
+        assert!(deduped.ends_with(
+            "}\n\n@article{other, title = {Deep Learning}, \
+            author = {LeCun, Yann}, year = {2015}}\n"
+        ));
+    }
+}
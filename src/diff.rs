@@ -0,0 +1,381 @@
+//! Entry-level comparison of two versions of a bibliography
+
+use crate::entry::Entry;
+use crate::report::{EntryStatus, Report};
+use colored::Colorize;
+use serde::Serialize;
+use std::collections::{BTreeSet, HashMap};
+
+/// A field whose value differs between the two versions
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct FieldChange {
+    pub field: String,
+    pub old: Option<String>,
+    pub new: Option<String>,
+}
+
+/// Citation keys added, removed and changed between two versions of a bibliography
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct BibDiff {
+    pub added: Vec<String>,
+    pub removed: Vec<String>,
+    /// Changed entries by key, with the fields that differ
+    pub changed: Vec<(String, Vec<FieldChange>)>,
+    pub unchanged: usize,
+}
+
+impl BibDiff {
+    pub fn is_changed(&self, key: &str) -> bool {
+        self.changed.iter().any(|(k, _)| k == key)
+    }
+}
+
+/// Compare entries by citation key. Whitespace and case in the entry type are not
+/// material; any other difference in a field value is.
+pub fn diff_entries(old: &[Entry], new: &[Entry]) -> BibDiff {
+    let old_by_key: HashMap<&str, &Entry> = old.iter().map(|e| (e.key.as_str(), e)).collect();
+    let new_keys: BTreeSet<&str> = new.iter().map(|e| e.key.as_str()).collect();
+
+    let mut diff = BibDiff::default();
+    for entry in new {
+        match old_by_key.get(entry.key.as_str()) {
+            None => diff.added.push(entry.key.clone()),
+            Some(previous) => {
+                let changes = field_changes(previous, entry);
+                if changes.is_empty() {
+                    diff.unchanged += 1;
+                } else {
+                    diff.changed.push((entry.key.clone(), changes));
+                }
+            }
+        }
+    }
+    diff.removed = old
+        .iter()
+        .filter(|e| !new_keys.contains(e.key.as_str()))
+        .map(|e| e.key.clone())
+        .collect();
+    diff
+}
+
+fn field_changes(old: &Entry, new: &Entry) -> Vec<FieldChange> {
+    let mut changes = Vec::new();
+    if !old.entry_type.eq_ignore_ascii_case(&new.entry_type) {
+        changes.push(FieldChange {
+            field: "type".to_string(),
+            old: Some(old.entry_type.clone()),
+            new: Some(new.entry_type.clone()),
+        });
+    }
+
+    let names: BTreeSet<&String> = old.fields.keys().chain(new.fields.keys()).collect();
+    for name in names {
+        let old_value = old.fields.get(name);
+        let new_value = new.fields.get(name);
+        if words(old_value) != words(new_value) {
+            changes.push(FieldChange {
+                field: name.clone(),
+                old: old_value.cloned(),
+                new: new_value.cloned(),
+            });
+        }
+    }
+    changes
+}
+
+fn words(value: Option<&String>) -> Option<Vec<&str>> {
+    value.map(|v| v.split_whitespace().collect())
+}
+
+/// Whether an entry's validation status got better or worse between the versions
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Trend {
+    Improved,
+    Regressed,
+    Unchanged,
+    /// A lookup failed in one of the versions, so they can't be compared
+    Unknown,
+}
+
+/// How good a status is; `None` for failed lookups, which say nothing about the entry
+fn status_quality(status: &EntryStatus) -> Option<u8> {
+    match status {
+        EntryStatus::Error => Some(0),
+        EntryStatus::Warning => Some(1),
+        EntryStatus::NotFound => Some(2),
+        EntryStatus::Ok(_) => Some(3),
+        EntryStatus::Failed(_) => None,
+    }
+}
+
+fn trend(old: &EntryStatus, new: &EntryStatus) -> Trend {
+    match (status_quality(old), status_quality(new)) {
+        (Some(old), Some(new)) if new > old => Trend::Improved,
+        (Some(old), Some(new)) if new < old => Trend::Regressed,
+        (Some(_), Some(_)) => Trend::Unchanged,
+        _ => Trend::Unknown,
+    }
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct AddedEntry {
+    pub key: String,
+    pub status: Option<EntryStatus>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct ChangedEntry {
+    pub key: String,
+    pub fields: Vec<FieldChange>,
+    pub old_status: Option<EntryStatus>,
+    pub new_status: Option<EntryStatus>,
+    pub trend: Trend,
+}
+
+/// Entry changes between two versions, with the validation status of each version
+#[derive(Debug, Clone, Serialize)]
+pub struct DiffReport {
+    pub old_label: String,
+    pub new_label: String,
+    pub added: Vec<AddedEntry>,
+    pub removed: Vec<String>,
+    pub changed: Vec<ChangedEntry>,
+    pub unchanged: usize,
+}
+
+impl DiffReport {
+    /// Attach statuses from reports validating the changed entries of each version and
+    /// the added entries of the new one
+    pub fn new(
+        diff: BibDiff,
+        labels: (String, String),
+        old_report: &Report,
+        new_report: &Report,
+    ) -> Self {
+        let statuses = |report: &Report| -> HashMap<String, EntryStatus> {
+            report
+                .entries
+                .iter()
+                .map(|r| (r.entry.key.clone(), r.status.clone()))
+                .collect()
+        };
+        let mut old_statuses = statuses(old_report);
+        let mut new_statuses = statuses(new_report);
+
+        let added = diff
+            .added
+            .into_iter()
+            .map(|key| AddedEntry {
+                status: new_statuses.remove(&key),
+                key,
+            })
+            .collect();
+        let changed = diff
+            .changed
+            .into_iter()
+            .map(|(key, fields)| {
+                let old_status = old_statuses.remove(&key);
+                let new_status = new_statuses.remove(&key);
+                let trend = match (&old_status, &new_status) {
+                    (Some(old), Some(new)) => trend(old, new),
+                    _ => Trend::Unknown,
+                };
+                ChangedEntry {
+                    key,
+                    fields,
+                    old_status,
+                    new_status,
+                    trend,
+                }
+            })
+            .collect();
+
+        Self {
+            old_label: labels.0,
+            new_label: labels.1,
+            added,
+            removed: diff.removed,
+            changed,
+            unchanged: diff.unchanged,
+        }
+    }
+
+    /// Changed entries whose status got worse
+    pub fn count_regressed(&self) -> usize {
+        self.changed
+            .iter()
+            .filter(|c| c.trend == Trend::Regressed)
+            .count()
+    }
+
+    pub fn count_improved(&self) -> usize {
+        self.changed
+            .iter()
+            .filter(|c| c.trend == Trend::Improved)
+            .count()
+    }
+
+    /// Added entries that have errors
+    pub fn count_added_errors(&self) -> usize {
+        self.added
+            .iter()
+            .filter(|a| matches!(a.status, Some(EntryStatus::Error)))
+            .count()
+    }
+
+    pub fn to_json(&self) -> serde_json::Result<String> {
+        serde_json::to_string_pretty(self)
+    }
+
+    /// Print the diff to stdout with colors
+    pub fn print(&self) {
+        println!();
+        println!("{}", "bibval Diff".bold());
+        println!("{}", "=".repeat(50));
+        println!();
+        println!("{} -> {}", self.old_label.cyan(), self.new_label.cyan());
+        println!(
+            "  {} added, {} removed, {} changed, {} unchanged",
+            self.added.len().to_string().green(),
+            self.removed.len().to_string().red(),
+            self.changed.len().to_string().yellow(),
+            self.unchanged
+        );
+        println!(
+            "  Status: {} improved, {} regressed, {} added with errors",
+            self.count_improved().to_string().green(),
+            self.count_regressed().to_string().red(),
+            self.count_added_errors().to_string().red()
+        );
+        println!();
+
+        if !self.added.is_empty() {
+            println!("{}", format!("ADDED ({})", self.added.len()).green().bold());
+            for added in &self.added {
+                println!(
+                    "  {} [{}] {}",
+                    "+".green(),
+                    added.key.cyan(),
+                    status_label(added.status.as_ref())
+                );
+            }
+            println!();
+        }
+
+        if !self.removed.is_empty() {
+            println!(
+                "{}",
+                format!("REMOVED ({})", self.removed.len()).red().bold()
+            );
+            for key in &self.removed {
+                println!("  {} [{}]", "-".red(), key.cyan());
+            }
+            println!();
+        }
+
+        if !self.changed.is_empty() {
+            println!(
+                "{}",
+                format!("CHANGED ({})", self.changed.len()).yellow().bold()
+            );
+            for changed in &self.changed {
+                let trend = match changed.trend {
+                    Trend::Improved => format!(" {}", "improved".green()),
+                    Trend::Regressed => format!(" {}", "regressed".red().bold()),
+                    Trend::Unchanged | Trend::Unknown => String::new(),
+                };
+                println!(
+                    "  {} [{}] {} -> {}{}",
+                    "~".yellow(),
+                    changed.key.cyan(),
+                    status_label(changed.old_status.as_ref()),
+                    status_label(changed.new_status.as_ref()),
+                    trend
+                );
+                for change in &changed.fields {
+                    println!(
+                        "       {}: {} -> {}",
+                        change.field,
+                        field_value(change.old.as_deref()).dimmed(),
+                        field_value(change.new.as_deref())
+                    );
+                }
+            }
+            println!();
+        }
+    }
+}
+
+fn status_label(status: Option<&EntryStatus>) -> colored::ColoredString {
+    match status {
+        Some(EntryStatus::Ok(_)) => "OK".green(),
+        Some(EntryStatus::Warning) => "WARN".yellow(),
+        Some(EntryStatus::Error) => "ERROR".red(),
+        Some(EntryStatus::NotFound) => "NOT FOUND".dimmed(),
+        Some(EntryStatus::Failed(_)) => "FAILED".red(),
+        None => "-".dimmed(),
+    }
+}
+
+fn field_value(value: Option<&str>) -> String {
+    match value {
+        Some(value) => format!("\"{}\"", value),
+        None => "(none)".to_string(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parser::parse_bib_string;
+
+    #[test]
+    fn entries_are_matched_by_key() {
+        let old = parse_bib_string(
+            "@article{kept, title={Same}, year={2020}}
+             @article{edited, title={Old title}, year={2020}}
+             @misc{dropped, title={Gone}}",
+        )
+        .unwrap();
+        let new = parse_bib_string(
+            "@ARTICLE{kept, title={Same},   year={2020}}
+             @article{edited, title={New title}, year={2020}, doi={10.1/x}}
+             @misc{fresh, title={New}}",
+        )
+        .unwrap();
+
+        let diff = diff_entries(&old, &new);
+        assert_eq!(diff.added, ["fresh"]);
+        assert_eq!(diff.removed, ["dropped"]);
+        assert_eq!(diff.unchanged, 1);
+
+        let (key, fields) = &diff.changed[0];
+        assert_eq!(key, "edited");
+        let names: Vec<_> = fields.iter().map(|c| c.field.as_str()).collect();
+        assert_eq!(names, ["doi", "title"]);
+        assert_eq!(fields[0].old, None);
+    }
+
+    #[test]
+    fn failed_lookups_are_not_compared() {
+        assert_eq!(
+            trend(&EntryStatus::Error, &EntryStatus::Warning),
+            Trend::Improved
+        );
+        assert_eq!(
+            trend(
+                &EntryStatus::Ok(crate::entry::ApiSource::Dblp),
+                &EntryStatus::NotFound
+            ),
+            Trend::Regressed
+        );
+        assert_eq!(
+            trend(
+                &EntryStatus::Failed("timeout".to_string()),
+                &EntryStatus::Error
+            ),
+            Trend::Unknown
+        );
+    }
+}
@@ -1,6 +1,13 @@
 use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::path::Path;
+use std::sync::Arc;
 
-/// Normalized bibliography entry for comparison across different sources
+/// Normalized bibliography entry for comparison across different sources.
+///
+/// Entries are cloned into results, reports and the cache. The title, venue and author
+/// names are reference-counted and shared between clones; the author list itself and the
+/// raw `fields`, abstracts included, are copied with each clone.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Entry {
     /// Citation key from the bib file
@@ -8,19 +15,62 @@ pub struct Entry {
     /// Entry type (article, inproceedings, book, etc.)
     pub entry_type: String,
     /// Paper title
-    pub title: Option<String>,
+    pub title: Option<Arc<str>>,
     /// List of authors
-    pub authors: Vec<String>,
+    pub authors: Vec<Arc<str>>,
     /// Publication year
     pub year: Option<i32>,
     /// Journal or conference venue
-    pub venue: Option<String>,
+    pub venue: Option<Arc<str>>,
     /// DOI identifier
     pub doi: Option<String>,
     /// ArXiv identifier (e.g., "2301.12345")
     pub arxiv_id: Option<String>,
+    /// PubMed identifier (e.g., "31452104")
+    #[serde(default)]
+    pub pmid: Option<String>,
+    /// PubMed Central identifier (e.g., "PMC6712345")
+    #[serde(default)]
+    pub pmcid: Option<String>,
+    /// MathSciNet review number (e.g., "MR0031344")
+    #[serde(default)]
+    pub mr_number: Option<String>,
+    /// HAL open archive identifier (e.g., "hal-01234567")
+    #[serde(default)]
+    pub hal_id: Option<String>,
+    /// ISBN as bare digits (e.g., "9780262035613")
+    #[serde(default)]
+    pub isbn: Option<String>,
+    /// ISSN (e.g., "0028-0836")
+    #[serde(default)]
+    pub issn: Option<String>,
     /// URL
     pub url: Option<String>,
+    /// Raw field values as written in the bib file, keyed by lowercase field name
+    #[serde(default)]
+    pub fields: BTreeMap<String, String>,
+    /// Where the entry is declared, for entries parsed from BibTeX
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub location: Option<SourceLocation>,
+}
+
+/// Where an entry is declared in its source
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SourceLocation {
+    /// The file, when the entry was read from one
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub file: Option<Arc<Path>>,
+    /// 1-based line of the entry's `@type{key,`
+    pub line: usize,
+}
+
+impl std::fmt::Display for SourceLocation {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match &self.file {
+            Some(file) => write!(f, "{}:{}", file.display(), self.line),
+            None => write!(f, "line {}", self.line),
+        }
+    }
 }
 
 impl Entry {
@@ -34,10 +84,65 @@ impl Entry {
             venue: None,
             doi: None,
             arxiv_id: None,
+            pmid: None,
+            pmcid: None,
+            mr_number: None,
+            hal_id: None,
+            isbn: None,
+            issn: None,
             url: None,
+            fields: BTreeMap::new(),
+            location: None,
         }
     }
 
+    /// Get the raw value of a field as written in the bib file
+    pub fn field(&self, name: &str) -> Option<&str> {
+        self.fields.get(name).map(String::as_str)
+    }
+
+    /// What kind of work the entry cites, which decides how it is validated
+    pub fn kind(&self) -> EntryKind {
+        match self.entry_type.to_lowercase().as_str() {
+            "software" => EntryKind::Software,
+            "dataset" => EntryKind::Dataset,
+            // biblatex treats @www and @electronic as aliases of @online
+            "online" | "www" | "electronic" => EntryKind::Online,
+            "patent" => EntryKind::Patent,
+            _ => EntryKind::Publication,
+        }
+    }
+
+    /// An arXiv preprint: an entry with an eprint and no DOI but arXiv's own, cited as
+    /// `@misc`/`@unpublished` or with arXiv as its venue
+    pub fn is_preprint(&self) -> bool {
+        let arxiv_only = self.doi.as_deref().is_none_or(is_arxiv_doi);
+        let unpublished = matches!(
+            self.entry_type.to_lowercase().as_str(),
+            "misc" | "unpublished"
+        ) || self.venue.as_deref().is_none_or(is_arxiv_venue);
+        self.arxiv_id.is_some() && arxiv_only && unpublished
+    }
+
+    /// Books, theses and reports: works that library catalogs hold
+    pub fn is_book_like(&self) -> bool {
+        matches!(
+            self.entry_type.to_lowercase().as_str(),
+            "book"
+                | "mvbook"
+                | "inbook"
+                | "incollection"
+                | "collection"
+                | "proceedings"
+                | "techreport"
+                | "report"
+                | "thesis"
+                | "phdthesis"
+                | "mastersthesis"
+                | "manual"
+        )
+    }
+
     /// Normalize title for comparison (lowercase, remove extra whitespace)
     pub fn normalized_title(&self) -> Option<String> {
         self.title.as_ref().map(|t| normalize_string(t))
@@ -49,9 +154,48 @@ impl Entry {
     }
 }
 
-/// Normalize a string for comparison: lowercase, collapse whitespace, remove punctuation
+/// Groups of entry types that share a validation flow
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum EntryKind {
+    /// Papers, books, theses and the like, matched against bibliographic databases
+    Publication,
+    /// Released software, identified by version and repository
+    Software,
+    /// Published datasets, identified by version and DOI
+    Dataset,
+    /// Web pages, identified by URL and access date
+    Online,
+    /// Patents, identified by publication number
+    Patent,
+}
+
+/// A DOI arXiv minted for one of its preprints ("10.48550/arXiv.1706.03762")
+pub fn is_arxiv_doi(doi: &str) -> bool {
+    doi.trim().to_lowercase().starts_with("10.48550/")
+}
+
+/// A venue naming arXiv itself: "arXiv preprint arXiv:1706.03762", "arXiv.org" or DBLP's
+/// "CoRR"
+pub fn is_arxiv_venue(venue: &str) -> bool {
+    let venue = venue.trim().to_lowercase();
+    venue.contains("arxiv") || venue == "corr"
+}
+
+/// Normalize a string for comparison: lowercase, collapse whitespace, remove punctuation.
+///
+/// Full-width forms fold to ASCII, and the punctuation of Japanese and Chinese text, which
+/// isn't surrounded by spaces, separates words like a space would.
 pub fn normalize_string(s: &str) -> String {
-    s.to_lowercase()
+    s.chars()
+        .map(|c| match c {
+            // Full-width ASCII forms: "ＧＰＴ－４"
+            '\u{FF01}'..='\u{FF5E}' => char::from_u32(c as u32 - 0xFEE0).unwrap_or(c),
+            // CJK symbols and punctuation, the katakana middle dot and half-width punctuation
+            '\u{3000}'..='\u{303F}' | '\u{30FB}' | '\u{FF5F}'..='\u{FF65}' => ' ',
+            c => c,
+        })
+        .collect::<String>()
+        .to_lowercase()
         .chars()
         .filter(|c| c.is_alphanumeric() || c.is_whitespace())
         .collect::<String>()
@@ -61,7 +205,7 @@ pub fn normalize_string(s: &str) -> String {
 }
 
 /// Result from an external API validation
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ValidationResult {
     /// Which API this result came from
     pub source: ApiSource,
@@ -73,7 +217,7 @@ pub struct ValidationResult {
     pub discrepancies: Vec<Discrepancy>,
 }
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
 pub enum ApiSource {
     CrossRef,
     Dblp,
@@ -83,6 +227,36 @@ pub enum ApiSource {
     OpenLibrary,
     OpenReview,
     Zenodo,
+    DataCite,
+    Web,
+    WorldCat,
+    PubMed,
+    Acm,
+    Springer,
+    Scopus,
+    WebOfScience,
+    HathiTrust,
+    Inspire,
+    Ads,
+    MathSciNet,
+    Osf,
+    Hal,
+    Core,
+    Unpaywall,
+    Figshare,
+    Dnb,
+    BritishLibrary,
+    OpenCitations,
+    Epo,
+    Ietf,
+    W3c,
+    GitHub,
+    SoftwareHeritage,
+    PapersWithCode,
+    Doaj,
+    Scielo,
+    Jstage,
+    DoiOrg,
 }
 
 impl std::fmt::Display for ApiSource {
@@ -96,11 +270,41 @@ impl std::fmt::Display for ApiSource {
             ApiSource::OpenLibrary => write!(f, "Open Library"),
             ApiSource::OpenReview => write!(f, "OpenReview"),
             ApiSource::Zenodo => write!(f, "Zenodo"),
+            ApiSource::DataCite => write!(f, "DataCite"),
+            ApiSource::Web => write!(f, "Web"),
+            ApiSource::WorldCat => write!(f, "WorldCat"),
+            ApiSource::PubMed => write!(f, "PubMed"),
+            ApiSource::Acm => write!(f, "ACM DL"),
+            ApiSource::Springer => write!(f, "Springer Nature"),
+            ApiSource::Scopus => write!(f, "Scopus"),
+            ApiSource::WebOfScience => write!(f, "Web of Science"),
+            ApiSource::HathiTrust => write!(f, "HathiTrust"),
+            ApiSource::Inspire => write!(f, "INSPIRE"),
+            ApiSource::Ads => write!(f, "NASA ADS"),
+            ApiSource::MathSciNet => write!(f, "MathSciNet"),
+            ApiSource::Osf => write!(f, "OSF Preprints"),
+            ApiSource::Hal => write!(f, "HAL"),
+            ApiSource::Core => write!(f, "CORE"),
+            ApiSource::Unpaywall => write!(f, "Unpaywall"),
+            ApiSource::Figshare => write!(f, "figshare"),
+            ApiSource::Dnb => write!(f, "DNB"),
+            ApiSource::BritishLibrary => write!(f, "British Library"),
+            ApiSource::OpenCitations => write!(f, "OpenCitations"),
+            ApiSource::Epo => write!(f, "EPO OPS"),
+            ApiSource::Ietf => write!(f, "IETF Datatracker"),
+            ApiSource::W3c => write!(f, "W3C"),
+            ApiSource::GitHub => write!(f, "GitHub"),
+            ApiSource::SoftwareHeritage => write!(f, "Software Heritage"),
+            ApiSource::PapersWithCode => write!(f, "Papers With Code"),
+            ApiSource::Doaj => write!(f, "DOAJ"),
+            ApiSource::Scielo => write!(f, "SciELO"),
+            ApiSource::Jstage => write!(f, "J-STAGE"),
+            ApiSource::DoiOrg => write!(f, "doi.org"),
         }
     }
 }
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Discrepancy {
     pub field: DiscrepancyField,
     pub severity: Severity,
@@ -109,13 +313,26 @@ pub struct Discrepancy {
     pub message: String,
 }
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
 pub enum DiscrepancyField {
     Title,
     Authors,
     Year,
     Venue,
     Doi,
+    Duplicate,
+    Key,
+    Url,
+    Crossref,
+    Placeholder,
+    Version,
+    Predatory,
+    Publisher,
+    Pmcid,
+    Pages,
+    MrNumber,
+    Retraction,
+    Swhid,
 }
 
 impl std::fmt::Display for DiscrepancyField {
@@ -126,11 +343,24 @@ impl std::fmt::Display for DiscrepancyField {
             DiscrepancyField::Year => write!(f, "Year"),
             DiscrepancyField::Venue => write!(f, "Venue"),
             DiscrepancyField::Doi => write!(f, "DOI"),
+            DiscrepancyField::Duplicate => write!(f, "Duplicate"),
+            DiscrepancyField::Key => write!(f, "Key"),
+            DiscrepancyField::Url => write!(f, "URL"),
+            DiscrepancyField::Crossref => write!(f, "Crossref"),
+            DiscrepancyField::Placeholder => write!(f, "Placeholder"),
+            DiscrepancyField::Version => write!(f, "Version"),
+            DiscrepancyField::Predatory => write!(f, "Predatory"),
+            DiscrepancyField::Publisher => write!(f, "Publisher"),
+            DiscrepancyField::Pmcid => write!(f, "PMCID"),
+            DiscrepancyField::Pages => write!(f, "Pages"),
+            DiscrepancyField::MrNumber => write!(f, "MR number"),
+            DiscrepancyField::Retraction => write!(f, "Retraction"),
+            DiscrepancyField::Swhid => write!(f, "SWHID"),
         }
     }
 }
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
 pub enum Severity {
     Info,
     Warning,
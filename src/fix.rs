@@ -0,0 +1,1278 @@
+//! High-confidence corrections written back into a BibTeX file, leaving everything the
+//! validators aren't sure about exactly as it was
+
+use crate::entry::{
+    normalize_string, ApiSource, DiscrepancyField, Entry, EntryKind, Severity, ValidationResult,
+};
+use crate::fusion::consensus_authors;
+use crate::lint::{normalize_doi, restyled_venue, LintConfig};
+use crate::matcher::{is_published_version, normalize_pages, published_entry_type};
+use crate::parser::ParseError;
+use crate::report::EntryReport;
+use biblatex::RawBibliography;
+use colored::Colorize;
+use regex_lite::Regex;
+use std::collections::HashMap;
+use std::io::{self, BufRead, Write};
+use std::ops::Range;
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, OnceLock};
+
+/// Entries are only corrected from matches at least this confident
+const MIN_CONFIDENCE: f64 = 0.9;
+
+/// A new value for a field, which is added when the entry doesn't have it yet
+#[derive(Debug, Clone, PartialEq)]
+pub struct Correction {
+    pub field: String,
+    pub old: Option<String>,
+    pub new: String,
+}
+
+/// The pseudo-field a correction of the entry type names, as in biblatex source maps
+pub const ENTRY_TYPE_FIELD: &str = "entrytype";
+
+/// Fields filled in when the entry lacks them and the confident matches agree on a value
+const FILLED_FIELDS: [&str; 4] = ["pages", "volume", "number", "publisher"];
+
+/// Fields BibTeX reads verbatim or that hold identifiers, so their values are written
+/// without LaTeX escapes
+const VERBATIM_FIELDS: [&str; 6] = ["doi", "year", "isbn", "issn", "url", ENTRY_TYPE_FIELD];
+
+/// Every field a correction can change
+pub const FIXABLE_FIELDS: [&str; 13] = [
+    "doi",
+    "year",
+    "title",
+    "author",
+    "pages",
+    "volume",
+    "number",
+    "publisher",
+    "journal",
+    "booktitle",
+    "isbn",
+    "issn",
+    ENTRY_TYPE_FIELD,
+];
+
+/// Which fields corrections may change. The default fixes every field.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct FixPolicy {
+    /// Only these fields are fixed; all of them when `None`
+    pub only: Option<Vec<String>>,
+    /// Fields never fixed, even when listed in `only`
+    pub except: Vec<String>,
+}
+
+impl FixPolicy {
+    /// Whether corrections of this field are applied
+    pub fn allows(&self, field: &str) -> bool {
+        let listed = |fields: &[String]| fields.iter().any(|f| f == field);
+        self.only.as_deref().is_none_or(listed) && !listed(&self.except)
+    }
+
+    /// The corrections of the fields this policy allows
+    pub fn filter(&self, mut corrections: Vec<Correction>) -> Vec<Correction> {
+        corrections.retain(|c| self.allows(&c.field));
+        corrections
+    }
+
+    /// A field name as corrections use it, accepting `authors` and `type` for `author`
+    /// and the entry type
+    pub fn field_from_str(name: &str) -> Result<String, String> {
+        let field = match name.trim().to_lowercase().as_str() {
+            "authors" => "author".to_string(),
+            "type" => ENTRY_TYPE_FIELD.to_string(),
+            field => field.to_string(),
+        };
+        if FIXABLE_FIELDS.contains(&field.as_str()) {
+            Ok(field)
+        } else {
+            Err(format!(
+                "`{}` can't be fixed; fixable fields are {}",
+                name,
+                FIXABLE_FIELDS.join(", ")
+            ))
+        }
+    }
+}
+
+/// The corrections the validators agree on for an entry: the consensus year, title,
+/// author list and missing DOI, the repair of a DOI that doesn't resolve, author
+/// spellings every confident match reports, the casing of an all-lowercase or
+/// all-uppercase title, braces around the title words sentence-case styles would
+/// lowercase, missing pages, volume, number and publisher, the missing ISBN of a book or
+/// ISSN of an article, and the type, venue, year and DOI of a preprint's published
+/// version. Entries without a confident match get none.
+pub fn corrections(report: &EntryReport) -> Vec<Correction> {
+    let entry = &report.entry;
+    let confident: Vec<_> = report
+        .validation_results
+        .iter()
+        .filter(|r| r.matched_entry.is_some() && r.confidence >= MIN_CONFIDENCE)
+        .collect();
+    if confident.is_empty() {
+        return Vec::new();
+    }
+
+    let mut corrections = Vec::new();
+    // A later correction of a field replaces an earlier one. Values come from the sources
+    // as plain text, sometimes with HTML markup, and are written as LaTeX.
+    let mut correct = |field: &str, new: String| {
+        let new = if VERBATIM_FIELDS.contains(&field) {
+            new
+        } else {
+            bibtex_text(&new)
+        };
+        corrections.retain(|c: &Correction| c.field != field);
+        let old = match field {
+            ENTRY_TYPE_FIELD => Some(entry.entry_type.clone()),
+            _ => entry.field(field).map(str::to_string),
+        };
+        if old.as_deref() != Some(new.as_str()) {
+            corrections.push(Correction {
+                field: field.to_string(),
+                old,
+                new,
+            });
+        }
+    };
+
+    // The fused consensus is the result without a matched entry
+    let consensus = report
+        .validation_results
+        .iter()
+        .filter(|r| r.matched_entry.is_none())
+        .flat_map(|r| &r.discrepancies);
+    let mut title_fixed = false;
+    let mut authors_fixed = false;
+    for discrepancy in consensus {
+        let value = discrepancy.remote_value.clone();
+        match discrepancy.field {
+            // A `date` can say more than the year, so only `year` fields are rewritten
+            DiscrepancyField::Year
+                if discrepancy.severity == Severity::Error && entry.field("year").is_some() =>
+            {
+                correct("year", value);
+            }
+            DiscrepancyField::Doi if entry.doi.is_none() => correct("doi", value),
+            DiscrepancyField::Title if discrepancy.severity == Severity::Error => {
+                correct("title", value);
+                title_fixed = true;
+            }
+            DiscrepancyField::Authors => {
+                if let Some(authors) = consensus_authors(report, &value) {
+                    correct("author", join_authors(&authors));
+                    authors_fixed = true;
+                }
+            }
+            _ => {}
+        }
+    }
+
+    // A DOI that doesn't resolve is replaced by the repair of it doi.org resolved
+    let repaired = confident
+        .iter()
+        .filter(|r| r.source == ApiSource::DoiOrg)
+        .flat_map(|r| &r.discrepancies)
+        .find(|d| d.field == DiscrepancyField::Doi && d.severity == Severity::Error);
+    if let Some(repaired) = repaired {
+        correct("doi", repaired.remote_value.clone());
+    }
+
+    if !authors_fixed {
+        if let Some(authors) = agreed_spellings(entry, &confident) {
+            correct("author", authors);
+        }
+    }
+    let titles: Vec<&str> = confident
+        .iter()
+        .filter_map(|r| r.matched_entry.as_ref()?.title.as_deref())
+        .collect();
+    if !title_fixed {
+        if let Some(title) = recased_title(entry, &titles) {
+            correct("title", title);
+        }
+    }
+    for field in FILLED_FIELDS {
+        if entry.field(field).is_none() {
+            if let Some(value) = agreed_value(field, &confident) {
+                correct(field, value);
+            }
+        }
+    }
+    let matched = || confident.iter().filter_map(|r| r.matched_entry.as_ref());
+    if entry.is_book_like() && entry.field("isbn").is_none() {
+        if let Some(isbn) = matched().find_map(|m| m.isbn.clone()) {
+            correct("isbn", isbn);
+        }
+    }
+    if entry.entry_type.eq_ignore_ascii_case("article") && entry.field("issn").is_none() {
+        if let Some(issn) = matched().find_map(|m| m.issn.clone()) {
+            correct("issn", issn);
+        }
+    }
+
+    // The eprint is kept, so the published version still links to the preprint
+    let published = confident
+        .iter()
+        .filter_map(|r| r.matched_entry.as_ref())
+        .find(|matched| is_published_version(entry, matched));
+    if let Some(published) = published {
+        let entry_type = published_entry_type(published);
+        let venue_field = match entry_type {
+            "article" => "journal",
+            _ => "booktitle",
+        };
+        correct(ENTRY_TYPE_FIELD, entry_type.to_string());
+        if let Some(venue) = &published.venue {
+            correct(venue_field, venue.to_string());
+        }
+        if let (Some(year), Some(_)) = (published.year, entry.field("year")) {
+            correct("year", year.to_string());
+        }
+        if let Some(doi) = &published.doi {
+            correct("doi", doi.clone());
+        }
+    }
+
+    // Protection goes on top of any other title correction
+    let title = corrections
+        .iter()
+        .find(|c| c.field == "title")
+        .map(|c| c.new.clone())
+        .or_else(|| entry.field("title").map(str::to_string));
+    if let Some(protected) = title.and_then(|title| protected_title(&title, &titles)) {
+        corrections.retain(|c| c.field != "title");
+        corrections.push(Correction {
+            field: "title".to_string(),
+            old: entry.field("title").map(str::to_string),
+            new: protected,
+        });
+    }
+
+    corrections
+}
+
+/// The title with braces around the words BibTeX's sentence-case styles would lowercase,
+/// cased as in the first canonical title with the same words: acronyms and mixed-case
+/// words (`{BERT}`, `{ImageNet}`), capitalized words when the canonical title is in
+/// sentence case, since those are proper nouns, and math. Words that already have
+/// braces or LaTeX commands are left alone.
+fn protected_title(title: &str, canonical_titles: &[&str]) -> Option<String> {
+    let canonical = canonical_titles
+        .iter()
+        .find(|t| normalize_string(t) == normalize_string(title))?;
+    let words: Vec<&str> = canonical
+        .split(|c: char| !c.is_alphanumeric())
+        .filter(|w| !w.is_empty())
+        .collect();
+    let proper_nouns = is_sentence_case(canonical);
+
+    let mut protected = String::with_capacity(title.len() + 8);
+    let mut next = 0;
+    let mut at_start = true;
+    let mut end = 0;
+    for span in tokens(title) {
+        // Whitespace between the words is kept as it was
+        protected.push_str(&title[end..span.start]);
+        end = span.end;
+        let token = &title[span];
+        if token.contains(['{', '}', '\\']) {
+            protected.push_str(token);
+        } else if token.contains('$') {
+            protected.push_str(&protect_math(token));
+        } else {
+            let mut run = String::new();
+            for c in token.chars().chain(std::iter::once(' ')) {
+                if c.is_alphanumeric() {
+                    run.push(c);
+                    continue;
+                }
+                if !run.is_empty() {
+                    let cased = match_word(&run, &words, &mut next);
+                    let mixed = cased.is_some_and(|w| w.chars().skip(1).any(char::is_uppercase));
+                    let proper = proper_nouns
+                        && !at_start
+                        && cased.is_some_and(|w| w.starts_with(char::is_uppercase));
+                    match cased {
+                        Some(cased) if mixed || proper => {
+                            protected.push_str(&format!("{{{}}}", cased))
+                        }
+                        _ => protected.push_str(&run),
+                    }
+                    at_start = false;
+                    run.clear();
+                }
+                if c != ' ' {
+                    protected.push(c);
+                }
+            }
+        }
+        // Styles keep the first letter after a colon capitalized
+        at_start = token.ends_with([':', '?', '!', '.']);
+    }
+    protected.push_str(&title[end..]);
+    (protected != title).then_some(protected)
+}
+
+/// The spans of a title's words, split at the whitespace outside braces and math
+fn tokens(title: &str) -> Vec<Range<usize>> {
+    let mut tokens = Vec::new();
+    let mut depth = 0;
+    let mut math = false;
+    let mut start = None;
+    for (i, c) in title.char_indices() {
+        match c {
+            '{' => depth += 1,
+            '}' => depth -= 1,
+            '$' if depth == 0 => math = !math,
+            c if c.is_whitespace() && depth == 0 && !math => {
+                if let Some(s) = start.take() {
+                    tokens.push(s..i);
+                }
+                continue;
+            }
+            _ => {}
+        }
+        start.get_or_insert(i);
+    }
+    if let Some(s) = start {
+        tokens.push(s..title.len());
+    }
+    tokens
+}
+
+/// Wrap each `$...$` in braces
+fn protect_math(token: &str) -> String {
+    let parts: Vec<&str> = token.split('$').collect();
+    // An unclosed `$` isn't math to protect
+    if parts.len() % 2 == 0 {
+        return token.to_string();
+    }
+    let mut protected = String::new();
+    for (i, part) in parts.iter().enumerate() {
+        if i % 2 == 1 {
+            protected.push_str(&format!("{{${}$}}", part));
+        } else {
+            protected.push_str(part);
+        }
+    }
+    protected
+}
+
+/// The canonical spelling of a word, looked for a few words past the last one found, so
+/// words the local title spells differently don't lose the place
+fn match_word<'a>(word: &str, words: &[&'a str], next: &mut usize) -> Option<&'a str> {
+    let offset = words
+        .get(*next..)?
+        .iter()
+        .take(8)
+        .position(|w| w.to_lowercase() == word.to_lowercase())?;
+    *next += offset + 1;
+    Some(words[*next - 1])
+}
+
+/// Whether a title is in sentence case: more of its words, besides the first and those
+/// after a colon, start lowercase than uppercase
+fn is_sentence_case(title: &str) -> bool {
+    let (mut lower, mut upper) = (0, 0);
+    let mut at_start = true;
+    for word in title.split_whitespace() {
+        match word.chars().next() {
+            Some(c) if !at_start && c.is_lowercase() => lower += 1,
+            Some(c) if !at_start && c.is_uppercase() => upper += 1,
+            _ => {}
+        }
+        at_start = word.ends_with([':', '?', '!', '.']);
+    }
+    lower > upper
+}
+
+/// The value of a field at least two confident matches report, when none of them
+/// reports a different one. Page ranges are compared and written in BibTeX's
+/// `first--last` form.
+fn agreed_value(field: &str, confident: &[&ValidationResult]) -> Option<String> {
+    let values: Vec<String> = confident
+        .iter()
+        .filter_map(|r| r.matched_entry.as_ref()?.field(field))
+        .map(|value| match field {
+            "pages" => normalize_pages(value).replace('-', "--"),
+            _ => value.trim().to_string(),
+        })
+        .collect();
+    let first = values.first()?;
+    let agreed = values.len() >= 2 && values.iter().all(|v| v.eq_ignore_ascii_case(first));
+    (agreed && !first.is_empty()).then(|| first.clone())
+}
+
+/// The listed fields the entry lacks, taken from the first confident match that has each
+/// (Semantic Scholar and OpenAlex serve abstracts, OpenAlex keywords, Zenodo and DataCite
+/// versions and licenses), as BibTeX text. Versions and licenses are only added to
+/// software and datasets.
+pub fn enrichment(report: &EntryReport, fields: &[&str]) -> Vec<Correction> {
+    let released = matches!(
+        report.entry.kind(),
+        EntryKind::Software | EntryKind::Dataset
+    );
+    let matched: Vec<&Entry> = report
+        .validation_results
+        .iter()
+        .filter(|r| r.confidence >= MIN_CONFIDENCE)
+        .filter_map(|r| r.matched_entry.as_ref())
+        .collect();
+    fields
+        .iter()
+        .filter(|field| released || !matches!(**field, "version" | "license"))
+        .filter(|field| {
+            report
+                .entry
+                .field(field)
+                .is_none_or(|v| v.trim().is_empty())
+        })
+        .filter_map(|field| {
+            let value = matched.iter().find_map(|m| m.field(field))?;
+            Some(Correction {
+                field: field.to_string(),
+                old: None,
+                new: bibtex_text(value),
+            })
+        })
+        .collect()
+}
+
+/// The entry's citation count as its `note`, when it has no note or one an earlier run
+/// wrote, so counts are kept current without overwriting notes of its own
+pub fn citation_note(report: &EntryReport) -> Option<Correction> {
+    let note = capitalize(&crate::report::citation_note(report.citations.as_ref()?));
+    let old = report.entry.field("note");
+    let ours = old.is_none_or(|old| {
+        old.trim().is_empty()
+            || ((old.starts_with("Cited ") || old.starts_with("Not cited "))
+                && old.contains(" according to "))
+    });
+    (ours && old != Some(note.as_str())).then(|| Correction {
+        field: "note".to_string(),
+        old: old.map(str::to_string),
+        new: note,
+    })
+}
+
+/// A legal open-access copy of the work as the `url` of an entry without one
+pub fn open_access_url(report: &EntryReport) -> Option<Correction> {
+    let open_access = report.open_access.as_ref()?;
+    report.entry.field("url").is_none().then(|| Correction {
+        field: "url".to_string(),
+        old: None,
+        new: open_access.url.clone(),
+    })
+}
+
+fn capitalize(text: &str) -> String {
+    let mut chars = text.chars();
+    match chars.next() {
+        Some(first) => first.to_uppercase().chain(chars).collect(),
+        None => String::new(),
+    }
+}
+
+/// Plain text as a BibTeX value: on one line, without HTML markup (`<i>`, `&amp;`), with
+/// LaTeX's special characters escaped and braces, which BibTeX would try to balance,
+/// dropped
+fn bibtex_text(text: &str) -> String {
+    static MARKUP: OnceLock<Regex> = OnceLock::new();
+    let markup = MARKUP.get_or_init(|| {
+        Regex::new(r"</?[a-zA-Z][a-zA-Z0-9:-]*(?:\s[^<>]*)?/?>").expect("valid markup regex")
+    });
+    let text = markup
+        .replace_all(text, "")
+        .replace("&lt;", "<")
+        .replace("&gt;", ">")
+        .replace("&quot;", "\"")
+        .replace("&#39;", "'")
+        .replace("&apos;", "'")
+        .replace("&nbsp;", " ")
+        .replace("&amp;", "&");
+
+    let mut escaped = String::with_capacity(text.len());
+    for word in text.split_whitespace() {
+        if !escaped.is_empty() {
+            escaped.push(' ');
+        }
+        for c in word.chars() {
+            match c {
+                '{' | '}' => {}
+                '\\' => escaped.push_str("\\textbackslash{}"),
+                '&' | '%' | '$' | '#' | '_' => {
+                    escaped.push('\\');
+                    escaped.push(c);
+                }
+                c => escaped.push(c),
+            }
+        }
+    }
+    escaped
+}
+
+/// The entry's known journal or conference respelled in the configured style. This
+/// needs no match, since the spelling comes from the bundled venue database.
+pub fn venue_style(entry: &Entry, config: &LintConfig) -> Option<Correction> {
+    let (field, new) = restyled_venue(entry, config)?;
+    let old = entry.field(field).map(str::to_string);
+    (old.as_deref() != Some(new.as_str())).then(|| Correction {
+        field: field.to_string(),
+        old,
+        new,
+    })
+}
+
+/// The author list with each misspelled name replaced by the spelling all the confident
+/// matches that flag it agree on
+fn agreed_spellings(entry: &Entry, confident: &[&ValidationResult]) -> Option<String> {
+    let mut changed = false;
+    let authors: Vec<String> = entry
+        .authors
+        .iter()
+        .map(|author| {
+            let spellings: Vec<&str> = confident
+                .iter()
+                .filter_map(|r| {
+                    r.discrepancies
+                        .iter()
+                        .find(|d| {
+                            d.field == DiscrepancyField::Authors && d.local_value == author.as_ref()
+                        })
+                        .map(|d| d.remote_value.as_str())
+                })
+                .collect();
+            let agreed = spellings.len() >= confident.len().min(2)
+                && spellings.iter().all(|s| *s == spellings[0]);
+            match spellings.first() {
+                Some(spelling) if agreed => {
+                    changed = true;
+                    spelling.to_string()
+                }
+                _ => author.to_string(),
+            }
+        })
+        .collect();
+    changed.then(|| authors.join(" and "))
+}
+
+/// The casing the matches agree on for a title written in all lowercase or all
+/// uppercase. Titles with braces are left alone, since their casing is protected.
+fn recased_title(entry: &Entry, titles: &[&str]) -> Option<String> {
+    let local = entry.field("title")?;
+    let cased = local.chars().any(|c| c.is_lowercase()) && local.chars().any(|c| c.is_uppercase());
+    if cased || local.contains('{') {
+        return None;
+    }
+    let first = *titles.first()?;
+    let agreed = titles.iter().all(|t| *t == first) && first.to_lowercase() == local.to_lowercase();
+    (agreed && first != local).then(|| first.to_string())
+}
+
+fn join_authors(authors: &[Arc<str>]) -> String {
+    let names: Vec<&str> = authors.iter().map(|a| a.as_ref()).collect();
+    names.join(" and ")
+}
+
+/// Apply corrections, keyed by citation key, to BibTeX source. Only the corrected field
+/// values change; added fields go after an entry's last field, indented like it.
+pub fn apply(
+    content: &str,
+    corrections: &HashMap<String, Vec<Correction>>,
+) -> Result<String, ParseError> {
+    let raw = RawBibliography::parse(content).map_err(|e| ParseError::ParseError(e.to_string()))?;
+
+    let mut edits: Vec<(Range<usize>, String)> = Vec::new();
+    for raw_entry in &raw.entries {
+        let Some(corrections) = corrections.get(raw_entry.v.key.v) else {
+            continue;
+        };
+        let mut added = String::new();
+        for correction in corrections {
+            if correction.field == ENTRY_TYPE_FIELD {
+                edits.push((raw_entry.v.kind.span.clone(), correction.new.clone()));
+                continue;
+            }
+            let pair = raw_entry
+                .v
+                .fields
+                .iter()
+                .find(|pair| pair.key.v.eq_ignore_ascii_case(&correction.field));
+            match pair {
+                Some(pair) => edits.push((
+                    value_span(content, &pair.value.span),
+                    format!("{{{}}}", correction.new),
+                )),
+                None => {
+                    let indent = raw_entry
+                        .v
+                        .fields
+                        .first()
+                        .map(|pair| {
+                            let line_start = content[..pair.key.span.start]
+                                .rfind('\n')
+                                .map_or(0, |i| i + 1);
+                            &content[line_start..pair.key.span.start]
+                        })
+                        .filter(|indent| indent.trim().is_empty())
+                        .unwrap_or("  ");
+                    added.push_str(&format!(
+                        ",\n{}{} = {{{}}}",
+                        indent, correction.field, correction.new
+                    ));
+                }
+            }
+        }
+        if let (false, Some(last)) = (added.is_empty(), raw_entry.v.fields.last()) {
+            let end = value_span(content, &last.value.span).end;
+            edits.push((end..end, added));
+        }
+    }
+
+    // From the end backwards, so earlier spans stay valid
+    edits.sort_by_key(|(range, _)| std::cmp::Reverse(range.start));
+    let mut fixed = content.to_string();
+    for (range, text) in edits {
+        fixed.replace_range(range, &text);
+    }
+    Ok(fixed)
+}
+
+/// Walk through the corrections, keyed by citation key, one at a time like `git add -p`:
+/// show the local and consensus values and ask whether to apply each. Returns the
+/// accepted corrections; running out of input rejects the rest.
+pub fn review(
+    pending: Vec<(String, Vec<Correction>)>,
+    mut input: impl BufRead,
+    mut output: impl Write,
+) -> io::Result<HashMap<String, Vec<Correction>>> {
+    const HELP: &str = "y - apply this change\n\
+                        n - keep the current value\n\
+                        a - apply this and every later change\n\
+                        d - keep the rest of this entry as it is\n\
+                        q - keep this and every later value\n";
+
+    let total: usize = pending
+        .iter()
+        .map(|(_, corrections)| corrections.len())
+        .sum();
+    let mut accepted: HashMap<String, Vec<Correction>> = HashMap::new();
+    let mut number = 0;
+    let mut accept_all = false;
+    'entries: for (key, corrections) in pending {
+        for correction in corrections {
+            number += 1;
+            if !accept_all {
+                writeln!(
+                    output,
+                    "\n{} {} ({}/{})",
+                    format!("[{}]", key).bold(),
+                    correction.field,
+                    number,
+                    total
+                )?;
+                let old = correction.old.as_deref().unwrap_or("(none)");
+                writeln!(output, "  Local:     {}", old.red())?;
+                writeln!(output, "  Consensus: {}", correction.new.green())?;
+            }
+            let answer = loop {
+                if accept_all {
+                    break 'y';
+                }
+                write!(
+                    output,
+                    "{}",
+                    "Apply this change [y,n,a,d,q,?]? ".blue().bold()
+                )?;
+                output.flush()?;
+                let mut line = String::new();
+                if input.read_line(&mut line)? == 0 {
+                    break 'q';
+                }
+                match line.trim() {
+                    "y" | "n" | "d" | "q" => break line.trim().chars().next().unwrap_or('n'),
+                    "a" => {
+                        accept_all = true;
+                        break 'y';
+                    }
+                    _ => write!(output, "{}", HELP)?,
+                }
+            };
+            match answer {
+                'y' => accepted.entry(key.clone()).or_default().push(correction),
+                'd' => continue 'entries,
+                'q' => break 'entries,
+                _ => {}
+            }
+        }
+    }
+    Ok(accepted)
+}
+
+/// The entry's DOI, when a confident match has the same one
+pub fn verified_doi(report: &EntryReport) -> Option<&str> {
+    let doi = report.entry.doi.as_deref()?;
+    let verified = report.validation_results.iter().any(|r| {
+        r.confidence >= MIN_CONFIDENCE
+            && r.matched_entry
+                .as_ref()
+                .and_then(|m| m.doi.as_deref())
+                .is_some_and(|matched| normalize_doi(matched) == normalize_doi(doi))
+    });
+    verified.then_some(doi)
+}
+
+/// Replace whole entries, keyed by citation key, with new BibTeX. Everything between the
+/// entries is kept as it was.
+pub fn regenerate(
+    content: &str,
+    replacements: &HashMap<String, String>,
+) -> Result<String, ParseError> {
+    let raw = RawBibliography::parse(content).map_err(|e| ParseError::ParseError(e.to_string()))?;
+
+    let mut fixed = content.to_string();
+    for raw_entry in raw.entries.iter().rev() {
+        if let Some(bibtex) = replacements.get(raw_entry.v.key.v) {
+            fixed.replace_range(entry_span(content, &raw_entry.span), bibtex);
+        }
+    }
+    Ok(fixed)
+}
+
+/// An entry's span from its `@` through its closing delimiter, which the parser's span
+/// stops short of
+pub(crate) fn entry_span(content: &str, span: &Range<usize>) -> Range<usize> {
+    let rest = content[span.end..].trim_start();
+    match rest.chars().next() {
+        Some(close @ ('}' | ')')) => span.start..content.len() - rest.len() + close.len_utf8(),
+        _ => span.clone(),
+    }
+}
+
+/// Rename entries, from old citation key to new, along with the `crossref` and `xref`
+/// fields that point to them
+pub fn rename_keys(content: &str, renames: &HashMap<String, String>) -> Result<String, ParseError> {
+    let raw = RawBibliography::parse(content).map_err(|e| ParseError::ParseError(e.to_string()))?;
+
+    let mut edits: Vec<(Range<usize>, String)> = Vec::new();
+    for raw_entry in &raw.entries {
+        if let Some(new) = renames.get(raw_entry.v.key.v) {
+            edits.push((raw_entry.v.key.span.clone(), new.clone()));
+        }
+        for pair in &raw_entry.v.fields {
+            if !["crossref", "xref"].contains(&pair.key.v.to_lowercase().as_str()) {
+                continue;
+            }
+            let span = value_span(content, &pair.value.span);
+            let target = content[span.clone()].trim_matches(['{', '}', '"']).trim();
+            if let Some(new) = renames.get(target) {
+                edits.push((span, format!("{{{}}}", new)));
+            }
+        }
+    }
+
+    edits.sort_by_key(|(range, _)| std::cmp::Reverse(range.start));
+    let mut renamed = content.to_string();
+    for (range, text) in edits {
+        renamed.replace_range(range, &text);
+    }
+    Ok(renamed)
+}
+
+/// Write every file or none of them: each is written beside its destination first, and
+/// only moved into place once all have been written
+pub fn write_all(files: &[(PathBuf, String)]) -> io::Result<()> {
+    let staging = |path: &PathBuf| {
+        let mut name = path.file_name().unwrap_or_default().to_os_string();
+        name.push(".bibval-tmp");
+        path.with_file_name(name)
+    };
+
+    let mut staged = Vec::new();
+    for (path, content) in files {
+        let tmp = staging(path);
+        if let Err(e) = std::fs::write(&tmp, content) {
+            for tmp in staged.iter().chain(std::iter::once(&tmp)) {
+                let _ = std::fs::remove_file(tmp);
+            }
+            return Err(e);
+        }
+        staged.push(tmp);
+    }
+    for ((path, _), tmp) in files.iter().zip(&staged) {
+        std::fs::rename(tmp, path)?;
+    }
+    Ok(())
+}
+
+/// A unified diff of the changes to a file, with `a/` and `b/` paths like `git diff`, or
+/// nothing if it is unchanged
+pub fn unified_diff(path: &Path, old: &str, new: &str) -> String {
+    if old == new {
+        return String::new();
+    }
+    let path = path.display();
+    similar::TextDiff::from_lines(old, new)
+        .unified_diff()
+        .context_radius(3)
+        .header(&format!("a/{}", path), &format!("b/{}", path))
+        .to_string()
+}
+
+/// A field value's span without the whitespace after unbraced values
+fn value_span(content: &str, span: &Range<usize>) -> Range<usize> {
+    let value = content[span.clone()].trim_end();
+    span.start..span.start + value.len()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::entry::{ApiSource, Discrepancy};
+    use crate::report::{CitationCount, EntryStatus};
+
+    #[test]
+    fn only_corrected_fields_change() {
+        let content = "% My references\n\
+            @article{smith2020,\n    title = {deep learning},\n    year = 2019,\n    \
+            journal = nature\n}\n\n@misc{other, note = {untouched}}\n";
+        let corrections = HashMap::from([(
+            "smith2020".to_string(),
+            vec![
+                Correction {
+                    field: "title".to_string(),
+                    old: Some("deep learning".to_string()),
+                    new: "Deep Learning".to_string(),
+                },
+                Correction {
+                    field: "year".to_string(),
+                    old: Some("2019".to_string()),
+                    new: "2015".to_string(),
+                },
+                Correction {
+                    field: "doi".to_string(),
+                    old: None,
+                    new: "10.1038/nature14539".to_string(),
+                },
+            ],
+        )]);
+
+        assert_eq!(
+            apply(content, &corrections).unwrap(),
+            "% My references\n\
+             @article{smith2020,\n    title = {Deep Learning},\n    year = {2015},\n    \
+             journal = nature,\n    doi = {10.1038/nature14539}\n}\n\n\
+             @misc{other, note = {untouched}}\n"
+        );
+    }
+
+    #[test]
+    fn regenerated_entries_replace_the_whole_entry() {
+        let content =
+            "@article{smith2020,\n  title = {deep learning}\n}\n% keep\n@misc{other, note = {x}}\n";
+        let replacements = HashMap::from([(
+            "smith2020".to_string(),
+            "@article{smith2020, title={Deep Learning}, year={2015}}".to_string(),
+        )]);
+        assert_eq!(
+            regenerate(content, &replacements).unwrap(),
+            "@article{smith2020, title={Deep Learning}, year={2015}}\n\
+             % keep\n@misc{other, note = {x}}\n"
+        );
+    }
+
+    #[test]
+    fn diffs_show_only_the_changed_lines() {
+        let old = "@article{a,\n  title = {Old},\n  year = {2019}\n}\n";
+        let new = "@article{a,\n  title = {Old},\n  year = {2020}\n}\n";
+        let diff = unified_diff(Path::new("refs.bib"), old, new);
+        assert!(diff.starts_with("--- a/refs.bib\n+++ b/refs.bib\n@@ -1,4 +1,4 @@\n"));
+        assert!(diff.contains("\n-  year = {2019}\n+  year = {2020}\n"));
+        assert!(unified_diff(Path::new("refs.bib"), old, old).is_empty());
+    }
+
+    #[test]
+    fn renamed_keys_carry_their_crossrefs() {
+        let content = "@inproceedings{Smith:2020, title = {A}, crossref = {proc20}}\n\
+            @proceedings{proc20, title = {B}}\n@misc{other, xref = proc20}\n";
+        let renames = HashMap::from([
+            ("Smith:2020".to_string(), "smith2020a".to_string()),
+            ("proc20".to_string(), "icml2020".to_string()),
+        ]);
+        assert_eq!(
+            rename_keys(content, &renames).unwrap(),
+            "@inproceedings{smith2020a, title = {A}, crossref = {icml2020}}\n\
+             @proceedings{icml2020, title = {B}}\n@misc{other, xref = {icml2020}}\n"
+        );
+    }
+
+    #[test]
+    fn reviews_keep_only_accepted_changes() {
+        let correction = |field: &str| Correction {
+            field: field.to_string(),
+            old: Some("old".to_string()),
+            new: "new".to_string(),
+        };
+        let pending = vec![
+            (
+                "a".to_string(),
+                vec![correction("title"), correction("year")],
+            ),
+            (
+                "b".to_string(),
+                vec![correction("title"), correction("year")],
+            ),
+            ("c".to_string(), vec![correction("title")]),
+        ];
+
+        // Reject a's title, accept its year, skip the rest of b, then stop at c
+        let input = io::Cursor::new("n\nwhat\ny\nd\nq\n");
+        let accepted = review(pending.clone(), input, io::sink()).unwrap();
+        assert_eq!(accepted.len(), 1);
+        assert_eq!(accepted["a"], vec![correction("year")]);
+
+        let accepted = review(pending, io::Cursor::new("y\na\n"), io::sink()).unwrap();
+        assert_eq!(accepted.values().map(Vec::len).sum::<usize>(), 5);
+    }
+
+    #[test]
+    fn policies_choose_the_fixed_fields() {
+        let correction = |field: &str| Correction {
+            field: field.to_string(),
+            old: None,
+            new: "new".to_string(),
+        };
+        let all = vec![correction("doi"), correction("year"), correction("title")];
+        assert_eq!(FixPolicy::default().filter(all.clone()), all);
+
+        let policy = FixPolicy {
+            only: Some(vec!["doi".to_string(), "title".to_string()]),
+            except: vec![FixPolicy::field_from_str("Title").unwrap()],
+        };
+        assert_eq!(policy.filter(all), vec![correction("doi")]);
+
+        assert_eq!(FixPolicy::field_from_str("authors").unwrap(), "author");
+        assert_eq!(FixPolicy::field_from_str("type").unwrap(), ENTRY_TYPE_FIELD);
+        assert!(FixPolicy::field_from_str("abstract").is_err());
+    }
+
+    #[test]
+    fn enrichment_adds_only_missing_fields() {
+        let mut entry = Entry::new("a".to_string(), "article".to_string());
+        entry
+            .fields
+            .insert("keywords".to_string(), "mine".to_string());
+        let mut matched = Entry::new("W1".to_string(), "article".to_string());
+        matched.fields.insert(
+            "abstract".to_string(),
+            "We cut\n costs by 50% & {more}.".to_string(),
+        );
+        matched
+            .fields
+            .insert("keywords".to_string(), "theirs".to_string());
+        matched
+            .fields
+            .insert("license".to_string(), "cc-by-4.0".to_string());
+        let mut report = EntryReport {
+            entry,
+            status: EntryStatus::Ok(ApiSource::OpenAlex),
+            validation_results: vec![ValidationResult {
+                source: ApiSource::OpenAlex,
+                matched_entry: Some(matched),
+                confidence: 0.95,
+                discrepancies: Vec::new(),
+            }],
+            lints: Vec::new(),
+            replacement: None,
+            citations: None,
+            open_access: None,
+            code: None,
+            trace: Vec::new(),
+        };
+
+        assert_eq!(
+            enrichment(&report, &["abstract", "keywords"]),
+            vec![Correction {
+                field: "abstract".to_string(),
+                old: None,
+                new: "We cut costs by 50\\% \\& more.".to_string(),
+            }]
+        );
+
+        // Licenses are only added to software and datasets
+        assert!(enrichment(&report, &["license"]).is_empty());
+        report.entry.entry_type = "software".to_string();
+        assert_eq!(enrichment(&report, &["license"])[0].new, "cc-by-4.0");
+    }
+
+    #[test]
+    fn citation_notes_keep_notes_of_their_own() {
+        let report = |note: Option<&str>| {
+            let mut entry = Entry::new("a".to_string(), "article".to_string());
+            if let Some(note) = note {
+                entry.fields.insert("note".to_string(), note.to_string());
+            }
+            EntryReport {
+                entry,
+                status: EntryStatus::Ok(ApiSource::OpenAlex),
+                validation_results: Vec::new(),
+                lints: Vec::new(),
+                replacement: None,
+                citations: Some(CitationCount {
+                    source: ApiSource::OpenAlex,
+                    count: 12,
+                }),
+                open_access: None,
+                code: None,
+                trace: Vec::new(),
+            }
+        };
+        let note = |note| citation_note(&report(note)).map(|c| c.new);
+
+        let current = "Cited 12 times according to OpenAlex";
+        assert_eq!(note(None).as_deref(), Some(current));
+        assert_eq!(
+            note(Some("Cited 3 times according to Semantic Scholar")).as_deref(),
+            Some(current)
+        );
+        assert_eq!(note(Some(current)), None);
+        assert_eq!(note(Some("Invited talk")), None);
+    }
+
+    #[test]
+    fn missing_fields_need_two_agreeing_sources() {
+        let result = |source, fields: &[(&str, &str)]| {
+            let mut matched = Entry::new("m".to_string(), "article".to_string());
+            for (name, value) in fields {
+                matched.fields.insert(name.to_string(), value.to_string());
+            }
+            ValidationResult {
+                source,
+                matched_entry: Some(matched),
+                confidence: 1.0,
+                discrepancies: Vec::new(),
+            }
+        };
+        let mut entry = Entry::new("a".to_string(), "article".to_string());
+        entry.fields.insert("volume".to_string(), "7".to_string());
+        let report = EntryReport {
+            entry,
+            status: EntryStatus::Ok(ApiSource::CrossRef),
+            validation_results: vec![
+                result(
+                    ApiSource::CrossRef,
+                    &[("pages", "101-114"), ("volume", "8"), ("number", "3")],
+                ),
+                result(
+                    ApiSource::Dblp,
+                    &[("pages", "101–114"), ("volume", "8"), ("number", "4")],
+                ),
+                result(ApiSource::OpenAlex, &[("publisher", "Nature")]),
+            ],
+            lints: Vec::new(),
+            replacement: None,
+            citations: None,
+            open_access: None,
+            code: None,
+            trace: Vec::new(),
+        };
+
+        // The volume is already there, the numbers disagree and only one source has a
+        // publisher
+        assert_eq!(
+            corrections(&report),
+            vec![Correction {
+                field: "pages".to_string(),
+                old: None,
+                new: "101--114".to_string(),
+            }]
+        );
+    }
+
+    #[test]
+    fn remote_values_are_written_as_latex() {
+        let matched = |source| {
+            let mut matched = Entry::new("m".to_string(), "article".to_string());
+            matched
+                .fields
+                .insert("publisher".to_string(), "Taylor &amp; Francis".to_string());
+            ValidationResult {
+                source,
+                matched_entry: Some(matched),
+                confidence: 1.0,
+                discrepancies: Vec::new(),
+            }
+        };
+        let consensus = ValidationResult {
+            source: ApiSource::CrossRef,
+            matched_entry: None,
+            confidence: 1.0,
+            discrepancies: vec![Discrepancy {
+                field: DiscrepancyField::Title,
+                severity: Severity::Error,
+                local_value: "Growth of E. coli".to_string(),
+                remote_value: "Growth of <i>E. coli</i> in 5% R&D_labs".to_string(),
+                message: "Title mismatch".to_string(),
+            }],
+        };
+        let mut entry = Entry::new("a".to_string(), "article".to_string());
+        entry
+            .fields
+            .insert("title".to_string(), "Growth of E. coli".to_string());
+        let report = EntryReport {
+            entry,
+            status: EntryStatus::Ok(ApiSource::CrossRef),
+            validation_results: vec![
+                matched(ApiSource::CrossRef),
+                matched(ApiSource::OpenAlex),
+                consensus,
+            ],
+            lints: Vec::new(),
+            replacement: None,
+            citations: None,
+            open_access: None,
+            code: None,
+            trace: Vec::new(),
+        };
+
+        let corrections = corrections(&report);
+        let new = |field| {
+            corrections
+                .iter()
+                .find(|c| c.field == field)
+                .map(|c| c.new.as_str())
+        };
+        assert_eq!(new("title"), Some("Growth of E. coli in 5\\% R\\&D\\_labs"));
+        assert_eq!(new("publisher"), Some("Taylor \\& Francis"));
+    }
+
+    #[test]
+    fn books_gain_isbns_and_articles_issns() {
+        let report = |entry_type: &str, matched: Entry| EntryReport {
+            entry: Entry::new("a".to_string(), entry_type.to_string()),
+            status: EntryStatus::Ok(ApiSource::CrossRef),
+            validation_results: vec![ValidationResult {
+                source: ApiSource::CrossRef,
+                matched_entry: Some(matched),
+                confidence: 1.0,
+                discrepancies: Vec::new(),
+            }],
+            lints: Vec::new(),
+            replacement: None,
+            citations: None,
+            open_access: None,
+            code: None,
+            trace: Vec::new(),
+        };
+        let mut matched = Entry::new("m".to_string(), "book".to_string());
+        matched.isbn = Some("9780262046305".to_string());
+        matched.issn = Some("0028-0836".to_string());
+
+        assert_eq!(
+            corrections(&report("book", matched.clone())),
+            vec![Correction {
+                field: "isbn".to_string(),
+                old: None,
+                new: "9780262046305".to_string(),
+            }]
+        );
+        assert_eq!(
+            corrections(&report("article", matched.clone())),
+            vec![Correction {
+                field: "issn".to_string(),
+                old: None,
+                new: "0028-0836".to_string(),
+            }]
+        );
+        assert!(corrections(&report("misc", matched)).is_empty());
+    }
+
+    #[test]
+    fn published_preprints_are_upgraded() {
+        let mut entry = Entry::new("vaswani2017".to_string(), "misc".to_string());
+        entry.arxiv_id = Some("1706.03762".to_string());
+        entry.year = Some(2017);
+        entry.fields.insert("year".to_string(), "2017".to_string());
+        let mut matched = Entry::new("m".to_string(), "proceedings-article".to_string());
+        matched.venue = Some("Advances in Neural Information Processing Systems".into());
+        matched.year = Some(2017);
+        matched.doi = Some("10.5555/3295222.3295349".to_string());
+        let report = EntryReport {
+            entry,
+            status: EntryStatus::Warning,
+            validation_results: vec![ValidationResult {
+                source: ApiSource::CrossRef,
+                matched_entry: Some(matched),
+                confidence: 0.95,
+                discrepancies: Vec::new(),
+            }],
+            lints: Vec::new(),
+            replacement: None,
+            citations: None,
+            open_access: None,
+            code: None,
+            trace: Vec::new(),
+        };
+
+        let fixes = HashMap::from([("vaswani2017".to_string(), corrections(&report))]);
+        let content = "@misc{vaswani2017,\n  title = {Attention Is All You Need},\n  \
+            year = {2017},\n  eprint = {1706.03762}\n}\n";
+        assert_eq!(
+            apply(content, &fixes).unwrap(),
+            "@inproceedings{vaswani2017,\n  title = {Attention Is All You Need},\n  \
+             year = {2017},\n  eprint = {1706.03762},\n  \
+             booktitle = {Advances in Neural Information Processing Systems},\n  \
+             doi = {10.5555/3295222.3295349}\n}\n"
+        );
+    }
+
+    #[test]
+    fn titles_protect_acronyms_proper_nouns_and_math() {
+        let canonical = "BERT: Pre-training of deep transformers for English on GPUs in $O(n)$";
+        assert_eq!(
+            protected_title(
+                "Bert: pre-training of deep transformers\n    for English on gpus in $O(n)$",
+                &[canonical]
+            )
+            .as_deref(),
+            Some(
+                "{BERT}: pre-training of deep transformers\n    for {English} on {GPUs} in {$O(n)$}"
+            )
+        );
+
+        // In title case, capitals say nothing about proper nouns
+        assert_eq!(
+            protected_title(
+                "Attention Is All You Need for {ImageNet}",
+                &["Attention Is All You Need for ImageNet"]
+            ),
+            None
+        );
+        assert_eq!(protected_title("Other words", &[canonical]), None);
+    }
+
+    #[test]
+    fn lowercase_titles_take_the_agreed_casing() {
+        let mut entry = Entry::new("a".to_string(), "article".to_string());
+        entry
+            .fields
+            .insert("title".to_string(), "deep learning".to_string());
+        assert_eq!(
+            recased_title(&entry, &["Deep Learning", "Deep Learning"]).as_deref(),
+            Some("Deep Learning")
+        );
+        assert_eq!(
+            recased_title(&entry, &["Deep Learning", "Deep learning"]),
+            None
+        );
+
+        entry
+            .fields
+            .insert("title".to_string(), "Deep learning".to_string());
+        assert_eq!(recased_title(&entry, &["Deep Learning"]), None);
+    }
+}
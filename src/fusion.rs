@@ -1,5 +1,11 @@
 use crate::entry::{ApiSource, Discrepancy, DiscrepancyField, Entry, Severity, ValidationResult};
+use crate::matcher::normalize_pages;
+use crate::parser::parse_bib_string;
+use crate::report::EntryReport;
+use crate::validators::acm::is_acm_doi;
+use crate::validators::springer::is_springer_doi;
 use std::collections::HashMap;
+use std::sync::Arc;
 
 /// Fused validation result after combining multiple validator responses
 pub struct FusedResult {
@@ -43,6 +49,11 @@ pub fn fuse_results(local: &Entry, results: &[ValidationResult]) -> FusedResult
     // Fuse author information
     fused_discrepancies.extend(fuse_authors(local, &valid_results));
 
+    // Fuse page ranges
+    if let Some(discrepancy) = fuse_pages(local, &valid_results) {
+        fused_discrepancies.push(discrepancy);
+    }
+
     // Check for missing DOI (any validator reporting it is enough)
     if let Some(discrepancy) = check_missing_doi(local, &valid_results) {
         fused_discrepancies.push(discrepancy);
@@ -55,10 +66,46 @@ pub fn fuse_results(local: &Entry, results: &[ValidationResult]) -> FusedResult
     }
 }
 
-/// Fuse year information - only report if majority agrees
+/// The source whose record is authoritative for an entry: the publisher that registered
+/// its DOI, when it can be queried directly
+fn authoritative_source(local: &Entry) -> Option<ApiSource> {
+    match &local.doi {
+        Some(doi) if is_acm_doi(doi) => Some(ApiSource::Acm),
+        Some(doi) if is_springer_doi(doi) => Some(ApiSource::Springer),
+        _ => None,
+    }
+}
+
+/// The authoritative source's result, if it found the entry
+fn authoritative_result<'a>(
+    local: &Entry,
+    results: &[&'a ValidationResult],
+) -> Option<&'a ValidationResult> {
+    let source = authoritative_source(local)?;
+    results.iter().find(|r| r.source == source).copied()
+}
+
+/// Fuse year information - only report if majority agrees, unless the DOI's publisher
+/// has a record
 fn fuse_year(local: &Entry, results: &[&ValidationResult]) -> Option<Discrepancy> {
     let local_year = local.year?;
 
+    // The publisher's record settles the year, however the other databases vote
+    if let Some(result) = authoritative_result(local, results) {
+        if let Some(year) = result.matched_entry.as_ref().and_then(|e| e.year) {
+            return (year != local_year).then(|| Discrepancy {
+                field: DiscrepancyField::Year,
+                severity: Severity::Error,
+                local_value: local_year.to_string(),
+                remote_value: year.to_string(),
+                message: format!(
+                    "Year mismatch: {} vs {} (per {}, the DOI's publisher)",
+                    local_year, year, result.source
+                ),
+            });
+        }
+    }
+
     // Collect years from all matched entries
     let mut year_counts: HashMap<i32, Vec<ApiSource>> = HashMap::new();
     for result in results {
@@ -184,6 +231,37 @@ fn fuse_authors(local: &Entry, results: &[&ValidationResult]) -> Vec<Discrepancy
     discrepancies
 }
 
+/// Fuse page ranges - the DOI's publisher decides when it has a record, otherwise at
+/// least 2 validators must agree on the range
+fn fuse_pages(local: &Entry, results: &[&ValidationResult]) -> Option<Discrepancy> {
+    let page_issue = |result: &ValidationResult| {
+        result
+            .discrepancies
+            .iter()
+            .find(|d| d.field == DiscrepancyField::Pages)
+            .cloned()
+    };
+
+    if let Some(result) = authoritative_result(local, results) {
+        return page_issue(result).map(|d| Discrepancy {
+            message: format!("{} (per {}, the DOI's publisher)", d.message, result.source),
+            ..d
+        });
+    }
+
+    let mut by_range: HashMap<String, Vec<Discrepancy>> = HashMap::new();
+    for discrepancy in results.iter().filter_map(|r| page_issue(r)) {
+        by_range
+            .entry(normalize_pages(&discrepancy.remote_value))
+            .or_default()
+            .push(discrepancy);
+    }
+    let (_, agreeing) = by_range.into_iter().max_by_key(|(_, d)| d.len())?;
+
+    let min_agreement = if results.len() == 1 { 1 } else { 2 };
+    (agreeing.len() >= min_agreement).then(|| agreeing[0].clone())
+}
+
 /// Check if DOI is missing locally but present in any remote entry
 fn check_missing_doi(local: &Entry, results: &[&ValidationResult]) -> Option<Discrepancy> {
     if local.doi.is_some() {
@@ -208,9 +286,77 @@ fn check_missing_doi(local: &Entry, results: &[&ValidationResult]) -> Option<Dis
     None
 }
 
+/// The entry of a report with the consensus of the validators applied: a curated
+/// replacement record is used when there is one; otherwise titles and years the
+/// validators agree are wrong are replaced, authors are taken from the most confident
+/// match when their count is wrong, and a missing DOI is filled in
+pub fn clean_entry(report: &EntryReport) -> Entry {
+    let replacement = report
+        .replacement
+        .as_ref()
+        .and_then(|r| parse_bib_string(&r.bibtex).ok()?.into_iter().next());
+    if let Some(entry) = replacement {
+        return entry;
+    }
+
+    let mut entry = report.entry.clone();
+    let Some(fused) = report
+        .validation_results
+        .iter()
+        .find(|result| result.matched_entry.is_none())
+    else {
+        return entry;
+    };
+
+    for discrepancy in &fused.discrepancies {
+        let value = discrepancy.remote_value.clone();
+        match discrepancy.field {
+            DiscrepancyField::Title if discrepancy.severity == Severity::Error => {
+                entry.title = Some(Arc::from(value.as_str()));
+                entry.fields.insert("title".to_string(), value);
+            }
+            DiscrepancyField::Year if discrepancy.severity == Severity::Error => {
+                if let Ok(year) = value.parse() {
+                    entry.year = Some(year);
+                    entry.fields.insert("year".to_string(), value);
+                }
+            }
+            DiscrepancyField::Authors => {
+                if let Some(authors) = consensus_authors(report, &value) {
+                    let names: Vec<&str> = authors.iter().map(|a| a.as_ref()).collect();
+                    entry
+                        .fields
+                        .insert("author".to_string(), names.join(" and "));
+                    entry.authors = authors;
+                }
+            }
+            DiscrepancyField::Doi if entry.doi.is_none() => {
+                entry.doi = Some(value.clone());
+                entry.fields.insert("doi".to_string(), value);
+            }
+            _ => {}
+        }
+    }
+    entry
+}
+
+/// The authors of the most confident match with as many authors as the consensus
+/// ("3 authors") says there are
+pub(crate) fn consensus_authors(report: &EntryReport, count: &str) -> Option<Vec<Arc<str>>> {
+    let count: usize = count.strip_suffix(" authors")?.parse().ok()?;
+    report
+        .validation_results
+        .iter()
+        .filter_map(|r| Some((r.confidence, r.matched_entry.as_ref()?)))
+        .filter(|(_, matched)| matched.authors.len() == count)
+        .max_by(|a, b| a.0.total_cmp(&b.0))
+        .map(|(_, matched)| matched.authors.clone())
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::report::EntryStatus;
 
     fn make_result(source: ApiSource, year: Option<i32>) -> ValidationResult {
         let mut entry = Entry::new("test".to_string(), "article".to_string());
@@ -228,7 +374,7 @@ mod tests {
         let mut local = Entry::new("test".to_string(), "article".to_string());
         local.year = Some(2020);
 
-        let results = vec![
+        let results = [
             make_result(ApiSource::CrossRef, Some(2019)),
             make_result(ApiSource::Dblp, Some(2019)),
             make_result(ApiSource::SemanticScholar, Some(2020)),
@@ -243,12 +389,33 @@ mod tests {
         assert_eq!(d.remote_value, "2019");
     }
 
+    #[test]
+    fn publisher_records_outweigh_the_majority() {
+        let mut local = Entry::new("test".to_string(), "inproceedings".to_string());
+        local.year = Some(2019);
+        local.doi = Some("10.1145/3313831.3376727".to_string());
+
+        let results = [
+            make_result(ApiSource::Acm, Some(2020)),
+            make_result(ApiSource::Dblp, Some(2019)),
+            make_result(ApiSource::SemanticScholar, Some(2019)),
+        ];
+
+        let refs: Vec<_> = results.iter().collect();
+        let discrepancy = fuse_year(&local, &refs).unwrap();
+        assert_eq!(discrepancy.remote_value, "2020");
+        assert!(discrepancy.message.contains("ACM DL"));
+
+        local.doi = Some("10.1000/other".to_string());
+        assert!(fuse_year(&local, &refs).is_none());
+    }
+
     #[test]
     fn test_no_consensus_no_error() {
         let mut local = Entry::new("test".to_string(), "article".to_string());
         local.year = Some(2020);
 
-        let results = vec![
+        let results = [
             make_result(ApiSource::CrossRef, Some(2019)),
             make_result(ApiSource::Dblp, Some(2018)),
             make_result(ApiSource::SemanticScholar, Some(2020)),
@@ -260,4 +427,58 @@ mod tests {
         // No consensus (all different years) - shouldn't report error
         assert!(discrepancy.is_none());
     }
+
+    #[test]
+    fn clean_entries_take_the_consensus() {
+        let mut entry = Entry::new("smith2020".to_string(), "article".to_string());
+        entry.title = Some("Deep Thngs".into());
+        entry
+            .fields
+            .insert("title".to_string(), "Deep Thngs".to_string());
+
+        let discrepancy = |field, severity, remote: &str| Discrepancy {
+            field,
+            severity,
+            local_value: String::new(),
+            remote_value: remote.to_string(),
+            message: String::new(),
+        };
+        let mut matched = Entry::new("x".to_string(), "article".to_string());
+        matched.authors = vec!["Ann Smith".into(), "Bo Lee".into()];
+        let report = EntryReport {
+            entry,
+            status: EntryStatus::Error,
+            validation_results: vec![
+                ValidationResult {
+                    source: ApiSource::Dblp,
+                    matched_entry: Some(matched),
+                    confidence: 0.8,
+                    discrepancies: Vec::new(),
+                },
+                ValidationResult {
+                    source: ApiSource::CrossRef,
+                    matched_entry: None,
+                    confidence: 0.9,
+                    discrepancies: vec![
+                        discrepancy(DiscrepancyField::Title, Severity::Error, "Deep Things"),
+                        discrepancy(DiscrepancyField::Year, Severity::Warning, "2021"),
+                        discrepancy(DiscrepancyField::Doi, Severity::Warning, "10.1000/x"),
+                        discrepancy(DiscrepancyField::Authors, Severity::Warning, "2 authors"),
+                    ],
+                },
+            ],
+            lints: Vec::new(),
+            replacement: None,
+            citations: None,
+            open_access: None,
+            code: None,
+            trace: Vec::new(),
+        };
+
+        let clean = clean_entry(&report);
+        assert_eq!(clean.field("title"), Some("Deep Things"));
+        assert_eq!(clean.field("year"), None);
+        assert_eq!(clean.doi.as_deref(), Some("10.1000/x"));
+        assert_eq!(clean.field("author"), Some("Ann Smith and Bo Lee"));
+    }
 }
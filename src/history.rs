@@ -0,0 +1,617 @@
+//! Persisted history of validation runs, for spotting regressions over time
+
+use crate::checkpoint::input_set_id;
+use crate::entry::{Discrepancy, DiscrepancyField, Severity};
+use crate::report::{EntryStatus, Report, Summary};
+use crate::score::QualityScore;
+use chrono::{DateTime, Duration, Utc};
+use colored::Colorize;
+use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, HashSet};
+use std::fs::{self, File, OpenOptions};
+use std::io::{BufRead, BufReader, Write};
+use std::path::{Path, PathBuf};
+use thiserror::Error;
+
+#[derive(Error, Debug)]
+pub enum HistoryError {
+    #[error("IO error: {0}")]
+    Io(#[from] std::io::Error),
+    #[error("Serialization error: {0}")]
+    Serialization(#[from] serde_json::Error),
+}
+
+/// A finding recorded for an entry
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct Issue {
+    pub field: DiscrepancyField,
+    pub severity: Severity,
+    pub message: String,
+}
+
+impl From<&Discrepancy> for Issue {
+    fn from(d: &Discrepancy) -> Self {
+        Self {
+            field: d.field,
+            severity: d.severity,
+            message: d.message.clone(),
+        }
+    }
+}
+
+/// What one run found for an entry
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct EntrySnapshot {
+    pub key: String,
+    pub status: EntryStatus,
+    /// Warnings and errors from offline checks
+    pub lints: Vec<Issue>,
+    /// Warnings and errors from database and URL lookups
+    pub findings: Vec<Issue>,
+}
+
+/// One line of the history file
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RunRecord {
+    pub timestamp: DateTime<Utc>,
+    pub summary: Summary,
+    #[serde(default)]
+    pub score: Option<QualityScore>,
+    pub entries: Vec<EntrySnapshot>,
+}
+
+impl RunRecord {
+    pub fn from_report(report: &Report, timestamp: DateTime<Utc>) -> Self {
+        let issues = |findings: &mut dyn Iterator<Item = &Discrepancy>| -> Vec<Issue> {
+            let mut seen = HashSet::new();
+            findings
+                .filter(|d| d.severity > Severity::Info)
+                .map(Issue::from)
+                .filter(|issue| seen.insert(issue.clone()))
+                .collect()
+        };
+
+        let entries = report
+            .entries
+            .iter()
+            .map(|r| {
+                let mut findings = r.validation_results.iter().flat_map(|v| &v.discrepancies);
+                EntrySnapshot {
+                    key: r.entry.key.clone(),
+                    status: r.status.clone(),
+                    lints: issues(&mut r.lints.iter()),
+                    findings: issues(&mut findings),
+                }
+            })
+            .collect();
+
+        Self {
+            timestamp,
+            summary: report.summary(),
+            score: report.score(),
+            entries,
+        }
+    }
+}
+
+/// Append-only file of run records, one JSON object per line
+pub struct History {
+    path: PathBuf,
+}
+
+impl History {
+    /// Default history location for a set of input files, under the data directory
+    pub fn default_path(files: &[PathBuf]) -> PathBuf {
+        dirs::data_dir()
+            .unwrap_or_else(|| PathBuf::from(".local/share"))
+            .join("bibval")
+            .join("history")
+            .join(format!("{}.ndjson", input_set_id(files)))
+    }
+
+    pub fn new(path: &Path) -> Self {
+        Self {
+            path: path.to_path_buf(),
+        }
+    }
+
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// Earlier runs, oldest first; a missing file is an empty history
+    pub fn load(&self) -> Result<Vec<RunRecord>, HistoryError> {
+        let file = match File::open(&self.path) {
+            Ok(file) => file,
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(Vec::new()),
+            Err(e) => return Err(e.into()),
+        };
+
+        let mut records = Vec::new();
+        for line in BufReader::new(file).lines() {
+            let line = line?;
+            if line.trim().is_empty() {
+                continue;
+            }
+            records.push(serde_json::from_str(&line)?);
+        }
+        Ok(records)
+    }
+
+    pub fn append(&self, record: &RunRecord) -> Result<(), HistoryError> {
+        if let Some(parent) = self.path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        let mut file = OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(&self.path)?;
+        let mut line = serde_json::to_string(record)?;
+        line.push('\n');
+        file.write_all(line.as_bytes())?;
+        Ok(())
+    }
+}
+
+/// The run to compare against: the latest one at least `min_age` before `now`, or
+/// simply the latest one
+pub fn baseline(
+    records: &[RunRecord],
+    now: DateTime<Utc>,
+    min_age: Option<Duration>,
+) -> Option<&RunRecord> {
+    let cutoff = now - min_age.unwrap_or_else(Duration::zero);
+    records.iter().rev().find(|r| r.timestamp <= cutoff)
+}
+
+/// Parse an age such as `30d`, `4w` or `12h`
+pub fn parse_age(text: &str) -> Option<Duration> {
+    let text = text.trim();
+    let split = text.find(|c: char| !c.is_ascii_digit())?;
+    let (number, unit) = text.split_at(split);
+    let number: i64 = number.parse().ok()?;
+    match unit {
+        "h" => Duration::try_hours(number),
+        "d" => Duration::try_days(number),
+        "w" => Duration::try_weeks(number),
+        _ => None,
+    }
+}
+
+/// An issue that a run found but its baseline did not
+#[derive(Debug, Clone, Serialize)]
+pub struct NewIssue {
+    pub key: String,
+    #[serde(flatten)]
+    pub issue: Issue,
+}
+
+/// How the current run differs from a baseline run
+#[derive(Debug, Clone, Serialize)]
+pub struct Trends {
+    pub since: DateTime<Utc>,
+    pub before: Summary,
+    pub after: Summary,
+    pub score_before: Option<u8>,
+    pub score_after: Option<u8>,
+    /// Entries that have errors now but didn't before
+    pub new_errors: Vec<String>,
+    /// URLs that have stopped resolving
+    pub dead_links: Vec<NewIssue>,
+    /// Every other new warning or error
+    pub new_issues: Vec<NewIssue>,
+    /// Entries that had errors before but don't now
+    pub fixed: Vec<String>,
+}
+
+impl Trends {
+    pub fn compare(baseline: &RunRecord, current: &RunRecord) -> Self {
+        let before: HashMap<&str, &EntrySnapshot> = baseline
+            .entries
+            .iter()
+            .map(|e| (e.key.as_str(), e))
+            .collect();
+        let is_error = |s: &EntrySnapshot| matches!(s.status, EntryStatus::Error);
+
+        let mut trends = Self {
+            since: baseline.timestamp,
+            before: baseline.summary,
+            after: current.summary,
+            score_before: baseline.score.map(|s| s.overall),
+            score_after: current.score.map(|s| s.overall),
+            new_errors: Vec::new(),
+            dead_links: Vec::new(),
+            new_issues: Vec::new(),
+            fixed: Vec::new(),
+        };
+
+        for entry in &current.entries {
+            let previous = before.get(entry.key.as_str());
+            if is_error(entry) && !previous.is_some_and(|p| is_error(p)) {
+                trends.new_errors.push(entry.key.clone());
+            }
+            if !is_error(entry) && previous.is_some_and(|p| is_error(p)) {
+                trends.fixed.push(entry.key.clone());
+            }
+
+            let known: HashSet<&Issue> = previous
+                .map(|p| p.lints.iter().chain(&p.findings).collect())
+                .unwrap_or_default();
+            for issue in &entry.findings {
+                if known.contains(issue) {
+                    continue;
+                }
+                let new = NewIssue {
+                    key: entry.key.clone(),
+                    issue: issue.clone(),
+                };
+                if issue.field == DiscrepancyField::Url && issue.severity == Severity::Error {
+                    trends.dead_links.push(new);
+                } else {
+                    trends.new_issues.push(new);
+                }
+            }
+            for issue in entry.lints.iter().filter(|i| !known.contains(i)) {
+                trends.new_issues.push(NewIssue {
+                    key: entry.key.clone(),
+                    issue: issue.clone(),
+                });
+            }
+        }
+        trends
+    }
+
+    /// Whether anything got worse; what a scheduled run should alert on
+    pub fn has_regressions(&self) -> bool {
+        !self.new_errors.is_empty() || !self.dead_links.is_empty() || !self.new_issues.is_empty()
+    }
+
+    /// Print the trends to stdout with colors
+    pub fn print(&self) {
+        println!();
+        println!("{}", "bibval Monitor".bold());
+        println!("{}", "=".repeat(50));
+        println!();
+        println!("Since {}:", self.since.format("%Y-%m-%d %H:%M UTC"));
+        println!(
+            "  errors {} -> {}, warnings {} -> {}",
+            self.before.errors,
+            self.after.errors.to_string().red(),
+            self.before.warnings,
+            self.after.warnings.to_string().yellow()
+        );
+        if let (Some(before), Some(after)) = (self.score_before, self.score_after) {
+            println!("  quality score {} -> {}", before, after);
+        }
+        println!();
+
+        if !self.new_errors.is_empty() {
+            println!(
+                "{}",
+                format!("NEW ERRORS ({})", self.new_errors.len())
+                    .red()
+                    .bold()
+            );
+            for key in &self.new_errors {
+                println!("  [{}]", key.cyan());
+            }
+            println!();
+        }
+
+        let sections = [
+            ("DEAD LINKS", &self.dead_links),
+            ("NEW ISSUES", &self.new_issues),
+        ];
+        for (title, issues) in sections {
+            if issues.is_empty() {
+                continue;
+            }
+            println!(
+                "{}",
+                format!("{} ({})", title, issues.len()).yellow().bold()
+            );
+            for new in issues {
+                let severity = match new.issue.severity {
+                    Severity::Error => "ERROR".red(),
+                    _ => "WARN".yellow(),
+                };
+                println!("  [{}] {} {}", new.key.cyan(), severity, new.issue.message);
+            }
+            println!();
+        }
+
+        if !self.fixed.is_empty() {
+            println!("{}", format!("FIXED ({})", self.fixed.len()).green().bold());
+            for key in &self.fixed {
+                println!("  [{}]", key.cyan());
+            }
+            println!();
+        }
+    }
+}
+
+/// An issue of one entry
+#[derive(Debug, Clone, Serialize)]
+pub struct EntryIssue {
+    pub key: String,
+    #[serde(flatten)]
+    pub issue: Issue,
+}
+
+/// An issue whose field is still flagged, but differently
+#[derive(Debug, Clone, Serialize)]
+pub struct ChangedIssue {
+    pub key: String,
+    pub before: Issue,
+    pub after: Issue,
+}
+
+/// How a run's issues differ from those of an earlier report, for `--compare-to`
+#[derive(Debug, Clone, Serialize)]
+pub struct Comparison {
+    pub before: Summary,
+    pub after: Summary,
+    /// Issues of fields that weren't flagged before, including those of new entries
+    pub new: Vec<EntryIssue>,
+    /// Issues of fields that aren't flagged any more; entries that were removed don't count
+    pub fixed: Vec<EntryIssue>,
+    pub changed: Vec<ChangedIssue>,
+}
+
+impl Comparison {
+    pub fn between(before: &RunRecord, after: &RunRecord) -> Self {
+        let previous: HashMap<&str, &EntrySnapshot> =
+            before.entries.iter().map(|e| (e.key.as_str(), e)).collect();
+
+        let mut comparison = Self {
+            before: before.summary,
+            after: after.summary,
+            new: Vec::new(),
+            fixed: Vec::new(),
+            changed: Vec::new(),
+        };
+        for entry in &after.entries {
+            let issues_before: Vec<&Issue> = previous
+                .get(entry.key.as_str())
+                .map(|p| p.lints.iter().chain(&p.findings).collect())
+                .unwrap_or_default();
+            let issues_after: Vec<&Issue> = entry.lints.iter().chain(&entry.findings).collect();
+
+            let mut gone: Vec<&Issue> = issues_before
+                .iter()
+                .filter(|i| !issues_after.contains(i))
+                .copied()
+                .collect();
+            for issue in issues_after.iter().filter(|i| !issues_before.contains(i)) {
+                // A flagged field whose message or severity changed is one changed issue
+                match gone.iter().position(|g| g.field == issue.field) {
+                    Some(i) => comparison.changed.push(ChangedIssue {
+                        key: entry.key.clone(),
+                        before: gone.remove(i).clone(),
+                        after: (*issue).clone(),
+                    }),
+                    None => comparison.new.push(EntryIssue {
+                        key: entry.key.clone(),
+                        issue: (*issue).clone(),
+                    }),
+                }
+            }
+            comparison
+                .fixed
+                .extend(gone.into_iter().map(|issue| EntryIssue {
+                    key: entry.key.clone(),
+                    issue: issue.clone(),
+                }));
+        }
+        comparison
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.new.is_empty() && self.fixed.is_empty() && self.changed.is_empty()
+    }
+
+    /// Print the comparison to stdout with colors
+    pub fn print(&self, against: &Path) {
+        println!();
+        println!("{}", "bibval Comparison".bold());
+        println!("{}", "=".repeat(50));
+        println!();
+        println!("Against {}:", against.display());
+        println!(
+            "  errors {} -> {}, warnings {} -> {}",
+            self.before.errors,
+            self.after.errors.to_string().red(),
+            self.before.warnings,
+            self.after.warnings.to_string().yellow()
+        );
+        println!();
+
+        if self.is_empty() {
+            println!("No issues were added, fixed or changed");
+            println!();
+            return;
+        }
+
+        let severity = |severity: Severity| match severity {
+            Severity::Error => "ERROR".red(),
+            _ => "WARN".yellow(),
+        };
+        let sections = [
+            (format!("NEW ({})", self.new.len()).red().bold(), &self.new),
+            (
+                format!("FIXED ({})", self.fixed.len()).green().bold(),
+                &self.fixed,
+            ),
+        ];
+        for (title, issues) in sections {
+            if issues.is_empty() {
+                continue;
+            }
+            println!("{}", title);
+            for entry in issues {
+                println!(
+                    "  [{}] {} {}",
+                    entry.key.cyan(),
+                    severity(entry.issue.severity),
+                    entry.issue.message
+                );
+            }
+            println!();
+        }
+
+        if !self.changed.is_empty() {
+            println!(
+                "{}",
+                format!("CHANGED ({})", self.changed.len()).yellow().bold()
+            );
+            for change in &self.changed {
+                println!(
+                    "  [{}] {} {}",
+                    change.key.cyan(),
+                    severity(change.before.severity),
+                    change.before.message.dimmed()
+                );
+                println!(
+                    "  {} {} {}",
+                    " ".repeat(change.key.len() + 2),
+                    severity(change.after.severity),
+                    change.after.message
+                );
+            }
+            println!();
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn record(days_ago: i64, entries: Vec<EntrySnapshot>) -> RunRecord {
+        let summary = Summary {
+            total: entries.len(),
+            ok: 0,
+            warnings: 0,
+            errors: 0,
+            not_found: 0,
+            failed: 0,
+        };
+        RunRecord {
+            timestamp: Utc::now() - Duration::days(days_ago),
+            summary,
+            score: None,
+            entries,
+        }
+    }
+
+    fn snapshot(key: &str, status: EntryStatus, findings: Vec<Issue>) -> EntrySnapshot {
+        EntrySnapshot {
+            key: key.to_string(),
+            status,
+            lints: Vec::new(),
+            findings,
+        }
+    }
+
+    #[test]
+    fn regressions_are_found_against_the_baseline() {
+        let dead = Issue {
+            field: DiscrepancyField::Url,
+            severity: Severity::Error,
+            message: "URL is dead (404)".to_string(),
+        };
+        let old = record(
+            40,
+            vec![
+                snapshot("a", EntryStatus::NotFound, vec![]),
+                snapshot("b", EntryStatus::Error, vec![]),
+            ],
+        );
+        let new = record(
+            0,
+            vec![
+                snapshot("a", EntryStatus::Error, vec![dead]),
+                snapshot("b", EntryStatus::NotFound, vec![]),
+            ],
+        );
+
+        let trends = Trends::compare(&old, &new);
+        assert_eq!(trends.new_errors, ["a"]);
+        assert_eq!(trends.dead_links.len(), 1);
+        assert_eq!(trends.fixed, ["b"]);
+        assert!(trends.has_regressions());
+        assert!(!Trends::compare(&new, &new).has_regressions());
+    }
+
+    #[test]
+    fn comparisons_pair_up_issues_by_field() {
+        let issue = |field, severity, message: &str| Issue {
+            field,
+            severity,
+            message: message.to_string(),
+        };
+        let year = issue(
+            DiscrepancyField::Year,
+            Severity::Error,
+            "Year mismatch: 2019 vs 2018",
+        );
+        let other_year = issue(
+            DiscrepancyField::Year,
+            Severity::Error,
+            "Year mismatch: 2017",
+        );
+        let title = issue(DiscrepancyField::Title, Severity::Warning, "Title differs");
+        let venue = issue(DiscrepancyField::Venue, Severity::Warning, "Venue differs");
+        let old = record(
+            1,
+            vec![
+                snapshot("a", EntryStatus::Error, vec![year.clone(), title.clone()]),
+                snapshot("gone", EntryStatus::Error, vec![year.clone()]),
+            ],
+        );
+        let new = record(
+            0,
+            vec![
+                snapshot("a", EntryStatus::Error, vec![other_year, venue]),
+                snapshot("b", EntryStatus::Warning, vec![title.clone()]),
+            ],
+        );
+
+        let comparison = Comparison::between(&old, &new);
+        let keyed = |issues: &[EntryIssue]| -> Vec<(String, DiscrepancyField)> {
+            issues
+                .iter()
+                .map(|i| (i.key.clone(), i.issue.field))
+                .collect()
+        };
+        assert_eq!(
+            keyed(&comparison.new),
+            [
+                ("a".to_string(), DiscrepancyField::Venue),
+                ("b".to_string(), DiscrepancyField::Title)
+            ]
+        );
+        assert_eq!(
+            keyed(&comparison.fixed),
+            [("a".to_string(), DiscrepancyField::Title)]
+        );
+        assert_eq!(comparison.changed.len(), 1);
+        assert_eq!(comparison.changed[0].before, year);
+        assert!(Comparison::between(&new, &new).is_empty());
+    }
+
+    #[test]
+    fn baselines_respect_a_minimum_age() {
+        let records = vec![record(40, vec![]), record(10, vec![]), record(1, vec![])];
+        let now = Utc::now();
+
+        let latest = baseline(&records, now, None).unwrap();
+        assert_eq!(latest.timestamp, records[2].timestamp);
+        let month_old = baseline(&records, now, parse_age("30d")).unwrap();
+        assert_eq!(month_old.timestamp, records[0].timestamp);
+        assert!(baseline(&records, now, parse_age("8w")).is_none());
+
+        assert_eq!(parse_age("12h"), Some(Duration::hours(12)));
+        assert_eq!(parse_age("month"), None);
+    }
+}
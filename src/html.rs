@@ -0,0 +1,357 @@
+//! Self-contained HTML report, filterable by status, severity and source
+
+use crate::entry::{Discrepancy, Entry, Severity};
+use crate::report::{EntryReport, EntryStatus, Report};
+use quick_xml::escape::escape;
+use std::collections::BTreeSet;
+use std::fmt::Write;
+
+const STYLE: &str = r#"
+body { font: 14px/1.4 system-ui, sans-serif; margin: 2em; color: #222; }
+h1 { margin-bottom: 0.2em; }
+.summary span { margin-right: 1.2em; }
+.filters { margin: 1em 0; display: flex; gap: 1em; flex-wrap: wrap; }
+table { border-collapse: collapse; width: 100%; }
+th, td { text-align: left; padding: 0.3em 0.6em; vertical-align: top; }
+thead th { border-bottom: 2px solid #ccc; }
+tbody.entry > tr.row { cursor: pointer; border-top: 1px solid #eee; }
+tbody.entry > tr.row:hover { background: #f6f6f6; }
+tr.detail > td { background: #fafafa; padding: 0.6em 1.2em 1em; }
+tr.detail table td, tr.detail table th { border-bottom: 1px solid #eee; }
+.ok { color: #2a7d2a; } .warning { color: #a66b00; } .error, .failed { color: #c22; }
+.not-found, .info, .none { color: #777; }
+.key { font-family: monospace; }
+pre { background: #f0f0f0; padding: 0.6em; overflow-x: auto; }
+"#;
+
+const SCRIPT: &str = r#"
+const filters = ["status", "severity", "source"].map(id => document.getElementById(id));
+const search = document.getElementById("search");
+function apply() {
+  const query = search.value.toLowerCase();
+  for (const entry of document.querySelectorAll("tbody.entry")) {
+    const d = entry.dataset;
+    const visible = (!filters[0].value || d.status === filters[0].value)
+      && (!filters[1].value || d.severity === filters[1].value)
+      && (!filters[2].value || d.sources.split("|").includes(filters[2].value))
+      && (!query || entry.textContent.toLowerCase().includes(query));
+    entry.hidden = !visible;
+  }
+}
+for (const input of [...filters, search]) input.addEventListener("input", apply);
+for (const row of document.querySelectorAll("tr.row")) {
+  row.addEventListener("click", () => {
+    const detail = row.nextElementSibling;
+    detail.hidden = !detail.hidden;
+  });
+}
+"#;
+
+/// Render the report as a single HTML page with its styles and script inlined
+pub fn render(report: &Report) -> String {
+    let summary = report.summary();
+    let mut html = String::new();
+
+    html.push_str("<!DOCTYPE html>\n<html lang=\"en\">\n<head>\n<meta charset=\"utf-8\">\n");
+    html.push_str("<title>bibval Report</title>\n");
+    let _ = writeln!(html, "<style>{}</style>\n</head>\n<body>", STYLE);
+    html.push_str("<h1>bibval Report</h1>\n<p class=\"summary\">");
+    let _ = write!(
+        html,
+        "<span>{} entries</span><span class=\"ok\">{} validated</span>\
+         <span class=\"warning\">{} warnings</span><span class=\"error\">{} errors</span>\
+         <span class=\"failed\">{} failed</span><span class=\"not-found\">{} not found</span>",
+        summary.total,
+        summary.ok,
+        summary.warnings,
+        summary.errors,
+        summary.failed,
+        summary.not_found
+    );
+    if let Some(score) = report.score() {
+        let _ = write!(
+            html,
+            "<span>Quality score {}/100 (accuracy {}, identifiers {}, completeness {}, \
+             consistency {})</span>",
+            score.overall,
+            score.accuracy_text(),
+            score.identifiers,
+            score.completeness,
+            score.consistency
+        );
+    }
+    html.push_str("</p>\n");
+
+    write_filters(&mut html, report);
+
+    html.push_str(
+        "<table>\n<thead><tr><th>Key</th><th>Title</th><th>Status</th><th>Sources</th>\
+         <th>Findings</th></tr></thead>\n",
+    );
+    for entry_report in &report.entries {
+        write_entry(&mut html, entry_report);
+    }
+    html.push_str("</table>\n");
+
+    let _ = writeln!(html, "<script>{}</script>\n</body>\n</html>", SCRIPT);
+    html
+}
+
+fn write_filters(html: &mut String, report: &Report) {
+    let sources: BTreeSet<String> = report
+        .entries
+        .iter()
+        .flat_map(|e| &e.validation_results)
+        .map(|r| r.source.to_string())
+        .collect();
+
+    html.push_str("<div class=\"filters\">\n<label>Status <select id=\"status\">");
+    html.push_str("<option value=\"\">all</option>");
+    for (value, label) in [
+        ("error", "errors"),
+        ("warning", "warnings"),
+        ("failed", "failed"),
+        ("not-found", "not found"),
+        ("ok", "ok"),
+    ] {
+        let _ = write!(html, "<option value=\"{}\">{}</option>", value, label);
+    }
+    html.push_str("</select></label>\n<label>Severity <select id=\"severity\">");
+    html.push_str("<option value=\"\">any</option>");
+    for value in ["error", "warning", "info", "none"] {
+        let _ = write!(html, "<option value=\"{0}\">{0}</option>", value);
+    }
+    html.push_str("</select></label>\n<label>Source <select id=\"source\">");
+    html.push_str("<option value=\"\">any</option>");
+    for source in &sources {
+        let _ = write!(html, "<option>{}</option>", escape(source.as_str()));
+    }
+    html.push_str("</select></label>\n");
+    html.push_str("<label>Search <input id=\"search\" type=\"search\"></label>\n</div>\n");
+}
+
+fn write_entry(html: &mut String, entry_report: &EntryReport) {
+    let entry = &entry_report.entry;
+    let (status, status_label) = match &entry_report.status {
+        EntryStatus::Ok(source) => ("ok", format!("OK ({})", source)),
+        EntryStatus::Warning => ("warning", "Warning".to_string()),
+        EntryStatus::Error => ("error", "Error".to_string()),
+        EntryStatus::NotFound => ("not-found", "Not found".to_string()),
+        EntryStatus::Failed(_) => ("failed", "Failed".to_string()),
+    };
+
+    let findings: Vec<(&Discrepancy, String)> = entry_report
+        .lints
+        .iter()
+        .map(|lint| (lint, "offline check".to_string()))
+        .chain(entry_report.validation_results.iter().flat_map(|result| {
+            let source = result.source.to_string();
+            result
+                .discrepancies
+                .iter()
+                .map(move |d| (d, source.clone()))
+        }))
+        .collect();
+    let severity = match findings.iter().map(|(d, _)| d.severity).max() {
+        Some(Severity::Error) => "error",
+        Some(Severity::Warning) => "warning",
+        Some(Severity::Info) => "info",
+        None => "none",
+    };
+    let sources: Vec<String> = entry_report
+        .validation_results
+        .iter()
+        .map(|r| r.source.to_string())
+        .collect::<BTreeSet<_>>()
+        .into_iter()
+        .collect();
+
+    let _ = writeln!(
+        html,
+        "<tbody class=\"entry\" data-status=\"{}\" data-severity=\"{}\" data-sources=\"{}\">",
+        status,
+        severity,
+        escape(sources.join("|").as_str())
+    );
+    let _ = writeln!(
+        html,
+        "<tr class=\"row\"><td class=\"key\">{}</td><td>{}</td><td class=\"{}\">{}</td>\
+         <td>{}</td><td class=\"{}\">{}</td></tr>",
+        escape(entry.key.as_str()),
+        escape(entry.title.as_deref().unwrap_or("(no title)")),
+        status,
+        escape(status_label.as_str()),
+        escape(sources.join(", ").as_str()),
+        severity,
+        findings.len()
+    );
+
+    html.push_str("<tr class=\"detail\" hidden><td colspan=\"5\">\n");
+    if let Some(location) = &entry.location {
+        let _ = writeln!(
+            html,
+            "<p>Declared at {}</p>",
+            escape(location.to_string().as_str())
+        );
+    }
+    write_links(html, "Cited", entry);
+    for result in &entry_report.validation_results {
+        if let Some(matched) = &result.matched_entry {
+            let label = format!(
+                "Matched in {} ({:.0}% confidence)",
+                result.source,
+                result.confidence * 100.0
+            );
+            write_links(html, &label, matched);
+        }
+    }
+    if let EntryStatus::Failed(reason) = &entry_report.status {
+        let _ = writeln!(html, "<p class=\"failed\">{}</p>", escape(reason.as_str()));
+    }
+    if !findings.is_empty() {
+        html.push_str(
+            "<table><tr><th>Severity</th><th>Field</th><th>Message</th><th>Local</th>\
+             <th>Remote</th><th>Via</th></tr>\n",
+        );
+        for (discrepancy, source) in &findings {
+            let severity = discrepancy.severity.to_string();
+            let _ = writeln!(
+                html,
+                "<tr><td class=\"{}\">{}</td><td>{}</td><td>{}</td><td>{}</td><td>{}</td>\
+                 <td>{}</td></tr>",
+                severity.to_lowercase(),
+                severity,
+                discrepancy.field,
+                escape(discrepancy.message.as_str()),
+                escape(discrepancy.local_value.as_str()),
+                escape(discrepancy.remote_value.as_str()),
+                escape(source.as_str())
+            );
+        }
+        html.push_str("</table>\n");
+    }
+    if let Some(citations) = &entry_report.citations {
+        let _ = writeln!(
+            html,
+            "<p>Cited {} times according to {}</p>",
+            citations.count, citations.source
+        );
+    }
+    if let Some(open_access) = &entry_report.open_access {
+        let _ = writeln!(
+            html,
+            "<p>Open access{}: {}</p>",
+            open_access
+                .status
+                .as_deref()
+                .map(|s| format!(" ({})", escape(s)))
+                .unwrap_or_default(),
+            link(&open_access.url, &open_access.url)
+        );
+    }
+    if let Some(code) = &entry_report.code {
+        let _ = writeln!(html, "<p>Official code: {}</p>", link(code, code));
+    }
+    if let Some(replacement) = &entry_report.replacement {
+        let _ = writeln!(
+            html,
+            "<p>Suggested replacement from {}:</p>\n<pre>{}</pre>",
+            replacement.source,
+            escape(replacement.bibtex.as_str())
+        );
+    }
+    html.push_str("</td></tr>\n</tbody>\n");
+}
+
+/// A line of links to the DOI, arXiv and URL of an entry
+fn write_links(html: &mut String, label: &str, entry: &Entry) {
+    let mut links = Vec::new();
+    if let Some(doi) = &entry.doi {
+        links.push(link(
+            &format!("https://doi.org/{}", doi),
+            &format!("doi:{}", doi),
+        ));
+    }
+    if let Some(arxiv_id) = &entry.arxiv_id {
+        links.push(link(
+            &format!("https://arxiv.org/abs/{}", arxiv_id),
+            &format!("arXiv:{}", arxiv_id),
+        ));
+    }
+    if let Some(url) = &entry.url {
+        links.push(link(url, url));
+    }
+    if !links.is_empty() {
+        let _ = writeln!(html, "<p>{}: {}</p>", escape(label), links.join(" · "));
+    }
+}
+
+/// A link, or just the text for anything but a web address, so a `javascript:` URL in a
+/// shared report can't run
+fn link(href: &str, text: &str) -> String {
+    let scheme = href
+        .split_once(':')
+        .map(|(scheme, _)| scheme.trim().to_lowercase());
+    match scheme.as_deref() {
+        Some("http" | "https") => format!("<a href=\"{}\">{}</a>", escape(href), escape(text)),
+        _ => escape(text).into_owned(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::entry::{ApiSource, DiscrepancyField, ValidationResult};
+
+    #[test]
+    fn entries_render_with_filters_links_and_escaping() {
+        let mut entry = Entry::new("smith2020".to_string(), "article".to_string());
+        entry.title = Some("Graphs <and> Trees".into());
+        entry.doi = Some("10.1000/graphs".to_string());
+        let mut matched = entry.clone();
+        matched.arxiv_id = Some("2001.00001".to_string());
+
+        let mut report = Report::new();
+        report.add(EntryReport {
+            entry,
+            status: EntryStatus::Error,
+            validation_results: vec![ValidationResult {
+                source: ApiSource::CrossRef,
+                matched_entry: Some(matched),
+                confidence: 0.8,
+                discrepancies: vec![Discrepancy {
+                    field: DiscrepancyField::Year,
+                    severity: Severity::Error,
+                    local_value: "2020".to_string(),
+                    remote_value: "2019".to_string(),
+                    message: "Year mismatch".to_string(),
+                }],
+            }],
+            lints: Vec::new(),
+            replacement: None,
+            citations: None,
+            open_access: None,
+            code: None,
+            trace: Vec::new(),
+        });
+
+        let html = render(&report);
+        assert!(html.contains("data-status=\"error\" data-severity=\"error\""));
+        assert!(html.contains("data-sources=\"CrossRef\""));
+        assert!(html.contains("<option>CrossRef</option>"));
+        assert!(html.contains("Graphs &lt;and&gt; Trees"));
+        assert!(html.contains("<a href=\"https://doi.org/10.1000/graphs\">"));
+        assert!(html.contains("<a href=\"https://arxiv.org/abs/2001.00001\">"));
+        assert!(html.contains("Matched in CrossRef (80% confidence)"));
+    }
+
+    #[test]
+    fn only_web_addresses_are_linked() {
+        assert_eq!(
+            link("https://example.org/a", "a"),
+            "<a href=\"https://example.org/a\">a</a>"
+        );
+        assert_eq!(link("javascript:alert(1)", "x"), "x");
+        assert_eq!(link(" JavaScript:alert(1)", "x"), "x");
+    }
+}
@@ -0,0 +1,177 @@
+//! LaTeX report, for including a bibliography audit as an appendix
+
+use crate::entry::{Discrepancy, Severity};
+use crate::report::{EntryReport, EntryStatus, Report};
+use std::fmt::Write;
+
+const HEADER: &str = "\\hline\n\\textbf{Key} & \\textbf{Status} & \\textbf{Issues} \\\\\n\\hline\n";
+
+/// Render the report as a LaTeX fragment: a summary paragraph and a `longtable` of the
+/// entries, their statuses and issues. The including document needs
+/// `\usepackage{longtable}`.
+pub fn render(report: &Report) -> String {
+    let summary = report.summary();
+    let mut latex = String::new();
+
+    latex.push_str("% Generated by bibval; requires \\usepackage{longtable}\n");
+    latex.push_str("\\section*{Bibliography Audit}\n\n");
+    let _ = write!(
+        latex,
+        "Of {} entries, {} were validated, {} have warnings, {} have errors, {} could not be \
+         checked and {} were not found in any database.",
+        summary.total,
+        summary.ok,
+        summary.warnings,
+        summary.errors,
+        summary.failed,
+        summary.not_found
+    );
+    if let Some(score) = report.score() {
+        let _ = write!(
+            latex,
+            " The bibliography's quality score is {}/100 (accuracy {}, identifiers {}, \
+             completeness {}, consistency {}).",
+            score.overall,
+            score.accuracy_text(),
+            score.identifiers,
+            score.completeness,
+            score.consistency
+        );
+    }
+    latex.push_str("\n\n");
+
+    latex.push_str("\\begin{longtable}");
+    latex.push_str("{@{}p{0.22\\textwidth}p{0.14\\textwidth}p{0.58\\textwidth}@{}}\n");
+    // The header is repeated on every page the table continues on
+    latex.push_str(HEADER);
+    latex.push_str("\\endfirsthead\n");
+    latex.push_str(HEADER);
+    latex.push_str("\\endhead\n");
+    latex.push_str("\\hline\n\\endfoot\n");
+    for entry_report in &report.entries {
+        write_entry(&mut latex, entry_report);
+    }
+    latex.push_str("\\end{longtable}\n");
+    latex
+}
+
+fn write_entry(latex: &mut String, entry_report: &EntryReport) {
+    let status = match &entry_report.status {
+        EntryStatus::Ok(source) => format!("OK ({})", source),
+        EntryStatus::Warning => "Warning".to_string(),
+        EntryStatus::Error => "Error".to_string(),
+        EntryStatus::NotFound => "Not found".to_string(),
+        EntryStatus::Failed(_) => "Failed".to_string(),
+    };
+
+    let mut issues: Vec<String> = entry_report
+        .lints
+        .iter()
+        .map(|lint| issue(lint, "offline check"))
+        .chain(entry_report.validation_results.iter().flat_map(|result| {
+            let source = result.source.to_string();
+            result.discrepancies.iter().map(move |d| issue(d, &source))
+        }))
+        .collect();
+    if let EntryStatus::Failed(reason) = &entry_report.status {
+        issues.push(escape(reason));
+    }
+
+    let _ = writeln!(
+        latex,
+        "\\texttt{{{}}} & {} & {} \\\\",
+        escape(&entry_report.entry.key),
+        escape(&status),
+        if issues.is_empty() {
+            "---".to_string()
+        } else {
+            issues.join(" \\newline ")
+        }
+    );
+}
+
+/// One finding: its severity, field, source and message, with both values when they
+/// disagree
+fn issue(discrepancy: &Discrepancy, source: &str) -> String {
+    let severity = match discrepancy.severity {
+        Severity::Error => "Error",
+        Severity::Warning => "Warning",
+        Severity::Info => "Info",
+    };
+    let mut text = format!(
+        "\\textbf{{{}}} ({}, {}): {}",
+        severity,
+        escape(&discrepancy.field.to_string()),
+        escape(source),
+        escape(&discrepancy.message)
+    );
+    if !(discrepancy.local_value.is_empty() && discrepancy.remote_value.is_empty()) {
+        let _ = write!(
+            text,
+            " (local: {}; remote: {})",
+            escape(&discrepancy.local_value),
+            escape(&discrepancy.remote_value)
+        );
+    }
+    text
+}
+
+/// Escape the characters LaTeX treats specially
+fn escape(s: &str) -> String {
+    let mut escaped = String::with_capacity(s.len());
+    for c in s.chars() {
+        match c {
+            '\\' => escaped.push_str("\\textbackslash{}"),
+            '~' => escaped.push_str("\\textasciitilde{}"),
+            '^' => escaped.push_str("\\textasciicircum{}"),
+            '&' | '%' | '$' | '#' | '_' | '{' | '}' => {
+                escaped.push('\\');
+                escaped.push(c);
+            }
+            c => escaped.push(c),
+        }
+    }
+    escaped
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::entry::{ApiSource, DiscrepancyField, Entry, ValidationResult};
+
+    #[test]
+    fn entries_render_as_escaped_table_rows() {
+        let entry = Entry::new("smith_2020".to_string(), "article".to_string());
+        let mut report = Report::new();
+        report.add(EntryReport {
+            entry,
+            status: EntryStatus::Error,
+            validation_results: vec![ValidationResult {
+                source: ApiSource::CrossRef,
+                matched_entry: None,
+                confidence: 0.8,
+                discrepancies: vec![Discrepancy {
+                    field: DiscrepancyField::Title,
+                    severity: Severity::Error,
+                    local_value: "R&D at 100%".to_string(),
+                    remote_value: "R&D".to_string(),
+                    message: "Title mismatch".to_string(),
+                }],
+            }],
+            lints: Vec::new(),
+            replacement: None,
+            citations: None,
+            open_access: None,
+            code: None,
+            trace: Vec::new(),
+        });
+
+        let latex = render(&report);
+        assert!(latex.contains("\\begin{longtable}"));
+        assert!(latex.contains("Of 1 entries, 0 were validated"));
+        assert!(latex.contains(
+            "\\texttt{smith\\_2020} & Error & \\textbf{Error} (Title, CrossRef): Title mismatch \
+             (local: R\\&D at 100\\%; remote: R\\&D) \\\\"
+        ));
+    }
+}
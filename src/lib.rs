@@ -1,29 +1,108 @@
+pub mod artifacts;
+pub mod bbl;
 pub mod cache;
+pub mod checkpoint;
+pub mod citations;
+pub mod csl;
+pub mod dedupe;
+pub mod diff;
 pub mod entry;
+pub mod fix;
 pub mod fusion;
+pub mod history;
+pub mod html;
+pub mod latex;
+pub mod lint;
+pub mod markdown;
 pub mod matcher;
+pub mod mcp;
+pub mod memo;
 pub mod parser;
+pub mod predatory;
+pub mod publishers;
 pub mod report;
+pub mod score;
+pub mod sources;
+pub mod template;
+pub mod throttle;
+pub mod tui;
 pub mod validators;
+pub mod venues;
+pub mod workflow;
+pub mod writer;
 
 use cache::Cache;
-use entry::{ApiSource, Entry, Severity, ValidationResult};
+use entry::{
+    ApiSource, Discrepancy, DiscrepancyField, Entry, EntryKind, Severity, ValidationResult,
+};
 use fusion::fuse_results;
-use matcher::{compare_entries, find_best_match, title_similarity, years_compatible};
-use report::{EntryReport, EntryStatus, Report};
+use lint::{normalize_doi, LintConfig};
+use matcher::{
+    compare_entries, compare_journal, find_best_match, match_score, rejection, title_similarity,
+    years_compatible,
+};
+use checkpoint::{Checkpoint, CompletedReports};
+use memo::Memo;
+use report::{
+    CitationCount, EntryReport, EntryStatus, OpenAccess, Replacement, Report, Statistics,
+};
+use throttle::{ThrottleStats, Throttles};
 
 /// Minimum title similarity to trust a DOI/arXiv ID lookup result
 const MIN_TITLE_SIMILARITY_FOR_ID_LOOKUP: f64 = 0.75;
+
+/// Cache reads in flight at once while prefetching
+const PREFETCH_CACHE_READS: usize = 20;
 use validators::{
-    arxiv::ArxivClient, crossref::CrossRefClient, dblp::DblpClient, openalex::OpenAlexClient,
-    openlibrary::OpenLibraryClient, openreview::OpenReviewClient, semantic::SemanticScholarClient,
-    zenodo::ZenodoClient, Validator, ValidatorError,
+    acm::{is_acm_doi, AcmClient},
+    ads::{local_bibcode, AdsClient},
+    arxiv::ArxivClient,
+    core::CoreClient,
+    crossref::{CrossRefClient, UpdateNotice, CROSSREF_BATCH_SIZE},
+    datacite::{is_datacite_doi, DataCiteClient, CONCEPT_DOI_FIELD, VERSION_COUNT_FIELD},
+    dblp::{rekey_bibtex, DblpBibtexFormat, DblpClient, DBLP_KEY_FIELD},
+    dblp_dump::DblpDump,
+    doaj::DoajClient,
+    doiorg::DoiOrgClient,
+    epo::{patent_number, EpoClient, EpoCredentials},
+    figshare::{figshare_article, FigshareClient},
+    github::{github_repository, repository_url, GitHubClient},
+    hal::HalClient,
+    openalex::{OpenAlexClient, OPENALEX_BATCH_SIZE},
+    opencitations::OpenCitationsClient,
+    hathitrust::{BookId, HathiTrustClient},
+    ietf::{rfc_number, IetfClient},
+    inspire::{is_hep, is_texkey, InspireClient},
+    jstage::{is_japanese, JstageClient},
+    mathscinet::MathSciNetClient,
+    openlibrary::OpenLibraryClient,
+    openreview::OpenReviewClient,
+    osf::{osf_guid, OsfClient},
+    paperswithcode::{is_ml, PapersWithCodeClient, CODE_REPOSITORY_FIELD},
+    pubmed::PubMedClient,
+    scielo::{is_ibero_american, is_scielo_doi, ScieloClient},
+    scopus::ScopusClient,
+    semantic::{SemanticScholarClient, SEMANTIC_SCHOLAR_BATCH_SIZE},
+    springer::{is_springer_doi, SpringerClient},
+    sru::{SruClient, SruLibrary},
+    swh::SoftwareHeritageClient,
+    unpaywall::{UnpaywallClient, OA_STATUS_FIELD, OA_URL_FIELD},
+    w3c::{tr_url, W3cClient},
+    web::WebClient,
+    worldcat::{WorldCatClient, WorldCatCredentials, OCLC_FIELD},
+    wos::{local_ut, WosClient},
+    zenodo::ZenodoClient,
+    Validator, ValidatorError, CITATION_COUNT_FIELD,
 };
 
-use futures::{stream, StreamExt};
+use futures::future::BoxFuture;
+use futures::{stream, FutureExt, StreamExt};
 use indicatif::{ProgressBar, ProgressStyle};
+use std::collections::{HashMap, HashSet};
+use std::path::PathBuf;
 use std::sync::atomic::{AtomicU64, Ordering};
 use std::sync::Arc;
+use std::time::Instant;
 
 /// Configuration for the validator
 pub struct ValidatorConfig {
@@ -33,9 +112,110 @@ pub struct ValidatorConfig {
     pub use_semantic: bool,
     pub use_openalex: bool,
     pub use_openlibrary: bool,
+    pub use_hathitrust: bool,
+    pub use_pubmed: bool,
+    pub use_doaj: bool,
+    pub use_scielo: bool,
+    pub use_jstage: bool,
+    pub use_acm: bool,
+    pub use_inspire: bool,
+    pub use_mathscinet: bool,
+    pub use_osf: bool,
+    pub use_paperswithcode: bool,
+    pub use_hal: bool,
+    pub use_ietf: bool,
+    pub use_w3c: bool,
+    /// Springer Nature Meta API key; `None` disables Springer Nature
+    pub springer_api_key: Option<String>,
+    /// Elsevier API key for Scopus; `None` disables Scopus
+    pub scopus_api_key: Option<String>,
+    /// Clarivate API key for the Web of Science Starter API; `None` disables it
+    pub wos_api_key: Option<String>,
+    /// NASA ADS API token; `None` disables ADS
+    pub ads_api_token: Option<String>,
+    /// CORE API key; `None` disables the CORE fallback search
+    pub core_api_key: Option<String>,
+    /// Email address for Unpaywall; `None` disables Unpaywall
+    pub unpaywall_email: Option<String>,
+    /// NCBI API key for PubMed, for higher rate limits
+    pub ncbi_api_key: Option<String>,
     pub use_openreview: bool,
     pub use_zenodo: bool,
+    pub use_datacite: bool,
+    /// Fall back to doi.org content negotiation for DOIs no other source knows
+    pub use_doi_org: bool,
+    pub use_figshare: bool,
+    /// Check the GitHub repositories @software entries cite
+    pub use_github: bool,
+    /// GitHub token, for higher rate limits
+    pub github_token: Option<String>,
+    /// Look up the repositories @software entries cite in Software Heritage
+    pub use_software_heritage: bool,
+    /// Ask OpenCitations when CrossRef and Semantic Scholar disagree on a year or venue
+    pub use_opencitations: bool,
+    /// Fetch the URLs of @online entries and look them up in the Wayback Machine
+    pub check_urls: bool,
+    /// Look for retraction, correction and expression-of-concern notices against DOIs
+    pub check_retractions: bool,
+    /// Look up books in WorldCat with these credentials; `None` disables WorldCat
+    pub worldcat: Option<WorldCatCredentials>,
+    /// Look up patents in EPO Open Patent Services with these credentials
+    pub epo: Option<EpoCredentials>,
+    /// National library catalogs to look up books in
+    pub sru_libraries: Vec<SruLibrary>,
     pub cache_enabled: bool,
+    pub lint: LintConfig,
+    /// Stop issuing identifier lookups once enough sources agree; `None` queries every source
+    pub short_circuit: Option<ShortCircuitPolicy>,
+    /// Run-state file recording completed entries; `None` disables checkpointing
+    pub checkpoint: Option<PathBuf>,
+    /// Reuse the reports an interrupted run already recorded in the checkpoint file
+    pub resume: bool,
+    /// Offer DBLP's BibTeX, in this format, as a replacement for entries with issues
+    pub dblp_bibtex: Option<DblpBibtexFormat>,
+    /// Answer DBLP lookups from this downloaded dump instead of the DBLP API
+    pub dblp_dump: Option<DblpDump>,
+    /// Report the citation counts of matched works
+    pub citation_counts: bool,
+    /// Report the official code repositories of matched machine learning papers
+    pub code_repositories: bool,
+    /// Report the open-access copies OpenAlex knows of too, and flag works that can only
+    /// be read behind a paywall
+    pub open_access: bool,
+    /// Draw progress with ASCII characters only, for dumb terminals and log collectors
+    pub ascii: bool,
+    /// Record in each entry's report the lookups made for it, what they returned and why
+    /// candidates were rejected
+    pub trace: bool,
+    /// Findings below this severity are dropped before an entry's status is decided
+    pub min_severity: Severity,
+}
+
+/// When to stop querying further sources for an entry
+#[derive(Debug, Clone, Copy)]
+pub struct ShortCircuitPolicy {
+    /// Number of agreeing sources after which remaining lookups are skipped
+    pub min_agreeing: usize,
+    /// Minimum match confidence for a source to count as agreeing
+    pub min_confidence: f64,
+}
+
+impl ShortCircuitPolicy {
+    pub fn after(min_agreeing: usize) -> Self {
+        Self {
+            min_agreeing: min_agreeing.max(1),
+            min_confidence: 0.9,
+        }
+    }
+
+    /// Whether the results gathered so far make further lookups unnecessary
+    fn is_satisfied(&self, results: &[ValidationResult]) -> bool {
+        let agreeing = results
+            .iter()
+            .filter(|r| r.confidence >= self.min_confidence && r.discrepancies.is_empty())
+            .count();
+        agreeing >= self.min_agreeing
+    }
 }
 
 impl Default for ValidatorConfig {
@@ -47,9 +227,53 @@ impl Default for ValidatorConfig {
             use_semantic: true,
             use_openalex: true,
             use_openlibrary: true,
+            use_hathitrust: true,
+            use_pubmed: true,
+            use_doaj: true,
+            use_scielo: true,
+            use_jstage: true,
+            use_acm: true,
+            use_inspire: true,
+            use_mathscinet: true,
+            use_osf: true,
+            use_paperswithcode: true,
+            use_hal: true,
+            use_ietf: true,
+            use_w3c: true,
+            springer_api_key: None,
+            scopus_api_key: None,
+            wos_api_key: None,
+            ads_api_token: None,
+            core_api_key: None,
+            unpaywall_email: None,
+            ncbi_api_key: None,
             use_openreview: false,
             use_zenodo: true,
+            use_datacite: true,
+            use_doi_org: true,
+            use_figshare: true,
+            use_github: true,
+            github_token: None,
+            use_software_heritage: true,
+            use_opencitations: true,
+            check_urls: true,
+            check_retractions: true,
+            worldcat: None,
+            epo: None,
+            sru_libraries: Vec::new(),
             cache_enabled: true,
+            lint: LintConfig::default(),
+            short_circuit: None,
+            checkpoint: None,
+            resume: false,
+            dblp_bibtex: None,
+            dblp_dump: None,
+            citation_counts: false,
+            code_repositories: false,
+            open_access: false,
+            ascii: false,
+            trace: false,
+            min_severity: Severity::Info,
         }
     }
 }
@@ -58,13 +282,57 @@ impl Default for ValidatorConfig {
 pub struct BibValidator {
     crossref: Option<CrossRefClient>,
     dblp: Option<DblpClient>,
+    dblp_dump: Option<DblpDump>,
     arxiv: Option<ArxivClient>,
     semantic: Option<SemanticScholarClient>,
     openalex: Option<OpenAlexClient>,
     openlibrary: Option<OpenLibraryClient>,
+    hathitrust: Option<HathiTrustClient>,
+    pubmed: Option<PubMedClient>,
+    doaj: Option<DoajClient>,
+    scielo: Option<ScieloClient>,
+    jstage: Option<JstageClient>,
+    acm: Option<AcmClient>,
+    inspire: Option<InspireClient>,
+    mathscinet: Option<MathSciNetClient>,
+    osf: Option<OsfClient>,
+    paperswithcode: Option<PapersWithCodeClient>,
+    hal: Option<HalClient>,
+    ietf: Option<IetfClient>,
+    w3c: Option<W3cClient>,
+    springer: Option<SpringerClient>,
+    scopus: Option<ScopusClient>,
+    wos: Option<WosClient>,
+    ads: Option<AdsClient>,
+    core: Option<CoreClient>,
+    unpaywall: Option<UnpaywallClient>,
     openreview: Option<OpenReviewClient>,
     zenodo: Option<ZenodoClient>,
+    github: Option<GitHubClient>,
+    software_heritage: Option<SoftwareHeritageClient>,
+    datacite: Option<DataCiteClient>,
+    doi_org: Option<DoiOrgClient>,
+    figshare: Option<FigshareClient>,
+    opencitations: Option<OpenCitationsClient>,
+    web: Option<WebClient>,
+    worldcat: Option<WorldCatClient>,
+    epo: Option<EpoClient>,
+    sru: Vec<SruClient>,
     cache: Cache,
+    lint: LintConfig,
+    short_circuit: Option<ShortCircuitPolicy>,
+    throttles: Throttles,
+    /// When the validator was built, for the wall-clock time of a run
+    started: Instant,
+    checkpoint: Option<PathBuf>,
+    resume: bool,
+    dblp_bibtex: Option<DblpBibtexFormat>,
+    citation_counts: bool,
+    open_access: bool,
+    check_retractions: bool,
+    ascii: bool,
+    trace: bool,
+    min_severity: Severity,
 }
 
 impl BibValidator {
@@ -82,6 +350,7 @@ impl BibValidator {
             } else {
                 None
             },
+            dblp_dump: config.dblp_dump,
             arxiv: if config.use_arxiv {
                 Some(ArxivClient::new())
             } else {
@@ -102,6 +371,77 @@ impl BibValidator {
             } else {
                 None
             },
+            hathitrust: if config.use_hathitrust {
+                Some(HathiTrustClient::new())
+            } else {
+                None
+            },
+            pubmed: if config.use_pubmed {
+                Some(PubMedClient::new(config.ncbi_api_key))
+            } else {
+                None
+            },
+            doaj: if config.use_doaj {
+                Some(DoajClient::new())
+            } else {
+                None
+            },
+            scielo: if config.use_scielo {
+                Some(ScieloClient::new())
+            } else {
+                None
+            },
+            jstage: if config.use_jstage {
+                Some(JstageClient::new())
+            } else {
+                None
+            },
+            acm: if config.use_acm {
+                Some(AcmClient::new())
+            } else {
+                None
+            },
+            inspire: if config.use_inspire {
+                Some(InspireClient::new())
+            } else {
+                None
+            },
+            mathscinet: if config.use_mathscinet {
+                Some(MathSciNetClient::new())
+            } else {
+                None
+            },
+            osf: if config.use_osf {
+                Some(OsfClient::new())
+            } else {
+                None
+            },
+            paperswithcode: if config.use_paperswithcode {
+                Some(PapersWithCodeClient::new(config.code_repositories))
+            } else {
+                None
+            },
+            hal: if config.use_hal {
+                Some(HalClient::new())
+            } else {
+                None
+            },
+            ietf: if config.use_ietf {
+                Some(IetfClient::new())
+            } else {
+                None
+            },
+            w3c: if config.use_w3c {
+                Some(W3cClient::new())
+            } else {
+                None
+            },
+            springer: config.springer_api_key.map(SpringerClient::new),
+            scopus: config.scopus_api_key.map(ScopusClient::new),
+            wos: config.wos_api_key.map(WosClient::new),
+            ads: config.ads_api_token.map(AdsClient::new),
+            core: config.core_api_key.map(CoreClient::new),
+            unpaywall: config.unpaywall_email.map(UnpaywallClient::new),
             openreview: if config.use_openreview {
                 Some(OpenReviewClient::new())
             } else {
@@ -112,287 +452,2337 @@ impl BibValidator {
             } else {
                 None
             },
+            github: if config.use_github {
+                Some(GitHubClient::new(config.github_token))
+            } else {
+                None
+            },
+            software_heritage: if config.use_software_heritage {
+                Some(SoftwareHeritageClient::new())
+            } else {
+                None
+            },
+            datacite: if config.use_datacite {
+                Some(DataCiteClient::new())
+            } else {
+                None
+            },
+            doi_org: if config.use_doi_org {
+                Some(DoiOrgClient::new())
+            } else {
+                None
+            },
+            figshare: if config.use_figshare {
+                Some(FigshareClient::new())
+            } else {
+                None
+            },
+            opencitations: if config.use_opencitations {
+                Some(OpenCitationsClient::new())
+            } else {
+                None
+            },
+            web: if config.check_urls {
+                Some(WebClient::new())
+            } else {
+                None
+            },
+            worldcat: config.worldcat.map(WorldCatClient::new),
+            epo: config.epo.map(EpoClient::new),
+            sru: config.sru_libraries.into_iter().map(SruClient::new).collect(),
             cache,
+            lint: config.lint,
+            short_circuit: config.short_circuit,
+            throttles: Throttles::new(),
+            started: Instant::now(),
+            checkpoint: config.checkpoint,
+            resume: config.resume,
+            dblp_bibtex: config.dblp_bibtex,
+            citation_counts: config.citation_counts,
+            open_access: config.open_access,
+            check_retractions: config.check_retractions,
+            ascii: config.ascii,
+            trace: config.trace,
+            min_severity: config.min_severity,
         })
     }
 
     /// Validate a list of entries and return a report
     pub async fn validate(&self, entries: Vec<Entry>) -> Report {
+        self.validate_only(entries, |_| true).await
+    }
+
+    /// Validate the entries accepted by `filter`, using the rest of the bibliography
+    /// only as context for cross-entry checks (duplicates, crossref targets)
+    pub async fn validate_only(
+        &self,
+        entries: Vec<Entry>,
+        filter: impl Fn(&Entry) -> bool,
+    ) -> Report {
+        let mut report = Report::new();
+        self.validate_only_each(entries, filter, |entry_report| report.add(entry_report))
+            .await;
+        report
+    }
+
+    /// Like [`validate_only`](Self::validate_only), but hand each entry's report to
+    /// `on_report` as soon as it is ready instead of collecting them
+    pub async fn validate_only_each(
+        &self,
+        entries: Vec<Entry>,
+        filter: impl Fn(&Entry) -> bool,
+        mut on_report: impl FnMut(EntryReport),
+    ) {
+        // Offline checks need the whole bibliography, so run them up front
+        let lints = lint::lint_entries(&entries, &self.lint);
+        let (checkpoint, mut completed) = self.start_checkpoint();
+        let mut any_failed = false;
+        let mut emit = |entry_report: EntryReport| {
+            any_failed |= matches!(entry_report.status, EntryStatus::Failed(_));
+            on_report(entry_report);
+        };
+        let selected: Vec<_> = entries
+            .into_iter()
+            .zip(lints)
+            .filter(|(entry, _)| filter(entry))
+            .filter(|(entry, _)| match completed.take(entry) {
+                Some(resumed) => {
+                    emit(resumed);
+                    false
+                }
+                None => true,
+            })
+            .collect();
+
+        let run = RunState {
+            prefetched: self.prefetch(&selected).await,
+            checkpoint,
+            ..RunState::default()
+        };
+
+        let pb = ProgressBar::new(selected.len() as u64);
+        pb.set_style(self.progress_style(true));
+
+        self.validate_batch(selected, &run, &pb, &mut emit).await;
+        pb.finish_with_message("Done!");
+
+        self.flush_cache().await;
+        finish_checkpoint(run.checkpoint, any_failed);
+    }
+
+    /// Validate entries as they are parsed, a batch at a time, without holding the whole
+    /// bibliography in memory.
+    ///
+    /// Only per-entry offline checks run, since duplicate and crossref checks need every
+    /// entry up front.
+    pub async fn validate_stream(
+        &self,
+        entries: impl futures::Stream<Item = Entry>,
+        filter: impl Fn(&Entry) -> bool,
+    ) -> Report {
+        let mut report = Report::new();
+        self.validate_stream_each(entries, filter, |entry_report| report.add(entry_report))
+            .await;
+        report
+    }
+
+    /// Like [`validate_stream`](Self::validate_stream), but hand each entry's report to
+    /// `on_report` as soon as it is ready, so no report of the whole bibliography is kept
+    pub async fn validate_stream_each(
+        &self,
+        entries: impl futures::Stream<Item = Entry>,
+        filter: impl Fn(&Entry) -> bool,
+        mut on_report: impl FnMut(EntryReport),
+    ) {
+        const STREAM_BATCH_SIZE: usize = 200;
+
+        let mut entries = std::pin::pin!(entries);
+        let pb = ProgressBar::new_spinner();
+        pb.set_style(self.progress_style(false));
+
+        let (checkpoint, mut completed) = self.start_checkpoint();
+        let mut run = RunState {
+            checkpoint,
+            ..RunState::default()
+        };
+        let mut any_failed = false;
+        let mut emit = |entry_report: EntryReport| {
+            any_failed |= matches!(entry_report.status, EntryStatus::Failed(_));
+            on_report(entry_report);
+        };
+        loop {
+            let mut batch = Vec::new();
+            while batch.len() < STREAM_BATCH_SIZE {
+                let Some(entry) = entries.next().await else {
+                    break;
+                };
+                if !filter(&entry) {
+                    continue;
+                }
+                match completed.take(&entry) {
+                    Some(resumed) => emit(resumed),
+                    None => {
+                        let lints = lint::lint_entry(&entry, &self.lint);
+                        batch.push((entry, lints));
+                    }
+                }
+            }
+            if batch.is_empty() {
+                break;
+            }
+
+            // Memoized lookups carry over between batches; only the batched prefetch is
+            // per batch
+            run = RunState {
+                prefetched: self.prefetch(&batch).await,
+                ..run
+            };
+            self.validate_batch(batch, &run, &pb, &mut emit).await;
+        }
+
+        pb.finish_with_message("Done!");
+        self.flush_cache().await;
+        finish_checkpoint(run.checkpoint, any_failed);
+    }
+
+    /// Write out cache entries still buffered at the end of a run
+    async fn flush_cache(&self) {
+        if let Err(e) = self.cache.flush().await {
+            tracing::warn!("Could not write cache: {}", e);
+        }
+    }
+
+    /// Open the run-state file, loading what an earlier run finished when resuming
+    fn start_checkpoint(&self) -> (Option<Checkpoint>, CompletedReports) {
+        let Some(path) = &self.checkpoint else {
+            return (None, CompletedReports::default());
+        };
+
+        let completed = if self.resume {
+            CompletedReports::load(path).unwrap_or_else(|e| {
+                tracing::warn!("Could not read run state {}: {}", path.display(), e);
+                CompletedReports::default()
+            })
+        } else {
+            CompletedReports::default()
+        };
+
+        let checkpoint = Checkpoint::create(path, self.resume)
+            .map_err(|e| tracing::warn!("Could not write run state {}: {}", path.display(), e))
+            .ok();
+        (checkpoint, completed)
+    }
+
+    /// Validate entries concurrently, advancing `pb` and passing each report to
+    /// `on_report` as it finishes
+    async fn validate_batch(
+        &self,
+        selected: Vec<(Entry, Vec<Discrepancy>)>,
+        run: &RunState,
+        pb: &ProgressBar,
+        on_report: &mut dyn FnMut(EntryReport),
+    ) {
+        // Entries in flight at once; each API's own throttle decides how many of their
+        // requests actually reach it
         const CONCURRENCY_LIMIT: usize = 20;
 
-        let total = entries.len() as u64;
         let progress = Arc::new(AtomicU64::new(0));
 
-        let pb = ProgressBar::new(total);
-        pb.set_style(
-            ProgressStyle::default_bar()
-                .template("{spinner:.green} [{elapsed_precise}] [{bar:40.cyan/blue}] {pos}/{len} {msg}")
-                .unwrap()
-                .progress_chars("#>-"),
-        );
-
-        let results: Vec<EntryReport> = stream::iter(entries)
-            .map(|entry| {
+        stream::iter(selected)
+            .map(|(entry, lints)| {
                 let progress = Arc::clone(&progress);
                 async move {
-                    let report = self.validate_entry(&entry).await;
+                    let report = self.validate_entry(entry, lints, run).await;
                     progress.fetch_add(1, Ordering::Relaxed);
                     report
                 }
             })
             .buffer_unordered(CONCURRENCY_LIMIT)
-            .inspect(|_| pb.inc(1))
-            .collect()
-            .await;
+            .for_each(|entry_report| {
+                pb.inc(1);
+                if let Some(checkpoint) = &run.checkpoint {
+                    if let Err(e) = checkpoint.record(&entry_report) {
+                        tracing::warn!("Could not record run state: {}", e);
+                    }
+                }
+                on_report(entry_report);
+                futures::future::ready(())
+            })
+            .await
+    }
 
-        pb.finish_with_message("Done!");
+    /// Style of the progress bar, or of the spinner when the number of entries isn't
+    /// known: uncolored when colors are off, and with ASCII spinner frames in ASCII mode
+    fn progress_style(&self, bar: bool) -> ProgressStyle {
+        let colors = colored::control::SHOULD_COLORIZE.should_colorize();
+        let (style, template) = match (bar, colors) {
+            (true, true) => (
+                ProgressStyle::default_bar(),
+                "{spinner:.green} [{elapsed_precise}] [{bar:40.cyan/blue}] {pos}/{len} {msg}",
+            ),
+            (true, false) => (
+                ProgressStyle::default_bar(),
+                "{spinner} [{elapsed_precise}] [{bar:40}] {pos}/{len} {msg}",
+            ),
+            (false, true) => (
+                ProgressStyle::default_spinner(),
+                "{spinner:.green} [{elapsed_precise}] {pos} entries {msg}",
+            ),
+            (false, false) => (
+                ProgressStyle::default_spinner(),
+                "{spinner} [{elapsed_precise}] {pos} entries {msg}",
+            ),
+        };
+        let style = style.template(template).unwrap().progress_chars("#>-");
+        if self.ascii {
+            style.tick_chars("-\\|/ ")
+        } else {
+            style
+        }
+    }
 
-        let mut report = Report::new();
-        for entry_report in results {
-            report.add(entry_report);
+    /// Request counts and adaptive concurrency limits per API source, for `--profile`
+    pub fn throttle_stats(&self) -> Vec<(ApiSource, ThrottleStats)> {
+        self.throttles.stats()
+    }
+
+    /// Coverage, confidence, API and cache statistics of a run that produced `report`
+    pub fn statistics(&self, report: &Report) -> Statistics {
+        Statistics::gather(
+            report,
+            &self.throttles.stats(),
+            self.cache.stats(),
+            self.started.elapsed(),
+        )
+    }
+
+    /// Look up the record registered for a DOI: CrossRef's if it has one, otherwise
+    /// DataCite's, otherwise whatever doi.org serves for it
+    pub async fn lookup_doi(&self, doi: &str) -> Result<Option<Entry>, ValidatorError> {
+        let doi = normalize_doi(doi);
+        let mut found = None;
+        if let (false, Some(client)) = (self.leaves_to_datacite(&doi), &self.crossref) {
+            found = self
+                .try_doi_lookup(client, &doi, &Prefetched::default())
+                .await?;
         }
-        report
+        if let (None, Some(client)) = (&found, &self.datacite) {
+            found = self
+                .throttles
+                .run(ApiSource::DataCite, client.search_by_doi(&doi))
+                .await?;
+        }
+        if let (None, Some(client)) = (&found, &self.doi_org) {
+            found = self
+                .throttles
+                .run(ApiSource::DoiOrg, client.search_by_doi(&doi))
+                .await?;
+        }
+        self.flush_cache().await;
+        Ok(found)
     }
 
-    /// Validate a single entry against all configured APIs
-    async fn validate_entry(&self, entry: &Entry) -> EntryReport {
-        let mut validation_results = Vec::new();
-        let mut api_errors = Vec::new();
+    /// Fetch identifier lookups for the whole bibliography in a few batched requests
+    async fn prefetch(&self, entries: &[(Entry, Vec<Discrepancy>)]) -> Prefetched {
+        let mut prefetched = Prefetched::default();
 
-        // Try DOI-based lookup first (most reliable)
-        if let Some(doi) = &entry.doi {
-            if let Some(ref client) = self.crossref {
-                match self.try_doi_lookup(client, doi).await {
-                    Ok(Some(result)) => {
-                        // Validate that the returned paper actually matches
-                        if is_valid_id_match(entry, &result) {
-                            let discrepancies = compare_entries(entry, &result);
-                            let confidence = if discrepancies.is_empty() { 1.0 } else { 0.8 };
-                            validation_results.push(ValidationResult {
-                                source: ApiSource::CrossRef,
-                                matched_entry: Some(result),
-                                confidence,
-                                discrepancies,
-                            });
-                        }
-                        // If invalid match, silently skip - DOI might be wrong
+        if let Some(ref client) = self.crossref {
+            // Only batch DOIs that aren't already cached from a previous run
+            let dois: Vec<&str> = entries
+                .iter()
+                .filter_map(|(entry, _)| entry.doi.as_deref())
+                .filter(|doi| !self.leaves_to_datacite(doi))
+                .collect();
+            // Cached works are passed on as they are, so the lookup doesn't ask again
+            let cached: Vec<(&str, Option<Entry>)> = stream::iter(dois)
+                .map(|doi| async move { (doi, self.cache.get::<Entry>("crossref_doi", doi).await) })
+                .buffer_unordered(PREFETCH_CACHE_READS)
+                .collect()
+                .await;
+            let mut dois = Vec::new();
+            for (doi, cached) in cached {
+                match cached {
+                    Some(entry) => {
+                        prefetched.crossref.insert(normalize_doi(doi), Some(entry));
                     }
-                    Ok(None) => {}
-                    Err(e) => api_errors.push(format!("CrossRef DOI lookup failed: {}", e)),
+                    None => dois.push(normalize_doi(doi)),
                 }
             }
-        }
+            dois.sort();
+            dois.dedup();
+            prefetched.crossref_uncached.extend(dois.iter().cloned());
+            // A comma would split the filter query, so those DOIs are looked up one by one
+            dois.retain(|doi| !doi.contains(','));
 
-        // Try arXiv ID lookup
-        if let Some(arxiv_id) = &entry.arxiv_id {
-            if let Some(ref client) = self.arxiv {
-                match client.search_by_arxiv_id(arxiv_id).await {
-                    Ok(Some(result)) => {
-                        if is_valid_id_match(entry, &result) {
-                            let discrepancies = compare_entries(entry, &result);
-                            validation_results.push(ValidationResult {
-                                source: ApiSource::ArXiv,
-                                matched_entry: Some(result),
-                                confidence: 0.95,
-                                discrepancies,
-                            });
+            for chunk in dois.chunks(CROSSREF_BATCH_SIZE) {
+                let batch = client.search_by_dois(chunk);
+                match self.throttles.run(ApiSource::CrossRef, batch).await {
+                    Ok(mut found) => {
+                        for doi in chunk {
+                            let work = found.remove(doi);
+                            if let Some(ref work) = work {
+                                let _ = self.cache.set("crossref_doi", doi, work).await;
+                            }
+                            prefetched.crossref.insert(doi.clone(), work);
                         }
                     }
-                    Ok(None) => {}
-                    Err(e) => api_errors.push(format!("ArXiv lookup failed: {}", e)),
+                    // Entries in a failed batch fall back to individual lookups
+                    Err(e) => tracing::debug!("CrossRef batch lookup failed: {}", e),
                 }
             }
+        }
 
-            // Also try Semantic Scholar with arXiv ID
-            if let Some(ref client) = self.semantic {
-                match client.search_by_arxiv_id(arxiv_id).await {
-                    Ok(Some(result)) => {
-                        if is_valid_id_match(entry, &result) {
-                            let discrepancies = compare_entries(entry, &result);
-                            validation_results.push(ValidationResult {
-                                source: ApiSource::SemanticScholar,
-                                matched_entry: Some(result),
-                                confidence: 0.9,
-                                discrepancies,
-                            });
-                        }
-                    }
-                    Ok(None) => {}
-                    Err(e) => api_errors.push(format!("Semantic Scholar arXiv lookup failed: {}", e)),
+        if let Some(ref client) = self.semantic {
+            // Same ID choice as validate_entry: arXiv ID if present, otherwise DOI
+            let mut ids: Vec<String> = entries
+                .iter()
+                .filter_map(|(entry, _)| match (&entry.arxiv_id, &entry.doi) {
+                    (Some(arxiv_id), _) => Some(semantic_arxiv_id(arxiv_id)),
+                    (None, Some(doi)) => Some(semantic_doi_id(doi)),
+                    (None, None) => None,
+                })
+                .collect();
+            ids.sort();
+            ids.dedup();
+
+            for chunk in ids.chunks(SEMANTIC_SCHOLAR_BATCH_SIZE) {
+                let batch = client.search_by_ids(chunk);
+                match self.throttles.run(ApiSource::SemanticScholar, batch).await {
+                    Ok(found) => prefetched.semantic.extend(chunk.iter().cloned().zip(found)),
+                    Err(e) => tracing::debug!("Semantic Scholar batch lookup failed: {}", e),
                 }
             }
         }
 
-        // If no exact matches, try title search
-        if validation_results.is_empty() {
-            if let Some(title) = &entry.title {
-                // Try DBLP
-                if let Some(ref client) = self.dblp {
-                    match client.search_by_title(title).await {
-                        Ok(results) => {
-                            if let Some((matched, confidence)) = find_best_match(entry, &results) {
-                                let discrepancies = compare_entries(entry, matched);
-                                validation_results.push(ValidationResult {
-                                    source: ApiSource::Dblp,
-                                    matched_entry: Some(matched.clone()),
-                                    confidence,
-                                    discrepancies,
-                                });
-                            }
-                        }
-                        Err(e) => api_errors.push(format!("DBLP lookup failed: {}", e)),
-                    }
-                }
-
-                // Try Semantic Scholar
-                if let Some(ref client) = self.semantic {
-                    match client.search_by_title(title).await {
-                        Ok(results) => {
-                            if let Some((matched, confidence)) = find_best_match(entry, &results) {
-                                let discrepancies = compare_entries(entry, matched);
-                                validation_results.push(ValidationResult {
-                                    source: ApiSource::SemanticScholar,
-                                    matched_entry: Some(matched.clone()),
-                                    confidence,
-                                    discrepancies,
-                                });
-                            }
-                        }
-                        Err(e) => api_errors.push(format!("Semantic Scholar lookup failed: {}", e)),
-                    }
-                }
-
-                // Try OpenAlex
-                if let Some(ref client) = self.openalex {
-                    match client.search_by_title(title).await {
-                        Ok(results) => {
-                            if let Some((matched, confidence)) = find_best_match(entry, &results) {
-                                let discrepancies = compare_entries(entry, matched);
-                                validation_results.push(ValidationResult {
-                                    source: ApiSource::OpenAlex,
-                                    matched_entry: Some(matched.clone()),
-                                    confidence,
-                                    discrepancies,
-                                });
-                            }
-                        }
-                        Err(e) => api_errors.push(format!("OpenAlex lookup failed: {}", e)),
-                    }
-                }
-
-                // Try Open Library (good for older books)
-                if let Some(ref client) = self.openlibrary {
-                    match client.search_by_title(title).await {
-                        Ok(results) => {
-                            if let Some((matched, confidence)) = find_best_match(entry, &results) {
-                                let discrepancies = compare_entries(entry, matched);
-                                validation_results.push(ValidationResult {
-                                    source: ApiSource::OpenLibrary,
-                                    matched_entry: Some(matched.clone()),
-                                    confidence,
-                                    discrepancies,
-                                });
-                            }
-                        }
-                        Err(e) => api_errors.push(format!("Open Library lookup failed: {}", e)),
-                    }
-                }
-
-                // Try OpenReview (good for ML conference papers)
-                if let Some(ref client) = self.openreview {
-                    match client.search_by_title(title).await {
-                        Ok(results) => {
-                            if let Some((matched, confidence)) = find_best_match(entry, &results) {
-                                let discrepancies = compare_entries(entry, matched);
-                                validation_results.push(ValidationResult {
-                                    source: ApiSource::OpenReview,
-                                    matched_entry: Some(matched.clone()),
-                                    confidence,
-                                    discrepancies,
-                                });
-                            }
-                        }
-                        Err(e) => api_errors.push(format!("OpenReview lookup failed: {}", e)),
-                    }
-                }
-
-                // Try Zenodo (good for software and datasets)
-                if let Some(ref client) = self.zenodo {
-                    match client.search_by_title(title).await {
-                        Ok(results) => {
-                            if let Some((matched, confidence)) = find_best_match(entry, &results) {
-                                let discrepancies = compare_entries(entry, matched);
-                                validation_results.push(ValidationResult {
-                                    source: ApiSource::Zenodo,
-                                    matched_entry: Some(matched.clone()),
-                                    confidence,
-                                    discrepancies,
-                                });
-                            }
+        if let Some(ref client) = self.openalex {
+            let mut dois: Vec<String> = entries
+                .iter()
+                .filter_map(|(entry, _)| entry.doi.as_deref())
+                .map(normalize_doi)
+                .collect();
+            dois.sort();
+            dois.dedup();
+
+            for chunk in dois.chunks(OPENALEX_BATCH_SIZE) {
+                let batch = client.search_by_dois(chunk);
+                match self.throttles.run(ApiSource::OpenAlex, batch).await {
+                    Ok(mut found) => {
+                        for doi in chunk {
+                            prefetched.openalex.insert(doi.clone(), found.remove(doi));
                         }
-                        Err(e) => api_errors.push(format!("Zenodo lookup failed: {}", e)),
                     }
+                    Err(e) => tracing::debug!("OpenAlex batch lookup failed: {}", e),
+                }
+            }
+        }
+
+        prefetched
+    }
+
+    /// Validate a single entry against all configured APIs
+    async fn validate_entry(
+        &self,
+        entry: Entry,
+        mut lints: Vec<Discrepancy>,
+        run: &RunState,
+    ) -> EntryReport {
+        let mut trace = Trace::new(self.trace);
+
+        // Software, datasets and web pages aren't in bibliographic databases, so each
+        // kind of entry goes through its own lookups
+        let (mut validation_results, mut api_errors) = match entry.kind() {
+            EntryKind::Publication => self.match_publication(&entry, run, &mut trace).await,
+            EntryKind::Software | EntryKind::Dataset => {
+                self.match_release(&entry, run, &mut trace).await
+            }
+            EntryKind::Online => self.check_online(&entry).await,
+            EntryKind::Patent => self.match_patent(&entry, run).await,
+        };
+
+        // W3C specifications are cited as web pages and as reports alike
+        if matches!(entry.kind(), EntryKind::Publication | EntryKind::Online) {
+            let (results, errors) = self.match_w3c_spec(&entry, run).await;
+            validation_results.extend(results);
+            api_errors.extend(errors);
+        }
+
+        // A retracted paper is an error however well its citation matches. The notice is
+        // no match, so it is kept out of the fusion and goes after the consensus.
+        let mut retraction = None;
+        if let (EntryKind::Publication, Some(doi)) = (entry.kind(), &entry.doi) {
+            match self.check_retraction(doi).await {
+                Ok(Some(discrepancy)) => {
+                    retraction = Some(ValidationResult {
+                        source: ApiSource::CrossRef,
+                        matched_entry: None,
+                        confidence: 1.0,
+                        discrepancies: vec![discrepancy],
+                    })
                 }
+                Ok(None) => {}
+                Err(e) => api_errors.push(format!("Retraction check failed: {}", e)),
             }
         }
 
+        if entry.kind() == EntryKind::Publication {
+            let (results, errors) = self.check_journal_issn(&entry, run).await;
+            validation_results.extend(results);
+            api_errors.extend(errors);
+        }
+
+        if self.open_access {
+            lints.extend(paywalled(&entry, &validation_results));
+        }
+
+        // Findings below the minimum severity neither show up nor count towards the status
+        let min_severity = self.min_severity;
+        for result in &mut validation_results {
+            result.discrepancies.retain(|d| d.severity >= min_severity);
+        }
+        lints.retain(|d| d.severity >= min_severity);
+
         // Fuse results from all validators to find consensus
-        let fused = fuse_results(entry, &validation_results);
-        let mut report_results = validation_results.clone();
+        let mut fused = fuse_results(&entry, &validation_results);
+        fused.discrepancies.retain(|d| d.severity >= min_severity);
+
+        // Determine overall status based on fused results and individual validator findings
+        let consensus_index = validation_results.len();
+        if let Some(mut retraction) = retraction {
+            retraction.discrepancies.retain(|d| d.severity >= min_severity);
+            validation_results.push(retraction);
+        }
+        let status = compute_status(&fused, &validation_results, &api_errors);
+        let status = apply_lint_status(status, &lints);
 
+        // The consensus is the first result without a matched entry
         if fused.has_matches {
-            report_results.push(ValidationResult {
+            let consensus = ValidationResult {
                 source: *fused.sources.first().unwrap_or(&ApiSource::CrossRef),
                 matched_entry: None,
                 confidence: 1.0,
-                discrepancies: fused.discrepancies.clone(),
-            });
+                discrepancies: fused.discrepancies,
+            };
+            validation_results.insert(consensus_index, consensus);
         }
 
-        // Determine overall status based on fused results and individual validator findings
-        let status = compute_status(&fused, &validation_results, &api_errors);
+        let replacement = match (&status, self.dblp_bibtex) {
+            (EntryStatus::Warning | EntryStatus::Error, Some(format)) => {
+                self.dblp_replacement(&entry.key, &validation_results, format).await
+            }
+            _ => None,
+        };
+        let citations = if self.citation_counts {
+            citation_count(&validation_results)
+        } else {
+            None
+        };
+        let open_access = open_access(&validation_results, self.open_access);
+        let code = validation_results
+            .iter()
+            .find_map(|r| r.matched_entry.as_ref()?.field(CODE_REPOSITORY_FIELD))
+            .map(str::to_string);
 
         EntryReport {
-            entry: entry.clone(),
+            entry,
             status,
-            validation_results: report_results,
+            validation_results,
+            lints,
+            replacement,
+            citations,
+            open_access,
+            code,
+            trace: trace.steps,
         }
     }
 
-    async fn try_doi_lookup(
+    /// Match a patent by its publication number in EPO Open Patent Services
+    async fn match_patent(
         &self,
-        client: &CrossRefClient,
-        doi: &str,
-    ) -> Result<Option<Entry>, ValidatorError> {
-        // Check cache first
-        if let Some(cached) = self.cache.get::<Entry>("crossref_doi", doi) {
-            return Ok(Some(cached));
+        entry: &Entry,
+        run: &RunState,
+    ) -> (Vec<ValidationResult>, Vec<String>) {
+        let (Some(client), Some(number)) = (&self.epo, patent_number(entry)) else {
+            return (Vec::new(), Vec::new());
+        };
+
+        let fetch = self.throttles.run(ApiSource::Epo, client.search_by_number(&number));
+        match run.ids.get_or_fetch(ApiSource::Epo, "patent", &number, fetch).await {
+            Ok(Some(result)) if is_valid_id_match(entry, &result) => {
+                let discrepancies = compare_entries(entry, &result);
+                let result = ValidationResult {
+                    source: ApiSource::Epo,
+                    confidence: id_match_confidence(ApiSource::Epo, &discrepancies),
+                    matched_entry: Some(result),
+                    discrepancies,
+                };
+                (vec![result], Vec::new())
+            }
+            Ok(_) => (Vec::new(), Vec::new()),
+            Err(e) => (Vec::new(), vec![format!("EPO patent lookup failed: {}", e)]),
         }
+    }
 
-        let result = client.search_by_doi(doi).await?;
+    /// Match a W3C specification by its technical report URL
+    async fn match_w3c_spec(
+        &self,
+        entry: &Entry,
+        run: &RunState,
+    ) -> (Vec<ValidationResult>, Vec<String>) {
+        let url = entry.url.as_deref().map(str::trim);
+        let (Some(client), Some(url)) = (&self.w3c, url.filter(|u| tr_url(u).is_some())) else {
+            return (Vec::new(), Vec::new());
+        };
 
-        // Cache the result
-        if let Some(ref entry) = result {
-            let _ = self.cache.set("crossref_doi", doi, entry);
+        let fetch = self.throttles.run(ApiSource::W3c, client.search_by_url(url));
+        match run.ids.get_or_fetch(ApiSource::W3c, "w3c", url, fetch).await {
+            Ok(Some(result)) if is_valid_id_match(entry, &result) => {
+                let discrepancies = compare_entries(entry, &result);
+                let result = ValidationResult {
+                    source: ApiSource::W3c,
+                    confidence: id_match_confidence(ApiSource::W3c, &discrepancies),
+                    matched_entry: Some(result),
+                    discrepancies,
+                };
+                (vec![result], Vec::new())
+            }
+            Ok(_) => (Vec::new(), Vec::new()),
+            Err(e) => (Vec::new(), vec![format!("W3C specification lookup failed: {}", e)]),
         }
-
-        Ok(result)
     }
-}
-
-/// Check if a matched entry from ID lookup is valid (title similar enough, year compatible)
-fn is_valid_id_match(local: &Entry, remote: &Entry) -> bool {
-    let title_sim = title_similarity(local, remote);
-    let years_ok = years_compatible(local, remote);
 
-    // For ID-based lookups (DOI, arXiv), we're more lenient on title
-    // but still require some similarity and year compatibility
-    title_sim >= MIN_TITLE_SIMILARITY_FOR_ID_LOOKUP && years_ok
-}
+    /// Check an entry's venue against the DOAJ record of the journal its ISSN names. Only
+    /// open-access journals are listed, so other ISSNs go unchecked.
+    async fn check_journal_issn(
+        &self,
+        entry: &Entry,
+        run: &RunState,
+    ) -> (Vec<ValidationResult>, Vec<String>) {
+        let (Some(client), Some(issn)) = (&self.doaj, entry.issn.clone()) else {
+            return (Vec::new(), Vec::new());
+        };
 
-fn determine_status(fused: &fusion::FusedResult) -> EntryStatus {
-    if !fused.has_matches {
-        return EntryStatus::NotFound;
+        let fetch = self.throttles.run(ApiSource::Doaj, client.search_by_issn(&issn));
+        match run.ids.get_or_fetch(ApiSource::Doaj, "issn", &issn, fetch).await {
+            Ok(Some(journal)) => {
+                let discrepancies = compare_journal(entry, &journal, &issn).into_iter().collect();
+                let result = ValidationResult {
+                    source: ApiSource::Doaj,
+                    matched_entry: None,
+                    confidence: 1.0,
+                    discrepancies,
+                };
+                (vec![result], Vec::new())
+            }
+            Ok(None) => (Vec::new(), Vec::new()),
+            Err(e) => (Vec::new(), vec![format!("DOAJ ISSN lookup failed: {}", e)]),
+        }
+    }
+
+    /// Look for update notices against a DOI, and report the most serious one
+    async fn check_retraction(&self, doi: &str) -> Result<Option<Discrepancy>, ValidatorError> {
+        let client = match (self.check_retractions, &self.crossref) {
+            (true, Some(client)) => client,
+            _ => return Ok(None),
+        };
+
+        let doi = normalize_doi(doi);
+        let notices = match self.cache.get::<Vec<UpdateNotice>>("crossref_updates", &doi).await {
+            Some(notices) => notices,
+            None => {
+                let request = client.update_notices(&doi);
+                let notices = self.throttles.run(ApiSource::CrossRef, request).await?;
+                let _ = self.cache.set("crossref_updates", &doi, &notices).await;
+                notices
+            }
+        };
+        Ok(retraction_discrepancy(&doi, &notices))
+    }
+
+    /// Match a publication by its identifiers, falling back to a title search
+    async fn match_publication(
+        &self,
+        entry: &Entry,
+        run: &RunState,
+        trace: &mut Trace,
+    ) -> (Vec<ValidationResult>, Vec<String>) {
+        let mut validation_results = Vec::new();
+        let mut api_errors = Vec::new();
+
+        // Identifier lookups (DOI, arXiv ID) are the most reliable. Without a short-circuit
+        // policy they all run at once; with one they run in waves in reporting order, and
+        // later waves are skipped once enough sources agree with the entry.
+        let wave_size = self.short_circuit.map_or(usize::MAX, |p| p.min_agreeing);
+        let mut pending = self.id_lookups(entry, run).into_iter().peekable();
+        while pending.peek().is_some() {
+            let wave: Vec<_> = pending.by_ref().take(wave_size).collect();
+            for lookup in futures::future::join_all(wave).await {
+                trace_id_lookup(trace, lookup.label, entry, &lookup.result);
+                match lookup.result {
+                    Ok(Some(result)) => {
+                        // Validate that the returned paper actually matches; if not, the
+                        // identifier might be wrong, so silently skip it
+                        if is_valid_id_match(entry, &result) {
+                            let discrepancies = compare_entries(entry, &result);
+                            validation_results.push(ValidationResult {
+                                source: lookup.source,
+                                confidence: id_match_confidence(lookup.source, &discrepancies),
+                                matched_entry: Some(result),
+                                discrepancies,
+                            });
+                        }
+                    }
+                    Ok(None) => {}
+                    Err(e) => api_errors.push(format!("{} failed: {}", lookup.label, e)),
+                }
+            }
+
+            if self.short_circuit.is_some_and(|p| p.is_satisfied(&validation_results)) {
+                if pending.peek().is_some() {
+                    trace.record(|| {
+                        "Skipped the remaining identifier lookups: enough sources agree".into()
+                    });
+                }
+                break;
+            }
+        }
+
+        // A DOI none of the sources know may be registered with an agency they don't index
+        // (mEDRA, JaLC, KISTI, ...), whose metadata doi.org still serves
+        if let (true, Some(doi)) = (validation_results.is_empty(), &entry.doi) {
+            if let Some(client) = &self.doi_org {
+                let doi = normalize_doi(doi);
+                let fetch = self
+                    .throttles
+                    .run(ApiSource::DoiOrg, self.negotiate_doi(client, &doi));
+                let result = run.ids.get_or_fetch(ApiSource::DoiOrg, "doi", &doi, fetch).await;
+                trace_id_lookup(trace, "doi.org DOI lookup", entry, &result);
+                match result {
+                    Ok(Some(result)) if is_valid_id_match(entry, &result) => {
+                        let discrepancies = compare_entries(entry, &result);
+                        validation_results.push(ValidationResult {
+                            source: ApiSource::DoiOrg,
+                            confidence: id_match_confidence(ApiSource::DoiOrg, &discrepancies),
+                            matched_entry: Some(result),
+                            discrepancies,
+                        });
+                    }
+                    Ok(_) => {}
+                    Err(e) => api_errors.push(format!("doi.org DOI lookup failed: {}", e)),
+                }
+            }
+        }
+
+        // A DOI that doesn't resolve is often a mangled copy of one that does
+        if let (true, Some(doi)) = (validation_results.is_empty(), &entry.doi) {
+            if let Some(client) = &self.doi_org {
+                match self.repair_doi(client, entry, doi, run, trace).await {
+                    Ok(result) => validation_results.extend(result),
+                    Err(e) => api_errors.push(format!("doi.org DOI repair failed: {}", e)),
+                }
+            }
+        }
+
+        // If no exact matches, fan out title searches to every configured validator
+        if validation_results.is_empty() {
+            let searchers = self.title_searchers(entry);
+            let (results, errors) = self.search_titles(entry, &searchers, run, trace).await;
+            validation_results.extend(results);
+            api_errors.extend(errors);
+        } else {
+            trace.record(|| "Skipped title searches: an identifier matched".into());
+        }
+
+        // CORE aggregates repository copies of nearly everything, so it is only asked
+        // once no other source knows the entry
+        if let (true, Some(client)) = (validation_results.is_empty(), &self.core) {
+            let searchers: [(ApiSource, &dyn Validator); 1] = [(ApiSource::Core, client)];
+            let (results, errors) = self.search_titles(entry, &searchers, run, trace).await;
+            validation_results.extend(results);
+            api_errors.extend(errors);
+        }
+
+        // OpenCitations casts the deciding vote when CrossRef and Semantic Scholar disagree
+        let disputed = disputed_doi(&validation_results);
+        if let (Some(doi), Some(client)) = (disputed, &self.opencitations) {
+            let fetch = self
+                .throttles
+                .run(ApiSource::OpenCitations, client.search_by_doi(&doi));
+            let result = run.ids.get_or_fetch(ApiSource::OpenCitations, "doi", &doi, fetch).await;
+            trace_id_lookup(trace, "OpenCitations DOI lookup", entry, &result);
+            match result {
+                Ok(Some(result)) if is_valid_id_match(entry, &result) => {
+                    let discrepancies = compare_entries(entry, &result);
+                    validation_results.push(ValidationResult {
+                        source: ApiSource::OpenCitations,
+                        confidence: id_match_confidence(ApiSource::OpenCitations, &discrepancies),
+                        matched_entry: Some(result),
+                        discrepancies,
+                    });
+                }
+                Ok(_) => {}
+                Err(e) => api_errors.push(format!("OpenCitations DOI lookup failed: {}", e)),
+            }
+        }
+
+        (validation_results, api_errors)
+    }
+
+    /// Fetch a DOI's metadata from doi.org, unless CrossRef registered it and was already
+    /// asked
+    async fn negotiate_doi(
+        &self,
+        client: &DoiOrgClient,
+        doi: &str,
+    ) -> Result<Option<Entry>, ValidatorError> {
+        match client.registration_agency(doi).await? {
+            Some(agency) if agency != "Crossref" || self.crossref.is_none() => {
+                client.search_by_doi(doi).await
+            }
+            _ => Ok(None),
+        }
+    }
+
+    /// Look up the variants of a DOI that doesn't exist (see [`lint::doi_repairs`]) and
+    /// match the entry against the first one doi.org resolves to the same work
+    async fn repair_doi(
+        &self,
+        client: &DoiOrgClient,
+        entry: &Entry,
+        doi: &str,
+        run: &RunState,
+        trace: &mut Trace,
+    ) -> Result<Option<ValidationResult>, Arc<ValidatorError>> {
+        let repairs = lint::doi_repairs(doi);
+        if repairs.is_empty() {
+            return Ok(None);
+        }
+        let agency = self
+            .throttles
+            .run(ApiSource::DoiOrg, client.registration_agency(doi))
+            .await
+            .map_err(Arc::new)?;
+        if agency.is_some() {
+            return Ok(None);
+        }
+
+        for repaired in repairs {
+            let fetch = self
+                .throttles
+                .run(ApiSource::DoiOrg, client.search_by_doi(&repaired));
+            let result = run
+                .ids
+                .get_or_fetch(ApiSource::DoiOrg, "repaired-doi", &repaired, fetch)
+                .await;
+            trace_id_lookup(trace, "doi.org repaired DOI lookup", entry, &result);
+            let Some(mut result) = result?.filter(|r| is_valid_id_match(entry, r)) else {
+                continue;
+            };
+            let mut discrepancies = compare_entries(entry, &result);
+            discrepancies.push(Discrepancy {
+                field: DiscrepancyField::Doi,
+                severity: Severity::Error,
+                local_value: doi.to_string(),
+                remote_value: repaired.clone(),
+                message: format!("DOI does not resolve; did you mean {}?", repaired),
+            });
+            result.doi = Some(repaired);
+            return Ok(Some(ValidationResult {
+                source: ApiSource::DoiOrg,
+                confidence: id_match_confidence(ApiSource::DoiOrg, &discrepancies),
+                matched_entry: Some(result),
+                discrepancies,
+            }));
+        }
+        Ok(None)
+    }
+
+    /// Match software or a dataset against the DOI registries that hold releases, and
+    /// check the cited version against the release
+    async fn match_release(
+        &self,
+        entry: &Entry,
+        run: &RunState,
+        trace: &mut Trace,
+    ) -> (Vec<ValidationResult>, Vec<String>) {
+        let mut validation_results = Vec::new();
+        let mut api_errors = Vec::new();
+
+        if let (Some(doi), Some(client)) = (&entry.doi, &self.datacite) {
+            let doi = normalize_doi(doi);
+            let fetch = self
+                .throttles
+                .run(ApiSource::DataCite, client.search_by_doi(&doi));
+            let result = run.ids.get_or_fetch(ApiSource::DataCite, "doi", &doi, fetch).await;
+            trace_id_lookup(trace, "DataCite DOI lookup", entry, &result);
+            match result {
+                Ok(Some(result)) if is_valid_id_match(entry, &result) => {
+                    let discrepancies = compare_entries(entry, &result);
+                    validation_results.push(ValidationResult {
+                        source: ApiSource::DataCite,
+                        confidence: id_match_confidence(ApiSource::DataCite, &discrepancies),
+                        matched_entry: Some(result),
+                        discrepancies,
+                    });
+                }
+                Ok(_) => {}
+                Err(e) => api_errors.push(format!("DataCite DOI lookup failed: {}", e)),
+            }
+        }
+
+        // figshare's own record carries the item's version history
+        let figshare_doi = entry.doi.as_deref().filter(|doi| figshare_article(doi).is_some());
+        if let (Some(doi), Some(client)) = (figshare_doi, &self.figshare) {
+            let doi = normalize_doi(doi);
+            let fetch = self
+                .throttles
+                .run(ApiSource::Figshare, client.search_by_doi(&doi));
+            let result = run.ids.get_or_fetch(ApiSource::Figshare, "doi", &doi, fetch).await;
+            trace_id_lookup(trace, "figshare DOI lookup", entry, &result);
+            match result {
+                Ok(Some(result)) if is_valid_id_match(entry, &result) => {
+                    let discrepancies = compare_entries(entry, &result);
+                    validation_results.push(ValidationResult {
+                        source: ApiSource::Figshare,
+                        confidence: id_match_confidence(ApiSource::Figshare, &discrepancies),
+                        matched_entry: Some(result),
+                        discrepancies,
+                    });
+                }
+                Ok(_) => {}
+                Err(e) => api_errors.push(format!("figshare DOI lookup failed: {}", e)),
+            }
+        }
+
+        if validation_results.is_empty() {
+            let mut searchers: Vec<(ApiSource, &dyn Validator)> = Vec::new();
+            if let Some(client) = &self.zenodo {
+                searchers.push((ApiSource::Zenodo, client));
+            }
+            if let Some(client) = &self.datacite {
+                searchers.push((ApiSource::DataCite, client));
+            }
+            if let Some(client) = &self.figshare {
+                searchers.push((ApiSource::Figshare, client));
+            }
+            let (results, errors) = self.search_titles(entry, &searchers, run, trace).await;
+            validation_results.extend(results);
+            api_errors.extend(errors);
+        }
+
+        for result in &mut validation_results {
+            if let Some(matched) = &result.matched_entry {
+                let findings = artifacts::check_release(entry, matched);
+                result.discrepancies.extend(findings);
+            }
+        }
+
+        // Zenodo keeps every version archived under the concept DOI, so a cited version
+        // missing there was withdrawn or never released
+        let concept = validation_results
+            .iter()
+            .filter_map(|r| r.matched_entry.as_ref())
+            .find_map(|m| match m.field(CONCEPT_DOI_FIELD) {
+                Some(concept) => Some(concept),
+                None => m.field(VERSION_COUNT_FIELD).and(m.doi.as_deref()),
+            })
+            .map(normalize_doi)
+            .filter(|doi| doi.starts_with("10.5281/zenodo."));
+        if let (Some(concept), Some(_), Some(client)) =
+            (concept, entry.field("version"), &self.zenodo)
+        {
+            let fetch = self
+                .throttles
+                .run(ApiSource::Zenodo, client.search_versions(&concept));
+            match run.ids.get_or_fetch(ApiSource::Zenodo, "versions", &concept, fetch).await {
+                Ok(Some(versions)) => {
+                    if let Some(finding) = artifacts::check_archived_version(entry, &versions) {
+                        validation_results.push(ValidationResult {
+                            source: ApiSource::Zenodo,
+                            matched_entry: None,
+                            confidence: 1.0,
+                            discrepancies: vec![finding],
+                        });
+                    }
+                }
+                Ok(None) => {}
+                Err(e) => api_errors.push(format!("Zenodo versions lookup failed: {}", e)),
+            }
+        }
+
+        if entry.kind() == EntryKind::Software {
+            let (results, errors) = self.check_software_repository(entry, run).await;
+            validation_results.extend(results);
+            api_errors.extend(errors);
+        }
+
+        (validation_results, api_errors)
+    }
+
+    /// Check that the repository a software entry cites still exists and has the cited
+    /// version, and suggest the identifiers its archived copies can be cited by
+    async fn check_software_repository(
+        &self,
+        entry: &Entry,
+        run: &RunState,
+    ) -> (Vec<ValidationResult>, Vec<String>) {
+        let Some(url) = repository_url(entry) else {
+            return (Vec::new(), Vec::new());
+        };
+        let mut validation_results = Vec::new();
+        let mut api_errors = Vec::new();
+
+        let mut release = None;
+        if let (Some((owner, name)), Some(client)) = (github_repository(url), &self.github) {
+            let key = format!("{}/{}", owner, name);
+            let fetch = self
+                .throttles
+                .run(ApiSource::GitHub, client.search_by_repository(&owner, &name));
+            match run.ids.get_or_fetch(ApiSource::GitHub, "repository", &key, fetch).await {
+                Ok(repository) => {
+                    let discrepancies =
+                        artifacts::check_repository(entry, url, repository.as_ref());
+                    let tag = repository.as_ref().and_then(|r| artifacts::release_tag(entry, r));
+                    release = tag.map(|tag| (owner, name, tag.to_string()));
+                    // The repository is the record, so the entry is matched against itself
+                    validation_results.push(ValidationResult {
+                        source: ApiSource::GitHub,
+                        matched_entry: repository.map(|_| entry.clone()),
+                        confidence: 1.0,
+                        discrepancies,
+                    });
+                }
+                Err(e) => api_errors.push(format!("GitHub repository lookup failed: {}", e)),
+            }
+        }
+
+        let archived = match &self.software_heritage {
+            Some(client) => {
+                let version = entry.field("version");
+                let key = format!("{}@{}", url, version.unwrap_or_default());
+                let fetch = self
+                    .throttles
+                    .run(ApiSource::SoftwareHeritage, client.search_by_origin(url, version));
+                let record = run
+                    .ids
+                    .get_or_fetch(ApiSource::SoftwareHeritage, "origin", &key, fetch)
+                    .await;
+                match record {
+                    Ok(record) => Some(record),
+                    Err(e) => {
+                        api_errors.push(format!("Software Heritage lookup failed: {}", e));
+                        None
+                    }
+                }
+            }
+            None => None,
+        };
+
+        // Zenodo's GitHub integration archives each release under its own DOI
+        let zenodo = match (&release, &self.zenodo) {
+            (Some((owner, name, tag)), Some(client)) => {
+                let key = format!("{}/{}@{}", owner, name, tag);
+                let fetch = self
+                    .throttles
+                    .run(ApiSource::Zenodo, client.search_by_release(owner, name, tag));
+                match run.ids.get_or_fetch(ApiSource::Zenodo, "release", &key, fetch).await {
+                    Ok(record) => record,
+                    Err(e) => {
+                        api_errors.push(format!("Zenodo release lookup failed: {}", e));
+                        None
+                    }
+                }
+            }
+            _ => None,
+        };
+
+        let archived = archived.as_ref().map(Option::as_ref);
+        for suggestion in artifacts::suggest_archives(entry, archived, zenodo.as_ref()) {
+            let source = match suggestion.field {
+                DiscrepancyField::Doi => ApiSource::Zenodo,
+                _ => ApiSource::SoftwareHeritage,
+            };
+            validation_results.push(ValidationResult {
+                source,
+                matched_entry: None,
+                confidence: 1.0,
+                discrepancies: vec![suggestion],
+            });
+        }
+
+        (validation_results, api_errors)
+    }
+
+    /// Check that an @online entry's URL still resolves and has been archived
+    async fn check_online(&self, entry: &Entry) -> (Vec<ValidationResult>, Vec<String>) {
+        let (Some(client), Some(url)) = (&self.web, entry.url.as_deref()) else {
+            return (Vec::new(), Vec::new());
+        };
+        let url = url.trim();
+
+        let check = match self.throttles.run(ApiSource::Web, client.check_url(url)).await {
+            Ok(check) => check,
+            Err(e) => return (Vec::new(), vec![format!("URL check failed: {}", e)]),
+        };
+        let mut api_errors = Vec::new();
+        // Only snapshots that exist are cached, since pages can be archived at any time
+        let snapshot = match self.cache.get::<String>("wayback", url).await {
+            Some(cached) => Some(Some(cached)),
+            None => match self
+                .throttles
+                .run(ApiSource::Web, client.archived_snapshot(url))
+                .await
+            {
+                Ok(snapshot) => {
+                    if let Some(snapshot) = &snapshot {
+                        let _ = self.cache.set("wayback", url, snapshot).await;
+                    }
+                    Some(snapshot)
+                }
+                Err(e) => {
+                    api_errors.push(format!("Wayback Machine lookup failed: {}", e));
+                    None
+                }
+            },
+        };
+
+        let discrepancies =
+            artifacts::check_web_page(url, &check, snapshot.as_ref().map(Option::as_deref));
+        // The page itself is the record, so the entry is matched against itself at the
+        // address the URL resolved to
+        let mut matched = entry.clone();
+        matched.url = Some(check.final_url);
+        let result = ValidationResult {
+            source: ApiSource::Web,
+            matched_entry: Some(matched),
+            confidence: 1.0,
+            discrepancies,
+        };
+
+        (vec![result], api_errors)
+    }
+
+    /// Search the given validators by title and keep each one's best match
+    async fn search_titles(
+        &self,
+        entry: &Entry,
+        searchers: &[(ApiSource, &dyn Validator)],
+        run: &RunState,
+        trace: &mut Trace,
+    ) -> (Vec<ValidationResult>, Vec<String>) {
+        let mut validation_results = Vec::new();
+        let mut api_errors = Vec::new();
+        let Some(title) = &entry.title else {
+            trace.record(|| "Skipped title searches: the entry has no title".into());
+            return (validation_results, api_errors);
+        };
+
+        let searches = searchers.iter().map(|(source, client)| {
+            let fetch = self.throttles.run(*source, client.search_by_title(title));
+            run.titles.get_or_fetch(*source, "title", title, fetch)
+        });
+        let responses = futures::future::join_all(searches).await;
+
+        for ((source, _), response) in searchers.iter().zip(responses) {
+            match response {
+                Ok(results) => {
+                    trace_candidates(trace, *source, entry, &results);
+                    if let Some((matched, confidence)) = find_best_match(entry, &results) {
+                        trace.record(|| {
+                            format!("  matched {}: confidence {:.2}", describe(matched), confidence)
+                        });
+                        let discrepancies = compare_entries(entry, matched);
+                        validation_results.push(ValidationResult {
+                            source: *source,
+                            matched_entry: Some(matched.clone()),
+                            confidence,
+                            discrepancies,
+                        });
+                    }
+                }
+                Err(e) => {
+                    trace.record(|| format!("{} title search failed: {}", source, e));
+                    api_errors.push(format!("{} lookup failed: {}", source, e));
+                }
+            }
+        }
+
+        (validation_results, api_errors)
+    }
+
+    /// DBLP's curated BibTeX for the record an entry matched, keyed by the entry's key
+    async fn dblp_replacement(
+        &self,
+        key: &str,
+        results: &[ValidationResult],
+        format: DblpBibtexFormat,
+    ) -> Option<Replacement> {
+        let client = self.dblp.as_ref()?;
+        let dblp_key = results
+            .iter()
+            .filter(|r| r.source == ApiSource::Dblp)
+            .find_map(|r| r.matched_entry.as_ref()?.field(DBLP_KEY_FIELD))?;
+
+        let namespace = match format {
+            DblpBibtexFormat::Condensed => "dblp_bibtex_condensed",
+            DblpBibtexFormat::Standard => "dblp_bibtex_standard",
+        };
+        let bibtex = match self.cache.get::<String>(namespace, dblp_key).await {
+            Some(cached) => cached,
+            None => {
+                let request = client.fetch_bibtex(dblp_key, format);
+                match self.throttles.run(ApiSource::Dblp, request).await {
+                    Ok(Some(bibtex)) => {
+                        let _ = self.cache.set(namespace, dblp_key, &bibtex).await;
+                        bibtex
+                    }
+                    Ok(None) => return None,
+                    Err(e) => {
+                        tracing::debug!("DBLP BibTeX for {} failed: {}", dblp_key, e);
+                        return None;
+                    }
+                }
+            }
+        };
+
+        Some(Replacement {
+            source: ApiSource::Dblp,
+            bibtex: rekey_bibtex(&bibtex, key)?,
+        })
+    }
+
+    /// Build the identifier lookups that apply to an entry, in reporting order
+    fn id_lookups<'a>(
+        &'a self,
+        entry: &'a Entry,
+        run: &'a RunState,
+    ) -> Vec<BoxFuture<'a, IdLookup>> {
+        let prefetched = &run.prefetched;
+        let mut lookups = Vec::new();
+
+        // Repository DOIs are registered with DataCite, and CrossRef would only 404
+        let datacite_doi = entry.doi.as_deref().filter(|doi| is_datacite_doi(doi));
+        if let (Some(doi), Some(client)) = (datacite_doi, &self.datacite) {
+            lookups.push(
+                async move {
+                    let fetch = self.throttles.run(ApiSource::DataCite, client.search_by_doi(doi));
+                    let doi = normalize_doi(doi);
+                    IdLookup {
+                        source: ApiSource::DataCite,
+                        label: "DataCite DOI lookup",
+                        result: run
+                            .ids
+                            .get_or_fetch(ApiSource::DataCite, "doi", &doi, fetch)
+                            .await,
+                    }
+                }
+                .boxed(),
+            );
+        }
+
+        // Posters and figures cited as publications still have figshare's record
+        let figshare_doi = entry.doi.as_deref().filter(|doi| figshare_article(doi).is_some());
+        if let (Some(doi), Some(client)) = (figshare_doi, &self.figshare) {
+            lookups.push(
+                async move {
+                    let fetch = self.throttles.run(ApiSource::Figshare, client.search_by_doi(doi));
+                    let doi = normalize_doi(doi);
+                    IdLookup {
+                        source: ApiSource::Figshare,
+                        label: "figshare DOI lookup",
+                        result: run
+                            .ids
+                            .get_or_fetch(ApiSource::Figshare, "doi", &doi, fetch)
+                            .await,
+                    }
+                }
+                .boxed(),
+            );
+        }
+
+        // A local DBLP dump answers DOI lookups without a request
+        if let (Some(doi), Some(dump)) = (&entry.doi, &self.dblp_dump) {
+            lookups.push(
+                async move {
+                    let doi = normalize_doi(doi);
+                    IdLookup {
+                        source: ApiSource::Dblp,
+                        label: "DBLP dump DOI lookup",
+                        result: run
+                            .ids
+                            .get_or_fetch(ApiSource::Dblp, "doi", &doi, dump.search_by_doi(&doi))
+                            .await,
+                    }
+                }
+                .boxed(),
+            );
+        }
+
+        let crossref_doi = entry.doi.as_deref().filter(|doi| !self.leaves_to_datacite(doi));
+        if let (Some(doi), Some(client)) = (crossref_doi, &self.crossref) {
+            lookups.push(
+                async move {
+                    let fetch = self.try_doi_lookup(client, doi, prefetched);
+                    IdLookup {
+                        source: ApiSource::CrossRef,
+                        label: "CrossRef DOI lookup",
+                        result: run
+                            .ids
+                            .get_or_fetch(ApiSource::CrossRef, "doi", &normalize_doi(doi), fetch)
+                            .await,
+                    }
+                }
+                .boxed(),
+            );
+        }
+
+        if let Some(arxiv_id) = &entry.arxiv_id {
+            if let Some(client) = &self.arxiv {
+                lookups.push(
+                    async move {
+                        let fetch = self
+                            .throttles
+                            .run(ApiSource::ArXiv, client.search_by_arxiv_id(arxiv_id));
+                        IdLookup {
+                            source: ApiSource::ArXiv,
+                            label: "ArXiv lookup",
+                            result: run
+                                .ids
+                                .get_or_fetch(ApiSource::ArXiv, "arxiv", arxiv_id, fetch)
+                                .await,
+                        }
+                    }
+                    .boxed(),
+                );
+            }
+
+            // Also try Semantic Scholar with arXiv ID
+            if let Some(client) = &self.semantic {
+                lookups.push(
+                    async move {
+                        let id = semantic_arxiv_id(arxiv_id);
+                        let fetch = async {
+                            match prefetched.semantic.get(&id) {
+                                Some(batched) => Ok(batched.clone()),
+                                None => {
+                                    let request = client.search_by_arxiv_id(arxiv_id);
+                                    self.throttles.run(ApiSource::SemanticScholar, request).await
+                                }
+                            }
+                        };
+                        IdLookup {
+                            source: ApiSource::SemanticScholar,
+                            label: "Semantic Scholar arXiv lookup",
+                            result: run
+                                .ids
+                                .get_or_fetch(ApiSource::SemanticScholar, "id", &id, fetch)
+                                .await,
+                        }
+                    }
+                    .boxed(),
+                );
+            }
+        } else if let (Some(doi), Some(client)) = (&entry.doi, &self.semantic) {
+            // Without an arXiv ID, ask Semantic Scholar about the DOI instead
+            lookups.push(
+                async move {
+                    let id = semantic_doi_id(doi);
+                    let fetch = async {
+                        match prefetched.semantic.get(&id) {
+                            Some(batched) => Ok(batched.clone()),
+                            None => {
+                                let request = client.search_by_doi(doi);
+                                self.throttles.run(ApiSource::SemanticScholar, request).await
+                            }
+                        }
+                    };
+                    IdLookup {
+                        source: ApiSource::SemanticScholar,
+                        label: "Semantic Scholar DOI lookup",
+                        result: run
+                            .ids
+                            .get_or_fetch(ApiSource::SemanticScholar, "id", &id, fetch)
+                            .await,
+                    }
+                }
+                .boxed(),
+            );
+        }
+
+        if let (Some(doi), Some(client)) = (&entry.doi, &self.openalex) {
+            lookups.push(
+                async move {
+                    let doi = normalize_doi(doi);
+                    let fetch = async {
+                        match prefetched.openalex.get(&doi) {
+                            Some(batched) => Ok(batched.clone()),
+                            None => {
+                                let request = client.search_by_doi(&doi);
+                                self.throttles.run(ApiSource::OpenAlex, request).await
+                            }
+                        }
+                    };
+                    IdLookup {
+                        source: ApiSource::OpenAlex,
+                        label: "OpenAlex DOI lookup",
+                        result: run
+                            .ids
+                            .get_or_fetch(ApiSource::OpenAlex, "doi", &doi, fetch)
+                            .await,
+                    }
+                }
+                .boxed(),
+            );
+        }
+
+        if let (Some(doi), Some(client)) = (&entry.doi, &self.acm) {
+            if is_acm_doi(doi) {
+                lookups.push(
+                    async move {
+                        let fetch = self.throttles.run(ApiSource::Acm, client.search_by_doi(doi));
+                        IdLookup {
+                            source: ApiSource::Acm,
+                            label: "ACM DL DOI lookup",
+                            result: run
+                                .ids
+                                .get_or_fetch(ApiSource::Acm, "doi", &normalize_doi(doi), fetch)
+                                .await,
+                        }
+                    }
+                    .boxed(),
+                );
+            }
+        }
+
+        if let (Some(doi), Some(client)) = (&entry.doi, &self.springer) {
+            if is_springer_doi(doi) {
+                lookups.push(
+                    async move {
+                        let fetch = self
+                            .throttles
+                            .run(ApiSource::Springer, client.search_by_doi(doi));
+                        let doi = normalize_doi(doi);
+                        IdLookup {
+                            source: ApiSource::Springer,
+                            label: "Springer Nature DOI lookup",
+                            result: run
+                                .ids
+                                .get_or_fetch(ApiSource::Springer, "doi", &doi, fetch)
+                                .await,
+                        }
+                    }
+                    .boxed(),
+                );
+            }
+        }
+
+        if let (Some(doi), Some(client)) = (&entry.doi, &self.scopus) {
+            lookups.push(
+                async move {
+                    let fetch = self.throttles.run(ApiSource::Scopus, client.search_by_doi(doi));
+                    IdLookup {
+                        source: ApiSource::Scopus,
+                        label: "Scopus DOI lookup",
+                        result: run
+                            .ids
+                            .get_or_fetch(ApiSource::Scopus, "doi", &normalize_doi(doi), fetch)
+                            .await,
+                    }
+                }
+                .boxed(),
+            );
+        }
+
+        if let Some(client) = &self.wos {
+            if let Some(ut) = local_ut(entry) {
+                lookups.push(
+                    async move {
+                        let fetch = self
+                            .throttles
+                            .run(ApiSource::WebOfScience, client.search_by_ut(ut));
+                        IdLookup {
+                            source: ApiSource::WebOfScience,
+                            label: "Web of Science UT lookup",
+                            result: run
+                                .ids
+                                .get_or_fetch(ApiSource::WebOfScience, "ut", ut, fetch)
+                                .await,
+                        }
+                    }
+                    .boxed(),
+                );
+            } else if let Some(doi) = &entry.doi {
+                lookups.push(
+                    async move {
+                        let fetch = self
+                            .throttles
+                            .run(ApiSource::WebOfScience, client.search_by_doi(doi));
+                        let doi = normalize_doi(doi);
+                        IdLookup {
+                            source: ApiSource::WebOfScience,
+                            label: "Web of Science DOI lookup",
+                            result: run
+                                .ids
+                                .get_or_fetch(ApiSource::WebOfScience, "doi", &doi, fetch)
+                                .await,
+                        }
+                    }
+                    .boxed(),
+                );
+            }
+        }
+
+        // INSPIRE covers high-energy physics; entries it exported carry its texkey as
+        // their citation key
+        if let (true, Some(client)) = (is_hep(entry), &self.inspire) {
+            if is_texkey(&entry.key) {
+                let texkey = entry.key.as_str();
+                lookups.push(
+                    async move {
+                        let fetch = self
+                            .throttles
+                            .run(ApiSource::Inspire, client.search_by_texkey(texkey));
+                        IdLookup {
+                            source: ApiSource::Inspire,
+                            label: "INSPIRE texkey lookup",
+                            result: run
+                                .ids
+                                .get_or_fetch(ApiSource::Inspire, "texkey", texkey, fetch)
+                                .await,
+                        }
+                    }
+                    .boxed(),
+                );
+            } else if let Some(arxiv_id) = &entry.arxiv_id {
+                lookups.push(
+                    async move {
+                        let fetch = self
+                            .throttles
+                            .run(ApiSource::Inspire, client.search_by_arxiv_id(arxiv_id));
+                        IdLookup {
+                            source: ApiSource::Inspire,
+                            label: "INSPIRE arXiv lookup",
+                            result: run
+                                .ids
+                                .get_or_fetch(ApiSource::Inspire, "arxiv", arxiv_id, fetch)
+                                .await,
+                        }
+                    }
+                    .boxed(),
+                );
+            } else if let Some(doi) = &entry.doi {
+                lookups.push(
+                    async move {
+                        let fetch = self
+                            .throttles
+                            .run(ApiSource::Inspire, client.search_by_doi(doi));
+                        let doi = normalize_doi(doi);
+                        IdLookup {
+                            source: ApiSource::Inspire,
+                            label: "INSPIRE DOI lookup",
+                            result: run
+                                .ids
+                                .get_or_fetch(ApiSource::Inspire, "doi", &doi, fetch)
+                                .await,
+                        }
+                    }
+                    .boxed(),
+                );
+            }
+        }
+
+        // MRef matches the citation itself, so the record it finds confirms or refutes
+        // the entry's MR number
+        if let (Some(mr_number), Some(client)) = (&entry.mr_number, &self.mathscinet) {
+            lookups.push(
+                async move {
+                    let fetch = self
+                        .throttles
+                        .run(ApiSource::MathSciNet, client.search_by_reference(entry));
+                    IdLookup {
+                        source: ApiSource::MathSciNet,
+                        label: "MathSciNet MR lookup",
+                        result: run
+                            .ids
+                            .get_or_fetch(ApiSource::MathSciNet, "mr", mr_number, fetch)
+                            .await,
+                    }
+                }
+                .boxed(),
+            );
+        }
+
+        if let Some(client) = &self.ads {
+            if let Some(bibcode) = local_bibcode(entry) {
+                lookups.push(
+                    async move {
+                        let fetch = self
+                            .throttles
+                            .run(ApiSource::Ads, client.search_by_bibcode(bibcode));
+                        IdLookup {
+                            source: ApiSource::Ads,
+                            label: "NASA ADS bibcode lookup",
+                            result: run
+                                .ids
+                                .get_or_fetch(ApiSource::Ads, "bibcode", bibcode, fetch)
+                                .await,
+                        }
+                    }
+                    .boxed(),
+                );
+            } else if let Some(doi) = &entry.doi {
+                lookups.push(
+                    async move {
+                        let fetch = self.throttles.run(ApiSource::Ads, client.search_by_doi(doi));
+                        let doi = normalize_doi(doi);
+                        IdLookup {
+                            source: ApiSource::Ads,
+                            label: "NASA ADS DOI lookup",
+                            result: run
+                                .ids
+                                .get_or_fetch(ApiSource::Ads, "doi", &doi, fetch)
+                                .await,
+                        }
+                    }
+                    .boxed(),
+                );
+            } else if let Some(arxiv_id) = &entry.arxiv_id {
+                lookups.push(
+                    async move {
+                        let fetch = self
+                            .throttles
+                            .run(ApiSource::Ads, client.search_by_arxiv_id(arxiv_id));
+                        IdLookup {
+                            source: ApiSource::Ads,
+                            label: "NASA ADS arXiv lookup",
+                            result: run
+                                .ids
+                                .get_or_fetch(ApiSource::Ads, "arxiv", arxiv_id, fetch)
+                                .await,
+                        }
+                    }
+                    .boxed(),
+                );
+            }
+        }
+
+        // Unpaywall knows every Crossref DOI, so a match confirms the DOI is real
+        if let (Some(doi), Some(client)) = (&entry.doi, &self.unpaywall) {
+            lookups.push(
+                async move {
+                    let fetch = self
+                        .throttles
+                        .run(ApiSource::Unpaywall, client.search_by_doi(doi));
+                    let doi = normalize_doi(doi);
+                    IdLookup {
+                        source: ApiSource::Unpaywall,
+                        label: "Unpaywall DOI lookup",
+                        result: run
+                            .ids
+                            .get_or_fetch(ApiSource::Unpaywall, "doi", &doi, fetch)
+                            .await,
+                    }
+                }
+                .boxed(),
+            );
+        }
+
+        if let (Some(doi), Some(client)) = (&entry.doi, &self.osf) {
+            if osf_guid(doi).is_some() {
+                lookups.push(
+                    async move {
+                        let fetch = self.throttles.run(ApiSource::Osf, client.search_by_doi(doi));
+                        let doi = normalize_doi(doi);
+                        IdLookup {
+                            source: ApiSource::Osf,
+                            label: "OSF DOI lookup",
+                            result: run
+                                .ids
+                                .get_or_fetch(ApiSource::Osf, "doi", &doi, fetch)
+                                .await,
+                        }
+                    }
+                    .boxed(),
+                );
+            }
+        }
+
+        if let (Some(hal_id), Some(client)) = (&entry.hal_id, &self.hal) {
+            lookups.push(
+                async move {
+                    let fetch = self.throttles.run(ApiSource::Hal, client.search_by_hal_id(hal_id));
+                    IdLookup {
+                        source: ApiSource::Hal,
+                        label: "HAL ID lookup",
+                        result: run
+                            .ids
+                            .get_or_fetch(ApiSource::Hal, "hal", hal_id, fetch)
+                            .await,
+                    }
+                }
+                .boxed(),
+            );
+        }
+
+        // Papers With Code only covers machine learning
+        if let (true, Some(arxiv_id), Some(client)) =
+            (is_ml(entry), &entry.arxiv_id, &self.paperswithcode)
+        {
+            lookups.push(
+                async move {
+                    let fetch = self
+                        .throttles
+                        .run(ApiSource::PapersWithCode, client.search_by_arxiv_id(arxiv_id));
+                    IdLookup {
+                        source: ApiSource::PapersWithCode,
+                        label: "Papers With Code arXiv lookup",
+                        result: run
+                            .ids
+                            .get_or_fetch(ApiSource::PapersWithCode, "arxiv", arxiv_id, fetch)
+                            .await,
+                    }
+                }
+                .boxed(),
+            );
+        }
+
+        // RFCs are cited as reports or @misc, by number or DOI
+        if let (Some(number), Some(client)) = (rfc_number(entry), &self.ietf) {
+            lookups.push(
+                async move {
+                    let fetch = self.throttles.run(ApiSource::Ietf, client.search_by_rfc(number));
+                    IdLookup {
+                        source: ApiSource::Ietf,
+                        label: "RFC lookup",
+                        result: run
+                            .ids
+                            .get_or_fetch(ApiSource::Ietf, "rfc", &number.to_string(), fetch)
+                            .await,
+                    }
+                }
+                .boxed(),
+            );
+        }
+
+        if let Some(client) = &self.pubmed {
+            if let Some(pmid) = &entry.pmid {
+                lookups.push(
+                    async move {
+                        let fetch = self
+                            .throttles
+                            .run(ApiSource::PubMed, client.search_by_pmid(pmid));
+                        IdLookup {
+                            source: ApiSource::PubMed,
+                            label: "PubMed PMID lookup",
+                            result: run
+                                .ids
+                                .get_or_fetch(ApiSource::PubMed, "pmid", pmid, fetch)
+                                .await,
+                        }
+                    }
+                    .boxed(),
+                );
+            } else if let Some(pmcid) = &entry.pmcid {
+                lookups.push(
+                    async move {
+                        let fetch = self
+                            .throttles
+                            .run(ApiSource::PubMed, client.search_by_pmcid(pmcid));
+                        IdLookup {
+                            source: ApiSource::PubMed,
+                            label: "PubMed PMCID lookup",
+                            result: run
+                                .ids
+                                .get_or_fetch(ApiSource::PubMed, "pmcid", pmcid, fetch)
+                                .await,
+                        }
+                    }
+                    .boxed(),
+                );
+            }
+        }
+
+        // DOAJ only indexes journal articles
+        let article_doi = entry.doi.as_deref().filter(|doi| !is_datacite_doi(doi));
+        let is_article = entry.entry_type.eq_ignore_ascii_case("article");
+        if let (true, Some(doi), Some(client)) = (is_article, article_doi, &self.doaj) {
+            lookups.push(
+                async move {
+                    let doi = normalize_doi(doi);
+                    let fetch = self.throttles.run(ApiSource::Doaj, client.search_by_doi(&doi));
+                    IdLookup {
+                        source: ApiSource::Doaj,
+                        label: "DOAJ DOI lookup",
+                        result: run.ids.get_or_fetch(ApiSource::Doaj, "doi", &doi, fetch).await,
+                    }
+                }
+                .boxed(),
+            );
+        }
+
+        let scielo_doi = entry.doi.as_deref().filter(|doi| is_scielo_doi(doi));
+        if let (Some(doi), Some(client)) = (scielo_doi, &self.scielo) {
+            lookups.push(
+                async move {
+                    let doi = normalize_doi(doi);
+                    let fetch = self.throttles.run(ApiSource::Scielo, client.search_by_doi(&doi));
+                    IdLookup {
+                        source: ApiSource::Scielo,
+                        label: "SciELO DOI lookup",
+                        result: run.ids.get_or_fetch(ApiSource::Scielo, "doi", &doi, fetch).await,
+                    }
+                }
+                .boxed(),
+            );
+        }
+
+        let jstage_doi = entry.doi.as_deref().filter(|_| is_japanese(entry));
+        if let (Some(doi), Some(client)) = (jstage_doi, &self.jstage) {
+            lookups.push(
+                async move {
+                    let doi = normalize_doi(doi);
+                    let fetch = self.throttles.run(ApiSource::Jstage, client.search_by_doi(&doi));
+                    IdLookup {
+                        source: ApiSource::Jstage,
+                        label: "J-STAGE DOI lookup",
+                        result: run.ids.get_or_fetch(ApiSource::Jstage, "doi", &doi, fetch).await,
+                    }
+                }
+                .boxed(),
+            );
+        }
+
+        if let Some(client) = &self.worldcat {
+            if let Some(isbn) = entry.isbn.clone() {
+                lookups.push(
+                    async move {
+                        let fetch = self
+                            .throttles
+                            .run(ApiSource::WorldCat, client.search_by_isbn(&isbn));
+                        IdLookup {
+                            source: ApiSource::WorldCat,
+                            label: "WorldCat ISBN lookup",
+                            result: run
+                                .ids
+                                .get_or_fetch(ApiSource::WorldCat, "isbn", &isbn, fetch)
+                                .await,
+                        }
+                    }
+                    .boxed(),
+                );
+            } else if let Some(oclc) = entry.field(OCLC_FIELD) {
+                lookups.push(
+                    async move {
+                        let fetch = self
+                            .throttles
+                            .run(ApiSource::WorldCat, client.search_by_oclc(oclc));
+                        IdLookup {
+                            source: ApiSource::WorldCat,
+                            label: "WorldCat OCLC lookup",
+                            result: run
+                                .ids
+                                .get_or_fetch(ApiSource::WorldCat, "oclc", oclc, fetch)
+                                .await,
+                        }
+                    }
+                    .boxed(),
+                );
+            }
+        }
+
+        if let (true, Some(isbn)) = (entry.is_book_like(), &entry.isbn) {
+            for client in &self.sru {
+                let isbn = isbn.clone();
+                lookups.push(
+                    async move {
+                        let source = client.library().source();
+                        let fetch = self.throttles.run(source, client.search_by_isbn(&isbn));
+                        IdLookup {
+                            source,
+                            label: match client.library() {
+                                SruLibrary::Dnb => "DNB ISBN lookup",
+                                SruLibrary::BritishLibrary => "British Library ISBN lookup",
+                            },
+                            result: run.ids.get_or_fetch(source, "isbn", &isbn, fetch).await,
+                        }
+                    }
+                    .boxed(),
+                );
+            }
+        }
+
+        if let Some(client) = &self.hathitrust {
+            let id = entry
+                .isbn
+                .clone()
+                .map(|isbn| (BookId::Isbn, isbn))
+                .or_else(|| entry.field(OCLC_FIELD).map(|id| (BookId::Oclc, id.to_string())))
+                .or_else(|| entry.field("lccn").map(|id| (BookId::Lccn, id.to_string())));
+            if let Some((kind, id)) = id {
+                lookups.push(
+                    async move {
+                        let fetch = self
+                            .throttles
+                            .run(ApiSource::HathiTrust, client.lookup(kind, &id));
+                        let namespace = match kind {
+                            BookId::Isbn => "isbn",
+                            BookId::Oclc => "oclc",
+                            BookId::Lccn => "lccn",
+                        };
+                        IdLookup {
+                            source: ApiSource::HathiTrust,
+                            label: "HathiTrust catalog lookup",
+                            result: run
+                                .ids
+                                .get_or_fetch(ApiSource::HathiTrust, namespace, &id, fetch)
+                                .await,
+                        }
+                    }
+                    .boxed(),
+                );
+            }
+        }
+
+        lookups
+    }
+
+    /// Validators to query by title when no identifier lookup matched, in reporting order
+    fn title_searchers(&self, entry: &Entry) -> Vec<(ApiSource, &dyn Validator)> {
+        let mut searchers: Vec<(ApiSource, &dyn Validator)> = Vec::new();
+
+        if let Some(dump) = &self.dblp_dump {
+            searchers.push((ApiSource::Dblp, dump));
+        } else if let Some(client) = &self.dblp {
+            searchers.push((ApiSource::Dblp, client));
+        }
+        if let Some(client) = &self.semantic {
+            searchers.push((ApiSource::SemanticScholar, client));
+        }
+        // Entries with a DOI were already looked up in OpenAlex by ID
+        if let (None, Some(client)) = (&entry.doi, &self.openalex) {
+            searchers.push((ApiSource::OpenAlex, client));
+        }
+        // Open Library is good for older books
+        if let Some(client) = &self.openlibrary {
+            searchers.push((ApiSource::OpenLibrary, client));
+        }
+        // The ACM DL search is only worth a request for entries citing ACM venues
+        let acm_venue = entry
+            .venue
+            .as_deref()
+            .is_some_and(|v| v.contains("ACM") || v.contains("SIG"));
+        if let (None, true, Some(client)) = (&entry.doi, acm_venue, &self.acm) {
+            searchers.push((ApiSource::Acm, client));
+        }
+        if let (None, Some(client)) = (&entry.doi, &self.springer) {
+            searchers.push((ApiSource::Springer, client));
+        }
+        if let Some(client) = &self.scopus {
+            searchers.push((ApiSource::Scopus, client));
+        }
+        if let Some(client) = &self.wos {
+            searchers.push((ApiSource::WebOfScience, client));
+        }
+        if let Some(client) = &self.ads {
+            searchers.push((ApiSource::Ads, client));
+        }
+        // OSF hosts social-science preprints, cited as @misc or @unpublished or under
+        // their provider's name (PsyArXiv, SocArXiv, ...); arXiv preprints are not among them
+        let osf_venue = entry.venue.as_deref().is_some_and(|v| {
+            let v = v.to_lowercase();
+            v.contains("arxiv") || v.contains("preprint")
+        });
+        let unpublished = matches!(
+            entry.entry_type.to_lowercase().as_str(),
+            "misc" | "unpublished"
+        );
+        let osf_preprint = entry.arxiv_id.is_none() && (unpublished || osf_venue);
+        if let (None, true, Some(client)) = (&entry.doi, osf_preprint, &self.osf) {
+            searchers.push((ApiSource::Osf, client));
+        }
+        // HAL holds deposits, often without a DOI, from French and European institutions
+        if let (None, Some(client)) = (&entry.doi, &self.hal) {
+            searchers.push((ApiSource::Hal, client));
+        }
+        if let (true, Some(client)) = (is_hep(entry), &self.inspire) {
+            searchers.push((ApiSource::Inspire, client));
+        }
+        if let (true, Some(client)) = (is_ml(entry), &self.paperswithcode) {
+            searchers.push((ApiSource::PapersWithCode, client));
+        }
+        // PubMed indexes biomedical journal articles
+        let is_article = entry.entry_type.eq_ignore_ascii_case("article");
+        if let (true, Some(client)) = (is_article, &self.pubmed) {
+            searchers.push((ApiSource::PubMed, client));
+        }
+        // DOAJ indexes articles in open-access journals
+        if let (true, Some(client)) = (is_article, &self.doaj) {
+            searchers.push((ApiSource::Doaj, client));
+        }
+        // SciELO covers the Latin American and Iberian journals the others miss
+        if let (true, Some(client)) = (is_ibero_american(entry), &self.scielo) {
+            searchers.push((ApiSource::Scielo, client));
+        }
+        if let (true, Some(client)) = (is_japanese(entry), &self.jstage) {
+            searchers.push((ApiSource::Jstage, client));
+        }
+        // HathiTrust is good for scanned books that predate DOIs
+        if let (true, None, Some(client)) = (entry.is_book_like(), &entry.doi, &self.hathitrust) {
+            searchers.push((ApiSource::HathiTrust, client));
+        }
+        // OpenReview is good for ML conference papers
+        if let Some(client) = &self.openreview {
+            searchers.push((ApiSource::OpenReview, client));
+        }
+        // Zenodo is good for software and datasets
+        if let Some(client) = &self.zenodo {
+            searchers.push((ApiSource::Zenodo, client));
+        }
+        // WorldCat covers obscure books and reports, but not articles
+        if let (true, Some(client)) = (entry.is_book_like(), &self.worldcat) {
+            searchers.push((ApiSource::WorldCat, client));
+        }
+        // National libraries catalog books published in their countries, whatever the
+        // language
+        if entry.is_book_like() {
+            for client in &self.sru {
+                searchers.push((client.library().source(), client));
+            }
+        }
+
+        searchers
+    }
+
+    /// Whether a DOI is left to DataCite alone, since CrossRef would only 404 on it. Without
+    /// a DataCite client CrossRef still gets asked.
+    fn leaves_to_datacite(&self, doi: &str) -> bool {
+        self.datacite.is_some() && is_datacite_doi(doi)
+    }
+
+    async fn try_doi_lookup(
+        &self,
+        client: &CrossRefClient,
+        doi: &str,
+        prefetched: &Prefetched,
+    ) -> Result<Option<Entry>, ValidatorError> {
+        // The prefetch already looked in the cache and batched what it didn't find there
+        let key = normalize_doi(doi);
+        if let Some(prefetched) = prefetched.crossref.get(&key) {
+            return Ok(prefetched.clone());
+        }
+        if !prefetched.crossref_uncached.contains(&key) {
+            if let Some(cached) = self.cache.get::<Entry>("crossref_doi", doi).await {
+                return Ok(Some(cached));
+            }
+        }
+
+        let result = self
+            .throttles
+            .run(ApiSource::CrossRef, client.search_by_doi(doi))
+            .await?;
+
+        // Cache the result
+        if let Some(ref entry) = result {
+            let _ = self.cache.set("crossref_doi", doi, entry).await;
+        }
+
+        Ok(result)
+    }
+}
+
+/// Outcome of a single identifier-based lookup
+struct IdLookup {
+    source: ApiSource,
+    /// Description used in error messages, e.g. "CrossRef DOI lookup"
+    label: &'static str,
+    result: Result<Option<Entry>, Arc<ValidatorError>>,
+}
+
+/// Confidence assigned to a match found through an identifier
+fn id_match_confidence(source: ApiSource, discrepancies: &[Discrepancy]) -> f64 {
+    match source {
+        ApiSource::CrossRef if discrepancies.is_empty() => 1.0,
+        ApiSource::CrossRef => 0.8,
+        ApiSource::ArXiv => 0.95,
+        _ => 0.9,
+    }
+}
+
+/// The most serious of the notices against a DOI: a retraction, then an expression of
+/// concern, then a correction
+fn retraction_discrepancy(doi: &str, notices: &[UpdateNotice]) -> Option<Discrepancy> {
+    let rank = |notice: &UpdateNotice| match notice.kind.as_str() {
+        "retraction" | "partial_retraction" | "withdrawal" | "removal" => 3,
+        "expression_of_concern" => 2,
+        "correction" | "erratum" | "corrigendum" => 1,
+        _ => 0,
+    };
+    let notice = notices
+        .iter()
+        .filter(|n| rank(n) > 0)
+        .max_by_key(|n| rank(n))?;
+
+    let description = match rank(notice) {
+        3 => "The cited work has been retracted",
+        2 => "The cited work has an expression of concern",
+        _ => "The cited work has a correction",
+    };
+    let mut message = description.to_string();
+    if let Some(notice_doi) = &notice.notice_doi {
+        message.push_str(&format!(" (notice {})", notice_doi));
+    }
+    if notice.source.as_deref() == Some("retraction-watch") {
+        message.push_str(", per Retraction Watch");
+    }
+
+    Some(Discrepancy {
+        field: DiscrepancyField::Retraction,
+        severity: Severity::Error,
+        local_value: doi.to_string(),
+        remote_value: notice.kind.replace('_', " "),
+        message,
+    })
+}
+
+/// The DOI of a work whose CrossRef and Semantic Scholar records disagree on its year
+/// or venue
+fn disputed_doi(results: &[ValidationResult]) -> Option<String> {
+    let record = |source| {
+        results
+            .iter()
+            .find(|r| r.source == source)
+            .and_then(|r| r.matched_entry.as_ref())
+    };
+    let crossref = record(ApiSource::CrossRef)?;
+    let semantic = record(ApiSource::SemanticScholar)?;
+
+    // Venues are compared by canonical name where known, so "NeurIPS" matches
+    // "Advances in Neural Information Processing Systems"
+    let venue = |e: &Entry| {
+        e.venue.as_deref().map(|v| match venues::find_venue(v) {
+            Some(venue) => venue.name.to_string(),
+            None => entry::normalize_string(v),
+        })
+    };
+    let years_differ = matches!((crossref.year, semantic.year), (Some(a), Some(b)) if a != b);
+    let venues_differ = matches!(
+        (venue(crossref), venue(semantic)),
+        (Some(a), Some(b)) if a != b
+    );
+
+    if years_differ || venues_differ {
+        crossref.doi.as_deref().map(normalize_doi)
+    } else {
+        None
+    }
+}
+
+/// State shared by all entries validated in one run
+#[derive(Default)]
+struct RunState {
+    prefetched: Prefetched,
+    /// Identifier lookups, so entries sharing a DOI or arXiv ID query each API once
+    ids: Memo<Option<Entry>>,
+    /// Title searches, so duplicated works query each API once
+    titles: Memo<Vec<Entry>>,
+    checkpoint: Option<Checkpoint>,
+}
+
+/// Drop the run state once every entry got an answer; keep it if any lookups failed so
+/// `--resume` can retry just those
+fn finish_checkpoint(checkpoint: Option<Checkpoint>, any_failed: bool) {
+    let Some(checkpoint) = checkpoint else {
+        return;
+    };
+    if !any_failed {
+        if let Err(e) = checkpoint.finish() {
+            tracing::warn!("Could not remove run state: {}", e);
+        }
+    }
+}
+
+/// Identifier lookups fetched in batches before per-entry validation starts
+#[derive(Default)]
+struct Prefetched {
+    /// CrossRef works by normalized DOI, from the cache or a batch; `None` means the batch
+    /// found no such DOI
+    crossref: HashMap<String, Option<Entry>>,
+    /// Normalized DOIs the cache was already asked for and didn't have
+    crossref_uncached: HashSet<String>,
+    /// Semantic Scholar papers by prefixed ID (`DOI:...`, `ARXIV:...`)
+    semantic: HashMap<String, Option<Entry>>,
+    /// OpenAlex works by normalized DOI
+    openalex: HashMap<String, Option<Entry>>,
+}
+
+fn semantic_arxiv_id(arxiv_id: &str) -> String {
+    format!("ARXIV:{}", arxiv_id.trim())
+}
+
+fn semantic_doi_id(doi: &str) -> String {
+    format!("DOI:{}", normalize_doi(doi))
+}
+
+/// Check if a matched entry from ID lookup is valid (title similar enough, year compatible)
+fn is_valid_id_match(local: &Entry, remote: &Entry) -> bool {
+    id_match_rejection(local, remote).is_none()
+}
+
+/// Why a record found through an identifier isn't the cited work, or `None` if it is
+fn id_match_rejection(local: &Entry, remote: &Entry) -> Option<String> {
+    // For ID-based lookups (DOI, arXiv), we're more lenient on title
+    // but still require some similarity and year compatibility
+    let title_sim = title_similarity(local, remote);
+    if title_sim < MIN_TITLE_SIMILARITY_FOR_ID_LOOKUP {
+        return Some(format!(
+            "title similarity {:.2} is below {:.2}",
+            title_sim, MIN_TITLE_SIMILARITY_FOR_ID_LOOKUP
+        ));
+    }
+    if !years_compatible(local, remote) {
+        return Some(format!(
+            "year {} is too far from {}",
+            remote.year.unwrap_or_default(),
+            local.year.unwrap_or_default()
+        ));
+    }
+    None
+}
+
+/// The decisions made while validating one entry, recorded with `-vv` to explain how it
+/// got its status
+struct Trace {
+    enabled: bool,
+    steps: Vec<String>,
+}
+
+impl Trace {
+    fn new(enabled: bool) -> Self {
+        Self {
+            enabled,
+            steps: Vec::new(),
+        }
+    }
+
+    /// Record a step, formatting it only when tracing
+    fn record(&mut self, step: impl FnOnce() -> String) {
+        if self.enabled {
+            self.steps.push(step());
+        }
+    }
+}
+
+/// Record what an identifier lookup returned, and why a record it found was rejected
+fn trace_id_lookup<E: std::fmt::Display>(
+    trace: &mut Trace,
+    label: &str,
+    entry: &Entry,
+    result: &Result<Option<Entry>, E>,
+) {
+    trace.record(|| match result {
+        Ok(Some(found)) => match id_match_rejection(entry, found) {
+            Some(reason) => format!("{}: rejected {}: {}", label, describe(found), reason),
+            None => format!("{}: found {}", label, describe(found)),
+        },
+        Ok(None) => format!("{}: no record", label),
+        Err(e) => format!("{} failed: {}", label, e),
+    });
+}
+
+/// Record the candidates a title search returned, and why each was rejected
+fn trace_candidates(trace: &mut Trace, source: ApiSource, entry: &Entry, candidates: &[Entry]) {
+    trace.record(|| match candidates.len() {
+        0 => format!("{} title search: no candidates", source),
+        1 => format!("{} title search: 1 candidate", source),
+        n => format!("{} title search: {} candidates", source, n),
+    });
+    for candidate in candidates {
+        trace.record(|| match rejection(entry, candidate) {
+            Some(reason) => format!("  rejected {}: {}", describe(candidate), reason),
+            None => format!(
+                "  candidate {}: score {:.2}",
+                describe(candidate),
+                match_score(entry, candidate)
+            ),
+        });
+    }
+}
+
+/// A record's title and year, as traces name it
+fn describe(entry: &Entry) -> String {
+    let title = entry.title.as_deref().unwrap_or("(no title)");
+    match entry.year {
+        Some(year) => format!("\"{}\" ({})", title, year),
+        None => format!("\"{}\"", title),
+    }
+}
+
+/// The highest citation count any matched record reports; databases index different
+/// sets of citing works, so they rarely agree exactly
+fn citation_count(results: &[ValidationResult]) -> Option<CitationCount> {
+    results
+        .iter()
+        .filter_map(|r| {
+            let count = r.matched_entry.as_ref()?.field(CITATION_COUNT_FIELD)?;
+            Some(CitationCount {
+                source: r.source,
+                count: count.parse().ok()?,
+            })
+        })
+        .max_by_key(|c| c.count)
+}
+
+/// The open-access copy Unpaywall found for the matched work, or with `with_openalex`
+/// the one OpenAlex found when Unpaywall has none
+fn open_access(results: &[ValidationResult], with_openalex: bool) -> Option<OpenAccess> {
+    let sources: &[ApiSource] = if with_openalex {
+        &[ApiSource::Unpaywall, ApiSource::OpenAlex]
+    } else {
+        &[ApiSource::Unpaywall]
+    };
+    sources.iter().find_map(|&source| {
+        let matched = results
+            .iter()
+            .filter(|r| r.source == source)
+            .find_map(|r| r.matched_entry.as_ref())?;
+        Some(OpenAccess {
+            url: matched.field(OA_URL_FIELD)?.to_string(),
+            status: matched.field(OA_STATUS_FIELD).map(str::to_string),
+        })
+    })
+}
+
+/// A finding for a work Unpaywall or OpenAlex says is closed access, when no source knows
+/// of a free copy and the entry doesn't link to one (a URL or an arXiv eprint) itself
+fn paywalled(entry: &Entry, results: &[ValidationResult]) -> Option<Discrepancy> {
+    let matched: Vec<(ApiSource, &Entry)> = results
+        .iter()
+        .filter_map(|r| Some((r.source, r.matched_entry.as_ref()?)))
+        .collect();
+    if entry.field("url").is_some()
+        || entry.arxiv_id.is_some()
+        || matched.iter().any(|(_, m)| m.field(OA_URL_FIELD).is_some())
+    {
+        return None;
+    }
+    let (source, _) = matched
+        .iter()
+        .find(|(_, m)| m.field(OA_STATUS_FIELD) == Some("closed"))?;
+    Some(Discrepancy {
+        field: DiscrepancyField::Url,
+        severity: Severity::Info,
+        local_value: entry.doi.clone().unwrap_or_default(),
+        remote_value: "closed".to_string(),
+        message: format!(
+            "Only available behind a paywall according to {}; no legal open-access copy is \
+             known",
+            source
+        ),
+    })
+}
+
+fn determine_status(fused: &fusion::FusedResult) -> EntryStatus {
+    if !fused.has_matches {
+        return EntryStatus::NotFound;
     }
 
     let has_errors = fused
@@ -445,10 +2835,22 @@ fn compute_status(
     status
 }
 
+/// Fold offline lint findings into the status computed from API lookups
+fn apply_lint_status(status: EntryStatus, lints: &[Discrepancy]) -> EntryStatus {
+    if lints.iter().any(|d| d.severity == Severity::Error) {
+        EntryStatus::Error
+    } else if matches!(status, EntryStatus::Ok(_))
+        && lints.iter().any(|d| d.severity == Severity::Warning)
+    {
+        EntryStatus::Warning
+    } else {
+        status
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::entry::{Discrepancy, DiscrepancyField};
 
     fn make_validation_result(severity: Severity) -> ValidationResult {
         ValidationResult {
@@ -490,4 +2892,147 @@ mod tests {
         let status = compute_status(&fused, &[], &[String::from("CrossRef rate limited")]);
         assert!(matches!(status, EntryStatus::Failed(_)));
     }
+
+    #[test]
+    fn short_circuit_counts_only_clean_confident_matches() {
+        let policy = ShortCircuitPolicy::after(2);
+        let clean = ValidationResult {
+            discrepancies: Vec::new(),
+            ..make_validation_result(Severity::Info)
+        };
+        let weak = ValidationResult {
+            confidence: 0.5,
+            ..clean.clone()
+        };
+
+        assert!(!policy.is_satisfied(&[clean.clone(), weak]));
+        assert!(!policy.is_satisfied(&[clean.clone(), make_validation_result(Severity::Info)]));
+        assert!(policy.is_satisfied(&[clean.clone(), clean]));
+    }
+
+    #[test]
+    fn citation_counts_take_the_highest_report() {
+        let with_count = |source, count: &str| {
+            let mut matched = Entry::new("w".to_string(), "article".to_string());
+            matched
+                .fields
+                .insert(CITATION_COUNT_FIELD.to_string(), count.to_string());
+            ValidationResult {
+                source,
+                matched_entry: Some(matched),
+                ..make_validation_result(Severity::Info)
+            }
+        };
+
+        let results = vec![
+            make_validation_result(Severity::Info),
+            with_count(ApiSource::SemanticScholar, "40"),
+            with_count(ApiSource::OpenAlex, "52"),
+        ];
+        let citations = citation_count(&results).unwrap();
+        assert_eq!(citations.count, 52);
+        assert_eq!(citations.source, ApiSource::OpenAlex);
+        assert!(citation_count(&results[..1]).is_none());
+    }
+
+    #[test]
+    fn open_access_falls_back_to_openalex_and_flags_paywalls() {
+        let with_access = |source, status: &str, url: Option<&str>| {
+            let mut matched = Entry::new("w".to_string(), "article".to_string());
+            matched
+                .fields
+                .insert(OA_STATUS_FIELD.to_string(), status.to_string());
+            if let Some(url) = url {
+                matched.fields.insert(OA_URL_FIELD.to_string(), url.to_string());
+            }
+            ValidationResult {
+                source,
+                matched_entry: Some(matched),
+                ..make_validation_result(Severity::Info)
+            }
+        };
+        let entry = Entry::new("a".to_string(), "article".to_string());
+
+        let green = [
+            with_access(ApiSource::Unpaywall, "closed", None),
+            with_access(ApiSource::OpenAlex, "green", Some("https://repo.example/a.pdf")),
+        ];
+        assert!(open_access(&green, false).is_none());
+        let found = open_access(&green, true).unwrap();
+        assert_eq!(found.url, "https://repo.example/a.pdf");
+        assert_eq!(found.status.as_deref(), Some("green"));
+        assert!(paywalled(&entry, &green).is_none());
+
+        let closed = [with_access(ApiSource::OpenAlex, "closed", None)];
+        let finding = paywalled(&entry, &closed).unwrap();
+        assert_eq!(finding.field, DiscrepancyField::Url);
+        assert_eq!(finding.severity, Severity::Info);
+
+        let mut preprint = entry.clone();
+        preprint.arxiv_id = Some("2101.00001".to_string());
+        assert!(paywalled(&preprint, &closed).is_none());
+    }
+
+    #[test]
+    fn retractions_outrank_other_notices() {
+        let notice = |kind: &str, source: &str| UpdateNotice {
+            kind: kind.to_string(),
+            notice_doi: Some(format!("10.1000/{}", kind)),
+            source: Some(source.to_string()),
+        };
+
+        let notices = vec![
+            notice("correction", "publisher"),
+            notice("retraction", "retraction-watch"),
+            notice("new_version", "publisher"),
+        ];
+        let discrepancy = retraction_discrepancy("10.1000/xyz", &notices).unwrap();
+        assert_eq!(discrepancy.field, DiscrepancyField::Retraction);
+        assert_eq!(discrepancy.severity, Severity::Error);
+        assert_eq!(discrepancy.remote_value, "retraction");
+        assert!(discrepancy.message.ends_with("per Retraction Watch"));
+
+        let concern = [notice("expression_of_concern", "publisher")];
+        let discrepancy = retraction_discrepancy("10.1000/xyz", &concern).unwrap();
+        assert_eq!(discrepancy.remote_value, "expression of concern");
+        assert!(retraction_discrepancy("10.1000/xyz", &notices[2..]).is_none());
+    }
+
+    #[test]
+    fn opencitations_is_asked_only_about_disputed_records() {
+        let record = |source, year, venue: &str| {
+            let mut matched = Entry::new("w".to_string(), "article".to_string());
+            matched.doi = Some("10.1000/XYZ".to_string());
+            matched.year = Some(year);
+            matched.venue = Some(Arc::from(venue));
+            ValidationResult {
+                source,
+                matched_entry: Some(matched),
+                ..make_validation_result(Severity::Info)
+            }
+        };
+
+        let agreeing = vec![
+            record(ApiSource::CrossRef, 2017, "Advances in Neural Information Processing Systems"),
+            record(ApiSource::SemanticScholar, 2017, "NeurIPS"),
+        ];
+        assert_eq!(disputed_doi(&agreeing), None);
+
+        let disputed = vec![
+            record(ApiSource::CrossRef, 2017, "Nature"),
+            record(ApiSource::SemanticScholar, 2018, "Nature"),
+        ];
+        assert_eq!(disputed_doi(&disputed).as_deref(), Some("10.1000/xyz"));
+        assert_eq!(disputed_doi(&disputed[..1]), None);
+    }
+
+    #[test]
+    fn lint_errors_override_api_status() {
+        let lints = make_validation_result(Severity::Error).discrepancies;
+        let status = apply_lint_status(EntryStatus::Ok(ApiSource::CrossRef), &lints);
+        assert!(matches!(status, EntryStatus::Error));
+
+        let status = apply_lint_status(EntryStatus::NotFound, &[]);
+        assert!(matches!(status, EntryStatus::NotFound));
+    }
 }
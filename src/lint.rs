@@ -0,0 +1,1331 @@
+use crate::entry::{normalize_string, Discrepancy, DiscrepancyField, Entry, EntryKind, Severity};
+use crate::matcher::{author_overlap, title_similarity, years_compatible};
+use crate::predatory::PredatoryList;
+use crate::publishers::{classify_publisher, PublisherMatch};
+use crate::venues::{classify_venue, find_venue, Venue, VenueForm};
+use regex_lite::Regex;
+use std::collections::{BTreeSet, HashMap, HashSet};
+use std::sync::OnceLock;
+
+/// Minimum title similarity for two entries to be considered near-duplicates
+const NEAR_DUPLICATE_TITLE_THRESHOLD: f64 = 0.93;
+
+/// Minimum author overlap for two entries to be considered near-duplicates
+const NEAR_DUPLICATE_AUTHOR_OVERLAP: f64 = 0.5;
+
+/// Markers that mean a field was never filled in
+const PLACEHOLDER_MARKERS: &[&str] = &["TODO", "FIXME", "XXX", "TBD", "???", "Lorem ipsum"];
+
+/// Markers that are also ordinary words or numbers ("XXX" is 30 in Roman numerals, and
+/// papers study TODO comments), so they only count as the whole value, in brackets, or
+/// at the start before a colon
+const AMBIGUOUS_MARKERS: &[&str] = &["TODO", "XXX", "TBD"];
+
+/// Fields where "XXX" is a Roman numeral
+const NUMBERED_FIELDS: &[&str] = &["volume", "number", "edition", "series"];
+
+/// Whole field values that are obviously left over from a template
+const TEMPLATE_VALUES: &[(&str, &[&str])] = &[
+    (
+        "author",
+        &[
+            "author, a.",
+            "a. author",
+            "author",
+            "authors",
+            "last, first",
+            "lastname, firstname",
+            "firstname lastname",
+            "surname, name",
+            "john doe",
+            "doe, john",
+        ],
+    ),
+    (
+        "title",
+        &["title", "paper title", "title of the paper", "untitled"],
+    ),
+    ("journal", &["journal", "journal name", "name of journal"]),
+    (
+        "booktitle",
+        &["booktitle", "conference name", "proceedings"],
+    ),
+    ("publisher", &["publisher", "publisher name"]),
+    ("year", &["yyyy", "0000", "9999"]),
+    ("doi", &["10.xxxx/xxxxx", "10.0000/0000", "doi"]),
+    ("pages", &["xx--yy", "xx-yy", "1--n"]),
+];
+
+/// Title words skipped when building a suggested citation key
+const KEY_STOPWORDS: &[&str] = &[
+    "a", "an", "the", "on", "of", "in", "for", "to", "and", "with", "towards", "toward",
+];
+
+/// Generational suffixes that follow the last name in a formatted author name
+const NAME_SUFFIXES: &[&str] = &["jr", "sr", "ii", "iii", "iv"];
+
+/// Configuration for the offline lint pass
+#[derive(Debug, Clone, Default)]
+pub struct LintConfig {
+    /// Pattern every citation key must match (unchecked when `None`)
+    pub key_pattern: Option<Regex>,
+    /// Required spelling for known journals (only consistency is checked when `None`)
+    pub journal_style: Option<VenueForm>,
+    /// Required spelling for known conferences in `booktitle` (unchecked when `None`)
+    pub conference_style: Option<VenueForm>,
+    /// Predatory journals and publishers to flag (unchecked when `None`)
+    pub predatory: Option<PredatoryList>,
+}
+
+impl LintConfig {
+    /// Resolve a key style preset (`authoryear`, `authoryearword`, `dblp`) or a custom regex
+    pub fn key_pattern_from_str(pattern: &str) -> Result<Regex, regex_lite::Error> {
+        match pattern {
+            "authoryear" => Regex::new(r"^[a-z]+[0-9]{4}[a-z]*$"),
+            "authoryearword" => Regex::new(r"^[a-z]+[0-9]{4}[a-z0-9]+$"),
+            "dblp" => Regex::new(r"^DBLP:[a-z]+/[A-Za-z0-9]+/[A-Za-z0-9-]+$"),
+            custom => Regex::new(custom),
+        }
+    }
+}
+
+/// Run offline checks over the whole bibliography before any API is consulted.
+///
+/// Returns one list of findings per entry, in the same order as `entries`.
+pub fn lint_entries(entries: &[Entry], config: &LintConfig) -> Vec<Vec<Discrepancy>> {
+    let mut findings: Vec<_> = entries
+        .iter()
+        .map(|entry| per_entry_checks(entry, config))
+        .collect();
+
+    check_duplicate_identifiers(entries, &mut findings);
+    check_near_duplicates(entries, &mut findings);
+    check_key_collisions(entries, &mut findings);
+    check_crossref_targets(entries, &mut findings);
+    check_journal_names(entries, config, &mut findings);
+
+    findings
+}
+
+/// Run only the checks that need nothing but the entry itself, for streaming mode where
+/// the rest of the bibliography isn't available
+pub fn lint_entry(entry: &Entry, config: &LintConfig) -> Vec<Discrepancy> {
+    let mut findings = per_entry_checks(entry, config);
+    if let Some(style) = config.journal_style {
+        findings.extend(check_journal_style(entry, style));
+    }
+    findings
+}
+
+fn per_entry_checks(entry: &Entry, config: &LintConfig) -> Vec<Discrepancy> {
+    let mut findings = check_key_style(entry, config);
+    findings.extend(check_url(entry));
+    findings.extend(check_venue_year(entry));
+    findings.extend(check_placeholders(entry));
+    findings.extend(check_type_fields(entry));
+    findings.extend(check_publisher(entry));
+    if let Some(style) = config.conference_style {
+        findings.extend(check_conference_style(entry, style));
+    }
+    if let Some(list) = &config.predatory {
+        findings.extend(list.check(entry));
+    }
+    findings
+}
+
+/// Normalize a DOI for comparison: lowercase, without resolver or `doi:` prefixes
+pub fn normalize_doi(doi: &str) -> String {
+    let doi = doi.trim().to_lowercase();
+    let doi = doi
+        .trim_start_matches("https://")
+        .trim_start_matches("http://")
+        .trim_start_matches("dx.doi.org/")
+        .trim_start_matches("doi.org/")
+        .trim_start_matches("doi:");
+    doi.trim().to_string()
+}
+
+/// Variants of a DOI that doesn't resolve, most likely first, for repairing the ways DOIs
+/// get mangled in the wild: percent-encoding and LaTeX or HTML escapes, whitespace from
+/// line wrapping, OCR misreadings of the `10.` prefix and registrant code (`lO.1145`,
+/// `10.II45`), and trailing punctuation from the surrounding sentence. Truncated DOIs
+/// can't be repaired this way and are left to title searches.
+pub fn doi_repairs(doi: &str) -> Vec<String> {
+    let doi = normalize_doi(doi);
+    let decoded = urlencoding::decode(&doi).map_or_else(|_| doi.clone(), |d| d.into_owned());
+    let unescaped = decoded
+        .replace("\\_", "_")
+        .replace("&lt;", "<")
+        .replace("&gt;", ">")
+        .replace("&amp;", "&");
+    let mut repaired: String = unescaped.chars().filter(|c| !c.is_whitespace()).collect();
+
+    // The prefix and registrant code are digits, so letters there are misread digits
+    if let Some((prefix, suffix)) = repaired.split_once('/') {
+        let digits: String = prefix
+            .chars()
+            .map(|c| match c {
+                'o' => '0',
+                'l' | 'i' => '1',
+                c => c,
+            })
+            .collect();
+        let registrant = digits.strip_prefix("10.").unwrap_or("");
+        if !registrant.is_empty() && registrant.chars().all(|c| c.is_ascii_digit() || c == '.') {
+            repaired = format!("{}/{}", digits, suffix);
+        }
+    }
+
+    let mut repairs = vec![repaired.clone()];
+    // Closing brackets are only stray when the DOI doesn't open them itself
+    let unbalanced = |doi: &str, open: char, close: char| {
+        doi.ends_with(close) && doi.matches(close).count() > doi.matches(open).count()
+    };
+    let mut trimmed = repaired.as_str();
+    loop {
+        let before = trimmed;
+        trimmed = trimmed.trim_end_matches(['.', ',', ';', ':', '\'', '"']);
+        if unbalanced(trimmed, '(', ')') || unbalanced(trimmed, '[', ']') {
+            trimmed = &trimmed[..trimmed.len() - 1];
+        }
+        if trimmed == before {
+            break;
+        }
+    }
+    repairs.push(trimmed.to_string());
+
+    repairs.dedup();
+    repairs.retain(|r| *r != doi && r.starts_with("10.") && r.contains('/'));
+    repairs
+}
+
+/// Normalize an ISBN for lookups: the first ISBN listed, as bare digits (and a final X)
+pub fn normalize_isbn(isbn: &str) -> Option<String> {
+    let first = isbn.split([',', ';']).next()?;
+    let digits: String = first
+        .chars()
+        .filter(|c| c.is_ascii_digit() || matches!(c, 'x' | 'X'))
+        .map(|c| c.to_ascii_uppercase())
+        .collect();
+    matches!(digits.len(), 10 | 13).then_some(digits)
+}
+
+/// Normalize an ISSN for lookups: the first ISSN listed, as "1234-567X"
+pub fn normalize_issn(issn: &str) -> Option<String> {
+    let first = issn.split([',', ';']).next()?;
+    let digits: String = first
+        .chars()
+        .filter(|c| c.is_ascii_digit() || matches!(c, 'x' | 'X'))
+        .map(|c| c.to_ascii_uppercase())
+        .collect();
+    (digits.len() == 8).then(|| format!("{}-{}", &digits[..4], &digits[4..]))
+}
+
+/// The version an arXiv ID pins, e.g. 3 for "2301.12345v3"
+pub fn arxiv_version(id: &str) -> Option<u32> {
+    let id = id.trim().to_lowercase();
+    let (base, version) = id.rsplit_once('v')?;
+    if base.is_empty() {
+        return None;
+    }
+    version.parse().ok()
+}
+
+/// Normalize an arXiv ID for comparison by dropping the version suffix
+pub fn normalize_arxiv_id(id: &str) -> String {
+    let id = id.trim().to_lowercase();
+    if let Some((base, version)) = id.rsplit_once('v') {
+        if !base.is_empty() && !version.is_empty() && version.chars().all(|c| c.is_ascii_digit()) {
+            return base.to_string();
+        }
+    }
+    id
+}
+
+/// Suggest a citation key of the form `lastnameYEARword` from an entry's metadata
+pub fn suggest_key(entry: &Entry) -> Option<String> {
+    let author = entry.authors.first()?;
+    // Authors are formatted "First von Last Jr", so the last name is the last word that
+    // isn't a suffix
+    let words: Vec<String> = author
+        .split_whitespace()
+        .map(|word| {
+            word.chars()
+                .filter(|c| c.is_ascii_alphabetic())
+                .collect::<String>()
+                .to_lowercase()
+        })
+        .collect();
+    let last_name = words
+        .iter()
+        .rev()
+        .find(|word| !NAME_SUFFIXES.contains(&word.as_str()))
+        .or(words.last())?
+        .clone();
+    let year = entry.year?;
+    let word: String = entry
+        .title
+        .as_deref()
+        .and_then(|title| {
+            normalize_string(title)
+                .split_whitespace()
+                .find(|w| !KEY_STOPWORDS.contains(w))
+                .map(|w| w.chars().filter(|c| c.is_ascii_alphanumeric()).collect())
+        })
+        .unwrap_or_default();
+
+    if last_name.is_empty() {
+        return None;
+    }
+    Some(format!("{}{}{}", last_name, year, word))
+}
+
+/// The key suggested for an entry whose key has characters BibTeX can't handle or
+/// doesn't match the configured style
+pub fn renamed_key(entry: &Entry, config: &LintConfig) -> Option<String> {
+    check_key_style(entry, config)
+        .into_iter()
+        .map(|d| d.remote_value)
+        .find(|suggestion| !suggestion.is_empty())
+}
+
+/// Check a single key for characters BibTeX can't handle and for the configured style
+fn check_key_style(entry: &Entry, config: &LintConfig) -> Vec<Discrepancy> {
+    let key = &entry.key;
+    let mut findings = Vec::new();
+    let suggestion = suggest_key(entry);
+
+    let problem = if key.chars().any(char::is_whitespace) {
+        Some((Severity::Error, "Citation key contains whitespace"))
+    } else if !key.is_ascii() {
+        Some((
+            Severity::Warning,
+            "Citation key contains non-ASCII characters",
+        ))
+    } else {
+        None
+    };
+
+    if let Some((severity, message)) = problem {
+        findings.push(Discrepancy {
+            field: DiscrepancyField::Key,
+            severity,
+            local_value: key.clone(),
+            remote_value: suggestion.clone().unwrap_or_default(),
+            message: message.to_string(),
+        });
+    }
+
+    if let Some(pattern) = &config.key_pattern {
+        if !pattern.is_match(key) {
+            // Only propose a replacement that actually satisfies the pattern
+            let suggestion = suggestion.filter(|s| pattern.is_match(s));
+            let message = match &suggestion {
+                Some(s) => format!(
+                    "Citation key does not match pattern {} (suggested: {})",
+                    pattern.as_str(),
+                    s
+                ),
+                None => format!("Citation key does not match pattern {}", pattern.as_str()),
+            };
+            findings.push(Discrepancy {
+                field: DiscrepancyField::Key,
+                severity: Severity::Warning,
+                local_value: key.clone(),
+                remote_value: suggestion.unwrap_or_default(),
+                message,
+            });
+        }
+    }
+
+    findings
+}
+
+/// Check that the `url` field is a plain, valid absolute URL that doesn't just repeat the DOI
+fn check_url(entry: &Entry) -> Vec<Discrepancy> {
+    let mut findings = Vec::new();
+    let Some(raw_url) = entry.field("url") else {
+        return findings;
+    };
+
+    let mut url = raw_url.trim().to_string();
+    let mut push = |severity, suggestion: String, message: &str| {
+        findings.push(Discrepancy {
+            field: DiscrepancyField::Url,
+            severity,
+            local_value: raw_url.to_string(),
+            remote_value: suggestion,
+            message: message.to_string(),
+        });
+    };
+
+    if let Some(inner) = url
+        .strip_prefix("\\url{")
+        .and_then(|rest| rest.strip_suffix('}'))
+    {
+        url = inner.trim().to_string();
+        push(
+            Severity::Warning,
+            url.clone(),
+            "URL is wrapped in \\url{}, which the url field already implies",
+        );
+    }
+
+    if url.chars().any(char::is_whitespace) {
+        url = url.split_whitespace().collect::<Vec<_>>().join("%20");
+        push(
+            Severity::Warning,
+            url.clone(),
+            "URL contains unescaped spaces",
+        );
+    }
+
+    match reqwest::Url::parse(&url) {
+        Ok(parsed) if parsed.has_host() => {}
+        _ => {
+            push(
+                Severity::Warning,
+                String::new(),
+                "URL is not a valid absolute URL",
+            );
+            return findings;
+        }
+    }
+
+    // A doi.org URL next to a doi field is redundant
+    if let (Some(doi), Some(idx)) = (entry.field("doi"), url.find("doi.org/")) {
+        let url_doi = normalize_doi(&urlencoding::decode(&url[idx + 8..]).unwrap_or_default());
+        if url_doi == normalize_doi(doi) {
+            push(
+                Severity::Info,
+                String::new(),
+                "URL duplicates the DOI; consider dropping the url field",
+            );
+        } else {
+            push(
+                Severity::Warning,
+                String::new(),
+                "URL points to a different DOI than the doi field",
+            );
+        }
+    }
+
+    findings
+}
+
+/// Compare years written into the venue ("NeurIPS 2020", "ICML '21", "34th ...") with `year`
+fn check_venue_year(entry: &Entry) -> Option<Discrepancy> {
+    let venue = entry.venue.as_deref()?;
+    let year = entry.year?;
+
+    let (venue_year, how) = if let Some(y) = explicit_year_in_venue(venue) {
+        (y, "Venue mentions")
+    } else {
+        let edition = ordinal_edition(venue)?;
+        let first_year = find_venue(venue)?.first_year?;
+        (first_year + edition - 1, "Venue edition corresponds to")
+    };
+
+    if venue_year == year {
+        return None;
+    }
+
+    Some(Discrepancy {
+        field: DiscrepancyField::Year,
+        severity: Severity::Warning,
+        local_value: year.to_string(),
+        remote_value: venue_year.to_string(),
+        message: format!("{} {} but year is {}", how, venue_year, year),
+    })
+}
+
+/// Find a four-digit year or an abbreviated `'YY` year in a venue string
+fn explicit_year_in_venue(venue: &str) -> Option<i32> {
+    static FULL: OnceLock<Regex> = OnceLock::new();
+    static SHORT: OnceLock<Regex> = OnceLock::new();
+    let full = FULL.get_or_init(|| Regex::new(r"\b(19\d{2}|20\d{2})\b").expect("valid year regex"));
+    // Digits going on after a dot are an identifier, like "arXiv:2005.14165"
+    let year = full.find_iter(venue).find(|m| {
+        let mut after = venue[m.end()..].chars();
+        !(after.next() == Some('.') && after.next().is_some_and(|c| c.is_ascii_digit()))
+    });
+    if let Some(m) = year {
+        return m.as_str().parse().ok();
+    }
+
+    let short = SHORT.get_or_init(|| Regex::new(r"['’](\d{2})\b").expect("valid year regex"));
+    let yy: i32 = short.captures(venue)?.get(1)?.as_str().parse().ok()?;
+    Some(if yy >= 50 { 1900 + yy } else { 2000 + yy })
+}
+
+/// Parse an ordinal edition number like "34th" or "Thirty-Fourth" from a venue string
+fn ordinal_edition(venue: &str) -> Option<i32> {
+    static NUMERIC: OnceLock<Regex> = OnceLock::new();
+    let numeric = NUMERIC.get_or_init(|| {
+        Regex::new(r"\b(\d{1,3})(?:st|nd|rd|th)\b").expect("valid ordinal regex")
+    });
+    if let Some(caps) = numeric.captures(venue) {
+        return caps.get(1)?.as_str().parse().ok();
+    }
+
+    const UNITS: &[&str] = &[
+        "first", "second", "third", "fourth", "fifth", "sixth", "seventh", "eighth", "ninth",
+    ];
+    const TEENS: &[&str] = &[
+        "tenth",
+        "eleventh",
+        "twelfth",
+        "thirteenth",
+        "fourteenth",
+        "fifteenth",
+        "sixteenth",
+        "seventeenth",
+        "eighteenth",
+        "nineteenth",
+    ];
+    const TENS: &[&str] = &["twenty", "thirty", "forty", "fifty", "sixty", "seventy"];
+    const ROUND_TENS: &[&str] = &[
+        "twentieth",
+        "thirtieth",
+        "fortieth",
+        "fiftieth",
+        "sixtieth",
+        "seventieth",
+    ];
+
+    let lower = venue.to_lowercase().replace('-', " ");
+    let words: Vec<_> = lower.split_whitespace().collect();
+    for (i, word) in words.iter().enumerate() {
+        if let Some(t) = TENS.iter().position(|t| t == word) {
+            let unit = words
+                .get(i + 1)
+                .and_then(|next| UNITS.iter().position(|u| u == next))?;
+            return Some((t as i32 + 2) * 10 + unit as i32 + 1);
+        }
+        if let Some(t) = ROUND_TENS.iter().position(|t| t == word) {
+            return Some((t as i32 + 2) * 10);
+        }
+        if let Some(t) = TEENS.iter().position(|t| t == word) {
+            return Some(t as i32 + 10);
+        }
+        if let Some(u) = UNITS.iter().position(|u| u == word) {
+            return Some(u as i32 + 1);
+        }
+    }
+    None
+}
+
+/// Suggest the registry's spelling for known publishers written another way
+fn check_publisher(entry: &Entry) -> Option<Discrepancy> {
+    let publisher = entry.field("publisher")?;
+    let (severity, known, message) = match classify_publisher(publisher)? {
+        PublisherMatch::Canonical(_) => return None,
+        PublisherMatch::Alias(known) => (
+            Severity::Info,
+            known,
+            format!("Publisher is usually written '{}'", known.name),
+        ),
+        PublisherMatch::Misspelled(known) => (
+            Severity::Warning,
+            known,
+            format!("Publisher may be misspelled; did you mean '{}'?", known.name),
+        ),
+    };
+
+    Some(Discrepancy {
+        field: DiscrepancyField::Publisher,
+        severity,
+        local_value: publisher.to_string(),
+        remote_value: known.name.to_string(),
+        message,
+    })
+}
+
+/// Check the fields that identify software, datasets and web pages, which have no
+/// publication record to fall back on
+fn check_type_fields(entry: &Entry) -> Vec<Discrepancy> {
+    let has = |name: &str| entry.field(name).is_some_and(|v| !v.trim().is_empty());
+    let missing = |field, severity, message: &str| Discrepancy {
+        field,
+        severity,
+        local_value: String::new(),
+        remote_value: String::new(),
+        message: message.to_string(),
+    };
+
+    let mut findings = Vec::new();
+    match entry.kind() {
+        EntryKind::Software if !has("url") && !has("repository") => findings.push(missing(
+            DiscrepancyField::Url,
+            Severity::Warning,
+            "Software entry has no url or repository field",
+        )),
+        EntryKind::Dataset if !has("doi") && !has("url") => findings.push(missing(
+            DiscrepancyField::Doi,
+            Severity::Warning,
+            "Dataset entry has neither a DOI nor a URL",
+        )),
+        EntryKind::Online if !has("url") => findings.push(missing(
+            DiscrepancyField::Url,
+            Severity::Error,
+            "Online entry has no url field",
+        )),
+        EntryKind::Online if !has("urldate") => findings.push(missing(
+            DiscrepancyField::Url,
+            Severity::Warning,
+            "Online entry has no urldate recording when the page was accessed",
+        )),
+        _ => {}
+    }
+    findings
+}
+
+/// Flag fields holding TODO markers or values copied verbatim from a template
+fn check_placeholders(entry: &Entry) -> Vec<Discrepancy> {
+    let mut findings = Vec::new();
+
+    for (name, value) in &entry.fields {
+        let marker = PLACEHOLDER_MARKERS.iter().find(|marker| {
+            if **marker == "XXX" && NUMBERED_FIELDS.contains(&name.as_str()) {
+                return false;
+            }
+            if AMBIGUOUS_MARKERS.contains(marker) {
+                stands_alone(value, marker)
+            } else {
+                contains_marker(value, marker)
+            }
+        });
+        let templated = TEMPLATE_VALUES
+            .iter()
+            .filter(|(field, _)| field == name)
+            .flat_map(|(_, values)| values.iter())
+            .any(|template| value.trim().trim_matches(['{', '}']).to_lowercase() == *template);
+
+        let message = match (marker, templated) {
+            (Some(marker), _) => format!("Placeholder '{}' in {} field", marker, name),
+            (None, true) => format!("Template value left in {} field", name),
+            (None, false) => continue,
+        };
+
+        findings.push(Discrepancy {
+            field: DiscrepancyField::Placeholder,
+            severity: Severity::Error,
+            local_value: value.clone(),
+            remote_value: String::new(),
+            message,
+        });
+    }
+
+    findings
+}
+
+/// Check for a placeholder marker that isn't part of a longer word (e.g. "XXXL")
+fn contains_marker(value: &str, marker: &str) -> bool {
+    value.match_indices(marker).any(|(idx, _)| {
+        let before = value[..idx].chars().next_back();
+        let after = value[idx + marker.len()..].chars().next();
+        let is_word = |c: Option<char>| c.is_some_and(|c| c.is_alphanumeric());
+        marker.starts_with('?') || (!is_word(before) && !is_word(after))
+    })
+}
+
+/// Check for a marker that is the whole value, bracketed ("[TODO]") or leads the value
+/// ("TODO: add pages")
+fn stands_alone(value: &str, marker: &str) -> bool {
+    let value = value.trim().trim_matches(['{', '}']).trim();
+    value == marker
+        || value.starts_with(&format!("{}:", marker))
+        || [('[', ']'), ('(', ')'), ('<', '>')]
+            .iter()
+            .any(|(open, close)| value.contains(&format!("{}{}{}", open, marker, close)))
+}
+
+/// Flag keys that only differ by case, which BibTeX treats as the same entry
+fn check_key_collisions(entries: &[Entry], findings: &mut [Vec<Discrepancy>]) {
+    let mut by_lowercase: HashMap<String, Vec<usize>> = HashMap::new();
+    for (idx, entry) in entries.iter().enumerate() {
+        by_lowercase
+            .entry(entry.key.to_lowercase())
+            .or_default()
+            .push(idx);
+    }
+
+    for indices in by_lowercase.values() {
+        for &idx in indices {
+            let others: Vec<_> = indices
+                .iter()
+                .map(|&other| entries[other].key.as_str())
+                .filter(|&other| other != entries[idx].key)
+                .collect();
+            if others.is_empty() {
+                continue;
+            }
+            findings[idx].push(Discrepancy {
+                field: DiscrepancyField::Key,
+                severity: Severity::Warning,
+                local_value: entries[idx].key.clone(),
+                remote_value: others.join(", "),
+                message: format!(
+                    "Citation key differs only by case from {}",
+                    others.join(", ")
+                ),
+            });
+        }
+    }
+}
+
+/// Check that `crossref`, `xref`, and `xdata` fields point at keys that exist
+fn check_crossref_targets(entries: &[Entry], findings: &mut [Vec<Discrepancy>]) {
+    let keys: HashSet<&str> = entries.iter().map(|e| e.key.as_str()).collect();
+    let lowercase_keys: HashSet<String> = keys.iter().map(|k| k.to_lowercase()).collect();
+
+    for (entry, entry_findings) in entries.iter().zip(findings.iter_mut()) {
+        for field in ["crossref", "xref", "xdata"] {
+            let Some(value) = entry.field(field) else {
+                continue;
+            };
+
+            for target in value.split(',').map(str::trim).filter(|t| !t.is_empty()) {
+                if keys.contains(target) {
+                    continue;
+                }
+
+                let (severity, message) = if lowercase_keys.contains(&target.to_lowercase()) {
+                    (
+                        Severity::Warning,
+                        format!(
+                            "{} target '{}' only matches a key with different case",
+                            field, target
+                        ),
+                    )
+                } else {
+                    (
+                        Severity::Error,
+                        format!(
+                            "{} target '{}' does not exist in the bibliography",
+                            field, target
+                        ),
+                    )
+                };
+                entry_findings.push(Discrepancy {
+                    field: DiscrepancyField::Crossref,
+                    severity,
+                    local_value: target.to_string(),
+                    remote_value: String::new(),
+                    message,
+                });
+            }
+        }
+    }
+}
+
+/// Flag known journals cited under different spellings, or not in the required style
+fn check_journal_names(entries: &[Entry], config: &LintConfig, findings: &mut [Vec<Discrepancy>]) {
+    if let Some(style) = config.journal_style {
+        for (entry, entry_findings) in entries.iter().zip(findings.iter_mut()) {
+            entry_findings.extend(check_journal_style(entry, style));
+        }
+        return;
+    }
+
+    let mut by_journal: HashMap<&str, Vec<(usize, VenueForm)>> = HashMap::new();
+    for (idx, entry) in entries.iter().enumerate() {
+        if let Some((venue, form)) = known_journal(entry) {
+            by_journal.entry(venue.name).or_default().push((idx, form));
+        }
+    }
+
+    for uses in by_journal.values() {
+        let mut counts: HashMap<VenueForm, usize> = HashMap::new();
+        for (_, form) in uses {
+            *counts.entry(*form).or_default() += 1;
+        }
+        if counts.len() < 2 {
+            continue;
+        }
+
+        // Suggest the spelling most of the bibliography already uses
+        let majority = counts
+            .iter()
+            .max_by_key(|(form, count)| (**count, **form == VenueForm::Full))
+            .map(|(form, _)| *form)
+            .unwrap_or(VenueForm::Full);
+
+        for &(idx, form) in uses {
+            if form == majority {
+                continue;
+            }
+            let journal = entries[idx].venue.as_deref().unwrap_or_default();
+            let (venue, _) = classify_venue(journal).expect("classified above");
+            findings[idx].push(Discrepancy {
+                field: DiscrepancyField::Venue,
+                severity: Severity::Warning,
+                local_value: journal.to_string(),
+                remote_value: venue.spelling(majority).to_string(),
+                message: format!(
+                    "Journal cited by {} here but by {} elsewhere: {}",
+                    form,
+                    majority,
+                    venue.spelling(majority)
+                ),
+            });
+        }
+    }
+}
+
+/// Flag a known journal that isn't spelled in the required style
+fn check_journal_style(entry: &Entry, style: VenueForm) -> Option<Discrepancy> {
+    let (venue, form) = known_journal(entry)?;
+    // A journal whose ISO-4 form is its full name ("Nature") is spelled right either way
+    let local = entry.venue.as_deref().unwrap_or_default();
+    if form == style || normalize_string(venue.spelling(style)) == normalize_string(local) {
+        return None;
+    }
+    Some(Discrepancy {
+        field: DiscrepancyField::Venue,
+        severity: Severity::Warning,
+        local_value: entry.venue.as_deref().unwrap_or_default().to_string(),
+        remote_value: venue.spelling(style).to_string(),
+        message: format!(
+            "Journal should use its {}: {}",
+            style,
+            venue.spelling(style)
+        ),
+    })
+}
+
+/// Flag a known conference whose `booktitle` isn't spelled in the required style. Other
+/// spellings of the name ("NIPS") are flagged in either style.
+fn check_conference_style(entry: &Entry, style: VenueForm) -> Option<Discrepancy> {
+    let booktitle = entry.field("booktitle")?;
+    let (venue, form) = classify_venue(booktitle).filter(|(venue, _)| !venue.is_journal())?;
+    if form == style || normalize_string(venue.spelling(style)) == normalize_string(booktitle) {
+        return None;
+    }
+    Some(Discrepancy {
+        field: DiscrepancyField::Venue,
+        severity: Severity::Warning,
+        local_value: booktitle.to_string(),
+        remote_value: venue.spelling(style).to_string(),
+        message: format!(
+            "Conference should use its {}: {}",
+            style,
+            venue.spelling(style)
+        ),
+    })
+}
+
+/// The field naming a known venue and its spelling in the style the config requires,
+/// when the entry spells it differently: `journal` by the journal style and `booktitle`
+/// by the conference style
+pub fn restyled_venue(entry: &Entry, config: &LintConfig) -> Option<(&'static str, String)> {
+    let journal = config
+        .journal_style
+        .and_then(|style| check_journal_style(entry, style))
+        .map(|d| ("journal", d.remote_value));
+    journal.or_else(|| {
+        let discrepancy = check_conference_style(entry, config.conference_style?)?;
+        Some(("booktitle", discrepancy.remote_value))
+    })
+}
+
+/// The bundled journal an entry's `journal` field names, and which spelling it uses
+/// (aliases count as full names)
+fn known_journal(entry: &Entry) -> Option<(&'static Venue, VenueForm)> {
+    let journal = entry.field("journal").and(entry.venue.as_deref())?;
+    let (venue, form) = classify_venue(journal)?;
+    if !venue.is_journal() {
+        return None;
+    }
+    let form = if form == VenueForm::Alias {
+        VenueForm::Full
+    } else {
+        form
+    };
+    Some((venue, form))
+}
+
+/// Flag entries that share a DOI or arXiv ID with another entry
+fn check_duplicate_identifiers(entries: &[Entry], findings: &mut [Vec<Discrepancy>]) {
+    let mut by_doi: HashMap<String, Vec<usize>> = HashMap::new();
+    let mut by_arxiv: HashMap<String, Vec<usize>> = HashMap::new();
+
+    for (idx, entry) in entries.iter().enumerate() {
+        if let Some(doi) = &entry.doi {
+            by_doi.entry(normalize_doi(doi)).or_default().push(idx);
+        }
+        if let Some(arxiv_id) = &entry.arxiv_id {
+            by_arxiv
+                .entry(normalize_arxiv_id(arxiv_id))
+                .or_default()
+                .push(idx);
+        }
+    }
+
+    for (label, groups) in [("DOI", by_doi), ("arXiv ID", by_arxiv)] {
+        for (id, indices) in groups {
+            if indices.len() < 2 {
+                continue;
+            }
+            for &idx in &indices {
+                let others: Vec<_> = indices
+                    .iter()
+                    .filter(|&&other| other != idx)
+                    .map(|&other| entries[other].key.as_str())
+                    .collect();
+                findings[idx].push(Discrepancy {
+                    field: DiscrepancyField::Duplicate,
+                    severity: Severity::Error,
+                    local_value: id.clone(),
+                    remote_value: others.join(", "),
+                    message: format!(
+                        "Duplicate {} {} also used by {}",
+                        label,
+                        id,
+                        others.join(", ")
+                    ),
+                });
+            }
+        }
+    }
+}
+
+/// Whether two entries share a DOI or arXiv ID
+pub fn same_identifier(a: &Entry, b: &Entry) -> bool {
+    let same = |x: Option<String>, y: Option<String>| x.is_some() && x == y;
+    same(
+        a.doi.as_deref().map(normalize_doi),
+        b.doi.as_deref().map(normalize_doi),
+    ) || same(
+        a.arxiv_id.as_deref().map(normalize_arxiv_id),
+        b.arxiv_id.as_deref().map(normalize_arxiv_id),
+    )
+}
+
+/// The title similarity of two entries with near-identical titles and overlapping
+/// authors, from compatible years
+pub fn near_duplicate_similarity(a: &Entry, b: &Entry) -> Option<f64> {
+    // Entries sharing a DOI are already reported as exact duplicates, and entries
+    // with different DOIs are distinct works (e.g. conference vs journal version)
+    if a.doi.is_some() && b.doi.is_some() {
+        return None;
+    }
+    if let (Some(id_a), Some(id_b)) = (&a.arxiv_id, &b.arxiv_id) {
+        if normalize_arxiv_id(id_a) == normalize_arxiv_id(id_b) {
+            return None;
+        }
+    }
+
+    if !years_compatible(a, b) {
+        return None;
+    }
+
+    let similarity = title_similarity(a, b);
+    (similarity >= NEAR_DUPLICATE_TITLE_THRESHOLD
+        && author_overlap(a, b) >= NEAR_DUPLICATE_AUTHOR_OVERLAP)
+        .then_some(similarity)
+}
+
+/// Flag pairs of entries with near-identical titles and overlapping authors
+fn check_near_duplicates(entries: &[Entry], findings: &mut [Vec<Discrepancy>]) {
+    for (i, j) in near_duplicate_candidates(entries) {
+        let (a, b) = (&entries[i], &entries[j]);
+        let Some(similarity) = near_duplicate_similarity(a, b) else {
+            continue;
+        };
+
+        for (idx, other) in [(i, b), (j, a)] {
+            findings[idx].push(Discrepancy {
+                field: DiscrepancyField::Duplicate,
+                severity: Severity::Warning,
+                local_value: entries[idx].title.as_deref().unwrap_or_default().to_string(),
+                remote_value: other.key.clone(),
+                message: format!(
+                    "Possible duplicate of [{}] (title similarity: {:.0}%)",
+                    other.key,
+                    similarity * 100.0
+                ),
+            });
+        }
+    }
+}
+
+/// Pairs of entries, in order, whose titles share one of their first two words (after a
+/// leading article). Near-identical titles always do, so only these pairs need comparing
+/// rather than every pair in a large bibliography.
+fn near_duplicate_candidates(entries: &[Entry]) -> BTreeSet<(usize, usize)> {
+    let mut buckets: HashMap<String, Vec<usize>> = HashMap::new();
+    for (idx, entry) in entries.iter().enumerate() {
+        let Some(title) = &entry.title else {
+            continue;
+        };
+        let title = normalize_string(title);
+        let mut words = title.split_whitespace().peekable();
+        if words.peek().is_some_and(|w| matches!(*w, "a" | "an" | "the")) {
+            words.next();
+        }
+        let keys: HashSet<&str> = words.take(2).collect();
+        for key in keys {
+            buckets.entry(key.to_string()).or_default().push(idx);
+        }
+    }
+
+    let mut pairs = BTreeSet::new();
+    for bucket in buckets.values() {
+        for (n, &i) in bucket.iter().enumerate() {
+            for &j in &bucket[n + 1..] {
+                pairs.insert((i, j));
+            }
+        }
+    }
+    pairs
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn entry_with_doi(key: &str, doi: &str) -> Entry {
+        let mut entry = Entry::new(key.to_string(), "article".to_string());
+        entry.doi = Some(doi.to_string());
+        entry
+    }
+
+    #[test]
+    fn duplicate_dois_are_reported_on_both_entries() {
+        let entries = vec![
+            entry_with_doi("a", "10.1234/Example"),
+            entry_with_doi("b", "https://doi.org/10.1234/example"),
+            entry_with_doi("c", "10.1234/other"),
+        ];
+
+        let findings = lint_entries(&entries, &LintConfig::default());
+        assert_eq!(findings[0].len(), 1);
+        assert_eq!(findings[0][0].remote_value, "b");
+        assert_eq!(findings[1][0].remote_value, "a");
+        assert!(findings[2].is_empty());
+    }
+
+    #[test]
+    fn arxiv_versions_count_as_duplicates() {
+        let mut a = Entry::new("a".to_string(), "misc".to_string());
+        a.arxiv_id = Some("2301.12345v1".to_string());
+        let mut b = Entry::new("b".to_string(), "misc".to_string());
+        b.arxiv_id = Some("2301.12345v3".to_string());
+
+        let findings = lint_entries(&[a, b], &LintConfig::default());
+        assert!(findings[0]
+            .iter()
+            .any(|d| d.field == DiscrepancyField::Duplicate));
+    }
+
+    #[test]
+    fn near_duplicate_titles_are_warnings() {
+        let mut a = Entry::new("smith2020".to_string(), "article".to_string());
+        a.title = Some("Learning to Rank with Deep Networks".into());
+        a.authors = vec!["John Smith".into()];
+        a.year = Some(2020);
+        let mut b = Entry::new("smith2020a".to_string(), "article".to_string());
+        b.title = Some("Learning to rank with deep networks.".into());
+        b.authors = vec!["J. Smith".into()];
+        b.year = Some(2020);
+        let mut c = Entry::new("doe2019".to_string(), "article".to_string());
+        c.title = Some("Quantum Error Correction".into());
+        c.year = Some(2019);
+
+        let findings = lint_entries(&[a, b, c], &LintConfig::default());
+        assert_eq!(findings[0].len(), 1);
+        assert_eq!(findings[0][0].severity, Severity::Warning);
+        assert_eq!(findings[0][0].remote_value, "smith2020a");
+        assert!(findings[2].is_empty());
+    }
+
+    #[test]
+    fn only_titles_sharing_a_leading_word_are_compared() {
+        let entry = |title: &str| {
+            let mut entry = Entry::new(title.to_string(), "article".to_string());
+            entry.title = Some(title.into());
+            entry
+        };
+        let entries = [
+            entry("The Lottery Ticket Hypothesis"),
+            entry("Lottery ticket hypothesis"),
+            entry("Quantum Error Correction"),
+            entry("Deep Residual Learning for Lottery Tickets"),
+        ];
+        assert_eq!(
+            near_duplicate_candidates(&entries).into_iter().collect::<Vec<_>>(),
+            vec![(0, 1)]
+        );
+    }
+
+    #[test]
+    fn key_style_violations_suggest_compliant_keys() {
+        let mut entry = Entry::new("Attention Paper".to_string(), "article".to_string());
+        entry.title = Some("Attention Is All You Need".into());
+        entry.authors = vec!["Ashish Vaswani".into()];
+        entry.year = Some(2017);
+
+        let config = LintConfig {
+            key_pattern: Some(LintConfig::key_pattern_from_str("authoryear").unwrap()),
+            ..Default::default()
+        };
+        let findings = lint_entries(&[entry], &config);
+
+        assert!(findings[0]
+            .iter()
+            .any(|d| d.severity == Severity::Error && d.field == DiscrepancyField::Key));
+        assert!(findings[0]
+            .iter()
+            .any(|d| d.remote_value == "vaswani2017attention"));
+    }
+
+    #[test]
+    fn suggested_keys_skip_name_suffixes() {
+        let mut entry = Entry::new("king".to_string(), "article".to_string());
+        entry.title = Some("Letter from Birmingham Jail".into());
+        entry.authors = vec!["Martin Luther King Jr.".into()];
+        entry.year = Some(1963);
+
+        assert_eq!(suggest_key(&entry).as_deref(), Some("king1963letter"));
+    }
+
+    #[test]
+    fn case_insensitive_key_collisions_are_flagged() {
+        let a = Entry::new("Smith2020".to_string(), "article".to_string());
+        let b = Entry::new("smith2020".to_string(), "article".to_string());
+
+        let findings = lint_entries(&[a, b], &LintConfig::default());
+        assert_eq!(findings[0][0].remote_value, "smith2020");
+        assert_eq!(findings[1][0].remote_value, "Smith2020");
+    }
+
+    #[test]
+    fn mangled_dois_are_repaired() {
+        assert_eq!(doi_repairs("10.1145/3290605.3300600."), vec!["10.1145/3290605.3300600"]);
+        assert_eq!(doi_repairs("lO.1145/3290605"), vec!["10.1145/3290605"]);
+        assert_eq!(
+            doi_repairs("10.1016/0004-3702(90)90007-M)."),
+            vec!["10.1016/0004-3702(90)90007-m"]
+        );
+        assert_eq!(doi_repairs("10.1000/a%2Fb"), vec!["10.1000/a/b"]);
+        assert_eq!(doi_repairs("10.1000/x\\_y z;"), vec!["10.1000/x_yz;", "10.1000/x_yz"]);
+        assert!(doi_repairs("10.1038/nature14539").is_empty());
+    }
+
+    #[test]
+    fn url_lint_unwraps_and_flags_redundant_doi_urls() {
+        let mut entry = Entry::new("a".to_string(), "article".to_string());
+        entry.fields.insert(
+            "url".to_string(),
+            "\\url{https://doi.org/10.1234/ABC}".to_string(),
+        );
+        entry
+            .fields
+            .insert("doi".to_string(), "10.1234/abc".to_string());
+
+        let findings = check_url(&entry);
+        assert_eq!(findings.len(), 2);
+        assert_eq!(findings[0].remote_value, "https://doi.org/10.1234/ABC");
+        assert_eq!(findings[1].severity, Severity::Info);
+    }
+
+    #[test]
+    fn url_lint_rejects_relative_urls_and_spaces() {
+        let mut entry = Entry::new("a".to_string(), "misc".to_string());
+        entry
+            .fields
+            .insert("url".to_string(), "example.com/some page".to_string());
+
+        let findings = check_url(&entry);
+        assert!(findings.iter().any(|d| d.message.contains("spaces")));
+        assert!(findings.iter().any(|d| d.message.contains("absolute")));
+    }
+
+    #[test]
+    fn online_and_software_entries_need_their_identifying_fields() {
+        let mut page = Entry::new("blog".to_string(), "online".to_string());
+        assert_eq!(check_type_fields(&page)[0].severity, Severity::Error);
+
+        page.fields
+            .insert("url".to_string(), "https://example.com".to_string());
+        assert!(check_type_fields(&page)[0].message.contains("urldate"));
+
+        let mut software = Entry::new("numpy".to_string(), "software".to_string());
+        assert_eq!(check_type_fields(&software).len(), 1);
+        software.fields.insert(
+            "repository".to_string(),
+            "https://github.com/numpy/numpy".to_string(),
+        );
+        assert!(check_type_fields(&software).is_empty());
+    }
+
+    #[test]
+    fn publisher_aliases_and_misspellings_are_reported() {
+        let mut entry = Entry::new("a".to_string(), "book".to_string());
+        entry
+            .fields
+            .insert("publisher".to_string(), "Springer-Verlag".to_string());
+        let finding = check_publisher(&entry).unwrap();
+        assert_eq!(finding.severity, Severity::Info);
+        assert_eq!(finding.remote_value, "Springer");
+
+        entry
+            .fields
+            .insert("publisher".to_string(), "Cambrige University Press".to_string());
+        assert_eq!(check_publisher(&entry).unwrap().severity, Severity::Warning);
+    }
+
+    #[test]
+    fn venue_years_must_match_year_field() {
+        let mut entry = Entry::new("a".to_string(), "inproceedings".to_string());
+        entry.venue = Some("NeurIPS 2020".into());
+        entry.year = Some(2021);
+        assert_eq!(check_venue_year(&entry).unwrap().remote_value, "2020");
+
+        entry.venue = Some("Proceedings of ICML '21".into());
+        assert!(check_venue_year(&entry).is_none());
+
+        entry.venue = Some("Thirty-Fourth AAAI Conference on Artificial Intelligence".into());
+        assert_eq!(check_venue_year(&entry).unwrap().remote_value, "2020");
+
+        entry.venue = Some("34th Conference on Neural Information Processing Systems".into());
+        entry.year = Some(2020);
+        assert!(check_venue_year(&entry).is_none());
+
+        // The arXiv ID's first digits are not a year
+        entry.venue = Some("arXiv preprint arXiv:2005.14165".into());
+        assert!(check_venue_year(&entry).is_none());
+    }
+
+    #[test]
+    fn missing_crossref_targets_are_errors() {
+        let proceedings = Entry::new("neurips2020".to_string(), "proceedings".to_string());
+        let mut paper = Entry::new("paper".to_string(), "inproceedings".to_string());
+        paper
+            .fields
+            .insert("crossref".to_string(), "neurips2020".to_string());
+        paper
+            .fields
+            .insert("xdata".to_string(), "missing, NeurIPS2020".to_string());
+
+        let findings = lint_entries(&[proceedings, paper], &LintConfig::default());
+        let crossref: Vec<_> = findings[1]
+            .iter()
+            .filter(|d| d.field == DiscrepancyField::Crossref)
+            .collect();
+        assert_eq!(crossref.len(), 2);
+        assert_eq!(crossref[0].severity, Severity::Error);
+        assert_eq!(crossref[1].severity, Severity::Warning);
+    }
+
+    #[test]
+    fn single_entry_lint_skips_cross_entry_checks() {
+        let mut paper = Entry::new("paper".to_string(), "inproceedings".to_string());
+        paper
+            .fields
+            .insert("crossref".to_string(), "elsewhere".to_string());
+        paper.fields.insert("note".to_string(), "TODO".to_string());
+
+        let findings = lint_entry(&paper, &LintConfig::default());
+        assert_eq!(findings.len(), 1);
+        assert_eq!(findings[0].field, DiscrepancyField::Placeholder);
+    }
+
+    #[test]
+    fn mixed_journal_spellings_suggest_the_majority_form() {
+        let journal = |key: &str, name: &str| {
+            let mut entry = Entry::new(key.to_string(), "article".to_string());
+            entry.venue = Some(name.into());
+            entry.fields.insert("journal".to_string(), name.to_string());
+            entry
+        };
+        let entries = [
+            journal("a", "Journal of Machine Learning Research"),
+            journal("b", "Journal of Machine Learning Research"),
+            journal("c", "J. Mach. Learn. Res."),
+        ];
+
+        let findings = lint_entries(&entries, &LintConfig::default());
+        assert!(findings[0].is_empty());
+        assert_eq!(
+            findings[2][0].remote_value,
+            "Journal of Machine Learning Research"
+        );
+
+        let config = LintConfig {
+            journal_style: Some(VenueForm::Iso4),
+            ..Default::default()
+        };
+        let findings = lint_entries(&entries, &config);
+        assert_eq!(findings[0][0].remote_value, "J. Mach. Learn. Res.");
+        assert!(findings[2].is_empty());
+
+        // Nature's ISO-4 abbreviation is its full name
+        assert!(lint_entry(&journal("d", "Nature"), &config).is_empty());
+        assert!(restyled_venue(&journal("d", "Nature"), &config).is_none());
+    }
+
+    #[test]
+    fn conferences_take_the_required_spelling() {
+        let mut entry = Entry::new("a".to_string(), "inproceedings".to_string());
+        entry.fields.insert("booktitle".to_string(), "NIPS".to_string());
+        let config = LintConfig {
+            conference_style: Some(VenueForm::Abbreviation),
+            ..Default::default()
+        };
+        assert_eq!(
+            restyled_venue(&entry, &config),
+            Some(("booktitle", "NeurIPS".to_string()))
+        );
+
+        entry
+            .fields
+            .insert("booktitle".to_string(), "ICML".to_string());
+        assert!(lint_entry(&entry, &config).is_empty());
+        let config = LintConfig {
+            conference_style: Some(VenueForm::Full),
+            ..Default::default()
+        };
+        assert_eq!(
+            lint_entry(&entry, &config)[0].remote_value,
+            "International Conference on Machine Learning"
+        );
+    }
+
+    #[test]
+    fn placeholders_and_template_values_are_errors() {
+        let mut entry = Entry::new("a".to_string(), "article".to_string());
+        entry
+            .fields
+            .insert("author".to_string(), "Author, A.".to_string());
+        entry
+            .fields
+            .insert("title".to_string(), "A Study of ??? Effects".to_string());
+        entry
+            .fields
+            .insert("note".to_string(), "TODO: add pages".to_string());
+        entry
+            .fields
+            .insert("journal".to_string(), "XXXL Structures".to_string());
+        entry.fields.insert("volume".to_string(), "XXX".to_string());
+        entry
+            .fields
+            .insert("booktitle".to_string(), "XXX Simpósio Brasileiro".to_string());
+        entry
+            .fields
+            .insert("abstract".to_string(), "We study TODO comments".to_string());
+
+        let findings = check_placeholders(&entry);
+        let severity = |field: &str| {
+            findings
+                .iter()
+                .find(|d| entry.field(field) == Some(d.local_value.as_str()))
+                .map(|d| d.severity)
+        };
+        assert_eq!(findings.len(), 3);
+        assert_eq!(severity("author"), Some(Severity::Error));
+        assert_eq!(severity("title"), Some(Severity::Error));
+        assert_eq!(severity("note"), Some(Severity::Error));
+    }
+}
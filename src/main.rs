@@ -1,81 +1,813 @@
-use bibval::{parser, BibValidator, ValidatorConfig};
-use clap::Parser;
+use bibval::{
+    checkpoint::Checkpoint,
+    citations::{self, Citations},
+    dedupe,
+    diff::{diff_entries, DiffReport},
+    entry::{Entry, Severity},
+    fix::{self, FixPolicy},
+    fusion,
+    history::{self, Comparison, History, RunRecord, Trends},
+    html, latex,
+    lint::{self, LintConfig},
+    markdown::MarkdownDocument,
+    mcp::McpServer,
+    parser,
+    predatory::PredatoryList,
+    report,
+    report::{EntryReport, EntryStatus, Report, SortOrder, Theme, Totals},
+    sources::git::{self, Checkout},
+    sources::grobid::{self, GrobidClient},
+    sources::mendeley::{self, Credentials, MendeleyClient},
+    sources::zotero::{self, Library, ZoteroClient},
+    template, tui,
+    validators::dblp::{rekey_bibtex, DblpBibtexFormat},
+    validators::doiorg::DoiOrgClient,
+    validators::dblp_dump::DblpDump,
+    validators::epo::EpoCredentials,
+    validators::sru::SruLibrary,
+    validators::worldcat::WorldCatCredentials,
+    venues::VenueForm,
+    workflow,
+    writer, BibValidator, ShortCircuitPolicy, ValidatorConfig,
+};
+use clap::{ArgAction, Parser, Subcommand};
 use colored::Colorize;
-use std::collections::HashSet;
-use std::path::PathBuf;
+use futures::StreamExt;
+use std::collections::{BTreeMap, HashMap, HashSet};
+use std::io::Write;
+use std::path::{Path, PathBuf};
 use std::process::ExitCode;
 
 #[derive(Parser, Debug)]
 #[command(name = "bibval")]
 #[command(version = "0.1.0")]
 #[command(about = "Validate BibTeX/BibLaTeX references against academic databases", long_about = None)]
+#[command(args_conflicts_with_subcommands = true, subcommand_negates_reqs = true)]
 struct Args {
-    /// Input .bib file(s) to validate
+    #[command(subcommand)]
+    command: Option<Command>,
+
+    /// Input .bib, .bbl, CSL-JSON/YAML, or Pandoc Markdown file(s) to validate
     #[arg(required = true)]
     files: Vec<PathBuf>,
 
     /// Disable CrossRef API
-    #[arg(long)]
+    #[arg(long, global = true)]
     no_crossref: bool,
 
     /// Disable DBLP API
-    #[arg(long)]
+    #[arg(long, global = true)]
     no_dblp: bool,
 
     /// Disable ArXiv API
-    #[arg(long)]
+    #[arg(long, global = true)]
     no_arxiv: bool,
 
     /// Disable Semantic Scholar API
-    #[arg(long)]
+    #[arg(long, global = true)]
     no_semantic: bool,
 
     /// Disable OpenAlex API
-    #[arg(long)]
+    #[arg(long, global = true)]
     no_openalex: bool,
 
     /// Disable Open Library API
-    #[arg(long)]
+    #[arg(long, global = true)]
     no_openlibrary: bool,
 
+    /// Disable HathiTrust API
+    #[arg(long, global = true)]
+    no_hathitrust: bool,
+
+    /// Disable PubMed API
+    #[arg(long, global = true)]
+    no_pubmed: bool,
+
+    /// Disable DOAJ lookups of open-access journal articles and ISSNs
+    #[arg(long, global = true)]
+    no_doaj: bool,
+
+    /// Disable SciELO lookups of Latin American and Iberian journal articles
+    #[arg(long, global = true)]
+    no_scielo: bool,
+
+    /// Disable J-STAGE lookups of Japanese journal articles
+    #[arg(long, global = true)]
+    no_jstage: bool,
+
+    /// NCBI API key; raises the PubMed rate limit
+    #[arg(long, env = "NCBI_API_KEY", hide_env_values = true, global = true)]
+    ncbi_api_key: Option<String>,
+
+    /// Disable ACM Digital Library lookups
+    #[arg(long, global = true)]
+    no_acm: bool,
+
+    /// Disable INSPIRE-HEP lookups
+    #[arg(long, global = true)]
+    no_inspire: bool,
+
+    /// Disable MathSciNet MR-number lookups
+    #[arg(long, global = true)]
+    no_mathscinet: bool,
+
+    /// Disable OSF Preprints lookups (PsyArXiv, SocArXiv, ...)
+    #[arg(long, global = true)]
+    no_osf: bool,
+
+    /// Disable Papers With Code lookups of machine learning papers
+    #[arg(long, global = true)]
+    no_paperswithcode: bool,
+
+    /// Disable HAL open archive lookups
+    #[arg(long, global = true)]
+    no_hal: bool,
+
+    /// Disable IETF Datatracker lookups of RFCs
+    #[arg(long, global = true)]
+    no_ietf: bool,
+
+    /// Disable W3C API lookups of cited specifications
+    #[arg(long, global = true)]
+    no_w3c: bool,
+
+    /// Springer Nature Meta API key; enables Springer Nature lookups
+    #[arg(long, env = "SPRINGER_API_KEY", hide_env_values = true, global = true)]
+    springer_key: Option<String>,
+
+    /// Elsevier API key; enables Scopus lookups
+    #[arg(long, env = "SCOPUS_API_KEY", hide_env_values = true, global = true)]
+    scopus_key: Option<String>,
+
+    /// Clarivate API key; enables Web of Science Starter API lookups
+    #[arg(long, env = "WOS_API_KEY", hide_env_values = true, global = true)]
+    wos_key: Option<String>,
+
+    /// NASA ADS API token; enables ADS lookups
+    #[arg(long, env = "ADS_API_TOKEN", hide_env_values = true, global = true)]
+    ads_token: Option<String>,
+
+    /// CORE API key; enables CORE as a fallback when no other source finds an entry
+    #[arg(long, env = "CORE_API_KEY", hide_env_values = true, global = true)]
+    core_key: Option<String>,
+
+    /// Email address for Unpaywall; enables DOI checks and reports open-access copies
+    #[arg(long, env = "UNPAYWALL_EMAIL", global = true)]
+    unpaywall_email: Option<String>,
+
     /// Disable OpenReview API
-    #[arg(long)]
+    #[arg(long, global = true)]
     no_openreview: bool,
 
     /// Disable Zenodo API
-    #[arg(long)]
+    #[arg(long, global = true)]
     no_zenodo: bool,
 
+    /// Disable DataCite API
+    #[arg(long, global = true)]
+    no_datacite: bool,
+
+    /// Disable the doi.org content-negotiation fallback for DOIs no other source knows
+    #[arg(long, global = true)]
+    no_doi_org: bool,
+
+    /// Disable figshare API
+    #[arg(long, global = true)]
+    no_figshare: bool,
+
+    /// Disable OpenCitations API
+    #[arg(long, global = true)]
+    no_opencitations: bool,
+
+    /// Don't check the GitHub repositories @software entries cite
+    #[arg(long, global = true)]
+    no_github: bool,
+
+    /// GitHub token, for higher rate limits on repository checks
+    #[arg(long, env = "GITHUB_TOKEN", hide_env_values = true, global = true)]
+    github_token: Option<String>,
+
+    /// Don't look up the repositories @software entries cite in Software Heritage
+    #[arg(long, global = true)]
+    no_software_heritage: bool,
+
+    /// Don't fetch the URLs of @online entries or look them up in the Wayback Machine
+    #[arg(long, global = true)]
+    no_url_check: bool,
+
+    /// Don't look for retraction, correction or expression-of-concern notices
+    #[arg(long, global = true)]
+    no_retraction_check: bool,
+
+    /// WorldCat Search API key (WSKey); enables WorldCat lookups of books and reports
+    #[arg(long, env = "WORLDCAT_API_KEY", hide_env_values = true, global = true)]
+    worldcat_key: Option<String>,
+
+    /// Secret of the WorldCat API key
+    #[arg(
+        long,
+        env = "WORLDCAT_API_SECRET",
+        hide_env_values = true,
+        requires = "worldcat_key",
+        global = true
+    )]
+    worldcat_secret: Option<String>,
+
+    /// EPO Open Patent Services consumer key; enables lookups of @patent entries
+    #[arg(long, env = "EPO_OPS_KEY", hide_env_values = true, global = true)]
+    epo_key: Option<String>,
+
+    /// Secret of the EPO Open Patent Services consumer key
+    #[arg(
+        long,
+        env = "EPO_OPS_SECRET",
+        hide_env_values = true,
+        requires = "epo_key",
+        global = true
+    )]
+    epo_secret: Option<String>,
+
+    /// National library catalogs to look up books in
+    #[arg(long, value_enum, value_delimiter = ',', value_name = "LIBRARY", global = true)]
+    library: Vec<NationalLibrary>,
+
     /// Disable caching of API responses
-    #[arg(long)]
+    #[arg(long, global = true)]
     no_cache: bool,
 
     /// Strict mode: exit with error code if any issues found
-    #[arg(long, short)]
+    #[arg(long, short, global = true)]
     strict: bool,
 
-    /// Verbose output
-    #[arg(long, short)]
-    verbose: bool,
+    /// Verbose output; repeat (-vv) to trace the lookups made for each entry
+    #[arg(long, short, action = ArgAction::Count, global = true)]
+    verbose: u8,
+
+    /// When to color output; `auto` colors terminals unless NO_COLOR is set
+    #[arg(long, value_enum, default_value_t = ColorMode::Auto, global = true)]
+    color: ColorMode,
+
+    /// Draw progress with ASCII characters only
+    #[arg(long, global = true)]
+    ascii: bool,
+
+    /// Severity colors of the text report, e.g. `error=magenta,info=bright black`
+    #[arg(long, env = "BIBVAL_THEME", value_name = "COLORS", global = true)]
+    theme: Option<Theme>,
 
     /// Only validate entries with these citation keys (comma-separated or repeatable)
-    #[arg(long = "key", short = 'k', value_delimiter = ',')]
+    #[arg(long = "key", short = 'k', value_delimiter = ',', global = true)]
     keys: Vec<String>,
+
+    /// Required citation key style: `authoryear`, `dblp`, or a custom regex
+    #[arg(long, global = true)]
+    key_pattern: Option<String>,
+
+    /// Require known journals to be spelled in this style
+    #[arg(long, value_enum, global = true)]
+    journal_style: Option<JournalStyle>,
+
+    /// Require known conferences in `booktitle` to be spelled in this style
+    #[arg(long, value_enum, global = true)]
+    conference_style: Option<ConferenceStyle>,
+
+    /// Flag journals, publishers and DOI prefixes on the bundled predatory list
+    #[arg(long, global = true)]
+    predatory: bool,
+
+    /// Also flag the entries of this predatory list (implies --predatory)
+    #[arg(long, value_name = "FILE", global = true)]
+    predatory_list: Option<PathBuf>,
+
+    /// Add information to the report from results already fetched
+    #[arg(long, value_enum, value_delimiter = ',', value_name = "WHAT", global = true)]
+    enrich: Vec<Enrichment>,
+
+    /// Report format
+    #[arg(long, value_enum, default_value_t = OutputFormat::Text, global = true)]
+    format: OutputFormat,
+
+    /// List entries in this order instead of grouping them by status
+    #[arg(long, value_enum, value_name = "ORDER", global = true)]
+    sort: Option<ReportOrder>,
+
+    /// Drop findings below this severity, so they are neither shown nor counted
+    #[arg(long, value_enum, default_value_t = MinSeverity::Info, global = true)]
+    min_severity: MinSeverity,
+
+    /// Render the report through this Tera template instead of a built-in format
+    #[arg(
+        long,
+        value_name = "PATH",
+        global = true,
+        conflicts_with_all = ["format", "tui", "compare_to"]
+    )]
+    template: Option<PathBuf>,
+
+    /// Insert missing DOIs the validators confidently agree on into the .bib files, once
+    /// doi.org confirms each is registered
+    #[arg(long, conflicts_with = "stream")]
+    fix_doi: bool,
+
+    /// Show what fix, dedupe or --fix-doi would change without writing anything; with
+    /// `--format diff`, as a unified diff
+    #[arg(long, global = true)]
+    dry_run: bool,
+
+    /// Print only the issues added, fixed or changed since this earlier JSON report
+    #[arg(long, value_name = "REPORT", global = true, conflicts_with = "tui")]
+    compare_to: Option<PathBuf>,
+
+    /// Also write the entry counts by status and finding counts by severity to this file,
+    /// as JSON
+    #[arg(long, value_name = "PATH", global = true)]
+    summary_json: Option<PathBuf>,
+
+    /// Write the JSON, HTML, GitHub or NDJSON report to this file instead of stdout
+    #[arg(long, value_name = "FILE")]
+    output: Option<PathBuf>,
+
+    /// Browse the results interactively instead of printing the report; the keys of
+    /// entries marked for fixing are printed on exit
+    #[arg(long, global = true, conflicts_with = "format")]
+    tui: bool,
+
+    /// Suggest DBLP's curated BibTeX as a replacement for entries with issues
+    #[arg(long, value_enum, value_name = "FORMAT", global = true)]
+    dblp_bibtex: Option<DblpFormat>,
+
+    /// Answer DBLP lookups offline from a downloaded dblp.xml.gz, indexed on first use
+    #[arg(long, value_name = "PATH", global = true)]
+    dblp_dump: Option<PathBuf>,
+
+    /// Skip remaining identifier lookups once N sources agree with an entry
+    #[arg(long, value_name = "N", global = true)]
+    short_circuit: Option<usize>,
+
+    /// Show per-API request rates and adaptive concurrency limits after the report
+    #[arg(long, global = true)]
+    profile: bool,
+
+    /// Parse and validate large files incrementally (cross-entry checks are skipped)
+    #[arg(long, global = true)]
+    stream: bool,
+
+    /// Record run state, and resume an interrupted run that recorded it, skipping entries
+    /// it already validated
+    #[arg(long, global = true)]
+    resume: bool,
+
+    /// Record run state for --resume at PATH (defaults to a file in the cache directory,
+    /// unless --no-cache is given)
+    #[arg(long, value_name = "PATH", global = true)]
+    state_file: Option<PathBuf>,
+}
+
+#[derive(Subcommand, Debug)]
+enum Command {
+    /// Validate a Zotero library through the Zotero Web API
+    Zotero(ZoteroArgs),
+    /// Validate a Mendeley library through the Mendeley API
+    Mendeley(MendeleyArgs),
+    /// Clone a git-hosted project (such as an Overleaf project) and validate the entries it cites
+    Remote(RemoteArgs),
+    /// Build a bibliography from a folder of PDFs using a GROBID service
+    Extract(ExtractArgs),
+    /// Apply the corrections the validators are confident about and write the corrected
+    /// bibliography
+    Fix(FixArgs),
+    /// Merge entries that cite the same work, keeping the fields each of them has
+    Dedupe(DedupeArgs),
+    /// Compare two versions of a bibliography and how their validation status differs
+    Diff(DiffArgs),
+    /// Validate and record the run in a history file, reporting only what got worse
+    Monitor(MonitorArgs),
+    /// Serve validation tools to AI assistants over the Model Context Protocol (stdio)
+    Mcp,
+}
+
+#[derive(clap::Args, Debug)]
+struct ZoteroArgs {
+    /// ID of a personal library (shown at zotero.org/settings/keys)
+    #[arg(long, required_unless_present = "group_id", conflicts_with = "group_id")]
+    user_id: Option<u64>,
+
+    /// ID of a group library
+    #[arg(long)]
+    group_id: Option<u64>,
+
+    /// Zotero API key; needs write access for --write-back
+    #[arg(long, env = "ZOTERO_API_KEY", hide_env_values = true)]
+    api_key: Option<String>,
+
+    /// Only validate items in this collection (collection key)
+    #[arg(long)]
+    collection: Option<String>,
+
+    /// Write corrected titles, dates and DOIs back to the library
+    #[arg(long)]
+    write_back: bool,
+}
+
+#[derive(clap::Args, Debug)]
+struct MendeleyArgs {
+    /// OAuth access token for the Mendeley API
+    #[arg(
+        long,
+        env = "MENDELEY_ACCESS_TOKEN",
+        hide_env_values = true,
+        required_unless_present = "refresh_token"
+    )]
+    access_token: Option<String>,
+
+    /// OAuth refresh token, exchanged for an access token with the app's client credentials
+    #[arg(
+        long,
+        env = "MENDELEY_REFRESH_TOKEN",
+        hide_env_values = true,
+        requires_all = ["client_id", "client_secret"]
+    )]
+    refresh_token: Option<String>,
+
+    /// Client ID of the Mendeley app the refresh token was issued to
+    #[arg(long, env = "MENDELEY_CLIENT_ID")]
+    client_id: Option<String>,
+
+    /// Client secret of the Mendeley app the refresh token was issued to
+    #[arg(long, env = "MENDELEY_CLIENT_SECRET", hide_env_values = true)]
+    client_secret: Option<String>,
+}
+
+#[derive(clap::Args, Debug)]
+struct RemoteArgs {
+    /// Git URL of the project, e.g. https://git.overleaf.com/<project-id>
+    url: String,
+
+    /// Branch to check out instead of the default branch
+    #[arg(long)]
+    branch: Option<String>,
+}
+
+#[derive(clap::Args, Debug)]
+struct ExtractArgs {
+    /// Folder of PDFs (searched recursively)
+    dir: PathBuf,
+
+    /// Base URL of the GROBID service
+    #[arg(long, env = "GROBID_URL", default_value = grobid::DEFAULT_GROBID_URL)]
+    grobid_url: String,
+
+    /// Where to write the extracted bibliography
+    #[arg(long, short, default_value = "extracted.bib")]
+    output: PathBuf,
+}
+
+#[derive(clap::Args, Debug)]
+struct FixArgs {
+    /// BibTeX file to correct
+    file: PathBuf,
+
+    /// Write the corrected bibliography here instead of to stdout
+    #[arg(long, short, value_name = "PATH", conflicts_with = "in_place")]
+    output: Option<PathBuf>,
+
+    /// Overwrite the file with its corrected version
+    #[arg(long)]
+    in_place: bool,
+
+    /// Ask before applying each correction, like `git add -p`
+    #[arg(long, short)]
+    interactive: bool,
+
+    /// Only fix these fields, e.g. `--fix=doi,year,pages`
+    #[arg(
+        long = "fix",
+        value_name = "FIELDS",
+        value_delimiter = ',',
+        value_parser = FixPolicy::field_from_str
+    )]
+    only: Option<Vec<String>>,
+
+    /// Never fix these fields, e.g. `--no-fix=title,authors`
+    #[arg(
+        long = "no-fix",
+        value_name = "FIELDS",
+        value_delimiter = ',',
+        value_parser = FixPolicy::field_from_str
+    )]
+    except: Vec<String>,
+
+    /// Replace entries whose DOI a match confirms with the BibTeX doi.org serves for it,
+    /// or with DBLP's record when --dblp-bibtex offers one, keeping their citation keys
+    #[arg(long, conflicts_with = "interactive")]
+    regenerate: bool,
+
+    /// Rename entries whose keys contain whitespace or non-ASCII characters, or don't
+    /// match --key-pattern, to their suggested `authorYEARword` keys
+    #[arg(long)]
+    rename_keys: bool,
+
+    /// Also rename the keys cited by the .tex files under this directory, writing them
+    /// together with the bibliography (needs --in-place or --output)
+    #[arg(long, value_name = "DIR", requires = "rename_keys")]
+    tex_dir: Option<PathBuf>,
+}
+
+#[derive(clap::Args, Debug)]
+struct DedupeArgs {
+    /// BibTeX file to deduplicate
+    file: PathBuf,
+
+    /// Write the merged bibliography here instead of to stdout
+    #[arg(long, short, value_name = "PATH", conflicts_with = "in_place")]
+    output: Option<PathBuf>,
+
+    /// Overwrite the file with its merged version
+    #[arg(long)]
+    in_place: bool,
+
+    /// Also cite the kept entries instead of the removed duplicates in the .tex files
+    /// under this directory, writing them together with the bibliography (needs
+    /// --in-place or --output)
+    #[arg(long, value_name = "DIR")]
+    tex_dir: Option<PathBuf>,
+}
+
+#[derive(clap::Args, Debug)]
+struct DiffArgs {
+    /// The old and new versions, or just the new one with --against
+    #[arg(required = true, num_args = 1..=2, value_name = "FILE")]
+    files: Vec<PathBuf>,
+
+    /// Compare the file with its contents at this git revision (e.g. HEAD, main~3)
+    #[arg(long, value_name = "REV")]
+    against: Option<String>,
+}
+
+#[derive(clap::Args, Debug)]
+struct MonitorArgs {
+    /// Bibliography file(s) to validate
+    #[arg(required = true)]
+    files: Vec<PathBuf>,
+
+    /// History file to record runs in (defaults to a file in the data directory)
+    #[arg(long, value_name = "PATH")]
+    history: Option<PathBuf>,
+
+    /// Compare with the latest run at least this old, e.g. 30d, 4w or 12h, instead of
+    /// the previous run
+    #[arg(long, value_name = "AGE", value_parser = parse_age)]
+    since: Option<chrono::Duration>,
+}
+
+fn parse_age(text: &str) -> Result<chrono::Duration, String> {
+    history::parse_age(text)
+        .ok_or_else(|| "expected a number of hours, days or weeks, e.g. 30d".to_string())
+}
+
+#[derive(clap::ValueEnum, Clone, Copy, Debug)]
+enum JournalStyle {
+    /// Full journal names
+    Full,
+    /// ISO-4 abbreviations
+    Iso4,
+}
+
+impl From<JournalStyle> for VenueForm {
+    fn from(style: JournalStyle) -> Self {
+        match style {
+            JournalStyle::Full => VenueForm::Full,
+            JournalStyle::Iso4 => VenueForm::Iso4,
+        }
+    }
+}
+
+#[derive(clap::ValueEnum, Clone, Copy, Debug)]
+enum ConferenceStyle {
+    /// Full conference names
+    Full,
+    /// Common short names (NeurIPS, ICML, PLDI)
+    Short,
+}
+
+impl From<ConferenceStyle> for VenueForm {
+    fn from(style: ConferenceStyle) -> Self {
+        match style {
+            ConferenceStyle::Full => VenueForm::Full,
+            ConferenceStyle::Short => VenueForm::Abbreviation,
+        }
+    }
+}
+
+#[derive(clap::ValueEnum, Clone, Copy, Debug)]
+enum DblpFormat {
+    /// Abbreviated venue names
+    Condensed,
+    /// Full venue names
+    Standard,
+}
+
+impl From<DblpFormat> for DblpBibtexFormat {
+    fn from(format: DblpFormat) -> Self {
+        match format {
+            DblpFormat::Condensed => DblpBibtexFormat::Condensed,
+            DblpFormat::Standard => DblpBibtexFormat::Standard,
+        }
+    }
+}
+
+#[derive(clap::ValueEnum, Clone, Copy, Debug, PartialEq, Eq)]
+enum Enrichment {
+    /// Citation counts from Semantic Scholar and OpenAlex, which `fix` writes into the
+    /// `note` field
+    Citations,
+    /// Official code repositories of machine learning papers, from Papers With Code
+    Code,
+    /// Abstracts from Semantic Scholar and OpenAlex, which `fix` adds to entries that lack
+    /// them
+    Abstract,
+    /// Keywords from OpenAlex, which `fix` adds to entries that lack them
+    Keywords,
+    /// Release versions of software and datasets from Zenodo and DataCite, which `fix`
+    /// adds to entries that lack them
+    Version,
+    /// Licenses of software and datasets from Zenodo and DataCite, which `fix` adds to
+    /// entries that lack them
+    License,
+    /// Open-access copies from OpenAlex as well as Unpaywall, flagging works only
+    /// available behind a paywall; `fix` adds them as the `url` of entries without one
+    OpenAccess,
+}
+
+#[derive(clap::ValueEnum, Clone, Copy, Debug, PartialEq, Eq)]
+enum NationalLibrary {
+    /// Deutsche Nationalbibliothek
+    Dnb,
+    /// British Library
+    Bl,
+}
+
+impl From<NationalLibrary> for SruLibrary {
+    fn from(library: NationalLibrary) -> Self {
+        match library {
+            NationalLibrary::Dnb => SruLibrary::Dnb,
+            NationalLibrary::Bl => SruLibrary::BritishLibrary,
+        }
+    }
+}
+
+#[derive(clap::ValueEnum, Clone, Copy, Debug, PartialEq, Eq)]
+enum OutputFormat {
+    /// Colored report for the terminal
+    Text,
+    /// The full report as JSON
+    Json,
+    /// Self-contained HTML page with filterable, expandable results
+    Html,
+    /// GitHub Actions workflow commands that annotate the .bib file's lines
+    Github,
+    /// One JSON object per line for each entry, written as soon as it is validated
+    Ndjson,
+    /// A LaTeX longtable of entries, statuses and issues, for a thesis appendix
+    Latex,
+    /// One `file:line: [key] SEVERITY field: message` line per issue, for grep and
+    /// quickfix lists
+    Compact,
+    /// With --dry-run, a unified diff of the changes fix, dedupe or --fix-doi would make,
+    /// for code review before applying them
+    Diff,
+}
+
+#[derive(clap::ValueEnum, Clone, Copy, Debug)]
+enum ColorMode {
+    /// Color terminals, unless NO_COLOR is set
+    Auto,
+    Always,
+    Never,
+}
+
+#[derive(clap::ValueEnum, Clone, Copy, Debug)]
+enum ReportOrder {
+    /// Alphabetically by citation key
+    Key,
+    /// Worst first: errors, failed lookups, warnings, not found, valid
+    Severity,
+    /// Least confident match first
+    Confidence,
+    /// By file and line
+    File,
+}
+
+impl From<ReportOrder> for SortOrder {
+    fn from(order: ReportOrder) -> Self {
+        match order {
+            ReportOrder::Key => SortOrder::Key,
+            ReportOrder::Severity => SortOrder::Severity,
+            ReportOrder::Confidence => SortOrder::Confidence,
+            ReportOrder::File => SortOrder::File,
+        }
+    }
+}
+
+#[derive(clap::ValueEnum, Clone, Copy, Debug, PartialEq)]
+enum MinSeverity {
+    Info,
+    Warning,
+    Error,
+}
+
+impl From<MinSeverity> for Severity {
+    fn from(severity: MinSeverity) -> Self {
+        match severity {
+            MinSeverity::Info => Severity::Info,
+            MinSeverity::Warning => Severity::Warning,
+            MinSeverity::Error => Severity::Error,
+        }
+    }
 }
 
 #[tokio::main]
 async fn main() -> ExitCode {
     let args = Args::parse();
 
+    match args.color {
+        ColorMode::Always => colored::control::set_override(true),
+        ColorMode::Never => colored::control::set_override(false),
+        // colored checks NO_COLOR, CLICOLOR and whether stdout is a terminal
+        ColorMode::Auto => {}
+    }
+    if let Some(theme) = args.theme {
+        report::set_theme(theme);
+    }
+
     // Initialize logging
-    if args.verbose {
+    if args.verbose > 0 {
         tracing_subscriber::fmt()
             .with_env_filter("bibval=debug")
+            .with_ansi(colored::control::SHOULD_COLORIZE.should_colorize())
+            .with_writer(std::io::stderr)
             .init();
     }
 
-    // Parse all input files
+    if args.compare_to.is_some() && !matches!(args.format, OutputFormat::Text | OutputFormat::Json)
+    {
+        eprintln!(
+            "{} Comparisons are reported only as text or JSON",
+            "Error:".red().bold()
+        );
+        return ExitCode::FAILURE;
+    }
+    if args.fix_doi && args.format == OutputFormat::Ndjson {
+        eprintln!(
+            "{} NDJSON reports aren't kept, so --fix-doi can't be used with them",
+            "Error:".red().bold()
+        );
+        return ExitCode::FAILURE;
+    }
+    let fixes = args.fix_doi || matches!(args.command, Some(Command::Fix(_) | Command::Dedupe(_)));
+    if args.dry_run && !fixes {
+        eprintln!(
+            "{} --dry-run applies only to fix, dedupe and --fix-doi",
+            "Error:".red().bold()
+        );
+        return ExitCode::FAILURE;
+    }
+    if args.format == OutputFormat::Diff && !args.dry_run {
+        eprintln!(
+            "{} --format diff shows the changes --dry-run would make; add --dry-run",
+            "Error:".red().bold()
+        );
+        return ExitCode::FAILURE;
+    }
+    if args.sort.is_some() && args.format == OutputFormat::Ndjson {
+        eprintln!(
+            "{} NDJSON is written as entries are validated, so it can't be sorted",
+            "Error:".red().bold()
+        );
+        return ExitCode::FAILURE;
+    }
+
+    match &args.command {
+        Some(Command::Zotero(zotero)) => return validate_zotero(&args, zotero).await,
+        Some(Command::Mendeley(mendeley)) => return validate_mendeley(&args, mendeley).await,
+        Some(Command::Remote(remote)) => return validate_remote(&args, remote).await,
+        Some(Command::Extract(extract)) => return extract_pdfs(&args, extract).await,
+        Some(Command::Fix(fix_args)) => return fix_bibliography(&args, fix_args).await,
+        Some(Command::Dedupe(dedupe)) => return dedupe_bibliography(&args, dedupe),
+        Some(Command::Diff(diff)) => return diff_bibliographies(&args, diff).await,
+        Some(Command::Monitor(monitor)) => return monitor_bibliography(&args, monitor).await,
+        Some(Command::Mcp) => return serve_mcp(&args).await,
+        None => {}
+    }
+
+    if args.stream {
+        return validate_streaming(&args).await;
+    }
+
+    // Parse all input files. Markdown documents contribute the bibliographies they
+    // link to, and narrow validation down to the entries they cite.
     let mut all_entries = Vec::new();
+    let mut cited: Option<Citations> = None;
 
     for file in &args.files {
         if !file.exists() {
@@ -83,11 +815,21 @@ async fn main() -> ExitCode {
             return ExitCode::FAILURE;
         }
 
-        println!("Parsing {}...", file.display().to_string().cyan());
+        eprintln!("Parsing {}...", file.display().to_string().cyan());
 
-        match parser::parse_bib_file(file) {
+        let parsed = if is_markdown(file) {
+            MarkdownDocument::open(file).and_then(|document| {
+                let entries = document.load_entries()?;
+                cited.get_or_insert_with(Citations::default).merge(document.citations);
+                Ok(entries)
+            })
+        } else {
+            parser::parse_file(file)
+        };
+
+        match parsed {
             Ok(entries) => {
-                println!("  Found {} entries", entries.len());
+                eprintln!("  Found {} entries", entries.len());
                 all_entries.extend(entries);
             }
             Err(e) => {
@@ -103,70 +845,1557 @@ async fn main() -> ExitCode {
     }
 
     if all_entries.is_empty() {
-        println!("{}", "No entries found to validate.".yellow());
+        eprintln!("{}", "No entries found to validate.".yellow());
         return ExitCode::SUCCESS;
     }
 
-    // Apply key filtering if requested
-    if !args.keys.is_empty() {
-        let key_filter: HashSet<_> = args.keys.iter().collect();
-        let before = all_entries.len();
-        all_entries.retain(|e| key_filter.contains(&e.key));
-        let removed = before - all_entries.len();
+    if let Some(citations) = &cited {
+        warn_missing_citations(&all_entries, citations);
+    }
+
+    // Apply key filtering if requested; filtered-out entries still serve as
+    // context for cross-entry checks such as crossref targets
+    let key_filter: HashSet<_> = args.keys.iter().cloned().collect();
+    let is_selected = |e: &Entry| {
+        cited.as_ref().is_none_or(|c| c.includes(&e.key))
+            && (key_filter.is_empty() || key_filter.contains(&e.key))
+    };
+    let selected = all_entries.iter().filter(|e| is_selected(e)).count();
+
+    if cited.is_some() && selected == 0 {
+        eprintln!("{}", "No cited entries to validate.".yellow());
+        return ExitCode::SUCCESS;
+    }
 
-        if args.verbose && removed > 0 {
-            println!(
+    if !key_filter.is_empty() {
+        let removed = all_entries.len() - selected;
+
+        if args.verbose > 0 && removed > 0 {
+            eprintln!(
                 "{} {} entries filtered out; validating {} remaining.",
                 "Info:".blue().bold(),
                 removed,
-                all_entries.len()
+                selected
             );
         }
 
-        if all_entries.is_empty() {
-            println!("{}", "No entries matched the provided keys.".yellow());
+        if selected == 0 {
+            eprintln!("{}", "No entries matched the provided keys.".yellow());
             return ExitCode::SUCCESS;
         }
     }
 
-    println!();
-    println!("Validating {} entries...", all_entries.len());
-    println!();
+    eprintln!();
+    eprintln!("Validating {} entries...", selected);
+    eprintln!();
 
-    // Configure validator
-    let config = ValidatorConfig {
-        use_crossref: !args.no_crossref,
-        use_dblp: !args.no_dblp,
-        use_arxiv: !args.no_arxiv,
-        use_semantic: !args.no_semantic,
-        use_openalex: !args.no_openalex,
-        use_openlibrary: !args.no_openlibrary,
-        use_openreview: !args.no_openreview,
-        use_zenodo: !args.no_zenodo,
-        cache_enabled: !args.no_cache,
+    let validator = match build_validator(&args, &args.files) {
+        Ok(v) => v,
+        Err(code) => return code,
     };
 
-    // Create validator
-    let validator = match BibValidator::new(config) {
-        Ok(v) => v,
-        Err(e) => {
-            eprintln!("{} Failed to initialize validator: {}", "Error:".red().bold(), e);
+    // Run validation
+    if args.format == OutputFormat::Ndjson {
+        let Some(mut sink) = NdjsonSink::open(&args) else {
             return ExitCode::FAILURE;
+        };
+        validator
+            .validate_only_each(all_entries, is_selected, |r| sink.write(&r))
+            .await;
+        return sink.finish(&args, &validator);
+    }
+    let mut report = validator.validate_only(all_entries, is_selected).await;
+
+    let code = finish(&args, &validator, &mut report);
+    if args.fix_doi && !fill_missing_dois(&args, &report).await {
+        return ExitCode::FAILURE;
+    }
+    code
+}
+
+/// Insert the DOIs the validators agree entries are missing into the BibTeX files they
+/// were read from, skipping any doi.org doesn't know; false if a file couldn't be written
+async fn fill_missing_dois(args: &Args, report: &Report) -> bool {
+    let client = DoiOrgClient::new();
+    let mut by_file: BTreeMap<&Path, HashMap<String, Vec<fix::Correction>>> = BTreeMap::new();
+    for entry_report in &report.entries {
+        let key = &entry_report.entry.key;
+        let location = entry_report.entry.location.as_ref();
+        let Some(file) = location.and_then(|l| l.file.as_deref()) else {
+            continue;
+        };
+        if !file.extension().is_some_and(|e| e.eq_ignore_ascii_case("bib")) {
+            continue;
+        }
+        let Some(correction) = fix::corrections(entry_report)
+            .into_iter()
+            .find(|c| c.field == "doi" && c.old.is_none())
+        else {
+            continue;
+        };
+
+        match client.registration_agency(&correction.new).await {
+            Ok(Some(_)) => {}
+            Ok(None) => {
+                eprintln!(
+                    "{} [{}] DOI {} isn't registered; not added",
+                    "Warning:".yellow().bold(),
+                    key,
+                    correction.new
+                );
+                continue;
+            }
+            Err(e) => {
+                eprintln!(
+                    "{} [{}] Could not check DOI {}: {}",
+                    "Warning:".yellow().bold(),
+                    key,
+                    correction.new,
+                    e
+                );
+                continue;
+            }
+        }
+        by_file.entry(file).or_default().insert(key.clone(), vec![correction]);
+    }
+
+    let mut ok = true;
+    for (file, corrections) in by_file {
+        let fixed = std::fs::read_to_string(file)
+            .map_err(|e| e.to_string())
+            .and_then(|content| {
+                let fixed = fix::apply(&content, &corrections).map_err(|e| e.to_string())?;
+                if !args.dry_run {
+                    let files = [(file.to_path_buf(), fixed)];
+                    return fix::write_all(&files).map_err(|e| e.to_string());
+                }
+                if args.format == OutputFormat::Diff {
+                    print!("{}", fix::unified_diff(file, &content, &fixed));
+                }
+                Ok(())
+            });
+        match fixed {
+            Ok(()) if args.dry_run => {
+                eprintln!("Would add {} DOIs to {}", corrections.len(), file.display())
+            }
+            Ok(()) => eprintln!("Added {} DOIs to {}", corrections.len(), file.display()),
+            Err(e) => {
+                eprintln!("{} Failed to update {}: {}", "Error:".red().bold(), file.display(), e);
+                ok = false;
+            }
+        }
+    }
+    ok
+}
+
+/// Validate each file while it is being read instead of parsing everything up front
+async fn validate_streaming(args: &Args) -> ExitCode {
+    /// Parsed entries waiting to be validated
+    const STREAM_QUEUE: usize = 1024;
+
+    let mut streams = Vec::new();
+    for file in &args.files {
+        match parser::BibStream::open(file) {
+            Ok(stream) => streams.push((file.clone(), stream)),
+            Err(e) => {
+                eprintln!(
+                    "{} Failed to open {}: {}",
+                    "Error:".red().bold(),
+                    file.display(),
+                    e
+                );
+                return ExitCode::FAILURE;
+            }
         }
+    }
+
+    let validator = match build_validator(args, &args.files) {
+        Ok(v) => v,
+        Err(code) => return code,
     };
 
-    // Run validation
-    let report = validator.validate(all_entries).await;
+    let key_filter: HashSet<_> = args.keys.iter().cloned().collect();
+    let is_selected = |e: &Entry| key_filter.is_empty() || key_filter.contains(&e.key);
 
-    // Print report
-    report.print();
+    eprintln!("Streaming {} file(s)...", streams.len());
+    eprintln!();
 
-    // Determine exit code
-    if args.strict && (report.count_errors() > 0 || report.count_warnings() > 0) {
-        ExitCode::FAILURE
-    } else if report.count_errors() > 0 {
-        ExitCode::FAILURE
+    // Files are read on a blocking thread so the runtime keeps serving lookups. A chunk
+    // that fails to parse is skipped so the rest of the file still gets checked
+    let (tx, mut rx) = tokio::sync::mpsc::channel(STREAM_QUEUE);
+    let reader = tokio::task::spawn_blocking(move || {
+        let mut skipped = 0;
+        for (file, stream) in streams {
+            for chunk in stream {
+                match chunk {
+                    Ok(entries) => {
+                        for entry in entries {
+                            if tx.blocking_send(entry).is_err() {
+                                return skipped;
+                            }
+                        }
+                    }
+                    Err(e) => {
+                        eprintln!(
+                            "{} Skipping part of {}: {}",
+                            "Warning:".yellow().bold(),
+                            file.display(),
+                            e
+                        );
+                        skipped += 1;
+                    }
+                }
+            }
+        }
+        skipped
+    });
+    let entries = futures::stream::poll_fn(move |cx| rx.poll_recv(cx));
+    let code = if args.format == OutputFormat::Ndjson {
+        let Some(mut sink) = NdjsonSink::open(args) else {
+            return ExitCode::FAILURE;
+        };
+        validator
+            .validate_stream_each(entries, is_selected, |r| sink.write(&r))
+            .await;
+        sink.finish(args, &validator)
     } else {
-        ExitCode::SUCCESS
+        let mut report = validator.validate_stream(entries, is_selected).await;
+        finish(args, &validator, &mut report)
+    };
+    match reader.await {
+        Ok(0) => code,
+        _ => ExitCode::FAILURE,
+    }
+}
+
+/// Validate the items of a Zotero library, optionally writing corrections back
+async fn validate_zotero(args: &Args, zotero: &ZoteroArgs) -> ExitCode {
+    let library = match (zotero.user_id, zotero.group_id) {
+        (Some(id), _) => Library::User(id),
+        (None, Some(id)) => Library::Group(id),
+        (None, None) => unreachable!("clap requires --user-id or --group-id"),
+    };
+    let client = ZoteroClient::new(library, zotero.api_key.clone());
+
+    eprintln!("Fetching Zotero library {}...", library.to_string().cyan());
+    let items = match client.fetch_items(zotero.collection.as_deref()).await {
+        Ok(items) => items,
+        Err(e) => {
+            eprintln!("{} Failed to fetch Zotero library: {}", "Error:".red().bold(), e);
+            return ExitCode::FAILURE;
+        }
+    };
+    eprintln!("  Found {} items", items.len());
+
+    if items.is_empty() {
+        eprintln!("{}", "No entries found to validate.".yellow());
+        return ExitCode::SUCCESS;
+    }
+
+    // Run state is keyed by library rather than by input file
+    let validator = match build_validator(args, &[PathBuf::from(format!("zotero:{}", library))]) {
+        Ok(v) => v,
+        Err(code) => return code,
+    };
+
+    let key_filter: HashSet<_> = args.keys.iter().cloned().collect();
+    let is_selected = |e: &Entry| key_filter.is_empty() || key_filter.contains(&e.key);
+
+    let entries: Vec<Entry> = items.iter().map(zotero::item_to_entry).collect();
+    eprintln!();
+    eprintln!("Validating {} entries...", entries.len());
+    eprintln!();
+
+    // Items sharing a citation key can't be told apart in the report, so never update them
+    let mut by_key: HashMap<&str, Option<&zotero::ZoteroItem>> = HashMap::new();
+    for (entry, item) in entries.iter().zip(&items) {
+        by_key
+            .entry(entry.key.as_str())
+            .and_modify(|shared| *shared = None)
+            .or_insert(Some(item));
+    }
+    let by_key: HashMap<String, &zotero::ZoteroItem> = by_key
+        .into_iter()
+        .filter_map(|(key, item)| Some((key.to_string(), item?)))
+        .collect();
+
+    let mut report = validator.validate_only(entries, is_selected).await;
+    let code = finish(args, &validator, &mut report);
+
+    if zotero.write_back {
+        let updates: Vec<_> = report
+            .entries
+            .iter()
+            .filter_map(|r| zotero::corrections(by_key.get(&r.entry.key)?, r))
+            .collect();
+        if updates.is_empty() {
+            eprintln!("No corrections to write back to Zotero.");
+            return code;
+        }
+
+        for update in &updates {
+            let fields: Vec<_> = update.fields.keys().map(String::as_str).collect();
+            eprintln!("  Updating {}: {}", update.key.dimmed(), fields.join(", "));
+        }
+        match client.update_items(&updates).await {
+            Ok(summary) => {
+                eprintln!("Updated {} Zotero items", summary.updated);
+                for (key, message) in &summary.failed {
+                    eprintln!(
+                        "{} Could not update {}: {}",
+                        "Warning:".yellow().bold(),
+                        key,
+                        message
+                    );
+                }
+            }
+            Err(e) => {
+                eprintln!("{} Failed to update Zotero library: {}", "Error:".red().bold(), e);
+                return ExitCode::FAILURE;
+            }
+        }
+    }
+
+    code
+}
+
+/// Validate the documents of a Mendeley library, reported by document ID
+async fn validate_mendeley(args: &Args, mendeley: &MendeleyArgs) -> ExitCode {
+    let credentials = match (&mendeley.access_token, &mendeley.refresh_token) {
+        (Some(token), _) => Credentials::AccessToken(token.clone()),
+        (None, Some(token)) => Credentials::RefreshToken {
+            client_id: mendeley.client_id.clone().unwrap_or_default(),
+            client_secret: mendeley.client_secret.clone().unwrap_or_default(),
+            refresh_token: token.clone(),
+        },
+        (None, None) => unreachable!("clap requires --access-token or --refresh-token"),
+    };
+    let client = match MendeleyClient::connect(credentials).await {
+        Ok(client) => client,
+        Err(e) => {
+            eprintln!("{} Failed to authenticate with Mendeley: {}", "Error:".red().bold(), e);
+            return ExitCode::FAILURE;
+        }
+    };
+
+    eprintln!("Fetching Mendeley library...");
+    let documents = match client.fetch_documents().await {
+        Ok(documents) => documents,
+        Err(e) => {
+            eprintln!("{} Failed to fetch Mendeley library: {}", "Error:".red().bold(), e);
+            return ExitCode::FAILURE;
+        }
+    };
+    eprintln!("  Found {} documents", documents.len());
+
+    if documents.is_empty() {
+        eprintln!("{}", "No entries found to validate.".yellow());
+        return ExitCode::SUCCESS;
+    }
+
+    let validator = match build_validator(args, &[PathBuf::from("mendeley:documents")]) {
+        Ok(v) => v,
+        Err(code) => return code,
+    };
+
+    let key_filter: HashSet<_> = args.keys.iter().cloned().collect();
+    let is_selected = |e: &Entry| key_filter.is_empty() || key_filter.contains(&e.key);
+
+    let entries: Vec<Entry> = documents.iter().map(mendeley::document_to_entry).collect();
+    eprintln!();
+    eprintln!("Validating {} entries...", entries.len());
+    eprintln!();
+
+    let mut report = validator.validate_only(entries, is_selected).await;
+    finish(args, &validator, &mut report)
+}
+
+/// Validate the bibliography entries cited by the LaTeX sources of a remote project
+async fn validate_remote(args: &Args, remote: &RemoteArgs) -> ExitCode {
+    eprintln!("Cloning {}...", remote.url.cyan());
+    let checkout = match Checkout::shallow_clone(&remote.url, remote.branch.as_deref()) {
+        Ok(checkout) => checkout,
+        Err(e) => {
+            eprintln!("{} Failed to clone {}: {}", "Error:".red().bold(), remote.url, e);
+            return ExitCode::FAILURE;
+        }
+    };
+
+    let (bib_files, tex_files) = match (
+        checkout.files_with_extension("bib"),
+        checkout.files_with_extension("tex"),
+    ) {
+        (Ok(bib), Ok(tex)) => (bib, tex),
+        (Err(e), _) | (_, Err(e)) => {
+            eprintln!("{} Failed to read the project: {}", "Error:".red().bold(), e);
+            return ExitCode::FAILURE;
+        }
+    };
+    let relative = |path: &PathBuf| {
+        path.strip_prefix(checkout.path()).unwrap_or(path).display().to_string()
+    };
+
+    let mut all_entries = Vec::new();
+    for file in &bib_files {
+        eprintln!("Parsing {}...", relative(file).cyan());
+        match parser::parse_bib_file(file) {
+            Ok(entries) => {
+                eprintln!("  Found {} entries", entries.len());
+                all_entries.extend(entries);
+            }
+            Err(e) => {
+                eprintln!("{} Failed to parse {}: {}", "Error:".red().bold(), relative(file), e);
+                return ExitCode::FAILURE;
+            }
+        }
+    }
+
+    if all_entries.is_empty() {
+        eprintln!("{}", "No entries found to validate.".yellow());
+        return ExitCode::SUCCESS;
+    }
+
+    let mut citations = Citations::default();
+    for file in &tex_files {
+        match std::fs::read_to_string(file) {
+            Ok(source) => citations.scan_latex(&source),
+            Err(e) => eprintln!(
+                "{} Could not read {}: {}",
+                "Warning:".yellow().bold(),
+                relative(file),
+                e
+            ),
+        }
+    }
+
+    // Without any LaTeX sources there is nothing to narrow the bibliography down by
+    let cited_only = !tex_files.is_empty() && !citations.all;
+    if cited_only {
+        warn_missing_citations(&all_entries, &citations);
+    }
+
+    let key_filter: HashSet<_> = args.keys.iter().cloned().collect();
+    let is_selected = |e: &Entry| {
+        (!cited_only || citations.includes(&e.key))
+            && (key_filter.is_empty() || key_filter.contains(&e.key))
+    };
+    let selected = all_entries.iter().filter(|e| is_selected(e)).count();
+    if selected == 0 {
+        eprintln!("{}", "No cited entries to validate.".yellow());
+        return ExitCode::SUCCESS;
+    }
+
+    eprintln!();
+    if cited_only {
+        eprintln!(
+            "Validating {} cited entries ({} uncited skipped)...",
+            selected,
+            all_entries.len() - selected
+        );
+    } else {
+        eprintln!("Validating {} entries...", selected);
+    }
+    eprintln!();
+
+    let validator = match build_validator(args, &[PathBuf::from(&remote.url)]) {
+        Ok(v) => v,
+        Err(code) => return code,
+    };
+
+    let mut report = validator.validate_only(all_entries, is_selected).await;
+    finish(args, &validator, &mut report)
+}
+
+/// PDFs processed by GROBID at the same time
+const GROBID_CONCURRENCY: usize = 4;
+
+/// Extract entries from PDFs with GROBID, validate them, and write the cleaned entries
+async fn extract_pdfs(args: &Args, extract: &ExtractArgs) -> ExitCode {
+    let mut pdfs = Vec::new();
+    if let Err(e) = find_files(&extract.dir, "pdf", &mut pdfs) {
+        eprintln!(
+            "{} Failed to read {}: {}",
+            "Error:".red().bold(),
+            extract.dir.display(),
+            e
+        );
+        return ExitCode::FAILURE;
+    }
+    pdfs.sort();
+    if pdfs.is_empty() {
+        eprintln!("{}", "No PDFs found.".yellow());
+        return ExitCode::SUCCESS;
+    }
+
+    eprintln!(
+        "Extracting metadata from {} PDFs with GROBID at {}...",
+        pdfs.len(),
+        extract.grobid_url.cyan()
+    );
+    let client = GrobidClient::new(&extract.grobid_url);
+    let extracted: Vec<_> = futures::stream::iter(&pdfs)
+        .map(|pdf| {
+            let client = &client;
+            async move { (pdf, client.process_header(pdf).await) }
+        })
+        .buffered(GROBID_CONCURRENCY)
+        .collect()
+        .await;
+
+    let mut entries = Vec::new();
+    let mut used_keys = HashSet::new();
+    for (pdf, result) in extracted {
+        match result {
+            Ok(Some(mut entry)) => {
+                entry.key = unique_key(&entry, pdf, &mut used_keys);
+                eprintln!("  {} {}", entry.key.cyan(), pdf.display().to_string().dimmed());
+                entries.push(entry);
+            }
+            Ok(None) => eprintln!(
+                "{} No metadata found in {}",
+                "Warning:".yellow().bold(),
+                pdf.display()
+            ),
+            Err(e) => eprintln!(
+                "{} Failed to process {}: {}",
+                "Warning:".yellow().bold(),
+                pdf.display(),
+                e
+            ),
+        }
+    }
+
+    if entries.is_empty() {
+        eprintln!("{} No entries could be extracted.", "Error:".red().bold());
+        return ExitCode::FAILURE;
+    }
+
+    let validator = match build_validator(args, std::slice::from_ref(&extract.dir)) {
+        Ok(v) => v,
+        Err(code) => return code,
+    };
+    eprintln!();
+    eprintln!("Validating {} entries...", entries.len());
+    eprintln!();
+
+    let key_filter: HashSet<_> = args.keys.iter().cloned().collect();
+    let is_selected = |e: &Entry| key_filter.is_empty() || key_filter.contains(&e.key);
+    let mut report = validator.validate_only(entries.clone(), is_selected).await;
+    let code = finish(args, &validator, &mut report);
+
+    let reports: HashMap<&str, _> = report
+        .entries
+        .iter()
+        .map(|r| (r.entry.key.as_str(), r))
+        .collect();
+    let cleaned: Vec<Entry> = entries
+        .into_iter()
+        .map(|entry| match reports.get(entry.key.as_str()) {
+            Some(report) => fusion::clean_entry(report),
+            None => entry,
+        })
+        .collect();
+
+    if let Err(e) = std::fs::write(&extract.output, writer::format_bibliography(&cleaned)) {
+        eprintln!(
+            "{} Failed to write {}: {}",
+            "Error:".red().bold(),
+            extract.output.display(),
+            e
+        );
+        return ExitCode::FAILURE;
+    }
+    eprintln!("Wrote {} entries to {}", cleaned.len(), extract.output.display());
+    code
+}
+
+/// Validate a BibTeX file and rewrite the fields the validators confidently agree are
+/// wrong, leaving the rest of the file as it was
+async fn fix_bibliography(args: &Args, fix_args: &FixArgs) -> ExitCode {
+    let file = &fix_args.file;
+    let content = match std::fs::read_to_string(file) {
+        Ok(content) => content,
+        Err(e) => {
+            eprintln!("{} Failed to read {}: {}", "Error:".red().bold(), file.display(), e);
+            return ExitCode::FAILURE;
+        }
+    };
+    let entries = match parser::parse_bib_string(&content) {
+        Ok(entries) => entries,
+        Err(e) => {
+            eprintln!("{} Failed to parse {}: {}", "Error:".red().bold(), file.display(), e);
+            return ExitCode::FAILURE;
+        }
+    };
+
+    let validator = match build_oneshot_validator(args) {
+        Ok(v) => v,
+        Err(code) => return code,
+    };
+    eprintln!("Validating {} entries...", entries.len());
+    let report = validator.validate(entries).await;
+
+    let lint_config = LintConfig {
+        // Already checked when the validator was built
+        key_pattern: args
+            .key_pattern
+            .as_deref()
+            .and_then(|p| LintConfig::key_pattern_from_str(p).ok()),
+        journal_style: args.journal_style.map(VenueForm::from),
+        conference_style: args.conference_style.map(VenueForm::from),
+        ..Default::default()
+    };
+    let policy = FixPolicy {
+        only: fix_args.only.clone(),
+        except: fix_args.except.clone(),
+    };
+    let enriched: Vec<&str> = args
+        .enrich
+        .iter()
+        .filter_map(|enrichment| match enrichment {
+            Enrichment::Abstract => Some("abstract"),
+            Enrichment::Keywords => Some("keywords"),
+            Enrichment::Version => Some("version"),
+            Enrichment::License => Some("license"),
+            Enrichment::Citations | Enrichment::Code | Enrichment::OpenAccess => None,
+        })
+        .collect();
+    let regenerated = if fix_args.regenerate {
+        regenerated_entries(&report).await
+    } else {
+        HashMap::new()
+    };
+    let pending: Vec<_> = report
+        .entries
+        .iter()
+        .filter(|r| !regenerated.contains_key(&r.entry.key))
+        .map(|r| {
+            let mut corrections = fix::corrections(r);
+            // A venue another correction rewrites keeps that spelling
+            if let Some(restyled) = fix::venue_style(&r.entry, &lint_config) {
+                if !corrections.iter().any(|c| c.field == restyled.field) {
+                    corrections.push(restyled);
+                }
+            }
+            let mut corrections = policy.filter(corrections);
+            corrections.extend(fix::enrichment(r, &enriched));
+            if args.enrich.contains(&Enrichment::Citations) {
+                corrections.extend(fix::citation_note(r));
+            }
+            if args.enrich.contains(&Enrichment::OpenAccess) {
+                corrections.extend(fix::open_access_url(r));
+            }
+            (r.entry.key.clone(), corrections)
+        })
+        .filter(|(_, corrections)| !corrections.is_empty())
+        .collect();
+    let corrections = if fix_args.interactive {
+        match fix::review(pending, std::io::stdin().lock(), std::io::stderr()) {
+            Ok(accepted) => accepted,
+            Err(e) => {
+                eprintln!("{} Failed to read answers: {}", "Error:".red().bold(), e);
+                return ExitCode::FAILURE;
+            }
+        }
+    } else {
+        for (key, entry_corrections) in &pending {
+            for correction in entry_corrections {
+                eprintln!(
+                    "  {} {}: {} {} {}",
+                    format!("[{}]", key).dimmed(),
+                    correction.field,
+                    correction.old.as_deref().unwrap_or("(none)").red(),
+                    "->".dimmed(),
+                    correction.new.green()
+                );
+            }
+        }
+        pending.into_iter().collect()
+    };
+
+    let renames = if fix_args.rename_keys {
+        key_renames(&report, &lint_config)
+    } else {
+        HashMap::new()
+    };
+
+    // Corrections are keyed by the old citation keys, so renaming comes last
+    let fixed = fix::apply(&content, &corrections)
+        .and_then(|fixed| fix::regenerate(&fixed, &regenerated))
+        .and_then(|fixed| fix::rename_keys(&fixed, &renames));
+    let fixed = match fixed {
+        Ok(fixed) => fixed,
+        Err(e) => {
+            eprintln!("{} Failed to correct {}: {}", "Error:".red().bold(), file.display(), e);
+            return ExitCode::FAILURE;
+        }
+    };
+    let destination = match (&fix_args.output, fix_args.in_place) {
+        (Some(path), _) => Some(path),
+        (None, true) => Some(file),
+        (None, false) => None,
+    };
+
+    let tex_dir = fix_args.tex_dir.as_deref();
+    let written =
+        write_with_citations(args, file, &content, fixed, destination, tex_dir, &renames);
+    if let Err(code) = written {
+        return code;
+    }
+
+    let unfixed = report
+        .entries
+        .iter()
+        .filter(|r| matches!(r.status, EntryStatus::Warning | EntryStatus::Error))
+        .filter(|r| !corrections.contains_key(&r.entry.key))
+        .count();
+    eprintln!(
+        "Corrected {} entries; {} entries with issues were left as they were",
+        corrections.len() + regenerated.len(),
+        unfixed
+    );
+    ExitCode::SUCCESS
+}
+
+/// Write a rewritten bibliography to its destination (stdout without one), together with
+/// the .tex files under `tex_dir` whose citations of renamed keys it rewrites
+///
+/// With --dry-run nothing is written; `--format diff` prints what would change instead.
+fn write_with_citations(
+    args: &Args,
+    file: &Path,
+    original: &str,
+    bibliography: String,
+    destination: Option<&PathBuf>,
+    tex_dir: Option<&Path>,
+    renames: &HashMap<String, String>,
+) -> Result<(), ExitCode> {
+    let mut files = Vec::new();
+    let mut diff = fix::unified_diff(file, original, &bibliography);
+    if let Some(dir) = tex_dir {
+        if destination.is_none() && !args.dry_run {
+            eprintln!(
+                "{} --tex-dir needs --in-place or --output, so the LaTeX sources and the \
+                 bibliography are written together",
+                "Error:".red().bold()
+            );
+            return Err(ExitCode::FAILURE);
+        }
+        let mut tex_files = Vec::new();
+        if let Err(e) = find_files(dir, "tex", &mut tex_files) {
+            eprintln!("{} Failed to read {}: {}", "Error:".red().bold(), dir.display(), e);
+            return Err(ExitCode::FAILURE);
+        }
+        for tex_file in tex_files {
+            let source = match std::fs::read_to_string(&tex_file) {
+                Ok(source) => source,
+                Err(e) => {
+                    eprintln!(
+                        "{} Failed to read {}: {}",
+                        "Error:".red().bold(),
+                        tex_file.display(),
+                        e
+                    );
+                    return Err(ExitCode::FAILURE);
+                }
+            };
+            let renamed = citations::rename_latex(&source, renames);
+            if renamed != source {
+                diff.push_str(&fix::unified_diff(&tex_file, &source, &renamed));
+                files.push((tex_file, renamed));
+            }
+        }
+    }
+
+    if args.dry_run {
+        if args.format == OutputFormat::Diff {
+            print!("{}", diff);
+        }
+        eprintln!("Dry run: no files were written");
+        return Ok(());
+    }
+    match destination {
+        Some(path) => {
+            let tex_count = files.len();
+            files.push((path.clone(), bibliography));
+            if let Err(e) = fix::write_all(&files) {
+                eprintln!("{} Failed to write {}: {}", "Error:".red().bold(), path.display(), e);
+                return Err(ExitCode::FAILURE);
+            }
+            if tex_count > 0 {
+                eprintln!("Renamed citations in {} LaTeX files", tex_count);
+            }
+        }
+        None => print!("{}", bibliography),
+    }
+    Ok(())
+}
+
+/// Merge the entries of a BibTeX file that cite the same work, rewriting the citations of
+/// the removed duplicates with --tex-dir
+fn dedupe_bibliography(args: &Args, dedupe_args: &DedupeArgs) -> ExitCode {
+    let file = &dedupe_args.file;
+    let content = match std::fs::read_to_string(file) {
+        Ok(content) => content,
+        Err(e) => {
+            eprintln!("{} Failed to read {}: {}", "Error:".red().bold(), file.display(), e);
+            return ExitCode::FAILURE;
+        }
+    };
+    let entries = match parser::parse_bib_string(&content) {
+        Ok(entries) => entries,
+        Err(e) => {
+            eprintln!("{} Failed to parse {}: {}", "Error:".red().bold(), file.display(), e);
+            return ExitCode::FAILURE;
+        }
+    };
+
+    let merges = dedupe::merges(&entries);
+    let mut renames = HashMap::new();
+    for merge in &merges {
+        eprintln!(
+            "  {} {} {}",
+            format!("[{}]", merge.entry.key).dimmed(),
+            "<-".dimmed(),
+            merge.removed.join(", ").red()
+        );
+        for removed in &merge.removed {
+            renames.insert(removed.clone(), merge.entry.key.clone());
+        }
+    }
+
+    let deduped = match dedupe::apply(&content, &merges) {
+        Ok(deduped) => deduped,
+        Err(e) => {
+            eprintln!("{} Failed to merge {}: {}", "Error:".red().bold(), file.display(), e);
+            return ExitCode::FAILURE;
+        }
+    };
+    let destination = match (&dedupe_args.output, dedupe_args.in_place) {
+        (Some(path), _) => Some(path),
+        (None, true) => Some(file),
+        (None, false) => None,
+    };
+    let tex_dir = dedupe_args.tex_dir.as_deref();
+    let written =
+        write_with_citations(args, file, &content, deduped, destination, tex_dir, &renames);
+    if let Err(code) = written {
+        return code;
+    }
+
+    eprintln!("Merged {} entries into {}", renames.len() + merges.len(), merges.len());
+    ExitCode::SUCCESS
+}
+
+/// New keys for the entries whose keys the key checks flag, with a letter appended when
+/// the suggestion is already taken
+fn key_renames(report: &Report, config: &LintConfig) -> HashMap<String, String> {
+    let mut used: HashSet<String> = report.entries.iter().map(|r| r.entry.key.clone()).collect();
+    let mut renames = HashMap::new();
+    for entry_report in &report.entries {
+        let old = &entry_report.entry.key;
+        let Some(base) = lint::renamed_key(&entry_report.entry, config) else {
+            continue;
+        };
+        let Some(new) = std::iter::once(base.clone())
+            .chain(('a'..='z').map(|suffix| format!("{}{}", base, suffix)))
+            .find(|key| !used.contains(key))
+        else {
+            continue;
+        };
+        eprintln!("  {} renamed to {}", format!("[{}]", old).dimmed(), new.green());
+        used.insert(new.clone());
+        renames.insert(old.clone(), new);
+    }
+    renames
+}
+
+/// The DBLP replacement `--dblp-bibtex` offers for each entry, or else doi.org's BibTeX
+/// for an entry with a verified DOI, keyed by and rekeyed to the entry's citation key
+async fn regenerated_entries(report: &Report) -> HashMap<String, String> {
+    let client = DoiOrgClient::new();
+    let mut regenerated = HashMap::new();
+    for entry_report in &report.entries {
+        let key = &entry_report.entry.key;
+        if let Some(replacement) = &entry_report.replacement {
+            eprintln!(
+                "  {} regenerated from {}",
+                format!("[{}]", key).dimmed(),
+                replacement.source
+            );
+            regenerated.insert(key.clone(), replacement.bibtex.clone());
+            continue;
+        }
+        let Some(doi) = fix::verified_doi(entry_report) else {
+            continue;
+        };
+        match client.fetch_bibtex(doi).await {
+            Ok(Some(bibtex)) => {
+                if let Some(bibtex) = rekey_bibtex(&bibtex, key) {
+                    eprintln!("  {} regenerated from doi:{}", format!("[{}]", key).dimmed(), doi);
+                    regenerated.insert(key.clone(), bibtex);
+                }
+            }
+            Ok(None) => eprintln!(
+                "{} [{}] doi.org has no BibTeX for {}; kept as it was",
+                "Warning:".yellow().bold(),
+                key,
+                doi
+            ),
+            Err(e) => eprintln!(
+                "{} [{}] Could not fetch BibTeX for {}: {}",
+                "Warning:".yellow().bold(),
+                key,
+                doi,
+                e
+            ),
+        }
+    }
+    regenerated
+}
+
+/// Validate the entries that differ between two versions of a bibliography and report
+/// how their status changed
+async fn diff_bibliographies(args: &Args, diff_args: &DiffArgs) -> ExitCode {
+    let parse_error = |label: &str, e: &dyn std::fmt::Display| {
+        eprintln!("{} Failed to parse {}: {}", "Error:".red().bold(), label, e);
+        ExitCode::FAILURE
+    };
+
+    let (old_label, old_entries, new_file) = match (&diff_args.against, &diff_args.files[..]) {
+        (Some(rev), [file]) => {
+            let label = format!("{} at {}", file.display(), rev);
+            let parsed = git::file_at_revision(file, rev)
+                .map_err(|e| e.to_string())
+                .and_then(|content| parser::parse_bib_string(&content).map_err(|e| e.to_string()));
+            match parsed {
+                Ok(entries) => (label, entries, file),
+                Err(e) => return parse_error(&label, &e),
+            }
+        }
+        (None, [old, new]) => match parser::parse_file(old) {
+            Ok(entries) => (old.display().to_string(), entries, new),
+            Err(e) => return parse_error(&old.display().to_string(), &e),
+        },
+        _ => {
+            eprintln!(
+                "{} Give two files, or one file and --against REV",
+                "Error:".red().bold()
+            );
+            return ExitCode::FAILURE;
+        }
+    };
+    let new_entries = match parser::parse_file(new_file) {
+        Ok(entries) => entries,
+        Err(e) => return parse_error(&new_file.display().to_string(), &e),
+    };
+
+    let diff = diff_entries(&old_entries, &new_entries);
+    let added: HashSet<&str> = diff.added.iter().map(String::as_str).collect();
+    let validator = match build_oneshot_validator(args) {
+        Ok(v) => v,
+        Err(code) => return code,
+    };
+
+    eprintln!();
+    eprintln!(
+        "Validating {} added and {} changed entries...",
+        diff.added.len(),
+        diff.changed.len()
+    );
+    eprintln!();
+
+    // Unchanged entries would get the same status in both versions
+    let old_report = validator
+        .validate_only(old_entries, |e| diff.is_changed(&e.key))
+        .await;
+    let new_report = validator
+        .validate_only(new_entries, |e| {
+            added.contains(e.key.as_str()) || diff.is_changed(&e.key)
+        })
+        .await;
+
+    let labels = (old_label, new_file.display().to_string());
+    let report = DiffReport::new(diff, labels, &old_report, &new_report);
+    match args.format {
+        OutputFormat::Text => report.print(),
+        OutputFormat::Json => match report.to_json() {
+            Ok(json) => println!("{}", json),
+            Err(e) => {
+                eprintln!("{} Failed to write JSON report: {}", "Error:".red().bold(), e);
+                return ExitCode::FAILURE;
+            }
+        },
+        OutputFormat::Html
+        | OutputFormat::Github
+        | OutputFormat::Ndjson
+        | OutputFormat::Latex
+        | OutputFormat::Compact
+        | OutputFormat::Diff => {
+            eprintln!("{} Diffs are reported only as text or JSON", "Error:".red().bold());
+            return ExitCode::FAILURE;
+        }
+    }
+
+    if report.count_regressed() > 0 || report.count_added_errors() > 0 {
+        ExitCode::FAILURE
+    } else {
+        ExitCode::SUCCESS
+    }
+}
+
+/// Validate, record the run, and report regressions since the baseline run. Output
+/// only appears when something got worse, so scheduled runs alert only then.
+async fn monitor_bibliography(args: &Args, monitor: &MonitorArgs) -> ExitCode {
+    let mut entries = Vec::new();
+    for file in &monitor.files {
+        match parser::parse_file(file) {
+            Ok(parsed) => entries.extend(parsed),
+            Err(e) => {
+                eprintln!(
+                    "{} Failed to parse {}: {}",
+                    "Error:".red().bold(),
+                    file.display(),
+                    e
+                );
+                return ExitCode::FAILURE;
+            }
+        }
+    }
+
+    let history = History::new(
+        &monitor
+            .history
+            .clone()
+            .unwrap_or_else(|| History::default_path(&monitor.files)),
+    );
+    let records = match history.load() {
+        Ok(records) => records,
+        Err(e) => {
+            eprintln!(
+                "{} Failed to read history {}: {}",
+                "Error:".red().bold(),
+                history.path().display(),
+                e
+            );
+            return ExitCode::FAILURE;
+        }
+    };
+
+    let validator = match build_oneshot_validator(args) {
+        Ok(v) => v,
+        Err(code) => return code,
+    };
+    eprintln!("Validating {} entries...", entries.len());
+    let report = validator.validate(entries).await;
+
+    let now = chrono::Utc::now();
+    let record = RunRecord::from_report(&report, now);
+    if let Err(e) = history.append(&record) {
+        eprintln!(
+            "{} Failed to record run in {}: {}",
+            "Error:".red().bold(),
+            history.path().display(),
+            e
+        );
+        return ExitCode::FAILURE;
+    }
+
+    let Some(baseline) = history::baseline(&records, now, monitor.since) else {
+        eprintln!(
+            "Recorded the first run to compare against in {}",
+            history.path().display()
+        );
+        return ExitCode::SUCCESS;
+    };
+
+    let trends = Trends::compare(baseline, &record);
+    match args.format {
+        OutputFormat::Json => match serde_json::to_string_pretty(&trends) {
+            Ok(json) => println!("{}", json),
+            Err(e) => {
+                eprintln!("{} Failed to write JSON report: {}", "Error:".red().bold(), e);
+                return ExitCode::FAILURE;
+            }
+        },
+        OutputFormat::Text if trends.has_regressions() => trends.print(),
+        OutputFormat::Text => eprintln!(
+            "No regressions since {}",
+            baseline.timestamp.format("%Y-%m-%d %H:%M UTC")
+        ),
+        OutputFormat::Html
+        | OutputFormat::Github
+        | OutputFormat::Ndjson
+        | OutputFormat::Latex
+        | OutputFormat::Compact
+        | OutputFormat::Diff => {
+            eprintln!("{} Trends are reported only as text or JSON", "Error:".red().bold());
+            return ExitCode::FAILURE;
+        }
+    }
+
+    if trends.has_regressions() {
+        ExitCode::FAILURE
+    } else {
+        ExitCode::SUCCESS
+    }
+}
+
+/// Answer Model Context Protocol requests on stdin until the client disconnects
+async fn serve_mcp(args: &Args) -> ExitCode {
+    let validator = match build_oneshot_validator(args) {
+        Ok(v) => v,
+        Err(code) => return code,
+    };
+
+    eprintln!("Serving MCP on stdio...");
+    match McpServer::new(validator).serve_stdio().await {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(e) => {
+            eprintln!("{} MCP server failed: {}", "Error:".red().bold(), e);
+            ExitCode::FAILURE
+        }
+    }
+}
+
+/// Collect the files with an extension under a directory, recursively
+fn find_files(
+    dir: &std::path::Path,
+    extension: &str,
+    files: &mut Vec<PathBuf>,
+) -> std::io::Result<()> {
+    for entry in std::fs::read_dir(dir)? {
+        let path = entry?.path();
+        if path.is_dir() {
+            find_files(&path, extension, files)?;
+        } else if path.extension().is_some_and(|e| e.eq_ignore_ascii_case(extension)) {
+            files.push(path);
+        }
+    }
+    Ok(())
+}
+
+/// An `authorYEARword` key for an extracted entry (the file name if that can't be
+/// built), with a letter appended when it is already taken
+fn unique_key(entry: &Entry, pdf: &std::path::Path, used: &mut HashSet<String>) -> String {
+    let base = lint::suggest_key(entry).unwrap_or_else(|| {
+        pdf.file_stem()
+            .map(|stem| stem.to_string_lossy().replace(|c: char| c.is_whitespace(), "_"))
+            .unwrap_or_else(|| "entry".to_string())
+    });
+    let mut key = base.clone();
+    for suffix in 'a'..='z' {
+        if !used.contains(&key) {
+            break;
+        }
+        key = format!("{}{}", base, suffix);
+    }
+    used.insert(key.clone());
+    key
+}
+
+/// Pandoc Markdown documents are recognized by extension
+fn is_markdown(path: &std::path::Path) -> bool {
+    path.extension()
+        .and_then(|e| e.to_str())
+        .is_some_and(|e| matches!(e.to_ascii_lowercase().as_str(), "md" | "markdown"))
+}
+
+/// Warn about cited keys that no bibliography entry has
+fn warn_missing_citations(entries: &[Entry], citations: &Citations) {
+    let known: HashSet<&str> = entries.iter().map(|e| e.key.as_str()).collect();
+    let missing: Vec<&str> = citations
+        .keys
+        .iter()
+        .map(String::as_str)
+        .filter(|key| !known.contains(key))
+        .collect();
+    if !missing.is_empty() {
+        eprintln!(
+            "{} Cited but not in the bibliography: {}",
+            "Warning:".yellow().bold(),
+            missing.join(", ")
+        );
+    }
+}
+
+/// Build the validator from command-line options, reporting invalid ones. `inputs`
+/// identify what is being validated, to locate the run state for `--resume`.
+fn build_validator(args: &Args, inputs: &[PathBuf]) -> Result<BibValidator, ExitCode> {
+    BibValidator::new(validator_config(args, inputs)?).map_err(|e| {
+        eprintln!("{} Failed to initialize validator: {}", "Error:".red().bold(), e);
+        ExitCode::FAILURE
+    })
+}
+
+/// Build a validator that records no run state, for commands whose runs are not
+/// resumed
+fn build_oneshot_validator(args: &Args) -> Result<BibValidator, ExitCode> {
+    let config = ValidatorConfig {
+        checkpoint: None,
+        ..validator_config(args, &[])?
+    };
+    BibValidator::new(config).map_err(|e| {
+        eprintln!("{} Failed to initialize validator: {}", "Error:".red().bold(), e);
+        ExitCode::FAILURE
+    })
+}
+
+/// The validator configuration given by command-line options
+fn validator_config(args: &Args, inputs: &[PathBuf]) -> Result<ValidatorConfig, ExitCode> {
+    let key_pattern = match args.key_pattern.as_deref().map(LintConfig::key_pattern_from_str) {
+        Some(Ok(pattern)) => Some(pattern),
+        Some(Err(e)) => {
+            eprintln!("{} Invalid key pattern: {}", "Error:".red().bold(), e);
+            return Err(ExitCode::FAILURE);
+        }
+        None => None,
+    };
+
+    let predatory = if args.predatory || args.predatory_list.is_some() {
+        let mut list = PredatoryList::bundled();
+        if let Some(path) = &args.predatory_list {
+            match PredatoryList::load(path) {
+                Ok(extra) => list.extend(extra),
+                Err(e) => {
+                    eprintln!("{} {}: {}", "Error:".red().bold(), path.display(), e);
+                    return Err(ExitCode::FAILURE);
+                }
+            }
+        }
+        Some(list)
+    } else {
+        None
+    };
+
+    let dblp_dump = match &args.dblp_dump {
+        Some(path) => {
+            if !DblpDump::is_indexed(path) {
+                eprintln!("Indexing DBLP dump {} (first use only)...", path.display());
+            }
+            match DblpDump::open(path) {
+                Ok(dump) => Some(dump),
+                Err(e) => {
+                    eprintln!("{} {}: {}", "Error:".red().bold(), path.display(), e);
+                    return Err(ExitCode::FAILURE);
+                }
+            }
+        }
+        None => None,
+    };
+
+    Ok(ValidatorConfig {
+        use_crossref: !args.no_crossref,
+        use_dblp: !args.no_dblp,
+        use_arxiv: !args.no_arxiv,
+        use_semantic: !args.no_semantic,
+        use_openalex: !args.no_openalex,
+        use_openlibrary: !args.no_openlibrary,
+        use_hathitrust: !args.no_hathitrust,
+        use_pubmed: !args.no_pubmed,
+        use_doaj: !args.no_doaj,
+        use_scielo: !args.no_scielo,
+        use_jstage: !args.no_jstage,
+        use_acm: !args.no_acm,
+        use_inspire: !args.no_inspire,
+        use_mathscinet: !args.no_mathscinet,
+        use_osf: !args.no_osf,
+        use_paperswithcode: !args.no_paperswithcode,
+        use_hal: !args.no_hal,
+        use_ietf: !args.no_ietf,
+        use_w3c: !args.no_w3c,
+        springer_api_key: args.springer_key.clone(),
+        scopus_api_key: args.scopus_key.clone(),
+        wos_api_key: args.wos_key.clone(),
+        ads_api_token: args.ads_token.clone(),
+        core_api_key: args.core_key.clone(),
+        unpaywall_email: args.unpaywall_email.clone(),
+        ncbi_api_key: args.ncbi_api_key.clone(),
+        use_openreview: !args.no_openreview,
+        use_zenodo: !args.no_zenodo,
+        use_datacite: !args.no_datacite,
+        use_doi_org: !args.no_doi_org,
+        use_figshare: !args.no_figshare,
+        use_github: !args.no_github,
+        github_token: args.github_token.clone(),
+        use_software_heritage: !args.no_software_heritage,
+        use_opencitations: !args.no_opencitations,
+        check_urls: !args.no_url_check,
+        check_retractions: !args.no_retraction_check,
+        worldcat: match (&args.worldcat_key, &args.worldcat_secret) {
+            (Some(key), Some(secret)) => Some(WorldCatCredentials {
+                key: key.clone(),
+                secret: secret.clone(),
+            }),
+            _ => None,
+        },
+        epo: match (&args.epo_key, &args.epo_secret) {
+            (Some(key), Some(secret)) => Some(EpoCredentials {
+                key: key.clone(),
+                secret: secret.clone(),
+            }),
+            _ => None,
+        },
+        sru_libraries: args.library.iter().map(|&l| l.into()).collect(),
+        cache_enabled: !args.no_cache,
+        lint: LintConfig {
+            key_pattern,
+            journal_style: args.journal_style.map(VenueForm::from),
+            conference_style: args.conference_style.map(VenueForm::from),
+            predatory,
+        },
+        short_circuit: args.short_circuit.map(ShortCircuitPolicy::after),
+        // Run state is only recorded when asked for, and never in the cache directory
+        // when the cache is off
+        checkpoint: match (&args.state_file, args.resume && !args.no_cache) {
+            (Some(path), _) => Some(path.clone()),
+            (None, true) => Some(Checkpoint::default_path(inputs)),
+            (None, false) => None,
+        },
+        resume: args.resume,
+        dblp_bibtex: args.dblp_bibtex.map(DblpBibtexFormat::from),
+        dblp_dump,
+        citation_counts: args.enrich.contains(&Enrichment::Citations),
+        code_repositories: args.enrich.contains(&Enrichment::Code),
+        open_access: args.enrich.contains(&Enrichment::OpenAccess),
+        ascii: args.ascii,
+        trace: args.verbose > 1,
+        min_severity: args.min_severity.into(),
+    })
+}
+
+/// Write a rendered report to `--output`, or stdout without one; false if that failed
+fn write_output(args: &Args, content: &str) -> bool {
+    let Some(path) = &args.output else {
+        println!("{}", content);
+        return true;
+    };
+    match std::fs::write(path, content) {
+        Ok(()) => {
+            eprintln!("Wrote the report to {}", path.display());
+            true
+        }
+        Err(e) => {
+            eprintln!(
+                "{} Failed to write {}: {}",
+                "Error:".red().bold(),
+                path.display(),
+                e
+            );
+            false
+        }
+    }
+}
+
+/// Print the report and pick the exit code
+fn finish(args: &Args, validator: &BibValidator, report: &mut Report) -> ExitCode {
+    if let Some(order) = args.sort {
+        report.sort(order.into());
+    }
+    report.statistics = Some(validator.statistics(report));
+    if let Some(path) = &args.compare_to {
+        return compare_reports(args, validator, report, path);
+    }
+    if let Some(path) = &args.template {
+        match template::render(report, path) {
+            Ok(output) => {
+                if !write_output(args, &output) {
+                    return ExitCode::FAILURE;
+                }
+            }
+            Err(e) => {
+                eprintln!("{} {}: {}", "Error:".red().bold(), path.display(), e);
+                return ExitCode::FAILURE;
+            }
+        }
+        return conclude(args, validator, &report.totals());
+    }
+
+    match args.format {
+        OutputFormat::Text if args.tui => match tui::browse(report) {
+            // Comma-separated, ready to pass back to --key
+            Ok(marked) if !marked.is_empty() => println!("{}", marked.join(",")),
+            Ok(_) => {}
+            Err(e) => {
+                eprintln!("{} Results browser failed: {}", "Error:".red().bold(), e);
+                return ExitCode::FAILURE;
+            }
+        },
+        OutputFormat::Text if args.sort.is_some() => report.print_in_order(args.verbose > 0),
+        OutputFormat::Text => report.print(args.verbose > 0),
+        OutputFormat::Json => match report.to_json() {
+            Ok(json) => {
+                if !write_output(args, &json) {
+                    return ExitCode::FAILURE;
+                }
+            }
+            Err(e) => {
+                eprintln!("{} Failed to write JSON report: {}", "Error:".red().bold(), e);
+                return ExitCode::FAILURE;
+            }
+        },
+        OutputFormat::Html => {
+            if !write_output(args, &html::render(report)) {
+                return ExitCode::FAILURE;
+            }
+        }
+        OutputFormat::Latex => {
+            if !write_output(args, &latex::render(report)) {
+                return ExitCode::FAILURE;
+            }
+        }
+        OutputFormat::Compact => {
+            if !write_output(args, &report.to_compact()) {
+                return ExitCode::FAILURE;
+            }
+        }
+        OutputFormat::Github => {
+            let commands = workflow::annotations(report);
+            if !write_output(args, &commands.join("\n")) {
+                return ExitCode::FAILURE;
+            }
+        }
+        OutputFormat::Ndjson => {
+            let Some(mut sink) = NdjsonSink::open(args) else {
+                return ExitCode::FAILURE;
+            };
+            for entry_report in &report.entries {
+                sink.write(entry_report);
+            }
+            return sink.finish(args, validator);
+        }
+        // Only with --fix-doi --dry-run, whose diff is printed instead of the report
+        OutputFormat::Diff => {}
+    }
+    conclude(args, validator, &report.totals())
+}
+
+/// Print how the report differs from an earlier one, and pick the exit code as usual
+fn compare_reports(
+    args: &Args,
+    validator: &BibValidator,
+    report: &Report,
+    earlier: &Path,
+) -> ExitCode {
+    let before = match std::fs::read_to_string(earlier)
+        .map_err(|e| e.to_string())
+        .and_then(|json| Report::from_json(&json).map_err(|e| e.to_string()))
+    {
+        Ok(before) => before,
+        Err(e) => {
+            eprintln!(
+                "{} Failed to read {}: {}",
+                "Error:".red().bold(),
+                earlier.display(),
+                e
+            );
+            return ExitCode::FAILURE;
+        }
+    };
+
+    let now = chrono::Utc::now();
+    let comparison = Comparison::between(
+        &RunRecord::from_report(&before, now),
+        &RunRecord::from_report(report, now),
+    );
+    if args.format == OutputFormat::Json {
+        match serde_json::to_string_pretty(&comparison) {
+            Ok(json) => {
+                if !write_output(args, &json) {
+                    return ExitCode::FAILURE;
+                }
+            }
+            Err(e) => {
+                eprintln!("{} Failed to write JSON report: {}", "Error:".red().bold(), e);
+                return ExitCode::FAILURE;
+            }
+        }
+    } else {
+        comparison.print(earlier);
+    }
+    conclude(args, validator, &report.totals())
+}
+
+/// Print the profile and write the summary file if asked for, and pick the exit code
+fn conclude(args: &Args, validator: &BibValidator, totals: &Totals) -> ExitCode {
+    if args.profile {
+        report::print_profile(&validator.throttle_stats());
+    }
+
+    if let Some(path) = &args.summary_json {
+        let written = serde_json::to_string_pretty(totals)
+            .map_err(std::io::Error::from)
+            .and_then(|json| std::fs::write(path, json + "\n"));
+        if let Err(e) = written {
+            eprintln!(
+                "{} Failed to write {}: {}",
+                "Error:".red().bold(),
+                path.display(),
+                e
+            );
+            return ExitCode::FAILURE;
+        }
+    }
+
+    if totals.entries.errors > 0 || (args.strict && totals.entries.warnings > 0) {
+        ExitCode::FAILURE
+    } else {
+        ExitCode::SUCCESS
+    }
+}
+
+/// Writes each entry report as a line of JSON as soon as it is ready, keeping only the
+/// counts the exit code and summary file need
+struct NdjsonSink {
+    out: Box<dyn Write>,
+    totals: Totals,
+    write_failed: bool,
+}
+
+impl NdjsonSink {
+    /// Write to `--output`, or stdout without one
+    fn open(args: &Args) -> Option<Self> {
+        let out: Box<dyn Write> = match &args.output {
+            Some(path) => match std::fs::File::create(path) {
+                Ok(file) => Box::new(std::io::BufWriter::new(file)),
+                Err(e) => {
+                    eprintln!(
+                        "{} Failed to create {}: {}",
+                        "Error:".red().bold(),
+                        path.display(),
+                        e
+                    );
+                    return None;
+                }
+            },
+            None => Box::new(std::io::stdout()),
+        };
+        Some(Self {
+            out,
+            totals: Totals::default(),
+            write_failed: false,
+        })
+    }
+
+    fn write(&mut self, entry_report: &EntryReport) {
+        self.totals.add(entry_report);
+        if self.write_failed {
+            return;
+        }
+        let written = serde_json::to_string(entry_report)
+            .map_err(std::io::Error::from)
+            .and_then(|line| writeln!(self.out, "{}", line))
+            .and_then(|()| self.out.flush());
+        if let Err(e) = written {
+            eprintln!("{} Failed to write NDJSON report: {}", "Error:".red().bold(), e);
+            self.write_failed = true;
+        }
+    }
+
+    fn finish(self, args: &Args, validator: &BibValidator) -> ExitCode {
+        if self.write_failed {
+            return ExitCode::FAILURE;
+        }
+        conclude(args, validator, &self.totals)
     }
 }
@@ -0,0 +1,156 @@
+//! Pandoc Markdown documents: their YAML metadata, linked bibliographies, and citations
+
+use crate::citations::Citations;
+use crate::csl::{self, CslItem};
+use crate::entry::Entry;
+use crate::parser::{self, ParseError};
+use serde::Deserialize;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// Metadata fields read from a document's YAML blocks
+#[derive(Debug, Default, Deserialize)]
+struct Metadata {
+    #[serde(default)]
+    bibliography: OneOrMany,
+    #[serde(default)]
+    references: Vec<CslItem>,
+    nocite: Option<String>,
+}
+
+#[derive(Debug, Default, Deserialize)]
+#[serde(untagged)]
+enum OneOrMany {
+    #[default]
+    None,
+    One(String),
+    Many(Vec<String>),
+}
+
+/// A Markdown document with the bibliography it links to and the keys it cites
+#[derive(Debug, Default)]
+pub struct MarkdownDocument {
+    /// Bibliography files from the `bibliography:` field, relative to the document
+    pub bibliographies: Vec<PathBuf>,
+    /// Entries of an inline `references:` block
+    pub references: Vec<Entry>,
+    pub citations: Citations,
+}
+
+impl MarkdownDocument {
+    pub fn parse(source: &str) -> Result<Self, ParseError> {
+        let mut document = Self::default();
+        let (blocks, body) = split_metadata(source);
+
+        for block in blocks {
+            let metadata: Metadata = serde_yaml::from_str(&block).map_err(|e| {
+                ParseError::ParseError(format!("invalid YAML metadata block: {}", e))
+            })?;
+
+            match metadata.bibliography {
+                OneOrMany::None => {}
+                OneOrMany::One(path) => document.bibliographies.push(path.into()),
+                OneOrMany::Many(paths) => document
+                    .bibliographies
+                    .extend(paths.into_iter().map(PathBuf::from)),
+            }
+            document
+                .references
+                .extend(metadata.references.iter().map(csl::item_to_entry));
+            if let Some(nocite) = metadata.nocite {
+                document.citations.scan_markdown(&nocite);
+            }
+        }
+
+        document.citations.scan_markdown(&body);
+        Ok(document)
+    }
+
+    /// Read a document, resolving its bibliography paths against its directory
+    pub fn open(path: &Path) -> Result<Self, ParseError> {
+        let mut document = Self::parse(&fs::read_to_string(path)?)?;
+        let dir = path.parent().unwrap_or(Path::new(""));
+        for bibliography in &mut document.bibliographies {
+            *bibliography = dir.join(&*bibliography);
+        }
+        Ok(document)
+    }
+
+    /// All entries available to the document: its linked bibliographies and inline references
+    pub fn load_entries(&self) -> Result<Vec<Entry>, ParseError> {
+        let mut entries = Vec::new();
+        for path in &self.bibliographies {
+            entries.extend(parser::parse_file(path)?);
+        }
+        entries.extend(self.references.iter().cloned());
+        Ok(entries)
+    }
+}
+
+/// Split YAML metadata blocks from the body. A block opens with `---` at the start of
+/// the document or after a blank line, and closes with `---` or `...`.
+fn split_metadata(source: &str) -> (Vec<String>, String) {
+    let lines: Vec<&str> = source.lines().collect();
+    let mut blocks = Vec::new();
+    let mut body = Vec::new();
+    let mut i = 0;
+
+    while i < lines.len() {
+        let opens = lines[i].trim_end() == "---"
+            && (i == 0 || lines[i - 1].trim().is_empty())
+            && lines.get(i + 1).is_some_and(|next| !next.trim().is_empty());
+        let close = lines[i + 1..]
+            .iter()
+            .position(|line| matches!(line.trim_end(), "---" | "..."));
+
+        match close {
+            Some(len) if opens => {
+                blocks.push(lines[i + 1..i + 1 + len].join("\n"));
+                i += len + 2;
+            }
+            _ => {
+                body.push(lines[i]);
+                i += 1;
+            }
+        }
+    }
+
+    (blocks, body.join("\n"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn reads_metadata_and_citations() {
+        let source = "---
+title: A Paper
+bibliography: [refs.bib, extra.json]
+nocite: '@background'
+references:
+- id: inline2020
+  title: Inline Reference
+  issued: {date-parts: [[2020]]}
+---
+
+# Intro
+
+Prior work [@smith20; @inline2020].
+
+---
+
+Text after a horizontal rule.
+";
+        let document = MarkdownDocument::parse(source).unwrap();
+        assert_eq!(
+            document.bibliographies,
+            [PathBuf::from("refs.bib"), PathBuf::from("extra.json")]
+        );
+        assert_eq!(document.references.len(), 1);
+        assert_eq!(document.references[0].year, Some(2020));
+
+        let keys: Vec<_> = document.citations.keys.iter().map(String::as_str).collect();
+        assert_eq!(keys, ["background", "inline2020", "smith20"]);
+    }
+}
@@ -1,5 +1,18 @@
-use crate::entry::{normalize_string, Discrepancy, DiscrepancyField, Entry, Severity};
+use crate::entry::{
+    is_arxiv_doi, is_arxiv_venue, normalize_string, Discrepancy, DiscrepancyField, Entry,
+    Severity,
+};
+use crate::lint::{arxiv_version, normalize_arxiv_id, normalize_doi};
+use crate::publishers::classify_publisher;
+use crate::venues::find_venue;
+use crate::validators::arxiv::ARXIV_UPDATED_FIELD;
+use crate::validators::doaj::ALTERNATIVE_TITLES_FIELD;
+use crate::validators::inspire::{is_texkey, INSPIRE_TEXKEYS_FIELD};
+use crate::validators::w3c::{
+    local_status, W3C_DATE_FIELD, W3C_LATEST_DATE_FIELD, W3C_LATEST_STATUS_FIELD, W3C_STATUS_FIELD,
+};
 use strsim::jaro_winkler;
+use std::sync::Arc;
 
 /// Threshold for title similarity (0.0 to 1.0)
 const TITLE_MATCH_THRESHOLD: f64 = 0.85;
@@ -29,8 +42,8 @@ pub fn compare_entries(local: &Entry, remote: &Entry) -> Vec<Discrepancy> {
             discrepancies.push(Discrepancy {
                 field: DiscrepancyField::Title,
                 severity: Severity::Error,
-                local_value: local_title.clone(),
-                remote_value: remote_title.clone(),
+                local_value: local_title.to_string(),
+                remote_value: remote_title.to_string(),
                 message: format!(
                     "Title significantly different (similarity: {:.0}%)",
                     similarity * 100.0
@@ -40,8 +53,8 @@ pub fn compare_entries(local: &Entry, remote: &Entry) -> Vec<Discrepancy> {
             discrepancies.push(Discrepancy {
                 field: DiscrepancyField::Title,
                 severity: Severity::Warning,
-                local_value: local_title.clone(),
-                remote_value: remote_title.clone(),
+                local_value: local_title.to_string(),
+                remote_value: remote_title.to_string(),
                 message: format!(
                     "Title slightly different (similarity: {:.0}%)",
                     similarity * 100.0
@@ -89,18 +102,323 @@ pub fn compare_entries(local: &Entry, remote: &Entry) -> Vec<Discrepancy> {
             discrepancies.push(Discrepancy {
                 field: DiscrepancyField::Venue,
                 severity: Severity::Info,
-                local_value: local_venue.clone(),
-                remote_value: remote_venue.clone(),
+                local_value: local_venue.to_string(),
+                remote_value: remote_venue.to_string(),
                 message: "Venue name differs".to_string(),
             });
         }
     }
 
+    discrepancies.extend(compare_pages(local, remote));
+    discrepancies.extend(compare_publishers(local, remote));
+    discrepancies.extend(compare_arxiv_versions(local, remote));
+    discrepancies.extend(compare_publication(local, remote));
+    discrepancies.extend(compare_w3c_versions(local, remote));
+    discrepancies.extend(compare_w3c_status(local, remote));
+    discrepancies.extend(compare_pubmed_ids(local, remote));
+    discrepancies.extend(compare_texkeys(local, remote));
+    discrepancies.extend(compare_mr_numbers(local, remote));
+
     discrepancies
 }
 
+/// Check that an entry's PubMed identifiers and DOI name the same work. When a record
+/// shares the entry's PMID or PMCID, a different DOI means one of the two is wrong.
+fn compare_pubmed_ids(local: &Entry, remote: &Entry) -> Vec<Discrepancy> {
+    let mut discrepancies = Vec::new();
+
+    if let (Some(local_pmcid), Some(remote_pmcid)) = (&local.pmcid, &remote.pmcid) {
+        if !local_pmcid.eq_ignore_ascii_case(remote_pmcid) {
+            discrepancies.push(Discrepancy {
+                field: DiscrepancyField::Pmcid,
+                severity: Severity::Error,
+                local_value: local_pmcid.clone(),
+                remote_value: remote_pmcid.clone(),
+                message: format!("PMCID mismatch: {} vs {}", local_pmcid, remote_pmcid),
+            });
+        }
+    }
+
+    let same = |a: &Option<String>, b: &Option<String>| {
+        matches!((a, b), (Some(a), Some(b)) if a.eq_ignore_ascii_case(b))
+    };
+    let shared_id = if same(&local.pmcid, &remote.pmcid) {
+        "PMCID"
+    } else if same(&local.pmid, &remote.pmid) {
+        "PMID"
+    } else {
+        return discrepancies;
+    };
+    if let (Some(local_doi), Some(remote_doi)) = (&local.doi, &remote.doi) {
+        if normalize_doi(local_doi) != normalize_doi(remote_doi) {
+            discrepancies.push(Discrepancy {
+                field: DiscrepancyField::Doi,
+                severity: Severity::Error,
+                local_value: local_doi.clone(),
+                remote_value: remote_doi.clone(),
+                message: format!(
+                    "{} and DOI point to different works; the {} is registered with DOI {}",
+                    shared_id, shared_id, remote_doi
+                ),
+            });
+        }
+    }
+
+    discrepancies
+}
+
+/// Check that a citation key taken from INSPIRE is one of the matched record's texkeys
+fn compare_texkeys(local: &Entry, remote: &Entry) -> Option<Discrepancy> {
+    let texkeys = remote.field(INSPIRE_TEXKEYS_FIELD)?;
+    if !is_texkey(&local.key) || texkeys.split(',').any(|k| k == local.key) {
+        return None;
+    }
+    let texkey = texkeys.split(',').next().unwrap_or(texkeys);
+    Some(Discrepancy {
+        field: DiscrepancyField::Key,
+        severity: Severity::Warning,
+        local_value: local.key.clone(),
+        remote_value: texkey.to_string(),
+        message: format!("Citation key does not match the INSPIRE texkey {}", texkey),
+    })
+}
+
+/// Check an entry's MR number against the MathSciNet record matching its citation
+fn compare_mr_numbers(local: &Entry, remote: &Entry) -> Option<Discrepancy> {
+    let (local_mr, remote_mr) = (local.mr_number.as_ref()?, remote.mr_number.as_ref()?);
+    if local_mr == remote_mr {
+        return None;
+    }
+    Some(Discrepancy {
+        field: DiscrepancyField::MrNumber,
+        severity: Severity::Error,
+        local_value: local_mr.clone(),
+        remote_value: remote_mr.clone(),
+        message: format!("MR number mismatch: {} vs {}", local_mr, remote_mr),
+    })
+}
+
+/// Compare page ranges, ignoring the dash style and article-number prefixes
+/// ("12:1--12:14" is "1-14")
+fn compare_pages(local: &Entry, remote: &Entry) -> Option<Discrepancy> {
+    let local_pages = local.field("pages")?;
+    let remote_pages = remote.field("pages")?;
+    if normalize_pages(local_pages) == normalize_pages(remote_pages) {
+        return None;
+    }
+
+    Some(Discrepancy {
+        field: DiscrepancyField::Pages,
+        severity: Severity::Warning,
+        local_value: local_pages.to_string(),
+        remote_value: remote_pages.to_string(),
+        message: format!("Page range differs: {} vs {}", local_pages, remote_pages),
+    })
+}
+
+pub fn normalize_pages(pages: &str) -> String {
+    let pages = pages
+        .trim()
+        .trim_start_matches("pp.")
+        .trim_start_matches("p.")
+        .replace(['–', '—'], "-");
+    pages
+        .split('-')
+        .map(str::trim)
+        .filter(|part| !part.is_empty())
+        .map(|part| part.rsplit(':').next().unwrap_or(part))
+        .collect::<Vec<_>>()
+        .join("-")
+}
+
+/// Compare a publisher field with the publisher a database reports, when the registry
+/// knows the remote one
+fn compare_publishers(local: &Entry, remote: &Entry) -> Option<Discrepancy> {
+    let local_publisher = local.field("publisher")?;
+    let known = classify_publisher(remote.field("publisher")?)?.publisher();
+    let local_known = classify_publisher(local_publisher).map(|m| m.publisher());
+    if local_known == Some(known) {
+        return None;
+    }
+
+    Some(Discrepancy {
+        field: DiscrepancyField::Publisher,
+        severity: Severity::Info,
+        local_value: local_publisher.to_string(),
+        remote_value: known.name.to_string(),
+        message: "Publisher differs from the one the DOI is registered to".to_string(),
+    })
+}
+
+/// Check that an entry's venue is the journal its ISSN was assigned to, given that
+/// journal's record
+pub fn compare_journal(local: &Entry, journal: &Entry, issn: &str) -> Option<Discrepancy> {
+    let venue = local.venue.as_deref()?;
+    let title = journal.title.as_deref()?;
+    let alternatives = journal.field(ALTERNATIVE_TITLES_FIELD).unwrap_or_default();
+    let same_journal = std::iter::once(title)
+        .chain(alternatives.lines())
+        .any(|name| {
+            let known = find_venue(venue).zip(find_venue(name));
+            known.is_some_and(|(a, b)| a == b) || abbreviates(venue, name)
+        });
+    if same_journal {
+        return None;
+    }
+
+    Some(Discrepancy {
+        field: DiscrepancyField::Venue,
+        severity: Severity::Warning,
+        local_value: venue.to_string(),
+        remote_value: title.to_string(),
+        message: format!("ISSN {} belongs to {}, not the entry's venue", issn, title),
+    })
+}
+
+/// Whether a journal name is the full name or an abbreviation of it that shortens each
+/// word ("J. Mach. Learn. Res." for "Journal of Machine Learning Research")
+fn abbreviates(short: &str, full: &str) -> bool {
+    const DROPPED: &[&str] = &["of", "the", "and", "for", "in", "on"];
+    let short = normalize_string(short);
+    let full = normalize_string(full);
+    let short_words: Vec<&str> = short.split_whitespace().collect();
+    let full_words: Vec<&str> = full
+        .split_whitespace()
+        .filter(|w| !DROPPED.contains(w) || short_words.contains(w))
+        .collect();
+    short_words.len() == full_words.len()
+        && short_words
+            .iter()
+            .zip(&full_words)
+            .all(|(s, f)| f.starts_with(s))
+}
+
+/// Flag a pinned arXiv version that has since been superseded
+fn compare_arxiv_versions(local: &Entry, remote: &Entry) -> Option<Discrepancy> {
+    let local_id = local.arxiv_id.as_deref()?;
+    let remote_id = remote.arxiv_id.as_deref()?;
+    let local_version = arxiv_version(local_id)?;
+    let remote_version = arxiv_version(remote_id)?;
+    let same_paper = normalize_arxiv_id(local_id) == normalize_arxiv_id(remote_id);
+    if !same_paper || remote_version <= local_version {
+        return None;
+    }
+
+    let posted = remote
+        .field(ARXIV_UPDATED_FIELD)
+        .map(|date| format!(", posted {}", date))
+        .unwrap_or_default();
+    Some(Discrepancy {
+        field: DiscrepancyField::Version,
+        severity: Severity::Warning,
+        local_value: local_id.to_string(),
+        remote_value: remote_id.to_string(),
+        message: format!(
+            "Cites arXiv v{} but the latest version is v{}{}",
+            local_version, remote_version, posted
+        ),
+    })
+}
+
+/// Whether the remote record is the published version of a preprint entry: a journal
+/// or conference paper with a DOI of its own
+pub fn is_published_version(local: &Entry, remote: &Entry) -> bool {
+    let doi = remote.doi.as_deref().filter(|doi| !is_arxiv_doi(doi));
+    let venue = remote.venue.as_deref().filter(|venue| !is_arxiv_venue(venue));
+    local.is_preprint() && doi.is_some() && venue.is_some()
+}
+
+/// The BibTeX entry type for the published version of a preprint: the record's own type
+/// when it says, otherwise what its venue looks like
+pub fn published_entry_type(remote: &Entry) -> &'static str {
+    match remote.entry_type.to_lowercase().as_str() {
+        "proceedings-article" | "inproceedings" | "conference and workshop papers" => {
+            return "inproceedings"
+        }
+        "journal-article" | "journal articles" => return "article",
+        _ => {}
+    }
+    let venue = remote.venue.as_deref().unwrap_or_default();
+    let in_proceedings = match find_venue(venue) {
+        Some(known) => !known.is_journal(),
+        None => {
+            let venue = venue.to_lowercase();
+            ["proceedings", "conference", "workshop", "symposium"]
+                .iter()
+                .any(|word| venue.contains(word))
+        }
+    };
+    if in_proceedings {
+        "inproceedings"
+    } else {
+        "article"
+    }
+}
+
+/// Flag a preprint that has since been published in a journal or at a conference
+fn compare_publication(local: &Entry, remote: &Entry) -> Option<Discrepancy> {
+    if !is_published_version(local, remote) {
+        return None;
+    }
+
+    let venue = remote.venue.as_deref().unwrap_or_default();
+    let doi = remote.doi.as_deref().unwrap_or_default();
+    let published = match remote.year {
+        Some(year) => format!("{} ({})", venue, year),
+        None => venue.to_string(),
+    };
+    Some(Discrepancy {
+        field: DiscrepancyField::Version,
+        severity: Severity::Warning,
+        local_value: format!("arXiv:{}", local.arxiv_id.as_deref().unwrap_or_default()),
+        remote_value: format!("{}, doi:{}", published, doi),
+        message: format!("Cites a preprint that has been published in {}", published),
+    })
+}
+
+/// Flag a W3C specification cited at a version that a later one has superseded
+fn compare_w3c_versions(local: &Entry, remote: &Entry) -> Option<Discrepancy> {
+    let latest_date = remote.field(W3C_LATEST_DATE_FIELD)?;
+    let cited_date = remote.field(W3C_DATE_FIELD)?;
+    if latest_date <= cited_date {
+        return None;
+    }
+
+    let cited_status = remote.field(W3C_STATUS_FIELD).unwrap_or("draft");
+    let latest_status = remote.field(W3C_LATEST_STATUS_FIELD).unwrap_or("version");
+    let title = remote.title.as_deref().or(local.title.as_deref()).unwrap_or("the specification");
+    Some(Discrepancy {
+        field: DiscrepancyField::Version,
+        severity: Severity::Warning,
+        local_value: format!("{} ({})", cited_status, cited_date),
+        remote_value: format!("{} ({})", latest_status, latest_date),
+        message: format!(
+            "Cites the {} of {} from {} but it has been superseded by the {} of {}",
+            cited_status, title, cited_date, latest_status, latest_date
+        ),
+    })
+}
+
+/// Flag a W3C maturity level that differs from the one the cited version has
+fn compare_w3c_status(local: &Entry, remote: &Entry) -> Option<Discrepancy> {
+    let claimed = local_status(local)?;
+    let actual = remote.field(W3C_STATUS_FIELD)?;
+    // The API qualifies some levels ("Candidate Recommendation Snapshot")
+    if actual.to_lowercase().starts_with(&claimed.to_lowercase()) {
+        return None;
+    }
+
+    Some(Discrepancy {
+        field: DiscrepancyField::Version,
+        severity: Severity::Warning,
+        local_value: claimed.to_string(),
+        remote_value: actual.to_string(),
+        message: format!("Cited as a {} but the cited version is a {}", claimed, actual),
+    })
+}
+
 /// Compare author lists and return discrepancies
-fn compare_authors(local: &[String], remote: &[String]) -> Vec<Discrepancy> {
+fn compare_authors(local: &[Arc<str>], remote: &[Arc<str>]) -> Vec<Discrepancy> {
     let mut discrepancies = Vec::new();
 
     if local.is_empty() || remote.is_empty() {
@@ -138,8 +456,8 @@ fn compare_authors(local: &[String], remote: &[String]) -> Vec<Discrepancy> {
                 discrepancies.push(Discrepancy {
                     field: DiscrepancyField::Authors,
                     severity: Severity::Warning,
-                    local_value: local_author.clone(),
-                    remote_value: remote_author.clone(),
+                    local_value: local_author.to_string(),
+                    remote_value: remote_author.to_string(),
                     message: format!(
                         "Author name spelling may differ: '{}' vs '{}'",
                         local_author, remote_author
@@ -206,16 +524,32 @@ pub fn author_overlap(local: &Entry, remote: &Entry) -> f64 {
 
 /// Calculate a combined match score considering title, year, and authors
 pub fn match_score(target: &Entry, candidate: &Entry) -> f64 {
+    score_or_rejection(target, candidate).unwrap_or(0.0)
+}
+
+/// Why a search result can't be the target entry, or `None` if it might be
+pub fn rejection(target: &Entry, candidate: &Entry) -> Option<String> {
+    score_or_rejection(target, candidate).err()
+}
+
+fn score_or_rejection(target: &Entry, candidate: &Entry) -> Result<f64, String> {
     let title_sim = title_similarity(target, candidate);
 
     // Hard filter: title must be reasonably similar
     if title_sim < TITLE_MATCH_THRESHOLD {
-        return 0.0;
+        return Err(format!(
+            "title similarity {:.2} is below {:.2}",
+            title_sim, TITLE_MATCH_THRESHOLD
+        ));
     }
 
     // Hard filter: years must be compatible
     if !years_compatible(target, candidate) {
-        return 0.0;
+        return Err(format!(
+            "year {} is too far from {}",
+            candidate.year.unwrap_or_default(),
+            target.year.unwrap_or_default()
+        ));
     }
 
     // Compute author overlap
@@ -223,7 +557,10 @@ pub fn match_score(target: &Entry, candidate: &Entry) -> f64 {
 
     // If we have author info and overlap is too low, reject
     if !target.authors.is_empty() && !candidate.authors.is_empty() && author_sim < MIN_AUTHOR_OVERLAP {
-        return 0.0;
+        return Err(format!(
+            "author overlap {:.2} is below {:.2}",
+            author_sim, MIN_AUTHOR_OVERLAP
+        ));
     }
 
     // Combined score: weight title heavily, but boost with author match
@@ -233,11 +570,11 @@ pub fn match_score(target: &Entry, candidate: &Entry) -> f64 {
     // Boost if DOIs match exactly
     if let (Some(doi_a), Some(doi_b)) = (&target.doi, &candidate.doi) {
         if doi_a.to_lowercase() == doi_b.to_lowercase() {
-            return 1.0; // Perfect match
+            return Ok(1.0); // Perfect match
         }
     }
 
-    base_score
+    Ok(base_score)
 }
 
 /// Find the best matching entry from a list of candidates
@@ -256,28 +593,179 @@ mod tests {
     #[test]
     fn test_title_similarity() {
         let mut a = Entry::new("a".to_string(), "article".to_string());
-        a.title = Some("Deep Learning for Image Classification".to_string());
+        a.title = Some("Deep Learning for Image Classification".into());
 
         let mut b = Entry::new("b".to_string(), "article".to_string());
-        b.title = Some("Deep Learning for Image Classification".to_string());
+        b.title = Some("Deep Learning for Image Classification".into());
 
         assert!(title_similarity(&a, &b) > 0.99);
 
-        b.title = Some("Deep Learning for Image Recognition".to_string());
+        b.title = Some("Deep Learning for Image Recognition".into());
         assert!(title_similarity(&a, &b) > 0.85);
 
-        b.title = Some("Quantum Computing in Finance".to_string());
+        b.title = Some("Quantum Computing in Finance".into());
         assert!(title_similarity(&a, &b) < 0.7);
     }
 
+    #[test]
+    fn rejected_candidates_say_why() {
+        let mut target = Entry::new("a".to_string(), "article".to_string());
+        target.title = Some("Deep Learning for Image Classification".into());
+        target.year = Some(2019);
+        let mut candidate = target.clone();
+        assert_eq!(rejection(&target, &candidate), None);
+
+        candidate.year = Some(2015);
+        assert_eq!(
+            rejection(&target, &candidate).as_deref(),
+            Some("year 2015 is too far from 2019")
+        );
+
+        candidate.title = Some("Quantum Computing in Finance".into());
+        assert!(rejection(&target, &candidate)
+            .unwrap()
+            .starts_with("title similarity"));
+        assert_eq!(match_score(&target, &candidate), 0.0);
+    }
+
+    #[test]
+    fn outdated_arxiv_versions_are_flagged() {
+        let mut local = Entry::new("a".to_string(), "article".to_string());
+        local.arxiv_id = Some("2301.12345v1".to_string());
+        let mut remote = Entry::new("b".to_string(), "article".to_string());
+        remote.arxiv_id = Some("2301.12345v4".to_string());
+        remote
+            .fields
+            .insert(ARXIV_UPDATED_FIELD.to_string(), "2024-03-02".to_string());
+
+        let discrepancy = compare_arxiv_versions(&local, &remote).unwrap();
+        assert_eq!(discrepancy.remote_value, "2301.12345v4");
+        assert!(discrepancy.message.contains("2024-03-02"));
+
+        local.arxiv_id = Some("2301.12345".to_string());
+        assert!(compare_arxiv_versions(&local, &remote).is_none());
+    }
+
+    #[test]
+    fn published_preprints_are_flagged() {
+        let mut local = Entry::new("a".to_string(), "article".to_string());
+        local.arxiv_id = Some("1512.03385".to_string());
+        local.venue = Some("arXiv preprint arXiv:1512.03385".into());
+        let mut remote = Entry::new("b".to_string(), "Conference and Workshop Papers".to_string());
+        remote.venue = Some("CVPR".into());
+        remote.year = Some(2016);
+        remote.doi = Some("10.1109/CVPR.2016.90".to_string());
+
+        let discrepancy = compare_publication(&local, &remote).unwrap();
+        assert_eq!(discrepancy.remote_value, "CVPR (2016), doi:10.1109/CVPR.2016.90");
+        assert_eq!(published_entry_type(&remote), "inproceedings");
+
+        // DBLP's CoRR record is the preprint itself
+        remote.venue = Some("CoRR".into());
+        assert!(compare_publication(&local, &remote).is_none());
+    }
+
+    #[test]
+    fn issns_of_other_journals_are_flagged() {
+        let mut local = Entry::new("a".to_string(), "article".to_string());
+        local.venue = Some("PLoS Med.".into());
+        let mut journal = Entry::new("1549-1676".to_string(), "periodical".to_string());
+        journal.title = Some("PLoS Medicine".into());
+        assert!(compare_journal(&local, &journal, "1549-1676").is_none());
+
+        journal.title = Some("PLOS ONE".into());
+        let discrepancy = compare_journal(&local, &journal, "1932-6203").unwrap();
+        assert_eq!(discrepancy.remote_value, "PLOS ONE");
+
+        local.venue = Some("J. Mach. Learn. Res.".into());
+        journal.title = Some("Journal of Machine Learning Research".into());
+        assert!(compare_journal(&local, &journal, "1532-4435").is_none());
+    }
+
+    #[test]
+    fn superseded_w3c_specifications_are_flagged() {
+        let mut local = Entry::new("a".to_string(), "online".to_string());
+        local
+            .fields
+            .insert("note".to_string(), "W3C Recommendation".to_string());
+        let mut remote = Entry::new("b".to_string(), "techreport".to_string());
+        remote.title = Some("Web Authentication".into());
+        for (name, value) in [
+            (W3C_STATUS_FIELD, "Working Draft"),
+            (W3C_DATE_FIELD, "2018-03-20"),
+            (W3C_LATEST_STATUS_FIELD, "Recommendation"),
+            (W3C_LATEST_DATE_FIELD, "2019-03-04"),
+        ] {
+            remote.fields.insert(name.to_string(), value.to_string());
+        }
+
+        let discrepancy = compare_w3c_versions(&local, &remote).unwrap();
+        assert_eq!(discrepancy.remote_value, "Recommendation (2019-03-04)");
+        assert_eq!(
+            compare_w3c_status(&local, &remote).unwrap().remote_value,
+            "Working Draft"
+        );
+
+        remote.fields.remove(W3C_LATEST_DATE_FIELD);
+        assert!(compare_w3c_versions(&local, &remote).is_none());
+    }
+
+    #[test]
+    fn pmcid_and_doi_for_different_works_are_flagged() {
+        let mut local = Entry::new("a".to_string(), "article".to_string());
+        local.pmcid = Some("PMC6712345".to_string());
+        local.doi = Some("10.1000/other".to_string());
+        let mut remote = Entry::new("b".to_string(), "article".to_string());
+        remote.pmcid = Some("PMC6712345".to_string());
+        remote.doi = Some("10.1000/ABC".to_string());
+
+        let discrepancies = compare_pubmed_ids(&local, &remote);
+        assert_eq!(discrepancies.len(), 1);
+        assert_eq!(discrepancies[0].field, DiscrepancyField::Doi);
+        assert!(discrepancies[0].message.starts_with("PMCID and DOI"));
+
+        local.doi = Some("https://doi.org/10.1000/abc".to_string());
+        assert!(compare_pubmed_ids(&local, &remote).is_empty());
+    }
+
+    #[test]
+    fn inspire_keys_must_match_the_texkey() {
+        let local = Entry::new("Maldacena:1997rf".to_string(), "article".to_string());
+        let mut remote = Entry::new("Maldacena:1997re".to_string(), "article".to_string());
+        remote
+            .fields
+            .insert(INSPIRE_TEXKEYS_FIELD.to_string(), "Maldacena:1997re".to_string());
+        let discrepancy = compare_texkeys(&local, &remote).unwrap();
+        assert_eq!(discrepancy.field, DiscrepancyField::Key);
+        assert_eq!(discrepancy.remote_value, "Maldacena:1997re");
+
+        let local = Entry::new("maldacena1997".to_string(), "article".to_string());
+        assert!(compare_texkeys(&local, &remote).is_none());
+    }
+
+    #[test]
+    fn page_ranges_compare_by_number() {
+        assert_eq!(normalize_pages("12:1--12:14"), "1-14");
+        assert_eq!(normalize_pages("pp. 1–14"), "1-14");
+
+        let mut local = Entry::new("a".to_string(), "inproceedings".to_string());
+        local.fields.insert("pages".to_string(), "1--14".to_string());
+        let mut remote = Entry::new("b".to_string(), "inproceedings".to_string());
+        remote.fields.insert("pages".to_string(), "1–14".to_string());
+        assert!(compare_pages(&local, &remote).is_none());
+
+        remote.fields.insert("pages".to_string(), "101–114".to_string());
+        assert!(compare_pages(&local, &remote).is_some());
+    }
+
     #[test]
     fn test_year_mismatch() {
         let mut local = Entry::new("test".to_string(), "article".to_string());
-        local.title = Some("Test Paper".to_string());
+        local.title = Some("Test Paper".into());
         local.year = Some(2021);
 
         let mut remote = Entry::new("test".to_string(), "article".to_string());
-        remote.title = Some("Test Paper".to_string());
+        remote.title = Some("Test Paper".into());
         remote.year = Some(2020);
 
         let discrepancies = compare_entries(&local, &remote);
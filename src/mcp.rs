@@ -0,0 +1,300 @@
+//! Model Context Protocol server exposing validation as tools for writing assistants
+//!
+//! Messages are newline-delimited JSON-RPC 2.0 on stdin and stdout.
+
+use crate::entry::{DiscrepancyField, Severity};
+use crate::fusion::clean_entry;
+use crate::parser::parse_bib_string;
+use crate::report::EntryStatus;
+use crate::writer::format_entry;
+use crate::BibValidator;
+use serde_json::{json, Value};
+use tokio::io::{self, AsyncBufReadExt, AsyncWriteExt, BufReader};
+
+/// Protocol revision this server implements
+const PROTOCOL_VERSION: &str = "2024-11-05";
+
+const PARSE_ERROR: i64 = -32700;
+const METHOD_NOT_FOUND: i64 = -32601;
+const INVALID_PARAMS: i64 = -32602;
+
+/// Serves tool calls with one validator, so its cache and rate limits carry over
+/// between calls
+pub struct McpServer {
+    validator: BibValidator,
+}
+
+impl McpServer {
+    pub fn new(validator: BibValidator) -> Self {
+        Self { validator }
+    }
+
+    /// Answer requests from stdin until it is closed
+    pub async fn serve_stdio(&self) -> io::Result<()> {
+        let mut lines = BufReader::new(io::stdin()).lines();
+        let mut stdout = io::stdout();
+        while let Some(line) = lines.next_line().await? {
+            if line.trim().is_empty() {
+                continue;
+            }
+            let response = match serde_json::from_str(&line) {
+                Ok(message) => self.handle(message).await,
+                Err(e) => Some(error_response(Value::Null, PARSE_ERROR, &e.to_string())),
+            };
+            if let Some(response) = response {
+                stdout.write_all(response.to_string().as_bytes()).await?;
+                stdout.write_all(b"\n").await?;
+                stdout.flush().await?;
+            }
+        }
+        Ok(())
+    }
+
+    /// The response to a JSON-RPC message; notifications get none
+    pub async fn handle(&self, message: Value) -> Option<Value> {
+        let id = message.get("id").cloned()?;
+        let method = message.get("method").and_then(Value::as_str).unwrap_or("");
+        let params = message.get("params").cloned().unwrap_or(Value::Null);
+
+        let result = match method {
+            "initialize" => Ok(json!({
+                "protocolVersion": PROTOCOL_VERSION,
+                "capabilities": { "tools": {} },
+                "serverInfo": { "name": "bibval", "version": env!("CARGO_PKG_VERSION") },
+            })),
+            "ping" => Ok(json!({})),
+            "tools/list" => Ok(json!({ "tools": tool_definitions() })),
+            "tools/call" => self.call_tool(&params).await,
+            _ => Err((METHOD_NOT_FOUND, format!("Unknown method '{}'", method))),
+        };
+
+        Some(match result {
+            Ok(result) => json!({ "jsonrpc": "2.0", "id": id, "result": result }),
+            Err((code, message)) => error_response(id, code, &message),
+        })
+    }
+
+    async fn call_tool(&self, params: &Value) -> Result<Value, (i64, String)> {
+        let name = params.get("name").and_then(Value::as_str).unwrap_or("");
+        let arguments = params.get("arguments").cloned().unwrap_or(Value::Null);
+        let argument = |key: &str| {
+            arguments
+                .get(key)
+                .and_then(Value::as_str)
+                .map(str::to_string)
+                .ok_or_else(|| (INVALID_PARAMS, format!("Missing string argument '{}'", key)))
+        };
+
+        // Failures of the tool itself are reported to the model, not as protocol errors
+        let outcome = match name {
+            "validate_bibtex" => self.validate_bibtex(&argument("bibtex")?).await,
+            "lookup_by_doi" => self.lookup_by_doi(&argument("doi")?).await,
+            "suggest_fixes" => self.suggest_fixes(&argument("bibtex")?).await,
+            _ => return Err((INVALID_PARAMS, format!("Unknown tool '{}'", name))),
+        };
+        let (text, is_error) = match outcome {
+            Ok(text) => (text, false),
+            Err(text) => (text, true),
+        };
+        Ok(json!({
+            "content": [{ "type": "text", "text": text }],
+            "isError": is_error,
+        }))
+    }
+
+    /// The full report for some BibTeX, as JSON
+    async fn validate_bibtex(&self, bibtex: &str) -> Result<String, String> {
+        let entries = parse_bib_string(bibtex).map_err(|e| e.to_string())?;
+        let report = self.validator.validate(entries).await;
+        report.to_json().map_err(|e| e.to_string())
+    }
+
+    /// The record registered for a DOI, as BibTeX
+    async fn lookup_by_doi(&self, doi: &str) -> Result<String, String> {
+        match self.validator.lookup_doi(doi).await {
+            Ok(Some(entry)) => Ok(format_entry(&entry)),
+            Ok(None) => Err(format!("No record found for DOI {}", doi)),
+            Err(e) => Err(e.to_string()),
+        }
+    }
+
+    /// Corrected BibTeX and the reasons for it, for each entry with issues
+    async fn suggest_fixes(&self, bibtex: &str) -> Result<String, String> {
+        let entries = parse_bib_string(bibtex).map_err(|e| e.to_string())?;
+        let report = self.validator.validate(entries).await;
+
+        let fixes: Vec<Value> = report
+            .entries
+            .iter()
+            .filter(|r| matches!(r.status, EntryStatus::Warning | EntryStatus::Error))
+            .map(|r| {
+                // The fused result (the one without a matched entry) already holds
+                // what the individual sources found
+                let fused = r
+                    .validation_results
+                    .iter()
+                    .filter(|v| v.matched_entry.is_none())
+                    .flat_map(|v| &v.discrepancies);
+                let issues: Vec<Value> = r
+                    .lints
+                    .iter()
+                    .chain(fused)
+                    .filter(|d| d.severity != Severity::Info)
+                    .map(|d| {
+                        json!({
+                            "field": field_name(d.field),
+                            "severity": format!("{:?}", d.severity).to_lowercase(),
+                            "message": d.message,
+                            "local": d.local_value,
+                            "remote": d.remote_value,
+                        })
+                    })
+                    .collect();
+                json!({
+                    "key": r.entry.key,
+                    "issues": issues,
+                    "bibtex": format_entry(&clean_entry(r)),
+                })
+            })
+            .collect();
+
+        if fixes.is_empty() {
+            return Ok("No issues found.".to_string());
+        }
+        serde_json::to_string_pretty(&fixes).map_err(|e| e.to_string())
+    }
+}
+
+fn error_response(id: Value, code: i64, message: &str) -> Value {
+    json!({
+        "jsonrpc": "2.0",
+        "id": id,
+        "error": { "code": code, "message": message },
+    })
+}
+
+fn field_name(field: DiscrepancyField) -> String {
+    format!("{:?}", field).to_lowercase()
+}
+
+fn tool_definitions() -> Value {
+    let bibtex_input = json!({
+        "type": "object",
+        "properties": {
+            "bibtex": { "type": "string", "description": "One or more BibTeX entries" },
+        },
+        "required": ["bibtex"],
+    });
+    json!([
+        {
+            "name": "validate_bibtex",
+            "description": "Check BibTeX entries against CrossRef, DBLP, arXiv and other \
+                databases, returning the full validation report as JSON",
+            "inputSchema": bibtex_input,
+        },
+        {
+            "name": "lookup_by_doi",
+            "description": "Fetch the registered metadata for a DOI as a BibTeX entry",
+            "inputSchema": {
+                "type": "object",
+                "properties": {
+                    "doi": {
+                        "type": "string",
+                        "description": "DOI, with or without a doi.org prefix",
+                    },
+                },
+                "required": ["doi"],
+            },
+        },
+        {
+            "name": "suggest_fixes",
+            "description": "Validate BibTeX entries and return corrected BibTeX for each entry \
+                with issues, along with the issues found",
+            "inputSchema": bibtex_input,
+        },
+    ])
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ValidatorConfig;
+
+    fn offline_server() -> McpServer {
+        let validator = BibValidator::new(ValidatorConfig {
+            use_crossref: false,
+            use_dblp: false,
+            use_arxiv: false,
+            use_semantic: false,
+            use_openalex: false,
+            use_openlibrary: false,
+            use_hathitrust: false,
+            use_pubmed: false,
+            use_doaj: false,
+            use_scielo: false,
+            use_jstage: false,
+            use_acm: false,
+            use_inspire: false,
+            use_mathscinet: false,
+            use_osf: false,
+            use_paperswithcode: false,
+            use_hal: false,
+            use_ietf: false,
+            use_w3c: false,
+            use_openreview: false,
+            use_zenodo: false,
+            use_datacite: false,
+            use_doi_org: false,
+            use_figshare: false,
+            use_github: false,
+            use_software_heritage: false,
+            use_opencitations: false,
+            check_urls: false,
+            check_retractions: false,
+            cache_enabled: false,
+            ..ValidatorConfig::default()
+        })
+        .unwrap();
+        McpServer::new(validator)
+    }
+
+    #[tokio::test]
+    async fn requests_are_answered_and_notifications_are_not() {
+        let server = offline_server();
+
+        let init = json!({ "jsonrpc": "2.0", "id": 1, "method": "initialize", "params": {} });
+        let response = server.handle(init).await.unwrap();
+        assert_eq!(response["result"]["serverInfo"]["name"], "bibval");
+
+        let notification = json!({ "jsonrpc": "2.0", "method": "notifications/initialized" });
+        assert!(server.handle(notification).await.is_none());
+
+        let list = json!({ "jsonrpc": "2.0", "id": 2, "method": "tools/list" });
+        let tools = server.handle(list).await.unwrap();
+        assert_eq!(tools["result"]["tools"].as_array().unwrap().len(), 3);
+
+        let unknown = json!({ "jsonrpc": "2.0", "id": 3, "method": "resources/list" });
+        let response = server.handle(unknown).await.unwrap();
+        assert_eq!(response["error"]["code"], METHOD_NOT_FOUND);
+    }
+
+    #[tokio::test]
+    async fn validate_bibtex_returns_the_report() {
+        let server = offline_server();
+        let call = json!({
+            "jsonrpc": "2.0",
+            "id": 4,
+            "method": "tools/call",
+            "params": {
+                "name": "validate_bibtex",
+                "arguments": { "bibtex": "@article{a, title = {Attention Is All You Need}}" },
+            },
+        });
+
+        let response = server.handle(call).await.unwrap();
+        assert_eq!(response["result"]["isError"], false);
+        let text = response["result"]["content"][0]["text"].as_str().unwrap();
+        let report: Value = serde_json::from_str(text).unwrap();
+        assert_eq!(report["summary"]["total"], 1);
+    }
+}
@@ -0,0 +1,111 @@
+use crate::entry::ApiSource;
+use crate::validators::ValidatorError;
+use std::collections::HashMap;
+use std::future::Future;
+use std::sync::{Arc, Mutex};
+use tokio::sync::OnceCell;
+
+type MemoCell<T> = Arc<OnceCell<T>>;
+
+/// A query: which API, what kind of lookup ("doi", "title", ...), and the query string
+type MemoKey = (ApiSource, &'static str, String);
+
+/// In-memory memoization of API queries for a single validation run.
+///
+/// Entries are validated concurrently, so an identical query issued while the first
+/// one is still in flight waits for that result instead of hitting the API again. Only
+/// successes are shared: a failed query is tried again by the next entry that issues it.
+pub struct Memo<T> {
+    cells: Mutex<HashMap<MemoKey, MemoCell<T>>>,
+}
+
+impl<T: Clone> Memo<T> {
+    pub fn new() -> Self {
+        Self {
+            cells: Mutex::new(HashMap::new()),
+        }
+    }
+
+    /// Return the memoized result for `(source, kind, query)`, running `fetch` only if
+    /// no other entry has issued the same query successfully yet
+    pub async fn get_or_fetch<F>(
+        &self,
+        source: ApiSource,
+        kind: &'static str,
+        query: &str,
+        fetch: F,
+    ) -> Result<T, Arc<ValidatorError>>
+    where
+        F: Future<Output = Result<T, ValidatorError>>,
+    {
+        let cell = {
+            let mut cells = self.cells.lock().unwrap_or_else(|e| e.into_inner());
+            Arc::clone(cells.entry((source, kind, query.to_string())).or_default())
+        };
+
+        cell.get_or_try_init(|| async { fetch.await.map_err(Arc::new) })
+            .await
+            .cloned()
+    }
+
+    /// Number of distinct queries seen so far
+    pub fn len(&self) -> usize {
+        self.cells.lock().unwrap_or_else(|e| e.into_inner()).len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+}
+
+impl<T: Clone> Default for Memo<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    #[tokio::test]
+    async fn identical_queries_fetch_once() {
+        let memo: Memo<Vec<u32>> = Memo::new();
+        let calls = AtomicUsize::new(0);
+        let fetch = || async {
+            calls.fetch_add(1, Ordering::SeqCst);
+            Ok(vec![1, 2, 3])
+        };
+
+        let (a, b) = futures::join!(
+            memo.get_or_fetch(ApiSource::Dblp, "title", "Attention", fetch()),
+            memo.get_or_fetch(ApiSource::Dblp, "title", "Attention", fetch()),
+        );
+        let c = memo
+            .get_or_fetch(ApiSource::OpenAlex, "title", "Attention", fetch())
+            .await;
+
+        assert_eq!(a.unwrap(), vec![1, 2, 3]);
+        assert_eq!(b.unwrap(), vec![1, 2, 3]);
+        assert!(c.is_ok());
+        assert_eq!(calls.load(Ordering::SeqCst), 2);
+        assert_eq!(memo.len(), 2);
+    }
+
+    #[tokio::test]
+    async fn failed_queries_are_retried() {
+        let memo: Memo<u32> = Memo::new();
+        let failed = memo
+            .get_or_fetch(ApiSource::CrossRef, "doi", "10.1000/x", async {
+                Err(ValidatorError::RateLimited)
+            })
+            .await;
+        let retried = memo
+            .get_or_fetch(ApiSource::CrossRef, "doi", "10.1000/x", async { Ok(7) })
+            .await;
+
+        assert!(failed.is_err());
+        assert_eq!(retried.unwrap(), 7);
+    }
+}
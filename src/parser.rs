@@ -1,7 +1,11 @@
-use crate::entry::Entry;
-use biblatex::{Bibliography, ChunksExt};
-use std::fs;
+use crate::entry::{Entry, SourceLocation};
+use crate::lint::{normalize_isbn, normalize_issn};
+use biblatex::{Bibliography, ChunksExt, RawBibliography};
+use std::collections::{BTreeMap, HashMap};
+use std::fs::{self, File};
+use std::io::{BufRead, BufReader};
 use std::path::Path;
+use std::sync::Arc;
 use thiserror::Error;
 
 #[derive(Error, Debug)]
@@ -15,11 +19,37 @@ pub enum ParseError {
 /// Parse a .bib file and return normalized entries
 pub fn parse_bib_file(path: &Path) -> Result<Vec<Entry>, ParseError> {
     let content = fs::read_to_string(path)?;
-    parse_bib_string(&content)
+    let mut entries = parse_bib_string(&content)?;
+    set_file(&mut entries, Arc::from(path));
+    Ok(entries)
+}
+
+/// Record the file entries were parsed from in their locations
+fn set_file(entries: &mut [Entry], file: Arc<Path>) {
+    for location in entries.iter_mut().filter_map(|e| e.location.as_mut()) {
+        location.file = Some(Arc::clone(&file));
+    }
+}
+
+/// Parse a bibliography file by extension: CSL-JSON (`.json`), CSL YAML (`.yaml`,
+/// `.yml`), formatted references (`.bbl`, or the `thebibliography` of a `.tex`), or
+/// BibTeX/BibLaTeX for anything else
+pub fn parse_file(path: &Path) -> Result<Vec<Entry>, ParseError> {
+    let extension = path.extension().and_then(|e| e.to_str()).unwrap_or("");
+    match extension.to_ascii_lowercase().as_str() {
+        "json" => crate::csl::parse_csl_json(&fs::read_to_string(path)?),
+        "yaml" | "yml" => crate::csl::parse_csl_yaml(&fs::read_to_string(path)?),
+        "bbl" | "tex" => crate::bbl::parse_bbl(&fs::read_to_string(path)?),
+        _ => parse_bib_file(path),
+    }
 }
 
 /// Parse a BibTeX string and return normalized entries
 pub fn parse_bib_string(content: &str) -> Result<Vec<Entry>, ParseError> {
+    let raw = RawBibliography::parse(content).map_err(|e| ParseError::ParseError(e.to_string()))?;
+    let mut raw_fields = extract_raw_fields(content, &raw);
+    let mut lines = entry_lines(content, &raw);
+
     let bibliography =
         Bibliography::parse(content).map_err(|e| ParseError::ParseError(e.to_string()))?;
 
@@ -30,10 +60,14 @@ pub fn parse_bib_string(content: &str) -> Result<Vec<Entry>, ParseError> {
         let entry_type = format!("{:?}", bib_entry.entry_type).to_lowercase();
 
         let mut entry = Entry::new(key, entry_type);
+        entry.fields = raw_fields.remove(entry.key.as_str()).unwrap_or_default();
+        entry.location = lines
+            .remove(entry.key.as_str())
+            .map(|line| SourceLocation { file: None, line });
 
         // Extract title
         if let Ok(title_chunks) = bib_entry.title() {
-            entry.title = Some(title_chunks.format_verbatim());
+            entry.title = Some(title_chunks.format_verbatim().into());
         }
 
         // Extract authors
@@ -52,7 +86,7 @@ pub fn parse_bib_string(content: &str) -> Result<Vec<Entry>, ParseError> {
                     if !person.suffix.is_empty() {
                         parts.push(person.suffix.as_str());
                     }
-                    parts.join(" ")
+                    parts.join(" ").into()
                 })
                 .collect();
         }
@@ -73,9 +107,9 @@ pub fn parse_bib_string(content: &str) -> Result<Vec<Entry>, ParseError> {
 
         // Extract venue (journal or booktitle)
         if let Ok(journal) = bib_entry.journal() {
-            entry.venue = Some(journal.format_verbatim());
+            entry.venue = Some(journal.format_verbatim().into());
         } else if let Ok(booktitle) = bib_entry.book_title() {
-            entry.venue = Some(booktitle.format_verbatim());
+            entry.venue = Some(booktitle.format_verbatim().into());
         }
 
         // Extract DOI
@@ -90,6 +124,42 @@ pub fn parse_bib_string(content: &str) -> Result<Vec<Entry>, ParseError> {
             }
         }
 
+        // Extract PubMed ID from the pmid field or a PubMed eprint
+        entry.pmid = entry
+            .field("pmid")
+            .or_else(|| {
+                entry
+                    .field("eprinttype")
+                    .filter(|t| t.trim().eq_ignore_ascii_case("pubmed"))
+                    .and(entry.field("eprint"))
+            })
+            .map(|id| id.trim().to_string())
+            .filter(|id| is_pmid(id));
+
+        // Extract PubMed Central ID from the pmcid or pmc field
+        entry.pmcid = entry
+            .field("pmcid")
+            .or_else(|| entry.field("pmc"))
+            .and_then(normalize_pmcid);
+
+        // Extract MathSciNet review number from the mrnumber field
+        entry.mr_number = entry.field("mrnumber").and_then(normalize_mr_number);
+
+        // Extract HAL ID from the hal_id field or a HAL eprint
+        entry.hal_id = entry
+            .field("hal_id")
+            .or_else(|| {
+                entry
+                    .field("eprinttype")
+                    .filter(|t| t.trim().eq_ignore_ascii_case("hal"))
+                    .and(entry.field("eprint"))
+            })
+            .and_then(normalize_hal_id);
+
+        // Extract the first ISBN and ISSN listed
+        entry.isbn = entry.field("isbn").and_then(normalize_isbn);
+        entry.issn = entry.field("issn").and_then(normalize_issn);
+
         // Extract URL
         if let Ok(url_str) = bib_entry.url() {
             entry.url = Some(url_str.clone());
@@ -107,6 +177,28 @@ pub fn parse_bib_string(content: &str) -> Result<Vec<Entry>, ParseError> {
                     entry.doi = Some(doi);
                 }
             }
+
+            // Try to extract PubMed ID from URL if not already set
+            if entry.pmid.is_none() {
+                entry.pmid = extract_pmid_from_url(&url_str);
+            }
+
+            // Try to extract PubMed Central ID from URL if not already set
+            if entry.pmcid.is_none() {
+                entry.pmcid = extract_pmcid_from_url(&url_str);
+            }
+
+            // Try to extract HAL ID from URL if not already set
+            if entry.hal_id.is_none() {
+                entry.hal_id = extract_hal_id_from_url(&url_str);
+            }
+
+            // Try to extract MR number from a MathSciNet URL if not already set
+            if entry.mr_number.is_none() && url_str.contains("mathscinet") {
+                entry.mr_number = url_str
+                    .split_once("mr=")
+                    .and_then(|(_, mr)| normalize_mr_number(mr));
+            }
         }
 
         entries.push(entry);
@@ -115,8 +207,240 @@ pub fn parse_bib_string(content: &str) -> Result<Vec<Entry>, ParseError> {
     Ok(entries)
 }
 
+/// Bytes of source gathered before a chunk is cut in streaming mode
+const STREAM_CHUNK_BYTES: usize = 256 * 1024;
+
+/// Incremental parser for large .bib files, yielding the entries of one chunk at a time.
+///
+/// Chunks are cut only where a new entry starts at brace depth zero, so no entry is
+/// split. `@string` definitions are carried into every later chunk; `crossref` parents
+/// are only resolved when they land in the same chunk as their children.
+pub struct BibStream<R> {
+    reader: R,
+    /// The file being read, recorded in entry locations
+    file: Option<Arc<Path>>,
+    /// Lines read before the current chunk
+    chunk_start_line: usize,
+    /// Lines read so far
+    lines_read: usize,
+    /// `@string` definitions seen in earlier chunks
+    strings: String,
+    /// `@string` definitions seen so far, including the current chunk
+    pending_strings: String,
+    /// Source of the current chunk
+    pending: String,
+    /// Brace depth at the end of `pending`
+    depth: i64,
+    /// Whether an `@` has been read at depth zero whose entry is not yet closed
+    in_entry: bool,
+    /// Whether `pending` ends inside a `"..."` field value
+    in_quotes: bool,
+    /// Whether the entry being read is an `@string` definition
+    in_string: bool,
+    chunk_bytes: usize,
+    done: bool,
+}
+
+impl BibStream<BufReader<File>> {
+    pub fn open(path: &Path) -> Result<Self, ParseError> {
+        let mut stream = Self::new(BufReader::new(File::open(path)?));
+        stream.file = Some(Arc::from(path));
+        Ok(stream)
+    }
+}
+
+impl<R: BufRead> BibStream<R> {
+    pub fn new(reader: R) -> Self {
+        Self {
+            reader,
+            file: None,
+            chunk_start_line: 0,
+            lines_read: 0,
+            strings: String::new(),
+            pending_strings: String::new(),
+            pending: String::new(),
+            depth: 0,
+            in_entry: false,
+            in_quotes: false,
+            in_string: false,
+            chunk_bytes: STREAM_CHUNK_BYTES,
+            done: false,
+        }
+    }
+
+    /// Cut chunks once they reach roughly `bytes` of source instead of the default
+    pub fn with_chunk_bytes(mut self, bytes: usize) -> Self {
+        self.chunk_bytes = bytes;
+        self
+    }
+
+    /// Update the brace depth over a line of BibTeX source.
+    ///
+    /// Text outside entries, `%` comments between fields, and braces in `"..."` values
+    /// are skipped, so a stray brace there cannot hold the depth above zero.
+    fn track_depth(&mut self, line: &str) {
+        let mut escaped = false;
+        for c in line.chars() {
+            let between_fields = self.depth == 1 && !self.in_quotes;
+            match c {
+                _ if escaped => {}
+                '%' if self.depth == 0 || between_fields => break,
+                '@' if self.depth == 0 => self.in_entry = true,
+                _ if !self.in_entry => {}
+                '"' if self.depth == 1 => self.in_quotes = !self.in_quotes,
+                '{' if !self.in_quotes => self.depth += 1,
+                '}' if !self.in_quotes => {
+                    self.depth -= 1;
+                    if self.depth == 0 {
+                        self.in_entry = false;
+                    }
+                }
+                _ => {}
+            }
+            escaped = c == '\\' && !escaped;
+        }
+    }
+
+    /// Parse the current chunk with the `@string` definitions that precede it
+    fn take_chunk(&mut self) -> Result<Vec<Entry>, ParseError> {
+        let chunk = std::mem::take(&mut self.pending);
+        let source = format!("{}{}", self.strings, chunk);
+        // Entry lines count from the start of `source`, whose carried-over `@string`
+        // definitions come before the chunk's first line
+        let prefix_lines = self.strings.matches('\n').count();
+        let chunk_start_line = self.chunk_start_line;
+        self.chunk_start_line = self.lines_read;
+        self.strings.clone_from(&self.pending_strings);
+
+        let mut entries = parse_bib_string(&source)?;
+        for location in entries.iter_mut().filter_map(|e| e.location.as_mut()) {
+            location.line = (location.line + chunk_start_line).saturating_sub(prefix_lines);
+        }
+        if let Some(file) = &self.file {
+            set_file(&mut entries, Arc::clone(file));
+        }
+        Ok(entries)
+    }
+}
+
+impl<R: BufRead> Iterator for BibStream<R> {
+    type Item = Result<Vec<Entry>, ParseError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            let mut line = String::new();
+            match self.reader.read_line(&mut line) {
+                Ok(0) => {
+                    if self.done || self.pending.trim().is_empty() {
+                        self.done = true;
+                        return None;
+                    }
+                    self.done = true;
+                    return Some(self.take_chunk());
+                }
+                Ok(_) => {}
+                Err(e) => {
+                    self.done = true;
+                    return Some(Err(e.into()));
+                }
+            }
+
+            let starts_entry = self.depth == 0 && line.trim_start().starts_with('@');
+            let chunk = if starts_entry && self.pending.len() >= self.chunk_bytes {
+                Some(self.take_chunk())
+            } else {
+                None
+            };
+
+            if starts_entry {
+                self.in_string = line.trim_start()[1..]
+                    .trim_start()
+                    .to_lowercase()
+                    .starts_with("string");
+            }
+            if self.in_string {
+                self.pending_strings.push_str(&line);
+            }
+            self.track_depth(&line);
+            self.pending.push_str(&line);
+            self.lines_read += 1;
+
+            if chunk.is_some() {
+                return chunk;
+            }
+        }
+    }
+}
+
+/// Collect the source text of every field, keyed by citation key then field name
+fn extract_raw_fields<'s>(
+    content: &str,
+    raw: &RawBibliography<'s>,
+) -> HashMap<&'s str, BTreeMap<String, String>> {
+    raw.entries
+        .iter()
+        .map(|raw_entry| {
+            let fields = raw_entry
+                .v
+                .fields
+                .iter()
+                .map(|pair| {
+                    let value = &content[pair.value.span.clone()];
+                    (pair.key.v.to_lowercase(), strip_delimiters(value).to_string())
+                })
+                .collect();
+            (raw_entry.v.key.v, fields)
+        })
+        .collect()
+}
+
+/// The 1-based line each entry starts on, keyed by citation key
+fn entry_lines<'s>(content: &str, raw: &RawBibliography<'s>) -> HashMap<&'s str, usize> {
+    let line_starts: Vec<usize> = std::iter::once(0)
+        .chain(content.match_indices('\n').map(|(i, _)| i + 1))
+        .collect();
+    raw.entries
+        .iter()
+        .map(|raw_entry| {
+            let line = line_starts.partition_point(|&start| start <= raw_entry.span.start);
+            (raw_entry.v.key.v, line)
+        })
+        .collect()
+}
+
+/// Remove one layer of enclosing braces or quotes from a raw field value
+fn strip_delimiters(value: &str) -> &str {
+    let value = value.trim();
+    let inner = value
+        .strip_prefix('{')
+        .and_then(|v| v.strip_suffix('}'))
+        .or_else(|| value.strip_prefix('"').and_then(|v| v.strip_suffix('"')));
+
+    match inner {
+        // Only strip if the delimiters wrap the whole value, not e.g. `{a} # {b}`
+        Some(inner) if is_balanced(inner) => inner,
+        _ => value,
+    }
+}
+
+/// Check that braces in a string are balanced
+fn is_balanced(s: &str) -> bool {
+    let mut depth = 0i32;
+    for c in s.chars() {
+        match c {
+            '{' => depth += 1,
+            '}' => depth -= 1,
+            _ => {}
+        }
+        if depth < 0 {
+            return false;
+        }
+    }
+    depth == 0
+}
+
 /// Extract a 4-digit year from a string
-fn extract_year_from_string(s: &str) -> Option<i32> {
+pub(crate) fn extract_year_from_string(s: &str) -> Option<i32> {
     // Find a 4-digit sequence that looks like a year (1900-2099)
     let mut chars = s.chars().peekable();
     while let Some(c) = chars.next() {
@@ -142,7 +466,7 @@ fn extract_year_from_string(s: &str) -> Option<i32> {
 }
 
 /// Check if a string looks like an arXiv ID
-fn is_arxiv_id(s: &str) -> bool {
+pub(crate) fn is_arxiv_id(s: &str) -> bool {
     // Old format: hep-th/9901001
     // New format: 2301.12345 or 2301.12345v1
     let s = s.trim();
@@ -164,7 +488,7 @@ fn is_arxiv_id(s: &str) -> bool {
 }
 
 /// Extract arXiv ID from a URL
-fn extract_arxiv_from_url(url: &str) -> Option<String> {
+pub(crate) fn extract_arxiv_from_url(url: &str) -> Option<String> {
     // https://arxiv.org/abs/2301.12345
     // https://arxiv.org/pdf/2301.12345.pdf
     if url.contains("arxiv.org") {
@@ -187,8 +511,95 @@ fn extract_arxiv_from_url(url: &str) -> Option<String> {
     None
 }
 
+/// Check if a string looks like a PubMed ID
+pub(crate) fn is_pmid(s: &str) -> bool {
+    !s.is_empty() && s.len() <= 9 && s.chars().all(|c| c.is_ascii_digit())
+}
+
+/// Extract PubMed ID from a URL
+pub(crate) fn extract_pmid_from_url(url: &str) -> Option<String> {
+    // https://pubmed.ncbi.nlm.nih.gov/31452104/
+    // https://www.ncbi.nlm.nih.gov/pubmed/31452104
+    let patterns = ["pubmed.ncbi.nlm.nih.gov/", "ncbi.nlm.nih.gov/pubmed/"];
+    for pattern in patterns {
+        if let Some(idx) = url.find(pattern) {
+            let id = url[idx + pattern.len()..]
+                .split(|c: char| !c.is_ascii_digit())
+                .next()?;
+            if is_pmid(id) {
+                return Some(id.to_string());
+            }
+        }
+    }
+    None
+}
+
+/// Normalize a PubMed Central ID to its `PMC` form ("pmc123" or "123" -> "PMC123")
+pub(crate) fn normalize_pmcid(s: &str) -> Option<String> {
+    let s = s.trim();
+    let digits = match s.get(..3) {
+        Some(prefix) if prefix.eq_ignore_ascii_case("pmc") => &s[3..],
+        _ => s,
+    };
+    is_pmid(digits).then(|| format!("PMC{}", digits))
+}
+
+/// Normalize a MathSciNet review number to its padded `MR` form. MathSciNet's own
+/// BibTeX writes the bare number, sometimes followed by the old volume-and-item
+/// reference ("31344 (12,89e)" -> "MR0031344").
+pub(crate) fn normalize_mr_number(s: &str) -> Option<String> {
+    let s = s.trim();
+    let s = match s.get(..2) {
+        Some(prefix) if prefix.eq_ignore_ascii_case("mr") => &s[2..],
+        _ => s,
+    };
+    let digits = s.split(|c: char| !c.is_ascii_digit()).next()?;
+    let number: u64 = digits.parse().ok()?;
+    Some(format!("MR{:07}", number))
+}
+
+/// Normalize a HAL ID, dropping any version suffix ("hal-01234567v2" -> "hal-01234567").
+/// The prefix names the portal: hal, tel for theses, inria, halshs, and so on.
+pub(crate) fn normalize_hal_id(s: &str) -> Option<String> {
+    let s = s.trim().to_lowercase();
+    let (portal, rest) = s.split_once('-')?;
+    let number = rest.split('v').next()?;
+    let valid = !portal.is_empty()
+        && portal.chars().all(|c| c.is_ascii_lowercase())
+        && number.len() == 8
+        && number.chars().all(|c| c.is_ascii_digit());
+    valid.then(|| format!("{}-{}", portal, number))
+}
+
+/// Extract HAL ID from a URL on hal.science or one of the HAL portals
+pub(crate) fn extract_hal_id_from_url(url: &str) -> Option<String> {
+    // https://hal.science/hal-01234567v2/document
+    // https://inria.hal.science/hal-01234567
+    // https://hal.archives-ouvertes.fr/tel-01234567
+    let rest = url.split_once("://").map_or(url, |(_, rest)| rest);
+    let (host, path) = rest.split_once('/')?;
+    if !host.contains("hal.") && !host.starts_with("hal") {
+        return None;
+    }
+    normalize_hal_id(path.split(['/', '?', '#']).next()?)
+}
+
+/// Extract PubMed Central ID from a URL
+pub(crate) fn extract_pmcid_from_url(url: &str) -> Option<String> {
+    // https://pmc.ncbi.nlm.nih.gov/articles/PMC6712345/
+    // https://www.ncbi.nlm.nih.gov/pmc/articles/PMC6712345/
+    if !url.contains("ncbi.nlm.nih.gov") {
+        return None;
+    }
+    let idx = url.find("/articles/PMC")?;
+    let id = url[idx + "/articles/".len()..]
+        .split(|c: char| !c.is_ascii_alphanumeric())
+        .next()?;
+    normalize_pmcid(id)
+}
+
 /// Extract DOI from a URL
-fn extract_doi_from_url(url: &str) -> Option<String> {
+pub(crate) fn extract_doi_from_url(url: &str) -> Option<String> {
     // https://doi.org/10.1234/example
     // https://dx.doi.org/10.1234/example
     if url.contains("doi.org/") {
@@ -205,6 +616,66 @@ fn extract_doi_from_url(url: &str) -> Option<String> {
 mod tests {
     use super::*;
 
+    #[test]
+    fn stream_yields_whole_entries_and_carries_strings() {
+        let source = r#"@string{jmlr = "Journal of Machine Learning Research"}
+
+@article{a,
+  title = {First {Paper}},
+  journal = jmlr,
+  year = {2020}
+}
+
+@article{b,
+  title = {Second},
+  journal = jmlr,
+  year = {2021}
+}
+@article{c, title = {Third}, year = {2022}}
+"#;
+        let chunks: Vec<_> = BibStream::new(source.as_bytes())
+            .with_chunk_bytes(1)
+            .collect::<Result<_, _>>()
+            .unwrap();
+
+        let keys: Vec<Vec<_>> = chunks
+            .iter()
+            .map(|chunk| chunk.iter().map(|e| e.key.as_str()).collect())
+            .collect();
+        assert_eq!(keys, vec![vec![], vec!["a"], vec!["b"], vec!["c"]]);
+        // Lines count from the top of the file, not of each chunk
+        let lines: Vec<_> = chunks
+            .iter()
+            .flatten()
+            .map(|e| e.location.as_ref().map(|l| l.line))
+            .collect();
+        assert_eq!(lines, vec![Some(3), Some(9), Some(14)]);
+        assert_eq!(
+            chunks[2][0].venue.as_deref(),
+            Some("Journal of Machine Learning Research")
+        );
+    }
+
+    #[test]
+    fn stray_braces_in_comments_do_not_stop_chunking() {
+        let source = r#"% old {draft
+@article{a,
+  title = "First Paper",
+  year = {2020}
+}
+% b = {unfinished,
+@comment{b, title = {Second}} % }}
+@article{c, url = {https://example.org/a%20b}, year = {2021}}
+@article{d, title = {Fourth}, year = {2022}}
+"#;
+        let keys: Vec<Vec<_>> = BibStream::new(source.as_bytes())
+            .with_chunk_bytes(1)
+            .map(|chunk| chunk.unwrap().into_iter().map(|e| e.key).collect())
+            .collect();
+
+        assert_eq!(keys, vec![vec![], vec!["a"], vec![], vec!["c"], vec!["d"]]);
+    }
+
     #[test]
     fn test_is_arxiv_id() {
         assert!(is_arxiv_id("2301.12345"));
@@ -227,6 +698,67 @@ mod tests {
         assert_eq!(extract_arxiv_from_url("https://example.com"), None);
     }
 
+    #[test]
+    fn test_pmid_sources() {
+        let bib = r#"
+            @article{field, title = {A}, pmid = {31452104}}
+            @article{eprint, title = {B}, eprint = {29364536}, eprinttype = {pubmed}}
+            @article{url, title = {C}, url = {https://pubmed.ncbi.nlm.nih.gov/12345678/}}
+            @article{arxiv, title = {D}, eprint = {2301.12345}}
+        "#;
+
+        let entries = parse_bib_string(bib).unwrap();
+        let pmids: Vec<_> = entries.iter().map(|e| e.pmid.as_deref()).collect();
+        assert_eq!(
+            pmids,
+            [Some("31452104"), Some("29364536"), Some("12345678"), None]
+        );
+    }
+
+    #[test]
+    fn test_pmcid_sources() {
+        assert_eq!(normalize_pmcid("pmc6712345"), Some("PMC6712345".to_string()));
+        assert_eq!(normalize_pmcid("6712345"), Some("PMC6712345".to_string()));
+        assert_eq!(normalize_pmcid("PMC"), None);
+        assert_eq!(
+            extract_pmcid_from_url("https://www.ncbi.nlm.nih.gov/pmc/articles/PMC6712345/"),
+            Some("PMC6712345".to_string())
+        );
+
+        let entries = parse_bib_string("@article{a, title = {A}, pmc = {PMC6712345}}").unwrap();
+        assert_eq!(entries[0].pmcid.as_deref(), Some("PMC6712345"));
+    }
+
+    #[test]
+    fn test_mr_number_sources() {
+        assert_eq!(normalize_mr_number("31344 (12,89e)"), Some("MR0031344".to_string()));
+        assert_eq!(normalize_mr_number("MR1234567"), Some("MR1234567".to_string()));
+        assert_eq!(normalize_mr_number("none"), None);
+
+        let bib = r#"
+            @article{a, title = {A}, mrnumber = {2345678}}
+            @article{b, title = {B}, url = {https://mathscinet.ams.org/mathscinet-getitem?mr=31344}}
+        "#;
+        let entries = parse_bib_string(bib).unwrap();
+        assert_eq!(entries[0].mr_number.as_deref(), Some("MR2345678"));
+        assert_eq!(entries[1].mr_number.as_deref(), Some("MR0031344"));
+    }
+
+    #[test]
+    fn test_hal_id_sources() {
+        assert_eq!(normalize_hal_id("hal-01234567v2"), Some("hal-01234567".to_string()));
+        assert_eq!(normalize_hal_id("hal-123"), None);
+        assert_eq!(
+            extract_hal_id_from_url("https://inria.hal.science/tel-01234567v1/document"),
+            Some("tel-01234567".to_string())
+        );
+        assert_eq!(extract_hal_id_from_url("https://example.com/hal-01234567"), None);
+
+        let bib = "@article{a, title = {A}, eprint = {hal-01234567}, eprinttype = {HAL}}";
+        let entries = parse_bib_string(bib).unwrap();
+        assert_eq!(entries[0].hal_id.as_deref(), Some("hal-01234567"));
+    }
+
     #[test]
     fn test_parse_simple_bib() {
         let bib = r#"
@@ -242,7 +774,16 @@ mod tests {
         let entries = parse_bib_string(bib).unwrap();
         assert_eq!(entries.len(), 1);
         assert_eq!(entries[0].key, "smith2021");
-        assert_eq!(entries[0].title, Some("A Great Paper".to_string()));
+        assert_eq!(entries[0].title.as_deref(), Some("A Great Paper"));
         assert_eq!(entries[0].year, Some(2021));
+        assert_eq!(entries[0].field("doi"), Some("10.1234/example"));
+    }
+
+    #[test]
+    fn test_strip_delimiters() {
+        assert_eq!(strip_delimiters("{The {BERT} Model}"), "The {BERT} Model");
+        assert_eq!(strip_delimiters("\"quoted\""), "quoted");
+        assert_eq!(strip_delimiters("{a} # {b}"), "{a} # {b}");
+        assert_eq!(strip_delimiters("2021"), "2021");
     }
 }
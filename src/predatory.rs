@@ -0,0 +1,148 @@
+//! Lists of predatory journals and publishers, for flagging citations of them
+
+use crate::entry::{normalize_string, Discrepancy, DiscrepancyField, Entry, Severity};
+use crate::lint::normalize_doi;
+use std::path::Path;
+use thiserror::Error;
+
+/// List shipped with bibval; see the file for its format
+const BUNDLED_LIST: &str = include_str!("../data/predatory.txt");
+
+#[derive(Error, Debug)]
+pub enum PredatoryListError {
+    #[error("Failed to read predatory list: {0}")]
+    Io(#[from] std::io::Error),
+    #[error("Line {line}: expected `publisher:`, `journal:` or `doi:`, found '{text}'")]
+    Syntax { line: usize, text: String },
+}
+
+/// Known predatory journals, publishers and DOI prefixes
+#[derive(Debug, Clone, Default)]
+pub struct PredatoryList {
+    /// Normalized journal names
+    journals: Vec<String>,
+    /// Normalized publisher names
+    publishers: Vec<String>,
+    /// DOI prefixes such as "10.4236"
+    doi_prefixes: Vec<String>,
+}
+
+impl PredatoryList {
+    /// The list bundled with bibval
+    pub fn bundled() -> Self {
+        Self::parse(BUNDLED_LIST).expect("bundled predatory list is well-formed")
+    }
+
+    /// Parse a list of `publisher: NAME`, `journal: NAME` and `doi: PREFIX` lines;
+    /// blank lines and lines starting with `#` are skipped
+    pub fn parse(content: &str) -> Result<Self, PredatoryListError> {
+        let mut list = Self::default();
+        for (idx, line) in content.lines().enumerate() {
+            let line = line.trim();
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+
+            let syntax_error = || PredatoryListError::Syntax {
+                line: idx + 1,
+                text: line.to_string(),
+            };
+            let (kind, value) = line.split_once(':').ok_or_else(syntax_error)?;
+            let value = value.trim();
+            match kind.trim().to_lowercase().as_str() {
+                "journal" => list.journals.push(normalize_string(value)),
+                "publisher" => list.publishers.push(normalize_string(value)),
+                "doi" => list
+                    .doi_prefixes
+                    .push(normalize_doi(value).trim_end_matches('/').to_string()),
+                _ => return Err(syntax_error()),
+            }
+        }
+        Ok(list)
+    }
+
+    /// Read a list from a file
+    pub fn load(path: &Path) -> Result<Self, PredatoryListError> {
+        Self::parse(&std::fs::read_to_string(path)?)
+    }
+
+    /// Add the entries of another list to this one
+    pub fn extend(&mut self, other: PredatoryList) {
+        self.journals.extend(other.journals);
+        self.publishers.extend(other.publishers);
+        self.doi_prefixes.extend(other.doi_prefixes);
+    }
+
+    /// Flag an entry whose journal, publisher or DOI prefix is on the list
+    pub fn check(&self, entry: &Entry) -> Option<Discrepancy> {
+        let journal = entry.field("journal").or(entry.field("journaltitle"));
+
+        let (value, message) = if let Some(journal) = listed(&self.journals, journal) {
+            (
+                journal.to_string(),
+                "Journal is on the list of predatory journals",
+            )
+        } else if let Some(publisher) = listed(&self.publishers, entry.field("publisher")) {
+            (
+                publisher.to_string(),
+                "Publisher is on the list of predatory publishers",
+            )
+        } else {
+            let doi = normalize_doi(entry.doi.as_deref()?);
+            let prefix = doi.split('/').next()?;
+            if !self.doi_prefixes.iter().any(|p| p == prefix) {
+                return None;
+            }
+            (
+                doi.clone(),
+                "DOI belongs to a publisher on the list of predatory publishers",
+            )
+        };
+
+        Some(Discrepancy {
+            field: DiscrepancyField::Predatory,
+            severity: Severity::Warning,
+            local_value: value,
+            remote_value: String::new(),
+            message: message.to_string(),
+        })
+    }
+}
+
+/// The value, if its normalized form is one of `names`
+fn listed<'a>(names: &[String], value: Option<&'a str>) -> Option<&'a str> {
+    value.filter(|value| names.contains(&normalize_string(value)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn journals_publishers_and_doi_prefixes_are_matched() {
+        let list = PredatoryList::bundled();
+
+        let mut entry = Entry::new("a".to_string(), "article".to_string());
+        entry.fields.insert(
+            "publisher".to_string(),
+            "Scientific Research Publishing".to_string(),
+        );
+        assert!(list.check(&entry).unwrap().message.contains("Publisher"));
+
+        let mut entry = Entry::new("b".to_string(), "article".to_string());
+        entry.doi = Some("https://doi.org/10.4236/jcc.2020.81001".to_string());
+        assert!(list.check(&entry).unwrap().message.contains("DOI"));
+
+        entry.doi = Some("10.1145/3292500.3330701".to_string());
+        assert!(list.check(&entry).is_none());
+    }
+
+    #[test]
+    fn lists_report_the_offending_line() {
+        let list = PredatoryList::parse("# comment\n\njournal: Journal of Stuff\n").unwrap();
+        assert_eq!(list.journals, vec!["journal of stuff"]);
+
+        let err = PredatoryList::parse("journal: A\nbogus line\n").unwrap_err();
+        assert!(matches!(err, PredatoryListError::Syntax { line: 2, .. }));
+    }
+}
@@ -0,0 +1,276 @@
+use crate::entry::normalize_string;
+use strsim::levenshtein;
+
+/// Characters of a known spelling per typo allowed in a misspelling of it, so names
+/// shorter than this ("ACM", "SIAM") are never reported as misspelled
+const CHARS_PER_TYPO: usize = 8;
+
+/// A bundled record describing a publisher and the names it goes by
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Publisher {
+    /// Name to write in the publisher field
+    pub name: &'static str,
+    /// Other spellings, including CrossRef member names
+    pub aliases: &'static [&'static str],
+}
+
+/// Bundled publisher registry
+pub static PUBLISHERS: &[Publisher] = &[
+    Publisher {
+        name: "Springer",
+        aliases: &[
+            "Springer-Verlag",
+            "Springer Verlag",
+            "Springer Berlin Heidelberg",
+            "Springer International Publishing",
+            "Springer Nature",
+            "Springer Nature Switzerland",
+            "Springer Science+Business Media",
+            "Springer Science and Business Media LLC",
+            "Springer US",
+        ],
+    },
+    Publisher {
+        name: "Elsevier",
+        aliases: &[
+            "Elsevier BV",
+            "Elsevier Science",
+            "Elsevier Science Inc.",
+            "Elsevier Ltd",
+        ],
+    },
+    Publisher {
+        name: "IEEE",
+        aliases: &[
+            "Institute of Electrical and Electronics Engineers",
+            "Institute of Electrical and Electronics Engineers (IEEE)",
+            "IEEE Press",
+            "IEEE Computer Society",
+        ],
+    },
+    Publisher {
+        name: "ACM",
+        aliases: &[
+            "Association for Computing Machinery",
+            "Association for Computing Machinery (ACM)",
+            "ACM Press",
+        ],
+    },
+    Publisher {
+        name: "Wiley",
+        aliases: &[
+            "John Wiley & Sons",
+            "John Wiley and Sons",
+            "Wiley-Blackwell",
+            "Wiley Online Library",
+        ],
+    },
+    Publisher {
+        name: "Taylor & Francis",
+        aliases: &[
+            "Taylor and Francis",
+            "Informa UK Limited",
+            "Taylor & Francis Group",
+        ],
+    },
+    Publisher {
+        name: "Cambridge University Press",
+        aliases: &[
+            "Cambridge Univ. Press",
+            "CUP",
+            "Cambridge University Press (CUP)",
+        ],
+    },
+    Publisher {
+        name: "Oxford University Press",
+        aliases: &["Oxford Univ. Press", "OUP", "Oxford University Press (OUP)"],
+    },
+    Publisher {
+        name: "MIT Press",
+        aliases: &["The MIT Press", "MIT Press - Journals"],
+    },
+    Publisher {
+        name: "SIAM",
+        aliases: &[
+            "Society for Industrial and Applied Mathematics",
+            "Society for Industrial & Applied Mathematics (SIAM)",
+        ],
+    },
+    Publisher {
+        name: "American Physical Society",
+        aliases: &["APS", "American Physical Society (APS)"],
+    },
+    Publisher {
+        name: "American Mathematical Society",
+        aliases: &["AMS", "American Mathematical Society (AMS)"],
+    },
+    Publisher {
+        name: "Association for Computational Linguistics",
+        aliases: &["ACL"],
+    },
+    Publisher {
+        name: "Curran Associates",
+        aliases: &["Curran Associates, Inc.", "Curran Associates Inc."],
+    },
+    Publisher {
+        name: "Morgan Kaufmann",
+        aliases: &[
+            "Morgan Kaufmann Publishers",
+            "Morgan Kaufmann Publishers Inc.",
+        ],
+    },
+    Publisher {
+        name: "PMLR",
+        aliases: &["Proceedings of Machine Learning Research"],
+    },
+    Publisher {
+        name: "O'Reilly Media",
+        aliases: &["O'Reilly", "O'Reilly Media, Inc."],
+    },
+    Publisher {
+        name: "Addison-Wesley",
+        aliases: &[
+            "Addison Wesley",
+            "Addison-Wesley Professional",
+            "Addison-Wesley Longman",
+        ],
+    },
+    Publisher {
+        name: "Princeton University Press",
+        aliases: &["Princeton Univ. Press"],
+    },
+    Publisher {
+        name: "De Gruyter",
+        aliases: &["Walter de Gruyter GmbH", "Walter de Gruyter"],
+    },
+    Publisher {
+        name: "SAGE",
+        aliases: &["SAGE Publications", "Sage Publications"],
+    },
+    Publisher {
+        name: "Frontiers",
+        aliases: &["Frontiers Media SA", "Frontiers Media S.A."],
+    },
+    Publisher {
+        name: "MDPI",
+        aliases: &["MDPI AG", "Multidisciplinary Digital Publishing Institute"],
+    },
+    Publisher {
+        name: "PLOS",
+        aliases: &[
+            "Public Library of Science",
+            "Public Library of Science (PLoS)",
+            "PLoS",
+        ],
+    },
+    Publisher {
+        name: "IOP Publishing",
+        aliases: &["Institute of Physics Publishing", "IOP"],
+    },
+];
+
+/// How a publisher field relates to the registry
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PublisherMatch {
+    /// The registry's own spelling
+    Canonical(&'static Publisher),
+    /// Another known spelling of a registered publisher, possibly followed by more words
+    /// ("Oxford University Press, USA", "IEEE Computer Society Press")
+    Alias(&'static Publisher),
+    /// Not a known spelling, but a few typos away from one with as many words
+    Misspelled(&'static Publisher),
+}
+
+impl PublisherMatch {
+    pub fn publisher(&self) -> &'static Publisher {
+        match self {
+            PublisherMatch::Canonical(p)
+            | PublisherMatch::Alias(p)
+            | PublisherMatch::Misspelled(p) => p,
+        }
+    }
+}
+
+/// Find the registered publisher a publisher field refers to
+pub fn classify_publisher(text: &str) -> Option<PublisherMatch> {
+    let normalized = normalize_string(text);
+    if normalized.is_empty() {
+        return None;
+    }
+
+    for publisher in PUBLISHERS {
+        if normalize_string(publisher.name) == normalized {
+            return Some(PublisherMatch::Canonical(publisher));
+        }
+        if publisher
+            .aliases
+            .iter()
+            .any(|a| normalize_string(a) == normalized)
+        {
+            return Some(PublisherMatch::Alias(publisher));
+        }
+    }
+
+    let spellings = || {
+        PUBLISHERS.iter().flat_map(|p| {
+            std::iter::once(p.name)
+                .chain(p.aliases.iter().copied())
+                .map(move |s| (p, normalize_string(s)))
+        })
+    };
+
+    // A known name with a place, imprint or company form after it
+    let extended = spellings()
+        .filter(|(_, spelling)| {
+            normalized
+                .strip_prefix(spelling.as_str())
+                .is_some_and(|rest| rest.starts_with(' '))
+        })
+        .max_by_key(|(_, spelling)| spelling.len());
+    if let Some((publisher, _)) = extended {
+        return Some(PublisherMatch::Alias(publisher));
+    }
+
+    let words = normalized.split_whitespace().count();
+    spellings()
+        .filter(|(_, spelling)| spelling.split_whitespace().count() == words)
+        .map(|(p, spelling)| (p, levenshtein(&spelling, &normalized), spelling.len()))
+        .filter(|(_, typos, len)| *typos <= len / CHARS_PER_TYPO)
+        .min_by_key(|(_, typos, _)| *typos)
+        .map(|(p, _, _)| PublisherMatch::Misspelled(p))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn publisher_spellings_are_classified() {
+        let springer = classify_publisher("Springer-Verlag").unwrap();
+        assert!(matches!(springer, PublisherMatch::Alias(_)));
+        assert_eq!(springer.publisher().name, "Springer");
+
+        assert!(matches!(
+            classify_publisher("IEEE"),
+            Some(PublisherMatch::Canonical(_))
+        ));
+        assert!(matches!(
+            classify_publisher("Elsevir"),
+            Some(PublisherMatch::Misspelled(p)) if p.name == "Elsevier"
+        ));
+        assert!(classify_publisher("Some Small Press").is_none());
+        assert!(classify_publisher("ACS").is_none());
+    }
+
+    #[test]
+    fn known_names_with_more_words_are_aliases() {
+        assert!(matches!(
+            classify_publisher("Oxford University Press, USA"),
+            Some(PublisherMatch::Alias(p)) if p.name == "Oxford University Press"
+        ));
+        assert!(matches!(
+            classify_publisher("IEEE Computer Society Press"),
+            Some(PublisherMatch::Alias(p)) if p.name == "IEEE"
+        ));
+    }
+}
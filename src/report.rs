@@ -1,19 +1,356 @@
-use crate::entry::{ApiSource, Discrepancy, Entry, Severity, ValidationResult};
-use colored::Colorize;
+use crate::entry::{ApiSource, Discrepancy, DiscrepancyField, Entry, Severity, ValidationResult};
+use crate::cache::CacheStats;
+use crate::fusion::clean_entry;
+use crate::score::QualityScore;
+use crate::throttle::ThrottleStats;
+use crate::writer::format_entry;
+use colored::{Color, Colorize};
+use serde::{Deserialize, Serialize};
+use std::cmp::Ordering;
+use std::collections::BTreeMap;
+use std::str::FromStr;
+use std::sync::OnceLock;
+use std::time::Duration;
 
 /// A complete validation report for all entries
 pub struct Report {
     pub entries: Vec<EntryReport>,
+    /// How the run that produced the report went, appended to it when set
+    pub statistics: Option<Statistics>,
+}
+
+/// The serialized form of a [`Report`]
+#[derive(Serialize)]
+pub(crate) struct ReportData<'a> {
+    summary: Summary,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    score: Option<QualityScore>,
+    entries: &'a [EntryReport],
+    #[serde(skip_serializing_if = "Option::is_none")]
+    statistics: Option<&'a Statistics>,
 }
 
 /// Report for a single bibliography entry
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct EntryReport {
     pub entry: Entry,
     pub status: EntryStatus,
     pub validation_results: Vec<ValidationResult>,
+    /// Findings from offline checks that don't depend on any API
+    pub lints: Vec<Discrepancy>,
+    /// Curated record offered as a full-entry replacement
+    #[serde(default)]
+    pub replacement: Option<Replacement>,
+    /// How often the work has been cited, when enrichment asked for it
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub citations: Option<CitationCount>,
+    /// Where the work can be read for free, when Unpaywall (or, when enrichment asked for
+    /// it, OpenAlex) knows of a copy
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub open_access: Option<OpenAccess>,
+    /// The work's official code repository, when enrichment asked for it
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub code: Option<String>,
+    /// The lookups made for the entry and what came of them, when tracing
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub trace: Vec<String>,
+}
+
+/// A citation count reported by one of the validators
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+pub struct CitationCount {
+    pub source: ApiSource,
+    pub count: u64,
+}
+
+/// The best open-access copy of a work, as reported by Unpaywall or OpenAlex
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct OpenAccess {
+    pub url: String,
+    /// The open-access status: gold, green, hybrid or bronze (or diamond, from OpenAlex)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub status: Option<String>,
+}
+
+/// A curated BibTeX record for an entry, carrying the entry's own citation key
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Replacement {
+    pub source: ApiSource,
+    pub bibtex: String,
+}
+
+/// Colors of each severity in the text report
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Theme {
+    pub error: Color,
+    pub warning: Color,
+    pub info: Color,
+}
+
+impl Default for Theme {
+    fn default() -> Self {
+        Self {
+            error: Color::Red,
+            warning: Color::Yellow,
+            info: Color::Blue,
+        }
+    }
+}
+
+impl FromStr for Theme {
+    type Err = String;
+
+    /// Parse overrides of the default colors: "error=magenta,info=bright black"
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut theme = Theme::default();
+        for setting in s.split(',').map(str::trim).filter(|s| !s.is_empty()) {
+            let (severity, color) = setting
+                .split_once('=')
+                .ok_or_else(|| format!("expected SEVERITY=COLOR, got '{}'", setting))?;
+            let color = Color::from_str(color.trim())
+                .map_err(|()| format!("unknown color '{}'", color.trim()))?;
+            match severity.trim() {
+                "error" => theme.error = color,
+                "warning" => theme.warning = color,
+                "info" => theme.info = color,
+                other => {
+                    return Err(format!(
+                        "unknown severity '{}', expected error, warning or info",
+                        other
+                    ))
+                }
+            }
+        }
+        Ok(theme)
+    }
+}
+
+static THEME: OnceLock<Theme> = OnceLock::new();
+
+/// Use `theme` for every report printed from now on; only the first call has an effect
+pub fn set_theme(theme: Theme) {
+    let _ = THEME.set(theme);
+}
+
+fn theme() -> Theme {
+    THEME.get().copied().unwrap_or_default()
+}
+
+/// How to order the entries of a report
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SortOrder {
+    /// Alphabetically by citation key
+    Key,
+    /// Errors first, then failed lookups, warnings, entries not found, and valid entries
+    Severity,
+    /// Least confident match first, with unmatched entries before all others
+    Confidence,
+    /// By file, then by the line each entry is declared at
+    File,
+}
+
+/// How a run went: which validators matched how many entries, how confidently, and what
+/// their requests cost, to help decide which validators are worth enabling
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Statistics {
+    pub sources: Vec<SourceStatistics>,
+    /// Mean confidence of every match, across sources
+    pub average_confidence: Option<f64>,
+    pub cache_hits: u64,
+    pub cache_misses: u64,
+    /// Time since the validator was built
+    pub wall_clock_secs: f64,
+    /// Sum of request latencies across all sources, which overlap in time
+    pub api_secs: f64,
+}
+
+/// One validator's share of a run
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SourceStatistics {
+    pub source: ApiSource,
+    /// Entries the source found a match for
+    pub matched: usize,
+    pub average_confidence: Option<f64>,
+    pub requests: u64,
+    pub rate_limited: u64,
+    /// Requests that failed other than by rate limiting
+    pub errors: u64,
+    pub api_secs: f64,
+}
+
+impl Statistics {
+    pub fn gather(
+        report: &Report,
+        throttles: &[(ApiSource, ThrottleStats)],
+        cache: CacheStats,
+        wall_clock: Duration,
+    ) -> Self {
+        let mut confidences: BTreeMap<String, (ApiSource, Vec<f64>)> = BTreeMap::new();
+        for result in report.entries.iter().flat_map(|e| &e.validation_results) {
+            if result.matched_entry.is_some() {
+                confidences
+                    .entry(result.source.to_string())
+                    .or_insert_with(|| (result.source, Vec::new()))
+                    .1
+                    .push(result.confidence);
+            }
+        }
+        for (source, _) in throttles {
+            confidences
+                .entry(source.to_string())
+                .or_insert_with(|| (*source, Vec::new()));
+        }
+
+        let sources = confidences
+            .into_values()
+            .map(|(source, confidences)| {
+                let throttle = throttles
+                    .iter()
+                    .find(|(s, _)| *s == source)
+                    .map(|(_, stats)| stats.clone())
+                    .unwrap_or_default();
+                SourceStatistics {
+                    source,
+                    matched: confidences.len(),
+                    average_confidence: mean(&confidences),
+                    requests: throttle.requests,
+                    rate_limited: throttle.rate_limited,
+                    errors: throttle.errors,
+                    api_secs: throttle.busy.as_secs_f64(),
+                }
+            })
+            .collect();
+        let all: Vec<f64> = report
+            .entries
+            .iter()
+            .flat_map(|e| &e.validation_results)
+            .filter(|r| r.matched_entry.is_some())
+            .map(|r| r.confidence)
+            .collect();
+
+        Self {
+            sources,
+            average_confidence: mean(&all),
+            cache_hits: cache.hits,
+            cache_misses: cache.misses,
+            wall_clock_secs: wall_clock.as_secs_f64(),
+            api_secs: throttles.iter().map(|(_, s)| s.busy).sum::<Duration>().as_secs_f64(),
+        }
+    }
+
+    /// Print the statistics block that ends the text report
+    fn print(&self, total: usize) {
+        println!("{}", "STATISTICS".bold());
+        if !self.sources.is_empty() {
+            println!(
+                "  {:<18} {:>9} {:>10} {:>8} {:>6} {:>6} {:>9}",
+                "Source", "Matched", "Confidence", "Requests", "429s", "Errors", "API time"
+            );
+        }
+        for s in &self.sources {
+            let errors = if s.errors > 0 {
+                s.errors.to_string().red()
+            } else {
+                s.errors.to_string().normal()
+            };
+            println!(
+                "  {:<18} {:>9} {:>10} {:>8} {:>6} {:>6} {:>8.1}s",
+                s.source.to_string(),
+                format!("{}/{}", s.matched, total),
+                percent(s.average_confidence),
+                s.requests,
+                s.rate_limited,
+                errors,
+                s.api_secs
+            );
+        }
+        println!(
+            "  Average confidence: {}",
+            percent(self.average_confidence)
+        );
+        let cache = CacheStats {
+            hits: self.cache_hits,
+            misses: self.cache_misses,
+        };
+        match cache.hit_rate() {
+            Some(rate) => println!(
+                "  Cache: {} hits, {} misses ({:.0}% hit rate)",
+                cache.hits,
+                cache.misses,
+                rate * 100.0
+            ),
+            None => println!("  Cache: not used"),
+        }
+        println!(
+            "  Time: {:.1}s wall clock, {:.1}s waiting on APIs",
+            self.wall_clock_secs, self.api_secs
+        );
+        println!();
+    }
+}
+
+fn mean(values: &[f64]) -> Option<f64> {
+    match values.len() {
+        0 => None,
+        n => Some(values.iter().sum::<f64>() / n as f64),
+    }
+}
+
+fn percent(value: Option<f64>) -> String {
+    value.map_or("-".to_string(), |v| format!("{:.0}%", v * 100.0))
+}
+
+/// Number of entries with each status
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Summary {
+    pub total: usize,
+    pub ok: usize,
+    pub warnings: usize,
+    pub errors: usize,
+    pub not_found: usize,
+    pub failed: usize,
 }
 
-#[derive(Debug, Clone, PartialEq, Eq)]
+/// Number of findings of each severity, from offline checks and every source
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct FindingCounts {
+    pub error: usize,
+    pub warning: usize,
+    pub info: usize,
+}
+
+/// The aggregate counts `--summary-json` writes, for CI jobs to gate on
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize)]
+pub struct Totals {
+    #[serde(flatten)]
+    pub entries: Summary,
+    pub findings: FindingCounts,
+}
+
+impl Totals {
+    /// Count one more entry report
+    pub fn add(&mut self, entry_report: &EntryReport) {
+        self.entries.total += 1;
+        match entry_report.status {
+            EntryStatus::Ok(_) => self.entries.ok += 1,
+            EntryStatus::Warning => self.entries.warnings += 1,
+            EntryStatus::Error => self.entries.errors += 1,
+            EntryStatus::NotFound => self.entries.not_found += 1,
+            EntryStatus::Failed(_) => self.entries.failed += 1,
+        }
+
+        let discrepancies = entry_report.validation_results.iter().flat_map(|r| &r.discrepancies);
+        for discrepancy in entry_report.lints.iter().chain(discrepancies) {
+            match discrepancy.severity {
+                Severity::Error => self.findings.error += 1,
+                Severity::Warning => self.findings.warning += 1,
+                Severity::Info => self.findings.info += 1,
+            }
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 pub enum EntryStatus {
     /// Entry validated successfully with no issues
     Ok(ApiSource),
@@ -31,6 +368,7 @@ impl Report {
     pub fn new() -> Self {
         Self {
             entries: Vec::new(),
+            statistics: None,
         }
     }
 
@@ -74,30 +412,121 @@ impl Report {
             .count()
     }
 
-    /// Print the report to stdout with colors
-    pub fn print(&self) {
-        println!();
-        println!("{}", "bibval Report".bold());
-        println!("{}", "=".repeat(50));
-        println!();
+    /// Counts of entries by status
+    pub fn summary(&self) -> Summary {
+        Summary {
+            total: self.entries.len(),
+            ok: self.count_ok(),
+            warnings: self.count_warnings(),
+            errors: self.count_errors(),
+            not_found: self.count_not_found(),
+            failed: self.count_failed(),
+        }
+    }
 
-        let total = self.entries.len();
-        let ok = self.count_ok();
-        let warnings = self.count_warnings();
-        let errors = self.count_errors();
-        let not_found = self.count_not_found();
-        let failed = self.count_failed();
+    /// Reorder the entries; entries that compare equal keep their relative order
+    pub fn sort(&mut self, order: SortOrder) {
+        match order {
+            SortOrder::Key => self.entries.sort_by_key(|e| e.entry.key.to_lowercase()),
+            SortOrder::Severity => self.entries.sort_by_key(|e| status_rank(&e.status)),
+            SortOrder::Confidence => self
+                .entries
+                .sort_by(|a, b| best_confidence(a).total_cmp(&best_confidence(b))),
+            SortOrder::File => self.entries.sort_by(|a, b| {
+                let position =
+                    |e: &EntryReport| e.entry.location.as_ref().map(|l| (l.file.clone(), l.line));
+                // Entries without a location go last
+                match (position(a), position(b)) {
+                    (Some(a), Some(b)) => a.cmp(&b),
+                    (Some(_), None) => Ordering::Less,
+                    (None, Some(_)) => Ordering::Greater,
+                    (None, None) => Ordering::Equal,
+                }
+            }),
+        }
+    }
 
-        println!("Processed: {} entries", total);
-        println!(
-            "  {} validated, {} warnings, {} errors, {} failed, {} not found",
-            ok.to_string().green(),
-            warnings.to_string().yellow(),
-            errors.to_string().red(),
-            failed.to_string().red().bold(),
-            not_found.to_string().dimmed()
-        );
-        println!();
+    /// Read a report written by [`Report::to_json`]
+    pub fn from_json(json: &str) -> serde_json::Result<Self> {
+        #[derive(Deserialize)]
+        struct JsonReport {
+            entries: Vec<EntryReport>,
+        }
+
+        let report: JsonReport = serde_json::from_str(json)?;
+        Ok(Self {
+            entries: report.entries,
+            statistics: None,
+        })
+    }
+
+    /// Entry counts by status and finding counts by severity
+    pub fn totals(&self) -> Totals {
+        let mut totals = Totals::default();
+        for entry_report in &self.entries {
+            totals.add(entry_report);
+        }
+        totals
+    }
+
+    /// Health score of the validated entries
+    pub fn score(&self) -> Option<QualityScore> {
+        QualityScore::of(&self.entries)
+    }
+
+    /// The summary, quality score and every entry report as pretty-printed JSON
+    pub fn to_json(&self) -> serde_json::Result<String> {
+        serde_json::to_string_pretty(&self.data())
+    }
+
+    /// Everything the report holds, as JSON output and templates see it
+    pub(crate) fn data(&self) -> ReportData<'_> {
+        ReportData {
+            summary: self.summary(),
+            score: self.score(),
+            entries: &self.entries,
+            statistics: self.statistics.as_ref(),
+        }
+    }
+
+    /// One line per finding, `file:line: [key] SEVERITY field: message`, for grep, awk and
+    /// editor quickfix lists. Unmatched entries and failed lookups get an `INFO` line.
+    pub fn to_compact(&self) -> String {
+        let mut lines = Vec::new();
+        for entry_report in &self.entries {
+            let label = entry_label(&entry_report.entry);
+            let findings = entry_report.lints.iter().chain(
+                entry_report
+                    .validation_results
+                    .iter()
+                    .flat_map(|r| &r.discrepancies),
+            );
+            for discrepancy in findings {
+                lines.push(format!(
+                    "{} {} {}: {}",
+                    label,
+                    discrepancy.severity,
+                    discrepancy.field,
+                    discrepancy.message.replace('\n', " ")
+                ));
+            }
+            match &entry_report.status {
+                EntryStatus::NotFound => {
+                    lines.push(format!("{} INFO entry: Not found in any database", label))
+                }
+                EntryStatus::Failed(reason) => {
+                    lines.push(format!("{} INFO entry: Validation failed: {}", label, reason))
+                }
+                _ => {}
+            }
+        }
+        lines.join("\n")
+    }
+
+    /// Print the report to stdout with colors, grouped by status. With `breakdown`, each
+    /// validated entry gets a table of the sources that matched it.
+    pub fn print(&self, breakdown: bool) {
+        self.print_summary();
 
         // Print errors first
         let error_entries: Vec<_> = self
@@ -107,9 +536,14 @@ impl Report {
             .collect();
 
         if !error_entries.is_empty() {
-            println!("{}", format!("ERRORS ({})", error_entries.len()).red().bold());
+            println!(
+                "{}",
+                format!("ERRORS ({})", error_entries.len())
+                    .color(theme().error)
+                    .bold()
+            );
             for entry_report in error_entries {
-                print_entry_report(entry_report);
+                print_entry_report(entry_report, breakdown);
             }
             println!();
         }
@@ -122,15 +556,14 @@ impl Report {
             .collect();
 
         if !failed_entries.is_empty() {
-            println!("{}", format!("FAILED ({})", failed_entries.len()).red().bold());
+            println!(
+                "{}",
+                format!("FAILED ({})", failed_entries.len())
+                    .color(theme().error)
+                    .bold()
+            );
             for entry_report in failed_entries {
-                if let EntryStatus::Failed(reason) = &entry_report.status {
-                    println!(
-                        "  {} {}",
-                        format!("[{}]", entry_report.entry.key).dimmed(),
-                        reason
-                    );
-                }
+                print_any_entry_report(entry_report, breakdown);
             }
             println!();
         }
@@ -146,11 +579,11 @@ impl Report {
             println!(
                 "{}",
                 format!("WARNINGS ({})", warning_entries.len())
-                    .yellow()
+                    .color(theme().warning)
                     .bold()
             );
             for entry_report in warning_entries {
-                print_entry_report(entry_report);
+                print_entry_report(entry_report, breakdown);
             }
             println!();
         }
@@ -170,16 +603,7 @@ impl Report {
                     .bold()
             );
             for entry_report in not_found_entries {
-                let title = entry_report
-                    .entry
-                    .title
-                    .as_deref()
-                    .unwrap_or("(no title)");
-                println!(
-                    "  {} {}",
-                    format!("[{}]", entry_report.entry.key).dimmed(),
-                    title
-                );
+                print_any_entry_report(entry_report, breakdown);
             }
             println!();
         }
@@ -194,13 +618,7 @@ impl Report {
         if !ok_entries.is_empty() {
             println!("{}", format!("OK ({})", ok_entries.len()).green().bold());
             for entry_report in ok_entries.iter().take(5) {
-                if let EntryStatus::Ok(source) = &entry_report.status {
-                    println!(
-                        "  {} Validated against {}",
-                        format!("[{}]", entry_report.entry.key).dimmed(),
-                        source.to_string().green()
-                    );
-                }
+                print_any_entry_report(entry_report, breakdown);
             }
             if ok_entries.len() > 5 {
                 println!(
@@ -212,7 +630,99 @@ impl Report {
         }
 
         println!();
+        self.print_statistics();
+    }
+
+    /// Print the report to stdout with colors, as one list in the order of `entries`
+    pub fn print_in_order(&self, breakdown: bool) {
+        self.print_summary();
+
+        for entry_report in &self.entries {
+            print_any_entry_report(entry_report, breakdown);
+        }
+
+        println!();
+        self.print_statistics();
+    }
+
+    fn print_statistics(&self) {
+        if let Some(statistics) = &self.statistics {
+            statistics.print(self.entries.len());
+        }
+    }
+
+    fn print_summary(&self) {
+        println!();
+        println!("{}", "bibval Report".bold());
+        println!("{}", "=".repeat(50));
+        println!();
+
+        let total = self.entries.len();
+        let ok = self.count_ok();
+        let warnings = self.count_warnings();
+        let errors = self.count_errors();
+        let not_found = self.count_not_found();
+        let failed = self.count_failed();
+
+        println!("Processed: {} entries", total);
+        println!(
+            "  {} validated, {} warnings, {} errors, {} failed, {} not found",
+            ok.to_string().green(),
+            warnings.to_string().color(theme().warning),
+            errors.to_string().color(theme().error),
+            failed.to_string().color(theme().error).bold(),
+            not_found.to_string().dimmed()
+        );
+        if let Some(score) = self.score() {
+            println!(
+                "  Quality score: {}/100 (accuracy {}, identifiers {}, completeness {}, \
+                 consistency {})",
+                score_color(score.overall),
+                score.accuracy_text(),
+                score.identifiers,
+                score.completeness,
+                score.consistency
+            );
+        }
+        println!();
+    }
+}
+
+/// Print per-source request rates and where each adaptive concurrency limit ended up, to
+/// stderr so it can accompany a machine-readable report
+pub fn print_profile(stats: &[(ApiSource, ThrottleStats)]) {
+    eprintln!("{}", "Profile".bold());
+    eprintln!("{}", "=".repeat(50));
+    eprintln!();
+
+    if stats.is_empty() {
+        eprintln!("  No API requests were made");
+        eprintln!();
+        return;
     }
+
+    eprintln!(
+        "  {:<18} {:>8} {:>8} {:>9} {:>12} {:>8}",
+        "Source", "Requests", "429s", "Req/s", "Mean latency", "Limit"
+    );
+    for (source, s) in stats {
+        let limit = format!("{:.0} (min {:.0})", s.limit.floor(), s.min_limit.floor());
+        let rate_limited = if s.rate_limited > 0 {
+            s.rate_limited.to_string().yellow()
+        } else {
+            s.rate_limited.to_string().normal()
+        };
+        eprintln!(
+            "  {:<18} {:>8} {:>8} {:>9.1} {:>10}ms {:>8}",
+            source.to_string(),
+            s.requests,
+            rate_limited,
+            s.requests_per_second(),
+            s.mean_latency().as_millis(),
+            limit
+        );
+    }
+    eprintln!();
 }
 
 impl Default for Report {
@@ -221,28 +731,284 @@ impl Default for Report {
     }
 }
 
-fn print_entry_report(entry_report: &EntryReport) {
-    let key = format!("[{}]", entry_report.entry.key);
+/// Where a status falls when worst comes first
+fn status_rank(status: &EntryStatus) -> u8 {
+    match status {
+        EntryStatus::Error => 0,
+        EntryStatus::Failed(_) => 1,
+        EntryStatus::Warning => 2,
+        EntryStatus::NotFound => 3,
+        EntryStatus::Ok(_) => 4,
+    }
+}
+
+/// Confidence of the best match, or -1 when nothing matched
+fn best_confidence(entry_report: &EntryReport) -> f64 {
+    entry_report
+        .validation_results
+        .iter()
+        .filter(|r| r.matched_entry.is_some())
+        .map(|r| r.confidence)
+        .fold(-1.0, f64::max)
+}
+
+/// Print an entry the way its status group shows it
+fn print_any_entry_report(entry_report: &EntryReport, breakdown: bool) {
+    let label = entry_label(&entry_report.entry);
+    match &entry_report.status {
+        EntryStatus::Error | EntryStatus::Warning => print_entry_report(entry_report, breakdown),
+        EntryStatus::Failed(reason) => {
+            println!("  {} {}", label.dimmed(), reason);
+            print_lints(entry_report);
+            print_trace(entry_report);
+        }
+        EntryStatus::NotFound => {
+            let title = entry_report
+                .entry
+                .title
+                .as_deref()
+                .unwrap_or("(no title)");
+            println!("  {} {}", label.dimmed(), title);
+            print_lints(entry_report);
+            print_trace(entry_report);
+        }
+        EntryStatus::Ok(source) => {
+            // An uncited work is more likely obscure or the wrong reference
+            let cited = match entry_report.citations {
+                Some(c) if c.count == 0 => format!(" ({})", citation_note(&c)).yellow(),
+                Some(c) => format!(" ({})", citation_note(&c)).dimmed(),
+                None => "".normal(),
+            };
+            println!(
+                "  {} Validated against {}{}",
+                label.dimmed(),
+                source.to_string().green(),
+                cited
+            );
+            if breakdown {
+                print_breakdown(entry_report);
+            }
+            print_trace(entry_report);
+        }
+    }
+}
+
+fn print_entry_report(entry_report: &EntryReport, breakdown: bool) {
+    let key = entry_label(&entry_report.entry);
+
+    print_lints(entry_report);
+
+    if breakdown {
+        print_breakdown(entry_report);
+    }
 
     for result in &entry_report.validation_results {
         for discrepancy in &result.discrepancies {
-            print_discrepancy(&key, discrepancy, &result.source);
+            print_discrepancy(&key, discrepancy, &result.source.to_string());
         }
     }
+
+    // A curated replacement, printed below, supersedes the consensus
+    if entry_report.replacement.is_none() {
+        let corrected = clean_entry(entry_report);
+        if corrected.fields != entry_report.entry.fields {
+            println!("  {} Suggested correction:", key.dimmed());
+            for line in format_entry(&corrected).lines() {
+                println!("       {}", line.dimmed());
+            }
+        }
+    }
+
+    if let Some(citations) = &entry_report.citations {
+        println!("  {} {}", key.dimmed(), citation_note(citations));
+    }
+
+    if let Some(open_access) = &entry_report.open_access {
+        println!("  {} {}", key.dimmed(), open_access_note(open_access));
+    }
+
+    if let Some(code) = &entry_report.code {
+        println!("  {} official code at {}", key.dimmed(), code);
+    }
+
+    if let Some(replacement) = &entry_report.replacement {
+        println!(
+            "  {} Suggested replacement from {}:",
+            key.dimmed(),
+            replacement.source
+        );
+        for line in replacement.bibtex.lines() {
+            println!("       {}", line.dimmed());
+        }
+    }
+
+    print_trace(entry_report);
+}
+
+/// The decision trail recorded for an entry with `-vv`
+fn print_trace(entry_report: &EntryReport) {
+    if entry_report.trace.is_empty() {
+        return;
+    }
+    println!("  {} Lookup trace:", entry_label(&entry_report.entry).dimmed());
+    for step in &entry_report.trace {
+        println!("       {}", step.dimmed());
+    }
+}
+
+/// A table of the sources that matched an entry: how confident each match was, and which
+/// of the fields both sides have it agreed and disagreed on
+fn print_breakdown(entry_report: &EntryReport) {
+    let matches: Vec<_> = entry_report
+        .validation_results
+        .iter()
+        .filter_map(|r| Some((r, r.matched_entry.as_ref()?)))
+        .collect();
+    if matches.is_empty() {
+        return;
+    }
+
+    let rows: Vec<_> = matches
+        .iter()
+        .map(|(result, matched)| {
+            let mut disagreed: Vec<DiscrepancyField> = Vec::new();
+            for discrepancy in &result.discrepancies {
+                if discrepancy.severity >= Severity::Warning
+                    && !disagreed.contains(&discrepancy.field)
+                {
+                    disagreed.push(discrepancy.field);
+                }
+            }
+            let agreed: Vec<DiscrepancyField> = compared_fields(&entry_report.entry, matched)
+                .into_iter()
+                .filter(|field| !disagreed.contains(field))
+                .collect();
+            (result.source.to_string(), result.confidence, agreed, disagreed)
+        })
+        .collect();
+    let width = rows
+        .iter()
+        .map(|(source, ..)| source.len())
+        .max()
+        .unwrap_or(0)
+        .max("Source".len());
+
+    // Padded before coloring, since the escape codes would count towards the width
+    let header = format!(
+        "{:width$}  {:>10}  {:32}  {}",
+        "Source", "Confidence", "Agrees on", "Disagrees on"
+    );
+    println!("       {}", header.bold());
+    for (source, confidence, agreed, disagreed) in rows {
+        let list = |fields: &[DiscrepancyField]| {
+            if fields.is_empty() {
+                "-".to_string()
+            } else {
+                let names: Vec<String> = fields.iter().map(|f| f.to_string()).collect();
+                names.join(", ")
+            }
+        };
+        println!(
+            "       {:width$}  {:>9.0}%  {}  {}",
+            source,
+            confidence * 100.0,
+            format!("{:32}", list(&agreed)).green(),
+            list(&disagreed).red()
+        );
+    }
+}
+
+/// The fields both entries have a value for, which a validator can compare
+fn compared_fields(local: &Entry, remote: &Entry) -> Vec<DiscrepancyField> {
+    [
+        (
+            DiscrepancyField::Title,
+            local.title.is_some() && remote.title.is_some(),
+        ),
+        (
+            DiscrepancyField::Authors,
+            !local.authors.is_empty() && !remote.authors.is_empty(),
+        ),
+        (
+            DiscrepancyField::Year,
+            local.year.is_some() && remote.year.is_some(),
+        ),
+        (
+            DiscrepancyField::Venue,
+            local.venue.is_some() && remote.venue.is_some(),
+        ),
+        (
+            DiscrepancyField::Doi,
+            local.doi.is_some() && remote.doi.is_some(),
+        ),
+        (
+            DiscrepancyField::Pages,
+            local.field("pages").is_some() && remote.field("pages").is_some(),
+        ),
+    ]
+    .into_iter()
+    .filter_map(|(field, both)| both.then_some(field))
+    .collect()
+}
+
+/// How often the work has been cited, as in "cited 12 times according to OpenAlex"
+pub fn citation_note(citations: &CitationCount) -> String {
+    match citations.count {
+        0 => format!("not cited according to {}", citations.source),
+        1 => format!("cited once according to {}", citations.source),
+        count => format!("cited {} times according to {}", count, citations.source),
+    }
+}
+
+fn open_access_note(open_access: &OpenAccess) -> String {
+    match &open_access.status {
+        Some(status) => format!("open access ({}) at {}", status, open_access.url),
+        None => format!("open access at {}", open_access.url),
+    }
+}
+
+/// The overall score colored like the status it most resembles
+fn score_color(score: u8) -> colored::ColoredString {
+    match score {
+        90.. => score.to_string().green(),
+        70..=89 => score.to_string().yellow(),
+        _ => score.to_string().red(),
+    }
+}
+
+/// The bracketed citation key, preceded by `file:line` when the entry's location is
+/// known so terminals and editors can jump to it
+fn entry_label(entry: &Entry) -> String {
+    match &entry.location {
+        Some(location) => format!("{}: [{}]", location, entry.key),
+        None => format!("[{}]", entry.key),
+    }
+}
+
+fn print_lints(entry_report: &EntryReport) {
+    let key = entry_label(&entry_report.entry);
+    for lint in &entry_report.lints {
+        print_discrepancy(&key, lint, "offline check");
+    }
 }
 
-fn print_discrepancy(key: &str, discrepancy: &Discrepancy, source: &ApiSource) {
+fn print_discrepancy(key: &str, discrepancy: &Discrepancy, source: &str) {
     let severity_str = match discrepancy.severity {
-        Severity::Error => "ERROR".red(),
-        Severity::Warning => "WARN".yellow(),
-        Severity::Info => "INFO".blue(),
+        Severity::Error => "ERROR".color(theme().error),
+        Severity::Warning => "WARN".color(theme().warning),
+        Severity::Info => "INFO".color(theme().info),
     };
 
+    // Citing a predatory venue is a reputational risk, so make it hard to miss
+    let message = match discrepancy.field {
+        DiscrepancyField::Predatory => discrepancy.message.bold().to_string(),
+        _ => discrepancy.message.clone(),
+    };
     println!(
         "  {} {} {} (via {})",
         key.dimmed(),
         severity_str,
-        discrepancy.message,
+        message,
         source
     );
 
@@ -265,3 +1031,180 @@ fn truncate(s: &str, max_len: usize) -> String {
         format!("{}...", &s[..max_len - 3])
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::entry::SourceLocation;
+
+    fn entry_report(key: &str, line: usize, status: EntryStatus, confidence: f64) -> EntryReport {
+        let mut entry = Entry::new(key.to_string(), "article".to_string());
+        entry.location = Some(SourceLocation { file: None, line });
+        EntryReport {
+            validation_results: vec![ValidationResult {
+                source: ApiSource::CrossRef,
+                matched_entry: Some(entry.clone()),
+                confidence,
+                discrepancies: Vec::new(),
+            }],
+            entry,
+            status,
+            lints: Vec::new(),
+            replacement: None,
+            citations: None,
+            open_access: None,
+            code: None,
+            trace: Vec::new(),
+        }
+    }
+
+    #[test]
+    fn entries_sort_by_each_order() {
+        let mut report = Report::new();
+        report.add(entry_report("b", 9, EntryStatus::Ok(ApiSource::CrossRef), 0.99));
+        report.add(entry_report("C", 1, EntryStatus::Warning, 0.8));
+        report.add(entry_report("a", 5, EntryStatus::Error, 0.9));
+        let keys = |report: &Report| -> Vec<String> {
+            report.entries.iter().map(|e| e.entry.key.clone()).collect()
+        };
+
+        report.sort(SortOrder::Key);
+        assert_eq!(keys(&report), ["a", "b", "C"]);
+        report.sort(SortOrder::Severity);
+        assert_eq!(keys(&report), ["a", "C", "b"]);
+        report.sort(SortOrder::Confidence);
+        assert_eq!(keys(&report), ["C", "a", "b"]);
+        report.sort(SortOrder::File);
+        assert_eq!(keys(&report), ["C", "a", "b"]);
+
+        report.entries[0].entry.location = None;
+        report.sort(SortOrder::File);
+        assert_eq!(keys(&report), ["a", "b", "C"]);
+    }
+
+    #[test]
+    fn statistics_combine_matches_and_requests_per_source() {
+        let mut report = Report::new();
+        report.add(entry_report("a", 1, EntryStatus::Ok(ApiSource::CrossRef), 1.0));
+        report.add(entry_report("b", 2, EntryStatus::Warning, 0.5));
+        let throttles = [(
+            ApiSource::Dblp,
+            ThrottleStats {
+                requests: 3,
+                errors: 1,
+                busy: Duration::from_secs(2),
+                ..ThrottleStats::default()
+            },
+        )];
+        let cache = CacheStats { hits: 3, misses: 1 };
+
+        let statistics = Statistics::gather(&report, &throttles, cache, Duration::from_secs(5));
+        let sources: Vec<_> = statistics
+            .sources
+            .iter()
+            .map(|s| (s.source, s.matched, s.average_confidence, s.errors))
+            .collect();
+        assert_eq!(
+            sources,
+            [
+                (ApiSource::CrossRef, 2, Some(0.75), 0),
+                (ApiSource::Dblp, 0, None, 1)
+            ]
+        );
+        assert_eq!(statistics.average_confidence, Some(0.75));
+        assert_eq!(statistics.api_secs, 2.0);
+    }
+
+    #[test]
+    fn offline_runs_spend_no_time_on_apis() {
+        let cache = CacheStats { hits: 0, misses: 0 };
+        let statistics = Statistics::gather(&Report::new(), &[], cache, Duration::from_secs(1));
+
+        // An empty f64 sum is -0.0, which would print as "-0.0s"
+        assert!(statistics.api_secs.is_sign_positive());
+    }
+
+    #[test]
+    fn totals_count_entries_and_findings() {
+        let mut report = Report::new();
+        report.add(entry_report("a", 1, EntryStatus::Ok(ApiSource::CrossRef), 1.0));
+        let mut warning = entry_report("b", 2, EntryStatus::Warning, 0.5);
+        warning.lints.push(Discrepancy {
+            field: DiscrepancyField::Key,
+            severity: Severity::Info,
+            local_value: String::new(),
+            remote_value: String::new(),
+            message: "Key doesn't follow the pattern".to_string(),
+        });
+        warning.validation_results[0].discrepancies.push(Discrepancy {
+            field: DiscrepancyField::Venue,
+            severity: Severity::Warning,
+            local_value: "NIPS".to_string(),
+            remote_value: "NeurIPS".to_string(),
+            message: "Venue differs".to_string(),
+        });
+        report.add(warning);
+
+        let totals = report.totals();
+        assert_eq!(totals.entries, report.summary());
+        assert_eq!(
+            totals.findings,
+            FindingCounts {
+                error: 0,
+                warning: 1,
+                info: 1
+            }
+        );
+        let json = serde_json::to_value(totals).unwrap();
+        assert_eq!(json["warnings"], 1);
+        assert_eq!(json["findings"]["info"], 1);
+    }
+
+    #[test]
+    fn compact_lines_carry_location_key_and_severity() {
+        let mut report = Report::new();
+        let mut found = entry_report("smith2020", 3, EntryStatus::Error, 0.9);
+        found.lints.push(Discrepancy {
+            field: DiscrepancyField::Year,
+            severity: Severity::Error,
+            local_value: "2020".to_string(),
+            remote_value: "2019".to_string(),
+            message: "Year mismatch".to_string(),
+        });
+        report.add(found);
+        report.add(entry_report("doe2021", 9, EntryStatus::NotFound, 0.0));
+
+        assert_eq!(
+            report.to_compact(),
+            "line 3: [smith2020] ERROR Year: Year mismatch\n\
+             line 9: [doe2021] INFO entry: Not found in any database"
+        );
+    }
+
+    #[test]
+    fn themes_override_default_colors() {
+        let theme: Theme = "error=magenta, info=bright black".parse().unwrap();
+        assert_eq!(theme.error, Color::Magenta);
+        assert_eq!(theme.warning, Color::Yellow);
+        assert_eq!(theme.info, Color::BrightBlack);
+
+        assert!("error=mauve".parse::<Theme>().is_err());
+        assert!("fatal=red".parse::<Theme>().is_err());
+        assert!("red".parse::<Theme>().is_err());
+    }
+
+    #[test]
+    fn only_fields_both_sides_have_are_compared() {
+        let mut local = Entry::new("a".to_string(), "article".to_string());
+        local.title = Some("Graphs".into());
+        local.year = Some(2020);
+        local.doi = Some("10.1000/graphs".to_string());
+        let mut remote = local.clone();
+        remote.doi = None;
+        remote.venue = Some("Nature".into());
+        assert_eq!(
+            compared_fields(&local, &remote),
+            [DiscrepancyField::Title, DiscrepancyField::Year]
+        );
+    }
+}
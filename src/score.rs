@@ -0,0 +1,277 @@
+//! Overall health score of a bibliography, for tracking it over time
+
+use crate::entry::{Discrepancy, DiscrepancyField, Entry, EntryKind, Severity};
+use crate::report::{EntryReport, EntryStatus};
+use serde::{Deserialize, Serialize};
+
+/// How much each category contributes to the overall score, out of 100
+const ACCURACY_WEIGHT: f64 = 40.0;
+const IDENTIFIERS_WEIGHT: f64 = 20.0;
+const COMPLETENESS_WEIGHT: f64 = 20.0;
+const CONSISTENCY_WEIGHT: f64 = 20.0;
+
+/// Bibliography health from 0 to 100, overall and by category
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct QualityScore {
+    pub overall: u8,
+    /// Entries carry a DOI, arXiv ID, PubMed ID, ISBN or URL, and it resolves to the work
+    pub identifiers: u8,
+    /// Metadata agrees with the databases; `None` when no entry could be looked up
+    pub accuracy: Option<u8>,
+    /// Fields expected for the entry type are filled in with real values
+    pub completeness: u8,
+    /// Keys, venues and publishers follow the conventions, with no duplicates
+    pub consistency: u8,
+}
+
+impl QualityScore {
+    /// Score the reports of a run; `None` when there is nothing to score
+    pub fn of(reports: &[EntryReport]) -> Option<Self> {
+        if reports.is_empty() {
+            return None;
+        }
+
+        let mean = |scores: Vec<f64>| {
+            (!scores.is_empty()).then(|| scores.iter().sum::<f64>() / scores.len() as f64)
+        };
+        let identifiers = mean(reports.iter().map(identifiers_score).collect())?;
+        // Entries whose lookups failed say nothing about accuracy
+        let accuracy = mean(reports.iter().filter_map(accuracy_score).collect());
+        let completeness = mean(reports.iter().map(completeness_score).collect())?;
+        let consistency = mean(reports.iter().map(consistency_score).collect())?;
+
+        // Without an accuracy score, the other categories make up the whole
+        let (accuracy_points, accuracy_weight) = match accuracy {
+            Some(accuracy) => (accuracy * ACCURACY_WEIGHT, ACCURACY_WEIGHT),
+            None => (0.0, 0.0),
+        };
+        let overall = (accuracy_points
+            + identifiers * IDENTIFIERS_WEIGHT
+            + completeness * COMPLETENESS_WEIGHT
+            + consistency * CONSISTENCY_WEIGHT)
+            / (accuracy_weight + IDENTIFIERS_WEIGHT + COMPLETENESS_WEIGHT + CONSISTENCY_WEIGHT);
+
+        Some(Self {
+            overall: percent(overall),
+            identifiers: percent(identifiers),
+            accuracy: accuracy.map(percent),
+            completeness: percent(completeness),
+            consistency: percent(consistency),
+        })
+    }
+
+    /// The accuracy sub-score for display, "-" when there is none
+    pub fn accuracy_text(&self) -> String {
+        self.accuracy
+            .map_or_else(|| "-".to_string(), |a| a.to_string())
+    }
+}
+
+fn percent(fraction: f64) -> u8 {
+    (fraction * 100.0).round().clamp(0.0, 100.0) as u8
+}
+
+/// 1 with no findings, ½ for a warning, 0 for an error
+fn worst_finding<'a>(findings: impl Iterator<Item = &'a Discrepancy>) -> f64 {
+    match findings.map(|d| d.severity).max() {
+        Some(Severity::Error) => 0.0,
+        Some(Severity::Warning) => 0.5,
+        Some(Severity::Info) | None => 1.0,
+    }
+}
+
+/// Discrepancies found by comparing the entry with the databases' consensus
+fn remote_findings(report: &EntryReport) -> impl Iterator<Item = &Discrepancy> {
+    report
+        .validation_results
+        .iter()
+        .filter(|r| r.matched_entry.is_none())
+        .flat_map(|r| &r.discrepancies)
+}
+
+fn all_findings(report: &EntryReport) -> impl Iterator<Item = &Discrepancy> {
+    report.lints.iter().chain(remote_findings(report))
+}
+
+fn has_identifier(entry: &Entry) -> bool {
+    let has = |name: &str| entry.field(name).is_some_and(|v| !v.trim().is_empty());
+    entry.doi.is_some()
+        || entry.arxiv_id.is_some()
+        || entry.pmid.is_some()
+        || entry.pmcid.is_some()
+        || entry.mr_number.is_some()
+        || entry.hal_id.is_some()
+        || has("isbn")
+        || (entry.kind() == EntryKind::Patent && has("number"))
+        || (entry.kind() != EntryKind::Publication && (entry.url.is_some() || has("url")))
+}
+
+/// Findings about the identifiers themselves rather than the metadata
+fn is_identifier_finding(d: &Discrepancy) -> bool {
+    matches!(
+        d.field,
+        DiscrepancyField::Doi
+            | DiscrepancyField::Url
+            | DiscrepancyField::Pmcid
+            | DiscrepancyField::MrNumber
+            | DiscrepancyField::Swhid
+    )
+}
+
+fn identifiers_score(report: &EntryReport) -> f64 {
+    if !has_identifier(&report.entry) {
+        return 0.0;
+    }
+    worst_finding(all_findings(report).filter(|d| is_identifier_finding(d)))
+}
+
+fn accuracy_score(report: &EntryReport) -> Option<f64> {
+    match report.status {
+        EntryStatus::Failed(_) => None,
+        // Unverifiable rather than wrong, including entries only the offline checks saw
+        EntryStatus::NotFound => Some(0.5),
+        EntryStatus::Warning | EntryStatus::Error if report.validation_results.is_empty() => {
+            Some(0.5)
+        }
+        _ => Some(worst_finding(
+            remote_findings(report).filter(|d| !is_identifier_finding(d)),
+        )),
+    }
+}
+
+/// Fields every entry of a type should have; alternatives are separated by `|`
+fn expected_fields(entry: &Entry) -> &'static [&'static str] {
+    match entry.entry_type.to_lowercase().as_str() {
+        "article" => &["author", "title", "journal|journaltitle", "year|date"],
+        "inproceedings" | "conference" => &["author", "title", "booktitle", "year|date"],
+        "book" => &["author|editor", "title", "publisher", "year|date"],
+        "online" | "www" | "electronic" => &["title", "url", "urldate"],
+        "software" | "dataset" => &["author", "title", "year|date", "version"],
+        _ => &["author", "title", "year|date"],
+    }
+}
+
+fn completeness_score(report: &EntryReport) -> f64 {
+    let entry = &report.entry;
+    let expected = expected_fields(entry);
+    let present = expected
+        .iter()
+        .filter(|alternatives| {
+            alternatives
+                .split('|')
+                .any(|name| entry.field(name).is_some_and(|v| !v.trim().is_empty()))
+        })
+        .count();
+    let placeholders = report
+        .lints
+        .iter()
+        .filter(|d| d.field == DiscrepancyField::Placeholder)
+        .count();
+    present.saturating_sub(placeholders) as f64 / expected.len() as f64
+}
+
+fn consistency_score(report: &EntryReport) -> f64 {
+    worst_finding(report.lints.iter().filter(|d| {
+        matches!(
+            d.field,
+            DiscrepancyField::Key
+                | DiscrepancyField::Duplicate
+                | DiscrepancyField::Crossref
+                | DiscrepancyField::Venue
+                | DiscrepancyField::Publisher
+                | DiscrepancyField::Year
+        )
+    }))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::entry::ApiSource;
+
+    fn report(entry: Entry, status: EntryStatus, lints: Vec<Discrepancy>) -> EntryReport {
+        EntryReport {
+            entry,
+            status,
+            validation_results: Vec::new(),
+            lints,
+            replacement: None,
+            citations: None,
+            open_access: None,
+            code: None,
+            trace: Vec::new(),
+        }
+    }
+
+    fn article(key: &str) -> Entry {
+        let mut entry = Entry::new(key.to_string(), "article".to_string());
+        for (name, value) in [
+            ("author", "Ada Lovelace"),
+            ("title", "Notes"),
+            ("journal", "Scientific Memoirs"),
+            ("year", "1843"),
+        ] {
+            entry.fields.insert(name.to_string(), value.to_string());
+        }
+        entry.doi = Some("10.1000/notes".to_string());
+        entry
+    }
+
+    #[test]
+    fn clean_bibliographies_score_full_marks() {
+        let reports = vec![report(
+            article("a"),
+            EntryStatus::Ok(ApiSource::CrossRef),
+            vec![],
+        )];
+        let score = QualityScore::of(&reports).unwrap();
+        assert_eq!(score.overall, 100);
+        assert!(QualityScore::of(&[]).is_none());
+    }
+
+    #[test]
+    fn findings_lower_their_categories() {
+        let mut bare = article("b");
+        bare.doi = None;
+        bare.fields.remove("journal");
+        let key_lint = Discrepancy {
+            field: DiscrepancyField::Key,
+            severity: Severity::Warning,
+            local_value: "b".to_string(),
+            remote_value: String::new(),
+            message: "Key does not match the required pattern".to_string(),
+        };
+        let reports = vec![
+            report(article("a"), EntryStatus::Ok(ApiSource::CrossRef), vec![]),
+            report(bare, EntryStatus::NotFound, vec![key_lint]),
+        ];
+
+        let score = QualityScore::of(&reports).unwrap();
+        assert_eq!(score.identifiers, 50);
+        assert_eq!(score.accuracy, Some(75));
+        assert_eq!(score.completeness, 88);
+        assert_eq!(score.consistency, 75);
+        assert_eq!(score.overall, 73);
+    }
+
+    #[test]
+    fn failed_lookups_leave_accuracy_unscored() {
+        let reports = vec![
+            report(
+                article("a"),
+                EntryStatus::Failed("timeout".to_string()),
+                vec![],
+            ),
+            report(
+                article("b"),
+                EntryStatus::Failed("timeout".to_string()),
+                vec![],
+            ),
+        ];
+
+        let score = QualityScore::of(&reports).unwrap();
+        assert_eq!(score.accuracy, None);
+        assert_eq!(score.accuracy_text(), "-");
+        assert_eq!(score.overall, 100);
+    }
+}
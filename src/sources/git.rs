@@ -0,0 +1,187 @@
+use super::SourceError;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+use tempfile::TempDir;
+
+/// Directories that never hold project sources
+const SKIPPED_DIRS: &[&str] = &[".git", "node_modules", "target"];
+
+/// Shallow clone of a remote project, removed when dropped
+pub struct Checkout {
+    dir: TempDir,
+}
+
+impl Checkout {
+    /// Shallow-clone `url` (an Overleaf git URL or any other git remote)
+    pub fn shallow_clone(url: &str, branch: Option<&str>) -> Result<Self, SourceError> {
+        let dir = tempfile::Builder::new()
+            .prefix("bibval-remote-")
+            .tempdir()?;
+
+        let mut command = Command::new("git");
+        command.args(["clone", "--quiet", "--depth", "1"]);
+        if let Some(branch) = branch {
+            command.args(["--branch", branch]);
+        }
+        // `--` keeps a URL like `--upload-pack=...` from being read as an option
+        let output = command
+            .arg("--")
+            .arg(url)
+            .arg(dir.path())
+            .output()
+            .map_err(|e| SourceError::GitError(format!("could not run git: {}", e)))?;
+
+        if !output.status.success() {
+            let stderr = String::from_utf8_lossy(&output.stderr);
+            return Err(SourceError::GitError(stderr.trim().to_string()));
+        }
+
+        Ok(Self { dir })
+    }
+
+    pub fn path(&self) -> &Path {
+        self.dir.path()
+    }
+
+    /// Files in the checkout with the given extension, sorted by path
+    pub fn files_with_extension(&self, extension: &str) -> Result<Vec<PathBuf>, SourceError> {
+        let mut files = Vec::new();
+        collect_files(self.path(), extension, &mut files)?;
+        files.sort();
+        Ok(files)
+    }
+}
+
+/// Contents of a file as of a git revision such as `HEAD~1` or a branch name
+pub fn file_at_revision(path: &Path, revision: &str) -> Result<String, SourceError> {
+    // git would read it as an option
+    if revision.starts_with('-') {
+        return Err(SourceError::GitError(format!(
+            "invalid revision '{}'",
+            revision
+        )));
+    }
+    let dir = match path.parent() {
+        Some(dir) if !dir.as_os_str().is_empty() => dir,
+        _ => Path::new("."),
+    };
+    let name = path
+        .file_name()
+        .ok_or_else(|| SourceError::GitError(format!("{} is not a file", path.display())))?;
+
+    // `./` makes git resolve the path relative to `dir` rather than the repository root
+    let output = Command::new("git")
+        .arg("-C")
+        .arg(dir)
+        .arg("show")
+        .arg(format!("{}:./{}", revision, name.to_string_lossy()))
+        .output()
+        .map_err(|e| SourceError::GitError(format!("could not run git: {}", e)))?;
+
+    if !output.status.success() {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        return Err(SourceError::GitError(stderr.trim().to_string()));
+    }
+    Ok(String::from_utf8_lossy(&output.stdout).into_owned())
+}
+
+fn collect_files(dir: &Path, extension: &str, files: &mut Vec<PathBuf>) -> std::io::Result<()> {
+    for entry in fs::read_dir(dir)? {
+        let entry = entry?;
+        let path = entry.path();
+        let file_type = entry.file_type()?;
+
+        if file_type.is_dir() {
+            let skipped = path
+                .file_name()
+                .and_then(|name| name.to_str())
+                .is_some_and(|name| SKIPPED_DIRS.contains(&name));
+            if !skipped {
+                collect_files(&path, extension, files)?;
+            }
+        } else if file_type.is_file()
+            && path
+                .extension()
+                .is_some_and(|ext| ext.eq_ignore_ascii_case(extension))
+        {
+            files.push(path);
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn clones_and_discovers_project_files() {
+        let origin = tempfile::tempdir().unwrap();
+        let git = |args: &[&str]| {
+            let status = Command::new("git")
+                .args(["-c", "user.name=test", "-c", "user.email=test@example.com"])
+                .args(args)
+                .current_dir(origin.path())
+                .output()
+                .unwrap()
+                .status;
+            assert!(status.success(), "git {:?} failed", args);
+        };
+
+        fs::create_dir_all(origin.path().join("sections")).unwrap();
+        fs::write(origin.path().join("main.tex"), r"\input{sections/intro}").unwrap();
+        fs::write(origin.path().join("sections/intro.tex"), r"\cite{a}").unwrap();
+        fs::write(origin.path().join("refs.BIB"), "@misc{a, title={A}}").unwrap();
+        git(&["init", "--quiet"]);
+        git(&["add", "."]);
+        git(&["commit", "--quiet", "-m", "init"]);
+
+        let url = format!("file://{}", origin.path().display());
+        let checkout = Checkout::shallow_clone(&url, None).unwrap();
+
+        let tex = checkout.files_with_extension("tex").unwrap();
+        let names: Vec<_> = tex
+            .iter()
+            .map(|p| p.strip_prefix(checkout.path()).unwrap().to_path_buf())
+            .collect();
+        assert_eq!(
+            names,
+            [
+                PathBuf::from("main.tex"),
+                PathBuf::from("sections/intro.tex")
+            ]
+        );
+        assert_eq!(checkout.files_with_extension("bib").unwrap().len(), 1);
+
+        assert!(Checkout::shallow_clone("file:///nonexistent/repo", None).is_err());
+    }
+
+    #[test]
+    fn reads_files_at_earlier_revisions() {
+        let repo = tempfile::tempdir().unwrap();
+        let git = |args: &[&str]| {
+            let status = Command::new("git")
+                .args(["-c", "user.name=test", "-c", "user.email=test@example.com"])
+                .args(args)
+                .current_dir(repo.path())
+                .output()
+                .unwrap()
+                .status;
+            assert!(status.success(), "git {:?} failed", args);
+        };
+
+        let bib = repo.path().join("refs.bib");
+        fs::write(&bib, "@misc{a, title={Old}}").unwrap();
+        git(&["init", "--quiet"]);
+        git(&["add", "."]);
+        git(&["commit", "--quiet", "-m", "init"]);
+        fs::write(&bib, "@misc{a, title={New}}").unwrap();
+
+        assert_eq!(
+            file_at_revision(&bib, "HEAD").unwrap(),
+            "@misc{a, title={Old}}"
+        );
+        assert!(file_at_revision(&bib, "no-such-rev").is_err());
+    }
+}
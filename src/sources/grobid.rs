@@ -0,0 +1,73 @@
+use super::SourceError;
+use crate::entry::Entry;
+use crate::parser::parse_bib_string;
+use reqwest::multipart::{Form, Part};
+use reqwest::Client;
+use std::path::Path;
+use std::time::Duration;
+
+pub const DEFAULT_GROBID_URL: &str = "http://localhost:8070";
+
+/// Header extraction of a large PDF can take a while on a busy server
+const REQUEST_TIMEOUT: Duration = Duration::from_secs(120);
+
+/// Client for a GROBID service, used to read the header metadata of PDFs
+pub struct GrobidClient {
+    client: Client,
+    base_url: String,
+}
+
+impl GrobidClient {
+    pub fn new(base_url: &str) -> Self {
+        let client = Client::builder()
+            .timeout(REQUEST_TIMEOUT)
+            .build()
+            .expect("Failed to create HTTP client");
+        Self {
+            client,
+            base_url: base_url.trim_end_matches('/').to_string(),
+        }
+    }
+
+    /// Extract the header metadata (title, authors, venue, year, DOI) of a PDF as an
+    /// entry. The entry's key is left for the caller to assign.
+    pub async fn process_header(&self, pdf: &Path) -> Result<Option<Entry>, SourceError> {
+        let bytes = tokio::fs::read(pdf).await?;
+        let file_name = pdf
+            .file_name()
+            .map(|name| name.to_string_lossy().into_owned())
+            .unwrap_or_else(|| "input.pdf".to_string());
+        let part = Part::bytes(bytes)
+            .file_name(file_name)
+            .mime_str("application/pdf")?;
+        let form = Form::new()
+            .part("input", part)
+            .text("consolidateHeader", "0");
+
+        let response = self
+            .client
+            .post(format!("{}/api/processHeaderDocument", self.base_url))
+            .header("Accept", "application/x-bibtex")
+            .multipart(form)
+            .send()
+            .await?;
+
+        let status = response.status();
+        // No header could be extracted from the document
+        if status == reqwest::StatusCode::NO_CONTENT {
+            return Ok(None);
+        }
+        let body = response.text().await?;
+        if !status.is_success() {
+            return Err(SourceError::ApiError {
+                service: "GROBID",
+                status,
+                message: body.trim().to_string(),
+            });
+        }
+
+        let entries =
+            parse_bib_string(&body).map_err(|e| SourceError::ParseError(e.to_string()))?;
+        Ok(entries.into_iter().next())
+    }
+}
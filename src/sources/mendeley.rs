@@ -0,0 +1,320 @@
+use super::SourceError;
+use crate::entry::Entry;
+use crate::lint::{normalize_isbn, normalize_issn};
+use crate::parser::is_arxiv_id;
+use reqwest::{Client, Response};
+use serde::Deserialize;
+use std::sync::Arc;
+
+const MENDELEY_API_BASE: &str = "https://api.mendeley.com";
+const USER_AGENT: &str = "bibval/0.1.0 (https://github.com/femtomc/bibval)";
+const DOCUMENT_MEDIA_TYPE: &str = "application/vnd.mendeley-document.1+json";
+
+/// Documents fetched per page (the API maximum)
+const PAGE_SIZE: usize = 500;
+
+/// How to authenticate against the Mendeley API
+#[derive(Debug, Clone)]
+pub enum Credentials {
+    /// An OAuth access token obtained elsewhere
+    AccessToken(String),
+    /// An app's client credentials and a refresh token, exchanged for an access token
+    RefreshToken {
+        client_id: String,
+        client_secret: String,
+        refresh_token: String,
+    },
+}
+
+#[derive(Debug, Deserialize)]
+struct TokenResponse {
+    access_token: String,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct MendeleyDocument {
+    pub id: String,
+    #[serde(rename = "type")]
+    pub doc_type: Option<String>,
+    pub title: Option<String>,
+    #[serde(default)]
+    pub authors: Vec<MendeleyPerson>,
+    pub year: Option<i32>,
+    /// Journal, proceedings, or book the document appeared in
+    pub source: Option<String>,
+    #[serde(default)]
+    pub identifiers: MendeleyIdentifiers,
+    #[serde(default)]
+    pub websites: Vec<String>,
+    pub volume: Option<String>,
+    pub issue: Option<String>,
+    pub pages: Option<String>,
+    pub publisher: Option<String>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct MendeleyPerson {
+    pub first_name: Option<String>,
+    pub last_name: Option<String>,
+}
+
+#[derive(Debug, Clone, Default, Deserialize)]
+pub struct MendeleyIdentifiers {
+    pub doi: Option<String>,
+    pub arxiv: Option<String>,
+    pub isbn: Option<String>,
+    pub issn: Option<String>,
+}
+
+pub struct MendeleyClient {
+    client: Client,
+    access_token: String,
+}
+
+impl MendeleyClient {
+    /// Authenticate, exchanging a refresh token for an access token if needed
+    pub async fn connect(credentials: Credentials) -> Result<Self, SourceError> {
+        let client = Client::builder()
+            .user_agent(USER_AGENT)
+            .build()
+            .expect("Failed to create HTTP client");
+
+        let access_token = match credentials {
+            Credentials::AccessToken(token) => token,
+            Credentials::RefreshToken {
+                client_id,
+                client_secret,
+                refresh_token,
+            } => {
+                let response = client
+                    .post(format!("{}/oauth/token", MENDELEY_API_BASE))
+                    .basic_auth(client_id, Some(client_secret))
+                    .form(&[
+                        ("grant_type", "refresh_token"),
+                        ("refresh_token", refresh_token.as_str()),
+                    ])
+                    .send()
+                    .await?;
+                let token: TokenResponse = check_status(response).await?.json().await?;
+                token.access_token
+            }
+        };
+
+        Ok(Self {
+            client,
+            access_token,
+        })
+    }
+
+    /// Fetch every document in the user's library, following pagination links
+    pub async fn fetch_documents(&self) -> Result<Vec<MendeleyDocument>, SourceError> {
+        let mut documents = Vec::new();
+        let mut url = Some(format!(
+            "{}/documents?view=all&limit={}",
+            MENDELEY_API_BASE, PAGE_SIZE
+        ));
+
+        while let Some(page_url) = url {
+            let response = self
+                .client
+                .get(&page_url)
+                .bearer_auth(&self.access_token)
+                .header("Accept", DOCUMENT_MEDIA_TYPE)
+                .send()
+                .await?;
+            let response = check_status(response).await?;
+
+            url = response
+                .headers()
+                .get_all("Link")
+                .iter()
+                .filter_map(|value| value.to_str().ok())
+                .find_map(next_link);
+            let page: Vec<MendeleyDocument> = response.json().await?;
+            documents.extend(page);
+        }
+
+        Ok(documents)
+    }
+}
+
+async fn check_status(response: Response) -> Result<Response, SourceError> {
+    let status = response.status();
+    if status.is_success() {
+        return Ok(response);
+    }
+    let message = response.text().await.unwrap_or_default();
+    Err(SourceError::ApiError {
+        service: "Mendeley",
+        status,
+        message: message.trim().to_string(),
+    })
+}
+
+/// The `rel="next"` target of a `Link` header, if any
+fn next_link(header: &str) -> Option<String> {
+    header.split(',').find_map(|link| {
+        let (target, params) = link.split_once(';')?;
+        params
+            .split(';')
+            .any(|param| param.trim() == "rel=\"next\"")
+            .then(|| {
+                target
+                    .trim()
+                    .trim_start_matches('<')
+                    .trim_end_matches('>')
+                    .to_string()
+            })
+    })
+}
+
+/// Map a Mendeley document to an entry keyed by its document ID
+pub fn document_to_entry(document: &MendeleyDocument) -> Entry {
+    let entry_type = entry_type(document.doc_type.as_deref().unwrap_or(""));
+    let mut entry = Entry::new(document.id.clone(), entry_type.to_string());
+    let mut fields = Vec::new();
+
+    if let Some(title) = non_empty(&document.title) {
+        entry.title = Some(title.into());
+        fields.push(("title", title.to_string()));
+    }
+
+    let names: Vec<(Option<&str>, &str)> = document
+        .authors
+        .iter()
+        .filter_map(|person| {
+            let last = non_empty(&person.last_name)?;
+            Some((non_empty(&person.first_name), last))
+        })
+        .collect();
+    if !names.is_empty() {
+        entry.authors = names
+            .iter()
+            .map(|(first, last)| match first {
+                Some(first) => Arc::from(format!("{} {}", first, last)),
+                None => Arc::from(*last),
+            })
+            .collect();
+        let bibtex_names: Vec<String> = names
+            .iter()
+            .map(|(first, last)| match first {
+                Some(first) => format!("{}, {}", last, first),
+                None => last.to_string(),
+            })
+            .collect();
+        fields.push(("author", bibtex_names.join(" and ")));
+    }
+
+    entry.year = document.year;
+    if let Some(year) = document.year {
+        fields.push(("year", year.to_string()));
+    }
+
+    if let Some(source) = non_empty(&document.source) {
+        entry.venue = Some(source.into());
+        let field = if entry_type == "article" {
+            "journal"
+        } else {
+            "booktitle"
+        };
+        fields.push((field, source.to_string()));
+    }
+
+    let ids = &document.identifiers;
+    entry.doi = non_empty(&ids.doi).map(str::to_string);
+    entry.arxiv_id = non_empty(&ids.arxiv)
+        .map(|id| id.trim_start_matches("arXiv:"))
+        .filter(|id| is_arxiv_id(id))
+        .map(str::to_string);
+    entry.url = document.websites.first().cloned();
+
+    for (name, value) in [
+        ("doi", entry.doi.as_deref()),
+        ("eprint", entry.arxiv_id.as_deref()),
+        ("url", entry.url.as_deref()),
+        ("volume", non_empty(&document.volume)),
+        ("number", non_empty(&document.issue)),
+        ("pages", non_empty(&document.pages)),
+        ("publisher", non_empty(&document.publisher)),
+        ("isbn", non_empty(&ids.isbn)),
+        ("issn", non_empty(&ids.issn)),
+    ] {
+        if let Some(value) = value {
+            fields.push((name, value.to_string()));
+        }
+    }
+
+    entry.fields = fields
+        .into_iter()
+        .map(|(name, value)| (name.to_string(), value))
+        .collect();
+    entry.isbn = entry.field("isbn").and_then(normalize_isbn);
+    entry.issn = entry.field("issn").and_then(normalize_issn);
+    entry
+}
+
+fn non_empty(value: &Option<String>) -> Option<&str> {
+    value.as_deref().map(str::trim).filter(|v| !v.is_empty())
+}
+
+/// Closest BibTeX entry type for a Mendeley document type
+fn entry_type(doc_type: &str) -> &'static str {
+    match doc_type {
+        "journal" | "magazine_article" | "newspaper_article" => "article",
+        "conference_proceedings" => "inproceedings",
+        "book" => "book",
+        "book_section" | "encyclopedia_article" => "incollection",
+        "thesis" => "phdthesis",
+        "report" | "working_paper" => "techreport",
+        "computer_program" => "software",
+        "web_page" => "online",
+        "patent" => "patent",
+        _ => "misc",
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn maps_documents_to_entries() {
+        let document: MendeleyDocument = serde_json::from_value(serde_json::json!({
+            "id": "4f3c6a5e-0000-3a8b-9c1d-2e5f6a7b8c9d",
+            "type": "conference_proceedings",
+            "title": "Deep Residual Learning for Image Recognition",
+            "authors": [
+                {"first_name": "Kaiming", "last_name": "He"},
+                {"first_name": "Xiangyu", "last_name": "Zhang"}
+            ],
+            "year": 2016,
+            "source": "CVPR",
+            "identifiers": {"doi": "10.1109/CVPR.2016.90", "arxiv": "1512.03385"},
+            "websites": ["https://arxiv.org/abs/1512.03385"],
+            "pages": "770-778"
+        }))
+        .unwrap();
+
+        let entry = document_to_entry(&document);
+        assert_eq!(entry.key, "4f3c6a5e-0000-3a8b-9c1d-2e5f6a7b8c9d");
+        assert_eq!(entry.entry_type, "inproceedings");
+        assert_eq!(
+            entry.field("author"),
+            Some("He, Kaiming and Zhang, Xiangyu")
+        );
+        assert_eq!(entry.field("booktitle"), Some("CVPR"));
+        assert_eq!(entry.doi.as_deref(), Some("10.1109/CVPR.2016.90"));
+        assert_eq!(entry.arxiv_id.as_deref(), Some("1512.03385"));
+        assert_eq!(entry.year, Some(2016));
+    }
+
+    #[test]
+    fn follows_next_links() {
+        let header = r#"<https://api.mendeley.com/documents?marker=abc>; rel="next", <https://api.mendeley.com/documents>; rel="first""#;
+        assert_eq!(
+            next_link(header).as_deref(),
+            Some("https://api.mendeley.com/documents?marker=abc")
+        );
+        assert_eq!(next_link(r#"<https://x>; rel="last""#), None);
+    }
+}
@@ -0,0 +1,26 @@
+//! Reference libraries that can be validated directly, without a .bib export
+
+pub mod git;
+pub mod grobid;
+pub mod mendeley;
+pub mod zotero;
+
+use thiserror::Error;
+
+#[derive(Error, Debug)]
+pub enum SourceError {
+    #[error("HTTP request failed: {0}")]
+    RequestError(#[from] reqwest::Error),
+    #[error("{service} returned {status}: {message}")]
+    ApiError {
+        service: &'static str,
+        status: reqwest::StatusCode,
+        message: String,
+    },
+    #[error("Failed to parse response: {0}")]
+    ParseError(String),
+    #[error("git failed: {0}")]
+    GitError(String),
+    #[error("IO error: {0}")]
+    Io(#[from] std::io::Error),
+}
@@ -0,0 +1,531 @@
+use super::SourceError;
+use crate::entry::{DiscrepancyField, Entry, Severity};
+use crate::lint::{normalize_isbn, normalize_issn};
+use crate::parser::{extract_arxiv_from_url, extract_year_from_string, is_arxiv_id};
+use crate::report::EntryReport;
+use reqwest::{Client, Method, RequestBuilder, Response};
+use serde::Deserialize;
+use serde_json::{Map, Value};
+use std::collections::HashMap;
+use std::fmt;
+use std::sync::Arc;
+
+const ZOTERO_API_BASE: &str = "https://api.zotero.org";
+const USER_AGENT: &str = "bibval/0.1.0 (https://github.com/femtomc/bibval)";
+
+/// Items fetched per page (the API maximum)
+const PAGE_SIZE: usize = 100;
+
+/// Most objects the API accepts in a single write request
+const WRITE_BATCH_SIZE: usize = 50;
+
+/// Creator roles treated as the work's authors, in order of preference
+const AUTHOR_ROLES: &[&str] = &["author", "programmer", "inventor", "presenter"];
+
+/// A personal or group library
+#[derive(Debug, Clone, Copy)]
+pub enum Library {
+    User(u64),
+    Group(u64),
+}
+
+impl fmt::Display for Library {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Library::User(id) => write!(f, "users/{}", id),
+            Library::Group(id) => write!(f, "groups/{}", id),
+        }
+    }
+}
+
+/// An item as returned by the Zotero Web API; `data` holds every field of its type
+#[derive(Debug, Clone, Deserialize)]
+pub struct ZoteroItem {
+    pub key: String,
+    pub version: u64,
+    pub data: Map<String, Value>,
+}
+
+impl ZoteroItem {
+    /// A string field, if present and not blank
+    fn get(&self, field: &str) -> Option<&str> {
+        self.data
+            .get(field)
+            .and_then(Value::as_str)
+            .map(str::trim)
+            .filter(|value| !value.is_empty())
+    }
+
+    fn item_type(&self) -> &str {
+        self.get("itemType").unwrap_or("")
+    }
+
+    /// A `Key: value` line from the free-form Extra field
+    fn extra(&self, name: &str) -> Option<&str> {
+        self.get("extra")?.lines().find_map(|line| {
+            let (key, value) = line.split_once(':')?;
+            key.trim()
+                .eq_ignore_ascii_case(name)
+                .then(|| value.trim())
+                .filter(|value| !value.is_empty())
+        })
+    }
+}
+
+/// Changed fields to write back to one item
+#[derive(Debug, Clone)]
+pub struct ItemUpdate {
+    pub key: String,
+    pub version: u64,
+    pub fields: Map<String, Value>,
+}
+
+/// Outcome of writing updates back to the library
+#[derive(Debug, Default)]
+pub struct UpdateSummary {
+    pub updated: usize,
+    /// Item key and error message for each rejected update
+    pub failed: Vec<(String, String)>,
+}
+
+#[derive(Debug, Deserialize)]
+struct WriteResponse {
+    #[serde(default)]
+    successful: HashMap<String, Value>,
+    #[serde(default)]
+    failed: HashMap<String, WriteFailure>,
+}
+
+#[derive(Debug, Deserialize)]
+struct WriteFailure {
+    message: String,
+}
+
+pub struct ZoteroClient {
+    client: Client,
+    library: Library,
+    api_key: Option<String>,
+}
+
+impl ZoteroClient {
+    pub fn new(library: Library, api_key: Option<String>) -> Self {
+        let client = Client::builder()
+            .user_agent(USER_AGENT)
+            .build()
+            .expect("Failed to create HTTP client");
+        Self {
+            client,
+            library,
+            api_key,
+        }
+    }
+
+    fn request(&self, method: Method, path: &str) -> RequestBuilder {
+        let url = format!("{}/{}/{}", ZOTERO_API_BASE, self.library, path);
+        let request = self
+            .client
+            .request(method, url)
+            .header("Zotero-API-Version", "3");
+        match &self.api_key {
+            Some(key) => request.header("Zotero-API-Key", key),
+            None => request,
+        }
+    }
+
+    /// Fetch every top-level item of the library (or of one collection), skipping notes
+    /// and attachments
+    pub async fn fetch_items(
+        &self,
+        collection: Option<&str>,
+    ) -> Result<Vec<ZoteroItem>, SourceError> {
+        let path = match collection {
+            Some(collection) => format!("collections/{}/items/top", collection),
+            None => "items/top".to_string(),
+        };
+
+        let mut items = Vec::new();
+        let mut start = 0;
+        loop {
+            let response = self
+                .request(Method::GET, &path)
+                .query(&[("format", "json")])
+                .query(&[("limit", PAGE_SIZE), ("start", start)])
+                .send()
+                .await?;
+            let response = check_status(response).await?;
+
+            let total: Option<usize> = response
+                .headers()
+                .get("Total-Results")
+                .and_then(|v| v.to_str().ok())
+                .and_then(|v| v.parse().ok());
+            let page: Vec<ZoteroItem> = response.json().await?;
+
+            start += page.len();
+            let done = page.is_empty() || total.is_none_or(|total| start >= total);
+            items.extend(
+                page.into_iter()
+                    .filter(|item| !matches!(item.item_type(), "note" | "attachment")),
+            );
+            if done {
+                return Ok(items);
+            }
+        }
+    }
+
+    /// Apply partial updates, a batch at a time. Each update carries the item version it
+    /// was computed from, so items edited in the meantime are rejected rather than
+    /// overwritten.
+    pub async fn update_items(&self, updates: &[ItemUpdate]) -> Result<UpdateSummary, SourceError> {
+        let mut summary = UpdateSummary::default();
+
+        for batch in updates.chunks(WRITE_BATCH_SIZE) {
+            let body: Vec<Value> = batch
+                .iter()
+                .map(|update| {
+                    let mut object = update.fields.clone();
+                    object.insert("key".to_string(), update.key.clone().into());
+                    object.insert("version".to_string(), update.version.into());
+                    Value::Object(object)
+                })
+                .collect();
+
+            let response = self
+                .request(Method::POST, "items")
+                .json(&body)
+                .send()
+                .await?;
+            let response: WriteResponse = check_status(response).await?.json().await?;
+
+            summary.updated += response.successful.len();
+            for (index, failure) in response.failed {
+                let key = index
+                    .parse::<usize>()
+                    .ok()
+                    .and_then(|i| batch.get(i))
+                    .map(|update| update.key.clone())
+                    .unwrap_or(index);
+                summary.failed.push((key, failure.message));
+            }
+        }
+
+        Ok(summary)
+    }
+}
+
+async fn check_status(response: Response) -> Result<Response, SourceError> {
+    let status = response.status();
+    if status.is_success() {
+        return Ok(response);
+    }
+    let message = response.text().await.unwrap_or_default();
+    Err(SourceError::ApiError {
+        service: "Zotero",
+        status,
+        message: message.trim().to_string(),
+    })
+}
+
+/// Map a Zotero item to an entry. The citation key comes from a Better BibTeX
+/// `Citation Key:` line or the `citationKey` field when set, otherwise the item key.
+pub fn item_to_entry(item: &ZoteroItem) -> Entry {
+    let key = item
+        .get("citationKey")
+        .or_else(|| item.extra("Citation Key"))
+        .unwrap_or(&item.key);
+    let mut entry = Entry::new(key.to_string(), entry_type(item.item_type()).to_string());
+
+    let mut fields = Vec::new();
+
+    if let Some(title) = item.get("title") {
+        entry.title = Some(title.into());
+        fields.push(("title", title.to_string()));
+    }
+
+    let authors = authors(item);
+    if !authors.is_empty() {
+        let bibtex_names: Vec<String> = authors
+            .iter()
+            .map(|(first, last)| match first {
+                Some(first) => format!("{}, {}", last, first),
+                None => format!("{{{}}}", last),
+            })
+            .collect();
+        fields.push(("author", bibtex_names.join(" and ")));
+        entry.authors = authors
+            .iter()
+            .map(|(first, last)| match first {
+                Some(first) => Arc::from(format!("{} {}", first, last)),
+                None => Arc::from(last.as_str()),
+            })
+            .collect();
+    }
+
+    if let Some(date) = item.get("date") {
+        entry.year = extract_year_from_string(date);
+        if let Some(year) = entry.year {
+            fields.push(("year", year.to_string()));
+        }
+    }
+
+    if let Some(journal) = item.get("publicationTitle") {
+        entry.venue = Some(journal.into());
+        fields.push(("journal", journal.to_string()));
+    } else if let Some(booktitle) = item.get("proceedingsTitle").or(item.get("bookTitle")) {
+        entry.venue = Some(booktitle.into());
+        fields.push(("booktitle", booktitle.to_string()));
+    }
+
+    entry.doi = item
+        .get("DOI")
+        .or_else(|| item.extra("DOI"))
+        .map(str::to_string);
+    entry.url = item.get("url").map(str::to_string);
+    entry.arxiv_id = arxiv_id(item);
+
+    for (name, field) in [
+        ("doi", entry.doi.clone()),
+        ("url", entry.url.clone()),
+        ("eprint", entry.arxiv_id.clone()),
+    ] {
+        if let Some(value) = field {
+            fields.push((name, value));
+        }
+    }
+    for (name, field) in [
+        ("volume", "volume"),
+        ("number", "issue"),
+        ("pages", "pages"),
+        ("publisher", "publisher"),
+        ("isbn", "ISBN"),
+        ("issn", "ISSN"),
+    ] {
+        if let Some(value) = item.get(field) {
+            fields.push((name, value.to_string()));
+        }
+    }
+
+    entry.fields = fields
+        .into_iter()
+        .map(|(name, value)| (name.to_string(), value))
+        .collect();
+    entry.isbn = entry.field("isbn").and_then(normalize_isbn);
+    entry.issn = entry.field("issn").and_then(normalize_issn);
+    entry
+}
+
+/// Closest BibTeX entry type for a Zotero item type
+fn entry_type(item_type: &str) -> &'static str {
+    match item_type {
+        "journalArticle" | "magazineArticle" | "newspaperArticle" | "preprint" => "article",
+        "conferencePaper" => "inproceedings",
+        "book" => "book",
+        "bookSection" => "incollection",
+        "thesis" => "phdthesis",
+        "report" => "techreport",
+        "computerProgram" => "software",
+        "dataset" => "dataset",
+        "webpage" | "blogPost" | "forumPost" => "online",
+        "patent" => "patent",
+        _ => "misc",
+    }
+}
+
+/// Authors as (first name, last name); single-field names have no first name
+fn authors(item: &ZoteroItem) -> Vec<(Option<String>, String)> {
+    let Some(creators) = item.data.get("creators").and_then(Value::as_array) else {
+        return Vec::new();
+    };
+    let name_of = |creator: &Value| {
+        let text = |field| creator.get(field).and_then(Value::as_str).map(str::trim);
+        match (text("firstName"), text("lastName"), text("name")) {
+            (first, Some(last), _) if !last.is_empty() => Some((
+                first.filter(|f| !f.is_empty()).map(str::to_string),
+                last.to_string(),
+            )),
+            (_, _, Some(name)) if !name.is_empty() => Some((None, name.to_string())),
+            _ => None,
+        }
+    };
+
+    let role = AUTHOR_ROLES.iter().find(|role| {
+        creators
+            .iter()
+            .any(|c| c.get("creatorType").and_then(Value::as_str) == Some(**role))
+    });
+    creators
+        .iter()
+        .filter(|c| {
+            role.is_none_or(|role| c.get("creatorType").and_then(Value::as_str) == Some(*role))
+        })
+        .filter_map(name_of)
+        .collect()
+}
+
+fn arxiv_id(item: &ZoteroItem) -> Option<String> {
+    let tagged = item
+        .get("archiveID")
+        .or_else(|| item.extra("arXiv"))
+        .map(|id| {
+            id.strip_prefix("arXiv:")
+                .or_else(|| id.strip_prefix("arxiv:"))
+                .unwrap_or(id)
+                .trim()
+        })
+        .filter(|id| is_arxiv_id(id));
+    match tagged {
+        Some(id) => Some(id.to_string()),
+        None => item.get("url").and_then(extract_arxiv_from_url),
+    }
+}
+
+/// Fields to correct on an item, taken from the consensus of the validators that
+/// matched it. Only fields the item type has are written.
+pub fn corrections(item: &ZoteroItem, report: &EntryReport) -> Option<ItemUpdate> {
+    // The fused result is the one without a matched entry of its own
+    let fused = report
+        .validation_results
+        .iter()
+        .find(|result| result.matched_entry.is_none())?;
+
+    let mut fields = Map::new();
+    for discrepancy in &fused.discrepancies {
+        let field = match discrepancy.field {
+            DiscrepancyField::Title if discrepancy.severity == Severity::Error => "title",
+            DiscrepancyField::Year if discrepancy.severity == Severity::Error => "date",
+            DiscrepancyField::Doi if item.get("DOI").is_none() => "DOI",
+            _ => continue,
+        };
+        if !item.data.contains_key(field) {
+            continue;
+        }
+        let value = match (field, item.get("date")) {
+            ("date", Some(date)) => with_year(date, &discrepancy.remote_value),
+            _ => discrepancy.remote_value.clone(),
+        };
+        fields.insert(field.to_string(), value.into());
+    }
+
+    (!fields.is_empty()).then(|| ItemUpdate {
+        key: item.key.clone(),
+        version: item.version,
+        fields,
+    })
+}
+
+/// A Zotero date ("2017-12-04", "December 4, 2017") with its year replaced, keeping the
+/// month and day; just the year when the date has none
+fn with_year(date: &str, year: &str) -> String {
+    let bytes = date.as_bytes();
+    let is_digit = |i: usize| bytes.get(i).is_some_and(u8::is_ascii_digit);
+    let start = (0..bytes.len())
+        .find(|&i| (i == 0 || !is_digit(i - 1)) && (i..i + 4).all(is_digit) && !is_digit(i + 4));
+    match start {
+        Some(start) => format!("{}{}{}", &date[..start], year, &date[start + 4..]),
+        None => year.to_string(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::entry::{ApiSource, Discrepancy, ValidationResult};
+    use crate::report::EntryStatus;
+
+    fn item(data: Value) -> ZoteroItem {
+        serde_json::from_value(serde_json::json!({
+            "key": "ABCD2345",
+            "version": 7,
+            "data": data,
+        }))
+        .unwrap()
+    }
+
+    #[test]
+    fn maps_items_to_entries() {
+        let item = item(serde_json::json!({
+            "itemType": "conferencePaper",
+            "title": "Attention Is All You Need",
+            "creators": [
+                {"creatorType": "author", "firstName": "Ashish", "lastName": "Vaswani"},
+                {"creatorType": "author", "name": "Google Brain"},
+                {"creatorType": "editor", "firstName": "I.", "lastName": "Guyon"}
+            ],
+            "date": "2017-12-04",
+            "proceedingsTitle": "Advances in Neural Information Processing Systems",
+            "DOI": "",
+            "url": "https://arxiv.org/abs/1706.03762",
+            "extra": "Citation Key: vaswani2017attention",
+            "pages": "5998-6008"
+        }));
+
+        let entry = item_to_entry(&item);
+        assert_eq!(entry.key, "vaswani2017attention");
+        assert_eq!(entry.entry_type, "inproceedings");
+        assert_eq!(entry.year, Some(2017));
+        assert_eq!(entry.authors.len(), 2);
+        assert_eq!(&*entry.authors[0], "Ashish Vaswani");
+        assert_eq!(entry.doi, None);
+        assert_eq!(entry.arxiv_id.as_deref(), Some("1706.03762"));
+        assert_eq!(
+            entry.field("author"),
+            Some("Vaswani, Ashish and {Google Brain}")
+        );
+        assert_eq!(
+            entry.field("booktitle"),
+            Some("Advances in Neural Information Processing Systems")
+        );
+        assert_eq!(entry.field("pages"), Some("5998-6008"));
+    }
+
+    #[test]
+    fn corrections_use_consensus_and_existing_fields() {
+        let item = item(serde_json::json!({
+            "itemType": "journalArticle",
+            "title": "A Paper",
+            "date": "2019",
+            "DOI": ""
+        }));
+        let discrepancy = |field, severity, remote: &str| Discrepancy {
+            field,
+            severity,
+            local_value: String::new(),
+            remote_value: remote.to_string(),
+            message: String::new(),
+        };
+        let report = EntryReport {
+            entry: item_to_entry(&item),
+            status: EntryStatus::Error,
+            validation_results: vec![ValidationResult {
+                source: ApiSource::CrossRef,
+                matched_entry: None,
+                confidence: 1.0,
+                discrepancies: vec![
+                    discrepancy(DiscrepancyField::Year, Severity::Error, "2020"),
+                    discrepancy(DiscrepancyField::Doi, Severity::Warning, "10.1000/x"),
+                    discrepancy(DiscrepancyField::Authors, Severity::Warning, "B. Smith"),
+                ],
+            }],
+            lints: Vec::new(),
+            replacement: None,
+            citations: None,
+            open_access: None,
+            code: None,
+            trace: Vec::new(),
+        };
+
+        let update = corrections(&item, &report).unwrap();
+        assert_eq!(update.version, 7);
+        assert_eq!(update.fields.len(), 2);
+        assert_eq!(update.fields["date"], "2020");
+        assert_eq!(update.fields["DOI"], "10.1000/x");
+    }
+
+    #[test]
+    fn year_corrections_keep_the_month_and_day() {
+        assert_eq!(with_year("2017-12-04", "2018"), "2018-12-04");
+        assert_eq!(with_year("December 4, 2017", "2018"), "December 4, 2018");
+        assert_eq!(with_year("04/12/2017", "2018"), "04/12/2018");
+        assert_eq!(with_year("in press", "2018"), "2018");
+    }
+}
@@ -0,0 +1,89 @@
+//! Reports rendered through user-supplied Tera templates, for output formats bibval
+//! doesn't have built in
+
+use crate::report::Report;
+use std::error::Error as _;
+use std::path::Path;
+use tera::{Context, Tera};
+use thiserror::Error;
+
+#[derive(Error, Debug)]
+pub enum TemplateError {
+    #[error("Failed to read template: {0}")]
+    Io(#[from] std::io::Error),
+    #[error("{0}")]
+    Render(String),
+}
+
+impl From<tera::Error> for TemplateError {
+    fn from(e: tera::Error) -> Self {
+        // Tera's own message only names the template; the cause and its position are
+        // further down the chain
+        let mut message = e.to_string();
+        let mut source = e.source();
+        while let Some(cause) = source {
+            message.push_str(": ");
+            message.push_str(&cause.to_string());
+            source = cause.source();
+        }
+        TemplateError::Render(message)
+    }
+}
+
+/// Render the report through the template at `path`. The template sees the same data as
+/// `--format json` (`summary`, `score`, `entries` and `statistics`), and its output is
+/// HTML-escaped when the file is named `.html`, `.htm` or `.xml`.
+pub fn render(report: &Report, path: &Path) -> Result<String, TemplateError> {
+    let source = std::fs::read_to_string(path)?;
+    let name = path
+        .file_name()
+        .map(|n| n.to_string_lossy().into_owned())
+        .unwrap_or_default();
+    render_str(report, &name, &source)
+}
+
+fn render_str(report: &Report, name: &str, source: &str) -> Result<String, TemplateError> {
+    let mut tera = Tera::default();
+    tera.add_raw_template(name, source)?;
+    let context = Context::from_serialize(report.data())?;
+    Ok(tera.render(name, &context)?)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::entry::{ApiSource, Entry};
+    use crate::report::{EntryReport, EntryStatus};
+
+    #[test]
+    fn templates_see_the_report_data() {
+        let mut entry = Entry::new("smith2020".to_string(), "article".to_string());
+        entry.title = Some("Graphs <and> Trees".into());
+        let mut report = Report::new();
+        report.add(EntryReport {
+            entry,
+            status: EntryStatus::Ok(ApiSource::Dblp),
+            validation_results: Vec::new(),
+            lints: Vec::new(),
+            replacement: None,
+            citations: None,
+            open_access: None,
+            code: None,
+            trace: Vec::new(),
+        });
+
+        let template = "{{ summary.total }} checked\n\
+            {% for e in entries %}{{ e.entry.key }}: {{ e.entry.title }}{% endfor %}";
+        assert_eq!(
+            render_str(&report, "audit.txt", template).unwrap(),
+            "1 checked\nsmith2020: Graphs <and> Trees"
+        );
+        assert_eq!(
+            render_str(&report, "audit.html", template).unwrap(),
+            "1 checked\nsmith2020: Graphs &lt;and&gt; Trees"
+        );
+
+        let error = render_str(&report, "audit.txt", "{{ missing.value }}").unwrap_err();
+        assert!(error.to_string().contains("missing"));
+    }
+}
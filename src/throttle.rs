@@ -0,0 +1,265 @@
+use crate::entry::ApiSource;
+use crate::validators::ValidatorError;
+use std::collections::HashMap;
+use std::future::Future;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+use tokio::sync::Notify;
+
+/// Requests allowed in flight per source before any responses have come back
+const INITIAL_LIMIT: f64 = 4.0;
+const MIN_LIMIT: f64 = 1.0;
+const MAX_LIMIT: f64 = 32.0;
+
+/// Adaptive (AIMD) concurrency limit for a single API.
+///
+/// Every healthy response grows the limit by `1 / limit`, i.e. by about one request per
+/// round trip, and a rate-limit response halves it. Requests that started before the
+/// last back-off don't halve it again, so a burst of 429s from one window counts once.
+pub struct Throttle {
+    state: Mutex<ThrottleState>,
+    released: Notify,
+}
+
+struct ThrottleState {
+    limit: f64,
+    in_flight: usize,
+    last_backoff: Option<Instant>,
+    stats: ThrottleStats,
+    first_start: Option<Instant>,
+    last_finish: Option<Instant>,
+}
+
+/// What a throttle saw over its lifetime, for `--profile`
+#[derive(Debug, Clone, Default)]
+pub struct ThrottleStats {
+    pub requests: u64,
+    pub rate_limited: u64,
+    /// Requests that failed for any other reason (network, server or parse errors)
+    pub errors: u64,
+    /// Concurrency limit at the end of the run
+    pub limit: f64,
+    /// Lowest limit reached after backing off
+    pub min_limit: f64,
+    /// Time from the first request starting to the last one finishing
+    pub elapsed: Duration,
+    /// Sum of request latencies
+    pub busy: Duration,
+}
+
+impl ThrottleStats {
+    pub fn requests_per_second(&self) -> f64 {
+        let secs = self.elapsed.as_secs_f64();
+        if secs > 0.0 {
+            self.requests as f64 / secs
+        } else {
+            0.0
+        }
+    }
+
+    pub fn mean_latency(&self) -> Duration {
+        match self.requests {
+            0 => Duration::ZERO,
+            n => self.busy / n as u32,
+        }
+    }
+}
+
+/// Slot in a throttle's window, released when dropped so cancelled requests don't leak
+struct Permit<'a> {
+    throttle: &'a Throttle,
+}
+
+impl Drop for Permit<'_> {
+    fn drop(&mut self) {
+        self.throttle.state.lock().unwrap().in_flight -= 1;
+        self.throttle.released.notify_waiters();
+    }
+}
+
+impl Throttle {
+    pub fn new() -> Self {
+        Self {
+            state: Mutex::new(ThrottleState {
+                limit: INITIAL_LIMIT,
+                in_flight: 0,
+                last_backoff: None,
+                stats: ThrottleStats {
+                    limit: INITIAL_LIMIT,
+                    min_limit: INITIAL_LIMIT,
+                    ..ThrottleStats::default()
+                },
+                first_start: None,
+                last_finish: None,
+            }),
+            released: Notify::new(),
+        }
+    }
+
+    /// Run `request` once a slot is free, adjusting the limit based on its outcome
+    pub async fn run<T, F>(&self, request: F) -> Result<T, ValidatorError>
+    where
+        F: Future<Output = Result<T, ValidatorError>>,
+    {
+        let _permit = self.acquire().await;
+        let started = Instant::now();
+        let result = request.await;
+        self.record(started, &result);
+        result
+    }
+
+    async fn acquire(&self) -> Permit<'_> {
+        loop {
+            // Register for wake-ups before checking, so a release in between isn't missed
+            let released = self.released.notified();
+            {
+                let mut state = self.state.lock().unwrap();
+                if (state.in_flight as f64) < state.limit.floor() {
+                    state.in_flight += 1;
+                    return Permit { throttle: self };
+                }
+            }
+            released.await;
+        }
+    }
+
+    fn record<T>(&self, started: Instant, result: &Result<T, ValidatorError>) {
+        let now = Instant::now();
+        let mut state = self.state.lock().unwrap();
+
+        match result {
+            Err(ValidatorError::RateLimited) => {
+                state.stats.rate_limited += 1;
+                if state.last_backoff.is_none_or(|backoff| started >= backoff) {
+                    state.limit = (state.limit / 2.0).max(MIN_LIMIT);
+                    state.last_backoff = Some(now);
+                }
+            }
+            Ok(_) => state.limit = (state.limit + 1.0 / state.limit).min(MAX_LIMIT),
+            // Network and parse errors say nothing about the server's capacity
+            Err(_) => state.stats.errors += 1,
+        }
+
+        state.first_start = Some(state.first_start.map_or(started, |first| first.min(started)));
+        state.last_finish = Some(now);
+        state.stats.requests += 1;
+        state.stats.busy += now - started;
+        state.stats.limit = state.limit;
+        state.stats.min_limit = state.stats.min_limit.min(state.limit);
+        if let Some(first) = state.first_start {
+            state.stats.elapsed = now - first;
+        }
+        drop(state);
+
+        // A grown limit may let a waiting request through before this permit is released
+        self.released.notify_waiters();
+    }
+
+    pub fn limit(&self) -> f64 {
+        self.state.lock().unwrap().limit
+    }
+
+    pub fn stats(&self) -> ThrottleStats {
+        self.state.lock().unwrap().stats.clone()
+    }
+}
+
+impl Default for Throttle {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// One throttle per API source, created on first use
+#[derive(Default)]
+pub struct Throttles {
+    by_source: Mutex<HashMap<ApiSource, Arc<Throttle>>>,
+}
+
+impl Throttles {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn get(&self, source: ApiSource) -> Arc<Throttle> {
+        let mut by_source = self.by_source.lock().unwrap();
+        Arc::clone(by_source.entry(source).or_default())
+    }
+
+    /// Run a request against `source` under that source's concurrency limit
+    pub async fn run<T, F>(&self, source: ApiSource, request: F) -> Result<T, ValidatorError>
+    where
+        F: Future<Output = Result<T, ValidatorError>>,
+    {
+        self.get(source).run(request).await
+    }
+
+    /// Statistics for every source that received at least one request, by name
+    pub fn stats(&self) -> Vec<(ApiSource, ThrottleStats)> {
+        let by_source = self.by_source.lock().unwrap();
+        let mut stats: Vec<_> = by_source
+            .iter()
+            .map(|(source, throttle)| (*source, throttle.stats()))
+            .filter(|(_, stats)| stats.requests > 0)
+            .collect();
+        stats.sort_by_key(|(source, _)| source.to_string());
+        stats
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn backs_off_on_rate_limit_and_recovers() {
+        let throttle = Throttle::new();
+
+        let _ = throttle.run(async { Err::<(), _>(ValidatorError::RateLimited) }).await;
+        assert_eq!(throttle.limit(), INITIAL_LIMIT / 2.0);
+
+        for _ in 0..10 {
+            let _ = throttle.run(async { Ok(()) }).await;
+        }
+        assert!(throttle.limit() > INITIAL_LIMIT / 2.0);
+
+        let stats = throttle.stats();
+        assert_eq!(stats.requests, 11);
+        assert_eq!(stats.rate_limited, 1);
+        assert_eq!(stats.min_limit, INITIAL_LIMIT / 2.0);
+    }
+
+    #[tokio::test]
+    async fn burst_of_rate_limits_backs_off_once() {
+        let throttle = Throttle::new();
+        let rate_limited = || async {
+            tokio::task::yield_now().await;
+            Err::<(), _>(ValidatorError::RateLimited)
+        };
+
+        // Both requests are in flight before either response arrives
+        let _ = tokio::join!(throttle.run(rate_limited()), throttle.run(rate_limited()));
+        assert_eq!(throttle.limit(), INITIAL_LIMIT / 2.0);
+    }
+
+    #[tokio::test]
+    async fn never_exceeds_limit() {
+        let throttle = Throttle::new();
+        let in_flight = std::sync::atomic::AtomicUsize::new(0);
+        let peak = std::sync::atomic::AtomicUsize::new(0);
+
+        let requests = (0..20).map(|_| {
+            throttle.run(async {
+                use std::sync::atomic::Ordering;
+                let now = in_flight.fetch_add(1, Ordering::SeqCst) + 1;
+                peak.fetch_max(now, Ordering::SeqCst);
+                tokio::task::yield_now().await;
+                in_flight.fetch_sub(1, Ordering::SeqCst);
+                Err::<(), _>(ValidatorError::NotFound)
+            })
+        });
+        futures::future::join_all(requests).await;
+
+        assert!(peak.into_inner() <= INITIAL_LIMIT as usize);
+    }
+}
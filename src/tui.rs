@@ -0,0 +1,489 @@
+//! Interactive browser for the results of a run
+
+use crate::entry::{Discrepancy, Severity};
+use crate::report::{EntryReport, EntryStatus, Report};
+use ratatui::crossterm::event::{self, Event, KeyCode, KeyEvent, KeyEventKind};
+use ratatui::layout::{Constraint, Direction, Layout};
+use ratatui::style::{Color, Modifier, Style};
+use ratatui::text::{Line, Span};
+use ratatui::widgets::{Block, Borders, List, ListItem, ListState, Paragraph, Wrap};
+use ratatui::{DefaultTerminal, Frame};
+use std::collections::BTreeSet;
+use std::io::{self, IsTerminal};
+
+const HELP: &str = "↑/↓ move  / filter  tab status  d DOI  a arXiv  u URL  m mark  q quit";
+
+/// Which entries the list shows, by status
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum StatusFilter {
+    All,
+    Errors,
+    Warnings,
+    Failed,
+    NotFound,
+    Ok,
+}
+
+impl StatusFilter {
+    fn next(self) -> Self {
+        match self {
+            StatusFilter::All => StatusFilter::Errors,
+            StatusFilter::Errors => StatusFilter::Warnings,
+            StatusFilter::Warnings => StatusFilter::Failed,
+            StatusFilter::Failed => StatusFilter::NotFound,
+            StatusFilter::NotFound => StatusFilter::Ok,
+            StatusFilter::Ok => StatusFilter::All,
+        }
+    }
+
+    fn accepts(self, status: &EntryStatus) -> bool {
+        match self {
+            StatusFilter::All => true,
+            StatusFilter::Errors => matches!(status, EntryStatus::Error),
+            StatusFilter::Warnings => matches!(status, EntryStatus::Warning),
+            StatusFilter::Failed => matches!(status, EntryStatus::Failed(_)),
+            StatusFilter::NotFound => matches!(status, EntryStatus::NotFound),
+            StatusFilter::Ok => matches!(status, EntryStatus::Ok(_)),
+        }
+    }
+
+    fn label(self) -> &'static str {
+        match self {
+            StatusFilter::All => "all",
+            StatusFilter::Errors => "errors",
+            StatusFilter::Warnings => "warnings",
+            StatusFilter::Failed => "failed",
+            StatusFilter::NotFound => "not found",
+            StatusFilter::Ok => "ok",
+        }
+    }
+}
+
+/// Browser state, kept apart from drawing so it can be driven without a terminal
+struct App<'a> {
+    entries: Vec<&'a EntryReport>,
+    /// Indices into `entries` that pass the filters, in display order
+    visible: Vec<usize>,
+    list: ListState,
+    status: StatusFilter,
+    query: String,
+    editing_query: bool,
+    marked: BTreeSet<String>,
+    message: Option<String>,
+}
+
+impl<'a> App<'a> {
+    fn new(report: &'a Report) -> Self {
+        // Worst entries first, as in the printed report
+        let mut entries: Vec<_> = report.entries.iter().collect();
+        entries.sort_by_key(|e| status_rank(&e.status));
+
+        let mut app = Self {
+            entries,
+            visible: Vec::new(),
+            list: ListState::default(),
+            status: StatusFilter::All,
+            query: String::new(),
+            editing_query: false,
+            marked: BTreeSet::new(),
+            message: None,
+        };
+        app.refilter();
+        app
+    }
+
+    fn refilter(&mut self) {
+        let query = self.query.to_lowercase();
+        self.visible = self
+            .entries
+            .iter()
+            .enumerate()
+            .filter(|(_, e)| self.status.accepts(&e.status))
+            .filter(|(_, e)| {
+                query.is_empty()
+                    || e.entry.key.to_lowercase().contains(&query)
+                    || e.entry
+                        .title
+                        .as_deref()
+                        .is_some_and(|t| t.to_lowercase().contains(&query))
+            })
+            .map(|(idx, _)| idx)
+            .collect();
+        self.list.select((!self.visible.is_empty()).then_some(0));
+    }
+
+    fn selected(&self) -> Option<&'a EntryReport> {
+        let idx = *self.visible.get(self.list.selected()?)?;
+        Some(self.entries[idx])
+    }
+
+    fn move_by(&mut self, delta: isize) {
+        if self.visible.is_empty() {
+            return;
+        }
+        let current = self.list.selected().unwrap_or(0) as isize;
+        let last = self.visible.len() as isize - 1;
+        self.list
+            .select(Some((current + delta).clamp(0, last) as usize));
+    }
+
+    fn toggle_mark(&mut self) {
+        if let Some(entry_report) = self.selected() {
+            let key = entry_report.entry.key.clone();
+            if !self.marked.remove(&key) {
+                self.marked.insert(key);
+            }
+        }
+    }
+
+    fn open(&mut self, what: &str, url: Option<String>) {
+        self.message = Some(match url {
+            Some(url) => match webbrowser::open(&url) {
+                Ok(()) => format!("Opened {}", url),
+                Err(e) => format!("Could not open {}: {}", url, e),
+            },
+            None => format!("Entry has no {}", what),
+        });
+    }
+
+    /// Apply a key press; returns false once the browser should close
+    fn handle_key(&mut self, key: KeyEvent) -> bool {
+        if self.editing_query {
+            match key.code {
+                KeyCode::Enter | KeyCode::Esc => self.editing_query = false,
+                KeyCode::Backspace => {
+                    self.query.pop();
+                    self.refilter();
+                }
+                KeyCode::Char(c) => {
+                    self.query.push(c);
+                    self.refilter();
+                }
+                _ => {}
+            }
+            return true;
+        }
+
+        self.message = None;
+        match key.code {
+            KeyCode::Char('q') | KeyCode::Esc => return false,
+            KeyCode::Down | KeyCode::Char('j') => self.move_by(1),
+            KeyCode::Up | KeyCode::Char('k') => self.move_by(-1),
+            KeyCode::PageDown => self.move_by(10),
+            KeyCode::PageUp => self.move_by(-10),
+            KeyCode::Char('/') => self.editing_query = true,
+            KeyCode::Tab => {
+                self.status = self.status.next();
+                self.refilter();
+            }
+            KeyCode::Char('m') => self.toggle_mark(),
+            KeyCode::Char('d') => {
+                let doi = self.selected().and_then(|e| e.entry.doi.clone());
+                self.open("DOI", doi.map(|doi| format!("https://doi.org/{}", doi)));
+            }
+            KeyCode::Char('a') => {
+                let arxiv = self.selected().and_then(|e| e.entry.arxiv_id.clone());
+                self.open(
+                    "arXiv ID",
+                    arxiv.map(|id| format!("https://arxiv.org/abs/{}", id)),
+                );
+            }
+            KeyCode::Char('u') => {
+                let url = self.selected().and_then(|e| e.entry.url.clone());
+                self.open("URL", url);
+            }
+            _ => {}
+        }
+        true
+    }
+}
+
+/// Browse a report in the terminal until the user quits. Returns the keys of the
+/// entries marked for fixing.
+pub fn browse(report: &Report) -> io::Result<Vec<String>> {
+    if !io::stdin().is_terminal() {
+        return Err(io::Error::other(
+            "the results browser needs an interactive terminal",
+        ));
+    }
+    let mut terminal = ratatui::try_init()?;
+    let result = run(&mut terminal, App::new(report));
+    ratatui::restore();
+    result
+}
+
+fn run(terminal: &mut DefaultTerminal, mut app: App) -> io::Result<Vec<String>> {
+    loop {
+        terminal.draw(|frame| draw(frame, &mut app))?;
+        if let Event::Key(key) = event::read()? {
+            if key.kind == KeyEventKind::Press && !app.handle_key(key) {
+                return Ok(app.marked.into_iter().collect());
+            }
+        }
+    }
+}
+
+fn draw(frame: &mut Frame, app: &mut App) {
+    let rows = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([Constraint::Min(3), Constraint::Length(1)])
+        .split(frame.area());
+    let columns = Layout::default()
+        .direction(Direction::Horizontal)
+        .constraints([Constraint::Percentage(40), Constraint::Percentage(60)])
+        .split(rows[0]);
+
+    let items: Vec<ListItem> = app
+        .visible
+        .iter()
+        .map(|&idx| {
+            let entry_report = app.entries[idx];
+            let (label, color) = status_style(&entry_report.status);
+            let mark = if app.marked.contains(&entry_report.entry.key) {
+                "* "
+            } else {
+                "  "
+            };
+            ListItem::new(Line::from(vec![
+                Span::raw(mark),
+                Span::styled(format!("{:<5} ", label), Style::default().fg(color)),
+                Span::raw(entry_report.entry.key.clone()),
+            ]))
+        })
+        .collect();
+
+    let filter = if app.query.is_empty() && !app.editing_query {
+        String::new()
+    } else {
+        format!(
+            " /{}{}",
+            app.query,
+            if app.editing_query { "_" } else { "" }
+        )
+    };
+    let title = format!(
+        " Entries: {} of {} ({}){} ",
+        app.visible.len(),
+        app.entries.len(),
+        app.status.label(),
+        filter
+    );
+    let list = List::new(items)
+        .block(Block::default().borders(Borders::ALL).title(title))
+        .highlight_style(Style::default().add_modifier(Modifier::REVERSED));
+    frame.render_stateful_widget(list, columns[0], &mut app.list);
+
+    let detail = app.selected().map(detail_lines).unwrap_or_default();
+    let detail = Paragraph::new(detail)
+        .block(Block::default().borders(Borders::ALL).title(" Details "))
+        .wrap(Wrap { trim: false });
+    frame.render_widget(detail, columns[1]);
+
+    let footer = app.message.clone().unwrap_or_else(|| HELP.to_string());
+    frame.render_widget(
+        Paragraph::new(footer).style(Style::default().fg(Color::DarkGray)),
+        rows[1],
+    );
+}
+
+/// The detail pane for one entry: its fields, offline findings, and every source match
+fn detail_lines(entry_report: &EntryReport) -> Vec<Line<'static>> {
+    let entry = &entry_report.entry;
+    let bold = Style::default().add_modifier(Modifier::BOLD);
+    let mut lines = vec![Line::from(Span::styled(
+        format!("@{}{{{}}}", entry.entry_type, entry.key),
+        bold,
+    ))];
+
+    let field = |name: &str, value: Option<String>| {
+        value.map(|value| Line::from(format!("  {:<8} {}", name, value)))
+    };
+    lines.extend(field("title", entry.title.as_deref().map(String::from)));
+    if !entry.authors.is_empty() {
+        lines.extend(field("authors", Some(entry.authors.join("; "))));
+    }
+    lines.extend(field("year", entry.year.map(|y| y.to_string())));
+    lines.extend(field("venue", entry.venue.as_deref().map(String::from)));
+    lines.extend(field("doi", entry.doi.clone()));
+    lines.extend(field("arxiv", entry.arxiv_id.clone()));
+    lines.extend(field("url", entry.url.clone()));
+
+    let (label, color) = status_style(&entry_report.status);
+    lines.push(Line::default());
+    lines.push(Line::from(vec![
+        Span::raw("Status: "),
+        Span::styled(label, Style::default().fg(color)),
+    ]));
+    if let EntryStatus::Failed(reason) = &entry_report.status {
+        lines.push(Line::from(format!("  {}", reason)));
+    }
+    if let Some(citations) = &entry_report.citations {
+        lines.push(Line::from(format!(
+            "Cited {} times according to {}",
+            citations.count, citations.source
+        )));
+    }
+    if let Some(open_access) = &entry_report.open_access {
+        lines.push(Line::from(format!("Open access at {}", open_access.url)));
+    }
+    if let Some(code) = &entry_report.code {
+        lines.push(Line::from(format!("Official code at {}", code)));
+    }
+
+    if !entry_report.lints.is_empty() {
+        lines.push(Line::default());
+        lines.push(Line::from(Span::styled("Offline checks", bold)));
+        lines.extend(entry_report.lints.iter().flat_map(discrepancy_lines));
+    }
+
+    for result in &entry_report.validation_results {
+        lines.push(Line::default());
+        let heading = match &result.matched_entry {
+            Some(matched) => format!(
+                "{} ({:.0}% confidence): {}",
+                result.source,
+                result.confidence * 100.0,
+                matched.title.as_deref().unwrap_or("(no title)")
+            ),
+            None => "Consensus across sources".to_string(),
+        };
+        lines.push(Line::from(Span::styled(heading, bold)));
+        if result.discrepancies.is_empty() {
+            lines.push(Line::from("  No discrepancies"));
+        }
+        lines.extend(result.discrepancies.iter().flat_map(discrepancy_lines));
+    }
+
+    if let Some(replacement) = &entry_report.replacement {
+        lines.push(Line::default());
+        lines.push(Line::from(Span::styled(
+            format!("Suggested replacement from {}", replacement.source),
+            bold,
+        )));
+        lines.extend(
+            replacement
+                .bibtex
+                .lines()
+                .map(|line| Line::from(format!("  {}", line))),
+        );
+    }
+
+    lines
+}
+
+fn discrepancy_lines(discrepancy: &Discrepancy) -> Vec<Line<'static>> {
+    let (label, color) = match discrepancy.severity {
+        Severity::Error => ("ERROR", Color::Red),
+        Severity::Warning => ("WARN", Color::Yellow),
+        Severity::Info => ("INFO", Color::Blue),
+    };
+    let mut lines = vec![Line::from(vec![
+        Span::raw("  "),
+        Span::styled(label, Style::default().fg(color)),
+        Span::raw(format!(" {}", discrepancy.message)),
+    ])];
+    let dim = Style::default().fg(Color::DarkGray);
+    if !discrepancy.local_value.is_empty() {
+        lines.push(Line::styled(
+            format!("      local:  {}", discrepancy.local_value),
+            dim,
+        ));
+    }
+    if !discrepancy.remote_value.is_empty() {
+        lines.push(Line::styled(
+            format!("      remote: {}", discrepancy.remote_value),
+            dim,
+        ));
+    }
+    lines
+}
+
+fn status_style(status: &EntryStatus) -> (&'static str, Color) {
+    match status {
+        EntryStatus::Ok(_) => ("OK", Color::Green),
+        EntryStatus::Warning => ("WARN", Color::Yellow),
+        EntryStatus::Error => ("ERROR", Color::Red),
+        EntryStatus::NotFound => ("N/F", Color::DarkGray),
+        EntryStatus::Failed(_) => ("FAIL", Color::Magenta),
+    }
+}
+
+fn status_rank(status: &EntryStatus) -> u8 {
+    match status {
+        EntryStatus::Error => 0,
+        EntryStatus::Failed(_) => 1,
+        EntryStatus::Warning => 2,
+        EntryStatus::NotFound => 3,
+        EntryStatus::Ok(_) => 4,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::entry::{ApiSource, Entry};
+    use ratatui::crossterm::event::KeyModifiers;
+
+    fn entry_report(key: &str, title: &str, status: EntryStatus) -> EntryReport {
+        let mut entry = Entry::new(key.to_string(), "article".to_string());
+        entry.title = Some(title.into());
+        EntryReport {
+            entry,
+            status,
+            validation_results: Vec::new(),
+            lints: Vec::new(),
+            replacement: None,
+            citations: None,
+            open_access: None,
+            code: None,
+            trace: Vec::new(),
+        }
+    }
+
+    fn press(app: &mut App, code: KeyCode) -> bool {
+        app.handle_key(KeyEvent::new(code, KeyModifiers::NONE))
+    }
+
+    #[test]
+    fn entries_are_filtered_by_status_and_query() {
+        let mut report = Report::new();
+        report.add(entry_report(
+            "ok1",
+            "Deep Learning",
+            EntryStatus::Ok(ApiSource::Dblp),
+        ));
+        report.add(entry_report("bad", "Deep Things", EntryStatus::Error));
+        report.add(entry_report("warn", "Shallow", EntryStatus::Warning));
+        let mut app = App::new(&report);
+
+        // Errors sort first
+        assert_eq!(app.selected().unwrap().entry.key, "bad");
+
+        press(&mut app, KeyCode::Char('/'));
+        for c in "deep".chars() {
+            press(&mut app, KeyCode::Char(c));
+        }
+        press(&mut app, KeyCode::Enter);
+        assert_eq!(app.visible.len(), 2);
+
+        press(&mut app, KeyCode::Tab);
+        assert_eq!(app.visible.len(), 1);
+
+        let mut terminal =
+            ratatui::Terminal::new(ratatui::backend::TestBackend::new(100, 20)).unwrap();
+        terminal.draw(|frame| draw(frame, &mut app)).unwrap();
+        let screen: String = terminal
+            .backend()
+            .buffer()
+            .content()
+            .iter()
+            .map(|cell| cell.symbol())
+            .collect();
+        assert!(screen.contains("Entries: 1 of 3 (errors) /deep"));
+        assert!(screen.contains("Deep Things"));
+
+        press(&mut app, KeyCode::Char('m'));
+        assert!(!press(&mut app, KeyCode::Char('q')));
+        assert_eq!(app.marked.iter().collect::<Vec<_>>(), ["bad"]);
+    }
+}
@@ -0,0 +1,165 @@
+use super::{async_trait, Validator, ValidatorError};
+use crate::csl::{item_to_entry, CslItem};
+use crate::entry::Entry;
+use crate::lint::normalize_doi;
+use regex_lite::Regex;
+use reqwest::Client;
+use serde::Deserialize;
+use std::collections::HashMap;
+use std::sync::OnceLock;
+
+const ACM_DL_BASE: &str = "https://dl.acm.org";
+
+/// DOI prefix ACM registers its publications under
+const ACM_DOI_PREFIX: &str = "10.1145/";
+
+/// Whether a DOI was registered by ACM, whose Digital Library is the record of truth
+pub fn is_acm_doi(doi: &str) -> bool {
+    normalize_doi(doi).starts_with(ACM_DOI_PREFIX)
+}
+
+/// Client for the ACM Digital Library, through its citation export and search pages
+pub struct AcmClient {
+    client: Client,
+}
+
+impl AcmClient {
+    pub fn new() -> Self {
+        let client = Client::builder()
+            .user_agent("bibval/0.1.0 (https://github.com/femtomc/bibval)")
+            .build()
+            .expect("Failed to create HTTP client");
+        Self { client }
+    }
+
+    /// Records for ACM DOIs, as exported by the Digital Library in CSL-JSON
+    async fn export(&self, dois: &[String]) -> Result<Vec<Entry>, ValidatorError> {
+        if dois.is_empty() {
+            return Ok(Vec::new());
+        }
+
+        let url = format!(
+            "{}/action/exportCiteProcCitation?dois={}&targetFile=custom-bibtex&format=bibTex",
+            ACM_DL_BASE,
+            urlencoding::encode(&dois.join(","))
+        );
+        let response = self.client.get(&url).send().await?;
+
+        if response.status() == reqwest::StatusCode::TOO_MANY_REQUESTS {
+            return Err(ValidatorError::RateLimited);
+        }
+
+        if !response.status().is_success() {
+            return Ok(Vec::new());
+        }
+
+        let response: ExportResponse = response.json().await.map_err(|e| {
+            ValidatorError::ParseError(format!("Failed to parse ACM DL export: {}", e))
+        })?;
+
+        Ok(response
+            .items
+            .iter()
+            .flat_map(|item| item.values())
+            .map(item_to_entry)
+            .collect())
+    }
+}
+
+impl Default for AcmClient {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Exported records, each keyed by its DOI
+#[derive(Debug, Deserialize)]
+struct ExportResponse {
+    #[serde(default)]
+    items: Vec<HashMap<String, CslItem>>,
+}
+
+/// DOIs of the results on an ACM DL search page, in ranking order
+fn result_dois(html: &str, limit: usize) -> Vec<String> {
+    static REGEX: OnceLock<Regex> = OnceLock::new();
+    let regex = REGEX.get_or_init(|| {
+        Regex::new(
+            r#"class="issue-item__title"[^>]*>\s*<a href="/doi/(?:abs/|full/)?(10\.1145/[^"?#]+)[^"]*""#,
+        )
+        .unwrap()
+    });
+
+    let mut dois: Vec<String> = Vec::new();
+    for capture in regex.captures_iter(html) {
+        let doi = capture[1].to_string();
+        if !dois.contains(&doi) {
+            dois.push(doi);
+        }
+        if dois.len() == limit {
+            break;
+        }
+    }
+    dois
+}
+
+#[async_trait]
+impl Validator for AcmClient {
+    async fn search_by_doi(&self, doi: &str) -> Result<Option<Entry>, ValidatorError> {
+        if !is_acm_doi(doi) {
+            return Ok(None);
+        }
+        Ok(self.export(&[normalize_doi(doi)]).await?.into_iter().next())
+    }
+
+    async fn search_by_title(&self, title: &str) -> Result<Vec<Entry>, ValidatorError> {
+        let url = format!(
+            "{}/action/doSearch?fillQuickSearch=false&expand=dl&pageSize=5&AllField={}",
+            ACM_DL_BASE,
+            urlencoding::encode(&format!("Title:\"{}\"", title.replace('"', "")))
+        );
+        let response = self.client.get(&url).send().await?;
+
+        if response.status() == reqwest::StatusCode::TOO_MANY_REQUESTS {
+            return Err(ValidatorError::RateLimited);
+        }
+
+        if !response.status().is_success() {
+            return Ok(Vec::new());
+        }
+
+        let html = response.text().await?;
+        self.export(&result_dois(&html, 5)).await
+    }
+
+    fn name(&self) -> &'static str {
+        "ACM DL"
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn exports_and_search_results_are_read() {
+        let json = r#"{"items": [{"10.1145/3313831.3376727": {
+            "id": "10.1145/3313831.3376727",
+            "type": "paper-conference",
+            "title": "A Paper",
+            "author": [{"family": "Doe", "given": "Jane"}],
+            "issued": {"date-parts": [[2020, 4, 21]]},
+            "container-title": "Proceedings of the 2020 CHI Conference",
+            "page": "1–14",
+            "DOI": "10.1145/3313831.3376727"
+        }}]}"#;
+        let response: ExportResponse = serde_json::from_str(json).unwrap();
+        let entry = item_to_entry(response.items[0].values().next().unwrap());
+        assert_eq!(entry.year, Some(2020));
+        assert_eq!(entry.field("pages"), Some("1–14"));
+
+        let html = r#"<h5 class="issue-item__title"><a href="/doi/10.1145/1.2?x">A</a></h5>
+            <h5 class="issue-item__title"><a href="/doi/abs/10.1145/3.4">B</a></h5>"#;
+        assert_eq!(result_dois(html, 5), ["10.1145/1.2", "10.1145/3.4"]);
+        assert!(is_acm_doi("https://doi.org/10.1145/3.4"));
+    }
+}
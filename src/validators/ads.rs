@@ -0,0 +1,236 @@
+use super::{async_trait, Validator, ValidatorError};
+use crate::entry::Entry;
+use crate::lint::normalize_doi;
+use reqwest::Client;
+use serde::Deserialize;
+use std::sync::Arc;
+
+const ADS_API_BASE: &str = "https://api.adsabs.harvard.edu/v1/search/query";
+const ADS_FIELDS: &str = "bibcode,title,author,year,pub,doi,identifier,page,volume,doctype";
+
+/// Field of a matched entry holding its ADS bibcode
+pub const ADS_BIBCODE_FIELD: &str = "bibcode";
+
+/// Whether a string has the shape of an ADS bibcode ("2019ApJ...875L...1E")
+pub fn is_bibcode(id: &str) -> bool {
+    id.len() == 19 && id.is_ascii() && id[..4].bytes().all(|b| b.is_ascii_digit())
+}
+
+/// The bibcode of a local entry: a `bibcode` field, the `adsurl` of ADS exports, or a
+/// citation key that is itself a bibcode
+pub fn local_bibcode(entry: &Entry) -> Option<&str> {
+    entry
+        .field(ADS_BIBCODE_FIELD)
+        .or_else(|| {
+            let url = entry.field("adsurl")?;
+            let (_, bibcode) = url.split_once("/abs/")?;
+            let bibcode = bibcode.split('/').next()?;
+            Some(bibcode)
+        })
+        .or(Some(entry.key.as_str()))
+        .filter(|id| is_bibcode(id))
+}
+
+/// Client for the NASA Astrophysics Data System, which needs an API token
+pub struct AdsClient {
+    client: Client,
+    token: String,
+}
+
+impl AdsClient {
+    pub fn new(token: String) -> Self {
+        let client = Client::builder()
+            .user_agent("bibval/0.1.0 (https://github.com/femtomc/bibval)")
+            .build()
+            .expect("Failed to create HTTP client");
+        Self { client, token }
+    }
+
+    /// Look up a record by bibcode
+    pub async fn search_by_bibcode(&self, bibcode: &str) -> Result<Option<Entry>, ValidatorError> {
+        let bibcode = urlencoding::decode(bibcode.trim())
+            .map(|b| b.into_owned())
+            .unwrap_or_else(|_| bibcode.trim().to_string());
+        self.first(&format!("bibcode:\"{}\"", bibcode)).await
+    }
+
+    /// Search by arXiv ID
+    pub async fn search_by_arxiv_id(
+        &self,
+        arxiv_id: &str,
+    ) -> Result<Option<Entry>, ValidatorError> {
+        self.first(&format!("arxiv:{}", arxiv_id.trim())).await
+    }
+
+    async fn first(&self, query: &str) -> Result<Option<Entry>, ValidatorError> {
+        Ok(self.search(query, 1).await?.into_iter().next())
+    }
+
+    async fn search(&self, query: &str, rows: usize) -> Result<Vec<Entry>, ValidatorError> {
+        let url = format!(
+            "{}?q={}&rows={}&fl={}",
+            ADS_API_BASE,
+            urlencoding::encode(query),
+            rows,
+            ADS_FIELDS
+        );
+
+        let response = self
+            .client
+            .get(&url)
+            .bearer_auth(&self.token)
+            .send()
+            .await?;
+
+        if response.status() == reqwest::StatusCode::TOO_MANY_REQUESTS {
+            return Err(ValidatorError::RateLimited);
+        }
+
+        if response.status() == reqwest::StatusCode::UNAUTHORIZED {
+            return Err(ValidatorError::ParseError(
+                "NASA ADS rejected the API token".to_string(),
+            ));
+        }
+
+        if !response.status().is_success() {
+            return Err(ValidatorError::ParseError(format!(
+                "NASA ADS returned {}",
+                response.status()
+            )));
+        }
+
+        let response: SearchResponse = response.json().await.map_err(|e| {
+            ValidatorError::ParseError(format!("Failed to parse NASA ADS response: {}", e))
+        })?;
+
+        Ok(response.response.docs.iter().map(Doc::to_entry).collect())
+    }
+}
+
+#[derive(Debug, Deserialize)]
+struct SearchResponse {
+    response: Docs,
+}
+
+#[derive(Debug, Deserialize)]
+struct Docs {
+    #[serde(default)]
+    docs: Vec<Doc>,
+}
+
+#[derive(Debug, Deserialize)]
+struct Doc {
+    bibcode: String,
+    #[serde(default)]
+    title: Vec<String>,
+    #[serde(default)]
+    author: Vec<String>,
+    year: Option<String>,
+    #[serde(rename = "pub")]
+    publication: Option<String>,
+    #[serde(default)]
+    doi: Vec<String>,
+    #[serde(default)]
+    identifier: Vec<String>,
+    #[serde(default)]
+    page: Vec<String>,
+    volume: Option<String>,
+    doctype: Option<String>,
+}
+
+impl Doc {
+    fn to_entry(&self) -> Entry {
+        let entry_type = match self.doctype.as_deref() {
+            Some("inproceedings") | Some("abstract") => "inproceedings",
+            Some("eprint") => "misc",
+            Some("book") => "book",
+            Some("inbook") => "incollection",
+            Some("phdthesis") => "phdthesis",
+            _ => "article",
+        };
+        let mut entry = Entry::new(self.bibcode.clone(), entry_type.to_string());
+
+        entry.title = self.title.first().map(|t| Arc::from(t.as_str()));
+        // Names are written "Family, Given"
+        entry.authors = self
+            .author
+            .iter()
+            .map(|a| match a.split_once(", ") {
+                Some((family, given)) => Arc::from(format!("{} {}", given, family)),
+                None => Arc::from(a.as_str()),
+            })
+            .collect();
+        entry.year = self.year.as_deref().and_then(|y| y.parse().ok());
+        entry.venue = self.publication.as_deref().map(Arc::from);
+        entry.doi = self.doi.first().cloned();
+        entry.arxiv_id = self
+            .identifier
+            .iter()
+            .find_map(|id| id.strip_prefix("arXiv:"))
+            .map(str::to_string);
+
+        for (name, value) in [
+            ("pages", self.page.first().cloned()),
+            ("volume", self.volume.clone()),
+            (ADS_BIBCODE_FIELD, Some(self.bibcode.clone())),
+        ] {
+            if let Some(value) = value {
+                entry.fields.insert(name.to_string(), value);
+            }
+        }
+
+        entry
+    }
+}
+
+#[async_trait]
+impl Validator for AdsClient {
+    async fn search_by_doi(&self, doi: &str) -> Result<Option<Entry>, ValidatorError> {
+        self.first(&format!("doi:\"{}\"", normalize_doi(doi))).await
+    }
+
+    async fn search_by_title(&self, title: &str) -> Result<Vec<Entry>, ValidatorError> {
+        self.search(&format!("title:\"{}\"", title.replace('"', "")), 5)
+            .await
+    }
+
+    fn name(&self) -> &'static str {
+        "NASA ADS"
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn bibcodes_are_found_in_ads_exports() {
+        let mut entry = Entry::new("2019ApJ...875L...1E".to_string(), "article".to_string());
+        assert_eq!(local_bibcode(&entry), Some("2019ApJ...875L...1E"));
+
+        entry.key = "eht2019".to_string();
+        assert_eq!(local_bibcode(&entry), None);
+        entry.fields.insert(
+            "adsurl".to_string(),
+            "https://ui.adsabs.harvard.edu/abs/2019ApJ...875L...1E".to_string(),
+        );
+        assert_eq!(local_bibcode(&entry), Some("2019ApJ...875L...1E"));
+
+        let json = r#"{"response": {"numFound": 1, "docs": [{
+            "bibcode": "2019ApJ...875L...1E",
+            "title": ["First M87 Event Horizon Telescope Results. I."],
+            "author": ["Event Horizon Telescope Collaboration", "Akiyama, Kazunori"],
+            "year": "2019",
+            "pub": "The Astrophysical Journal",
+            "doi": ["10.3847/2041-8213/ab0ec7"],
+            "identifier": ["arXiv:1906.11238", "2019ApJ...875L...1E"],
+            "page": ["L1"],
+            "doctype": "article"
+        }]}}"#;
+        let response: SearchResponse = serde_json::from_str(json).unwrap();
+        let entry = response.response.docs[0].to_entry();
+        assert_eq!(entry.authors[1], Arc::from("Kazunori Akiyama"));
+        assert_eq!(entry.arxiv_id.as_deref(), Some("1906.11238"));
+        assert_eq!(entry.field("pages"), Some("L1"));
+    }
+}
@@ -1,11 +1,16 @@
 use super::{async_trait, Validator, ValidatorError};
 use crate::entry::Entry;
+use crate::lint::normalize_arxiv_id;
 use quick_xml::events::Event;
 use quick_xml::Reader;
 use reqwest::Client;
+use std::sync::Arc;
 
 const ARXIV_API_BASE: &str = "https://export.arxiv.org/api/query";
 
+/// Field of a matched entry holding the date its latest version was posted
+pub const ARXIV_UPDATED_FIELD: &str = "arxiv_updated";
+
 pub struct ArxivClient {
     client: Client,
 }
@@ -49,7 +54,13 @@ impl Validator for ArxivClient {
     }
 
     async fn search_by_arxiv_id(&self, arxiv_id: &str) -> Result<Option<Entry>, ValidatorError> {
-        let url = format!("{}?id_list={}", ARXIV_API_BASE, arxiv_id);
+        // Without a version arXiv answers with the latest one, so a pinned version can be
+        // compared against it
+        let url = format!(
+            "{}?id_list={}",
+            ARXIV_API_BASE,
+            normalize_arxiv_id(arxiv_id)
+        );
 
         let response = self.client.get(&url).send().await?;
         let text = response.text().await?;
@@ -101,7 +112,7 @@ fn parse_arxiv_atom(xml: &str) -> Result<Vec<Entry>, ValidatorError> {
                     in_author = false;
                     if let Some(ref mut entry) = current_entry {
                         if !current_author.is_empty() {
-                            entry.authors.push(current_author.clone());
+                            entry.authors.push(Arc::from(current_author.as_str()));
                         }
                     }
                 }
@@ -116,20 +127,26 @@ fn parse_arxiv_atom(xml: &str) -> Result<Vec<Entry>, ValidatorError> {
                             entry.title = Some(
                                 text.split_whitespace()
                                     .collect::<Vec<_>>()
-                                    .join(" "),
+                                    .join(" ")
+                                    .into(),
                             );
                         }
-                        "id" => {
-                            // Extract arXiv ID from URL: http://arxiv.org/abs/2301.12345v1
-                            if text.contains("arxiv.org/abs/") {
-                                let id = text
-                                    .split("arxiv.org/abs/")
-                                    .nth(1)
-                                    .unwrap_or(&text)
-                                    .to_string();
-                                entry.arxiv_id = Some(id.clone());
-                                entry.key = id;
-                            }
+                        // Extract arXiv ID from URL: http://arxiv.org/abs/2301.12345v1
+                        "id" if text.contains("arxiv.org/abs/") => {
+                            let id = text
+                                .split("arxiv.org/abs/")
+                                .nth(1)
+                                .unwrap_or(&text)
+                                .to_string();
+                            entry.arxiv_id = Some(id.clone());
+                            entry.key = id;
+                        }
+                        "updated" => {
+                            // Date of the latest version: 2023-03-02T00:00:00Z
+                            let date = text.split('T').next().unwrap_or(&text);
+                            entry
+                                .fields
+                                .insert(ARXIV_UPDATED_FIELD.to_string(), date.to_string());
                         }
                         "published" => {
                             // Extract year from date: 2023-01-15T00:00:00Z
@@ -174,6 +191,7 @@ mod tests {
     <id>http://arxiv.org/abs/2301.12345v1</id>
     <title>A Great Paper About Machine Learning</title>
     <published>2023-01-15T00:00:00Z</published>
+    <updated>2023-02-20T10:11:12Z</updated>
     <author>
       <name>John Smith</name>
     </author>
@@ -186,11 +204,12 @@ mod tests {
         let entries = parse_arxiv_atom(xml).unwrap();
         assert_eq!(entries.len(), 1);
         assert_eq!(
-            entries[0].title,
-            Some("A Great Paper About Machine Learning".to_string())
+            entries[0].title.as_deref(),
+            Some("A Great Paper About Machine Learning")
         );
         assert_eq!(entries[0].arxiv_id, Some("2301.12345v1".to_string()));
         assert_eq!(entries[0].year, Some(2023));
         assert_eq!(entries[0].authors.len(), 2);
+        assert_eq!(entries[0].field(ARXIV_UPDATED_FIELD), Some("2023-02-20"));
     }
 }
@@ -0,0 +1,173 @@
+use super::{async_trait, Validator, ValidatorError};
+use crate::entry::Entry;
+use crate::lint::normalize_doi;
+use reqwest::Client;
+use serde::Deserialize;
+use std::sync::Arc;
+
+const CORE_API_BASE: &str = "https://api.core.ac.uk/v3/search/works";
+
+/// Client for the CORE v3 API, an aggregator of open access papers from repositories
+/// worldwide. It needs an API key.
+pub struct CoreClient {
+    client: Client,
+    api_key: String,
+}
+
+impl CoreClient {
+    pub fn new(api_key: String) -> Self {
+        let client = Client::builder()
+            .user_agent("bibval/0.1.0 (https://github.com/femtomc/bibval)")
+            .build()
+            .expect("Failed to create HTTP client");
+        Self { client, api_key }
+    }
+
+    async fn search(&self, query: &str, limit: usize) -> Result<Vec<Entry>, ValidatorError> {
+        let url = format!(
+            "{}?q={}&limit={}",
+            CORE_API_BASE,
+            urlencoding::encode(query),
+            limit
+        );
+
+        let response = self
+            .client
+            .get(&url)
+            .bearer_auth(&self.api_key)
+            .send()
+            .await?;
+
+        if response.status() == reqwest::StatusCode::TOO_MANY_REQUESTS {
+            return Err(ValidatorError::RateLimited);
+        }
+
+        if response.status() == reqwest::StatusCode::UNAUTHORIZED {
+            return Err(ValidatorError::ParseError(
+                "CORE rejected the API key".to_string(),
+            ));
+        }
+
+        if !response.status().is_success() {
+            return Err(ValidatorError::ParseError(format!(
+                "CORE returned {}",
+                response.status()
+            )));
+        }
+
+        let response: SearchResponse = response.json().await.map_err(|e| {
+            ValidatorError::ParseError(format!("Failed to parse CORE response: {}", e))
+        })?;
+
+        Ok(response.results.iter().map(Work::to_entry).collect())
+    }
+}
+
+#[derive(Debug, Deserialize)]
+struct SearchResponse {
+    #[serde(default)]
+    results: Vec<Work>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct Work {
+    id: u64,
+    title: Option<String>,
+    #[serde(default)]
+    authors: Vec<Author>,
+    year_published: Option<i32>,
+    doi: Option<String>,
+    publisher: Option<String>,
+    #[serde(default)]
+    journals: Vec<Journal>,
+    document_type: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+struct Author {
+    name: String,
+}
+
+#[derive(Debug, Deserialize)]
+struct Journal {
+    title: Option<String>,
+}
+
+impl Work {
+    fn to_entry(&self) -> Entry {
+        let entry_type = match self.document_type.as_deref() {
+            Some("thesis") => "phdthesis",
+            Some("conference paper") => "inproceedings",
+            Some("book") => "book",
+            _ => "article",
+        };
+        let mut entry = Entry::new(self.id.to_string(), entry_type.to_string());
+
+        entry.title = self.title.as_deref().map(Arc::from);
+        // Repositories mostly write names "Family, Given"
+        entry.authors = self
+            .authors
+            .iter()
+            .map(|a| match a.name.split_once(", ") {
+                Some((family, given)) => Arc::from(format!("{} {}", given, family)),
+                None => Arc::from(a.name.as_str()),
+            })
+            .collect();
+        entry.year = self.year_published;
+        entry.venue = self
+            .journals
+            .iter()
+            .find_map(|j| j.title.as_deref())
+            .map(Arc::from);
+        entry.doi = self.doi.clone();
+        if let Some(publisher) = self.publisher.as_ref().filter(|p| !p.is_empty()) {
+            entry
+                .fields
+                .insert("publisher".to_string(), publisher.clone());
+        }
+
+        entry
+    }
+}
+
+#[async_trait]
+impl Validator for CoreClient {
+    async fn search_by_doi(&self, doi: &str) -> Result<Option<Entry>, ValidatorError> {
+        let query = format!("doi:\"{}\"", normalize_doi(doi));
+        Ok(self.search(&query, 1).await?.into_iter().next())
+    }
+
+    async fn search_by_title(&self, title: &str) -> Result<Vec<Entry>, ValidatorError> {
+        self.search(&format!("title:\"{}\"", title.replace('"', "")), 5)
+            .await
+    }
+
+    fn name(&self) -> &'static str {
+        "CORE"
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn works_are_read() {
+        let json = r#"{"totalHits": 1, "limit": 5, "offset": 0, "results": [{
+            "id": 82641234,
+            "title": "A Repository Paper",
+            "authors": [{"name": "Doe, Jane"}, {"name": "John Smith"}],
+            "yearPublished": 2018,
+            "doi": null,
+            "journals": [{"title": "Journal of Things", "identifiers": []}],
+            "documentType": "research"
+        }]}"#;
+        let response: SearchResponse = serde_json::from_str(json).unwrap();
+        let entry = response.results[0].to_entry();
+        assert_eq!(entry.key, "82641234");
+        assert_eq!(entry.authors[0], Arc::from("Jane Doe"));
+        assert_eq!(entry.venue.as_deref(), Some("Journal of Things"));
+        assert_eq!(entry.year, Some(2018));
+    }
+}
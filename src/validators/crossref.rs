@@ -1,11 +1,17 @@
 use super::{async_trait, Validator, ValidatorError};
 use crate::entry::Entry;
+use crate::lint::{normalize_doi, normalize_isbn, normalize_issn};
 use reqwest::Client;
-use serde::Deserialize;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::sync::Arc;
 
 const CROSSREF_API_BASE: &str = "https://api.crossref.org/works";
 const USER_AGENT: &str = "bibval/0.1.0 (https://github.com/femtomc/bibval; mailto:user@example.com)";
 
+/// Maximum number of DOIs combined into one filter query
+pub const CROSSREF_BATCH_SIZE: usize = 50;
+
 pub struct CrossRefClient {
     client: Client,
 }
@@ -18,6 +24,137 @@ impl CrossRefClient {
             .expect("Failed to create HTTP client");
         Self { client }
     }
+
+    /// Look up several DOIs with a single `filter=doi:a,doi:b` query.
+    ///
+    /// Returns works keyed by normalized DOI; DOIs missing from the map are unknown to CrossRef.
+    /// Filter values are separated by commas, so DOIs containing one can't be batched.
+    pub async fn search_by_dois(
+        &self,
+        dois: &[String],
+    ) -> Result<HashMap<String, Entry>, ValidatorError> {
+        if dois.is_empty() {
+            return Ok(HashMap::new());
+        }
+
+        let filter = dois
+            .iter()
+            .map(|doi| format!("doi:{}", doi))
+            .collect::<Vec<_>>()
+            .join(",");
+        let url = format!(
+            "{}?filter={}&rows={}",
+            CROSSREF_API_BASE,
+            urlencoding::encode(&filter),
+            dois.len()
+        );
+
+        let response = self.client.get(&url).send().await?;
+
+        if response.status() == reqwest::StatusCode::TOO_MANY_REQUESTS {
+            return Err(ValidatorError::RateLimited);
+        }
+
+        let response: CrossRefResponse = response.json().await?;
+
+        if response.status != "ok" {
+            return Err(ValidatorError::ParseError(format!(
+                "CrossRef batch query returned status {}",
+                response.status
+            )));
+        }
+
+        let works = match response.message {
+            CrossRefMessage::Search(result) => result.items,
+            CrossRefMessage::Single(work) => vec![*work],
+        };
+
+        Ok(works
+            .iter()
+            .filter_map(|w| Some((normalize_doi(w.doi.as_deref()?), w.to_entry())))
+            .collect())
+    }
+}
+
+impl CrossRefClient {
+    /// Find the retraction, correction and expression-of-concern notices registered
+    /// against a DOI, whether by its publisher or by Retraction Watch, via the notices'
+    /// `update-to` relationships
+    pub async fn update_notices(&self, doi: &str) -> Result<Vec<UpdateNotice>, ValidatorError> {
+        let doi = normalize_doi(doi);
+        let url = format!(
+            "{}?filter={}&rows=20",
+            CROSSREF_API_BASE,
+            urlencoding::encode(&format!("updates:{}", doi))
+        );
+
+        let response = self.client.get(&url).send().await?;
+
+        if response.status() == reqwest::StatusCode::TOO_MANY_REQUESTS {
+            return Err(ValidatorError::RateLimited);
+        }
+
+        let response: NoticeResponse = response.json().await.map_err(|e| {
+            ValidatorError::ParseError(format!("Failed to parse CrossRef notices: {}", e))
+        })?;
+
+        Ok(response
+            .message
+            .items
+            .into_iter()
+            .flat_map(|notice| {
+                let notice_doi = notice.doi;
+                notice
+                    .update_to
+                    .into_iter()
+                    .filter(|update| normalize_doi(&update.doi) == doi)
+                    .map(move |update| UpdateNotice {
+                        kind: update.update_type,
+                        notice_doi: notice_doi.clone(),
+                        source: update.source,
+                    })
+            })
+            .collect())
+    }
+}
+
+/// A notice updating a published work, such as a retraction
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct UpdateNotice {
+    /// CrossRef's update type: `retraction`, `correction`, `expression_of_concern`, ...
+    pub kind: String,
+    /// DOI of the notice itself
+    pub notice_doi: Option<String>,
+    /// Who reported the update: `publisher` or `retraction-watch`
+    pub source: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+struct NoticeResponse {
+    message: NoticeItems,
+}
+
+#[derive(Debug, Deserialize)]
+struct NoticeItems {
+    #[serde(default)]
+    items: Vec<Notice>,
+}
+
+#[derive(Debug, Deserialize)]
+struct Notice {
+    #[serde(rename = "DOI")]
+    doi: Option<String>,
+    #[serde(rename = "update-to", default)]
+    update_to: Vec<Update>,
+}
+
+#[derive(Debug, Deserialize)]
+struct Update {
+    #[serde(rename = "DOI")]
+    doi: String,
+    #[serde(rename = "type")]
+    update_type: String,
+    source: Option<String>,
 }
 
 impl Default for CrossRefClient {
@@ -32,11 +169,12 @@ struct CrossRefResponse {
     message: CrossRefMessage,
 }
 
+// Search must come first: every field of a work is optional, so any object would match Single
 #[derive(Debug, Deserialize)]
 #[serde(untagged)]
 enum CrossRefMessage {
-    Single(CrossRefWork),
     Search(CrossRefSearchResult),
+    Single(Box<CrossRefWork>),
 }
 
 #[derive(Debug, Deserialize)]
@@ -59,6 +197,15 @@ struct CrossRefWork {
     published_online: Option<CrossRefDate>,
     #[serde(rename = "type")]
     work_type: Option<String>,
+    /// Name of the CrossRef member that registered the DOI
+    publisher: Option<String>,
+    volume: Option<String>,
+    issue: Option<String>,
+    page: Option<String>,
+    #[serde(rename = "ISSN")]
+    issn: Option<Vec<String>>,
+    #[serde(rename = "ISBN")]
+    isbn: Option<Vec<String>>,
 }
 
 #[derive(Debug, Deserialize)]
@@ -81,7 +228,7 @@ impl CrossRefWork {
             self.work_type.clone().unwrap_or_else(|| "article".to_string()),
         );
 
-        entry.title = self.title.as_ref().and_then(|t| t.first().cloned());
+        entry.title = self.title.as_ref().and_then(|t| t.first()).map(|t| Arc::from(t.as_str()));
         entry.doi = self.doi.clone();
 
         if let Some(authors) = &self.author {
@@ -89,11 +236,11 @@ impl CrossRefWork {
                 .iter()
                 .map(|a| {
                     if let Some(name) = &a.name {
-                        name.clone()
+                        Arc::from(name.as_str())
                     } else {
                         let given = a.given.as_deref().unwrap_or("");
                         let family = a.family.as_deref().unwrap_or("");
-                        format!("{} {}", given, family).trim().to_string()
+                        Arc::from(format!("{} {}", given, family).trim())
                     }
                 })
                 .collect();
@@ -102,7 +249,26 @@ impl CrossRefWork {
         entry.venue = self
             .container_title
             .as_ref()
-            .and_then(|t| t.first().cloned());
+            .and_then(|t| t.first())
+            .map(|t| Arc::from(t.as_str()));
+
+        for (name, value) in [
+            ("publisher", &self.publisher),
+            ("volume", &self.volume),
+            ("number", &self.issue),
+            ("pages", &self.page),
+        ] {
+            if let Some(value) = value {
+                entry.fields.insert(name.to_string(), value.clone());
+            }
+        }
+        for (name, values) in [("issn", &self.issn), ("isbn", &self.isbn)] {
+            if let Some(values) = values.as_ref().filter(|v| !v.is_empty()) {
+                entry.fields.insert(name.to_string(), values.join(", "));
+            }
+        }
+        entry.issn = entry.field("issn").and_then(normalize_issn);
+        entry.isbn = entry.field("isbn").and_then(normalize_isbn);
 
         // Try different date fields
         let date = self
@@ -0,0 +1,293 @@
+use super::{async_trait, Validator, ValidatorError};
+use crate::entry::Entry;
+use crate::lint::normalize_doi;
+use reqwest::Client;
+use serde::Deserialize;
+use std::sync::Arc;
+
+const DATACITE_API_BASE: &str = "https://api.datacite.org/dois";
+
+/// Field of a matched entry holding the version the DOI was registered for
+pub const VERSION_FIELD: &str = "version";
+/// Field of a matched entry holding the concept DOI a version DOI belongs to
+pub const CONCEPT_DOI_FIELD: &str = "concept_doi";
+/// Field of a matched entry holding the number of versions a concept DOI has
+pub const VERSION_COUNT_FIELD: &str = "version_count";
+/// Field of a matched entry holding the license the work is released under
+pub const LICENSE_FIELD: &str = "license";
+
+/// DOI prefixes of repositories that register with DataCite rather than CrossRef:
+/// Zenodo, Dryad, figshare, PANGAEA, ICPSR, Harvard Dataverse, arXiv, OSF, Mendeley
+/// Data and GBIF
+const DATACITE_DOI_PREFIXES: &[&str] = &[
+    "10.5281/",
+    "10.5061/",
+    "10.6084/",
+    "10.1594/",
+    "10.3886/",
+    "10.7910/",
+    "10.48550/",
+    "10.17605/",
+    "10.17632/",
+    "10.15468/",
+];
+
+/// Whether a DOI was registered with DataCite, so CrossRef won't know it
+pub fn is_datacite_doi(doi: &str) -> bool {
+    let doi = normalize_doi(doi);
+    DATACITE_DOI_PREFIXES
+        .iter()
+        .any(|prefix| doi.starts_with(prefix))
+}
+
+/// Client for DataCite, the DOI registry behind most software and dataset DOIs
+/// (including Zenodo's)
+pub struct DataCiteClient {
+    client: Client,
+}
+
+impl DataCiteClient {
+    pub fn new() -> Self {
+        let client = Client::builder()
+            .user_agent("bibval/0.1.0 (https://github.com/femtomc/bibval)")
+            .build()
+            .expect("Failed to create HTTP client");
+        Self { client }
+    }
+}
+
+impl Default for DataCiteClient {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[derive(Debug, Deserialize)]
+struct SingleResponse {
+    data: Record,
+}
+
+#[derive(Debug, Deserialize)]
+struct SearchResponse {
+    data: Vec<Record>,
+}
+
+#[derive(Debug, Deserialize)]
+struct Record {
+    attributes: Attributes,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct Attributes {
+    doi: Option<String>,
+    #[serde(default)]
+    titles: Vec<Title>,
+    #[serde(default)]
+    creators: Vec<Creator>,
+    publication_year: Option<serde_json::Value>,
+    publisher: Option<serde_json::Value>,
+    version: Option<String>,
+    types: Option<Types>,
+    #[serde(default)]
+    related_identifiers: Vec<RelatedIdentifier>,
+    #[serde(default)]
+    rights_list: Vec<Rights>,
+    url: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct Rights {
+    rights: Option<String>,
+    rights_identifier: Option<String>,
+    rights_uri: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+struct Title {
+    title: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct Creator {
+    name: Option<String>,
+    given_name: Option<String>,
+    family_name: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct Types {
+    resource_type_general: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct RelatedIdentifier {
+    relation_type: Option<String>,
+    related_identifier: Option<String>,
+    related_identifier_type: Option<String>,
+}
+
+impl Attributes {
+    fn to_entry(&self) -> Entry {
+        let entry_type = match self
+            .types
+            .as_ref()
+            .and_then(|t| t.resource_type_general.as_deref())
+        {
+            Some("Software") => "software",
+            Some("Dataset") => "dataset",
+            Some("JournalArticle") => "article",
+            Some("ConferencePaper") => "inproceedings",
+            _ => "misc",
+        };
+        let mut entry = Entry::new(self.doi.clone().unwrap_or_default(), entry_type.to_string());
+
+        entry.title = self
+            .titles
+            .iter()
+            .find_map(|t| t.title.as_deref())
+            .map(Arc::from);
+        entry.authors = self
+            .creators
+            .iter()
+            .filter_map(|c| match (&c.given_name, &c.family_name) {
+                (Some(given), Some(family)) => Some(Arc::from(format!("{} {}", given, family))),
+                _ => c.name.as_deref().map(Arc::from),
+            })
+            .collect();
+        entry.year = self.publication_year.as_ref().and_then(|y| match y {
+            serde_json::Value::Number(n) => n.as_i64().and_then(|y| i32::try_from(y).ok()),
+            serde_json::Value::String(s) => s.parse().ok(),
+            _ => None,
+        });
+        // Publisher is a plain string in older responses and an object in newer ones
+        entry.venue = self.publisher.as_ref().and_then(|p| match p {
+            serde_json::Value::String(s) => Some(Arc::from(s.as_str())),
+            serde_json::Value::Object(o) => o.get("name")?.as_str().map(Arc::from),
+            _ => None,
+        });
+        entry.doi = self.doi.clone();
+        entry.url = self.url.clone();
+
+        if let Some(version) = &self.version {
+            entry
+                .fields
+                .insert(VERSION_FIELD.to_string(), version.clone());
+        }
+        if let Some(concept) = self.related_dois("IsVersionOf").next() {
+            entry
+                .fields
+                .insert(CONCEPT_DOI_FIELD.to_string(), concept.to_string());
+        }
+        if let Some(license) = self.license() {
+            entry
+                .fields
+                .insert(LICENSE_FIELD.to_string(), license.to_string());
+        }
+        let versions = self.related_dois("HasVersion").count();
+        if versions > 0 {
+            entry
+                .fields
+                .insert(VERSION_COUNT_FIELD.to_string(), versions.to_string());
+        }
+
+        entry
+    }
+
+    /// The license, by its SPDX identifier when the record gives one. Access rights such as
+    /// "Open Access" share the list and are skipped.
+    fn license(&self) -> Option<&str> {
+        let licenses = self.rights_list.iter().filter(|r| {
+            !r.rights_uri
+                .as_deref()
+                .is_some_and(|uri| uri.starts_with("info:eu-repo/semantics"))
+        });
+        licenses
+            .clone()
+            .find_map(|r| r.rights_identifier.as_deref())
+            .or_else(|| licenses.clone().find_map(|r| r.rights.as_deref()))
+    }
+
+    /// DOIs this record is related to in the given way, e.g. "IsVersionOf"
+    fn related_dois<'a>(&'a self, relation: &'a str) -> impl Iterator<Item = &'a str> {
+        self.related_identifiers
+            .iter()
+            .filter(move |r| {
+                r.relation_type.as_deref() == Some(relation)
+                    && r.related_identifier_type.as_deref() == Some("DOI")
+            })
+            .filter_map(|r| r.related_identifier.as_deref())
+    }
+}
+
+#[async_trait]
+impl Validator for DataCiteClient {
+    async fn search_by_doi(&self, doi: &str) -> Result<Option<Entry>, ValidatorError> {
+        let url = format!("{}/{}", DATACITE_API_BASE, urlencoding::encode(doi));
+
+        let response = self.client.get(&url).send().await?;
+
+        if response.status() == reqwest::StatusCode::NOT_FOUND {
+            return Ok(None);
+        }
+
+        if response.status() == reqwest::StatusCode::TOO_MANY_REQUESTS {
+            return Err(ValidatorError::RateLimited);
+        }
+
+        let response: SingleResponse = response.json().await.map_err(|e| {
+            ValidatorError::ParseError(format!("Failed to parse DataCite response: {}", e))
+        })?;
+
+        Ok(Some(response.data.attributes.to_entry()))
+    }
+
+    async fn search_by_title(&self, title: &str) -> Result<Vec<Entry>, ValidatorError> {
+        let query = format!("titles.title:\"{}\"", title.replace('"', ""));
+        let url = format!(
+            "{}?query={}&page[size]=5",
+            DATACITE_API_BASE,
+            urlencoding::encode(&query)
+        );
+
+        let response = self.client.get(&url).send().await?;
+
+        if response.status() == reqwest::StatusCode::TOO_MANY_REQUESTS {
+            return Err(ValidatorError::RateLimited);
+        }
+
+        if !response.status().is_success() {
+            return Ok(Vec::new());
+        }
+
+        let response: SearchResponse = response.json().await.map_err(|e| {
+            ValidatorError::ParseError(format!("Failed to parse DataCite response: {}", e))
+        })?;
+
+        Ok(response
+            .data
+            .iter()
+            .map(|r| r.attributes.to_entry())
+            .collect())
+    }
+
+    fn name(&self) -> &'static str {
+        "DataCite"
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn repository_dois_are_datacite_dois() {
+        assert!(is_datacite_doi("10.5061/dryad.abc123"));
+        assert!(is_datacite_doi("https://doi.org/10.6084/m9.figshare.123"));
+        assert!(!is_datacite_doi("10.1145/3313831.3376727"));
+    }
+}
@@ -2,8 +2,31 @@ use super::{async_trait, Validator, ValidatorError};
 use crate::entry::Entry;
 use reqwest::Client;
 use serde::Deserialize;
+use std::sync::Arc;
 
 const DBLP_API_BASE: &str = "https://dblp.org/search/publ/api";
+const DBLP_RECORD_BASE: &str = "https://dblp.org/rec";
+
+/// Field of a matched entry holding its DBLP record key, e.g. `conf/cvpr/HeZRS16`
+pub const DBLP_KEY_FIELD: &str = "dblp";
+
+/// Which of DBLP's BibTeX renderings to fetch for a record
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DblpBibtexFormat {
+    /// Abbreviated venue names, no crossref
+    Condensed,
+    /// Full venue names, no crossref
+    Standard,
+}
+
+impl DblpBibtexFormat {
+    fn param(self) -> u8 {
+        match self {
+            DblpBibtexFormat::Condensed => 0,
+            DblpBibtexFormat::Standard => 1,
+        }
+    }
+}
 
 pub struct DblpClient {
     client: Client,
@@ -17,6 +40,41 @@ impl DblpClient {
             .expect("Failed to create HTTP client");
         Self { client }
     }
+
+    /// Fetch the curated BibTeX of a DBLP record
+    pub async fn fetch_bibtex(
+        &self,
+        dblp_key: &str,
+        format: DblpBibtexFormat,
+    ) -> Result<Option<String>, ValidatorError> {
+        let url = format!(
+            "{}/{}.bib?param={}",
+            DBLP_RECORD_BASE,
+            dblp_key,
+            format.param()
+        );
+
+        let response = self.client.get(&url).send().await?;
+
+        if response.status() == reqwest::StatusCode::NOT_FOUND {
+            return Ok(None);
+        }
+
+        if response.status() == reqwest::StatusCode::TOO_MANY_REQUESTS {
+            return Err(ValidatorError::RateLimited);
+        }
+
+        let bibtex = response.text().await?;
+        Ok(Some(bibtex.trim().to_string()).filter(|b| b.starts_with('@')))
+    }
+}
+
+/// Replace the citation key of the first entry in a BibTeX record, so a DBLP record
+/// (keyed `DBLP:conf/...`) can stand in for a local entry
+pub fn rekey_bibtex(bibtex: &str, key: &str) -> Option<String> {
+    let open = bibtex.find('{')?;
+    let comma = open + bibtex[open..].find(',')?;
+    Some(format!("{}{}{}", &bibtex[..=open], key, &bibtex[comma..]))
 }
 
 impl Default for DblpClient {
@@ -51,10 +109,14 @@ struct DblpInfo {
     authors: Option<DblpAuthors>,
     year: Option<String>,
     venue: Option<String>,
+    volume: Option<String>,
+    number: Option<String>,
+    pages: Option<String>,
     doi: Option<String>,
     #[serde(rename = "type")]
     pub_type: Option<String>,
     url: Option<String>,
+    key: Option<String>,
 }
 
 #[derive(Debug, Deserialize)]
@@ -96,15 +158,29 @@ impl DblpInfo {
             self.pub_type.clone().unwrap_or_else(|| "article".to_string()),
         );
 
-        entry.title = self.title.clone().map(|t| t.trim_end_matches('.').to_string());
+        entry.title = self.title.as_deref().map(|t| Arc::from(t.trim_end_matches('.')));
         entry.doi = self.doi.clone();
         entry.year = self.year.as_ref().and_then(|y| y.parse().ok());
-        entry.venue = self.venue.clone();
+        entry.venue = self.venue.as_deref().map(Arc::from);
+        if let Some(key) = &self.key {
+            entry.fields.insert(DBLP_KEY_FIELD.to_string(), key.clone());
+        }
+        for (name, value) in [
+            ("volume", &self.volume),
+            ("number", &self.number),
+            ("pages", &self.pages),
+        ] {
+            if let Some(value) = value {
+                entry.fields.insert(name.to_string(), value.clone());
+            }
+        }
 
         if let Some(authors) = &self.authors {
             entry.authors = match &authors.author {
-                DblpAuthorList::Single(a) => vec![a.name().to_string()],
-                DblpAuthorList::Multiple(list) => list.iter().map(|a| a.name().to_string()).collect(),
+                DblpAuthorList::Single(a) => vec![Arc::from(a.name())],
+                DblpAuthorList::Multiple(list) => {
+                    list.iter().map(|a| Arc::from(a.name())).collect()
+                }
             };
         }
 
@@ -151,3 +227,17 @@ impl Validator for DblpClient {
         "DBLP"
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn rekeyed_records_keep_the_local_key() {
+        let bibtex = "@inproceedings{DBLP:conf/cvpr/HeZRS16,\n  author = {Kaiming He},\n}";
+        assert_eq!(
+            rekey_bibtex(bibtex, "he2016deep").as_deref(),
+            Some("@inproceedings{he2016deep,\n  author = {Kaiming He},\n}")
+        );
+    }
+}
@@ -0,0 +1,382 @@
+use super::dblp::DBLP_KEY_FIELD;
+use super::{async_trait, Validator, ValidatorError};
+use crate::entry::{normalize_string, Entry};
+use crate::lint::normalize_doi;
+use flate2::read::GzDecoder;
+use quick_xml::events::Event;
+use quick_xml::Reader;
+use serde::{Deserialize, Serialize};
+use std::fs::File;
+use std::io::{self, BufRead, BufReader, BufWriter, Read, Write};
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use thiserror::Error;
+use tokio::io::{AsyncBufReadExt, AsyncReadExt, AsyncSeekExt};
+
+/// Elements of `dblp.xml` that are publication records; `www` person pages are skipped
+const RECORD_ELEMENTS: &[&str] = &[
+    "article",
+    "inproceedings",
+    "proceedings",
+    "book",
+    "incollection",
+    "phdthesis",
+    "mastersthesis",
+    "data",
+];
+
+/// Size of an index entry: a key hash and a record offset, both big-endian u64
+const INDEX_ENTRY_LEN: u64 = 16;
+
+#[derive(Error, Debug)]
+pub enum DblpDumpError {
+    #[error("Failed to read DBLP dump: {0}")]
+    Io(#[from] io::Error),
+    #[error("Malformed DBLP dump: {0}")]
+    Xml(#[from] quick_xml::Error),
+}
+
+/// A downloaded DBLP dump (`dblp.xml.gz`), answering lookups offline.
+///
+/// The first open indexes the dump into files beside it: the records as JSON lines,
+/// and sorted tables from title and DOI hashes to record offsets that lookups binary
+/// search on disk. Later opens reuse the index until the dump is replaced.
+pub struct DblpDump {
+    records: PathBuf,
+    titles: PathBuf,
+    dois: PathBuf,
+}
+
+impl DblpDump {
+    /// Open a dump, indexing it first if it has no up-to-date index
+    pub fn open(path: &Path) -> Result<Self, DblpDumpError> {
+        let dump = Self {
+            records: sidecar(path, "records"),
+            titles: sidecar(path, "titles"),
+            dois: sidecar(path, "dois"),
+        };
+        if !Self::is_indexed(path) {
+            dump.build_index(path)?;
+        }
+        Ok(dump)
+    }
+
+    /// Whether the dump has an index at least as new as itself
+    pub fn is_indexed(path: &Path) -> bool {
+        let modified = |p: &Path| p.metadata().and_then(|m| m.modified()).ok();
+        match (modified(path), modified(&sidecar(path, "records"))) {
+            (Some(dump), Some(records)) => records >= dump,
+            _ => false,
+        }
+    }
+
+    fn build_index(&self, path: &Path) -> Result<(), DblpDumpError> {
+        let file = File::open(path)?;
+        let input: Box<dyn Read> = if path.extension().is_some_and(|e| e == "gz") {
+            Box::new(GzDecoder::new(file))
+        } else {
+            Box::new(file)
+        };
+
+        // Records go to a temporary file renamed into place last, so an interrupted
+        // indexing run is redone rather than half used
+        let partial = sidecar(path, "records.partial");
+        let mut records = BufWriter::new(File::create(&partial)?);
+        let mut offset = 0;
+        let mut titles = Vec::new();
+        let mut dois = Vec::new();
+
+        read_records(BufReader::new(input), |record| {
+            let mut line = serde_json::to_string(&record).map_err(io::Error::from)?;
+            line.push('\n');
+            records.write_all(line.as_bytes())?;
+
+            if let Some(title) = &record.title {
+                titles.push((title_hash(title), offset));
+            }
+            if let Some(doi) = &record.doi {
+                dois.push((doi_hash(doi), offset));
+            }
+            offset += line.len() as u64;
+            Ok(())
+        })?;
+        records.flush()?;
+
+        write_index(&self.titles, titles)?;
+        write_index(&self.dois, dois)?;
+        std::fs::rename(&partial, &self.records)?;
+        Ok(())
+    }
+
+    /// The records whose key hashes to `hash` in an index
+    async fn lookup(&self, index: &Path, hash: u64, limit: usize) -> io::Result<Vec<Record>> {
+        let mut index = tokio::fs::File::open(index).await?;
+        let len = index.metadata().await?.len() / INDEX_ENTRY_LEN;
+
+        // Binary search for the first entry with this hash
+        let (mut low, mut high) = (0, len);
+        while low < high {
+            let mid = (low + high) / 2;
+            if read_index_entry(&mut index, mid).await?.0 < hash {
+                low = mid + 1;
+            } else {
+                high = mid;
+            }
+        }
+
+        let mut records = tokio::io::BufReader::new(tokio::fs::File::open(&self.records).await?);
+        let mut found = Vec::new();
+        for position in low..len.min(low + limit as u64) {
+            let (entry_hash, offset) = read_index_entry(&mut index, position).await?;
+            if entry_hash != hash {
+                break;
+            }
+            records.seek(io::SeekFrom::Start(offset)).await?;
+            let mut line = String::new();
+            records.read_line(&mut line).await?;
+            found.push(serde_json::from_str(&line)?);
+        }
+        Ok(found)
+    }
+}
+
+/// A file stored next to the dump, e.g. `dblp.xml.gz.bibval-titles`
+fn sidecar(path: &Path, name: &str) -> PathBuf {
+    let mut file_name = path.file_name().unwrap_or_default().to_os_string();
+    file_name.push(format!(".bibval-{}", name));
+    path.with_file_name(file_name)
+}
+
+fn hash(key: &str) -> u64 {
+    let digest = blake3::hash(key.as_bytes());
+    u64::from_be_bytes(digest.as_bytes()[..8].try_into().unwrap())
+}
+
+fn title_hash(title: &str) -> u64 {
+    hash(&normalize_string(title))
+}
+
+fn doi_hash(doi: &str) -> u64 {
+    hash(&normalize_doi(doi))
+}
+
+fn write_index(path: &Path, mut entries: Vec<(u64, u64)>) -> io::Result<()> {
+    entries.sort_unstable();
+    let mut file = BufWriter::new(File::create(path)?);
+    for (hash, offset) in entries {
+        file.write_all(&hash.to_be_bytes())?;
+        file.write_all(&offset.to_be_bytes())?;
+    }
+    file.flush()
+}
+
+async fn read_index_entry(index: &mut tokio::fs::File, position: u64) -> io::Result<(u64, u64)> {
+    let mut bytes = [0; INDEX_ENTRY_LEN as usize];
+    index
+        .seek(io::SeekFrom::Start(position * INDEX_ENTRY_LEN))
+        .await?;
+    index.read_exact(&mut bytes).await?;
+    let (hash, offset) = bytes.split_at(8);
+    Ok((
+        u64::from_be_bytes(hash.try_into().unwrap()),
+        u64::from_be_bytes(offset.try_into().unwrap()),
+    ))
+}
+
+/// A publication record of the dump, as stored in the index
+#[derive(Debug, Default, PartialEq, Serialize, Deserialize)]
+struct Record {
+    key: String,
+    kind: String,
+    title: Option<String>,
+    authors: Vec<String>,
+    year: Option<i32>,
+    venue: Option<String>,
+    doi: Option<String>,
+    pages: Option<String>,
+    volume: Option<String>,
+}
+
+impl Record {
+    fn to_entry(&self) -> Entry {
+        let mut entry = Entry::new(
+            self.doi.clone().unwrap_or_else(|| self.key.clone()),
+            self.kind.clone(),
+        );
+
+        entry.title = self
+            .title
+            .as_deref()
+            .map(|t| Arc::from(t.trim_end_matches('.')));
+        entry.authors = self.authors.iter().map(|a| Arc::from(a.as_str())).collect();
+        entry.year = self.year;
+        entry.venue = self.venue.as_deref().map(Arc::from);
+        entry.doi = self.doi.clone();
+        entry
+            .fields
+            .insert(DBLP_KEY_FIELD.to_string(), self.key.clone());
+        for (name, value) in [("pages", &self.pages), ("volume", &self.volume)] {
+            if let Some(value) = value {
+                entry.fields.insert(name.to_string(), value.clone());
+            }
+        }
+
+        entry
+    }
+}
+
+/// Stream the publication records of `dblp.xml` to `record`
+fn read_records<R: BufRead>(
+    input: R,
+    mut record: impl FnMut(Record) -> io::Result<()>,
+) -> Result<(), DblpDumpError> {
+    let mut reader = Reader::from_reader(input);
+    let mut buf = Vec::new();
+    let mut current: Option<Record> = None;
+    // The field being read, and its text so far (titles may contain markup)
+    let mut field = String::new();
+    let mut text = String::new();
+
+    loop {
+        match reader.read_event_into(&mut buf)? {
+            Event::Start(ref e) => {
+                let name = String::from_utf8_lossy(e.local_name().as_ref()).to_string();
+                match &current {
+                    None if RECORD_ELEMENTS.contains(&name.as_str()) => {
+                        let key = e
+                            .try_get_attribute("key")
+                            .ok()
+                            .flatten()
+                            .map(|a| String::from_utf8_lossy(&a.value).to_string())
+                            .unwrap_or_default();
+                        current = Some(Record {
+                            key,
+                            kind: name,
+                            ..Record::default()
+                        });
+                    }
+                    // Markup inside a field, e.g. <i> or <sub> in a title
+                    Some(_) if !field.is_empty() => {}
+                    Some(_) => {
+                        field = name;
+                        text.clear();
+                    }
+                    None => {}
+                }
+            }
+            Event::Text(ref e) if !field.is_empty() => {
+                text.push_str(&e.unescape().unwrap_or_default());
+            }
+            Event::End(ref e) => {
+                let name = e.local_name();
+                let Some(rec) = current.as_mut() else {
+                    buf.clear();
+                    continue;
+                };
+                if name.as_ref() == field.as_bytes() {
+                    let value = text.trim().to_string();
+                    match field.as_str() {
+                        "author" => rec.authors.push(value),
+                        "title" => rec.title = Some(value),
+                        "year" => rec.year = value.parse().ok(),
+                        "journal" | "booktitle" => rec.venue = Some(value),
+                        "pages" => rec.pages = Some(value),
+                        "volume" => rec.volume = Some(value),
+                        "ee" if rec.doi.is_none() && value.contains("doi.org/") => {
+                            rec.doi = Some(normalize_doi(&value));
+                        }
+                        _ => {}
+                    }
+                    field.clear();
+                } else if name.as_ref() == rec.kind.as_bytes() {
+                    record(current.take().unwrap())?;
+                }
+            }
+            Event::Eof => break,
+            _ => {}
+        }
+        buf.clear();
+    }
+
+    Ok(())
+}
+
+fn index_error(e: io::Error) -> ValidatorError {
+    ValidatorError::ParseError(format!("Failed to read DBLP dump index: {}", e))
+}
+
+#[async_trait]
+impl Validator for DblpDump {
+    async fn search_by_doi(&self, doi: &str) -> Result<Option<Entry>, ValidatorError> {
+        let records = self
+            .lookup(&self.dois, doi_hash(doi), 1)
+            .await
+            .map_err(index_error)?;
+        Ok(records.first().map(Record::to_entry))
+    }
+
+    async fn search_by_title(&self, title: &str) -> Result<Vec<Entry>, ValidatorError> {
+        let records = self
+            .lookup(&self.titles, title_hash(title), 5)
+            .await
+            .map_err(index_error)?;
+        Ok(records.iter().map(Record::to_entry).collect())
+    }
+
+    fn name(&self) -> &'static str {
+        "DBLP"
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const DUMP: &str = r#"<?xml version="1.0" encoding="ISO-8859-1"?>
+<!DOCTYPE dblp SYSTEM "dblp.dtd">
+<dblp>
+<www mdate="2020-01-01" key="homepages/h/KaimingHe"><author>Kaiming He</author><title>Home Page</title></www>
+<inproceedings mdate="2021-06-01" key="conf/cvpr/HeZRS16">
+<author>Kaiming He</author>
+<author>Xiangyu Zhang</author>
+<title>Deep Residual Learning for Image Recognition.</title>
+<pages>770-778</pages>
+<year>2016</year>
+<booktitle>CVPR</booktitle>
+<ee>https://doi.org/10.1109/CVPR.2016.90</ee>
+</inproceedings>
+<article mdate="2022-01-01" key="journals/x/Muller20">
+<author>J&uuml;rgen M&uuml;ller</author>
+<title>On H<sub>2</sub> Storage.</title>
+<year>2020</year>
+<journal>J. Chem.</journal>
+</article>
+</dblp>"#;
+
+    #[tokio::test]
+    async fn dumps_are_indexed_and_searched() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("dblp.xml");
+        std::fs::write(&path, DUMP).unwrap();
+
+        assert!(!DblpDump::is_indexed(&path));
+        let dump = DblpDump::open(&path).unwrap();
+        assert!(DblpDump::is_indexed(&path));
+
+        let entry = dump
+            .search_by_doi("https://doi.org/10.1109/cvpr.2016.90")
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(entry.entry_type, "inproceedings");
+        assert_eq!(entry.field(DBLP_KEY_FIELD), Some("conf/cvpr/HeZRS16"));
+        assert_eq!(entry.year, Some(2016));
+
+        let entries = dump.search_by_title("On H2 storage").await.unwrap();
+        assert_eq!(entries.len(), 1);
+        assert_eq!(entries[0].authors, vec![Arc::from("Jürgen Müller")]);
+        assert_eq!(entries[0].title.as_deref(), Some("On H2 Storage"));
+
+        assert!(dump.search_by_title("Home Page").await.unwrap().is_empty());
+    }
+}
@@ -0,0 +1,264 @@
+use super::{async_trait, Validator, ValidatorError};
+use crate::entry::Entry;
+use crate::lint::{normalize_doi, normalize_issn};
+use reqwest::Client;
+use serde::Deserialize;
+use std::sync::Arc;
+
+const DOAJ_API_BASE: &str = "https://doaj.org/api/search";
+
+/// Field of a DOAJ journal record holding the journal's alternative titles, one per line
+pub const ALTERNATIVE_TITLES_FIELD: &str = "alternative_titles";
+
+/// Client for the Directory of Open Access Journals, which indexes the articles and
+/// the metadata of peer-reviewed open-access journals
+pub struct DoajClient {
+    client: Client,
+}
+
+impl DoajClient {
+    pub fn new() -> Self {
+        let client = Client::builder()
+            .user_agent("bibval/0.1.0 (https://github.com/femtomc/bibval)")
+            .build()
+            .expect("Failed to create HTTP client");
+        Self { client }
+    }
+
+    /// Look up the journal an ISSN ("1932-6203") was assigned to. The record is returned
+    /// as an entry whose title and venue are the journal's title.
+    pub async fn search_by_issn(&self, issn: &str) -> Result<Option<Entry>, ValidatorError> {
+        let url = format!(
+            "{}/journals/{}",
+            DOAJ_API_BASE,
+            urlencoding::encode(&format!("issn:\"{}\"", issn))
+        );
+        let page: Page<Journal> = self.get(&url).await?;
+        Ok(page.results.first().map(|j| j.bibjson.to_entry()))
+    }
+
+    async fn search_articles(&self, query: &str) -> Result<Vec<Entry>, ValidatorError> {
+        let url = format!(
+            "{}/articles/{}?pageSize=5",
+            DOAJ_API_BASE,
+            urlencoding::encode(query)
+        );
+        let page: Page<Article> = self.get(&url).await?;
+        Ok(page.results.iter().map(|a| a.bibjson.to_entry()).collect())
+    }
+
+    async fn get<T: for<'de> Deserialize<'de>>(
+        &self,
+        url: &str,
+    ) -> Result<Page<T>, ValidatorError> {
+        let response = self.client.get(url).send().await?;
+
+        if response.status() == reqwest::StatusCode::TOO_MANY_REQUESTS {
+            return Err(ValidatorError::RateLimited);
+        }
+
+        if !response.status().is_success() {
+            return Ok(Page {
+                results: Vec::new(),
+            });
+        }
+
+        response.json().await.map_err(|e| {
+            ValidatorError::ParseError(format!("Failed to parse DOAJ response: {}", e))
+        })
+    }
+}
+
+impl Default for DoajClient {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(bound(deserialize = "T: Deserialize<'de>"))]
+struct Page<T> {
+    #[serde(default)]
+    results: Vec<T>,
+}
+
+#[derive(Debug, Deserialize)]
+struct Article {
+    bibjson: ArticleBibjson,
+}
+
+#[derive(Debug, Deserialize)]
+struct ArticleBibjson {
+    title: Option<String>,
+    year: Option<String>,
+    #[serde(default)]
+    author: Vec<Author>,
+    journal: Option<ArticleJournal>,
+    #[serde(default)]
+    identifier: Vec<Identifier>,
+    start_page: Option<String>,
+    end_page: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+struct Author {
+    name: String,
+}
+
+#[derive(Debug, Deserialize)]
+struct ArticleJournal {
+    title: Option<String>,
+    volume: Option<String>,
+    number: Option<String>,
+    publisher: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+struct Identifier {
+    #[serde(rename = "type")]
+    id_type: String,
+    id: String,
+}
+
+impl ArticleBibjson {
+    fn to_entry(&self) -> Entry {
+        let doi = self
+            .identifier
+            .iter()
+            .find(|i| i.id_type.eq_ignore_ascii_case("doi"))
+            .map(|i| normalize_doi(&i.id));
+        let mut entry = Entry::new(doi.clone().unwrap_or_default(), "article".to_string());
+
+        entry.title = self.title.as_deref().map(Arc::from);
+        entry.authors = self
+            .author
+            .iter()
+            .map(|a| Arc::from(a.name.as_str()))
+            .collect();
+        entry.year = self.year.as_deref().and_then(|y| y.trim().parse().ok());
+        entry.doi = doi;
+
+        let journal = self.journal.as_ref();
+        entry.venue = journal.and_then(|j| j.title.as_deref()).map(Arc::from);
+        let pages = match (&self.start_page, &self.end_page) {
+            (Some(start), Some(end)) => Some(format!("{}--{}", start, end)),
+            (Some(start), None) => Some(start.clone()),
+            _ => None,
+        };
+        let issns: Vec<&str> = self
+            .identifier
+            .iter()
+            .filter(|i| matches!(i.id_type.as_str(), "pissn" | "eissn"))
+            .map(|i| i.id.as_str())
+            .collect();
+        for (name, value) in [
+            ("volume", journal.and_then(|j| j.volume.clone())),
+            ("number", journal.and_then(|j| j.number.clone())),
+            ("publisher", journal.and_then(|j| j.publisher.clone())),
+            ("pages", pages),
+            ("issn", (!issns.is_empty()).then(|| issns.join(", "))),
+        ] {
+            if let Some(value) = value.filter(|v| !v.is_empty()) {
+                entry.fields.insert(name.to_string(), value);
+            }
+        }
+        entry.issn = issns.first().and_then(|issn| normalize_issn(issn));
+
+        entry
+    }
+}
+
+#[derive(Debug, Deserialize)]
+struct Journal {
+    bibjson: JournalBibjson,
+}
+
+#[derive(Debug, Deserialize)]
+struct JournalBibjson {
+    title: String,
+    alternative_title: Option<String>,
+    pissn: Option<String>,
+    eissn: Option<String>,
+    publisher: Option<Publisher>,
+}
+
+#[derive(Debug, Deserialize)]
+struct Publisher {
+    name: Option<String>,
+}
+
+impl JournalBibjson {
+    fn to_entry(&self) -> Entry {
+        let issns: Vec<&str> = [&self.pissn, &self.eissn]
+            .into_iter()
+            .flatten()
+            .map(String::as_str)
+            .collect();
+        let mut entry = Entry::new(issns.join(", "), "periodical".to_string());
+
+        entry.title = Some(Arc::from(self.title.as_str()));
+        entry.venue = Some(Arc::from(self.title.as_str()));
+        entry.fields.insert("issn".to_string(), issns.join(", "));
+        entry.issn = issns.first().and_then(|issn| normalize_issn(issn));
+        if let Some(alternative) = &self.alternative_title {
+            entry
+                .fields
+                .insert(ALTERNATIVE_TITLES_FIELD.to_string(), alternative.clone());
+        }
+        if let Some(name) = self.publisher.as_ref().and_then(|p| p.name.clone()) {
+            entry.fields.insert("publisher".to_string(), name);
+        }
+
+        entry
+    }
+}
+
+#[async_trait]
+impl Validator for DoajClient {
+    async fn search_by_doi(&self, doi: &str) -> Result<Option<Entry>, ValidatorError> {
+        let query = format!("doi:\"{}\"", normalize_doi(doi));
+        Ok(self.search_articles(&query).await?.into_iter().next())
+    }
+
+    async fn search_by_title(&self, title: &str) -> Result<Vec<Entry>, ValidatorError> {
+        // Quotes would end the phrase query early
+        let query = format!("bibjson.title:\"{}\"", title.replace('"', ""));
+        self.search_articles(&query).await
+    }
+
+    fn name(&self) -> &'static str {
+        "DOAJ"
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn articles_and_journals_are_read() {
+        let json = r#"{"total": 1, "results": [{"id": "abc", "bibjson": {
+            "title": "Why Most Published Research Findings Are False",
+            "year": "2005",
+            "author": [{"name": "John P. A. Ioannidis"}],
+            "journal": {"title": "PLoS Medicine", "volume": "2", "number": "8",
+                "publisher": "Public Library of Science (PLoS)"},
+            "identifier": [{"type": "doi", "id": "10.1371/journal.pmed.0020124"},
+                {"type": "eissn", "id": "1549-1676"}],
+            "start_page": "e124"}}]}"#;
+        let page: Page<Article> = serde_json::from_str(json).unwrap();
+        let article = page.results[0].bibjson.to_entry();
+        assert_eq!(article.doi.as_deref(), Some("10.1371/journal.pmed.0020124"));
+        assert_eq!(article.venue.as_deref(), Some("PLoS Medicine"));
+        assert_eq!(article.field("pages"), Some("e124"));
+        assert_eq!(article.field("issn"), Some("1549-1676"));
+
+        let json = r#"{"results": [{"bibjson": {"title": "PLOS ONE",
+            "alternative_title": "Public Library of Science ONE",
+            "eissn": "1932-6203", "publisher": {"name": "Public Library of Science"}}}]}"#;
+        let page: Page<Journal> = serde_json::from_str(json).unwrap();
+        let journal = page.results[0].bibjson.to_entry();
+        assert_eq!(journal.venue.as_deref(), Some("PLOS ONE"));
+        assert_eq!(journal.field("issn"), Some("1932-6203"));
+    }
+}
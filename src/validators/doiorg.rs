@@ -0,0 +1,174 @@
+use super::{async_trait, Validator, ValidatorError};
+use crate::csl::{item_to_entry, CslItem};
+use crate::entry::Entry;
+use crate::lint::normalize_doi;
+use reqwest::Client;
+use serde::Deserialize;
+use serde_json::Value;
+
+const DOI_ORG_BASE: &str = "https://doi.org";
+
+/// Client for doi.org itself, which serves the metadata of a DOI from whichever agency
+/// registered it (CrossRef, DataCite, mEDRA, JaLC, KISTI, ...) through content negotiation
+pub struct DoiOrgClient {
+    client: Client,
+}
+
+impl DoiOrgClient {
+    pub fn new() -> Self {
+        let client = Client::builder()
+            .user_agent("bibval/0.1.0 (https://github.com/femtomc/bibval)")
+            .build()
+            .expect("Failed to create HTTP client");
+        Self { client }
+    }
+
+    /// The agency a DOI is registered with ("Crossref", "DataCite", "mEDRA"), or `None`
+    /// when the DOI does not exist
+    pub async fn registration_agency(&self, doi: &str) -> Result<Option<String>, ValidatorError> {
+        let url = format!("{}/ra/{}", DOI_ORG_BASE, normalize_doi(doi));
+        let response = self.client.get(&url).send().await?;
+
+        if response.status() == reqwest::StatusCode::TOO_MANY_REQUESTS {
+            return Err(ValidatorError::RateLimited);
+        }
+
+        if !response.status().is_success() {
+            return Ok(None);
+        }
+
+        let agencies: Vec<Agency> = response.json().await.map_err(|e| {
+            ValidatorError::ParseError(format!("Failed to parse doi.org response: {}", e))
+        })?;
+        Ok(agencies.into_iter().next().and_then(|a| a.ra))
+    }
+
+    /// The BibTeX the registration agency serves for a DOI, or `None` when the DOI does
+    /// not exist or its agency offers no BibTeX
+    pub async fn fetch_bibtex(&self, doi: &str) -> Result<Option<String>, ValidatorError> {
+        let url = format!("{}/{}", DOI_ORG_BASE, normalize_doi(doi));
+        let response = self
+            .client
+            .get(&url)
+            .header("Accept", "application/x-bibtex")
+            .send()
+            .await?;
+
+        if response.status() == reqwest::StatusCode::TOO_MANY_REQUESTS {
+            return Err(ValidatorError::RateLimited);
+        }
+
+        if !response.status().is_success() {
+            return Ok(None);
+        }
+
+        let bibtex = response.text().await?;
+        Ok(Some(bibtex.trim().to_string()).filter(|b| b.starts_with('@')))
+    }
+}
+
+impl Default for DoiOrgClient {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[derive(Debug, Deserialize)]
+struct Agency {
+    /// Absent, with a `status` instead, for DOIs that don't exist
+    #[serde(rename = "RA")]
+    ra: Option<String>,
+}
+
+/// Read the CSL-JSON doi.org answers with. The agencies disagree on a few shapes: CrossRef
+/// writes `container-title`, `ISSN` and `ISBN` as arrays and may omit `id`.
+fn parse_csl(doi: &str, mut item: Value) -> Result<Entry, ValidatorError> {
+    let Some(object) = item.as_object_mut() else {
+        return Err(ValidatorError::ParseError(
+            "doi.org returned CSL that isn't an object".to_string(),
+        ));
+    };
+    for name in ["container-title", "ISSN", "ISBN"] {
+        if let Some(Value::Array(values)) = object.get(name) {
+            let first = values.first().cloned().unwrap_or(Value::Null);
+            object.insert(name.to_string(), first);
+        }
+    }
+    object.insert("id".to_string(), Value::String(doi.to_string()));
+
+    let item: CslItem = serde_json::from_value(item).map_err(|e| {
+        ValidatorError::ParseError(format!("Failed to parse doi.org CSL-JSON: {}", e))
+    })?;
+    let mut entry = item_to_entry(&item);
+    entry.doi = Some(normalize_doi(entry.doi.as_deref().unwrap_or(doi)));
+    Ok(entry)
+}
+
+#[async_trait]
+impl Validator for DoiOrgClient {
+    async fn search_by_doi(&self, doi: &str) -> Result<Option<Entry>, ValidatorError> {
+        let doi = normalize_doi(doi);
+        let url = format!("{}/{}", DOI_ORG_BASE, doi);
+        let response = self
+            .client
+            .get(&url)
+            .header("Accept", "application/vnd.citationstyles.csl+json")
+            .send()
+            .await?;
+
+        if response.status() == reqwest::StatusCode::TOO_MANY_REQUESTS {
+            return Err(ValidatorError::RateLimited);
+        }
+
+        // 404 for unknown DOIs, 406 when the agency offers no CSL-JSON
+        if !response.status().is_success() {
+            return Ok(None);
+        }
+
+        let item: Value = response.json().await.map_err(|e| {
+            ValidatorError::ParseError(format!("Failed to parse doi.org response: {}", e))
+        })?;
+        parse_csl(&doi, item).map(Some)
+    }
+
+    async fn search_by_title(&self, _title: &str) -> Result<Vec<Entry>, ValidatorError> {
+        // doi.org only resolves DOIs
+        Ok(Vec::new())
+    }
+
+    fn name(&self) -> &'static str {
+        "doi.org"
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn csl_from_any_agency_is_read() {
+        // mEDRA, with a string container title
+        let json = r#"{"type": "article-journal", "id": "https://doi.org/10.1400/123456",
+            "title": "Il restauro dei codici miniati", "DOI": "10.1400/123456",
+            "author": [{"family": "Rossi", "given": "Maria"}],
+            "issued": {"date-parts": [[2004]]}, "container-title": "Studi medievali",
+            "volume": "45", "page": "101-130"}"#;
+        let entry = parse_csl("10.1400/123456", serde_json::from_str(json).unwrap()).unwrap();
+        assert_eq!(entry.key, "10.1400/123456");
+        assert_eq!(
+            entry.title.as_deref(),
+            Some("Il restauro dei codici miniati")
+        );
+        assert_eq!(entry.venue.as_deref(), Some("Studi medievali"));
+        assert_eq!(entry.year, Some(2004));
+
+        // CrossRef, with arrays and no id
+        let json = r#"{"type": "article-journal", "title": "Deep learning",
+            "DOI": "10.1038/NATURE14539", "container-title": ["Nature"],
+            "ISSN": ["0028-0836", "1476-4687"], "issued": {"date-parts": [[2015, 5, 27]]}}"#;
+        let entry = parse_csl("10.1038/nature14539", serde_json::from_str(json).unwrap()).unwrap();
+        assert_eq!(entry.venue.as_deref(), Some("Nature"));
+        assert_eq!(entry.doi.as_deref(), Some("10.1038/nature14539"));
+        assert_eq!(entry.field("issn"), Some("0028-0836"));
+    }
+}
@@ -0,0 +1,333 @@
+use super::{async_trait, Validator, ValidatorError};
+use crate::entry::Entry;
+use regex_lite::Regex;
+use reqwest::Client;
+use serde::Deserialize;
+use serde_json::Value;
+use std::sync::{Arc, OnceLock};
+use std::time::{Duration, Instant};
+use tokio::sync::Mutex;
+
+const OPS_API_BASE: &str = "https://ops.epo.org/3.2/rest-services/published-data/publication";
+const OPS_TOKEN_URL: &str = "https://ops.epo.org/3.2/auth/accesstoken";
+
+/// Seconds before a token's expiry at which a new one is requested
+const TOKEN_EXPIRY_MARGIN_SECS: u64 = 60;
+
+/// Country codes implied by biblatex's patent types (`@patent{..., type = {patentus}}`)
+const PATENT_TYPE_COUNTRIES: &[(&str, &str)] = &[
+    ("patentus", "US"),
+    ("patrequs", "US"),
+    ("patenteu", "EP"),
+    ("patreqeu", "EP"),
+    ("patentde", "DE"),
+    ("patreqde", "DE"),
+    ("patentfr", "FR"),
+    ("patreqfr", "FR"),
+    ("patentuk", "GB"),
+    ("patrequk", "GB"),
+    ("patentjp", "JP"),
+    ("patreqjp", "JP"),
+    ("patentinternational", "WO"),
+    ("patreqinternational", "WO"),
+];
+
+/// The publication number of a patent entry in EPO's EPODOC format ("US7654321").
+///
+/// The `number` field may carry the country and kind code ("US 7,654,321 B2"); without
+/// a country, it is taken from a biblatex patent `type`.
+pub fn patent_number(entry: &Entry) -> Option<String> {
+    static REGEX: OnceLock<Regex> = OnceLock::new();
+    let regex = REGEX.get_or_init(|| Regex::new(r"^([A-Z]{2})?(\d+)(?:[A-Z]\d?)?$").unwrap());
+
+    let raw: String = entry
+        .field("number")?
+        .to_uppercase()
+        .chars()
+        .filter(|c| c.is_ascii_alphanumeric())
+        .collect();
+    let captures = regex.captures(&raw)?;
+    let country = match captures.get(1) {
+        Some(country) => country.as_str(),
+        None => {
+            let patent_type = entry.field("type")?.to_lowercase();
+            PATENT_TYPE_COUNTRIES
+                .iter()
+                .find(|(t, _)| *t == patent_type)
+                .map(|(_, country)| *country)?
+        }
+    };
+    let mut digits = captures[2].to_string();
+    // US application publications ("2015/0123456") drop the serial's leading zero
+    if country == "US" && digits.len() == 11 && digits.as_bytes()[4] == b'0' {
+        digits.remove(4);
+    }
+    Some(format!("{}{}", country, digits))
+}
+
+/// Consumer key and secret of an EPO Open Patent Services account
+#[derive(Debug, Clone)]
+pub struct EpoCredentials {
+    pub key: String,
+    pub secret: String,
+}
+
+/// Client for EPO Open Patent Services, which holds the bibliographic data of patents
+/// from most patent offices
+pub struct EpoClient {
+    client: Client,
+    credentials: EpoCredentials,
+    /// Access token and when it expires
+    token: Mutex<Option<(String, Instant)>>,
+}
+
+impl EpoClient {
+    pub fn new(credentials: EpoCredentials) -> Self {
+        let client = Client::builder()
+            .user_agent("bibval/0.1.0 (https://github.com/femtomc/bibval)")
+            .build()
+            .expect("Failed to create HTTP client");
+        Self {
+            client,
+            credentials,
+            token: Mutex::new(None),
+        }
+    }
+
+    /// Look up a patent by its EPODOC publication number
+    pub async fn search_by_number(&self, number: &str) -> Result<Option<Entry>, ValidatorError> {
+        let token = self.access_token().await?;
+        let url = format!("{}/epodoc/{}/biblio", OPS_API_BASE, number);
+
+        let response = self
+            .client
+            .get(&url)
+            .bearer_auth(token)
+            .header("Accept", "application/json")
+            .send()
+            .await?;
+
+        // OPS signals an exhausted quota with 403 as well as 429
+        if response.status() == reqwest::StatusCode::TOO_MANY_REQUESTS
+            || response.status() == reqwest::StatusCode::FORBIDDEN
+        {
+            return Err(ValidatorError::RateLimited);
+        }
+
+        if response.status() == reqwest::StatusCode::NOT_FOUND {
+            return Ok(None);
+        }
+
+        if !response.status().is_success() {
+            return Err(ValidatorError::ParseError(format!(
+                "EPO OPS returned {}",
+                response.status()
+            )));
+        }
+
+        let body: Value = response.json().await.map_err(|e| {
+            ValidatorError::ParseError(format!("Failed to parse EPO OPS response: {}", e))
+        })?;
+        Ok(parse_biblio(&body))
+    }
+
+    /// A current access token, requesting a new one when it is about to expire
+    async fn access_token(&self) -> Result<String, ValidatorError> {
+        let mut token = self.token.lock().await;
+        if let Some((value, expires)) = token.as_ref() {
+            if Instant::now() < *expires {
+                return Ok(value.clone());
+            }
+        }
+
+        let response = self
+            .client
+            .post(OPS_TOKEN_URL)
+            .basic_auth(&self.credentials.key, Some(&self.credentials.secret))
+            .form(&[("grant_type", "client_credentials")])
+            .send()
+            .await?;
+
+        if !response.status().is_success() {
+            return Err(ValidatorError::ParseError(format!(
+                "EPO rejected the OPS credentials ({})",
+                response.status()
+            )));
+        }
+
+        let granted: TokenResponse = response.json().await.map_err(|e| {
+            ValidatorError::ParseError(format!("Failed to parse EPO token response: {}", e))
+        })?;
+        // OPS sends the lifetime as a string
+        let lifetime = granted
+            .expires_in
+            .parse::<u64>()
+            .unwrap_or(0)
+            .saturating_sub(TOKEN_EXPIRY_MARGIN_SECS);
+        let expires = Instant::now() + Duration::from_secs(lifetime);
+        *token = Some((granted.access_token.clone(), expires));
+        Ok(granted.access_token)
+    }
+}
+
+#[derive(Debug, Deserialize)]
+struct TokenResponse {
+    access_token: String,
+    expires_in: String,
+}
+
+/// OPS renders XML as JSON, so an element that can repeat is an object when it occurs
+/// once and an array otherwise
+fn list(value: &Value) -> Vec<&Value> {
+    match value {
+        Value::Array(items) => items.iter().collect(),
+        Value::Null => Vec::new(),
+        other => vec![other],
+    }
+}
+
+/// The text content of an element
+fn text(value: &Value) -> Option<&str> {
+    value.get("$").and_then(Value::as_str)
+}
+
+/// Read the first exchange document of a `biblio` response
+fn parse_biblio(body: &Value) -> Option<Entry> {
+    let document =
+        *list(&body["ops:world-patent-data"]["exchange-documents"]["exchange-document"]).first()?;
+    let biblio = &document["bibliographic-data"];
+
+    let country = document["@country"].as_str().unwrap_or_default();
+    let number = document["@doc-number"].as_str().unwrap_or_default();
+    let kind = document["@kind"].as_str().unwrap_or_default();
+    let mut entry = Entry::new(format!("{}{}", country, number), "patent".to_string());
+
+    let titles = list(&biblio["invention-title"]);
+    entry.title = titles
+        .iter()
+        .find(|t| t["@lang"] == "en")
+        .or(titles.first())
+        .and_then(|t| text(t))
+        .map(Arc::from);
+
+    // Inventors are listed in the office's original form ("Doe, Jane") and in EPODOC's
+    // ("DOE JANE [US]"); the original is closer to how citations write them
+    let inventors = list(&biblio["parties"]["inventors"]["inventor"]);
+    let names = |format: &str| -> Vec<&str> {
+        inventors
+            .iter()
+            .filter(|i| i["@data-format"] == format)
+            .filter_map(|i| text(&i["inventor-name"]["name"]))
+            .collect()
+    };
+    let original = names("original");
+    let names = if original.is_empty() {
+        names("epodoc")
+    } else {
+        original
+    };
+    entry.authors = names
+        .iter()
+        .map(|name| {
+            let name = name
+                .split(" [")
+                .next()
+                .unwrap_or(name)
+                .trim_end_matches(',');
+            match name.split_once(", ") {
+                Some((family, given)) => Arc::from(format!("{} {}", given.trim(), family)),
+                None => Arc::from(name.trim()),
+            }
+        })
+        .collect();
+
+    entry.year = list(&biblio["publication-reference"]["document-id"])
+        .iter()
+        .find_map(|id| text(&id["date"])?.get(..4)?.parse().ok());
+
+    entry.fields.insert(
+        "number".to_string(),
+        format!("{}{}{}", country, number, kind),
+    );
+    Some(entry)
+}
+
+#[async_trait]
+impl Validator for EpoClient {
+    async fn search_by_doi(&self, _doi: &str) -> Result<Option<Entry>, ValidatorError> {
+        // Patents have no DOIs
+        Ok(None)
+    }
+
+    async fn search_by_title(&self, _title: &str) -> Result<Vec<Entry>, ValidatorError> {
+        // Patents are only looked up by number
+        Ok(Vec::new())
+    }
+
+    fn name(&self) -> &'static str {
+        "EPO OPS"
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn patent_numbers_are_normalized() {
+        let patent = |number: &str, patent_type: Option<&str>| {
+            let mut entry = Entry::new("p".to_string(), "patent".to_string());
+            entry
+                .fields
+                .insert("number".to_string(), number.to_string());
+            if let Some(patent_type) = patent_type {
+                entry
+                    .fields
+                    .insert("type".to_string(), patent_type.to_string());
+            }
+            patent_number(&entry)
+        };
+
+        assert_eq!(
+            patent("US 7,654,321 B2", None).as_deref(),
+            Some("US7654321")
+        );
+        assert_eq!(
+            patent("7654321", Some("patentus")).as_deref(),
+            Some("US7654321")
+        );
+        assert_eq!(patent("EP1234567A1", None).as_deref(), Some("EP1234567"));
+        assert_eq!(
+            patent("US 2015/0123456 A1", None).as_deref(),
+            Some("US2015123456")
+        );
+        assert_eq!(patent("7654321", None), None);
+    }
+
+    #[test]
+    fn biblio_responses_are_read() {
+        let json = r#"{"ops:world-patent-data": {"exchange-documents": {"exchange-document": {
+            "@country": "US", "@doc-number": "7654321", "@kind": "B2",
+            "bibliographic-data": {
+                "publication-reference": {"document-id": [
+                    {"@document-id-type": "docdb", "date": {"$": "20100202"}},
+                    {"@document-id-type": "epodoc", "doc-number": {"$": "US7654321"}}
+                ]},
+                "invention-title": [
+                    {"@lang": "fr", "$": "Dispositif"},
+                    {"@lang": "en", "$": "Widget assembly"}
+                ],
+                "parties": {"inventors": {"inventor": [
+                    {"@data-format": "epodoc", "inventor-name": {"name": {"$": "DOE JANE [US]"}}},
+                    {"@data-format": "original", "inventor-name": {"name": {"$": "Doe, Jane"}}}
+                ]}}
+            }
+        }}}}"#;
+        let body: Value = serde_json::from_str(json).unwrap();
+        let entry = parse_biblio(&body).unwrap();
+        assert_eq!(entry.title.as_deref(), Some("Widget assembly"));
+        assert_eq!(entry.authors, vec![Arc::from("Jane Doe")]);
+        assert_eq!(entry.year, Some(2010));
+        assert_eq!(entry.field("number"), Some("US7654321B2"));
+    }
+}
@@ -0,0 +1,213 @@
+use super::datacite::{VERSION_COUNT_FIELD, VERSION_FIELD};
+use super::{async_trait, Validator, ValidatorError};
+use crate::entry::Entry;
+use crate::lint::normalize_doi;
+use reqwest::Client;
+use serde::Deserialize;
+use std::sync::Arc;
+
+const FIGSHARE_API_BASE: &str = "https://api.figshare.com/v2";
+
+/// DOI prefix figshare registers its items under
+const FIGSHARE_DOI_PREFIX: &str = "10.6084/m9.figshare.";
+
+/// The article ID and version named by a figshare DOI
+/// ("10.6084/m9.figshare.12345678.v2" -> (12345678, Some(2)))
+pub fn figshare_article(doi: &str) -> Option<(u64, Option<u32>)> {
+    let doi = normalize_doi(doi);
+    let rest = doi.strip_prefix(FIGSHARE_DOI_PREFIX)?;
+    let (id, version) = match rest.split_once(".v") {
+        Some((id, version)) => (id, Some(version.parse().ok()?)),
+        None => (rest, None),
+    };
+    Some((id.parse().ok()?, version))
+}
+
+/// Client for the figshare API, which hosts datasets, figures, posters and software
+pub struct FigshareClient {
+    client: Client,
+}
+
+impl FigshareClient {
+    pub fn new() -> Self {
+        let client = Client::builder()
+            .user_agent("bibval/0.1.0 (https://github.com/femtomc/bibval)")
+            .build()
+            .expect("Failed to create HTTP client");
+        Self { client }
+    }
+
+    async fn article(
+        &self,
+        id: u64,
+        version: Option<u32>,
+    ) -> Result<Option<Entry>, ValidatorError> {
+        let url = match version {
+            Some(version) => format!("{}/articles/{}/versions/{}", FIGSHARE_API_BASE, id, version),
+            None => format!("{}/articles/{}", FIGSHARE_API_BASE, id),
+        };
+        let response = self.client.get(&url).send().await?;
+
+        if response.status() == reqwest::StatusCode::TOO_MANY_REQUESTS {
+            return Err(ValidatorError::RateLimited);
+        }
+
+        if !response.status().is_success() {
+            return Ok(None);
+        }
+
+        let article: Article = response.json().await.map_err(|e| {
+            ValidatorError::ParseError(format!("Failed to parse figshare response: {}", e))
+        })?;
+
+        let mut entry = article.to_entry();
+        // The unversioned DOI always resolves to the latest version, so like a concept
+        // DOI it covers every version
+        if let (None, Some(latest)) = (version, article.version.filter(|v| *v > 1)) {
+            entry
+                .fields
+                .insert(VERSION_COUNT_FIELD.to_string(), latest.to_string());
+        }
+        Ok(Some(entry))
+    }
+}
+
+impl Default for FigshareClient {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[derive(Debug, Deserialize)]
+struct Article {
+    title: Option<String>,
+    doi: Option<String>,
+    #[serde(default)]
+    authors: Vec<Author>,
+    published_date: Option<String>,
+    version: Option<u32>,
+    defined_type_name: Option<String>,
+    url_public_html: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+struct Author {
+    full_name: String,
+}
+
+/// A search hit, which lacks authors until its full record is fetched
+#[derive(Debug, Deserialize)]
+struct SearchHit {
+    id: u64,
+}
+
+impl Article {
+    fn to_entry(&self) -> Entry {
+        let entry_type = match self.defined_type_name.as_deref() {
+            Some("dataset") => "dataset",
+            Some("software") => "software",
+            Some("journal contribution") => "article",
+            Some("thesis") => "phdthesis",
+            _ => "misc",
+        };
+        let mut entry = Entry::new(self.doi.clone().unwrap_or_default(), entry_type.to_string());
+
+        entry.title = self.title.as_deref().map(Arc::from);
+        entry.authors = self
+            .authors
+            .iter()
+            .map(|a| Arc::from(a.full_name.as_str()))
+            .collect();
+        entry.year = self
+            .published_date
+            .as_deref()
+            .and_then(|d| d.get(..4)?.parse().ok());
+        entry.doi = self.doi.clone();
+        entry.url = self.url_public_html.clone();
+        if let Some(version) = self.version {
+            entry
+                .fields
+                .insert(VERSION_FIELD.to_string(), version.to_string());
+        }
+
+        entry
+    }
+}
+
+#[async_trait]
+impl Validator for FigshareClient {
+    async fn search_by_doi(&self, doi: &str) -> Result<Option<Entry>, ValidatorError> {
+        match figshare_article(doi) {
+            Some((id, version)) => self.article(id, version).await,
+            None => Ok(None),
+        }
+    }
+
+    async fn search_by_title(&self, title: &str) -> Result<Vec<Entry>, ValidatorError> {
+        let body = serde_json::json!({
+            "search_for": format!(":title: \"{}\"", title.replace('"', "")),
+            "page_size": 3,
+        });
+        let response = self
+            .client
+            .post(format!("{}/articles/search", FIGSHARE_API_BASE))
+            .json(&body)
+            .send()
+            .await?;
+
+        if response.status() == reqwest::StatusCode::TOO_MANY_REQUESTS {
+            return Err(ValidatorError::RateLimited);
+        }
+
+        if !response.status().is_success() {
+            return Ok(Vec::new());
+        }
+
+        let hits: Vec<SearchHit> = response.json().await.map_err(|e| {
+            ValidatorError::ParseError(format!("Failed to parse figshare search: {}", e))
+        })?;
+
+        let mut entries = Vec::new();
+        for hit in hits {
+            entries.extend(self.article(hit.id, None).await?);
+        }
+        Ok(entries)
+    }
+
+    fn name(&self) -> &'static str {
+        "figshare"
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn figshare_dois_name_articles_and_versions() {
+        assert_eq!(
+            figshare_article("10.6084/m9.figshare.12345678.v2"),
+            Some((12345678, Some(2)))
+        );
+        assert_eq!(
+            figshare_article("https://doi.org/10.6084/M9.FIGSHARE.12345678"),
+            Some((12345678, None))
+        );
+        assert_eq!(figshare_article("10.5281/zenodo.123"), None);
+
+        let json = r#"{
+            "id": 12345678,
+            "title": "A Poster",
+            "doi": "10.6084/m9.figshare.12345678.v2",
+            "authors": [{"id": 1, "full_name": "Jane Doe"}],
+            "published_date": "2020-05-01T10:00:00Z",
+            "version": 2,
+            "defined_type_name": "poster"
+        }"#;
+        let article: Article = serde_json::from_str(json).unwrap();
+        let entry = article.to_entry();
+        assert_eq!(entry.entry_type, "misc");
+        assert_eq!(entry.field(VERSION_FIELD), Some("2"));
+        assert_eq!(entry.year, Some(2020));
+    }
+}
@@ -0,0 +1,210 @@
+use super::{async_trait, Validator, ValidatorError};
+use crate::entry::Entry;
+use regex_lite::Regex;
+use reqwest::Client;
+use serde::Deserialize;
+use std::sync::{Arc, OnceLock};
+
+const GITHUB_API_BASE: &str = "https://api.github.com";
+
+/// Field of a matched entry holding the repository's tags, newline-separated
+pub const GITHUB_TAGS_FIELD: &str = "github_tags";
+/// Field of a matched entry set when the repository has been archived (made read-only)
+pub const GITHUB_ARCHIVED_FIELD: &str = "github_archived";
+
+/// The address of the repository a software entry cites, from its `repository` field
+/// or, failing that, its URL
+pub fn repository_url(entry: &Entry) -> Option<&str> {
+    let url = entry.field("repository").or(entry.url.as_deref())?.trim();
+    let hosted = [
+        "github.com/",
+        "gitlab.com/",
+        "bitbucket.org/",
+        "codeberg.org/",
+    ]
+    .iter()
+    .any(|host| url.contains(host));
+    hosted.then_some(url)
+}
+
+/// The owner and name of a GitHub repository ("https://github.com/numpy/numpy.git")
+pub fn github_repository(url: &str) -> Option<(String, String)> {
+    static REGEX: OnceLock<Regex> = OnceLock::new();
+    let regex = REGEX.get_or_init(|| {
+        Regex::new(r"github\.com[/:]([A-Za-z0-9_.-]+)/([A-Za-z0-9_.-]+?)(?:\.git)?(?:[/#?].*)?$")
+            .unwrap()
+    });
+    let captures = regex.captures(url)?;
+    Some((captures[1].to_string(), captures[2].to_string()))
+}
+
+/// Client for the GitHub REST API. Unauthenticated clients get 60 requests an hour.
+pub struct GitHubClient {
+    client: Client,
+    token: Option<String>,
+}
+
+impl GitHubClient {
+    pub fn new(token: Option<String>) -> Self {
+        let client = Client::builder()
+            .user_agent("bibval/0.1.0 (https://github.com/femtomc/bibval)")
+            .build()
+            .expect("Failed to create HTTP client");
+        Self { client, token }
+    }
+
+    /// Look up a repository and its tags; `None` when the repository does not exist
+    pub async fn search_by_repository(
+        &self,
+        owner: &str,
+        name: &str,
+    ) -> Result<Option<Entry>, ValidatorError> {
+        let url = format!("{}/repos/{}/{}", GITHUB_API_BASE, owner, name);
+        let Some(repository) = self.get::<Repository>(&url).await? else {
+            return Ok(None);
+        };
+        // Only the first page of tags, which GitHub lists newest first
+        let tags_url = format!("{}/tags?per_page=100", url);
+        let tags = self.get::<Vec<Tag>>(&tags_url).await?.unwrap_or_default();
+
+        Ok(Some(repository.to_entry(&tags)))
+    }
+
+    async fn get<T: for<'de> Deserialize<'de>>(
+        &self,
+        url: &str,
+    ) -> Result<Option<T>, ValidatorError> {
+        let mut request = self
+            .client
+            .get(url)
+            .header("Accept", "application/vnd.github+json");
+        if let Some(token) = &self.token {
+            request = request.bearer_auth(token);
+        }
+        let response = request.send().await?;
+
+        // GitHub signals an exhausted rate limit with 403 as well as 429
+        if response.status() == reqwest::StatusCode::TOO_MANY_REQUESTS
+            || response.status() == reqwest::StatusCode::FORBIDDEN
+        {
+            return Err(ValidatorError::RateLimited);
+        }
+
+        if response.status() == reqwest::StatusCode::NOT_FOUND {
+            return Ok(None);
+        }
+
+        if !response.status().is_success() {
+            return Err(ValidatorError::ParseError(format!(
+                "GitHub returned {}",
+                response.status()
+            )));
+        }
+
+        let body = response.json().await.map_err(|e| {
+            ValidatorError::ParseError(format!("Failed to parse GitHub response: {}", e))
+        })?;
+        Ok(Some(body))
+    }
+}
+
+#[derive(Debug, Deserialize)]
+struct Repository {
+    /// "numpy/numpy"; differs from the requested name when the repository has moved
+    full_name: String,
+    html_url: String,
+    description: Option<String>,
+    #[serde(default)]
+    archived: bool,
+    /// "2006-01-19T08:36:40Z"
+    created_at: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+struct Tag {
+    name: String,
+}
+
+impl Repository {
+    fn to_entry(&self, tags: &[Tag]) -> Entry {
+        let mut entry = Entry::new(self.full_name.clone(), "software".to_string());
+
+        let name = self.full_name.rsplit('/').next().unwrap_or(&self.full_name);
+        entry.title = Some(Arc::from(name));
+        entry.year = self
+            .created_at
+            .as_deref()
+            .and_then(|d| d.get(..4)?.parse().ok());
+        entry.url = Some(self.html_url.clone());
+        if let Some(description) = &self.description {
+            entry
+                .fields
+                .insert("abstract".to_string(), description.clone());
+        }
+        if !tags.is_empty() {
+            let tags: Vec<&str> = tags.iter().map(|t| t.name.as_str()).collect();
+            entry
+                .fields
+                .insert(GITHUB_TAGS_FIELD.to_string(), tags.join("\n"));
+        }
+        if self.archived {
+            entry
+                .fields
+                .insert(GITHUB_ARCHIVED_FIELD.to_string(), "true".to_string());
+        }
+
+        entry
+    }
+}
+
+#[async_trait]
+impl Validator for GitHubClient {
+    async fn search_by_doi(&self, _doi: &str) -> Result<Option<Entry>, ValidatorError> {
+        // Repositories have no DOIs
+        Ok(None)
+    }
+
+    async fn search_by_title(&self, _title: &str) -> Result<Vec<Entry>, ValidatorError> {
+        // Repositories are looked up by address
+        Ok(Vec::new())
+    }
+
+    fn name(&self) -> &'static str {
+        "GitHub"
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn repository_addresses_are_parsed() {
+        let repository = |url: &str| github_repository(url);
+        let numpy = Some(("numpy".to_string(), "numpy".to_string()));
+        assert_eq!(repository("https://github.com/numpy/numpy"), numpy);
+        assert_eq!(repository("https://github.com/numpy/numpy.git"), numpy);
+        assert_eq!(repository("git@github.com:numpy/numpy.git"), numpy);
+        assert_eq!(
+            repository("https://github.com/pytorch/vision/tree/v0.15.2"),
+            Some(("pytorch".to_string(), "vision".to_string()))
+        );
+        assert_eq!(repository("https://gitlab.com/inkscape/inkscape"), None);
+
+        let json = r#"{"full_name": "numpy/numpy", "html_url": "https://github.com/numpy/numpy",
+            "description": "The fundamental package for scientific computing with Python.",
+            "archived": false, "created_at": "2010-09-13T23:02:39Z"}"#;
+        let record: Repository = serde_json::from_str(json).unwrap();
+        let tags = [
+            Tag {
+                name: "v2.0.0".to_string(),
+            },
+            Tag {
+                name: "v1.26.4".to_string(),
+            },
+        ];
+        let entry = record.to_entry(&tags);
+        assert_eq!(entry.field(GITHUB_TAGS_FIELD), Some("v2.0.0\nv1.26.4"));
+        assert_eq!(entry.field(GITHUB_ARCHIVED_FIELD), None);
+    }
+}
@@ -0,0 +1,173 @@
+use super::{async_trait, Validator, ValidatorError};
+use crate::entry::Entry;
+use crate::lint::normalize_doi;
+use reqwest::Client;
+use serde::Deserialize;
+use std::sync::Arc;
+
+const HAL_API_BASE: &str = "https://api.archives-ouvertes.fr/search/";
+const HAL_FIELDS: &str = "halId_s,title_s,authFullName_s,producedDateY_i,journalTitle_s,\
+conferenceTitle_s,doiId_s,docType_s,page_s,volume_s";
+
+/// Client for HAL, the French national open archive
+pub struct HalClient {
+    client: Client,
+}
+
+impl HalClient {
+    pub fn new() -> Self {
+        let client = Client::builder()
+            .user_agent("bibval/0.1.0 (https://github.com/femtomc/bibval)")
+            .build()
+            .expect("Failed to create HTTP client");
+        Self { client }
+    }
+
+    /// Look up a deposit by HAL ID ("hal-01234567")
+    pub async fn search_by_hal_id(&self, hal_id: &str) -> Result<Option<Entry>, ValidatorError> {
+        let query = format!("halId_s:\"{}\"", hal_id.trim());
+        Ok(self.search(&query, 1).await?.into_iter().next())
+    }
+
+    async fn search(&self, query: &str, rows: usize) -> Result<Vec<Entry>, ValidatorError> {
+        let url = format!(
+            "{}?q={}&fl={}&rows={}&wt=json",
+            HAL_API_BASE,
+            urlencoding::encode(query),
+            HAL_FIELDS,
+            rows
+        );
+        let response = self.client.get(&url).send().await?;
+
+        if response.status() == reqwest::StatusCode::TOO_MANY_REQUESTS {
+            return Err(ValidatorError::RateLimited);
+        }
+
+        if !response.status().is_success() {
+            return Ok(Vec::new());
+        }
+
+        let response: SearchResponse = response.json().await.map_err(|e| {
+            ValidatorError::ParseError(format!("Failed to parse HAL response: {}", e))
+        })?;
+
+        Ok(response.response.docs.iter().map(Doc::to_entry).collect())
+    }
+}
+
+impl Default for HalClient {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[derive(Debug, Deserialize)]
+struct SearchResponse {
+    response: Docs,
+}
+
+#[derive(Debug, Deserialize)]
+struct Docs {
+    #[serde(default)]
+    docs: Vec<Doc>,
+}
+
+#[derive(Debug, Deserialize)]
+struct Doc {
+    #[serde(rename = "halId_s")]
+    hal_id: String,
+    #[serde(rename = "title_s", default)]
+    title: Vec<String>,
+    #[serde(rename = "authFullName_s", default)]
+    authors: Vec<String>,
+    #[serde(rename = "producedDateY_i")]
+    year: Option<i32>,
+    #[serde(rename = "journalTitle_s")]
+    journal: Option<String>,
+    #[serde(rename = "conferenceTitle_s")]
+    conference: Option<String>,
+    #[serde(rename = "doiId_s")]
+    doi: Option<String>,
+    #[serde(rename = "docType_s")]
+    doc_type: Option<String>,
+    #[serde(rename = "page_s")]
+    pages: Option<String>,
+    #[serde(rename = "volume_s")]
+    volume: Option<String>,
+}
+
+impl Doc {
+    fn to_entry(&self) -> Entry {
+        let entry_type = match self.doc_type.as_deref() {
+            Some("ART") => "article",
+            Some("COMM") => "inproceedings",
+            Some("OUV") => "book",
+            Some("COUV") => "incollection",
+            Some("THESE") | Some("HDR") => "phdthesis",
+            Some("REPORT") => "techreport",
+            _ => "misc",
+        };
+        let mut entry = Entry::new(self.hal_id.clone(), entry_type.to_string());
+
+        entry.title = self.title.first().map(|t| Arc::from(t.as_str()));
+        entry.authors = self.authors.iter().map(|a| Arc::from(a.as_str())).collect();
+        entry.year = self.year;
+        entry.venue = self
+            .journal
+            .as_deref()
+            .or(self.conference.as_deref())
+            .map(Arc::from);
+        entry.doi = self.doi.clone();
+        entry.hal_id = Some(self.hal_id.clone());
+
+        for (name, value) in [
+            ("pages", self.pages.clone()),
+            ("volume", self.volume.clone()),
+        ] {
+            if let Some(value) = value.filter(|v| !v.is_empty()) {
+                entry.fields.insert(name.to_string(), value);
+            }
+        }
+
+        entry
+    }
+}
+
+#[async_trait]
+impl Validator for HalClient {
+    async fn search_by_doi(&self, doi: &str) -> Result<Option<Entry>, ValidatorError> {
+        let query = format!("doiId_s:\"{}\"", normalize_doi(doi));
+        Ok(self.search(&query, 1).await?.into_iter().next())
+    }
+
+    async fn search_by_title(&self, title: &str) -> Result<Vec<Entry>, ValidatorError> {
+        self.search(&format!("title_t:\"{}\"", title.replace('"', "")), 5)
+            .await
+    }
+
+    fn name(&self) -> &'static str {
+        "HAL"
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn deposits_are_read() {
+        let json = r#"{"response": {"numFound": 1, "start": 0, "docs": [{
+            "halId_s": "hal-01234567",
+            "title_s": ["Un article"],
+            "authFullName_s": ["Marie Curie", "Pierre Curie"],
+            "producedDateY_i": 2019,
+            "conferenceTitle_s": "Conférence Internationale",
+            "docType_s": "COMM"
+        }]}}"#;
+        let response: SearchResponse = serde_json::from_str(json).unwrap();
+        let entry = response.response.docs[0].to_entry();
+        assert_eq!(entry.entry_type, "inproceedings");
+        assert_eq!(entry.venue.as_deref(), Some("Conférence Internationale"));
+        assert_eq!(entry.hal_id.as_deref(), Some("hal-01234567"));
+    }
+}
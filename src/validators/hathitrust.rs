@@ -0,0 +1,320 @@
+use super::{async_trait, Validator, ValidatorError};
+use crate::entry::Entry;
+use futures::future::join_all;
+use quick_xml::events::Event;
+use quick_xml::Reader;
+use reqwest::Client;
+use serde::Deserialize;
+use std::collections::BTreeMap;
+use std::sync::Arc;
+
+const HATHITRUST_API_BASE: &str = "https://catalog.hathitrust.org/api/volumes/full";
+const OPENLIBRARY_SEARCH_URL: &str = "https://openlibrary.org/search.json";
+
+/// Field of a matched entry holding the HathiTrust catalog record URL
+pub const HATHITRUST_RECORD_FIELD: &str = "hathitrust";
+
+/// Catalog identifiers the Bibliographic API can look up
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BookId {
+    Isbn,
+    Oclc,
+    Lccn,
+}
+
+impl BookId {
+    fn as_str(&self) -> &'static str {
+        match self {
+            BookId::Isbn => "isbn",
+            BookId::Oclc => "oclc",
+            BookId::Lccn => "lccn",
+        }
+    }
+}
+
+/// Client for the HathiTrust Bibliographic API, which covers the digitized holdings of
+/// research libraries, including books that predate DOIs
+pub struct HathiTrustClient {
+    client: Client,
+}
+
+impl HathiTrustClient {
+    pub fn new() -> Self {
+        let client = Client::builder()
+            .user_agent("bibval/0.1.0 (https://github.com/femtomc/bibval)")
+            .build()
+            .expect("Failed to create HTTP client");
+        Self { client }
+    }
+
+    /// Look up the catalog record for an ISBN, OCLC number or LCCN
+    pub async fn lookup(&self, kind: BookId, id: &str) -> Result<Option<Entry>, ValidatorError> {
+        let id: String = id
+            .trim()
+            .trim_start_matches("(OCoLC)")
+            .chars()
+            .filter(|c| c.is_ascii_alphanumeric())
+            .collect();
+        if id.is_empty() {
+            return Ok(None);
+        }
+
+        let url = format!("{}/{}/{}.json", HATHITRUST_API_BASE, kind.as_str(), id);
+        let response = self.client.get(&url).send().await?;
+
+        if response.status() == reqwest::StatusCode::TOO_MANY_REQUESTS {
+            return Err(ValidatorError::RateLimited);
+        }
+
+        if !response.status().is_success() {
+            return Ok(None);
+        }
+
+        let response: VolumesResponse = response.json().await.map_err(|e| {
+            ValidatorError::ParseError(format!("Failed to parse HathiTrust response: {}", e))
+        })?;
+
+        // Several catalog records can share an identifier; they describe the same book
+        Ok(response.records.values().next().map(Record::to_entry))
+    }
+
+    /// Catalog identifiers of books with this title, from Open Library's index
+    async fn candidate_ids(&self, title: &str) -> Result<Vec<(BookId, String)>, ValidatorError> {
+        let url = format!(
+            "{}?title={}&limit=3&fields=isbn,oclc,lccn",
+            OPENLIBRARY_SEARCH_URL,
+            urlencoding::encode(title)
+        );
+        let response = self.client.get(&url).send().await?;
+
+        if !response.status().is_success() {
+            return Ok(Vec::new());
+        }
+
+        let response: CandidateResponse = response.json().await.map_err(|e| {
+            ValidatorError::ParseError(format!("Failed to parse Open Library response: {}", e))
+        })?;
+
+        Ok(response
+            .docs
+            .into_iter()
+            .filter_map(|doc| {
+                let first = |ids: Option<Vec<String>>| ids.and_then(|ids| ids.into_iter().next());
+                first(doc.oclc)
+                    .map(|id| (BookId::Oclc, id))
+                    .or_else(|| first(doc.lccn).map(|id| (BookId::Lccn, id)))
+                    .or_else(|| first(doc.isbn).map(|id| (BookId::Isbn, id)))
+            })
+            .collect())
+    }
+}
+
+impl Default for HathiTrustClient {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[derive(Debug, Deserialize)]
+struct VolumesResponse {
+    #[serde(default)]
+    records: BTreeMap<String, Record>,
+}
+
+#[derive(Debug, Deserialize)]
+struct Record {
+    #[serde(rename = "recordURL")]
+    record_url: Option<String>,
+    #[serde(default)]
+    titles: Vec<String>,
+    #[serde(default)]
+    isbns: Vec<String>,
+    #[serde(default)]
+    oclcs: Vec<String>,
+    #[serde(rename = "publishDates", default)]
+    publish_dates: Vec<String>,
+    #[serde(rename = "marc-xml")]
+    marc_xml: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+struct CandidateResponse {
+    #[serde(default)]
+    docs: Vec<CandidateDoc>,
+}
+
+#[derive(Debug, Deserialize)]
+struct CandidateDoc {
+    isbn: Option<Vec<String>>,
+    oclc: Option<Vec<String>>,
+    lccn: Option<Vec<String>>,
+}
+
+impl Record {
+    fn to_entry(&self) -> Entry {
+        let oclc = self.oclcs.first().cloned();
+        let mut entry = Entry::new(oclc.clone().unwrap_or_default(), "book".to_string());
+
+        let marc = self.marc_xml.as_deref().map(parse_marc).unwrap_or_default();
+
+        entry.title = self
+            .titles
+            .first()
+            .map(|t| Arc::from(clean_marc_value(t.split(" / ").next().unwrap_or(t))));
+        entry.authors = marc.authors.iter().map(|a| Arc::from(a.as_str())).collect();
+        entry.year = self.publish_dates.iter().find_map(|d| {
+            let digits: String = d.chars().filter(char::is_ascii_digit).take(4).collect();
+            (digits.len() == 4).then(|| digits.parse().ok()).flatten()
+        });
+        entry.venue = marc.publisher.as_deref().map(Arc::from);
+
+        if let Some(url) = &self.record_url {
+            entry
+                .fields
+                .insert(HATHITRUST_RECORD_FIELD.to_string(), url.clone());
+        }
+        if let Some(isbn) = self.isbns.first() {
+            entry.fields.insert("isbn".to_string(), isbn.clone());
+        }
+        if let Some(oclc) = oclc {
+            entry.fields.insert("oclc".to_string(), oclc);
+        }
+
+        entry
+    }
+}
+
+/// Authors and publisher from a MARC21 record
+#[derive(Debug, Default)]
+struct MarcFields {
+    authors: Vec<String>,
+    publisher: Option<String>,
+}
+
+/// Read main and added personal names (100, 700) and the publisher (260 or 264)
+fn parse_marc(xml: &str) -> MarcFields {
+    let mut reader = Reader::from_str(xml);
+    reader.config_mut().trim_text(true);
+
+    let mut fields = MarcFields::default();
+    let mut tag = String::new();
+    let mut code = String::new();
+    let mut buf = Vec::new();
+
+    loop {
+        match reader.read_event_into(&mut buf) {
+            Ok(Event::Start(ref e)) => {
+                let attribute = |name: &[u8]| {
+                    e.attributes()
+                        .flatten()
+                        .find(|a| a.key.local_name().as_ref() == name)
+                        .map(|a| String::from_utf8_lossy(&a.value).to_string())
+                        .unwrap_or_default()
+                };
+                match e.local_name().as_ref() {
+                    b"datafield" => tag = attribute(b"tag"),
+                    b"subfield" => code = attribute(b"code"),
+                    _ => {}
+                }
+            }
+            Ok(Event::End(ref e)) => match e.local_name().as_ref() {
+                b"datafield" => tag.clear(),
+                b"subfield" => code.clear(),
+                _ => {}
+            },
+            Ok(Event::Text(ref e)) => {
+                let text = e.unescape().unwrap_or_default();
+                match (tag.as_str(), code.as_str()) {
+                    ("100" | "700", "a") => fields.authors.push(clean_marc_value(&text)),
+                    ("260" | "264", "b") if fields.publisher.is_none() => {
+                        fields.publisher = Some(clean_marc_value(&text));
+                    }
+                    _ => {}
+                }
+            }
+            Ok(Event::Eof) | Err(_) => break,
+            _ => {}
+        }
+        buf.clear();
+    }
+
+    fields
+}
+
+/// Strip the ISBD punctuation that ends MARC subfields ("programming." or
+/// "Knuth, Donald E.,"), keeping the periods of initials and abbreviations
+pub(super) fn clean_marc_value(value: &str) -> String {
+    let value = value
+        .trim()
+        .trim_end_matches([',', ':', ';', '/', '='])
+        .trim_end();
+    match value.strip_suffix('.') {
+        Some(stripped)
+            if stripped
+                .rsplit(' ')
+                .next()
+                .is_some_and(|word| word.len() > 3) =>
+        {
+            stripped.to_string()
+        }
+        _ => value.to_string(),
+    }
+}
+
+#[async_trait]
+impl Validator for HathiTrustClient {
+    async fn search_by_doi(&self, _doi: &str) -> Result<Option<Entry>, ValidatorError> {
+        // HathiTrust doesn't index DOIs
+        Ok(None)
+    }
+
+    /// The Bibliographic API has no title search, so identifiers of candidate books
+    /// come from Open Library and are looked up in HathiTrust
+    async fn search_by_title(&self, title: &str) -> Result<Vec<Entry>, ValidatorError> {
+        let ids = self.candidate_ids(title).await?;
+        let lookups = ids.iter().map(|(kind, id)| self.lookup(*kind, id));
+        Ok(join_all(lookups)
+            .await
+            .into_iter()
+            .filter_map(|result| result.ok().flatten())
+            .collect())
+    }
+
+    fn name(&self) -> &'static str {
+        "HathiTrust"
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn records_read_marc_authors_and_publisher() {
+        let json = r#"{
+            "records": {
+                "001655538": {
+                    "recordURL": "https://catalog.hathitrust.org/Record/001655538",
+                    "titles": ["The art of computer programming / [by] Donald E. Knuth."],
+                    "isbns": ["0201038013"],
+                    "oclcs": ["823849"],
+                    "lccns": [],
+                    "publishDates": ["1968-"],
+                    "marc-xml": "<collection xmlns=\"http://www.loc.gov/MARC21/slim\"><record><datafield tag=\"100\" ind1=\"1\" ind2=\" \"><subfield code=\"a\">Knuth, Donald E.,</subfield><subfield code=\"d\">1938-</subfield></datafield><datafield tag=\"260\" ind1=\" \" ind2=\" \"><subfield code=\"a\">Reading, Mass.,</subfield><subfield code=\"b\">Addison-Wesley Pub. Co.</subfield><subfield code=\"c\">[1968-</subfield></datafield></record></collection>"
+                }
+            },
+            "items": []
+        }"#;
+
+        let response: VolumesResponse = serde_json::from_str(json).unwrap();
+        let entry = response.records.values().next().unwrap().to_entry();
+        assert_eq!(
+            entry.title.as_deref(),
+            Some("The art of computer programming")
+        );
+        assert_eq!(entry.authors, vec![Arc::from("Knuth, Donald E.")]);
+        assert_eq!(entry.venue.as_deref(), Some("Addison-Wesley Pub. Co."));
+        assert_eq!(entry.year, Some(1968));
+        assert_eq!(entry.field("oclc"), Some("823849"));
+    }
+}
@@ -0,0 +1,200 @@
+use super::{async_trait, Validator, ValidatorError};
+use crate::entry::Entry;
+use crate::lint::normalize_doi;
+use regex_lite::Regex;
+use reqwest::Client;
+use serde::Deserialize;
+use std::sync::{Arc, OnceLock};
+
+const DATATRACKER_BASE: &str = "https://datatracker.ietf.org/doc";
+
+/// DOI prefix of RFCs ("10.17487/RFC7231")
+const RFC_DOI_PREFIX: &str = "10.17487/rfc";
+
+/// The number of the RFC an entry cites, from its DOI, an RFC Editor URL, a `number`
+/// field ("RFC 7231", or "7231" in an RFC series), or its title
+pub fn rfc_number(entry: &Entry) -> Option<u32> {
+    static REGEX: OnceLock<Regex> = OnceLock::new();
+    let regex = REGEX.get_or_init(|| Regex::new(r"(?i)\brfc\s*-?\s*(\d{1,5})\b").unwrap());
+    let from_text = |text: &str| regex.captures(text)?[1].parse().ok();
+
+    if let Some(doi) = &entry.doi {
+        if let Some(number) = normalize_doi(doi).strip_prefix(RFC_DOI_PREFIX) {
+            return number.parse().ok();
+        }
+    }
+
+    let url = entry.url.as_deref().or(entry.field("howpublished"));
+    if let Some(number) = url
+        .filter(|u| u.contains("rfc-editor.org") || u.contains("ietf.org"))
+        .and_then(|u| from_text(u.rsplit('/').find(|s| !s.is_empty())?))
+    {
+        return Some(number);
+    }
+
+    if let Some(number) = entry.field("number") {
+        let series = [
+            entry.field("series"),
+            entry.field("type"),
+            entry.field("institution"),
+        ];
+        let rfc_series = series.iter().flatten().any(|s| {
+            let s = s.to_lowercase();
+            s.contains("request for comments") || s == "rfc" || s.contains("ietf")
+        });
+        match from_text(number) {
+            Some(found) => return Some(found),
+            None if rfc_series => return number.trim().parse().ok(),
+            None => {}
+        }
+    }
+
+    entry.title.as_deref().and_then(from_text)
+}
+
+/// Client for the IETF Datatracker, which holds the record of every RFC
+pub struct IetfClient {
+    client: Client,
+}
+
+impl IetfClient {
+    pub fn new() -> Self {
+        let client = Client::builder()
+            .user_agent("bibval/0.1.0 (https://github.com/femtomc/bibval)")
+            .build()
+            .expect("Failed to create HTTP client");
+        Self { client }
+    }
+
+    /// Look up an RFC by number
+    pub async fn search_by_rfc(&self, number: u32) -> Result<Option<Entry>, ValidatorError> {
+        let url = format!("{}/rfc{}/doc.json", DATATRACKER_BASE, number);
+        let response = self.client.get(&url).send().await?;
+
+        if response.status() == reqwest::StatusCode::TOO_MANY_REQUESTS {
+            return Err(ValidatorError::RateLimited);
+        }
+
+        if !response.status().is_success() {
+            return Ok(None);
+        }
+
+        let document: Document = response.json().await.map_err(|e| {
+            ValidatorError::ParseError(format!("Failed to parse Datatracker response: {}", e))
+        })?;
+
+        Ok(Some(document.to_entry(number)))
+    }
+}
+
+impl Default for IetfClient {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[derive(Debug, Deserialize)]
+struct Document {
+    title: Option<String>,
+    #[serde(default)]
+    authors: Vec<Author>,
+    /// When the RFC was published ("2014-06-07T00:00:00")
+    time: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+struct Author {
+    name: String,
+}
+
+impl Document {
+    fn to_entry(&self, number: u32) -> Entry {
+        let mut entry = Entry::new(format!("rfc{}", number), "techreport".to_string());
+
+        entry.title = self.title.as_deref().map(Arc::from);
+        entry.authors = self
+            .authors
+            .iter()
+            .map(|a| Arc::from(a.name.as_str()))
+            .collect();
+        entry.year = self.time.as_deref().and_then(|t| t.get(..4)?.parse().ok());
+        entry.doi = Some(format!("10.17487/RFC{:04}", number));
+        entry
+            .fields
+            .insert("number".to_string(), number.to_string());
+        entry
+            .fields
+            .insert("series".to_string(), "Request for Comments".to_string());
+
+        entry
+    }
+}
+
+#[async_trait]
+impl Validator for IetfClient {
+    async fn search_by_doi(&self, doi: &str) -> Result<Option<Entry>, ValidatorError> {
+        match normalize_doi(doi)
+            .strip_prefix(RFC_DOI_PREFIX)
+            .and_then(|n| n.parse().ok())
+        {
+            Some(number) => self.search_by_rfc(number).await,
+            None => Ok(None),
+        }
+    }
+
+    async fn search_by_title(&self, _title: &str) -> Result<Vec<Entry>, ValidatorError> {
+        // RFCs are looked up by number
+        Ok(Vec::new())
+    }
+
+    fn name(&self) -> &'static str {
+        "IETF Datatracker"
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn rfc_numbers_are_found() {
+        let entry = |fields: &[(&str, &str)]| {
+            let mut entry = Entry::new("rfc".to_string(), "techreport".to_string());
+            for (name, value) in fields {
+                match *name {
+                    "title" => entry.title = Some(Arc::from(*value)),
+                    "doi" => entry.doi = Some(value.to_string()),
+                    "url" => entry.url = Some(value.to_string()),
+                    _ => {
+                        entry.fields.insert(name.to_string(), value.to_string());
+                    }
+                }
+            }
+            rfc_number(&entry)
+        };
+
+        assert_eq!(entry(&[("doi", "10.17487/RFC7231")]), Some(7231));
+        assert_eq!(
+            entry(&[("url", "https://www.rfc-editor.org/rfc/rfc9110.html")]),
+            Some(9110)
+        );
+        assert_eq!(
+            entry(&[("number", "7231"), ("series", "Request for Comments")]),
+            Some(7231)
+        );
+        assert_eq!(entry(&[("number", "RFC 2616")]), Some(2616));
+        assert_eq!(
+            entry(&[("title", "Hypertext Transfer Protocol (HTTP/1.1): RFC 7230")]),
+            Some(7230)
+        );
+        assert_eq!(entry(&[("number", "42"), ("institution", "MIT")]), None);
+
+        let json = r#"{"name": "rfc7231", "title": "HTTP/1.1: Semantics and Content",
+            "authors": [{"name": "Roy T. Fielding", "email": "fielding@gbiv.com"}],
+            "time": "2014-06-07T00:00:00"}"#;
+        let document: Document = serde_json::from_str(json).unwrap();
+        let rfc = document.to_entry(7231);
+        assert_eq!(rfc.year, Some(2014));
+        assert_eq!(rfc.doi.as_deref(), Some("10.17487/RFC7231"));
+    }
+}
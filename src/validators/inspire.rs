@@ -0,0 +1,262 @@
+use super::{async_trait, Validator, ValidatorError};
+use crate::entry::Entry;
+use crate::lint::normalize_doi;
+use regex_lite::Regex;
+use reqwest::Client;
+use serde::Deserialize;
+use std::sync::{Arc, OnceLock};
+
+const INSPIRE_API_BASE: &str = "https://inspirehep.net/api/literature";
+const INSPIRE_FIELDS: &str =
+    "titles,authors.full_name,publication_info,dois,arxiv_eprints,texkeys,control_number";
+
+/// Field of a matched entry holding its INSPIRE texkeys, comma-separated
+pub const INSPIRE_TEXKEYS_FIELD: &str = "inspire_texkeys";
+
+/// arXiv archives whose papers INSPIRE indexes
+const HEP_ARCHIVES: &[&str] = &["hep-", "gr-qc", "nucl-", "astro-ph", "physics.acc-ph"];
+
+/// Whether a citation key has the shape of an INSPIRE texkey ("Maldacena:1997re")
+pub fn is_texkey(key: &str) -> bool {
+    static REGEX: OnceLock<Regex> = OnceLock::new();
+    REGEX
+        .get_or_init(|| Regex::new(r"^[A-Za-z][^:\s]*:(1[89]|20)\d{2}[a-z]{2,3}$").unwrap())
+        .is_match(key)
+}
+
+/// Whether an entry cites high-energy physics: an INSPIRE texkey, a HEP arXiv
+/// archive, or a physics venue
+pub fn is_hep(entry: &Entry) -> bool {
+    let archive = entry
+        .field("primaryclass")
+        .or(entry.arxiv_id.as_deref().filter(|id| id.contains('/')));
+    let venue = entry.venue.as_deref().unwrap_or("");
+    is_texkey(&entry.key)
+        || archive.is_some_and(|a| HEP_ARCHIVES.iter().any(|hep| a.starts_with(hep)))
+        || ["Phys.", "Physical Review", "JHEP", "JCAP", "Nucl."]
+            .iter()
+            .any(|v| venue.contains(v))
+}
+
+/// Client for INSPIRE-HEP, the high-energy physics literature database
+pub struct InspireClient {
+    client: Client,
+}
+
+impl InspireClient {
+    pub fn new() -> Self {
+        let client = Client::builder()
+            .user_agent("bibval/0.1.0 (https://github.com/femtomc/bibval)")
+            .build()
+            .expect("Failed to create HTTP client");
+        Self { client }
+    }
+
+    /// Search by arXiv ID
+    pub async fn search_by_arxiv_id(
+        &self,
+        arxiv_id: &str,
+    ) -> Result<Option<Entry>, ValidatorError> {
+        let id = arxiv_id.trim();
+        // Versions are not part of the eprint INSPIRE records
+        let id = match id.rsplit_once('v') {
+            Some((base, version)) if version.chars().all(|c| c.is_ascii_digit()) => base,
+            _ => id,
+        };
+        self.first(&format!("arxiv:{}", id)).await
+    }
+
+    /// Search by INSPIRE texkey
+    pub async fn search_by_texkey(&self, texkey: &str) -> Result<Option<Entry>, ValidatorError> {
+        self.first(&format!("texkey:\"{}\"", texkey.trim())).await
+    }
+
+    async fn first(&self, query: &str) -> Result<Option<Entry>, ValidatorError> {
+        Ok(self.search(query, 1).await?.into_iter().next())
+    }
+
+    async fn search(&self, query: &str, size: usize) -> Result<Vec<Entry>, ValidatorError> {
+        let url = format!(
+            "{}?q={}&size={}&fields={}",
+            INSPIRE_API_BASE,
+            urlencoding::encode(query),
+            size,
+            INSPIRE_FIELDS
+        );
+
+        let response = self.client.get(&url).send().await?;
+
+        if response.status() == reqwest::StatusCode::TOO_MANY_REQUESTS {
+            return Err(ValidatorError::RateLimited);
+        }
+
+        if !response.status().is_success() {
+            return Ok(Vec::new());
+        }
+
+        let response: SearchResponse = response.json().await.map_err(|e| {
+            ValidatorError::ParseError(format!("Failed to parse INSPIRE response: {}", e))
+        })?;
+
+        Ok(response
+            .hits
+            .hits
+            .iter()
+            .map(|hit| hit.metadata.to_entry())
+            .collect())
+    }
+}
+
+impl Default for InspireClient {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[derive(Debug, Deserialize)]
+struct SearchResponse {
+    hits: Hits,
+}
+
+#[derive(Debug, Deserialize)]
+struct Hits {
+    #[serde(default)]
+    hits: Vec<Hit>,
+}
+
+#[derive(Debug, Deserialize)]
+struct Hit {
+    metadata: Record,
+}
+
+#[derive(Debug, Deserialize)]
+struct Record {
+    control_number: Option<u64>,
+    #[serde(default)]
+    titles: Vec<Title>,
+    #[serde(default)]
+    authors: Vec<Author>,
+    #[serde(default)]
+    publication_info: Vec<PublicationInfo>,
+    #[serde(default)]
+    dois: Vec<Value>,
+    #[serde(default)]
+    arxiv_eprints: Vec<Value>,
+    #[serde(default)]
+    texkeys: Vec<String>,
+}
+
+#[derive(Debug, Deserialize)]
+struct Title {
+    title: String,
+}
+
+#[derive(Debug, Deserialize)]
+struct Author {
+    full_name: String,
+}
+
+#[derive(Debug, Deserialize)]
+struct PublicationInfo {
+    journal_title: Option<String>,
+    journal_volume: Option<String>,
+    year: Option<i32>,
+    page_start: Option<String>,
+    page_end: Option<String>,
+    artid: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+struct Value {
+    value: String,
+}
+
+impl Record {
+    fn to_entry(&self) -> Entry {
+        let key = self
+            .texkeys
+            .first()
+            .cloned()
+            .or_else(|| self.control_number.map(|n| n.to_string()))
+            .unwrap_or_default();
+        let journal = self
+            .publication_info
+            .iter()
+            .find(|p| p.journal_title.is_some());
+        let entry_type = if journal.is_some() { "article" } else { "misc" };
+        let mut entry = Entry::new(key, entry_type.to_string());
+
+        entry.title = self.titles.first().map(|t| Arc::from(t.title.as_str()));
+        // Names are written "Family, Given"
+        entry.authors = self
+            .authors
+            .iter()
+            .map(|a| match a.full_name.split_once(", ") {
+                Some((family, given)) => Arc::from(format!("{} {}", given, family)),
+                None => Arc::from(a.full_name.as_str()),
+            })
+            .collect();
+        entry.doi = self.dois.first().map(|d| d.value.clone());
+        entry.arxiv_id = self.arxiv_eprints.first().map(|e| e.value.clone());
+
+        if let Some(info) = journal {
+            entry.year = info.year;
+            entry.venue = info.journal_title.as_deref().map(Arc::from);
+            let pages = match (&info.page_start, &info.page_end) {
+                (Some(start), Some(end)) => Some(format!("{}--{}", start, end)),
+                (Some(start), None) => Some(start.clone()),
+                _ => info.artid.clone(),
+            };
+            for (name, value) in [("pages", pages), ("volume", info.journal_volume.clone())] {
+                if let Some(value) = value {
+                    entry.fields.insert(name.to_string(), value);
+                }
+            }
+        } else {
+            entry.year = self.publication_info.iter().find_map(|p| p.year);
+        }
+
+        if !self.texkeys.is_empty() {
+            entry
+                .fields
+                .insert(INSPIRE_TEXKEYS_FIELD.to_string(), self.texkeys.join(","));
+        }
+
+        entry
+    }
+}
+
+#[async_trait]
+impl Validator for InspireClient {
+    async fn search_by_doi(&self, doi: &str) -> Result<Option<Entry>, ValidatorError> {
+        self.first(&format!("doi:{}", normalize_doi(doi))).await
+    }
+
+    async fn search_by_title(&self, title: &str) -> Result<Vec<Entry>, ValidatorError> {
+        self.search(&format!("title \"{}\"", title.replace('"', "")), 5)
+            .await
+    }
+
+    fn name(&self) -> &'static str {
+        "INSPIRE"
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn texkeys_are_recognized() {
+        assert!(is_texkey("Maldacena:1997re"));
+        assert!(is_texkey("ATLAS:2012yve"));
+        assert!(!is_texkey("maldacena1997large"));
+        assert!(!is_texkey("Maldacena:1997"));
+
+        let mut entry = Entry::new("adscft".to_string(), "article".to_string());
+        entry.arxiv_id = Some("hep-th/9711200".to_string());
+        assert!(is_hep(&entry));
+        entry.arxiv_id = Some("2301.12345".to_string());
+        assert!(!is_hep(&entry));
+    }
+}
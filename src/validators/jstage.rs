@@ -0,0 +1,339 @@
+use super::{async_trait, Validator, ValidatorError};
+use crate::entry::{normalize_string, Entry};
+use crate::lint::normalize_doi;
+use quick_xml::events::Event;
+use quick_xml::Reader;
+use regex_lite::Regex;
+use reqwest::Client;
+use std::sync::{Arc, OnceLock};
+use strsim::jaro_winkler;
+
+const JSTAGE_API_BASE: &str = "https://api.jstage.jst.go.jp/searchapi/do";
+
+/// Whether a string contains Hiragana, Katakana or CJK ideographs
+pub fn has_japanese_script(s: &str) -> bool {
+    s.chars().any(|c| {
+        matches!(c,
+            '\u{3040}'..='\u{30FF}' | '\u{4E00}'..='\u{9FFF}' | '\u{FF66}'..='\u{FF9D}')
+    })
+}
+
+/// Whether an entry likely cites a Japanese-published work: a Japanese `langid`/`language`,
+/// or a title or venue written in Japanese or naming Japan
+pub fn is_japanese(entry: &Entry) -> bool {
+    let language = entry
+        .field("langid")
+        .or(entry.field("language"))
+        .unwrap_or_default()
+        .to_lowercase();
+    let title = entry.title.as_deref().unwrap_or_default();
+    let venue = entry.venue.as_deref().unwrap_or_default();
+    language.starts_with("ja")
+        || has_japanese_script(title)
+        || has_japanese_script(venue)
+        || venue.to_lowercase().contains("japan")
+}
+
+/// The journal code, volume and issue of a J-STAGE article page
+/// ("https://www.jstage.jst.go.jp/article/jsai/25/1/25_1_1/_article/-char/ja/")
+fn article_issue(url: &str) -> Option<(String, String, String)> {
+    static REGEX: OnceLock<Regex> = OnceLock::new();
+    let regex = REGEX.get_or_init(|| {
+        Regex::new(r"jstage\.jst\.go\.jp/article/([^/]+)/([^/]+)/([^/]+)/").unwrap()
+    });
+    let captures = regex.captures(url)?;
+    Some((
+        captures[1].to_string(),
+        captures[2].to_string(),
+        captures[3].to_string(),
+    ))
+}
+
+/// Client for the J-STAGE WebAPI, which indexes the journals published on J-STAGE by
+/// Japanese academic societies
+pub struct JstageClient {
+    client: Client,
+}
+
+impl JstageClient {
+    pub fn new() -> Self {
+        let client = Client::builder()
+            .user_agent("bibval/0.1.0 (https://github.com/femtomc/bibval)")
+            .build()
+            .expect("Failed to create HTTP client");
+        Self { client }
+    }
+
+    async fn search(&self, query: &[(&str, &str)]) -> Result<Vec<Article>, ValidatorError> {
+        let response = self
+            .client
+            .get(JSTAGE_API_BASE)
+            .query(&[("service", "3")])
+            .query(query)
+            .send()
+            .await?;
+
+        if response.status() == reqwest::StatusCode::TOO_MANY_REQUESTS {
+            return Err(ValidatorError::RateLimited);
+        }
+
+        if !response.status().is_success() {
+            return Ok(Vec::new());
+        }
+
+        let text = response.text().await?;
+        parse_jstage_atom(&text)
+    }
+}
+
+impl Default for JstageClient {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// An article as J-STAGE describes it, with each text field in English and Japanese
+#[derive(Debug, Default)]
+struct Article {
+    /// Titles keyed by language ("en", "ja")
+    titles: Vec<(String, String)>,
+    /// Author names keyed by language
+    authors: Vec<(String, String)>,
+    /// Journal titles keyed by language
+    journals: Vec<(String, String)>,
+    year: Option<i32>,
+    volume: Option<String>,
+    number: Option<String>,
+    start_page: Option<String>,
+    end_page: Option<String>,
+    doi: Option<String>,
+}
+
+impl Article {
+    /// Convert to an entry in the language of the title closest to `query` when given
+    /// and otherwise in Japanese, which every J-STAGE article has
+    fn to_entry(&self, query: Option<&str>) -> Entry {
+        let title = match query.map(normalize_string) {
+            Some(query) => self.titles.iter().max_by(|a, b| {
+                let similarity = |t: &str| jaro_winkler(&normalize_string(t), &query);
+                similarity(&a.1).total_cmp(&similarity(&b.1))
+            }),
+            None => self
+                .titles
+                .iter()
+                .find(|(language, _)| language == "ja")
+                .or(self.titles.first()),
+        };
+        let language = title.map_or("ja", |(language, _)| language.as_str());
+        // Fall back to the other language for text J-STAGE only has in one
+        let in_language = |values: &[(String, String)]| -> Vec<String> {
+            let matching: Vec<String> = values
+                .iter()
+                .filter(|(l, _)| l == language)
+                .map(|(_, v)| v.clone())
+                .collect();
+            match (matching.is_empty(), values.first()) {
+                (true, Some((other, _))) => values
+                    .iter()
+                    .filter(|(l, _)| l == other)
+                    .map(|(_, v)| v.clone())
+                    .collect(),
+                _ => matching,
+            }
+        };
+
+        let mut entry = Entry::new(self.doi.clone().unwrap_or_default(), "article".to_string());
+        entry.title = title.map(|(_, t)| Arc::from(t.as_str()));
+        entry.authors = in_language(&self.authors)
+            .iter()
+            .map(|a| Arc::from(a.as_str()))
+            .collect();
+        entry.venue = in_language(&self.journals)
+            .first()
+            .map(|j| Arc::from(j.as_str()));
+        entry.year = self.year;
+        entry.doi = self.doi.as_deref().map(normalize_doi);
+
+        let pages = match (&self.start_page, &self.end_page) {
+            (Some(start), Some(end)) if start != end => Some(format!("{}--{}", start, end)),
+            (Some(start), _) => Some(start.clone()),
+            _ => None,
+        };
+        for (name, value) in [
+            ("volume", self.volume.clone()),
+            ("number", self.number.clone()),
+            ("pages", pages),
+        ] {
+            if let Some(value) = value.filter(|v| !v.is_empty()) {
+                entry.fields.insert(name.to_string(), value);
+            }
+        }
+
+        entry
+    }
+}
+
+#[async_trait]
+impl Validator for JstageClient {
+    async fn search_by_doi(&self, doi: &str) -> Result<Option<Entry>, ValidatorError> {
+        // The WebAPI has no DOI search, so the DOI is resolved to its article page and the
+        // issue that page belongs to is searched for it
+        let doi = normalize_doi(doi);
+        let url = format!("https://doi.org/{}", doi);
+        let response = self.client.get(&url).send().await?;
+        let Some((journal, volume, number)) = article_issue(response.url().as_str()) else {
+            return Ok(None);
+        };
+
+        let query = [
+            ("cdjournal", journal.as_str()),
+            ("vol", volume.as_str()),
+            ("no", number.as_str()),
+            ("count", "1000"),
+        ];
+        let articles = self.search(&query).await?;
+        Ok(articles
+            .iter()
+            .find(|a| a.doi.as_deref().is_some_and(|d| normalize_doi(d) == doi))
+            .map(|a| a.to_entry(None)))
+    }
+
+    async fn search_by_title(&self, title: &str) -> Result<Vec<Entry>, ValidatorError> {
+        let articles = self.search(&[("article", title), ("count", "5")]).await?;
+        Ok(articles.iter().map(|a| a.to_entry(Some(title))).collect())
+    }
+
+    fn name(&self) -> &'static str {
+        "J-STAGE"
+    }
+}
+
+/// Parse the Atom feed of a J-STAGE search, whose text fields are split into `<en>` and
+/// `<ja>` children
+fn parse_jstage_atom(xml: &str) -> Result<Vec<Article>, ValidatorError> {
+    let mut reader = Reader::from_str(xml);
+    reader.config_mut().trim_text(true);
+
+    let mut articles = Vec::new();
+    let mut current: Option<Article> = None;
+    let mut path: Vec<String> = Vec::new();
+    let mut buf = Vec::new();
+
+    loop {
+        let text = match reader.read_event_into(&mut buf) {
+            Ok(Event::Start(ref e)) => {
+                let name = String::from_utf8_lossy(e.name().as_ref()).to_string();
+                if name == "entry" {
+                    current = Some(Article::default());
+                }
+                path.push(name);
+                None
+            }
+            Ok(Event::End(_)) => {
+                if path.pop().as_deref() == Some("entry") {
+                    if let Some(article) = current.take() {
+                        if !article.titles.is_empty() {
+                            articles.push(article);
+                        }
+                    }
+                }
+                None
+            }
+            Ok(Event::Text(ref e)) => Some(e.unescape().unwrap_or_default().into_owned()),
+            // Titles are usually wrapped in CDATA sections
+            Ok(Event::CData(ref e)) => Some(String::from_utf8_lossy(e).into_owned()),
+            Ok(Event::Eof) => break,
+            Err(e) => {
+                return Err(ValidatorError::ParseError(format!(
+                    "Error parsing J-STAGE XML: {}",
+                    e
+                )));
+            }
+            _ => None,
+        };
+        buf.clear();
+
+        let (Some(text), Some(article)) = (text, current.as_mut()) else {
+            continue;
+        };
+        let text = text.trim().to_string();
+        if text.is_empty() {
+            continue;
+        }
+        // Path below <entry>: ["article_title", "ja"], ["author", "en", "name"]
+        let start = path.iter().position(|t| t == "entry").map_or(0, |i| i + 1);
+        let tags: Vec<&str> = path[start..].iter().map(String::as_str).collect();
+        match tags.as_slice() {
+            ["article_title", language] => article.titles.push((language.to_string(), text)),
+            ["author", language, "name"] => article.authors.push((language.to_string(), text)),
+            ["material_title", language] => article.journals.push((language.to_string(), text)),
+            ["pubyear"] => article.year = text.get(..4).and_then(|y| y.parse().ok()),
+            ["prism:volume"] | ["volume"] => article.volume = Some(text),
+            ["prism:number"] | ["number"] => article.number = Some(text),
+            ["prism:startingPage"] | ["startingpage"] => article.start_page = Some(text),
+            ["prism:endingPage"] | ["endingpage"] => article.end_page = Some(text),
+            ["prism:doi"] | ["doi"] => article.doi = Some(text),
+            _ => {}
+        }
+    }
+
+    Ok(articles)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn articles_are_read_in_either_language() {
+        let xml = r#"<?xml version="1.0" encoding="UTF-8"?>
+<feed xmlns="http://www.w3.org/2005/Atom"
+      xmlns:prism="http://prismstandard.org/namespaces/basic/2.0/">
+  <entry>
+    <article_title>
+      <en><![CDATA[Deep Learning and Its Applications]]></en>
+      <ja><![CDATA[深層学習とその応用]]></ja>
+    </article_title>
+    <author>
+      <en><name>Taro Yamada</name></en>
+      <ja><name>山田 太郎</name></ja>
+    </author>
+    <material_title>
+      <en>Journal of the Japanese Society for Artificial Intelligence</en>
+      <ja>人工知能</ja>
+    </material_title>
+    <prism:volume>28</prism:volume>
+    <prism:number>4</prism:number>
+    <prism:startingPage>512</prism:startingPage>
+    <prism:endingPage>520</prism:endingPage>
+    <pubyear>2013</pubyear>
+    <prism:doi>10.11517/jjsai.28.4_512</prism:doi>
+  </entry>
+</feed>"#;
+        let articles = parse_jstage_atom(xml).unwrap();
+        assert_eq!(articles.len(), 1);
+
+        let original = articles[0].to_entry(None);
+        assert_eq!(original.title.as_deref(), Some("深層学習とその応用"));
+        assert_eq!(original.authors[0].as_ref(), "山田 太郎");
+        assert_eq!(original.venue.as_deref(), Some("人工知能"));
+        assert_eq!(original.field("pages"), Some("512--520"));
+        assert_eq!(original.doi.as_deref(), Some("10.11517/jjsai.28.4_512"));
+        assert!(is_japanese(&original));
+
+        let translated = articles[0].to_entry(Some("Deep learning and its applications"));
+        assert_eq!(translated.authors[0].as_ref(), "Taro Yamada");
+
+        // Full-width forms and Japanese punctuation normalize like their ASCII counterparts
+        assert_eq!(
+            normalize_string("ＡＩ研究の動向、ＧＰＴ－４"),
+            normalize_string("AI研究の動向 GPT-4")
+        );
+        assert_eq!(
+            article_issue(
+                "https://www.jstage.jst.go.jp/article/jjsai/28/4/28_512/_article/-char/ja/"
+            ),
+            Some(("jjsai".to_string(), "28".to_string(), "4".to_string()))
+        );
+    }
+}
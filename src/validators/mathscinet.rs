@@ -0,0 +1,129 @@
+use super::{async_trait, Validator, ValidatorError};
+use crate::entry::Entry;
+use crate::parser::parse_bib_string;
+use regex_lite::Regex;
+use reqwest::Client;
+use std::sync::OnceLock;
+
+const MREF_BASE: &str = "https://mathscinet.ams.org/mathscinet-mref";
+
+/// Client for MRef, the public reference matcher of MathSciNet. It takes a free-form
+/// citation and returns the best-matching record, MR number included, as BibTeX.
+pub struct MathSciNetClient {
+    client: Client,
+}
+
+impl MathSciNetClient {
+    pub fn new() -> Self {
+        let client = Client::builder()
+            .user_agent("bibval/0.1.0 (https://github.com/femtomc/bibval)")
+            .build()
+            .expect("Failed to create HTTP client");
+        Self { client }
+    }
+
+    /// Match an entry's citation against MathSciNet, to confirm its MR number
+    pub async fn search_by_reference(
+        &self,
+        entry: &Entry,
+    ) -> Result<Option<Entry>, ValidatorError> {
+        let authors: Vec<&str> = entry.authors.iter().map(|a| &**a).collect();
+        let parts = [
+            Some(authors.join(", ")),
+            entry.title.as_deref().map(str::to_string),
+            entry.venue.as_deref().map(str::to_string),
+            entry.year.map(|y| y.to_string()),
+        ];
+        let reference: Vec<String> = parts
+            .into_iter()
+            .flatten()
+            .filter(|p| !p.is_empty())
+            .collect();
+        if reference.is_empty() {
+            return Ok(None);
+        }
+        self.mref(&reference.join(", ")).await
+    }
+
+    async fn mref(&self, reference: &str) -> Result<Option<Entry>, ValidatorError> {
+        let url = format!(
+            "{}?ref={}&dataType=bibtex",
+            MREF_BASE,
+            urlencoding::encode(reference)
+        );
+        let response = self.client.get(&url).send().await?;
+
+        if response.status() == reqwest::StatusCode::TOO_MANY_REQUESTS {
+            return Err(ValidatorError::RateLimited);
+        }
+
+        if !response.status().is_success() {
+            return Ok(None);
+        }
+
+        let html = response.text().await?;
+        Ok(match_bibtex(&html)
+            .and_then(|bibtex| parse_bib_string(&bibtex).ok()?.into_iter().next()))
+    }
+}
+
+impl Default for MathSciNetClient {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// The BibTeX of the matched record on an MRef page, if any
+fn match_bibtex(html: &str) -> Option<String> {
+    static REGEX: OnceLock<Regex> = OnceLock::new();
+    let regex = REGEX.get_or_init(|| Regex::new(r"(?s)<pre>\s*(@.*?)</pre>").unwrap());
+    let bibtex = regex.captures(html)?[1]
+        .replace("&lt;", "<")
+        .replace("&gt;", ">")
+        .replace("&quot;", "\"")
+        .replace("&amp;", "&");
+    // MathSciNet writes "@article {MR0031344," which the BibTeX parser rejects
+    let (entry_type, rest) = bibtex.split_once('{')?;
+    Some(format!("{}{{{}", entry_type.trim_end(), rest))
+}
+
+#[async_trait]
+impl Validator for MathSciNetClient {
+    async fn search_by_doi(&self, _doi: &str) -> Result<Option<Entry>, ValidatorError> {
+        // MRef matches citations, not identifiers
+        Ok(None)
+    }
+
+    async fn search_by_title(&self, title: &str) -> Result<Vec<Entry>, ValidatorError> {
+        Ok(self.mref(title).await?.into_iter().collect())
+    }
+
+    fn name(&self) -> &'static str {
+        "MathSciNet"
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn mref_matches_carry_their_mr_number() {
+        let html = r#"<table><tr><td align="left"><pre>@article {MR0031344,
+    AUTHOR = {Nash, Jr., John F.},
+     TITLE = {Equilibrium points in {$n$}-person games},
+   JOURNAL = {Proc. Nat. Acad. Sci. U.S.A.},
+    VOLUME = {36},
+      YEAR = {1950},
+     PAGES = {48--49},
+  MRNUMBER = {31344},
+}
+</pre></td></tr></table>"#;
+        let entry = parse_bib_string(&match_bibtex(html).unwrap())
+            .unwrap()
+            .remove(0);
+        assert_eq!(entry.mr_number.as_deref(), Some("MR0031344"));
+        assert_eq!(entry.year, Some(1950));
+        assert!(match_bibtex("<p>No Unique Match Found</p>").is_none());
+    }
+}
@@ -1,17 +1,50 @@
 pub use async_trait::async_trait;
 
+pub mod acm;
+pub mod ads;
 pub mod arxiv;
+pub mod core;
 pub mod crossref;
+pub mod datacite;
 pub mod dblp;
+pub mod dblp_dump;
+pub mod doaj;
+pub mod doiorg;
+pub mod epo;
+pub mod figshare;
+pub mod github;
+pub mod hal;
+pub mod hathitrust;
+pub mod ietf;
+pub mod inspire;
+pub mod jstage;
+pub mod mathscinet;
 pub mod openalex;
+pub mod opencitations;
 pub mod openlibrary;
 pub mod openreview;
+pub mod osf;
+pub mod paperswithcode;
+pub mod pubmed;
+pub mod scielo;
+pub mod scopus;
 pub mod semantic;
+pub mod springer;
+pub mod sru;
+pub mod swh;
+pub mod unpaywall;
+pub mod w3c;
+pub mod web;
+pub mod worldcat;
+pub mod wos;
 pub mod zenodo;
 
 use crate::entry::Entry;
 use thiserror::Error;
 
+/// Field of a matched entry holding how often the database has seen the work cited
+pub const CITATION_COUNT_FIELD: &str = "citation_count";
+
 #[derive(Error, Debug)]
 pub enum ValidatorError {
     #[error("HTTP request failed: {0}")]
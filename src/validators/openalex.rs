@@ -1,10 +1,17 @@
-use super::{async_trait, Validator, ValidatorError};
+use super::unpaywall::{OA_STATUS_FIELD, OA_URL_FIELD};
+use super::{async_trait, Validator, ValidatorError, CITATION_COUNT_FIELD};
 use crate::entry::Entry;
+use crate::lint::normalize_doi;
 use reqwest::Client;
 use serde::Deserialize;
+use std::collections::HashMap;
+use std::sync::Arc;
 
 const OPENALEX_API_BASE: &str = "https://api.openalex.org";
 
+/// Maximum number of values OpenAlex accepts in one OR filter
+pub const OPENALEX_BATCH_SIZE: usize = 50;
+
 pub struct OpenAlexClient {
     client: Client,
 }
@@ -17,6 +24,49 @@ impl OpenAlexClient {
             .expect("Failed to create HTTP client");
         Self { client }
     }
+
+    /// Look up several DOIs with a single `filter=doi:a|b|c` query.
+    ///
+    /// Returns works keyed by normalized DOI; DOIs missing from the map are unknown to OpenAlex.
+    pub async fn search_by_dois(
+        &self,
+        dois: &[String],
+    ) -> Result<HashMap<String, Entry>, ValidatorError> {
+        if dois.is_empty() {
+            return Ok(HashMap::new());
+        }
+
+        let url = format!(
+            "{}/works?filter=doi:{}&per_page={}",
+            OPENALEX_API_BASE,
+            urlencoding::encode(&dois.join("|")),
+            dois.len()
+        );
+
+        let response = self.client.get(&url).send().await?;
+
+        if response.status() == reqwest::StatusCode::TOO_MANY_REQUESTS {
+            return Err(ValidatorError::RateLimited);
+        }
+
+        if !response.status().is_success() {
+            return Err(ValidatorError::ParseError(format!(
+                "OpenAlex batch query returned HTTP {}",
+                response.status()
+            )));
+        }
+
+        let response: SearchResponse = response.json().await.map_err(|e| {
+            ValidatorError::ParseError(format!("Failed to parse OpenAlex response: {}", e))
+        })?;
+
+        Ok(response
+            .results
+            .iter()
+            .map(|w| w.to_entry())
+            .filter_map(|entry| Some((normalize_doi(entry.doi.as_deref()?), entry)))
+            .collect())
+    }
 }
 
 impl Default for OpenAlexClient {
@@ -41,6 +91,31 @@ struct Work {
     #[serde(rename = "primary_location")]
     primary_location: Option<Location>,
     doi: Option<String>,
+    cited_by_count: Option<u64>,
+    biblio: Option<Biblio>,
+    /// Each word of the abstract with the positions it appears at
+    abstract_inverted_index: Option<HashMap<String, Vec<usize>>>,
+    keywords: Option<Vec<Keyword>>,
+    open_access: Option<OpenAccess>,
+}
+
+#[derive(Debug, Deserialize)]
+struct OpenAccess {
+    oa_status: Option<String>,
+    oa_url: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+struct Keyword {
+    display_name: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+struct Biblio {
+    volume: Option<String>,
+    issue: Option<String>,
+    first_page: Option<String>,
+    last_page: Option<String>,
 }
 
 #[derive(Debug, Deserialize)]
@@ -61,6 +136,7 @@ struct Location {
 #[derive(Debug, Deserialize)]
 struct Source {
     display_name: Option<String>,
+    host_organization_name: Option<String>,
 }
 
 impl Work {
@@ -70,13 +146,18 @@ impl Work {
             "article".to_string(),
         );
 
-        entry.title = self.title.clone();
+        entry.title = self.title.as_deref().map(Arc::from);
         entry.year = self.publication_year;
 
         // Extract venue from primary location
         if let Some(loc) = &self.primary_location {
             if let Some(source) = &loc.source {
-                entry.venue = source.display_name.clone();
+                entry.venue = source.display_name.as_deref().map(Arc::from);
+                if let Some(publisher) = &source.host_organization_name {
+                    entry
+                        .fields
+                        .insert("publisher".to_string(), publisher.clone());
+                }
             }
         }
 
@@ -84,7 +165,7 @@ impl Work {
         if let Some(authorships) = &self.authorships {
             entry.authors = authorships
                 .iter()
-                .filter_map(|a| a.author.as_ref()?.display_name.clone())
+                .filter_map(|a| a.author.as_ref()?.display_name.as_deref().map(Arc::from))
                 .collect();
         }
 
@@ -93,8 +174,72 @@ impl Work {
             entry.doi = Some(doi.replace("https://doi.org/", ""));
         }
 
+        if let Some(biblio) = &self.biblio {
+            let pages = match (&biblio.first_page, &biblio.last_page) {
+                (Some(first), Some(last)) if first != last => Some(format!("{}--{}", first, last)),
+                (Some(first), _) => Some(first.clone()),
+                _ => None,
+            };
+            for (name, value) in [
+                ("volume", biblio.volume.clone()),
+                ("number", biblio.issue.clone()),
+                ("pages", pages),
+            ] {
+                if let Some(value) = value {
+                    entry.fields.insert(name.to_string(), value);
+                }
+            }
+        }
+
+        if let Some(text) = self.abstract_text() {
+            entry.fields.insert("abstract".to_string(), text);
+        }
+        let keywords: Vec<&str> = self
+            .keywords
+            .iter()
+            .flatten()
+            .filter_map(|k| k.display_name.as_deref())
+            .collect();
+        if !keywords.is_empty() {
+            entry
+                .fields
+                .insert("keywords".to_string(), keywords.join(", "));
+        }
+
+        if let Some(open_access) = &self.open_access {
+            for (name, value) in [
+                (OA_URL_FIELD, &open_access.oa_url),
+                (OA_STATUS_FIELD, &open_access.oa_status),
+            ] {
+                if let Some(value) = value.as_deref().filter(|v| !v.is_empty()) {
+                    entry.fields.insert(name.to_string(), value.to_string());
+                }
+            }
+        }
+
+        if let Some(count) = self.cited_by_count {
+            entry
+                .fields
+                .insert(CITATION_COUNT_FIELD.to_string(), count.to_string());
+        }
+
         entry
     }
+
+    /// The abstract, put back together from the inverted index OpenAlex serves instead
+    fn abstract_text(&self) -> Option<String> {
+        let mut words: Vec<(usize, &str)> = self
+            .abstract_inverted_index
+            .iter()
+            .flatten()
+            .flat_map(|(word, positions)| positions.iter().map(move |&p| (p, word.as_str())))
+            .collect();
+        if words.is_empty() {
+            return None;
+        }
+        words.sort_unstable();
+        Some(words.into_iter().map(|(_, word)| word).collect::<Vec<_>>().join(" "))
+    }
 }
 
 #[async_trait]
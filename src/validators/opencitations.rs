@@ -0,0 +1,176 @@
+use super::{async_trait, Validator, ValidatorError};
+use crate::entry::Entry;
+use crate::lint::normalize_doi;
+use reqwest::Client;
+use serde::Deserialize;
+use std::sync::Arc;
+
+const OPENCITATIONS_META_BASE: &str = "https://api.opencitations.net/meta/v1/metadata";
+
+/// Client for OpenCitations Meta, an open database of DOI metadata curated independently
+/// of the publishers and search engines, so a neutral party when those disagree
+pub struct OpenCitationsClient {
+    client: Client,
+}
+
+impl OpenCitationsClient {
+    pub fn new() -> Self {
+        let client = Client::builder()
+            .user_agent("bibval/0.1.0 (https://github.com/femtomc/bibval)")
+            .build()
+            .expect("Failed to create HTTP client");
+        Self { client }
+    }
+}
+
+impl Default for OpenCitationsClient {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[derive(Debug, Deserialize)]
+struct Record {
+    /// Space-separated identifiers: "doi:10.1007/... omid:br/0610..."
+    id: String,
+    title: Option<String>,
+    /// "Family, Given [orcid:... omid:...]; Family, Given [...]"
+    author: Option<String>,
+    pub_date: Option<String>,
+    /// "Scientometrics [issn:0138-9130 omid:br/...]"
+    venue: Option<String>,
+    volume: Option<String>,
+    issue: Option<String>,
+    page: Option<String>,
+    #[serde(rename = "type")]
+    record_type: Option<String>,
+    publisher: Option<String>,
+}
+
+/// A Meta value without its bracketed identifiers
+fn strip_ids(value: &str) -> &str {
+    value.split(" [").next().unwrap_or(value).trim()
+}
+
+impl Record {
+    fn to_entry(&self) -> Entry {
+        let entry_type = match self.record_type.as_deref() {
+            Some("proceedings article") => "inproceedings",
+            Some("book chapter") => "incollection",
+            Some("book") | Some("edited book") => "book",
+            Some("dissertation") => "phdthesis",
+            Some("report") => "techreport",
+            _ => "article",
+        };
+        let doi = self
+            .id
+            .split_whitespace()
+            .find_map(|id| id.strip_prefix("doi:"))
+            .map(str::to_string);
+        let mut entry = Entry::new(
+            doi.clone().unwrap_or_else(|| self.id.clone()),
+            entry_type.to_string(),
+        );
+
+        entry.title = self.title.as_deref().map(Arc::from);
+        entry.authors = self
+            .author
+            .iter()
+            .flat_map(|authors| authors.split("; "))
+            .map(|author| match strip_ids(author).split_once(", ") {
+                Some((family, given)) => Arc::from(format!("{} {}", given, family)),
+                None => Arc::from(strip_ids(author)),
+            })
+            .collect();
+        entry.year = self
+            .pub_date
+            .as_deref()
+            .and_then(|d| d.get(..4)?.parse().ok());
+        entry.venue = self
+            .venue
+            .as_deref()
+            .map(strip_ids)
+            .filter(|v| !v.is_empty())
+            .map(Arc::from);
+        entry.doi = doi;
+
+        for (name, value) in [
+            ("volume", self.volume.as_deref()),
+            ("number", self.issue.as_deref()),
+            ("pages", self.page.as_deref()),
+            ("publisher", self.publisher.as_deref().map(strip_ids)),
+        ] {
+            if let Some(value) = value.filter(|v| !v.is_empty()) {
+                entry.fields.insert(name.to_string(), value.to_string());
+            }
+        }
+
+        entry
+    }
+}
+
+#[async_trait]
+impl Validator for OpenCitationsClient {
+    async fn search_by_doi(&self, doi: &str) -> Result<Option<Entry>, ValidatorError> {
+        let url = format!("{}/doi:{}", OPENCITATIONS_META_BASE, normalize_doi(doi));
+        let response = self.client.get(&url).send().await?;
+
+        if response.status() == reqwest::StatusCode::TOO_MANY_REQUESTS {
+            return Err(ValidatorError::RateLimited);
+        }
+
+        if !response.status().is_success() {
+            return Ok(None);
+        }
+
+        let records: Vec<Record> = response.json().await.map_err(|e| {
+            ValidatorError::ParseError(format!("Failed to parse OpenCitations response: {}", e))
+        })?;
+
+        Ok(records.first().map(Record::to_entry))
+    }
+
+    async fn search_by_title(&self, _title: &str) -> Result<Vec<Entry>, ValidatorError> {
+        // Meta is looked up by identifier only
+        Ok(Vec::new())
+    }
+
+    fn name(&self) -> &'static str {
+        "OpenCitations"
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn meta_records_are_read() {
+        let json = r#"[{
+            "id": "doi:10.1007/s11192-019-03217-6 omid:br/06101234",
+            "title": "Software Review: COCI",
+            "author": "Heibi, Ivan [orcid:0000-0001-5366-5194 omid:ra/061]; Peroni, Silvio [omid:ra/062]",
+            "pub_date": "2019-09-14",
+            "venue": "Scientometrics [issn:0138-9130 issn:1588-2861 omid:br/063]",
+            "volume": "121",
+            "issue": "2",
+            "page": "1213-1228",
+            "type": "journal article",
+            "publisher": "Springer Science And Business Media LLC [crossref:297 omid:ra/064]",
+            "editor": ""
+        }]"#;
+        let records: Vec<Record> = serde_json::from_str(json).unwrap();
+        let entry = records[0].to_entry();
+        assert_eq!(entry.doi.as_deref(), Some("10.1007/s11192-019-03217-6"));
+        assert_eq!(
+            entry.authors,
+            vec![Arc::from("Ivan Heibi"), Arc::from("Silvio Peroni")]
+        );
+        assert_eq!(entry.venue.as_deref(), Some("Scientometrics"));
+        assert_eq!(entry.year, Some(2019));
+        assert_eq!(
+            entry.field("publisher"),
+            Some("Springer Science And Business Media LLC")
+        );
+    }
+}
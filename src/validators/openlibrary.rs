@@ -1,7 +1,9 @@
 use super::{async_trait, Validator, ValidatorError};
 use crate::entry::Entry;
+use crate::lint::normalize_isbn;
 use reqwest::Client;
 use serde::Deserialize;
+use std::sync::Arc;
 
 const OPENLIBRARY_API_BASE: &str = "https://openlibrary.org";
 
@@ -105,6 +107,10 @@ struct BookEdition {
     publish_date: Option<String>,
     publishers: Option<Vec<String>>,
     works: Option<Vec<WorkRef>>,
+    #[serde(default)]
+    isbn_13: Vec<String>,
+    #[serde(default)]
+    isbn_10: Vec<String>,
 }
 
 #[derive(Debug, Deserialize)]
@@ -124,15 +130,15 @@ impl SearchDoc {
             "book".to_string(),
         );
 
-        entry.title = self.title.clone();
+        entry.title = self.title.as_deref().map(Arc::from);
         entry.year = self.first_publish_year;
 
         if let Some(authors) = &self.author_name {
-            entry.authors = authors.clone();
+            entry.authors = authors.iter().map(|a| Arc::from(a.as_str())).collect();
         }
 
         if let Some(publishers) = &self.publisher {
-            entry.venue = publishers.first().cloned();
+            entry.venue = publishers.first().map(|p| Arc::from(p.as_str()));
         }
 
         entry
@@ -143,7 +149,7 @@ impl BookEdition {
     fn to_entry(&self) -> Entry {
         let mut entry = Entry::new(String::new(), "book".to_string());
 
-        entry.title = self.title.clone();
+        entry.title = self.title.as_deref().map(Arc::from);
 
         // Parse year from publish_date (e.g., "1996", "January 1, 1996", etc.)
         if let Some(date) = &self.publish_date {
@@ -151,7 +157,13 @@ impl BookEdition {
         }
 
         if let Some(publishers) = &self.publishers {
-            entry.venue = publishers.first().cloned();
+            entry.venue = publishers.first().map(|p| Arc::from(p.as_str()));
+        }
+
+        // Only editions have ISBNs of their own; search results list those of every edition
+        if let Some(isbn) = self.isbn_13.iter().chain(&self.isbn_10).next() {
+            entry.fields.insert("isbn".to_string(), isbn.clone());
+            entry.isbn = normalize_isbn(isbn);
         }
 
         entry
@@ -161,7 +173,7 @@ impl BookEdition {
 impl Work {
     fn to_entry(&self) -> Entry {
         let mut entry = Entry::new(String::new(), "book".to_string());
-        entry.title = self.title.clone();
+        entry.title = self.title.as_deref().map(Arc::from);
         entry
     }
 }
@@ -3,6 +3,7 @@ use crate::entry::Entry;
 use chrono::{DateTime, Datelike, Utc};
 use reqwest::Client;
 use serde::Deserialize;
+use std::sync::Arc;
 
 const OPENREVIEW_API_BASE: &str = "https://api.openreview.net";
 
@@ -105,21 +106,21 @@ impl Note {
 
         if let Some(content) = &self.content {
             if let Some(title) = &content.title {
-                entry.title = Some(title.as_str().to_string());
+                entry.title = Some(Arc::from(title.as_str()));
             }
 
             if let Some(authors) = &content.authors {
-                entry.authors = authors.as_vec().to_vec();
+                entry.authors = authors.as_vec().iter().map(|a| Arc::from(a.as_str())).collect();
             }
 
             if let Some(venue) = &content.venue {
-                entry.venue = Some(venue.as_str().to_string());
+                entry.venue = Some(Arc::from(venue.as_str()));
             }
         }
 
         // Use top-level venue if content venue is missing
         if entry.venue.is_none() {
-            entry.venue = self.venue.clone();
+            entry.venue = self.venue.as_deref().map(Arc::from);
         }
 
         // Extract year from creation date (milliseconds since epoch)
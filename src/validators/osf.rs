@@ -0,0 +1,249 @@
+use super::{async_trait, Validator, ValidatorError};
+use crate::entry::Entry;
+use crate::lint::normalize_doi;
+use reqwest::Client;
+use serde::Deserialize;
+use std::sync::Arc;
+
+const OSF_API_BASE: &str = "https://api.osf.io/v2/preprints";
+
+/// The OSF preprint GUID of a DOI minted by an OSF provider ("10.31234/osf.io/abcde")
+pub fn osf_guid(doi: &str) -> Option<String> {
+    let doi = normalize_doi(doi);
+    let (_, guid) = doi.split_once("/osf.io/")?;
+    (!guid.is_empty() && guid.chars().all(|c| c.is_ascii_alphanumeric() || c == '_'))
+        .then(|| guid.to_string())
+}
+
+/// Client for OSF Preprints, which hosts PsyArXiv, SocArXiv, EdArXiv and the other
+/// OSF preprint providers
+pub struct OsfClient {
+    client: Client,
+}
+
+impl OsfClient {
+    pub fn new() -> Self {
+        let client = Client::builder()
+            .user_agent("bibval/0.1.0 (https://github.com/femtomc/bibval)")
+            .build()
+            .expect("Failed to create HTTP client");
+        Self { client }
+    }
+
+    async fn get<T: for<'de> Deserialize<'de>>(
+        &self,
+        url: &str,
+    ) -> Result<Option<T>, ValidatorError> {
+        let response = self.client.get(url).send().await?;
+
+        if response.status() == reqwest::StatusCode::TOO_MANY_REQUESTS {
+            return Err(ValidatorError::RateLimited);
+        }
+
+        if !response.status().is_success() {
+            return Ok(None);
+        }
+
+        let body = response.json().await.map_err(|e| {
+            ValidatorError::ParseError(format!("Failed to parse OSF response: {}", e))
+        })?;
+        Ok(Some(body))
+    }
+}
+
+impl Default for OsfClient {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[derive(Debug, Deserialize)]
+struct SingleResponse {
+    data: Preprint,
+}
+
+#[derive(Debug, Deserialize)]
+struct ListResponse {
+    #[serde(default)]
+    data: Vec<Preprint>,
+}
+
+#[derive(Debug, Deserialize)]
+struct Preprint {
+    id: String,
+    attributes: Attributes,
+    links: Option<Links>,
+    embeds: Option<Embeds>,
+}
+
+#[derive(Debug, Deserialize)]
+struct Attributes {
+    title: Option<String>,
+    date_published: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+struct Links {
+    preprint_doi: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+struct Embeds {
+    contributors: Option<Contributors>,
+    provider: Option<Provider>,
+}
+
+#[derive(Debug, Deserialize)]
+struct Contributors {
+    #[serde(default)]
+    data: Vec<Contributor>,
+}
+
+#[derive(Debug, Deserialize)]
+struct Contributor {
+    attributes: ContributorAttributes,
+    embeds: Option<ContributorEmbeds>,
+}
+
+#[derive(Debug, Deserialize)]
+struct ContributorAttributes {
+    #[serde(default)]
+    bibliographic: bool,
+}
+
+#[derive(Debug, Deserialize)]
+struct ContributorEmbeds {
+    users: Option<User>,
+}
+
+#[derive(Debug, Deserialize)]
+struct User {
+    data: Option<UserData>,
+}
+
+#[derive(Debug, Deserialize)]
+struct UserData {
+    attributes: UserAttributes,
+}
+
+#[derive(Debug, Deserialize)]
+struct UserAttributes {
+    full_name: String,
+}
+
+#[derive(Debug, Deserialize)]
+struct Provider {
+    data: Option<ProviderData>,
+}
+
+#[derive(Debug, Deserialize)]
+struct ProviderData {
+    attributes: ProviderAttributes,
+}
+
+#[derive(Debug, Deserialize)]
+struct ProviderAttributes {
+    name: String,
+}
+
+impl Preprint {
+    fn to_entry(&self) -> Entry {
+        let mut entry = Entry::new(self.id.clone(), "misc".to_string());
+
+        entry.title = self.attributes.title.as_deref().map(Arc::from);
+        entry.year = self
+            .attributes
+            .date_published
+            .as_deref()
+            .and_then(|d| d.get(..4)?.parse().ok());
+        entry.doi = self
+            .links
+            .as_ref()
+            .and_then(|l| l.preprint_doi.as_deref())
+            .map(normalize_doi);
+
+        let embeds = self.embeds.as_ref();
+        // Non-bibliographic contributors are not authors
+        entry.authors = embeds
+            .and_then(|e| e.contributors.as_ref())
+            .map(|c| c.data.as_slice())
+            .unwrap_or_default()
+            .iter()
+            .filter(|c| c.attributes.bibliographic)
+            .filter_map(|c| c.embeds.as_ref()?.users.as_ref()?.data.as_ref())
+            .map(|u| Arc::from(u.attributes.full_name.as_str()))
+            .collect();
+        // The provider (PsyArXiv, SocArXiv, ...) is where the preprint was published
+        entry.venue = embeds
+            .and_then(|e| e.provider.as_ref()?.data.as_ref())
+            .map(|p| Arc::from(p.attributes.name.as_str()));
+
+        entry
+    }
+}
+
+#[async_trait]
+impl Validator for OsfClient {
+    async fn search_by_doi(&self, doi: &str) -> Result<Option<Entry>, ValidatorError> {
+        let Some(guid) = osf_guid(doi) else {
+            return Ok(None);
+        };
+        let url = format!(
+            "{}/{}/?embed=contributors&embed=provider",
+            OSF_API_BASE, guid
+        );
+        let response: Option<SingleResponse> = self.get(&url).await?;
+        Ok(response.map(|r| r.data.to_entry()))
+    }
+
+    async fn search_by_title(&self, title: &str) -> Result<Vec<Entry>, ValidatorError> {
+        let url = format!(
+            "{}/?filter[title]={}&page[size]=5&embed=contributors&embed=provider",
+            OSF_API_BASE,
+            urlencoding::encode(title)
+        );
+        let response: Option<ListResponse> = self.get(&url).await?;
+        Ok(response
+            .map(|r| r.data.iter().map(Preprint::to_entry).collect())
+            .unwrap_or_default())
+    }
+
+    fn name(&self) -> &'static str {
+        "OSF Preprints"
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn providers_become_venues() {
+        assert_eq!(
+            osf_guid("https://doi.org/10.31234/osf.io/abc12"),
+            Some("abc12".to_string())
+        );
+        assert_eq!(osf_guid("10.1000/xyz"), None);
+
+        let json = r#"{"data": {
+            "id": "abc12",
+            "attributes": {"title": "A Preprint", "date_published": "2020-03-01T12:00:00Z"},
+            "links": {"preprint_doi": "https://doi.org/10.31234/osf.io/abc12"},
+            "embeds": {
+                "contributors": {"data": [
+                    {"attributes": {"bibliographic": true},
+                     "embeds": {"users": {"data": {"attributes": {"full_name": "Jane Doe"}}}}},
+                    {"attributes": {"bibliographic": false},
+                     "embeds": {"users": {"data": {"attributes": {"full_name": "An Admin"}}}}}
+                ]},
+                "provider": {"data": {"attributes": {"name": "PsyArXiv"}}}
+            }
+        }}"#;
+        let response: SingleResponse = serde_json::from_str(json).unwrap();
+        let entry = response.data.to_entry();
+        assert_eq!(entry.venue.as_deref(), Some("PsyArXiv"));
+        assert_eq!(entry.authors, vec![Arc::from("Jane Doe")]);
+        assert_eq!(entry.doi.as_deref(), Some("10.31234/osf.io/abc12"));
+        assert_eq!(entry.year, Some(2020));
+    }
+}
@@ -0,0 +1,224 @@
+use super::{async_trait, Validator, ValidatorError};
+use crate::entry::Entry;
+use crate::lint::normalize_arxiv_id;
+use crate::venues::find_venue;
+use reqwest::Client;
+use serde::Deserialize;
+use std::sync::Arc;
+
+const PWC_API_BASE: &str = "https://paperswithcode.com/api/v1";
+
+/// Field of a matched entry holding the paper's official code repository
+pub const CODE_REPOSITORY_FIELD: &str = "code_repository";
+
+/// arXiv categories of machine learning and its applications
+const ML_CATEGORIES: &[&str] = &[
+    "cs.LG", "cs.AI", "cs.CV", "cs.CL", "cs.NE", "cs.IR", "cs.RO", "stat.ML", "eess.AS", "eess.IV",
+];
+
+/// Abbreviations of the bundled machine learning venues
+const ML_VENUES: &[&str] = &[
+    "NeurIPS", "ICML", "ICLR", "AAAI", "IJCAI", "ACL", "UAI", "AISTATS", "COLT", "KDD", "CVPR",
+    "EMNLP", "JMLR", "TMLR", "MLJ", "NECO", "TPAMI",
+];
+
+/// Whether an entry cites machine learning work: an ML arXiv category or an ML venue
+pub fn is_ml(entry: &Entry) -> bool {
+    let category = entry.field("primaryclass").unwrap_or("");
+    let venue = entry.venue.as_deref().and_then(find_venue);
+    ML_CATEGORIES.iter().any(|c| category.starts_with(c))
+        || venue.is_some_and(|v| ML_VENUES.contains(&v.abbreviation))
+}
+
+/// Client for Papers With Code, which links machine learning papers to their code
+pub struct PapersWithCodeClient {
+    client: Client,
+    /// Also look up each matched paper's official code repository
+    code_repositories: bool,
+}
+
+impl PapersWithCodeClient {
+    pub fn new(code_repositories: bool) -> Self {
+        let client = Client::builder()
+            .user_agent("bibval/0.1.0 (https://github.com/femtomc/bibval)")
+            .build()
+            .expect("Failed to create HTTP client");
+        Self {
+            client,
+            code_repositories,
+        }
+    }
+
+    /// Search by arXiv ID
+    pub async fn search_by_arxiv_id(
+        &self,
+        arxiv_id: &str,
+    ) -> Result<Option<Entry>, ValidatorError> {
+        let url = format!(
+            "{}/papers/?arxiv_id={}",
+            PWC_API_BASE,
+            urlencoding::encode(&normalize_arxiv_id(arxiv_id))
+        );
+        let papers = self.search(&url).await?;
+        match papers.into_iter().next() {
+            Some(paper) => Ok(Some(self.to_entry(paper).await?)),
+            None => Ok(None),
+        }
+    }
+
+    async fn search(&self, url: &str) -> Result<Vec<Paper>, ValidatorError> {
+        let page: Page<Paper> = self.get(url).await?;
+        Ok(page.results)
+    }
+
+    async fn get<T: for<'de> Deserialize<'de> + Default>(
+        &self,
+        url: &str,
+    ) -> Result<T, ValidatorError> {
+        let response = self.client.get(url).send().await?;
+
+        if response.status() == reqwest::StatusCode::TOO_MANY_REQUESTS {
+            return Err(ValidatorError::RateLimited);
+        }
+
+        if !response.status().is_success() {
+            return Ok(T::default());
+        }
+
+        response.json().await.map_err(|e| {
+            ValidatorError::ParseError(format!("Failed to parse Papers With Code response: {}", e))
+        })
+    }
+
+    async fn to_entry(&self, paper: Paper) -> Result<Entry, ValidatorError> {
+        let mut entry = paper.to_entry();
+        if self.code_repositories {
+            let url = format!("{}/papers/{}/repositories/", PWC_API_BASE, paper.id);
+            let repositories: Page<Repository> = self.get(&url).await?;
+            if let Some(official) = repositories.results.into_iter().find(|r| r.is_official) {
+                entry
+                    .fields
+                    .insert(CODE_REPOSITORY_FIELD.to_string(), official.url);
+            }
+        }
+        Ok(entry)
+    }
+}
+
+impl Default for PapersWithCodeClient {
+    fn default() -> Self {
+        Self::new(false)
+    }
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(bound(deserialize = "T: Deserialize<'de>"))]
+struct Page<T> {
+    #[serde(default)]
+    results: Vec<T>,
+}
+
+impl<T> Default for Page<T> {
+    fn default() -> Self {
+        Self {
+            results: Vec::new(),
+        }
+    }
+}
+
+#[derive(Debug, Deserialize)]
+struct Paper {
+    /// Slug of the paper's page ("attention-is-all-you-need")
+    id: String,
+    arxiv_id: Option<String>,
+    title: Option<String>,
+    #[serde(default)]
+    authors: Vec<String>,
+    /// "2017-06-12"
+    published: Option<String>,
+    /// Slug of the proceedings the paper appeared in ("neurips-2017-12")
+    proceeding: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+struct Repository {
+    url: String,
+    #[serde(default)]
+    is_official: bool,
+}
+
+impl Paper {
+    fn to_entry(&self) -> Entry {
+        let entry_type = if self.proceeding.is_some() {
+            "inproceedings"
+        } else {
+            "article"
+        };
+        let mut entry = Entry::new(self.id.clone(), entry_type.to_string());
+
+        entry.title = self.title.as_deref().map(Arc::from);
+        entry.authors = self.authors.iter().map(|a| Arc::from(a.as_str())).collect();
+        entry.year = self
+            .published
+            .as_deref()
+            .and_then(|d| d.get(..4)?.parse().ok());
+        entry.arxiv_id = self.arxiv_id.clone();
+
+        entry
+    }
+}
+
+#[async_trait]
+impl Validator for PapersWithCodeClient {
+    async fn search_by_doi(&self, _doi: &str) -> Result<Option<Entry>, ValidatorError> {
+        // Papers With Code has no DOI lookup
+        Ok(None)
+    }
+
+    async fn search_by_title(&self, title: &str) -> Result<Vec<Entry>, ValidatorError> {
+        let url = format!(
+            "{}/papers/?q={}&items_per_page=5",
+            PWC_API_BASE,
+            urlencoding::encode(title)
+        );
+        let mut entries = Vec::new();
+        for paper in self.search(&url).await? {
+            entries.push(self.to_entry(paper).await?);
+        }
+        Ok(entries)
+    }
+
+    fn name(&self) -> &'static str {
+        "Papers With Code"
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn ml_entries_are_recognized_and_papers_read() {
+        let mut entry = Entry::new("vaswani2017".to_string(), "inproceedings".to_string());
+        entry.venue = Some(Arc::from(
+            "Advances in Neural Information Processing Systems",
+        ));
+        assert!(is_ml(&entry));
+        entry.venue = Some(Arc::from("Physical Review Letters"));
+        assert!(!is_ml(&entry));
+        entry
+            .fields
+            .insert("primaryclass".to_string(), "cs.CL".to_string());
+        assert!(is_ml(&entry));
+
+        let json = r#"{"count": 1, "results": [{"id": "attention-is-all-you-need",
+            "arxiv_id": "1706.03762", "title": "Attention Is All You Need",
+            "authors": ["Ashish Vaswani", "Noam Shazeer"], "published": "2017-06-12",
+            "conference": "neurips-2017-12", "proceeding": "neurips-2017-12"}]}"#;
+        let page: Page<Paper> = serde_json::from_str(json).unwrap();
+        let paper = page.results[0].to_entry();
+        assert_eq!(paper.entry_type, "inproceedings");
+        assert_eq!(paper.year, Some(2017));
+        assert_eq!(paper.arxiv_id.as_deref(), Some("1706.03762"));
+    }
+}
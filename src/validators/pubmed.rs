@@ -0,0 +1,258 @@
+use super::{async_trait, Validator, ValidatorError};
+use crate::entry::Entry;
+use reqwest::Client;
+use serde::Deserialize;
+use serde_json::Value;
+use std::collections::HashMap;
+use std::sync::Arc;
+
+const EUTILS_API_BASE: &str = "https://eutils.ncbi.nlm.nih.gov/entrez/eutils";
+const IDCONV_API_URL: &str = "https://www.ncbi.nlm.nih.gov/pmc/utils/idconv/v1.0/";
+
+/// Client for PubMed through the NCBI E-utilities
+pub struct PubMedClient {
+    client: Client,
+    /// NCBI API key, which raises the rate limit from 3 to 10 requests per second
+    api_key: Option<String>,
+}
+
+impl PubMedClient {
+    pub fn new(api_key: Option<String>) -> Self {
+        let client = Client::builder()
+            .user_agent("bibval/0.1.0 (https://github.com/femtomc/bibval)")
+            .build()
+            .expect("Failed to create HTTP client");
+        Self { client, api_key }
+    }
+
+    /// Look up an article by PMID
+    pub async fn search_by_pmid(&self, pmid: &str) -> Result<Option<Entry>, ValidatorError> {
+        Ok(self
+            .summaries(&[pmid.trim().to_string()])
+            .await?
+            .into_iter()
+            .next())
+    }
+
+    /// Look up an article by PubMed Central ID, resolving it to a PMID with the NCBI ID
+    /// converter
+    pub async fn search_by_pmcid(&self, pmcid: &str) -> Result<Option<Entry>, ValidatorError> {
+        let mut url = format!(
+            "{}?ids={}&format=json&tool=bibval",
+            IDCONV_API_URL,
+            urlencoding::encode(pmcid.trim())
+        );
+        if let Some(key) = &self.api_key {
+            url.push_str("&api_key=");
+            url.push_str(&urlencoding::encode(key));
+        }
+        let response: IdConvResponse = self.get(&url).await?;
+
+        let Some(record) = response.records.into_iter().next() else {
+            return Ok(None);
+        };
+        if let Some(pmid) = record.pmid {
+            return self.search_by_pmid(&pmid).await;
+        }
+        // Articles deposited in PMC but not indexed in PubMed have no summary; the DOI
+        // is all there is to compare
+        Ok(record.doi.map(|doi| {
+            let mut entry = Entry::new(record.pmcid.clone().unwrap_or_default(), "article".into());
+            entry.doi = Some(doi);
+            entry.pmcid = record.pmcid;
+            entry
+        }))
+    }
+
+    fn url(&self, tool: &str, query: &str) -> String {
+        let mut url = format!(
+            "{}/{}.fcgi?db=pubmed&retmode=json&tool=bibval&{}",
+            EUTILS_API_BASE, tool, query
+        );
+        if let Some(key) = &self.api_key {
+            url.push_str("&api_key=");
+            url.push_str(&urlencoding::encode(key));
+        }
+        url
+    }
+
+    async fn get<T: serde::de::DeserializeOwned>(&self, url: &str) -> Result<T, ValidatorError> {
+        let response = self.client.get(url).send().await?;
+
+        if response.status() == reqwest::StatusCode::TOO_MANY_REQUESTS {
+            return Err(ValidatorError::RateLimited);
+        }
+
+        if !response.status().is_success() {
+            return Err(ValidatorError::ParseError(format!(
+                "PubMed returned {}",
+                response.status()
+            )));
+        }
+
+        response.json().await.map_err(|e| {
+            ValidatorError::ParseError(format!("Failed to parse PubMed response: {}", e))
+        })
+    }
+
+    /// PMIDs matching an Entrez query, best first
+    async fn esearch(&self, term: &str, limit: usize) -> Result<Vec<String>, ValidatorError> {
+        let url = self.url(
+            "esearch",
+            &format!("term={}&retmax={}", urlencoding::encode(term), limit),
+        );
+        let response: SearchResponse = self.get(&url).await?;
+        Ok(response.esearchresult.idlist)
+    }
+
+    /// Article summaries for PMIDs, in the order given
+    async fn summaries(&self, pmids: &[String]) -> Result<Vec<Entry>, ValidatorError> {
+        if pmids.is_empty() {
+            return Ok(Vec::new());
+        }
+
+        let url = self.url("esummary", &format!("id={}", pmids.join(",")));
+        let response: SummaryResponse = self.get(&url).await?;
+
+        // The result object maps each PMID to its summary, next to a list of the PMIDs
+        let mut result = response.result;
+        let uids: Vec<String> = result
+            .remove("uids")
+            .and_then(|uids| serde_json::from_value(uids).ok())
+            .unwrap_or_default();
+        Ok(uids
+            .iter()
+            .filter_map(|uid| serde_json::from_value::<Summary>(result.remove(uid)?).ok())
+            .filter(|summary| summary.error.is_none())
+            .map(|summary| summary.to_entry())
+            .collect())
+    }
+}
+
+#[derive(Debug, Deserialize)]
+struct SearchResponse {
+    esearchresult: SearchResult,
+}
+
+#[derive(Debug, Deserialize)]
+struct SearchResult {
+    #[serde(default)]
+    idlist: Vec<String>,
+}
+
+#[derive(Debug, Deserialize)]
+struct IdConvResponse {
+    #[serde(default)]
+    records: Vec<IdConvRecord>,
+}
+
+#[derive(Debug, Deserialize)]
+struct IdConvRecord {
+    pmcid: Option<String>,
+    pmid: Option<String>,
+    doi: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+struct SummaryResponse {
+    #[serde(default)]
+    result: HashMap<String, Value>,
+}
+
+#[derive(Debug, Deserialize)]
+struct Summary {
+    uid: String,
+    title: Option<String>,
+    #[serde(default)]
+    authors: Vec<Author>,
+    pubdate: Option<String>,
+    fulljournalname: Option<String>,
+    #[serde(default)]
+    articleids: Vec<ArticleId>,
+    error: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+struct Author {
+    name: String,
+    authtype: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+struct ArticleId {
+    idtype: String,
+    value: String,
+}
+
+impl Summary {
+    fn to_entry(&self) -> Entry {
+        let mut entry = Entry::new(self.uid.clone(), "article".to_string());
+
+        // Titles end with a period, and translated titles are bracketed
+        entry.title = self.title.as_deref().map(|t| {
+            let t = t.trim().trim_end_matches('.');
+            Arc::from(
+                t.strip_prefix('[')
+                    .and_then(|t| t.strip_suffix(']'))
+                    .unwrap_or(t),
+            )
+        });
+        entry.authors = self
+            .authors
+            .iter()
+            .filter(|a| a.authtype.as_deref().is_none_or(|t| t == "Author"))
+            .map(|a| Arc::from(medline_name(&a.name)))
+            .collect();
+        entry.year = self
+            .pubdate
+            .as_deref()
+            .and_then(|d| d.get(..4)?.parse().ok());
+        entry.venue = self.fulljournalname.as_deref().map(Arc::from);
+        entry.pmid = Some(self.uid.clone());
+
+        for id in &self.articleids {
+            match id.idtype.as_str() {
+                "doi" => entry.doi = Some(id.value.clone()),
+                "pmc" => entry.pmcid = Some(id.value.clone()),
+                _ => {}
+            }
+        }
+
+        entry
+    }
+}
+
+/// Turn a MEDLINE display name ("Smith JA") into "J A Smith", so it compares with
+/// names written in full
+fn medline_name(name: &str) -> String {
+    match name.trim().rsplit_once(' ') {
+        Some((surname, initials))
+            if initials.len() <= 3 && initials.chars().all(|c| c.is_ascii_uppercase()) =>
+        {
+            let mut parts: Vec<String> = initials.chars().map(String::from).collect();
+            parts.push(surname.to_string());
+            parts.join(" ")
+        }
+        _ => name.trim().to_string(),
+    }
+}
+
+#[async_trait]
+impl Validator for PubMedClient {
+    async fn search_by_doi(&self, doi: &str) -> Result<Option<Entry>, ValidatorError> {
+        let pmids = self.esearch(&format!("{}[doi]", doi.trim()), 1).await?;
+        Ok(self.summaries(&pmids).await?.into_iter().next())
+    }
+
+    async fn search_by_title(&self, title: &str) -> Result<Vec<Entry>, ValidatorError> {
+        let title = title.replace(['"', '[', ']'], " ");
+        let pmids = self
+            .esearch(&format!("\"{}\"[Title]", title.trim()), 5)
+            .await?;
+        self.summaries(&pmids).await
+    }
+
+    fn name(&self) -> &'static str {
+        "PubMed"
+    }
+}
@@ -0,0 +1,280 @@
+use super::{async_trait, Validator, ValidatorError};
+use crate::entry::{normalize_string, Entry};
+use crate::lint::normalize_doi;
+use reqwest::Client;
+use serde::Deserialize;
+use std::collections::HashMap;
+use std::sync::Arc;
+use strsim::jaro_winkler;
+
+const SCIELO_SEARCH_BASE: &str = "https://search.scielo.org/";
+
+/// DOI prefix SciELO Brazil registers its journals' articles under
+const SCIELO_DOI_PREFIX: &str = "10.1590/";
+
+/// Words common in the names of Portuguese- and Spanish-language journals
+const IBERO_AMERICAN_VENUE_WORDS: &[&str] = &[
+    "revista",
+    "cadernos",
+    "arquivos",
+    "anais",
+    "ciência",
+    "ciencia",
+    "saúde",
+    "salud",
+    "estudios",
+    "estudos",
+    "acta",
+    "boletín",
+    "educação",
+    "educación",
+];
+
+/// Whether a DOI was registered by SciELO Brazil
+pub fn is_scielo_doi(doi: &str) -> bool {
+    normalize_doi(doi).starts_with(SCIELO_DOI_PREFIX)
+}
+
+/// Whether an entry likely cites a Latin American, Spanish or Portuguese journal, which
+/// DBLP and often CrossRef don't cover: a Portuguese or Spanish `langid`/`language`,
+/// a SciELO DOI, or a venue named like one
+pub fn is_ibero_american(entry: &Entry) -> bool {
+    let language = entry
+        .field("langid")
+        .or(entry.field("language"))
+        .unwrap_or_default()
+        .to_lowercase();
+    let venue = entry.venue.as_deref().unwrap_or_default().to_lowercase();
+    [
+        "pt",
+        "es",
+        "portug",
+        "spanish",
+        "brazil",
+        "español",
+        "castellano",
+    ]
+    .iter()
+    .any(|l| language.starts_with(l))
+        || entry.doi.as_deref().is_some_and(is_scielo_doi)
+        || venue
+            .split_whitespace()
+            .any(|w| IBERO_AMERICAN_VENUE_WORDS.contains(&w))
+}
+
+/// Client for the SciELO search service, which indexes the open-access journals of the
+/// SciELO network in Latin America, Spain, Portugal and South Africa
+pub struct ScieloClient {
+    client: Client,
+}
+
+impl ScieloClient {
+    pub fn new() -> Self {
+        let client = Client::builder()
+            .user_agent("bibval/0.1.0 (https://github.com/femtomc/bibval)")
+            .build()
+            .expect("Failed to create HTTP client");
+        Self { client }
+    }
+
+    async fn search(&self, query: &str, count: usize) -> Result<Vec<Document>, ValidatorError> {
+        let url = format!(
+            "{}?q={}&count={}&output=json",
+            SCIELO_SEARCH_BASE,
+            urlencoding::encode(query),
+            count
+        );
+        let response = self.client.get(&url).send().await?;
+
+        if response.status() == reqwest::StatusCode::TOO_MANY_REQUESTS {
+            return Err(ValidatorError::RateLimited);
+        }
+
+        if !response.status().is_success() {
+            return Ok(Vec::new());
+        }
+
+        let body: SearchResponse = response.json().await.map_err(|e| {
+            ValidatorError::ParseError(format!("Failed to parse SciELO response: {}", e))
+        })?;
+        Ok(body
+            .dia_server_response
+            .into_iter()
+            .flat_map(|r| r.response.docs)
+            .collect())
+    }
+}
+
+impl Default for ScieloClient {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[derive(Debug, Deserialize)]
+struct SearchResponse {
+    #[serde(rename = "diaServerResponse", default)]
+    dia_server_response: Vec<ServerResponse>,
+}
+
+#[derive(Debug, Deserialize)]
+struct ServerResponse {
+    response: Docs,
+}
+
+#[derive(Debug, Deserialize)]
+struct Docs {
+    #[serde(default)]
+    docs: Vec<Document>,
+}
+
+#[derive(Debug, Deserialize)]
+struct Document {
+    /// "S0034-89102009000100001-scl"
+    id: String,
+    /// Authors as "Surname, Given"
+    #[serde(default)]
+    au: Vec<String>,
+    /// Publication year ("2009")
+    da: Option<String>,
+    doi: Option<String>,
+    #[serde(default)]
+    journal_title: Vec<String>,
+    /// Languages the article is published in, original first
+    #[serde(default)]
+    la: Vec<String>,
+    volume: Option<String>,
+    issue: Option<String>,
+    start_page: Option<String>,
+    end_page: Option<String>,
+    /// Titles keyed by language: "ti_pt", "ti_en", "ti_es"
+    #[serde(flatten)]
+    other: HashMap<String, serde_json::Value>,
+}
+
+impl Document {
+    /// The article's titles in each language it was published in
+    fn titles(&self) -> Vec<(&str, &str)> {
+        self.other
+            .iter()
+            .filter_map(|(name, value)| {
+                let language = name.strip_prefix("ti_")?;
+                let title = match value {
+                    serde_json::Value::Array(titles) => titles.first()?.as_str()?,
+                    other => other.as_str()?,
+                };
+                Some((language, title))
+            })
+            .collect()
+    }
+
+    /// Convert to an entry, titled with the translation closest to `query` when given
+    /// and otherwise in the article's original language
+    fn to_entry(&self, query: Option<&str>) -> Entry {
+        let mut entry = Entry::new(
+            self.id.trim_end_matches("-scl").to_string(),
+            "article".to_string(),
+        );
+
+        let titles = self.titles();
+        let title = match query.map(normalize_string) {
+            Some(query) => titles
+                .iter()
+                .map(|(_, title)| (jaro_winkler(&normalize_string(title), &query), *title))
+                .max_by(|a, b| a.0.total_cmp(&b.0))
+                .map(|(_, title)| title),
+            None => self
+                .la
+                .first()
+                .and_then(|la| titles.iter().find(|(language, _)| language == la))
+                .or(titles.first())
+                .map(|(_, title)| *title),
+        };
+        entry.title = title.map(Arc::from);
+        entry.authors = self
+            .au
+            .iter()
+            .map(|name| match name.split_once(", ") {
+                Some((family, given)) => Arc::from(format!("{} {}", given, family)),
+                None => Arc::from(name.as_str()),
+            })
+            .collect();
+        entry.year = self.da.as_deref().and_then(|d| d.get(..4)?.parse().ok());
+        entry.venue = self.journal_title.first().map(|t| Arc::from(t.as_str()));
+        entry.doi = self.doi.as_deref().map(normalize_doi);
+
+        let pages = match (&self.start_page, &self.end_page) {
+            (Some(start), Some(end)) => Some(format!("{}--{}", start, end)),
+            (Some(start), None) => Some(start.clone()),
+            _ => None,
+        };
+        for (name, value) in [
+            ("volume", self.volume.clone()),
+            ("number", self.issue.clone()),
+            ("pages", pages),
+        ] {
+            if let Some(value) = value.filter(|v| !v.is_empty()) {
+                entry.fields.insert(name.to_string(), value);
+            }
+        }
+
+        entry
+    }
+}
+
+#[async_trait]
+impl Validator for ScieloClient {
+    async fn search_by_doi(&self, doi: &str) -> Result<Option<Entry>, ValidatorError> {
+        let query = format!("doi:\"{}\"", normalize_doi(doi));
+        let docs = self.search(&query, 1).await?;
+        Ok(docs.first().map(|d| d.to_entry(None)))
+    }
+
+    async fn search_by_title(&self, title: &str) -> Result<Vec<Entry>, ValidatorError> {
+        // Quotes would end the phrase query early
+        let query = format!("ti:\"{}\"", title.replace('"', ""));
+        let docs = self.search(&query, 5).await?;
+        Ok(docs.iter().map(|d| d.to_entry(Some(title))).collect())
+    }
+
+    fn name(&self) -> &'static str {
+        "SciELO"
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn documents_are_titled_in_the_cited_language() {
+        let json = r#"{"diaServerResponse": [{"response": {"numFound": 1, "docs": [{
+            "id": "S0034-89102009000100001-scl",
+            "ti_pt": ["Prevalência de hipertensão arterial em adultos"],
+            "ti_en": ["Prevalence of hypertension in adults"],
+            "au": ["Silva, Maria", "Souza, João"],
+            "da": "2009",
+            "doi": "10.1590/S0034-89102009000100001",
+            "journal_title": ["Revista de Saúde Pública"],
+            "la": ["pt"],
+            "volume": "43", "issue": "1", "start_page": "1", "end_page": "9"
+        }]}}]}"#;
+        let body: SearchResponse = serde_json::from_str(json).unwrap();
+        let document = &body.dia_server_response[0].response.docs[0];
+
+        let original = document.to_entry(None);
+        assert_eq!(
+            original.title.as_deref(),
+            Some("Prevalência de hipertensão arterial em adultos")
+        );
+        assert_eq!(original.authors[0].as_ref(), "Maria Silva");
+        assert_eq!(original.field("pages"), Some("1--9"));
+        assert!(is_ibero_american(&original));
+
+        let translated = document.to_entry(Some("Prevalence of hypertension in adults"));
+        assert_eq!(
+            translated.title.as_deref(),
+            Some("Prevalence of hypertension in adults")
+        );
+    }
+}
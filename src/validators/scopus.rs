@@ -0,0 +1,196 @@
+use super::{async_trait, Validator, ValidatorError};
+use crate::entry::Entry;
+use crate::lint::normalize_doi;
+use reqwest::Client;
+use serde::Deserialize;
+use std::sync::Arc;
+
+const SCOPUS_API_BASE: &str = "https://api.elsevier.com/content/search/scopus";
+
+/// Client for the Scopus Search API, which needs an institutional Elsevier API key
+pub struct ScopusClient {
+    client: Client,
+    api_key: String,
+}
+
+impl ScopusClient {
+    pub fn new(api_key: String) -> Self {
+        let client = Client::builder()
+            .user_agent("bibval/0.1.0 (https://github.com/femtomc/bibval)")
+            .build()
+            .expect("Failed to create HTTP client");
+        Self { client, api_key }
+    }
+
+    async fn search(&self, query: &str, count: usize) -> Result<Vec<Entry>, ValidatorError> {
+        let url = format!(
+            "{}?query={}&count={}",
+            SCOPUS_API_BASE,
+            urlencoding::encode(query),
+            count
+        );
+
+        let response = self
+            .client
+            .get(&url)
+            .header("X-ELS-APIKey", &self.api_key)
+            .header("Accept", "application/json")
+            .send()
+            .await?;
+
+        if response.status() == reqwest::StatusCode::TOO_MANY_REQUESTS {
+            return Err(ValidatorError::RateLimited);
+        }
+
+        if matches!(
+            response.status(),
+            reqwest::StatusCode::UNAUTHORIZED | reqwest::StatusCode::FORBIDDEN
+        ) {
+            return Err(ValidatorError::ParseError(
+                "Scopus rejected the API key (it may only work from an institutional network)"
+                    .to_string(),
+            ));
+        }
+
+        if !response.status().is_success() {
+            return Err(ValidatorError::ParseError(format!(
+                "Scopus returned {}",
+                response.status()
+            )));
+        }
+
+        let response: SearchResponse = response.json().await.map_err(|e| {
+            ValidatorError::ParseError(format!("Failed to parse Scopus response: {}", e))
+        })?;
+
+        // An empty result set comes back as a single entry carrying an error message
+        Ok(response
+            .results
+            .entry
+            .iter()
+            .filter(|r| r.error.is_none())
+            .map(SearchEntry::to_entry)
+            .collect())
+    }
+}
+
+#[derive(Debug, Deserialize)]
+struct SearchResponse {
+    #[serde(rename = "search-results")]
+    results: SearchResults,
+}
+
+#[derive(Debug, Deserialize)]
+struct SearchResults {
+    #[serde(default)]
+    entry: Vec<SearchEntry>,
+}
+
+#[derive(Debug, Deserialize)]
+struct SearchEntry {
+    #[serde(rename = "dc:title")]
+    title: Option<String>,
+    #[serde(rename = "prism:publicationName")]
+    publication_name: Option<String>,
+    #[serde(rename = "prism:coverDate")]
+    cover_date: Option<String>,
+    #[serde(rename = "prism:doi")]
+    doi: Option<String>,
+    #[serde(rename = "prism:pageRange")]
+    page_range: Option<String>,
+    #[serde(rename = "prism:volume")]
+    volume: Option<String>,
+    #[serde(rename = "subtypeDescription")]
+    subtype: Option<String>,
+    #[serde(rename = "dc:identifier")]
+    identifier: Option<String>,
+    error: Option<String>,
+}
+
+impl SearchEntry {
+    fn to_entry(&self) -> Entry {
+        let entry_type = match self.subtype.as_deref() {
+            Some("Conference Paper") => "inproceedings",
+            Some("Book Chapter") => "incollection",
+            Some("Book") => "book",
+            _ => "article",
+        };
+        // "SCOPUS_ID:85071234567"
+        let key = self
+            .identifier
+            .as_deref()
+            .map(|id| id.trim_start_matches("SCOPUS_ID:").to_string())
+            .unwrap_or_default();
+        let mut entry = Entry::new(key, entry_type.to_string());
+
+        entry.title = self.title.as_deref().map(Arc::from);
+        // Search results name only the first author, so authors are left out rather
+        // than reported as a shorter list
+        entry.year = self
+            .cover_date
+            .as_deref()
+            .and_then(|d| d.get(..4)?.parse().ok());
+        entry.venue = self.publication_name.as_deref().map(Arc::from);
+        entry.doi = self.doi.clone();
+
+        for (name, value) in [
+            ("pages", self.page_range.clone()),
+            ("volume", self.volume.clone()),
+        ] {
+            if let Some(value) = value.filter(|v| !v.is_empty()) {
+                entry.fields.insert(name.to_string(), value);
+            }
+        }
+
+        entry
+    }
+}
+
+#[async_trait]
+impl Validator for ScopusClient {
+    async fn search_by_doi(&self, doi: &str) -> Result<Option<Entry>, ValidatorError> {
+        let query = format!("DOI({})", normalize_doi(doi));
+        Ok(self.search(&query, 1).await?.into_iter().next())
+    }
+
+    async fn search_by_title(&self, title: &str) -> Result<Vec<Entry>, ValidatorError> {
+        let title = title.replace(['"', '(', ')', '{', '}'], " ");
+        self.search(&format!("TITLE(\"{}\")", title.trim()), 5)
+            .await
+    }
+
+    fn name(&self) -> &'static str {
+        "Scopus"
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn empty_result_sets_yield_no_entries() {
+        let json = r#"{"search-results": {"entry": [
+            {"@_fa": "true", "error": "Result set was empty"}
+        ]}}"#;
+        let response: SearchResponse = serde_json::from_str(json).unwrap();
+        assert!(response.results.entry.iter().all(|r| r.error.is_some()));
+
+        let json = r#"{"search-results": {"entry": [{
+            "dc:identifier": "SCOPUS_ID:85071234567",
+            "dc:title": "A Paper",
+            "dc:creator": "Doe J.",
+            "prism:publicationName": "Journal of Things",
+            "prism:coverDate": "2019-08-01",
+            "prism:doi": "10.1000/xyz",
+            "prism:pageRange": "101-114",
+            "subtypeDescription": "Article"
+        }]}}"#;
+        let response: SearchResponse = serde_json::from_str(json).unwrap();
+        let entry = response.results.entry[0].to_entry();
+        assert_eq!(entry.key, "85071234567");
+        assert_eq!(entry.year, Some(2019));
+        assert!(entry.authors.is_empty());
+        assert_eq!(entry.field("pages"), Some("101-114"));
+    }
+}
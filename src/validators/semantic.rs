@@ -1,9 +1,15 @@
-use super::{async_trait, Validator, ValidatorError};
+use super::{async_trait, Validator, ValidatorError, CITATION_COUNT_FIELD};
 use crate::entry::Entry;
 use reqwest::Client;
 use serde::Deserialize;
+use std::sync::Arc;
 
 const SEMANTIC_SCHOLAR_API_BASE: &str = "https://api.semanticscholar.org/graph/v1";
+const PAPER_FIELDS: &str =
+    "title,authors,year,venue,journal,externalIds,citationCount,abstract";
+
+/// Maximum number of IDs accepted by one `/paper/batch` request
+pub const SEMANTIC_SCHOLAR_BATCH_SIZE: usize = 500;
 
 pub struct SemanticScholarClient {
     client: Client,
@@ -17,6 +23,49 @@ impl SemanticScholarClient {
             .expect("Failed to create HTTP client");
         Self { client }
     }
+
+    /// Look up many papers in one `/paper/batch` request.
+    ///
+    /// IDs use Semantic Scholar's prefixed form (`DOI:...`, `ARXIV:...`); the result is
+    /// aligned with `ids`, with `None` for IDs Semantic Scholar doesn't know.
+    pub async fn search_by_ids(&self, ids: &[String]) -> Result<Vec<Option<Entry>>, ValidatorError> {
+        if ids.is_empty() {
+            return Ok(Vec::new());
+        }
+
+        let url = format!(
+            "{}/paper/batch?fields={}",
+            SEMANTIC_SCHOLAR_API_BASE, PAPER_FIELDS
+        );
+
+        let response = self
+            .client
+            .post(&url)
+            .json(&serde_json::json!({ "ids": ids }))
+            .send()
+            .await?;
+
+        if response.status() == reqwest::StatusCode::TOO_MANY_REQUESTS {
+            return Err(ValidatorError::RateLimited);
+        }
+
+        let papers: Vec<Option<Paper>> = response.json().await.map_err(|e| {
+            ValidatorError::ParseError(format!("Failed to parse Semantic Scholar response: {}", e))
+        })?;
+
+        if papers.len() != ids.len() {
+            return Err(ValidatorError::ParseError(format!(
+                "Semantic Scholar batch returned {} results for {} IDs",
+                papers.len(),
+                ids.len()
+            )));
+        }
+
+        Ok(papers
+            .into_iter()
+            .map(|paper| paper.map(|p| p.to_entry()))
+            .collect())
+    }
 }
 
 impl Default for SemanticScholarClient {
@@ -40,6 +89,17 @@ struct Paper {
     venue: Option<String>,
     #[serde(rename = "externalIds")]
     external_ids: Option<ExternalIds>,
+    #[serde(rename = "citationCount")]
+    citation_count: Option<u64>,
+    journal: Option<Journal>,
+    #[serde(rename = "abstract")]
+    abstract_text: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+struct Journal {
+    volume: Option<String>,
+    pages: Option<String>,
 }
 
 #[derive(Debug, Deserialize)]
@@ -62,14 +122,14 @@ impl Paper {
             "article".to_string(),
         );
 
-        entry.title = self.title.clone();
+        entry.title = self.title.as_deref().map(Arc::from);
         entry.year = self.year;
-        entry.venue = self.venue.clone();
+        entry.venue = self.venue.as_deref().map(Arc::from);
 
         if let Some(authors) = &self.authors {
             entry.authors = authors
                 .iter()
-                .filter_map(|a| a.name.clone())
+                .filter_map(|a| a.name.as_deref().map(Arc::from))
                 .collect();
         }
 
@@ -78,6 +138,27 @@ impl Paper {
             entry.arxiv_id = ids.arxiv.clone();
         }
 
+        if let Some(journal) = &self.journal {
+            for (name, value) in [("volume", &journal.volume), ("pages", &journal.pages)] {
+                // Pages come with surrounding whitespace and line breaks
+                if let Some(value) = value.as_deref().map(str::trim).filter(|v| !v.is_empty()) {
+                    entry.fields.insert(name.to_string(), value.to_string());
+                }
+            }
+        }
+
+        if let Some(text) = self.abstract_text.as_deref().filter(|t| !t.trim().is_empty()) {
+            entry
+                .fields
+                .insert("abstract".to_string(), text.trim().to_string());
+        }
+
+        if let Some(count) = self.citation_count {
+            entry
+                .fields
+                .insert(CITATION_COUNT_FIELD.to_string(), count.to_string());
+        }
+
         entry
     }
 }
@@ -86,8 +167,8 @@ impl Paper {
 impl Validator for SemanticScholarClient {
     async fn search_by_doi(&self, doi: &str) -> Result<Option<Entry>, ValidatorError> {
         let url = format!(
-            "{}/paper/DOI:{}?fields=title,authors,year,venue,externalIds",
-            SEMANTIC_SCHOLAR_API_BASE, doi
+            "{}/paper/DOI:{}?fields={}",
+            SEMANTIC_SCHOLAR_API_BASE, doi, PAPER_FIELDS
         );
 
         let response = self.client.get(&url).send().await?;
@@ -109,9 +190,10 @@ impl Validator for SemanticScholarClient {
 
     async fn search_by_title(&self, title: &str) -> Result<Vec<Entry>, ValidatorError> {
         let url = format!(
-            "{}/paper/search?query={}&fields=title,authors,year,venue,externalIds&limit=5",
+            "{}/paper/search?query={}&fields={}&limit=5",
             SEMANTIC_SCHOLAR_API_BASE,
-            urlencoding::encode(title)
+            urlencoding::encode(title),
+            PAPER_FIELDS
         );
 
         let response = self.client.get(&url).send().await?;
@@ -134,8 +216,8 @@ impl Validator for SemanticScholarClient {
 
     async fn search_by_arxiv_id(&self, arxiv_id: &str) -> Result<Option<Entry>, ValidatorError> {
         let url = format!(
-            "{}/paper/ARXIV:{}?fields=title,authors,year,venue,externalIds",
-            SEMANTIC_SCHOLAR_API_BASE, arxiv_id
+            "{}/paper/ARXIV:{}?fields={}",
+            SEMANTIC_SCHOLAR_API_BASE, arxiv_id, PAPER_FIELDS
         );
 
         let response = self.client.get(&url).send().await?;
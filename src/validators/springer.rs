@@ -0,0 +1,158 @@
+use super::{async_trait, Validator, ValidatorError};
+use crate::entry::Entry;
+use crate::lint::normalize_doi;
+use reqwest::Client;
+use serde::Deserialize;
+use std::sync::Arc;
+
+const SPRINGER_API_BASE: &str = "https://api.springernature.com/meta/v2/json";
+
+/// DOI prefixes of Springer, Nature and BMC publications
+const SPRINGER_DOI_PREFIXES: &[&str] = &["10.1007/", "10.1038/", "10.1186/"];
+
+/// Whether a DOI was registered by Springer Nature
+pub fn is_springer_doi(doi: &str) -> bool {
+    let doi = normalize_doi(doi);
+    SPRINGER_DOI_PREFIXES
+        .iter()
+        .any(|prefix| doi.starts_with(prefix))
+}
+
+/// Client for the Springer Nature Meta API, covering Springer and Nature journals,
+/// books and proceedings such as LNCS
+pub struct SpringerClient {
+    client: Client,
+    api_key: String,
+}
+
+impl SpringerClient {
+    pub fn new(api_key: String) -> Self {
+        let client = Client::builder()
+            .user_agent("bibval/0.1.0 (https://github.com/femtomc/bibval)")
+            .build()
+            .expect("Failed to create HTTP client");
+        Self { client, api_key }
+    }
+
+    async fn search(&self, query: &str, limit: usize) -> Result<Vec<Entry>, ValidatorError> {
+        let url = format!(
+            "{}?q={}&p={}&api_key={}",
+            SPRINGER_API_BASE,
+            urlencoding::encode(query),
+            limit,
+            urlencoding::encode(&self.api_key)
+        );
+
+        let response = self.client.get(&url).send().await?;
+
+        if response.status() == reqwest::StatusCode::TOO_MANY_REQUESTS {
+            return Err(ValidatorError::RateLimited);
+        }
+
+        if !response.status().is_success() {
+            return Err(ValidatorError::ParseError(format!(
+                "Springer Nature returned {}",
+                response.status()
+            )));
+        }
+
+        let response: MetaResponse = response.json().await.map_err(|e| {
+            ValidatorError::ParseError(format!("Failed to parse Springer Nature response: {}", e))
+        })?;
+
+        Ok(response.records.iter().map(Record::to_entry).collect())
+    }
+}
+
+#[derive(Debug, Deserialize)]
+struct MetaResponse {
+    #[serde(default)]
+    records: Vec<Record>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct Record {
+    title: Option<String>,
+    #[serde(default)]
+    creators: Vec<Creator>,
+    publication_name: Option<String>,
+    doi: Option<String>,
+    publication_date: Option<String>,
+    starting_page: Option<String>,
+    ending_page: Option<String>,
+    volume: Option<String>,
+    publisher: Option<String>,
+    content_type: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+struct Creator {
+    creator: String,
+}
+
+impl Record {
+    fn to_entry(&self) -> Entry {
+        let entry_type = match self.content_type.as_deref() {
+            Some("Chapter") | Some("Chapter ConferencePaper") => "incollection",
+            Some("Book") => "book",
+            _ => "article",
+        };
+        let mut entry = Entry::new(self.doi.clone().unwrap_or_default(), entry_type.to_string());
+
+        entry.title = self.title.as_deref().map(Arc::from);
+        // Creators are written "Family, Given"
+        entry.authors = self
+            .creators
+            .iter()
+            .map(|c| match c.creator.split_once(", ") {
+                Some((family, given)) => Arc::from(format!("{} {}", given, family)),
+                None => Arc::from(c.creator.as_str()),
+            })
+            .collect();
+        entry.year = self
+            .publication_date
+            .as_deref()
+            .and_then(|d| d.get(..4)?.parse().ok());
+        entry.venue = self.publication_name.as_deref().map(Arc::from);
+        entry.doi = self.doi.clone();
+
+        let pages = match (&self.starting_page, &self.ending_page) {
+            (Some(start), Some(end)) if start != end => Some(format!("{}--{}", start, end)),
+            (Some(start), _) => Some(start.clone()),
+            _ => None,
+        };
+        for (name, value) in [
+            ("pages", pages),
+            ("volume", self.volume.clone()),
+            ("publisher", self.publisher.clone()),
+        ] {
+            if let Some(value) = value.filter(|v| !v.is_empty()) {
+                entry.fields.insert(name.to_string(), value);
+            }
+        }
+
+        entry
+    }
+}
+
+#[async_trait]
+impl Validator for SpringerClient {
+    async fn search_by_doi(&self, doi: &str) -> Result<Option<Entry>, ValidatorError> {
+        let doi = normalize_doi(doi);
+        Ok(self
+            .search(&format!("doi:{}", doi), 1)
+            .await?
+            .into_iter()
+            .next())
+    }
+
+    async fn search_by_title(&self, title: &str) -> Result<Vec<Entry>, ValidatorError> {
+        self.search(&format!("title:\"{}\"", title.replace('"', "")), 5)
+            .await
+    }
+
+    fn name(&self) -> &'static str {
+        "Springer Nature"
+    }
+}
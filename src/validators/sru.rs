@@ -0,0 +1,286 @@
+use super::hathitrust::clean_marc_value;
+use super::{async_trait, Validator, ValidatorError};
+use crate::entry::{ApiSource, Entry};
+use crate::lint::normalize_isbn;
+use quick_xml::events::Event;
+use quick_xml::Reader;
+use reqwest::Client;
+use std::sync::Arc;
+
+/// National library catalogs with an SRU (Search/Retrieve via URL) endpoint serving
+/// MARC21 records
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SruLibrary {
+    /// Deutsche Nationalbibliothek
+    Dnb,
+    /// British Library
+    BritishLibrary,
+}
+
+impl SruLibrary {
+    pub fn source(&self) -> ApiSource {
+        match self {
+            SruLibrary::Dnb => ApiSource::Dnb,
+            SruLibrary::BritishLibrary => ApiSource::BritishLibrary,
+        }
+    }
+
+    fn base_url(&self) -> &'static str {
+        match self {
+            SruLibrary::Dnb => "https://services.dnb.de/sru/dnb",
+            SruLibrary::BritishLibrary => "https://sru.bl.uk/SRU",
+        }
+    }
+
+    /// CQL indexes for ISBNs and titles
+    fn indexes(&self) -> (&'static str, &'static str) {
+        match self {
+            SruLibrary::Dnb => ("num", "tit"),
+            SruLibrary::BritishLibrary => ("bath.isbn", "dc.title"),
+        }
+    }
+
+    fn record_schema(&self) -> &'static str {
+        match self {
+            SruLibrary::Dnb => "MARC21-xml",
+            SruLibrary::BritishLibrary => "marcxml",
+        }
+    }
+}
+
+/// Client for a national library's SRU catalog
+pub struct SruClient {
+    client: Client,
+    library: SruLibrary,
+}
+
+impl SruClient {
+    pub fn new(library: SruLibrary) -> Self {
+        let client = Client::builder()
+            .user_agent("bibval/0.1.0 (https://github.com/femtomc/bibval)")
+            .build()
+            .expect("Failed to create HTTP client");
+        Self { client, library }
+    }
+
+    pub fn library(&self) -> SruLibrary {
+        self.library
+    }
+
+    /// Look up a book by ISBN
+    pub async fn search_by_isbn(&self, isbn: &str) -> Result<Option<Entry>, ValidatorError> {
+        let (isbn_index, _) = self.library.indexes();
+        let query = format!("{}={}", isbn_index, isbn);
+        Ok(self.search(&query, 1).await?.into_iter().next())
+    }
+
+    async fn search(&self, query: &str, limit: usize) -> Result<Vec<Entry>, ValidatorError> {
+        let url = format!(
+            "{}?version=1.1&operation=searchRetrieve&query={}&recordSchema={}&maximumRecords={}",
+            self.library.base_url(),
+            urlencoding::encode(query),
+            self.library.record_schema(),
+            limit
+        );
+        let response = self.client.get(&url).send().await?;
+
+        if response.status() == reqwest::StatusCode::TOO_MANY_REQUESTS {
+            return Err(ValidatorError::RateLimited);
+        }
+
+        if !response.status().is_success() {
+            return Ok(Vec::new());
+        }
+
+        let xml = response.text().await?;
+        Ok(parse_records(&xml)
+            .iter()
+            .map(MarcRecord::to_entry)
+            .collect())
+    }
+}
+
+/// The fields of a MARC21 record that describe a book
+#[derive(Debug, Default)]
+struct MarcRecord {
+    control_number: Option<String>,
+    title: Option<String>,
+    subtitle: Option<String>,
+    authors: Vec<String>,
+    publisher: Option<String>,
+    date: Option<String>,
+    fixed_data: Option<String>,
+    isbn: Option<String>,
+}
+
+impl MarcRecord {
+    fn to_entry(&self) -> Entry {
+        let mut entry = Entry::new(
+            self.control_number.clone().unwrap_or_default(),
+            "book".to_string(),
+        );
+
+        entry.title = self.title.as_deref().map(|title| {
+            let title = clean_marc_value(title);
+            match self.subtitle.as_deref().map(clean_marc_value) {
+                Some(subtitle) => Arc::from(format!("{}: {}", title, subtitle)),
+                None => Arc::from(title),
+            }
+        });
+        entry.authors = self.authors.iter().map(|a| Arc::from(a.as_str())).collect();
+        // 260/264$c ("c2015."), falling back to the first date in the 008 field
+        entry.year = self
+            .date
+            .iter()
+            .map(|d| {
+                d.chars()
+                    .filter(char::is_ascii_digit)
+                    .take(4)
+                    .collect::<String>()
+            })
+            .chain(
+                self.fixed_data
+                    .iter()
+                    .filter_map(|f| f.get(7..11).map(str::to_string)),
+            )
+            .find_map(|year| (year.len() == 4).then(|| year.parse().ok()).flatten());
+        entry.venue = self.publisher.as_deref().map(Arc::from);
+        if let Some(isbn) = &self.isbn {
+            entry.fields.insert("isbn".to_string(), isbn.clone());
+            entry.isbn = normalize_isbn(isbn);
+        }
+
+        entry
+    }
+}
+
+/// Read every MARC21 record in an SRU response. Records start at their leader, since
+/// the SRU envelope's own elements are also called `record`.
+fn parse_records(xml: &str) -> Vec<MarcRecord> {
+    let mut reader = Reader::from_str(xml);
+    reader.config_mut().trim_text(true);
+
+    let mut records: Vec<MarcRecord> = Vec::new();
+    let mut tag = String::new();
+    let mut code = String::new();
+    let mut buf = Vec::new();
+
+    loop {
+        match reader.read_event_into(&mut buf) {
+            Ok(Event::Start(ref e)) => {
+                let attribute = |name: &[u8]| {
+                    e.attributes()
+                        .flatten()
+                        .find(|a| a.key.local_name().as_ref() == name)
+                        .map(|a| String::from_utf8_lossy(&a.value).to_string())
+                        .unwrap_or_default()
+                };
+                match e.local_name().as_ref() {
+                    b"leader" => records.push(MarcRecord::default()),
+                    b"controlfield" | b"datafield" => tag = attribute(b"tag"),
+                    b"subfield" => code = attribute(b"code"),
+                    _ => {}
+                }
+            }
+            Ok(Event::End(ref e)) => match e.local_name().as_ref() {
+                b"controlfield" | b"datafield" => tag.clear(),
+                b"subfield" => code.clear(),
+                _ => {}
+            },
+            Ok(Event::Text(ref e)) => {
+                let Some(record) = records.last_mut() else {
+                    buf.clear();
+                    continue;
+                };
+                let text = e.unescape().unwrap_or_default().to_string();
+                match (tag.as_str(), code.as_str()) {
+                    ("001", "") => record.control_number = Some(text),
+                    ("008", "") => record.fixed_data = Some(text),
+                    ("020", "a") if record.isbn.is_none() => {
+                        let isbn = text.split_whitespace().next().unwrap_or_default();
+                        record.isbn = Some(isbn.to_string());
+                    }
+                    ("100" | "700", "a") => record.authors.push(clean_marc_value(&text)),
+                    ("245", "a") => record.title = Some(text),
+                    ("245", "b") => record.subtitle = Some(text),
+                    ("260" | "264", "b") if record.publisher.is_none() => {
+                        record.publisher = Some(clean_marc_value(&text));
+                    }
+                    ("260" | "264", "c") if record.date.is_none() => record.date = Some(text),
+                    _ => {}
+                }
+            }
+            Ok(Event::Eof) | Err(_) => break,
+            _ => {}
+        }
+        buf.clear();
+    }
+
+    records
+}
+
+#[async_trait]
+impl Validator for SruClient {
+    async fn search_by_doi(&self, _doi: &str) -> Result<Option<Entry>, ValidatorError> {
+        // Library catalogs don't index DOIs
+        Ok(None)
+    }
+
+    async fn search_by_title(&self, title: &str) -> Result<Vec<Entry>, ValidatorError> {
+        let (_, title_index) = self.library.indexes();
+        let query = format!("{}=\"{}\"", title_index, title.replace('"', ""));
+        self.search(&query, 5).await
+    }
+
+    fn name(&self) -> &'static str {
+        match self.library {
+            SruLibrary::Dnb => "DNB",
+            SruLibrary::BritishLibrary => "British Library",
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn sru_responses_yield_each_marc_record() {
+        let xml = r#"<searchRetrieveResponse xmlns="http://www.loc.gov/zing/srw/">
+          <numberOfRecords>1</numberOfRecords>
+          <records><record><recordData>
+            <record xmlns="http://www.loc.gov/MARC21/slim" type="Bibliographic">
+              <leader>00000nam a2200000 c 4500</leader>
+              <controlfield tag="001">1012345678</controlfield>
+              <controlfield tag="008">110101s2011    gw |||||      00| ||ger c</controlfield>
+              <datafield tag="020" ind1=" " ind2=" ">
+                <subfield code="a">9783161484100 Pp. : EUR 29.00</subfield>
+              </datafield>
+              <datafield tag="100" ind1="1" ind2=" ">
+                <subfield code="a">Müller, Hans</subfield>
+              </datafield>
+              <datafield tag="245" ind1="1" ind2="0">
+                <subfield code="a">Grundlagen der Logik</subfield>
+                <subfield code="b">eine Einführung</subfield>
+              </datafield>
+              <datafield tag="264" ind1=" " ind2="1">
+                <subfield code="b">Mohr Siebeck</subfield>
+              </datafield>
+            </record>
+          </recordData></record></records>
+        </searchRetrieveResponse>"#;
+
+        let records = parse_records(xml);
+        assert_eq!(records.len(), 1);
+        let entry = records[0].to_entry();
+        assert_eq!(entry.key, "1012345678");
+        assert_eq!(
+            entry.title.as_deref(),
+            Some("Grundlagen der Logik: eine Einführung")
+        );
+        assert_eq!(entry.authors, vec![Arc::from("Müller, Hans")]);
+        assert_eq!(entry.year, Some(2011));
+        assert_eq!(entry.venue.as_deref(), Some("Mohr Siebeck"));
+        assert_eq!(entry.field("isbn"), Some("9783161484100"));
+    }
+}
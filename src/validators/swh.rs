@@ -0,0 +1,178 @@
+use super::{async_trait, Validator, ValidatorError};
+use crate::entry::Entry;
+use reqwest::Client;
+use serde::Deserialize;
+use std::collections::HashMap;
+
+const SWH_API_BASE: &str = "https://archive.softwareheritage.org/api/1";
+
+/// Field of a matched entry holding the SWHID of the archived repository, or of the
+/// cited release when it was found in the archive
+pub const SWHID_FIELD: &str = "swhid";
+
+/// Client for the Software Heritage archive, which preserves the source code of public
+/// repositories and identifies every snapshot and release with a SWHID
+pub struct SoftwareHeritageClient {
+    client: Client,
+}
+
+impl SoftwareHeritageClient {
+    pub fn new() -> Self {
+        let client = Client::builder()
+            .user_agent("bibval/0.1.0 (https://github.com/femtomc/bibval)")
+            .build()
+            .expect("Failed to create HTTP client");
+        Self { client }
+    }
+
+    /// Look up the latest archived snapshot of a repository and, given a version, the
+    /// release tagged for it; `None` when the repository was never archived
+    pub async fn search_by_origin(
+        &self,
+        origin: &str,
+        version: Option<&str>,
+    ) -> Result<Option<Entry>, ValidatorError> {
+        let origin = origin.trim_end_matches('/').trim_end_matches(".git");
+        let url = format!(
+            "{}/origin/{}/visit/latest/?require_snapshot=true",
+            SWH_API_BASE, origin
+        );
+        let Some(visit) = self.get::<Visit>(&url).await? else {
+            return Ok(None);
+        };
+        let Some(snapshot) = visit.snapshot else {
+            return Ok(None);
+        };
+
+        let mut swhid = format!("swh:1:snp:{}", snapshot);
+        if let Some(version) = version {
+            let version = version.trim();
+            // Tags are usually the bare version or the version with a "v"
+            let tags = [
+                format!("refs/tags/{}", version),
+                format!("refs/tags/v{}", version),
+            ];
+            for tag in &tags {
+                let url = format!(
+                    "{}/snapshot/{}/?branches_from={}&branches_count=1",
+                    SWH_API_BASE,
+                    snapshot,
+                    urlencoding::encode(tag)
+                );
+                let branch = self
+                    .get::<Snapshot>(&url)
+                    .await?
+                    .and_then(|mut s| s.branches.remove(tag).flatten());
+                if let Some(found) = branch.as_ref().and_then(Branch::swhid) {
+                    swhid = found;
+                    break;
+                }
+            }
+        }
+
+        let mut entry = Entry::new(swhid.clone(), "software".to_string());
+        entry.url = Some(visit.origin);
+        entry.fields.insert(SWHID_FIELD.to_string(), swhid);
+        Ok(Some(entry))
+    }
+
+    async fn get<T: for<'de> Deserialize<'de>>(
+        &self,
+        url: &str,
+    ) -> Result<Option<T>, ValidatorError> {
+        let response = self.client.get(url).send().await?;
+
+        if response.status() == reqwest::StatusCode::TOO_MANY_REQUESTS {
+            return Err(ValidatorError::RateLimited);
+        }
+
+        if !response.status().is_success() {
+            return Ok(None);
+        }
+
+        let body = response.json().await.map_err(|e| {
+            ValidatorError::ParseError(format!("Failed to parse Software Heritage response: {}", e))
+        })?;
+        Ok(Some(body))
+    }
+}
+
+impl Default for SoftwareHeritageClient {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[derive(Debug, Deserialize)]
+struct Visit {
+    origin: String,
+    snapshot: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+struct Snapshot {
+    #[serde(default)]
+    branches: HashMap<String, Option<Branch>>,
+}
+
+#[derive(Debug, Deserialize)]
+struct Branch {
+    target: String,
+    target_type: String,
+}
+
+impl Branch {
+    /// The SWHID of the object a branch points to
+    fn swhid(&self) -> Option<String> {
+        let kind = match self.target_type.as_str() {
+            "release" => "rel",
+            "revision" => "rev",
+            "directory" => "dir",
+            "content" => "cnt",
+            _ => return None,
+        };
+        Some(format!("swh:1:{}:{}", kind, self.target))
+    }
+}
+
+#[async_trait]
+impl Validator for SoftwareHeritageClient {
+    async fn search_by_doi(&self, _doi: &str) -> Result<Option<Entry>, ValidatorError> {
+        // The archive is indexed by origin URL, not DOI
+        Ok(None)
+    }
+
+    async fn search_by_title(&self, _title: &str) -> Result<Vec<Entry>, ValidatorError> {
+        // The archive is indexed by origin URL, not title
+        Ok(Vec::new())
+    }
+
+    fn name(&self) -> &'static str {
+        "Software Heritage"
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn snapshot_branches_resolve_to_swhids() {
+        let json = r#"{"id": "bcfd516ef0e188d20056c77b8577577ac3ca6e58",
+            "branches": {"refs/tags/v1.26.4": {
+                "target": "6c2d3c5b8a4cdb7a5e5e7c06a2b6a0d7e7b2c1a9",
+                "target_type": "release"
+            }, "HEAD": null},
+            "next_branch": "refs/tags/v1.26.5"}"#;
+        let mut snapshot: Snapshot = serde_json::from_str(json).unwrap();
+        let branch = snapshot
+            .branches
+            .remove("refs/tags/v1.26.4")
+            .flatten()
+            .unwrap();
+        assert_eq!(
+            branch.swhid().as_deref(),
+            Some("swh:1:rel:6c2d3c5b8a4cdb7a5e5e7c06a2b6a0d7e7b2c1a9")
+        );
+    }
+}
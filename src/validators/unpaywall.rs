@@ -0,0 +1,194 @@
+use super::{async_trait, Validator, ValidatorError};
+use crate::entry::Entry;
+use crate::lint::normalize_doi;
+use reqwest::Client;
+use serde::Deserialize;
+use std::sync::Arc;
+
+const UNPAYWALL_API_BASE: &str = "https://api.unpaywall.org/v2";
+
+/// Field of a matched entry holding the URL of its best open-access copy
+pub const OA_URL_FIELD: &str = "oa_url";
+
+/// Field of a matched entry holding its open-access status (gold, green, hybrid, bronze)
+pub const OA_STATUS_FIELD: &str = "oa_status";
+
+/// Client for Unpaywall, which knows every Crossref DOI and where it can be read for
+/// free. Unpaywall asks callers to identify themselves with an email address.
+pub struct UnpaywallClient {
+    client: Client,
+    email: String,
+}
+
+impl UnpaywallClient {
+    pub fn new(email: String) -> Self {
+        let client = Client::builder()
+            .user_agent("bibval/0.1.0 (https://github.com/femtomc/bibval)")
+            .build()
+            .expect("Failed to create HTTP client");
+        Self { client, email }
+    }
+
+    async fn get<T: for<'de> Deserialize<'de>>(
+        &self,
+        url: &str,
+    ) -> Result<Option<T>, ValidatorError> {
+        let response = self.client.get(url).send().await?;
+
+        if response.status() == reqwest::StatusCode::TOO_MANY_REQUESTS {
+            return Err(ValidatorError::RateLimited);
+        }
+
+        // Unknown DOIs are a 404
+        if !response.status().is_success() {
+            return Ok(None);
+        }
+
+        let body = response.json().await.map_err(|e| {
+            ValidatorError::ParseError(format!("Failed to parse Unpaywall response: {}", e))
+        })?;
+        Ok(Some(body))
+    }
+}
+
+#[derive(Debug, Deserialize)]
+struct SearchResponse {
+    #[serde(default)]
+    results: Vec<SearchResult>,
+}
+
+#[derive(Debug, Deserialize)]
+struct SearchResult {
+    response: Work,
+}
+
+#[derive(Debug, Deserialize)]
+struct Work {
+    doi: String,
+    title: Option<String>,
+    year: Option<i32>,
+    journal_name: Option<String>,
+    publisher: Option<String>,
+    genre: Option<String>,
+    #[serde(default)]
+    z_authors: Option<Vec<Author>>,
+    oa_status: Option<String>,
+    best_oa_location: Option<Location>,
+}
+
+#[derive(Debug, Deserialize)]
+struct Author {
+    given: Option<String>,
+    family: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+struct Location {
+    url: Option<String>,
+}
+
+impl Work {
+    fn to_entry(&self) -> Entry {
+        let entry_type = match self.genre.as_deref() {
+            Some("proceedings-article") => "inproceedings",
+            Some("book-chapter") => "incollection",
+            Some("book") | Some("monograph") => "book",
+            _ => "article",
+        };
+        let mut entry = Entry::new(self.doi.clone(), entry_type.to_string());
+
+        entry.title = self.title.as_deref().map(Arc::from);
+        entry.authors = self
+            .z_authors
+            .iter()
+            .flatten()
+            .filter_map(|a| match (&a.given, &a.family) {
+                (Some(given), Some(family)) => Some(Arc::from(format!("{} {}", given, family))),
+                (None, Some(family)) => Some(Arc::from(family.as_str())),
+                _ => None,
+            })
+            .collect();
+        entry.year = self.year;
+        entry.venue = self.journal_name.as_deref().map(Arc::from);
+        entry.doi = Some(self.doi.clone());
+
+        let oa_url = self.best_oa_location.as_ref().and_then(|l| l.url.clone());
+        for (name, value) in [
+            ("publisher", self.publisher.clone()),
+            (OA_URL_FIELD, oa_url),
+            (OA_STATUS_FIELD, self.oa_status.clone()),
+        ] {
+            if let Some(value) = value.filter(|v| !v.is_empty()) {
+                entry.fields.insert(name.to_string(), value);
+            }
+        }
+
+        entry
+    }
+}
+
+#[async_trait]
+impl Validator for UnpaywallClient {
+    async fn search_by_doi(&self, doi: &str) -> Result<Option<Entry>, ValidatorError> {
+        let url = format!(
+            "{}/{}?email={}",
+            UNPAYWALL_API_BASE,
+            normalize_doi(doi),
+            urlencoding::encode(&self.email)
+        );
+        let work: Option<Work> = self.get(&url).await?;
+        Ok(work.map(|w| w.to_entry()))
+    }
+
+    async fn search_by_title(&self, title: &str) -> Result<Vec<Entry>, ValidatorError> {
+        let url = format!(
+            "{}/search?query={}&email={}",
+            UNPAYWALL_API_BASE,
+            urlencoding::encode(title),
+            urlencoding::encode(&self.email)
+        );
+        let response: Option<SearchResponse> = self.get(&url).await?;
+        Ok(response
+            .map(|r| {
+                r.results
+                    .iter()
+                    .take(5)
+                    .map(|r| r.response.to_entry())
+                    .collect()
+            })
+            .unwrap_or_default())
+    }
+
+    fn name(&self) -> &'static str {
+        "Unpaywall"
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn open_access_locations_are_kept() {
+        let json = r#"{
+            "doi": "10.1038/nature12373",
+            "title": "Nanometre-scale thermometry in a living cell",
+            "year": 2013,
+            "genre": "journal-article",
+            "journal_name": "Nature",
+            "publisher": "Springer Science and Business Media LLC",
+            "z_authors": [{"given": "G.", "family": "Kucsko"}, {"raw_author_name": "Anon"}],
+            "is_oa": true,
+            "oa_status": "green",
+            "best_oa_location": {"url": "https://dash.harvard.edu/bitstream/1/12285462/1/Nanometer.pdf"}
+        }"#;
+        let work: Work = serde_json::from_str(json).unwrap();
+        let entry = work.to_entry();
+        assert_eq!(entry.authors, vec![Arc::from("G. Kucsko")]);
+        assert_eq!(entry.field(OA_STATUS_FIELD), Some("green"));
+        assert!(entry
+            .field(OA_URL_FIELD)
+            .unwrap()
+            .ends_with("Nanometer.pdf"));
+    }
+}
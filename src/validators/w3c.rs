@@ -0,0 +1,247 @@
+use super::{async_trait, Validator, ValidatorError};
+use crate::entry::Entry;
+use regex_lite::Regex;
+use reqwest::Client;
+use serde::Deserialize;
+use std::sync::{Arc, OnceLock};
+
+const W3C_API_BASE: &str = "https://api.w3.org/specifications";
+
+/// Field of a matched entry holding the maturity of the cited version ("Working Draft")
+pub const W3C_STATUS_FIELD: &str = "w3c_status";
+
+/// Field of a matched entry holding the date of the cited version ("2016-08-18")
+pub const W3C_DATE_FIELD: &str = "w3c_date";
+
+/// Fields of a matched entry holding the status and date of the latest version, when
+/// the cited version is not the latest
+pub const W3C_LATEST_STATUS_FIELD: &str = "w3c_latest_status";
+pub const W3C_LATEST_DATE_FIELD: &str = "w3c_latest_date";
+
+/// W3C maturity levels, longest first so "Candidate Recommendation" is not read as
+/// "Recommendation"
+const STATUSES: &[&str] = &[
+    "Superseded Recommendation",
+    "Proposed Recommendation",
+    "Candidate Recommendation",
+    "Working Group Note",
+    "Working Draft",
+    "Recommendation",
+    "Group Note",
+    "Draft Note",
+    "Statement",
+    "Retired",
+];
+
+/// The shortname and, for a dated version, the date ("20171214") of the specification
+/// a W3C technical report URL names.
+///
+/// Dated URLs look like `https://www.w3.org/TR/2017/REC-html52-20171214/`; undated ones,
+/// `https://www.w3.org/TR/html52/`, always show the latest version.
+pub fn tr_url(url: &str) -> Option<(String, Option<String>)> {
+    static DATED: OnceLock<Regex> = OnceLock::new();
+    static UNDATED: OnceLock<Regex> = OnceLock::new();
+    let dated = DATED.get_or_init(|| {
+        Regex::new(r"w3\.org/TR/\d{4}/[A-Z]+-([A-Za-z0-9.-]+?)-(\d{8})/?").unwrap()
+    });
+    let undated = UNDATED.get_or_init(|| Regex::new(r"w3\.org/TR/([A-Za-z0-9.-]+)/?$").unwrap());
+
+    if let Some(captures) = dated.captures(url) {
+        return Some((captures[1].to_string(), Some(captures[2].to_string())));
+    }
+    let captures = undated.captures(url.split(['#', '?']).next()?)?;
+    Some((captures[1].to_string(), None))
+}
+
+/// The maturity level an entry claims for a specification ("W3C Recommendation" in its
+/// `type`, `howpublished` or `note`)
+pub fn local_status(entry: &Entry) -> Option<&'static str> {
+    ["type", "howpublished", "note"]
+        .iter()
+        .filter_map(|name| entry.field(name))
+        .find_map(|text| {
+            let text = text.to_lowercase();
+            STATUSES
+                .iter()
+                .find(|status| text.contains(&status.to_lowercase()))
+                .copied()
+        })
+}
+
+/// Client for the W3C API, which lists every version of every W3C specification
+pub struct W3cClient {
+    client: Client,
+}
+
+impl W3cClient {
+    pub fn new() -> Self {
+        let client = Client::builder()
+            .user_agent("bibval/0.1.0 (https://github.com/femtomc/bibval)")
+            .build()
+            .expect("Failed to create HTTP client");
+        Self { client }
+    }
+
+    /// Look up the specification version a technical report URL names
+    pub async fn search_by_url(&self, url: &str) -> Result<Option<Entry>, ValidatorError> {
+        let Some((shortname, date)) = tr_url(url) else {
+            return Ok(None);
+        };
+
+        let spec_url = format!("{}/{}", W3C_API_BASE, shortname);
+        let Some(spec) = self.get::<Specification>(&spec_url).await? else {
+            return Ok(None);
+        };
+        // Version URLs end in the version's date
+        let latest_date = spec
+            .links
+            .latest_version
+            .as_ref()
+            .and_then(|link| link.href.rsplit('/').next())
+            .map(str::to_string);
+
+        let cited_date = match date.or(latest_date.clone()) {
+            Some(date) => date,
+            None => return Ok(None),
+        };
+        let version_url = format!("{}/versions/{}", spec_url, cited_date);
+        let Some(version) = self.get::<Version>(&version_url).await? else {
+            return Ok(None);
+        };
+
+        let mut entry = version.to_entry(&shortname);
+        if let Some(latest_date) = latest_date.filter(|d| *d != cited_date) {
+            let latest_url = format!("{}/versions/{}", spec_url, latest_date);
+            if let Some(latest) = self.get::<Version>(&latest_url).await? {
+                for (name, value) in [
+                    (W3C_LATEST_STATUS_FIELD, latest.status),
+                    (W3C_LATEST_DATE_FIELD, latest.date),
+                ] {
+                    if let Some(value) = value {
+                        entry.fields.insert(name.to_string(), value);
+                    }
+                }
+            }
+        }
+
+        Ok(Some(entry))
+    }
+
+    async fn get<T: for<'de> Deserialize<'de>>(
+        &self,
+        url: &str,
+    ) -> Result<Option<T>, ValidatorError> {
+        let response = self.client.get(url).send().await?;
+
+        if response.status() == reqwest::StatusCode::TOO_MANY_REQUESTS {
+            return Err(ValidatorError::RateLimited);
+        }
+
+        if !response.status().is_success() {
+            return Ok(None);
+        }
+
+        let body = response.json().await.map_err(|e| {
+            ValidatorError::ParseError(format!("Failed to parse W3C API response: {}", e))
+        })?;
+        Ok(Some(body))
+    }
+}
+
+impl Default for W3cClient {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[derive(Debug, Deserialize)]
+struct Specification {
+    #[serde(rename = "_links")]
+    links: SpecificationLinks,
+}
+
+#[derive(Debug, Deserialize)]
+struct SpecificationLinks {
+    #[serde(rename = "latest-version")]
+    latest_version: Option<Link>,
+}
+
+#[derive(Debug, Deserialize)]
+struct Link {
+    href: String,
+}
+
+#[derive(Debug, Deserialize)]
+struct Version {
+    title: Option<String>,
+    status: Option<String>,
+    /// "2017-12-14"
+    date: Option<String>,
+    uri: Option<String>,
+}
+
+impl Version {
+    fn to_entry(&self, shortname: &str) -> Entry {
+        let mut entry = Entry::new(shortname.to_string(), "techreport".to_string());
+
+        entry.title = self.title.as_deref().map(Arc::from);
+        entry.year = self.date.as_deref().and_then(|d| d.get(..4)?.parse().ok());
+        entry.url = self.uri.clone();
+        for (name, value) in [
+            (W3C_STATUS_FIELD, &self.status),
+            (W3C_DATE_FIELD, &self.date),
+        ] {
+            if let Some(value) = value {
+                entry.fields.insert(name.to_string(), value.clone());
+            }
+        }
+
+        entry
+    }
+}
+
+#[async_trait]
+impl Validator for W3cClient {
+    async fn search_by_doi(&self, _doi: &str) -> Result<Option<Entry>, ValidatorError> {
+        // W3C specifications have no DOIs
+        Ok(None)
+    }
+
+    async fn search_by_title(&self, _title: &str) -> Result<Vec<Entry>, ValidatorError> {
+        // Specifications are looked up by their technical report URL
+        Ok(Vec::new())
+    }
+
+    fn name(&self) -> &'static str {
+        "W3C"
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn technical_report_urls_are_parsed() {
+        assert_eq!(
+            tr_url("https://www.w3.org/TR/2017/REC-html52-20171214/"),
+            Some(("html52".to_string(), Some("20171214".to_string())))
+        );
+        assert_eq!(
+            tr_url("https://www.w3.org/TR/2020/WD-css-grid-1-20200818/"),
+            Some(("css-grid-1".to_string(), Some("20200818".to_string())))
+        );
+        assert_eq!(
+            tr_url("https://www.w3.org/TR/webauthn-2/#sctn-intro"),
+            Some(("webauthn-2".to_string(), None))
+        );
+        assert_eq!(tr_url("https://www.w3.org/People/"), None);
+
+        let mut entry = Entry::new("html".to_string(), "misc".to_string());
+        entry.fields.insert(
+            "type".to_string(),
+            "W3C Candidate Recommendation".to_string(),
+        );
+        assert_eq!(local_status(&entry), Some("Candidate Recommendation"));
+    }
+}
@@ -0,0 +1,105 @@
+use super::ValidatorError;
+use reqwest::{Client, StatusCode};
+use serde::Deserialize;
+use std::time::Duration;
+
+const WAYBACK_API_URL: &str = "https://archive.org/wayback/available";
+
+/// Slow pages shouldn't hold up a whole run
+const REQUEST_TIMEOUT: Duration = Duration::from_secs(20);
+
+/// Outcome of fetching a cited URL
+#[derive(Debug, Clone)]
+pub struct UrlCheck {
+    pub status: StatusCode,
+    /// Where the URL ended up after following redirects
+    pub final_url: String,
+}
+
+impl UrlCheck {
+    /// Whether the page is gone for good rather than temporarily unavailable
+    pub fn is_dead(&self) -> bool {
+        matches!(self.status, StatusCode::NOT_FOUND | StatusCode::GONE)
+    }
+}
+
+/// Client for checking that cited web pages are reachable and archived
+pub struct WebClient {
+    client: Client,
+}
+
+impl WebClient {
+    pub fn new() -> Self {
+        let client = Client::builder()
+            .user_agent("bibval/0.1.0 (https://github.com/femtomc/bibval)")
+            .timeout(REQUEST_TIMEOUT)
+            .build()
+            .expect("Failed to create HTTP client");
+        Self { client }
+    }
+
+    /// Fetch a URL, following redirects
+    pub async fn check_url(&self, url: &str) -> Result<UrlCheck, ValidatorError> {
+        let mut response = self.client.head(url).send().await?;
+
+        // Plenty of servers refuse HEAD requests, so retry those with a GET
+        if matches!(
+            response.status(),
+            StatusCode::METHOD_NOT_ALLOWED | StatusCode::FORBIDDEN
+        ) {
+            response = self.client.get(url).send().await?;
+        }
+
+        Ok(UrlCheck {
+            status: response.status(),
+            final_url: response.url().to_string(),
+        })
+    }
+
+    /// The closest Wayback Machine snapshot of a URL, if it was ever archived
+    pub async fn archived_snapshot(&self, url: &str) -> Result<Option<String>, ValidatorError> {
+        let response = self
+            .client
+            .get(WAYBACK_API_URL)
+            .query(&[("url", url)])
+            .send()
+            .await?;
+
+        if response.status() == StatusCode::TOO_MANY_REQUESTS {
+            return Err(ValidatorError::RateLimited);
+        }
+
+        let response: WaybackResponse = response.json().await.map_err(|e| {
+            ValidatorError::ParseError(format!("Failed to parse Wayback response: {}", e))
+        })?;
+
+        Ok(response
+            .archived_snapshots
+            .closest
+            .filter(|snapshot| snapshot.available)
+            .map(|snapshot| snapshot.url))
+    }
+}
+
+impl Default for WebClient {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[derive(Debug, Deserialize)]
+struct WaybackResponse {
+    #[serde(default)]
+    archived_snapshots: ArchivedSnapshots,
+}
+
+#[derive(Debug, Default, Deserialize)]
+struct ArchivedSnapshots {
+    closest: Option<Snapshot>,
+}
+
+#[derive(Debug, Deserialize)]
+struct Snapshot {
+    available: bool,
+    url: String,
+}
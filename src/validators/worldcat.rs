@@ -0,0 +1,286 @@
+use super::{async_trait, Validator, ValidatorError};
+use crate::entry::Entry;
+use reqwest::Client;
+use serde::Deserialize;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+use tokio::sync::Mutex;
+
+const WORLDCAT_API_BASE: &str = "https://americas.discovery.api.oclc.org/worldcat/search/v2";
+const OCLC_TOKEN_URL: &str = "https://oauth.oclc.org/token";
+
+/// Field of a matched entry holding its OCLC number
+pub const OCLC_FIELD: &str = "oclc";
+
+/// Seconds before a token's expiry at which a new one is requested
+const TOKEN_EXPIRY_MARGIN_SECS: u64 = 60;
+
+/// WSKey credentials for the WorldCat Search API
+#[derive(Debug, Clone)]
+pub struct WorldCatCredentials {
+    pub key: String,
+    pub secret: String,
+}
+
+/// Client for WorldCat, the union catalog of OCLC member libraries
+pub struct WorldCatClient {
+    client: Client,
+    credentials: WorldCatCredentials,
+    /// Access token and when it expires
+    token: Mutex<Option<(String, Instant)>>,
+}
+
+impl WorldCatClient {
+    pub fn new(credentials: WorldCatCredentials) -> Self {
+        let client = Client::builder()
+            .user_agent("bibval/0.1.0 (https://github.com/femtomc/bibval)")
+            .build()
+            .expect("Failed to create HTTP client");
+        Self {
+            client,
+            credentials,
+            token: Mutex::new(None),
+        }
+    }
+
+    /// Search by ISBN
+    pub async fn search_by_isbn(&self, isbn: &str) -> Result<Option<Entry>, ValidatorError> {
+        Ok(self
+            .search(&format!("bn:{}", isbn), 1)
+            .await?
+            .into_iter()
+            .next())
+    }
+
+    /// Search by OCLC number
+    pub async fn search_by_oclc(&self, oclc: &str) -> Result<Option<Entry>, ValidatorError> {
+        let number = oclc
+            .trim()
+            .trim_start_matches("(OCoLC)")
+            .trim_start_matches("ocm");
+        Ok(self
+            .search(&format!("no:{}", number), 1)
+            .await?
+            .into_iter()
+            .next())
+    }
+
+    async fn search(&self, query: &str, limit: usize) -> Result<Vec<Entry>, ValidatorError> {
+        let token = self.access_token().await?;
+        let url = format!(
+            "{}/bibs?q={}&limit={}",
+            WORLDCAT_API_BASE,
+            urlencoding::encode(query),
+            limit
+        );
+
+        let response = self.client.get(&url).bearer_auth(token).send().await?;
+
+        if response.status() == reqwest::StatusCode::TOO_MANY_REQUESTS {
+            return Err(ValidatorError::RateLimited);
+        }
+
+        if !response.status().is_success() {
+            return Err(ValidatorError::ParseError(format!(
+                "WorldCat returned {}",
+                response.status()
+            )));
+        }
+
+        let response: SearchResponse = response.json().await.map_err(|e| {
+            ValidatorError::ParseError(format!("Failed to parse WorldCat response: {}", e))
+        })?;
+
+        Ok(response
+            .bib_records
+            .iter()
+            .map(BibRecord::to_entry)
+            .collect())
+    }
+
+    /// A current access token, requesting a new one when it is about to expire
+    async fn access_token(&self) -> Result<String, ValidatorError> {
+        let mut token = self.token.lock().await;
+        if let Some((value, expires)) = token.as_ref() {
+            if Instant::now() < *expires {
+                return Ok(value.clone());
+            }
+        }
+
+        let response = self
+            .client
+            .post(OCLC_TOKEN_URL)
+            .basic_auth(&self.credentials.key, Some(&self.credentials.secret))
+            .form(&[("grant_type", "client_credentials"), ("scope", "wcapi")])
+            .send()
+            .await?;
+
+        if !response.status().is_success() {
+            return Err(ValidatorError::ParseError(format!(
+                "OCLC rejected the WorldCat credentials ({})",
+                response.status()
+            )));
+        }
+
+        let granted: TokenResponse = response.json().await.map_err(|e| {
+            ValidatorError::ParseError(format!("Failed to parse OCLC token response: {}", e))
+        })?;
+        let lifetime = granted.expires_in.saturating_sub(TOKEN_EXPIRY_MARGIN_SECS);
+        let expires = Instant::now() + Duration::from_secs(lifetime);
+        *token = Some((granted.access_token.clone(), expires));
+        Ok(granted.access_token)
+    }
+}
+
+#[derive(Debug, Deserialize)]
+struct TokenResponse {
+    access_token: String,
+    expires_in: u64,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct SearchResponse {
+    #[serde(default)]
+    bib_records: Vec<BibRecord>,
+}
+
+#[derive(Debug, Deserialize)]
+struct BibRecord {
+    identifier: Option<Identifier>,
+    title: Option<TitleInfo>,
+    contributor: Option<Contributor>,
+    date: Option<DateInfo>,
+    #[serde(default)]
+    publishers: Vec<Publisher>,
+    format: Option<Format>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct Identifier {
+    oclc_number: Option<String>,
+    #[serde(default)]
+    isbns: Vec<String>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct TitleInfo {
+    #[serde(default)]
+    main_titles: Vec<Text>,
+}
+
+#[derive(Debug, Deserialize)]
+struct Text {
+    text: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+struct Contributor {
+    #[serde(default)]
+    creators: Vec<Creator>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct Creator {
+    first_name: Option<Text>,
+    second_name: Option<Text>,
+    non_person_name: Option<Text>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct DateInfo {
+    publication_date: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct Publisher {
+    publisher_name: Option<Text>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct Format {
+    general_format: Option<String>,
+}
+
+impl BibRecord {
+    fn to_entry(&self) -> Entry {
+        let entry_type = match self
+            .format
+            .as_ref()
+            .and_then(|f| f.general_format.as_deref())
+        {
+            Some("Thesis") => "thesis",
+            Some("Book") | Some("eBook") => "book",
+            _ => "misc",
+        };
+        let oclc = self.identifier.as_ref().and_then(|i| i.oclc_number.clone());
+        let mut entry = Entry::new(oclc.clone().unwrap_or_default(), entry_type.to_string());
+
+        // Catalog titles carry the statement of responsibility after a slash
+        entry.title = self
+            .title
+            .iter()
+            .flat_map(|t| &t.main_titles)
+            .find_map(|t| t.text.as_deref())
+            .map(|t| Arc::from(t.split(" / ").next().unwrap_or(t).trim()));
+        entry.authors = self
+            .contributor
+            .iter()
+            .flat_map(|c| &c.creators)
+            .filter_map(|c| {
+                let text = |t: &Option<Text>| t.as_ref().and_then(|t| t.text.clone());
+                match (text(&c.first_name), text(&c.second_name)) {
+                    (Some(first), Some(second)) => Some(format!("{} {}", first, second)),
+                    (None, Some(second)) => Some(second),
+                    _ => text(&c.non_person_name),
+                }
+            })
+            .map(Arc::from)
+            .collect();
+        entry.year = self
+            .date
+            .as_ref()
+            .and_then(|d| d.publication_date.as_deref())
+            .and_then(|d| {
+                let digits: String = d.chars().filter(char::is_ascii_digit).take(4).collect();
+                digits.parse().ok()
+            });
+        entry.venue = self
+            .publishers
+            .iter()
+            .find_map(|p| p.publisher_name.as_ref()?.text.as_deref())
+            .map(Arc::from);
+
+        if let Some(oclc) = oclc {
+            entry.fields.insert(OCLC_FIELD.to_string(), oclc);
+        }
+        if let Some(isbn) = self.identifier.as_ref().and_then(|i| i.isbns.first()) {
+            entry.fields.insert("isbn".to_string(), isbn.clone());
+        }
+
+        entry
+    }
+}
+
+#[async_trait]
+impl Validator for WorldCatClient {
+    async fn search_by_doi(&self, _doi: &str) -> Result<Option<Entry>, ValidatorError> {
+        // Catalog records are not indexed by DOI
+        Ok(None)
+    }
+
+    async fn search_by_title(&self, title: &str) -> Result<Vec<Entry>, ValidatorError> {
+        self.search(&format!("ti:\"{}\"", title.replace('"', "")), 5)
+            .await
+    }
+
+    fn name(&self) -> &'static str {
+        "WorldCat"
+    }
+}
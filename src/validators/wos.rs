@@ -0,0 +1,224 @@
+use super::{async_trait, Validator, ValidatorError};
+use crate::entry::Entry;
+use crate::lint::normalize_doi;
+use reqwest::Client;
+use serde::Deserialize;
+use std::sync::Arc;
+
+const WOS_API_BASE: &str = "https://api.clarivate.com/apis/wos-starter/v1/documents";
+
+/// Field of a matched entry holding its Web of Science accession number (UT)
+pub const WOS_UT_FIELD: &str = "wos_ut";
+
+/// The accession number of a local entry: a `wos_ut` field, or the `unique-id` field
+/// of Web of Science BibTeX exports
+pub fn local_ut(entry: &Entry) -> Option<&str> {
+    entry
+        .field(WOS_UT_FIELD)
+        .or_else(|| entry.field("unique-id").filter(|id| id.starts_with("WOS:")))
+}
+
+/// Client for the Web of Science Starter API, which needs a Clarivate API key
+pub struct WosClient {
+    client: Client,
+    api_key: String,
+}
+
+impl WosClient {
+    pub fn new(api_key: String) -> Self {
+        let client = Client::builder()
+            .user_agent("bibval/0.1.0 (https://github.com/femtomc/bibval)")
+            .build()
+            .expect("Failed to create HTTP client");
+        Self { client, api_key }
+    }
+
+    /// Look up a record by accession number ("WOS:000480353400001")
+    pub async fn search_by_ut(&self, ut: &str) -> Result<Option<Entry>, ValidatorError> {
+        let ut = ut.trim();
+        let ut = if ut.starts_with("WOS:") {
+            ut.to_string()
+        } else {
+            format!("WOS:{}", ut)
+        };
+        Ok(self
+            .search(&format!("UT={}", ut), 1)
+            .await?
+            .into_iter()
+            .next())
+    }
+
+    async fn search(&self, query: &str, limit: usize) -> Result<Vec<Entry>, ValidatorError> {
+        let url = format!(
+            "{}?db=WOS&q={}&limit={}",
+            WOS_API_BASE,
+            urlencoding::encode(query),
+            limit
+        );
+
+        let response = self
+            .client
+            .get(&url)
+            .header("X-ApiKey", &self.api_key)
+            .send()
+            .await?;
+
+        if response.status() == reqwest::StatusCode::TOO_MANY_REQUESTS {
+            return Err(ValidatorError::RateLimited);
+        }
+
+        if matches!(
+            response.status(),
+            reqwest::StatusCode::UNAUTHORIZED | reqwest::StatusCode::FORBIDDEN
+        ) {
+            return Err(ValidatorError::ParseError(
+                "Web of Science rejected the API key".to_string(),
+            ));
+        }
+
+        if !response.status().is_success() {
+            return Err(ValidatorError::ParseError(format!(
+                "Web of Science returned {}",
+                response.status()
+            )));
+        }
+
+        let response: DocumentsResponse = response.json().await.map_err(|e| {
+            ValidatorError::ParseError(format!("Failed to parse Web of Science response: {}", e))
+        })?;
+
+        Ok(response.hits.iter().map(Document::to_entry).collect())
+    }
+}
+
+#[derive(Debug, Deserialize)]
+struct DocumentsResponse {
+    #[serde(default)]
+    hits: Vec<Document>,
+}
+
+#[derive(Debug, Deserialize)]
+struct Document {
+    uid: String,
+    title: Option<String>,
+    #[serde(default)]
+    types: Vec<String>,
+    source: Option<Source>,
+    names: Option<Names>,
+    identifiers: Option<Identifiers>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct Source {
+    source_title: Option<String>,
+    publish_year: Option<i32>,
+    volume: Option<String>,
+    pages: Option<Pages>,
+}
+
+#[derive(Debug, Deserialize)]
+struct Pages {
+    range: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+struct Names {
+    #[serde(default)]
+    authors: Vec<Author>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct Author {
+    display_name: String,
+}
+
+#[derive(Debug, Deserialize)]
+struct Identifiers {
+    doi: Option<String>,
+}
+
+impl Document {
+    fn to_entry(&self) -> Entry {
+        let entry_type = if self.types.iter().any(|t| t == "Proceedings Paper") {
+            "inproceedings"
+        } else {
+            "article"
+        };
+        let mut entry = Entry::new(self.uid.clone(), entry_type.to_string());
+
+        entry.title = self.title.as_deref().map(Arc::from);
+        // Names are written "Family, Given"
+        entry.authors = self
+            .names
+            .iter()
+            .flat_map(|n| &n.authors)
+            .map(|a| match a.display_name.split_once(", ") {
+                Some((family, given)) => Arc::from(format!("{} {}", given, family)),
+                None => Arc::from(a.display_name.as_str()),
+            })
+            .collect();
+        if let Some(source) = &self.source {
+            entry.year = source.publish_year;
+            entry.venue = source.source_title.as_deref().map(Arc::from);
+            let pages = source.pages.as_ref().and_then(|p| p.range.clone());
+            for (name, value) in [("pages", pages), ("volume", source.volume.clone())] {
+                if let Some(value) = value.filter(|v| !v.is_empty()) {
+                    entry.fields.insert(name.to_string(), value);
+                }
+            }
+        }
+        entry.doi = self.identifiers.as_ref().and_then(|i| i.doi.clone());
+        entry
+            .fields
+            .insert(WOS_UT_FIELD.to_string(), self.uid.clone());
+
+        entry
+    }
+}
+
+#[async_trait]
+impl Validator for WosClient {
+    async fn search_by_doi(&self, doi: &str) -> Result<Option<Entry>, ValidatorError> {
+        let query = format!("DO=\"{}\"", normalize_doi(doi));
+        Ok(self.search(&query, 1).await?.into_iter().next())
+    }
+
+    async fn search_by_title(&self, title: &str) -> Result<Vec<Entry>, ValidatorError> {
+        let title = title.replace(['"', '(', ')'], " ");
+        self.search(&format!("TI=(\"{}\")", title.trim()), 5).await
+    }
+
+    fn name(&self) -> &'static str {
+        "Web of Science"
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn documents_carry_their_accession_number() {
+        let json = r#"{"metadata": {"total": 1}, "hits": [{
+            "uid": "WOS:000480353400001",
+            "title": "A Paper",
+            "types": ["Article"],
+            "source": {
+                "sourceTitle": "NATURE",
+                "publishYear": 2019,
+                "volume": "572",
+                "pages": {"range": "101-114", "begin": "101", "end": "114", "count": 14}
+            },
+            "names": {"authors": [{"displayName": "Doe, Jane", "wosStandard": "Doe, J"}]},
+            "identifiers": {"doi": "10.1038/s41586-019-1234-5"}
+        }]}"#;
+        let response: DocumentsResponse = serde_json::from_str(json).unwrap();
+        let entry = response.hits[0].to_entry();
+        assert_eq!(entry.field(WOS_UT_FIELD), Some("WOS:000480353400001"));
+        assert_eq!(entry.authors, vec![Arc::from("Jane Doe")]);
+        assert_eq!(entry.field("pages"), Some("101-114"));
+        assert_eq!(entry.doi.as_deref(), Some("10.1038/s41586-019-1234-5"));
+    }
+}
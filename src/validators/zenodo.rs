@@ -1,10 +1,16 @@
+use super::datacite::{CONCEPT_DOI_FIELD, LICENSE_FIELD, VERSION_FIELD};
 use super::{async_trait, Validator, ValidatorError};
 use crate::entry::Entry;
 use reqwest::Client;
 use serde::Deserialize;
+use std::sync::Arc;
 
 const ZENODO_API_BASE: &str = "https://zenodo.org/api";
 
+/// Field of a concept record holding the versions archived under it, one per line and
+/// newest first
+pub const ZENODO_VERSIONS_FIELD: &str = "zenodo_versions";
+
 pub struct ZenodoClient {
     client: Client,
 }
@@ -17,6 +23,82 @@ impl ZenodoClient {
             .expect("Failed to create HTTP client");
         Self { client }
     }
+
+    /// Find the record Zenodo's GitHub integration archived for a release, which links
+    /// back to the tag it was made from
+    pub async fn search_by_release(
+        &self,
+        owner: &str,
+        repository: &str,
+        tag: &str,
+    ) -> Result<Option<Entry>, ValidatorError> {
+        let release = format!("https://github.com/{}/{}/tree/{}", owner, repository, tag);
+        let query = format!("related.identifier:\"{}\"", release);
+        let url = format!(
+            "{}/records?q={}&size=1",
+            ZENODO_API_BASE,
+            urlencoding::encode(&query)
+        );
+
+        let response = self.client.get(&url).send().await?;
+
+        if response.status() == reqwest::StatusCode::TOO_MANY_REQUESTS {
+            return Err(ValidatorError::RateLimited);
+        }
+
+        if !response.status().is_success() {
+            return Ok(None);
+        }
+
+        let search_response: SearchResponse = response.json().await.map_err(|e| {
+            ValidatorError::ParseError(format!("Failed to parse Zenodo response: {}", e))
+        })?;
+
+        Ok(search_response.hits.hits.first().map(|r| r.to_entry()))
+    }
+
+    /// The versions archived under a concept DOI, as a record keyed by the concept DOI
+    /// (`None` when Zenodo has no records under it)
+    pub async fn search_versions(
+        &self,
+        concept_doi: &str,
+    ) -> Result<Option<Entry>, ValidatorError> {
+        let query = format!("conceptdoi:\"{}\"", concept_doi);
+        let url = format!(
+            "{}/records?q={}&all_versions=true&sort=mostrecent&size=100",
+            ZENODO_API_BASE,
+            urlencoding::encode(&query)
+        );
+
+        let response = self.client.get(&url).send().await?;
+
+        if response.status() == reqwest::StatusCode::TOO_MANY_REQUESTS {
+            return Err(ValidatorError::RateLimited);
+        }
+
+        if !response.status().is_success() {
+            return Ok(None);
+        }
+
+        let search_response: SearchResponse = response.json().await.map_err(|e| {
+            ValidatorError::ParseError(format!("Failed to parse Zenodo response: {}", e))
+        })?;
+
+        let versions: Vec<&str> = search_response
+            .hits
+            .hits
+            .iter()
+            .filter_map(|r| r.metadata.version.as_deref())
+            .collect();
+        if versions.is_empty() {
+            return Ok(None);
+        }
+        let mut record = Entry::new(concept_doi.to_string(), "software".to_string());
+        record
+            .fields
+            .insert(ZENODO_VERSIONS_FIELD.to_string(), versions.join("\n"));
+        Ok(Some(record))
+    }
 }
 
 impl Default for ZenodoClient {
@@ -38,6 +120,7 @@ struct Hits {
 #[derive(Debug, Deserialize)]
 struct Record {
     id: Option<u64>,
+    conceptdoi: Option<String>,
     metadata: Metadata,
 }
 
@@ -48,6 +131,13 @@ struct Metadata {
     publication_date: Option<String>,
     doi: Option<String>,
     resource_type: Option<ResourceType>,
+    version: Option<String>,
+    license: Option<License>,
+}
+
+#[derive(Debug, Deserialize)]
+struct License {
+    id: Option<String>,
 }
 
 #[derive(Debug, Deserialize)]
@@ -81,7 +171,7 @@ impl Record {
             entry_type.to_string(),
         );
 
-        entry.title = self.metadata.title.clone();
+        entry.title = self.metadata.title.as_deref().map(Arc::from);
 
         // Extract year from publication_date (format: "YYYY-MM-DD" or "YYYY")
         if let Some(date) = &self.metadata.publication_date {
@@ -94,12 +184,28 @@ impl Record {
         if let Some(creators) = &self.metadata.creators {
             entry.authors = creators
                 .iter()
-                .filter_map(|c| c.name.clone())
+                .filter_map(|c| c.name.as_deref().map(Arc::from))
                 .collect();
         }
 
         entry.doi = self.metadata.doi.clone();
 
+        if let Some(version) = &self.metadata.version {
+            entry
+                .fields
+                .insert(VERSION_FIELD.to_string(), version.clone());
+        }
+        if let Some(license) = self.metadata.license.as_ref().and_then(|l| l.id.as_ref()) {
+            entry
+                .fields
+                .insert(LICENSE_FIELD.to_string(), license.clone());
+        }
+        if let Some(concept) = &self.conceptdoi {
+            entry
+                .fields
+                .insert(CONCEPT_DOI_FIELD.to_string(), concept.clone());
+        }
+
         entry
     }
 }
@@ -0,0 +1,409 @@
+use crate::entry::normalize_string;
+
+/// A bundled record describing a well-known publication venue
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Venue {
+    /// Canonical full name
+    pub name: &'static str,
+    /// Common short name
+    pub abbreviation: &'static str,
+    /// Other spellings seen in bibliographies
+    pub aliases: &'static [&'static str],
+    /// ISO-4 abbreviation, for journals
+    pub iso4: Option<&'static str>,
+    /// Year of edition one under current annual numbering, for venues cited as "34th ..."
+    pub first_year: Option<i32>,
+}
+
+/// Bundled venue database
+pub static VENUES: &[Venue] = &[
+    Venue {
+        name: "Advances in Neural Information Processing Systems",
+        abbreviation: "NeurIPS",
+        aliases: &[
+            "NIPS",
+            "Conference on Neural Information Processing Systems",
+        ],
+        iso4: None,
+        first_year: Some(1987),
+    },
+    Venue {
+        name: "International Conference on Machine Learning",
+        abbreviation: "ICML",
+        aliases: &[],
+        iso4: None,
+        first_year: Some(1984),
+    },
+    Venue {
+        name: "International Conference on Learning Representations",
+        abbreviation: "ICLR",
+        aliases: &[],
+        iso4: None,
+        first_year: Some(2013),
+    },
+    Venue {
+        name: "AAAI Conference on Artificial Intelligence",
+        abbreviation: "AAAI",
+        aliases: &[],
+        iso4: None,
+        first_year: Some(1987),
+    },
+    Venue {
+        name: "International Joint Conference on Artificial Intelligence",
+        abbreviation: "IJCAI",
+        aliases: &[],
+        iso4: None,
+        first_year: None,
+    },
+    Venue {
+        name: "Annual Meeting of the Association for Computational Linguistics",
+        abbreviation: "ACL",
+        aliases: &[],
+        iso4: None,
+        first_year: Some(1963),
+    },
+    Venue {
+        name: "Conference on Uncertainty in Artificial Intelligence",
+        abbreviation: "UAI",
+        aliases: &[],
+        iso4: None,
+        first_year: Some(1985),
+    },
+    Venue {
+        name: "International Conference on Artificial Intelligence and Statistics",
+        abbreviation: "AISTATS",
+        aliases: &[],
+        iso4: None,
+        first_year: Some(1998),
+    },
+    Venue {
+        name: "Conference on Learning Theory",
+        abbreviation: "COLT",
+        aliases: &[],
+        iso4: None,
+        first_year: Some(1988),
+    },
+    Venue {
+        name: "ACM SIGKDD Conference on Knowledge Discovery and Data Mining",
+        abbreviation: "KDD",
+        aliases: &["SIGKDD"],
+        iso4: None,
+        first_year: Some(1995),
+    },
+    Venue {
+        name: "ACM SIGPLAN Conference on Programming Language Design and Implementation",
+        abbreviation: "PLDI",
+        aliases: &[],
+        iso4: None,
+        first_year: Some(1980),
+    },
+    Venue {
+        name: "ACM SIGPLAN Symposium on Principles of Programming Languages",
+        abbreviation: "POPL",
+        aliases: &[],
+        iso4: None,
+        first_year: Some(1974),
+    },
+    Venue {
+        name: "International Conference on Software Engineering",
+        abbreviation: "ICSE",
+        aliases: &[],
+        iso4: None,
+        first_year: Some(1979),
+    },
+    Venue {
+        name: "ACM Symposium on Theory of Computing",
+        abbreviation: "STOC",
+        aliases: &[],
+        iso4: None,
+        first_year: Some(1969),
+    },
+    Venue {
+        name: "ACM-SIAM Symposium on Discrete Algorithms",
+        abbreviation: "SODA",
+        aliases: &[],
+        iso4: None,
+        first_year: Some(1990),
+    },
+    Venue {
+        name: "IEEE/CVF Conference on Computer Vision and Pattern Recognition",
+        abbreviation: "CVPR",
+        aliases: &["IEEE Conference on Computer Vision and Pattern Recognition"],
+        iso4: None,
+        first_year: None,
+    },
+    Venue {
+        name: "Conference on Empirical Methods in Natural Language Processing",
+        abbreviation: "EMNLP",
+        aliases: &[],
+        iso4: None,
+        first_year: None,
+    },
+    Venue {
+        name: "Annual Conference of the North American Chapter of the Association for Computational Linguistics",
+        abbreviation: "NAACL",
+        aliases: &["NAACL-HLT"],
+        iso4: None,
+        first_year: None,
+    },
+    Venue {
+        name: "IEEE/CVF International Conference on Computer Vision",
+        abbreviation: "ICCV",
+        aliases: &["IEEE International Conference on Computer Vision"],
+        iso4: None,
+        first_year: None,
+    },
+    Venue {
+        name: "European Conference on Computer Vision",
+        abbreviation: "ECCV",
+        aliases: &[],
+        iso4: None,
+        first_year: None,
+    },
+    Venue {
+        name: "ACM SIGPLAN International Conference on Functional Programming",
+        abbreviation: "ICFP",
+        aliases: &[],
+        iso4: None,
+        first_year: Some(1996),
+    },
+    Venue {
+        name: "ACM Symposium on Operating Systems Principles",
+        abbreviation: "SOSP",
+        aliases: &[],
+        iso4: None,
+        first_year: None,
+    },
+    Venue {
+        name: "USENIX Symposium on Operating Systems Design and Implementation",
+        abbreviation: "OSDI",
+        aliases: &[],
+        iso4: None,
+        first_year: None,
+    },
+    Venue {
+        name: "Journal of Machine Learning Research",
+        abbreviation: "JMLR",
+        aliases: &[],
+        iso4: Some("J. Mach. Learn. Res."),
+        first_year: None,
+    },
+    Venue {
+        name: "Transactions on Machine Learning Research",
+        abbreviation: "TMLR",
+        aliases: &[],
+        iso4: Some("Trans. Mach. Learn. Res."),
+        first_year: None,
+    },
+    Venue {
+        name: "Machine Learning",
+        abbreviation: "MLJ",
+        aliases: &[],
+        iso4: Some("Mach. Learn."),
+        first_year: None,
+    },
+    Venue {
+        name: "Neural Computation",
+        abbreviation: "NECO",
+        aliases: &[],
+        iso4: Some("Neural Comput."),
+        first_year: None,
+    },
+    Venue {
+        name: "Neural Networks",
+        abbreviation: "NN",
+        aliases: &[],
+        iso4: Some("Neural Netw."),
+        first_year: None,
+    },
+    Venue {
+        name: "Artificial Intelligence",
+        abbreviation: "AIJ",
+        aliases: &[],
+        iso4: Some("Artif. Intell."),
+        first_year: None,
+    },
+    Venue {
+        name: "IEEE Transactions on Pattern Analysis and Machine Intelligence",
+        abbreviation: "TPAMI",
+        aliases: &["PAMI"],
+        iso4: Some("IEEE Trans. Pattern Anal. Mach. Intell."),
+        first_year: None,
+    },
+    Venue {
+        name: "Communications of the ACM",
+        abbreviation: "CACM",
+        aliases: &[],
+        iso4: Some("Commun. ACM"),
+        first_year: None,
+    },
+    Venue {
+        name: "Journal of the ACM",
+        abbreviation: "JACM",
+        aliases: &[],
+        iso4: Some("J. ACM"),
+        first_year: None,
+    },
+    Venue {
+        name: "ACM Transactions on Programming Languages and Systems",
+        abbreviation: "TOPLAS",
+        aliases: &[],
+        iso4: Some("ACM Trans. Program. Lang. Syst."),
+        first_year: None,
+    },
+    Venue {
+        name: "Journal of Statistical Software",
+        abbreviation: "JSS",
+        aliases: &[],
+        iso4: Some("J. Stat. Softw."),
+        first_year: None,
+    },
+    Venue {
+        name: "Journal of the American Statistical Association",
+        abbreviation: "JASA",
+        aliases: &[],
+        iso4: Some("J. Am. Stat. Assoc."),
+        first_year: None,
+    },
+    Venue {
+        name: "Proceedings of the National Academy of Sciences of the United States of America",
+        abbreviation: "PNAS",
+        aliases: &["Proceedings of the National Academy of Sciences"],
+        iso4: Some("Proc. Natl. Acad. Sci. U.S.A."),
+        first_year: None,
+    },
+    Venue {
+        name: "Physical Review Letters",
+        abbreviation: "PRL",
+        aliases: &[],
+        iso4: Some("Phys. Rev. Lett."),
+        first_year: None,
+    },
+    Venue {
+        name: "Nature",
+        abbreviation: "Nature",
+        aliases: &[],
+        iso4: Some("Nature"),
+        first_year: None,
+    },
+    Venue {
+        name: "Science",
+        abbreviation: "Science",
+        aliases: &[],
+        iso4: Some("Science"),
+        first_year: None,
+    },
+];
+
+/// How a venue string refers to a bundled venue
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum VenueForm {
+    /// The canonical full name
+    Full,
+    /// The common short name
+    Abbreviation,
+    /// The ISO-4 abbreviation
+    Iso4,
+    /// One of the other known spellings
+    Alias,
+}
+
+impl std::fmt::Display for VenueForm {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            VenueForm::Full => write!(f, "full name"),
+            VenueForm::Abbreviation => write!(f, "short name"),
+            VenueForm::Iso4 => write!(f, "ISO-4 abbreviation"),
+            VenueForm::Alias => write!(f, "alternative name"),
+        }
+    }
+}
+
+impl Venue {
+    /// Whether this venue is a journal (journals carry an ISO-4 abbreviation)
+    pub fn is_journal(&self) -> bool {
+        self.iso4.is_some()
+    }
+
+    /// The spelling of this venue in the given form
+    pub fn spelling(&self, form: VenueForm) -> &'static str {
+        match form {
+            VenueForm::Full | VenueForm::Alias => self.name,
+            VenueForm::Abbreviation => self.abbreviation,
+            VenueForm::Iso4 => self.iso4.unwrap_or(self.abbreviation),
+        }
+    }
+}
+
+/// Identify a venue string that is exactly one of a bundled venue's spellings
+pub fn classify_venue(text: &str) -> Option<(&'static Venue, VenueForm)> {
+    let normalized = normalize_string(text);
+
+    // Check full names first so that e.g. "Nature" is a full name, not an ISO-4 form
+    VENUES.iter().find_map(|venue| {
+        let forms = [
+            (Some(venue.name), VenueForm::Full),
+            (venue.iso4, VenueForm::Iso4),
+            (Some(venue.abbreviation), VenueForm::Abbreviation),
+        ];
+        forms
+            .into_iter()
+            .filter_map(|(spelling, form)| spelling.map(|s| (s, form)))
+            .chain(venue.aliases.iter().map(|a| (*a, VenueForm::Alias)))
+            .find(|(spelling, _)| normalize_string(spelling) == normalized)
+            .map(|(_, form)| (venue, form))
+    })
+}
+
+/// Find the venue a free-form venue string refers to, if it is in the database
+pub fn find_venue(text: &str) -> Option<&'static Venue> {
+    if let Some((venue, _)) = classify_venue(text) {
+        return Some(venue);
+    }
+
+    let normalized = normalize_string(text);
+    let words: Vec<_> = normalized.split_whitespace().collect();
+
+    // Journal names like "Science" are too generic to match as substrings
+    VENUES
+        .iter()
+        .filter(|venue| !venue.is_journal())
+        .find(|venue| {
+            std::iter::once(venue.name)
+                .chain(venue.aliases.iter().copied())
+                .any(|name| normalized.contains(&normalize_string(name)))
+                || std::iter::once(venue.abbreviation)
+                    .chain(venue.aliases.iter().copied())
+                    .map(normalize_string)
+                    .any(|abbr| !abbr.contains(' ') && words.contains(&abbr.as_str()))
+        })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn finds_venues_by_name_or_abbreviation() {
+        assert_eq!(find_venue("NeurIPS 2020").unwrap().abbreviation, "NeurIPS");
+        assert_eq!(
+            find_venue("Proceedings of the 37th International Conference on Machine Learning")
+                .unwrap()
+                .abbreviation,
+            "ICML"
+        );
+        assert!(find_venue("Journal of Obscure Results").is_none());
+    }
+
+    #[test]
+    fn classifies_journal_spellings() {
+        let (venue, form) = classify_venue("J. Mach. Learn. Res.").unwrap();
+        assert_eq!(venue.abbreviation, "JMLR");
+        assert_eq!(form, VenueForm::Iso4);
+
+        let (_, form) = classify_venue("Journal of Machine Learning Research").unwrap();
+        assert_eq!(form, VenueForm::Full);
+
+        assert!(classify_venue("Proceedings of JMLR Workshops").is_none());
+    }
+}
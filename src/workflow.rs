@@ -0,0 +1,153 @@
+//! GitHub Actions workflow commands, which show findings inline on pull request diffs
+
+use crate::entry::{Discrepancy, Severity};
+use crate::report::{EntryStatus, Report};
+use std::path::Path;
+
+/// One workflow command per finding: errors as `::error`, warnings as `::warning`, and
+/// informational findings, unmatched entries and failed lookups as `::notice`. Entries
+/// parsed from a file are annotated at the line they start on.
+pub fn annotations(report: &Report) -> Vec<String> {
+    let mut commands = Vec::new();
+    for entry_report in &report.entries {
+        let key = &entry_report.entry.key;
+        let location = entry_report
+            .entry
+            .location
+            .as_ref()
+            .and_then(|l| Some((l.file.as_deref()?, l.line)));
+
+        let findings = entry_report
+            .lints
+            .iter()
+            .map(|lint| (lint, "offline check".to_string()))
+            .chain(entry_report.validation_results.iter().flat_map(|result| {
+                let source = result.source.to_string();
+                result
+                    .discrepancies
+                    .iter()
+                    .map(move |d| (d, source.clone()))
+            }));
+        for (discrepancy, source) in findings {
+            let title = format!("{}: {} (via {})", key, discrepancy.field, source);
+            commands.push(command(
+                discrepancy.severity,
+                location,
+                &title,
+                &finding_message(discrepancy),
+            ));
+        }
+
+        match &entry_report.status {
+            EntryStatus::NotFound => commands.push(command(
+                Severity::Info,
+                location,
+                key,
+                "Not found in any database",
+            )),
+            EntryStatus::Failed(reason) => commands.push(command(
+                Severity::Info,
+                location,
+                key,
+                &format!("Validation failed: {}", reason),
+            )),
+            _ => {}
+        }
+    }
+    commands
+}
+
+fn finding_message(discrepancy: &Discrepancy) -> String {
+    if discrepancy.severity >= Severity::Warning
+        && !(discrepancy.local_value.is_empty() && discrepancy.remote_value.is_empty())
+    {
+        format!(
+            "{}\nLocal:  {}\nRemote: {}",
+            discrepancy.message, discrepancy.local_value, discrepancy.remote_value
+        )
+    } else {
+        discrepancy.message.clone()
+    }
+}
+
+fn command(
+    severity: Severity,
+    location: Option<(&Path, usize)>,
+    title: &str,
+    message: &str,
+) -> String {
+    let name = match severity {
+        Severity::Error => "error",
+        Severity::Warning => "warning",
+        Severity::Info => "notice",
+    };
+    let mut properties = Vec::new();
+    if let Some((file, line)) = location {
+        properties.push(format!(
+            "file={}",
+            escape_property(&file.display().to_string())
+        ));
+        properties.push(format!("line={}", line));
+    }
+    properties.push(format!("title={}", escape_property(title)));
+    format!(
+        "::{} {}::{}",
+        name,
+        properties.join(","),
+        escape_data(message)
+    )
+}
+
+/// Escape a command's message, which runs to the end of the line
+fn escape_data(s: &str) -> String {
+    s.replace('%', "%25")
+        .replace('\r', "%0D")
+        .replace('\n', "%0A")
+}
+
+/// Escape a property value, which also mustn't contain the separators
+fn escape_property(s: &str) -> String {
+    escape_data(s).replace(':', "%3A").replace(',', "%2C")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::entry::{DiscrepancyField, Entry, SourceLocation};
+    use crate::report::EntryReport;
+
+    #[test]
+    fn findings_become_commands_at_their_entry() {
+        let mut entry = Entry::new("smith2020".to_string(), "article".to_string());
+        entry.location = Some(SourceLocation {
+            file: Some(Path::new("refs.bib").into()),
+            line: 3,
+        });
+
+        let mut report = Report::new();
+        report.add(EntryReport {
+            entry,
+            status: EntryStatus::Error,
+            validation_results: Vec::new(),
+            lints: vec![Discrepancy {
+                field: DiscrepancyField::Year,
+                severity: Severity::Error,
+                local_value: "2020".to_string(),
+                remote_value: "2019, 2021".to_string(),
+                message: "Year is 100% wrong".to_string(),
+            }],
+            replacement: None,
+            citations: None,
+            open_access: None,
+            code: None,
+            trace: Vec::new(),
+        });
+        assert_eq!(
+            annotations(&report),
+            vec![
+                "::error file=refs.bib,line=3,title=smith2020%3A Year (via offline check)::\
+                 Year is 100%25 wrong%0ALocal:  2020%0ARemote: 2019, 2021"
+            ]
+        );
+    }
+}
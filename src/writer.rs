@@ -0,0 +1,95 @@
+//! Writing entries back out as BibTeX
+
+use crate::entry::Entry;
+use std::fmt::Write;
+
+/// Fields written first, in this order; any others follow alphabetically
+const FIELD_ORDER: &[&str] = &[
+    "author",
+    "editor",
+    "title",
+    "journal",
+    "booktitle",
+    "year",
+    "volume",
+    "number",
+    "pages",
+    "publisher",
+    "doi",
+    "eprint",
+    "url",
+];
+
+/// Format one entry as BibTeX, with field values in braces
+pub fn format_entry(entry: &Entry) -> String {
+    let mut fields: Vec<(&str, &str)> = entry
+        .fields
+        .iter()
+        .map(|(name, value)| (name.as_str(), value.as_str()))
+        .filter(|(_, value)| !value.trim().is_empty())
+        .collect();
+    fields.sort_by_key(|(name, _)| {
+        let rank = FIELD_ORDER.iter().position(|f| f == name);
+        (rank.unwrap_or(FIELD_ORDER.len()), *name)
+    });
+
+    let width = fields.iter().map(|(name, _)| name.len()).max().unwrap_or(0);
+    let mut out = format!("@{}{{{},\n", entry.entry_type, entry.key);
+    for (name, value) in fields {
+        let _ = writeln!(
+            out,
+            "  {:width$} = {{{}}},",
+            name,
+            value.trim(),
+            width = width
+        );
+    }
+    out.push_str("}\n");
+    out
+}
+
+/// Format entries as a BibTeX file, separated by blank lines
+pub fn format_bibliography(entries: &[Entry]) -> String {
+    entries
+        .iter()
+        .map(format_entry)
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parser::parse_bib_string;
+
+    #[test]
+    fn formatted_entries_round_trip() {
+        let mut entry = Entry::new("he2016deep".to_string(), "inproceedings".to_string());
+        for (name, value) in [
+            ("year", "2016"),
+            ("title", "Deep Residual Learning"),
+            ("note", "Oral"),
+            ("author", "He, Kaiming and Sun, Jian"),
+            ("booktitle", "CVPR"),
+        ] {
+            entry.fields.insert(name.to_string(), value.to_string());
+        }
+
+        let bibtex = format_entry(&entry);
+        assert_eq!(
+            bibtex,
+            "@inproceedings{he2016deep,
+  author    = {He, Kaiming and Sun, Jian},
+  title     = {Deep Residual Learning},
+  booktitle = {CVPR},
+  year      = {2016},
+  note      = {Oral},
+}
+"
+        );
+
+        let parsed = parse_bib_string(&bibtex).unwrap();
+        assert_eq!(parsed[0].fields, entry.fields);
+        assert_eq!(parsed[0].year, Some(2016));
+    }
+}